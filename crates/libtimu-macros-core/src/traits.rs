@@ -37,6 +37,23 @@ impl LabeledSpan {
     }
 }
 
+/// A mechanical edit that fixes an error
+///
+/// The edit replaces a range of a source file with a new text, an empty range
+/// inserts the text and an empty replacement removes the range. Suggestions
+/// are shown with the error and applied by `timuc fix`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Suggestion {
+    /// What the edit does, shown to the user
+    pub message: String,
+    /// The source file the edit applies to
+    pub code: crate::SourceCode,
+    /// The source range replaced by the edit
+    pub range: Range<usize>,
+    /// The text the range is replaced with
+    pub replacement: String,
+}
+
 /// Core trait for Timu error types that provides rich diagnostic information
 /// 
 /// This trait enables errors to provide detailed information including:
@@ -44,6 +61,7 @@ impl LabeledSpan {
 /// - Nested errors and references to other errors
 /// - Help text and error codes
 /// - Source code context
+/// - Suggested edits that fix the error
 /// 
 /// The trait is designed to be implemented via the `TimuError` derive macro.
 pub trait TimuErrorTrait: Display {
@@ -64,4 +82,19 @@ pub trait TimuErrorTrait: Display {
     
     /// Returns optional help text to assist in resolving this error
     fn help(&self) -> Option<Box<dyn Display>> { None }
+
+    /// Returns the edits that fix this error, if the fix is mechanical
+    fn suggestions(&self) -> Option<Vec<Suggestion>> { None }
+}
+
+/// Diagnostic information known without an instance of the error
+///
+/// Implemented by the `TimuError` derive macro for error structs, it lets
+/// reports describe an error kind before any error of that kind is found.
+pub trait StaticDiagnostic {
+    /// Code set with `#[diagnostic(code(..))]`
+    const CODE: Option<&'static str>;
+
+    /// Help set with `#[diagnostic(help(..))]`, `None` when the help depends on the error
+    const HELP: Option<&'static str>;
 }
@@ -15,6 +15,7 @@
 //! - `#[help]` - Marks a field as containing help text for the error
 //! - `#[errors]` - Marks a field containing nested errors
 //! - `#[reference]` - Marks a field containing error references
+//! - `#[suggestions]` - Marks a `Vec<Suggestion>` field with the edits that fix the error
 //!
 //! ## Type Attributes  
 //! - `#[diagnostic(code = "E001")]` - Sets an error code for the error type
@@ -43,6 +44,10 @@
 //! - `errors()` - Returns nested errors for chaining
 //! - `references()` - Returns error references
 //! - `error_code()` - Returns error code if specified
+//! - `suggestions()` - Returns the suggested edits if available
+//!
+//! Structs also get a `StaticDiagnostic` implementation with the code and the
+//! help of the `#[diagnostic(...)]` attribute.
 
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
@@ -262,6 +267,28 @@ fn get_errors(fields: &mut FieldsNamed) -> Option<proc_macro2::TokenStream> {
     None
 }
 
+/// Finds the field marked with `#[suggestions]` attribute and generates suggestion code
+/// 
+/// The field holds the edits that fix the error, the generated code clones
+/// them for the trait return type.
+/// 
+/// # Arguments
+/// * `fields` - The named fields of the struct being processed
+/// 
+/// # Returns
+/// * `Some(TokenStream)` - Code to access the suggestions field as `Some(field.clone())`
+/// * `None` - If no suggestions field exists
+fn get_suggestions(fields: &mut FieldsNamed) -> Option<(Field, proc_macro2::TokenStream)> {
+    for field in fields.named.iter_mut() {
+        if field.attrs.iter().any(|attr| attr.path().is_ident("suggestions")) {
+            let name = &field.ident;
+            return Some((field.clone(), quote! { Some(#name.clone()) }));
+        }
+    }
+
+    None
+}
+
 /// Generates `TimuErrorTrait` implementation for struct types
 /// 
 /// This function processes a struct definition marked with `#[derive(TimuError)]`
@@ -275,6 +302,9 @@ fn get_errors(fields: &mut FieldsNamed) -> Option<proc_macro2::TokenStream> {
 /// - `error_code()` - Returns error code from diagnostic or None
 /// - `help()` - Returns help text from diagnostic or field
 /// - `references()` - Returns related error references
+/// - `suggestions()` - Returns the suggested edits
+///
+/// It also implements `StaticDiagnostic` with the code and the static help.
 /// 
 /// # Arguments
 /// * `name` - The struct identifier
@@ -295,8 +325,13 @@ fn build_struct(name: Ident, diagnostic: Diagnostic, mut data: DataStruct) -> To
             None => quote!( None ),
         };
 
-        let error_code = match diagnostic.code {
-            Some(code) => quote!( Some(Box::new(#code.to_string())) ),
+        let (error_code, static_code) = match diagnostic.code {
+            Some(code) => (quote!( Some(Box::new(#code.to_string())) ), quote!( Some(#code) )),
+            None => (quote!( None ), quote!( None )),
+        };
+
+        let static_help = match diagnostic.help.as_ref() {
+            Some(help) => quote!( Some(#help) ),
             None => quote!( None ),
         };
 
@@ -308,6 +343,14 @@ fn build_struct(name: Ident, diagnostic: Diagnostic, mut data: DataStruct) -> To
             },
         };
 
+        let suggestions = match get_suggestions(fields) {
+            Some((field, _)) => {
+                let name = &field.ident;
+                quote!( Some(self.#name.clone()) )
+            },
+            None => quote!( None ),
+        };
+
         let labels = get_labels(fields).into_iter().map(|(_, token)| token).collect::<Vec<_>>();
         let reerences = get_references(fields).into_iter().map(|(_, token)| token).collect::<Vec<_>>();
 
@@ -319,6 +362,12 @@ fn build_struct(name: Ident, diagnostic: Diagnostic, mut data: DataStruct) -> To
                 fn source_code(&self) -> Option<Box<libtimu_macros_core::SourceCode>> { #source_code }
                 fn error_code(&self) -> Option<Box<dyn std::fmt::Display>> { #error_code }
                 fn help(&self) -> Option<Box<dyn std::fmt::Display>> { #help }
+                fn suggestions(&self) -> Option<Vec<libtimu_macros_core::traits::Suggestion>> { #suggestions }
+            }
+
+            impl libtimu_macros_core::traits::StaticDiagnostic for #name {
+                const CODE: Option<&'static str> = #static_code;
+                const HELP: Option<&'static str> = #static_help;
             }
         });
     }
@@ -464,6 +513,28 @@ fn generate_enum_references(enum_name: &Ident, enum_field_ident: &Ident, fields:
     }
 }
 
+/// Generates suggestion extraction logic for enum variants
+/// 
+/// Creates match arm code for extracting the suggested edits from the field
+/// marked with `#[suggestions]` in specific enum variants.
+/// 
+/// # Arguments
+/// * `enum_name` - The enum type identifier
+/// * `enum_field_ident` - The specific variant identifier
+/// * `fields` - The named fields of the variant
+/// 
+/// # Returns
+/// A token stream containing the match arm for this variant
+fn generate_enum_suggestions(enum_name: &Ident, enum_field_ident: &Ident, fields: &mut FieldsNamed) -> proc_macro2::TokenStream {
+    match get_suggestions(fields) {
+        Some((field, token)) => {
+            let name = &field.ident;
+            quote!( #enum_name::#enum_field_ident { #name, .. } => #token )
+        },
+        None => quote!( #enum_name::#enum_field_ident { .. } => None ),
+    }
+}
+
 /// Generates trait method implementations for enum types
 /// 
 /// This function creates match expressions for enum trait methods by iterating
@@ -501,6 +572,7 @@ fn enum_generator(enum_name: &Ident, function_name: Ident, variants: &mut [Varia
                             "error_code" => generate_enum_error_code(enum_name, &enum_field_ident, &diagnostic),
                             "help" => generate_enum_help(enum_name, &enum_field_ident, &diagnostic),
                             "errors" => generate_enum_errors(enum_name, &enum_field_ident, fields),
+                            "suggestions" => generate_enum_suggestions(enum_name, &enum_field_ident, fields),
                             _ => panic!("Unknown field ({function_name})")
                         };
 
@@ -575,6 +647,7 @@ fn build_enum(name: Ident, data: DataEnum) -> TokenStream {
     let help = enum_generator(&name, format_ident!("help"), &mut (variants.clone()));
     let errors = enum_generator(&name, format_ident!("errors"), &mut (variants.clone()));
     let references = enum_generator(&name, format_ident!("references"), &mut (variants.clone()));
+    let suggestions = enum_generator(&name, format_ident!("suggestions"), &mut (variants.clone()));
 
     TokenStream::from(quote!{
         impl libtimu_macros_core::traits::TimuErrorTrait for #name {
@@ -584,6 +657,7 @@ fn build_enum(name: Ident, data: DataEnum) -> TokenStream {
             fn source_code(&self) -> Option<Box<libtimu_macros_core::SourceCode>> { #source_code }
             fn error_code(&self) -> Option<Box<dyn std::fmt::Display>> { #error_code }
             fn help(&self) -> Option<Box<dyn std::fmt::Display>> { #help }
+            fn suggestions(&self) -> Option<Vec<libtimu_macros_core::traits::Suggestion>> { #suggestions }
         }
     })
 }
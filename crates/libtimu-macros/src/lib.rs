@@ -20,7 +20,8 @@ mod error;
 /// - `diagnostic` - Marks fields with diagnostic messages
 /// - `errors` - Marks fields containing nested errors
 /// - `reference` - Marks fields containing error references
-#[proc_macro_derive(TimuError, attributes(source_code, label, help, diagnostic, errors, reference))]
+/// - `suggestions` - Marks fields containing the edits that fix the error
+#[proc_macro_derive(TimuError, attributes(source_code, label, help, diagnostic, errors, reference, suggestions))]
 pub fn derive_timu_error(input: TokenStream) -> TokenStream {
     timu_error(input)
 }
@@ -35,6 +35,10 @@
 //! - [`FunctionDefinitionAst`]: Standalone function definitions
 //! - [`ExtendDefinitionAst`]: Extensions that add functionality to existing classes
 //! - [`UseAst`]: Import statements for cross-module dependencies
+//! - [`TypeAliasAst`]: Alternative names for existing types
+//! - [`EnumDefinitionAst`]: Enums whose variants can carry values
+//! - [`ModuleVariableAst`]: Module-level constants and global variables
+//! - [`AttributeAst`]: Attributes like `@allow(...)` in front of declarations
 //!
 //! ## Expressions and Operations
 //! - [`ExpressionAst`]: All expression types including operations and calls
@@ -66,7 +70,7 @@
 //! let tir_context = process_ast(vec![ast.into()])?;
 //! ```
 
-use std::{borrow::Cow, rc::Rc};
+use std::{borrow::Cow, ops::Range, rc::Rc};
 use strum_macros::EnumIs;
 use crate::{
     file::SourceFile,
@@ -199,7 +203,7 @@ impl<'base> AsMut<PrimitiveValue<'base>> for PrimitiveValue<'base> {
 ///
 /// In Timu, variables can be declared as either mutable or immutable,
 /// affecting how they can be used throughout their lifetime.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone, Copy)]
 pub enum VariableDefinitionType {
     /// Immutable variable - value cannot be changed after initialization
     Const,
@@ -283,6 +287,7 @@ pub enum ExpressionOperatorType {
 /// A file AST contains:
 /// - Source file metadata (path, content)
 /// - A sequence of top-level statements (classes, functions, interfaces, etc.)
+/// - The attributes written in front of the statements
 ///
 /// # Usage
 ///
@@ -302,6 +307,34 @@ pub struct FileAst<'base> {
     pub file: SourceFile,
     /// All top-level statements in the file
     pub statements: Vec<FileStatementAst<'base>>,
+    /// Attributes of the top-level statements, in source order
+    pub attributes: Vec<AttributeAst<'base>>,
+}
+
+/// Attribute written in front of a top-level statement.
+///
+/// Attributes change how the compiler treats the statement they belong to,
+/// `@allow` turns off the given lints for it.
+///
+/// # Syntax Example
+///
+/// ```timu
+/// @allow(unused_variables, unused_functions)
+/// func draft(): i32 {
+///     var value = 1;
+///     return 0;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AttributeAst<'base> {
+    /// Name of the attribute, such as `allow`
+    pub name: Span<'base>,
+    /// Names given in the parentheses
+    pub arguments: Vec<Span<'base>>,
+    /// Byte range of the attribute itself
+    pub position: Range<usize>,
+    /// Byte range of the statement the attribute belongs to
+    pub target: Range<usize>,
 }
 
 /// Top-level statements that can appear in a Timu source file.
@@ -317,6 +350,9 @@ pub struct FileAst<'base> {
 /// - **Interface**: Interface contracts defining method signatures
 /// - **Extend**: Extensions that add functionality to existing classes
 /// - **Use**: Import statements for cross-module dependencies
+/// - **TypeAlias**: Alternative names for existing types
+/// - **Enum**: Enums whose variants can carry values
+/// - **Variable**: Module-level constants and global variables
 ///
 /// # Design Notes
 ///
@@ -334,13 +370,19 @@ pub enum FileStatementAst<'base> {
     Extend(Rc<ExtendDefinitionAst<'base>>),
     /// Use/import statement
     Use(Rc<UseAst<'base>>),
+    /// Type alias definition statement
+    TypeAlias(Rc<TypeAliasAst<'base>>),
+    /// Enum definition statement
+    Enum(Rc<EnumDefinitionAst<'base>>),
+    /// Module-level constant or global variable
+    Variable(Rc<ModuleVariableAst<'base>>),
 }
 
 /// Import statement for bringing external modules into scope.
 ///
 /// Use statements allow Timu code to reference types and functions from
-/// other modules. They support both direct imports and aliased imports
-/// for namespace management.
+/// other modules. They support direct, aliased, grouped and wildcard imports,
+/// and a `pub use` re-exports the imported items from the current module.
 ///
 /// # Syntax Examples
 ///
@@ -348,18 +390,40 @@ pub enum FileStatementAst<'base> {
 /// use module.SomeClass;           // Direct import
 /// use module.SomeClass as Alias;  // Aliased import  
 /// use module;                     // Module import
+/// use module.{A, B as C, sub.D};  // Grouped import
+/// use module.*;                   // Every public item of the module
+/// pub use module.SomeClass;       // Re-export
 /// ```
 ///
 /// # Fields
 ///
-/// - `import`: The module path being imported
+/// - `is_public`: The `pub` keyword of a re-export
+/// - `import`: The module path being imported, the common prefix for grouped
+///   and wildcard imports
 /// - `alias`: Optional alias name for the imported item
+/// - `kind`: Whether one item, a group of items or every item is imported
 #[derive(Debug)]
 pub struct UseAst<'base> {
+    /// Public modifier, the imported items are re-exported from the module
+    pub is_public: Option<Span<'base>>,
     /// Optional alias for the imported item
     pub alias: Option<Span<'base>>,
     /// Path to the module or item being imported
     pub import: SplitedPath<'base>,
+    /// The imported items below the path
+    pub kind: UseKindAst<'base>,
+}
+
+/// The items a [`UseAst`] imports below its path.
+#[derive(Debug, EnumIs)]
+pub enum UseKindAst<'base> {
+    /// The path itself is imported, `use lib.A;`
+    Single,
+    /// Every item of the group, `use lib.{A, B as C};`. The items hold the
+    /// full path, including the prefix of the group.
+    Group(Vec<UseAst<'base>>),
+    /// Every public item of the module, `use lib.*;`. Holds the span of `*`.
+    Wildcard(Span<'base>),
 }
 
 impl<'base> UseAst<'base> {
@@ -382,13 +446,129 @@ impl<'base> UseAst<'base> {
     pub fn ast_name(&self) -> Span<'base> {
         self.import.paths.last().unwrap().clone()
     }
+
+    /// Returns the imports of a single or grouped use statement.
+    ///
+    /// A single import yields itself and a group yields its items, a wildcard
+    /// import has no named items and yields nothing.
+    pub fn items(&self) -> impl Iterator<Item = &UseAst<'base>> {
+        let items = match &self.kind {
+            UseKindAst::Single => std::slice::from_ref(self),
+            UseKindAst::Group(items) => items.as_slice(),
+            UseKindAst::Wildcard(_) => &[],
+        };
+        items.iter()
+    }
+}
+
+/// Type alias definition AST node.
+///
+/// Gives an existing type another name. The alias is transparent, every use
+/// of the alias is the aliased type itself.
+///
+/// # Syntax Example
+///
+/// ```timu
+/// type Point = geometry.shapes.Point;
+/// pub type Meters = i32;
+/// ```
+///
+/// # Fields
+///
+/// - `is_public`: Optional `pub` modifier, aliases are imported like classes
+/// - `name`: The alias name
+/// - `target`: The aliased type, it can not be nullable or a reference
+/// - `index`: Unique identifier for this alias
+#[derive(Debug, PartialEq)]
+pub struct TypeAliasAst<'base> {
+    /// Optional public visibility modifier
+    pub is_public: Option<Span<'base>>,
+    /// Alias name identifier
+    pub name: Span<'base>,
+    /// The aliased type
+    pub target: TypeNameAst<'base>,
+    /// Unique index for this alias
+    pub index: AstIndex,
+}
+
+/// Enum definition AST node.
+///
+/// An enum value is exactly one of its variants. A variant can carry values
+/// of fixed types, its payload, which makes the enum a tagged union.
+///
+/// # Syntax Example
+///
+/// ```timu
+/// enum Color { Red, Green, Blue }
+/// pub enum Shape {
+///     Circle(float),
+///     Rect(float, float),
+///     Empty,
+/// }
+/// ```
+///
+/// # Fields
+///
+/// - `is_public`: Optional `pub` modifier, enums are imported like classes
+/// - `name`: The enum name
+/// - `variants`: The variants in declaration order
+/// - `index`: Unique identifier for this enum
+#[derive(Debug, PartialEq)]
+pub struct EnumDefinitionAst<'base> {
+    /// Optional public visibility modifier
+    pub is_public: Option<Span<'base>>,
+    /// Enum name identifier
+    pub name: Span<'base>,
+    /// Variants in declaration order
+    pub variants: Vec<EnumVariantAst<'base>>,
+    /// Unique index for this enum
+    pub index: AstIndex,
+}
+
+/// Single variant of an enum, like `Rect(float, float)`
+#[derive(Debug, PartialEq)]
+pub struct EnumVariantAst<'base> {
+    /// Variant name identifier
+    pub name: Span<'base>,
+    /// Types of the carried values, empty for a plain variant
+    pub payload: Vec<TypeNameAst<'base>>,
+}
+
+/// Module-level constant or global variable AST node.
+///
+/// Declared at the top level of a file, next to the functions and classes.
+/// The initial value has to be a compile-time constant expression, it can
+/// only use literals, other module constants and const function calls.
+///
+/// # Syntax Example
+///
+/// ```timu
+/// pub const MAX_USERS: i32 = 100;
+/// const LIMIT = MAX_USERS * 2;
+/// var counter: i32 = 0;
+/// ```
+///
+/// # Fields
+///
+/// - `is_public`: Optional `pub` modifier, public ones can be imported
+/// - `definition`: The declaration, it always has an initial value
+/// - `index`: Unique identifier for this declaration
+#[derive(Debug, PartialEq)]
+pub struct ModuleVariableAst<'base> {
+    /// Optional public visibility modifier
+    pub is_public: Option<Span<'base>>,
+    /// The `const` or `var` declaration
+    pub definition: VariableDefinitionAst<'base>,
+    /// Unique index for this declaration
+    pub index: AstIndex,
 }
 
 /// Class definition AST node.
 ///
 /// Represents a class declaration in Timu source code. Classes can contain
 /// both fields (data members) and methods (functions). They serve as the
-/// primary unit of object-oriented programming in Timu.
+/// primary unit of object-oriented programming in Timu. A class can inherit
+/// the fields and methods of a single base class.
 ///
 /// # Syntax Example
 ///
@@ -401,11 +581,16 @@ impl<'base> UseAst<'base> {
 ///         return this.name;
 ///     }
 /// }
+///
+/// class Employee : Person {
+///     company: string;
+/// }
 /// ```
 ///
 /// # Fields
 ///
 /// - `name`: The class name identifier
+/// - `base_class`: The class this class inherits from
 /// - `fields`: All members (fields and methods) of the class
 /// - `index`: Unique identifier for this class within the compilation unit
 #[derive(Debug, PartialEq)]
@@ -414,6 +599,8 @@ pub struct ClassDefinitionAst<'base> {
     pub is_public: Option<Span<'base>>,
     /// Class name identifier
     pub name: Span<'base>,
+    /// Optional base class the fields and methods are inherited from
+    pub base_class: Option<TypeNameAst<'base>>,
     /// All class members (fields and methods)
     pub fields: Vec<ClassDefinitionFieldAst<'base>>,
     /// Unique index for this class
@@ -463,7 +650,7 @@ pub struct InterfaceDefinitionAst<'base> {
 /// and field declarations that implementing classes must provide.
 #[derive(Debug, PartialEq)]
 pub enum InterfaceDefinitionFieldAst<'base> {
-    /// Method signature without implementation
+    /// Method signature, optionally with a default implementation
     Function(InterfaceFunctionDefinitionAst<'base>),
     /// Field declaration
     Field(FieldAst<'base>),
@@ -490,6 +677,7 @@ pub enum InterfaceDefinitionFieldAst<'base> {
 /// - `name`: The class being extended
 /// - `fields`: New methods and fields added by this extension
 /// - `base_interfaces`: Interfaces implemented by this extension
+/// - `close_brace`: The `}` ending the extension
 #[derive(Debug, PartialEq)]
 pub struct ExtendDefinitionAst<'base> {
     /// The class being extended
@@ -498,6 +686,8 @@ pub struct ExtendDefinitionAst<'base> {
     pub fields: Vec<ExtendDefinitionFieldAst<'base>>,
     /// Interfaces implemented by this extension
     pub base_interfaces: Vec<TypeNameAst<'base>>,
+    /// The closing `}` of the extension, missing members are inserted before it
+    pub close_brace: Span<'base>,
 }
 
 /// Members that can be added by an extension definition.
@@ -505,6 +695,7 @@ pub struct ExtendDefinitionAst<'base> {
 /// Extensions can add both new methods (with implementations) and
 /// new fields to existing classes.
 #[derive(Debug, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 pub enum ExtendDefinitionFieldAst<'base> {
     /// New method implementation
     Function(FunctionDefinitionAst<'base>),
@@ -532,14 +723,18 @@ pub enum ExtendDefinitionFieldAst<'base> {
 /// &string?        // Nullable reference type
 /// module.Class    // Qualified type name
 /// &module.Class?  // Complex qualified nullable reference
+/// func(i32): bool // Function type
+/// (i32, string)   // Tuple type
 /// ```
 ///
 /// # Fields
 ///
 /// - `reference`: Whether this is a reference type (`&`)
 /// - `nullable`: Whether this type can be null (`?`)
-/// - `names`: Path components (e.g., ["module", "Class"])
+/// - `names`: Path components (e.g., ["module", "Class"]), only the `func` keyword for function types and the `(` for tuple types
 /// - `names_span`: Source span covering the entire type reference
+/// - `function`: Argument and return types when this is a function type
+/// - `tuple`: Element types when this is a tuple type
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct TypeNameAst<'base> {
     /// Whether this is a reference type (prefixed with &)
@@ -549,7 +744,33 @@ pub struct TypeNameAst<'base> {
     /// Type name components (for qualified names like module.Class)
     pub names: Vec<Span<'base>>,
     /// Source span covering the entire type reference
-    pub names_span: Span<'base>
+    pub names_span: Span<'base>,
+    /// Signature of a function type such as `func(i32, i32): i32`
+    pub function: Option<Box<FunctionTypeAst<'base>>>,
+    /// Element types of a tuple type such as `(i32, string)`
+    pub tuple: Option<Vec<TypeNameAst<'base>>>,
+}
+
+/// Signature part of a function type.
+///
+/// Function types describe values that can be called, such as named
+/// functions passed as arguments or lambda expressions.
+///
+/// # Syntax Examples
+///
+/// ```timu
+/// func(): void
+/// func(i32, i32): i32
+/// func(ref string?): bool
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionTypeAst<'base> {
+    /// Types of the arguments, in declaration order
+    pub arguments: Vec<TypeNameAst<'base>>,
+    /// Source span covering the parenthesized argument list
+    pub arguments_span: Span<'base>,
+    /// Type returned by the function
+    pub return_type: TypeNameAst<'base>,
 }
 
 impl ToRange for TypeNameAst<'_> {
@@ -571,7 +792,7 @@ impl ToRange for TypeNameAst<'_> {
 /// obj.field.value     // Nested property access
 /// module.function     // Module-qualified access
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct RefAst<'base> {
     /// Path components for the reference (e.g., ["obj", "field", "value"])
     pub names: Vec<Span<'base>>,
@@ -619,7 +840,9 @@ pub enum FunctionArgumentAst<'base> {
 /// - **Variable Assignment**: `x = value;`
 /// - **Function Call**: `function(args);`
 /// - **If Condition**: `if (condition) { ... }`
-#[derive(Debug, PartialEq)]
+/// - **Match**: `match (value) { pattern => body, ... }`
+/// - **Return**: `return value;`
+#[derive(Debug, Clone, PartialEq)]
 pub enum BodyStatementAst<'base> {
     /// Variable declaration with optional initialization
     VariableDefinition(VariableDefinitionAst<'base>),
@@ -629,6 +852,10 @@ pub enum BodyStatementAst<'base> {
     FunctionCall(FunctionCallAst<'base>),
     /// Conditional statement (if/else)
     IfCondition(IfConditionAst<'base>),
+    /// Match used as a statement, the values of its arms are discarded
+    Match(MatchAst<'base>),
+    /// Return from the current function
+    Return(ReturnAst<'base>),
 }
 
 /// Statement block containing a sequence of statements.
@@ -643,7 +870,7 @@ pub enum BodyStatementAst<'base> {
 /// - Function implementations
 /// - If/else blocks
 /// - Loop bodies (when added to the language)
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct BodyAst<'base> {
     /// Sequence of statements in this block
     pub statements: Vec<BodyStatementAst<'base>>,
@@ -658,6 +885,8 @@ pub struct BodyAst<'base> {
 pub enum FunctionDefinitionLocationAst<'base> {
     /// Function defined within a class (method)
     Class(Span<'base>),
+    /// Function defined within an extend block of the class
+    Extend(Span<'base>),
     /// Function defined at module level (standalone function)
     #[allow(dead_code)]
     Module,
@@ -691,6 +920,12 @@ pub enum FunctionDefinitionLocationAst<'base> {
 pub struct FunctionDefinitionAst<'base> {
     /// Optional public visibility modifier
     pub is_public: Option<Span<'base>>,
+    /// Optional `const` modifier, marks the function as evaluable at compile time
+    pub is_const: Option<Span<'base>>,
+    /// Optional `native` modifier, the function has no body and is implemented by the host
+    pub is_native: Option<Span<'base>>,
+    /// Optional `extern` modifier, the function has no body and is a foreign symbol
+    pub is_extern: Option<Span<'base>>,
     /// Function name identifier
     pub name: Span<'base>,
     /// Function parameters
@@ -716,7 +951,7 @@ pub struct FunctionDefinitionAst<'base> {
 ///
 /// - **This**: Calls on current object (e.g., `this.method()`)
 /// - **Direct**: Direct calls (e.g., `obj.method()`, `module.function()`)
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum FunctionCallType<'base> {
     /// Call on current object (this.method)
     This(Vec<Span<'base>>),
@@ -745,7 +980,7 @@ pub enum FunctionCallType<'base> {
 /// - `arguments_span`: Source span covering the argument list
 /// - `path`: How the function is referenced (this/direct)
 /// - `arguments`: Expression arguments passed to the function
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct FunctionCallAst<'base> {
     /// Source span of the function name being called
     pub call_span: Span<'base>,
@@ -820,17 +1055,18 @@ pub enum FunctionCallPathAst<'base> {
     TypeName(TypeNameAst<'base>),
 }
 
-/// Interface method signature without implementation.
+/// Interface method signature with an optional default implementation.
 ///
-/// Represents a method signature declared in an interface.
-/// Unlike regular function definitions, these only specify
-/// the signature without providing an implementation.
+/// Represents a method declared in an interface. Usually only the
+/// signature is given and every extension implements the method. A method
+/// with a body is a default implementation, extensions may omit it.
 ///
 /// # Syntax Example
 ///
 /// ```timu
 /// interface Drawable {
 ///     func draw(this, canvas: Canvas): void;  // Method signature only
+///     func scale(this): i32 { return 1; }     // Default implementation
 /// }
 /// ```
 #[derive(Debug, PartialEq)]
@@ -839,8 +1075,14 @@ pub struct InterfaceFunctionDefinitionAst<'base> {
     pub name: Span<'base>,
     /// Method parameters
     pub arguments: Vec<FunctionArgumentAst<'base>>,
+    /// Source span covering the entire parameter list
+    pub arguments_span: Span<'base>,
     /// Method return type
     pub return_type: TypeNameAst<'base>,
+    /// Default implementation, `None` for a signature only method
+    pub body: Option<Rc<BodyAst<'base>>>,
+    /// Unique index for this method
+    pub index: AstIndex,
 }
 
 /// Members that can appear within a class definition.
@@ -848,6 +1090,7 @@ pub struct InterfaceFunctionDefinitionAst<'base> {
 /// Classes can contain both data fields and method implementations.
 /// This enum distinguishes between the two types of class members.
 #[derive(Debug, PartialEq)]
+#[allow(clippy::large_enum_variant)]
 pub enum ClassDefinitionFieldAst<'base> {
     /// Data field declaration
     Field(FieldAst<'base>),
@@ -866,17 +1109,21 @@ pub enum ClassDefinitionFieldAst<'base> {
 /// name: string;           // Private field
 /// public age: i32;        // Public field
 /// data: &SomeType?;       // Complex type with modifiers
+/// static count: i32;      // Class level field
 /// ```
 ///
 /// # Fields
 ///
 /// - `is_public`: Optional public visibility modifier
+/// - `is_static`: Optional `static` modifier, only valid in classes
 /// - `name`: Field name identifier
 /// - `field_type`: Field type with optional modifiers
 #[derive(Debug, PartialEq)]
 pub struct FieldAst<'base> {
     /// Optional public visibility modifier
     pub is_public: Option<Span<'base>>,
+    /// Optional `static` modifier, the field belongs to the class instead of its objects
+    pub is_static: Option<Span<'base>>,
     /// Field name identifier
     pub name: Span<'base>,
     /// Field type
@@ -897,6 +1144,8 @@ pub struct FieldAst<'base> {
 /// - **Ident**: Simple identifiers (`variable`)
 /// - **FunctionCall**: Function/method invocations
 /// - **Operation**: Binary operations with left/right operands
+/// - **Lambda**: Anonymous functions (`func(a: i32): i32 { return a; }`)
+/// - **Match**: Value chosen by the first matching pattern
 ///
 /// # Syntax Examples
 ///
@@ -908,8 +1157,11 @@ pub struct FieldAst<'base> {
 /// !condition              // Not
 /// func(arg1, arg2)        // FunctionCall
 /// a + b * c               // Operation (with precedence)
+/// func(a: i32): i32 { }   // Lambda
+/// match (flag) { true => 1, false => 0 }  // Match
+/// (1, "one")              // Tuple
 /// ```
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ExpressionAst<'base> {
     /// Literal primitive value
     Primitive { 
@@ -935,6 +1187,150 @@ pub enum ExpressionAst<'base> {
         /// Right operand
         right: Box<ExpressionAst<'base>> 
     },
+    /// Anonymous function
+    Lambda(LambdaAst<'base>),
+    /// Match expression
+    Match(Box<MatchAst<'base>>),
+    /// Tuple literal
+    Tuple(TupleAst<'base>),
+}
+
+/// Tuple literal AST node.
+///
+/// Groups two or more values into a single value, the elements are read
+/// back with `ref pair.0` or by destructuring the tuple into variables.
+///
+/// # Syntax Examples
+///
+/// ```timu
+/// (1, "one")
+/// (name, (width, height))
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TupleAst<'base> {
+    /// Source span covering the parentheses and the elements
+    pub span: Span<'base>,
+    /// Element values, in order
+    pub elements: Vec<ExpressionAst<'base>>,
+}
+
+/// Lambda expression AST node.
+///
+/// Lambdas are anonymous functions written inside expressions. They can use
+/// the variables of the enclosing scopes, mutable locals can only be captured
+/// when the lambda is declared `ref`.
+///
+/// # Syntax Examples
+///
+/// ```timu
+/// func(a: i32, b: i32): i32 { return a + b; }
+/// ref func(): void { counter = counter + 1; }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LambdaAst<'base> {
+    /// Optional `ref` modifier, allows capturing mutable locals by reference
+    pub is_ref: Option<Span<'base>>,
+    /// Source span covering the whole lambda
+    pub span: Span<'base>,
+    /// Lambda parameters
+    pub arguments: Vec<FunctionArgumentAst<'base>>,
+    /// Source span covering the entire parameter list
+    pub arguments_span: Span<'base>,
+    /// Lambda return type
+    pub return_type: TypeNameAst<'base>,
+    /// Lambda body implementation
+    pub body: Rc<BodyAst<'base>>,
+    /// Unique index for this lambda
+    pub index: AstIndex,
+}
+
+/// Match expression AST node.
+///
+/// The value is compared against the pattern of every arm in order and the
+/// first matching arm is evaluated. Used as an expression, the arms produce
+/// the value of the match.
+///
+/// # Syntax Example
+///
+/// ```timu
+/// match (shape) {
+///     circle: Circle => circle.radius(),
+///     null => 0,
+///     _ => {
+///         log("unknown shape");
+///         return -1;
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchAst<'base> {
+    /// Source span covering the whole match
+    pub span: Span<'base>,
+    /// The matched value
+    pub expression: ExpressionAst<'base>,
+    /// Arms in source order
+    pub arms: Vec<MatchArmAst<'base>>,
+}
+
+/// Single `pattern => body` arm of a match
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArmAst<'base> {
+    /// Pattern the matched value is compared against
+    pub pattern: PatternAst<'base>,
+    /// Evaluated when the pattern matches
+    pub body: MatchArmBodyAst<'base>,
+}
+
+/// Body of a match arm
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchArmBodyAst<'base> {
+    /// Single expression, the value of the arm
+    Expression(Box<ExpressionAst<'base>>),
+    /// Statement block, the arm has no value
+    Block(BodyAst<'base>),
+}
+
+/// Pattern of a match arm.
+///
+/// # Syntax Examples
+///
+/// ```timu
+/// 42                  // Literal
+/// null                // Null, only for nullable values
+/// _                   // Wildcard, matches everything
+/// Color.Red           // Enum variant
+/// Shape.Rect(w, h)    // Enum variant, binds the payload as `w` and `h`
+/// circle: Circle      // Class type, binds the value as `circle`
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum PatternAst<'base> {
+    /// Matches values equal to the literal
+    Literal {
+        /// Source span of the literal
+        span: Span<'base>,
+        /// The literal value
+        value: PrimitiveValue<'base>,
+    },
+    /// Matches the missing value of a nullable type
+    Null(Span<'base>),
+    /// Matches every value
+    Wildcard(Span<'base>),
+    /// Matches a variant of an enum
+    Variant {
+        /// Source span of the whole pattern
+        span: Span<'base>,
+        /// Path of the variant, like `Color.Red`
+        path: Vec<Span<'base>>,
+        /// Names the payload values are bound to, `None` when the payload is not bound
+        bindings: Option<Vec<Span<'base>>>,
+    },
+    /// Matches objects of a class and binds them to a new constant
+    Class {
+        /// Name the matched object is bound to
+        binding: Span<'base>,
+        /// Class the object has to be an instance of
+        class: TypeNameAst<'base>,
+    },
 }
 
 /// Conditional statement AST node (if/else if/else).
@@ -960,7 +1356,7 @@ pub enum ExpressionAst<'base> {
 /// - `true_body`: Statements executed when condition is true
 /// - `else_ifs`: Additional condition/body pairs for else-if branches
 /// - `false_body`: Optional else clause statements
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct IfConditionAst<'base> {
     /// Main condition expression
     pub expression: ExpressionAst<'base>,
@@ -984,20 +1380,24 @@ pub struct IfConditionAst<'base> {
 /// var x: i32 = 42;        // Mutable with explicit type and initializer
 /// const name = "hello";   // Immutable with inferred type
 /// var count: i32;         // Mutable with explicit type, no initializer
+/// var (x, y) = point();   // Destructuring a tuple into two variables
 /// ```
 ///
 /// # Fields
 ///
 /// - `variable_definition_type`: Mutability (var/const)
-/// - `name`: Variable name identifier
+/// - `name`: Variable name identifier, the whole parenthesized list when destructuring
+/// - `destructure`: Variable names receiving the tuple elements, in order
 /// - `expected_type`: Optional explicit type annotation
 /// - `expression`: Optional initialization expression
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct VariableDefinitionAst<'base> {
     /// Variable mutability (var or const)
     pub variable_definition_type: VariableDefinitionType,
     /// Variable name identifier
     pub name: Span<'base>,
+    /// Names of the variables defined by a destructuring definition
+    pub destructure: Option<Vec<Span<'base>>>,
     /// Optional explicit type annotation
     pub expected_type: Option<TypeNameAst<'base>>,
     /// Optional initialization expression
@@ -1019,12 +1419,39 @@ pub struct VariableDefinitionAst<'base> {
 ///
 /// # Fields
 ///
+/// - `path`: Names leading to an assigned field
 /// - `name`: Variable name being assigned to
 /// - `expression`: New value expression
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct VariableAssignAst<'base> {
+    /// Names leading to an assigned field, like `Counter` in `Counter.count = 1;`, empty for variables
+    pub path: Vec<Span<'base>>,
     /// Variable name being assigned to
     pub name: Span<'base>,
     /// New value expression
     pub expression: ExpressionAst<'base>,
 }
+
+/// Return statement AST node.
+///
+/// Leaves the current function, optionally handing a value back to
+/// the caller. Functions returning `void` use the bare form.
+///
+/// # Syntax Example
+///
+/// ```timu
+/// return a + b;
+/// return;
+/// ```
+///
+/// # Fields
+///
+/// - `span`: Source span of the `return` keyword
+/// - `expression`: Optional returned value
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReturnAst<'base> {
+    /// Source span of the `return` keyword
+    pub span: Span<'base>,
+    /// Optional returned value
+    pub expression: Option<ExpressionAst<'base>>,
+}
@@ -0,0 +1,67 @@
+//! Code generation backends for the Timu language compiler.
+//!
+//! Backends consume the type-checked program stored in a [`TirContext`] and
+//! produce code for a specific target. The type checker already guarantees
+//! that every expression, call and statement is valid, so backends only
+//! report constructs that the target cannot express yet.
+//!
+//! # Available Backends
+//!
+//! - [`wat`]: WebAssembly text format, suitable for sandboxed execution
+//!
+//! # Usage
+//!
+//! ```ignore
+//! let context = process_ast(vec![ast.into()])?;
+//! let wat = libtimu::backend::wat::generate(&context)?;
+//! ```
+//!
+//! [`TirContext`]: crate::tir::TirContext
+
+use std::ops::Range;
+
+use libtimu_macros::TimuError;
+use libtimu_macros_core::SourceCode;
+use strum_macros::{EnumDiscriminants, EnumProperty};
+
+use crate::{file::SourceFile, tir::TirError};
+
+pub mod wat;
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("the {backend} backend does not support {message}")]
+#[diagnostic(code("T0035"), help("rewrite the code without this construct or use another backend"))]
+pub struct UnsupportedConstruct {
+    pub message: String,
+    pub backend: &'static str,
+
+    #[label("not supported")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error, EnumDiscriminants, EnumProperty)]
+pub enum BackendError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    UnsupportedConstruct(Box<UnsupportedConstruct>),
+}
+
+impl BackendError {
+    pub fn unsupported(backend: &'static str, message: impl Into<String>, position: Range<usize>, source: &SourceFile) -> TirError {
+        BackendError::UnsupportedConstruct(UnsupportedConstruct {
+            message: message.into(),
+            backend,
+            position,
+            code: source.into(),
+        }.into()).into()
+    }
+}
+
+impl From<BackendError> for TirError {
+    fn from(value: BackendError) -> Self {
+        TirError::Backend(Box::new(value))
+    }
+}
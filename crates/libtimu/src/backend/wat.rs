@@ -0,0 +1,1665 @@
+//! WebAssembly text format (WAT) backend.
+//!
+//! This backend turns a type-checked program into a single WebAssembly module
+//! in text format. The output can be assembled with any WebAssembly toolchain
+//! and executed in a sandboxed runtime. The only access to the host are the
+//! native functions of the standard library and extern functions, see
+//! [Native Functions](#native-functions).
+//!
+//! # Type Mapping
+//!
+//! | Timu type                          | WebAssembly type        |
+//! |------------------------------------|-------------------------|
+//! | `bool`, `i8`-`i32`, `u8`-`u32`     | `i32`                   |
+//! | `i64`, `u64`                       | `i64`                   |
+//! | `float`                            | `f32`                   |
+//! | `double`                           | `f64`                   |
+//! | `string`, classes, interfaces      | `i32` pointer to memory |
+//! | `void`                             | no result               |
+//!
+//! Integers narrower than 32 bits are wrapped back into their range after
+//! every arithmetic operation, and unsigned types use the unsigned variants
+//! of division, remainder, shift and comparison instructions.
+//!
+//! # Memory Layout
+//!
+//! The module exports its linear memory as `memory`. Address `0` is kept
+//! free so it can act as a null pointer.
+//!
+//! - **Strings** are stored as a 32-bit little endian byte length followed by
+//!   the UTF-8 bytes. String literals become data segments, starting at
+//!   address `8`, and a string value is the address of its length prefix.
+//! - **Classes** live in linear memory and are passed around as pointers. Fields
+//!   are laid out in definition order, each aligned to its own size. The
+//!   fields of a base class come first, so a subclass object is also a valid
+//!   object of its base class. Static fields are not part of the objects, each
+//!   one is a zero initialized global named after its path, such as
+//!   `$main.Counter::count`.
+//! - **Module variables** are globals named after their path, such as
+//!   `$main.LIMIT`, initialised with the value evaluated at compile time.
+//!   Constants are immutable globals, global variables are mutable ones.
+//! - **Heap** allocations are served by a bump allocator that starts after the
+//!   last data segment. It is exported as `timu_alloc`, so the host can create
+//!   objects and strings before calling into the module.
+//!
+//! # Functions
+//!
+//! Every function gets the WAT name of its full path, such as `$main.add` or
+//! `$main.Point::length`. Methods taking `this` receive the object pointer as
+//! their first parameter. Public module-level functions are exported under
+//! their full path, so `pub func add(...)` in module `main` is exported as
+//! `main.add`.
+//!
+//! Default functions of an interface are emitted once for every class using
+//! them, named after the class such as `$main.Square::sides`, so the calls
+//! through `this` in the body reach the functions of that class. Calling an
+//! interface function on a value typed as the interface is not supported.
+//!
+//! Calls are dispatched statically: an inherited method is called on the
+//! subclass object with the function of the base class. Objects do not record
+//! their class, so calling a method through a class that has a subclass
+//! overriding it is reported as unsupported.
+//!
+//! # Native Functions
+//!
+//! Native functions of the [standard library](crate::prelude) have no body.
+//! Each one that the program calls is imported from the `std` module under its
+//! full path, such as `(import "std" "std.io.println" ...)`, and uses the
+//! same type mapping as any other function. Strings are passed as pointers,
+//! so the host reads them from the exported `memory`.
+//!
+//! Extern functions are imported the same way from the `env` module under
+//! their symbol name, so `extern func puts(text: ref string): i32;` becomes
+//! `(import "env" "puts" ...)` and the host links it to the foreign function.
+//! `timuc run` only links the externs on its allow-list to host closures.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let context = process_ast(vec![ast.into()])?;
+//! let wat = libtimu::backend::wat::generate(&context)?;
+//! let wasm = wat::parse_str(&wat)?;
+//! ```
+
+use std::{borrow::Cow, collections::{HashMap, HashSet}};
+
+use indexmap::IndexMap;
+
+use crate::{
+    ast::{BodyAst, BodyStatementAst, ExpressionAst, ExpressionOperatorType, FunctionArgumentAst, FunctionCallAst, FunctionCallType, FunctionDefinitionLocationAst, PrimitiveValue},
+    nom_tools::{Span, SpanInfo},
+    tir::{
+        context::SpanKey,
+        resolver::{function::FunctionDefinition, statement::{default_literal_type, expression_position, get_primitive_location, get_primitive_type, is_bool_operator, is_integer, is_numeric, operand_type}, TypeLocation},
+        PrimitiveType, TirContext, TirError, TypeValue,
+    },
+};
+
+use super::BackendError;
+
+const BACKEND_NAME: &str = "wat";
+
+/// First address used for string literals, address `0` is reserved as null
+const DATA_START: u32 = 8;
+
+const PAGE_SIZE: u32 = 65536;
+
+/// Runtime support functions that are added to every module
+const RUNTIME: &str = r#"  (func $timu_alloc (export "timu_alloc") (param $size i32) (result i32)
+    (local $pointer i32)
+    global.get $__heap_top
+    local.set $pointer
+    global.get $__heap_top
+    local.get $size
+    i32.const 7
+    i32.add
+    i32.const -8
+    i32.and
+    i32.add
+    global.set $__heap_top
+    (block $done
+      global.get $__heap_top
+      memory.size
+      i32.const 16
+      i32.shl
+      i32.le_u
+      br_if $done
+      global.get $__heap_top
+      memory.size
+      i32.const 16
+      i32.shl
+      i32.sub
+      i32.const 65535
+      i32.add
+      i32.const 16
+      i32.shr_u
+      memory.grow
+      i32.const -1
+      i32.eq
+      if
+        unreachable
+      end)
+    local.get $pointer)
+  (func $timu_memcopy (param $destination i32) (param $source i32) (param $length i32)
+    (local $index i32)
+    (block $done
+      (loop $copy
+        local.get $index
+        local.get $length
+        i32.ge_u
+        br_if $done
+        local.get $destination
+        local.get $index
+        i32.add
+        local.get $source
+        local.get $index
+        i32.add
+        i32.load8_u
+        i32.store8
+        local.get $index
+        i32.const 1
+        i32.add
+        local.set $index
+        br $copy)))
+  (func $timu_string_concat (param $left i32) (param $right i32) (result i32)
+    (local $left_length i32)
+    (local $right_length i32)
+    (local $result i32)
+    local.get $left
+    i32.load
+    local.set $left_length
+    local.get $right
+    i32.load
+    local.set $right_length
+    local.get $left_length
+    local.get $right_length
+    i32.add
+    i32.const 4
+    i32.add
+    call $timu_alloc
+    local.set $result
+    local.get $result
+    local.get $left_length
+    local.get $right_length
+    i32.add
+    i32.store
+    local.get $result
+    i32.const 4
+    i32.add
+    local.get $left
+    i32.const 4
+    i32.add
+    local.get $left_length
+    call $timu_memcopy
+    local.get $result
+    i32.const 4
+    i32.add
+    local.get $left_length
+    i32.add
+    local.get $right
+    i32.const 4
+    i32.add
+    local.get $right_length
+    call $timu_memcopy
+    local.get $result)
+  (func $timu_string_equal (param $left i32) (param $right i32) (result i32)
+    (local $length i32)
+    (local $index i32)
+    local.get $left
+    i32.load
+    local.set $length
+    local.get $length
+    local.get $right
+    i32.load
+    i32.ne
+    if
+      i32.const 0
+      return
+    end
+    (block $done
+      (loop $compare
+        local.get $index
+        local.get $length
+        i32.ge_u
+        br_if $done
+        local.get $left
+        local.get $index
+        i32.add
+        i32.load8_u offset=4
+        local.get $right
+        local.get $index
+        i32.add
+        i32.load8_u offset=4
+        i32.ne
+        if
+          i32.const 0
+          return
+        end
+        local.get $index
+        i32.const 1
+        i32.add
+        local.set $index
+        br $compare))
+    i32.const 1)
+"#;
+
+/// WebAssembly value types used by the generated code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+impl ValueType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ValueType::I32 => "i32",
+            ValueType::I64 => "i64",
+            ValueType::F32 => "f32",
+            ValueType::F64 => "f64",
+        }
+    }
+}
+
+/// Generates a WebAssembly text module for the whole program
+///
+/// # Arguments
+/// * `context` - The type-checked program
+///
+/// # Returns
+/// * `Ok(String)` - The module in WebAssembly text format
+/// * `Err(TirError)` - The program uses a construct the backend cannot express
+pub fn generate(context: &TirContext<'_>) -> Result<String, TirError> {
+    WatGenerator::new(context).generate()
+}
+
+#[derive(Debug, Clone)]
+struct LocalVariable {
+    name: String,
+    location: TypeLocation,
+}
+
+/// Code generation state of a single function
+#[derive(Debug, Default)]
+struct FunctionBuilder {
+    locals: Vec<(String, ValueType)>,
+    used_names: Vec<String>,
+    scopes: Vec<HashMap<String, LocalVariable>>,
+    lines: Vec<String>,
+    depth: usize,
+    this: Option<TypeLocation>,
+    return_type: Option<TypeLocation>,
+}
+
+impl FunctionBuilder {
+    fn push<T: AsRef<str>>(&mut self, instruction: T) {
+        self.lines.push(format!("{}{}", "  ".repeat(self.depth + 2), instruction.as_ref()));
+    }
+
+    fn unique_name(&mut self, name: &str) -> String {
+        let mut unique_name = name.to_string();
+        let mut index = 1;
+
+        while self.used_names.contains(&unique_name) {
+            unique_name = format!("{name}_{index}");
+            index += 1;
+        }
+
+        self.used_names.push(unique_name.clone());
+        unique_name
+    }
+
+    fn declare_local(&mut self, name: &str, location: TypeLocation, value_type: ValueType) -> String {
+        let unique_name = self.unique_name(name);
+        self.locals.push((unique_name.clone(), value_type));
+        self.scopes.last_mut().expect("Function scope missing, but this is a bug").insert(name.to_string(), LocalVariable { name: unique_name.clone(), location });
+        unique_name
+    }
+
+    fn lookup(&self, name: &str) -> Option<&LocalVariable> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+}
+
+/// Emits WebAssembly text for a type-checked program
+pub struct WatGenerator<'ctx, 'base> {
+    context: &'ctx TirContext<'base>,
+    strings: IndexMap<String, u32>,
+    data_end: u32,
+    /// Global name and type of every static field, keyed by the class and the field name
+    statics: HashMap<(TypeLocation, String), (String, TypeLocation)>,
+    /// Global name and type of every module variable and static field, keyed by the span of its name
+    globals: HashMap<SpanKey, (String, TypeLocation)>,
+    /// Function name of the copy of an interface default function, keyed by the class using it and the function name
+    defaults: HashMap<(TypeLocation, String), String>,
+}
+
+impl<'ctx, 'base> WatGenerator<'ctx, 'base> {
+    pub fn new(context: &'ctx TirContext<'base>) -> Self {
+        Self {
+            context,
+            strings: IndexMap::new(),
+            data_end: DATA_START,
+            statics: HashMap::new(),
+            globals: HashMap::new(),
+            defaults: HashMap::new(),
+        }
+    }
+
+    /// Generates the complete module
+    pub fn generate(mut self) -> Result<String, TirError> {
+        let context = self.context;
+        let mut functions = Vec::new();
+
+        let mut imports = Vec::new();
+        let called = context.call_targets.values().collect::<HashSet<_>>();
+        let mut globals = self.generate_statics()?;
+        globals.extend(self.generate_module_variables()?);
+        let defaults = self.collect_defaults();
+
+        for (_, location, signature) in context.types.iter() {
+            match signature.value.as_ref() {
+                TypeValue::Function(function) if !function.ast.has_body() && called.contains(&location) => imports.push(self.generate_import(function)?),
+                TypeValue::Function(function) if !function.ast.has_body() || self.interface_of_default(function).is_some() => (),
+                TypeValue::Function(function) => functions.push(self.generate_function(function, None)?),
+                _ => (),
+            };
+        }
+
+        for (class_location, function) in defaults.into_iter() {
+            functions.push(self.generate_function(function, Some(class_location))?);
+        }
+
+        let heap_start = self.data_end.div_ceil(8) * 8;
+        let pages = heap_start.div_ceil(PAGE_SIZE).max(1);
+
+        let mut output = String::new();
+        output.push_str("(module\n");
+
+        for import in imports.into_iter() {
+            output.push_str(&import);
+        }
+
+        output.push_str(&format!("  (memory (export \"memory\") {pages})\n"));
+        output.push_str(&format!("  (global $__heap_top (mut i32) (i32.const {heap_start}))\n"));
+
+        for global in globals.into_iter() {
+            output.push_str(&global);
+        }
+
+        for (text, address) in self.strings.iter() {
+            let mut bytes = (text.len() as u32).to_le_bytes().to_vec();
+            bytes.extend_from_slice(text.as_bytes());
+            output.push_str(&format!("  (data (i32.const {address}) \"{}\")\n", escape_bytes(&bytes)));
+        }
+
+        output.push_str(RUNTIME);
+
+        for function in functions.into_iter() {
+            output.push_str(&function);
+        }
+
+        output.push_str(")\n");
+        Ok(output)
+    }
+
+    /// Declares a global for every static field of the classes
+    fn generate_statics(&mut self) -> Result<Vec<String>, TirError> {
+        let mut globals = Vec::new();
+
+        for (path, location, signature) in self.context.types.iter() {
+            let TypeValue::Class(class) = signature.value.as_ref() else {
+                continue;
+            };
+
+            for (name, field) in class.fields.iter() {
+                // Functions without `this` are static members too, but they are not stored
+                if !class.static_members.contains(name) || matches!(self.context.types.get_from_location(field.location).map(|signature| signature.value.as_ref()), Some(TypeValue::Function(_))) {
+                    continue;
+                }
+
+                let global = format!("{}::{}", path.get_raw_path(), name);
+                let value_type = self.required_value_type(field.location, &(&field.span).into())?;
+                globals.push(format!("  (global ${global} (mut {0}) ({0}.const 0))\n", value_type.name()));
+                // Methods of the class use static fields by their name alone
+                self.globals.insert(SpanKey::from(&field.span), (global.clone(), field.location));
+                self.statics.insert((location, name.to_string()), (global, field.location));
+            }
+        }
+
+        Ok(globals)
+    }
+
+    /// Declares a global for every module-level constant and global variable
+    fn generate_module_variables(&mut self) -> Result<Vec<String>, TirError> {
+        let mut globals = Vec::new();
+
+        for (key, global) in self.context.globals.iter() {
+            let position = (&global.definition.name).into();
+            let value_type = self.required_value_type(global.location, &position)?;
+            let (init, _) = match self.context.global_values.get(key) {
+                Some(value) => self.literal(value, Some(global.location)),
+                None => return Err(unsupported("module variable without a value", &position)),
+            };
+
+            let value_type = match global.is_const() {
+                true => value_type.name().to_string(),
+                false => format!("(mut {})", value_type.name()),
+            };
+
+            globals.push(format!("  (global ${} {value_type} ({init}))\n", global.path));
+            self.globals.insert(key.clone(), (global.path.clone(), global.location));
+        }
+
+        Ok(globals)
+    }
+
+    /// Finds the global of a module variable or static field an identifier refers to
+    fn find_global(&self, ident: &Span<'base>) -> Option<&(String, TypeLocation)> {
+        let definition = self.context.identifiers.get(&SpanKey::from(ident))?.definition.as_ref()?;
+        self.globals.get(definition)
+    }
+
+    /// Finds the global of a static field, static fields of the base classes are shared with the subclasses
+    fn find_static(&self, class_location: TypeLocation, field_name: &str) -> Option<&(String, TypeLocation)> {
+        match self.statics.get(&(class_location, field_name.to_string())) {
+            Some(global) => Some(global),
+            None => match self.context.types.get_from_location(class_location).map(|signature| signature.value.as_ref()) {
+                Some(TypeValue::Class(class)) => class.base.and_then(|base| self.find_static(base, field_name)),
+                _ => None,
+            },
+        }
+    }
+
+    /// Names a copy of every interface default function for each class using it
+    ///
+    /// The body of a default function calls the other members of the interface
+    /// through `this`, so every class gets a copy where `this` is an object of the class.
+    fn collect_defaults(&mut self) -> Vec<(TypeLocation, &'ctx FunctionDefinition<'base>)> {
+        let mut defaults = Vec::new();
+
+        for (path, location, signature) in self.context.types.iter() {
+            let TypeValue::Class(class) = signature.value.as_ref() else {
+                continue;
+            };
+
+            for (name, field) in class.fields.iter() {
+                if let Some(TypeValue::Function(function)) = self.context.types.get_from_location(field.location).map(|signature| signature.value.as_ref())
+                    && self.interface_of_default(function).is_some() {
+                    self.defaults.insert((location, name.to_string()), format!("{}::{}", path.get_raw_path(), name));
+                    defaults.push((location, function.as_ref()));
+                }
+            }
+        }
+
+        defaults
+    }
+
+    /// Returns the interface of a default function, its `this` is the interface instead of a class
+    fn interface_of_default(&self, function: &FunctionDefinition<'base>) -> Option<TypeLocation> {
+        match (function.ast.arguments.first(), function.arguments.first()) {
+            (Some(FunctionArgumentAst::This(_)), Some(argument)) => matches!(self.context.types.get_from_location(argument.field_type).map(|signature| signature.value.as_ref()), Some(TypeValue::Interface(_))).then_some(argument.field_type),
+            _ => None,
+        }
+    }
+
+    /// Declares a native or extern function that the host provides
+    fn generate_import(&self, function: &'ctx FunctionDefinition<'base>) -> Result<String, TirError> {
+        let path = function.signature_path.get_raw_path();
+        let (module, name) = match function.ast.is_extern {
+            Some(_) => ("env", function.name.text),
+            None => ("std", path.as_ref()),
+        };
+        let mut output = format!("  (import \"{module}\" \"{name}\" (func ${path}");
+
+        for argument in function.arguments.iter() {
+            let value_type = self.required_value_type(argument.field_type, &(&argument.name).into())?;
+            output.push_str(&format!(" (param {})", value_type.name()));
+        }
+
+        if let Some(value_type) = self.value_type(function.return_type, &(&function.ast.return_type.names_span).into())? {
+            output.push_str(&format!(" (result {})", value_type.name()));
+        }
+
+        output.push_str("))\n");
+        Ok(output)
+    }
+
+    /// Emits a function, the copy of a default function for a class is emitted when `implementor` is set
+    fn generate_function(&mut self, function: &'ctx FunctionDefinition<'base>, implementor: Option<TypeLocation>) -> Result<String, TirError> {
+        let path = match implementor {
+            Some(class_location) => Cow::Owned(self.defaults[&(class_location, function.name.text.to_string())].clone()),
+            None => Cow::Borrowed(function.signature_path.get_raw_path().as_ref()),
+        };
+        let mut builder = FunctionBuilder { scopes: vec![HashMap::new()], ..Default::default() };
+        let mut header = format!("  (func ${path}");
+
+        if function.is_public && matches!(function.ast.location.as_ref(), FunctionDefinitionLocationAst::Module) {
+            header.push_str(&format!(" (export \"{path}\")"));
+        }
+
+        for (argument, argument_ast) in function.arguments.iter().zip(function.ast.arguments.iter()) {
+            let value_type = self.required_value_type(argument.field_type, &(&argument.name).into())?;
+            let mut location = argument.field_type;
+            let name = match argument_ast {
+                FunctionArgumentAst::This(_) => {
+                    location = implementor.unwrap_or(location);
+                    builder.this = Some(location);
+                    "this"
+                },
+                FunctionArgumentAst::Argument { name, .. } => name.text,
+            };
+
+            let unique_name = builder.unique_name(name);
+            builder.scopes[0].insert(name.to_string(), LocalVariable { name: unique_name.clone(), location });
+            header.push_str(&format!(" (param ${unique_name} {})", value_type.name()));
+        }
+
+        let return_value_type = self.value_type(function.return_type, &(&function.ast.return_type.names_span).into())?;
+        if let Some(value_type) = return_value_type {
+            header.push_str(&format!(" (result {})", value_type.name()));
+            builder.return_type = Some(function.return_type);
+        }
+
+        for statement in function.ast.body.statements.iter() {
+            self.emit_statement(&mut builder, statement)?;
+        }
+
+        // The type checker does not require a return at the end of a non-void function
+        if return_value_type.is_some() {
+            builder.push("unreachable");
+        }
+
+        let mut output = header;
+        output.push('\n');
+        for (name, value_type) in builder.locals.iter() {
+            output.push_str(&format!("    (local ${name} {})\n", value_type.name()));
+        }
+
+        for line in builder.lines.iter() {
+            output.push_str(line);
+            output.push('\n');
+        }
+
+        output.push_str("  )\n");
+        Ok(output)
+    }
+
+    fn emit_statement(&mut self, function: &mut FunctionBuilder, statement: &'ctx BodyStatementAst<'base>) -> Result<(), TirError> {
+        match statement {
+            BodyStatementAst::VariableDefinition(definition) if definition.destructure.is_some() => return Err(unsupported("tuples", &(&definition.name).into())),
+            BodyStatementAst::VariableDefinition(definition) => {
+                let location = match self.context.variable_types.get(&SpanKey::from(&definition.name)) {
+                    Some(location) => *location,
+                    None => return Err(unsupported("unresolved variable", &(&definition.name).into())),
+                };
+
+                let value_type = self.required_value_type(location, &(&definition.name).into())?;
+                if let Some(expression) = &definition.expression {
+                    self.emit_expression(function, expression, Some(location))?;
+                }
+
+                let name = function.declare_local(definition.name.text, location, value_type);
+                if definition.expression.is_some() {
+                    function.push(format!("local.set ${name}"));
+                }
+            },
+            BodyStatementAst::VariableAssign(assign) if !assign.path.is_empty() => {
+                let first = &assign.path[0];
+
+                // `Counter.count = 1;` sets the global of a static field, the class name is not a local
+                let global = match (function.lookup(first.text), assign.path.len(), self.context.identifiers.get(&SpanKey::from(first))) {
+                    (None, 1, Some(target)) => self.find_static(target.location, assign.name.text).cloned(),
+                    _ => None,
+                };
+
+                match global {
+                    Some((global, location)) => {
+                        self.emit_expression(function, &assign.expression, Some(location))?;
+                        function.push(format!("global.set ${global}"));
+                    },
+                    None => {
+                        let mut location = self.emit_local_get(function, first)?;
+                        for name in assign.path[1..].iter() {
+                            location = self.emit_field_load(function, location, name)?;
+                        }
+
+                        self.emit_field_store(function, location, &assign.name, &assign.expression)?;
+                    },
+                };
+            },
+            BodyStatementAst::VariableAssign(assign) => {
+                let (instruction, name, location) = match (function.lookup(assign.name.text), self.find_global(&assign.name)) {
+                    (Some(variable), _) => ("local.set", variable.name.clone(), variable.location),
+                    (None, Some((global, location))) => ("global.set", global.clone(), *location),
+                    (None, None) => return Err(unsupported("assignment to a non local variable", &(&assign.name).into())),
+                };
+
+                self.emit_expression(function, &assign.expression, Some(location))?;
+                function.push(format!("{instruction} ${name}"));
+            },
+            BodyStatementAst::FunctionCall(function_call) => {
+                let return_type = self.emit_call(function, function_call)?;
+                if self.value_type(return_type, &(&function_call.call_span).into())?.is_some() {
+                    function.push("drop");
+                }
+            },
+            BodyStatementAst::IfCondition(if_condition) => {
+                let false_body = if_condition.false_body.as_ref();
+                self.emit_if(function, &if_condition.expression, &if_condition.true_body, &if_condition.else_ifs, false_body)?;
+            },
+            BodyStatementAst::Match(match_ast) => return Err(unsupported("match expressions", &(&match_ast.span).into())),
+            BodyStatementAst::Return(return_statement) => {
+                if let Some(expression) = &return_statement.expression {
+                    self.emit_expression(function, expression, function.return_type)?;
+                }
+                function.push("return");
+            },
+        };
+
+        Ok(())
+    }
+
+    fn emit_if(&mut self, function: &mut FunctionBuilder, condition: &'ctx ExpressionAst<'base>, body: &'ctx BodyAst<'base>, else_ifs: &'ctx [(ExpressionAst<'base>, BodyAst<'base>)], false_body: Option<&'ctx BodyAst<'base>>) -> Result<(), TirError> {
+        let bool_location = get_primitive_location(self.context, PrimitiveType::Bool);
+        self.emit_expression(function, condition, Some(bool_location))?;
+
+        function.push("if");
+        self.emit_block(function, body)?;
+
+        match else_ifs.split_first() {
+            Some(((else_if_condition, else_if_body), rest)) => {
+                function.push("else");
+                function.depth += 1;
+                self.emit_if(function, else_if_condition, else_if_body, rest, false_body)?;
+                function.depth -= 1;
+            },
+            None => if let Some(false_body) = false_body {
+                function.push("else");
+                self.emit_block(function, false_body)?;
+            }
+        }
+
+        function.push("end");
+        Ok(())
+    }
+
+    fn emit_block(&mut self, function: &mut FunctionBuilder, body: &'ctx BodyAst<'base>) -> Result<(), TirError> {
+        function.depth += 1;
+        function.scopes.push(HashMap::new());
+
+        for statement in body.statements.iter() {
+            self.emit_statement(function, statement)?;
+        }
+
+        function.scopes.pop();
+        function.depth -= 1;
+        Ok(())
+    }
+
+    /// Emits an expression and returns the type of the value left on the stack
+    ///
+    /// `expected` is the type required by the surrounding code. It decides the
+    /// type of numeric literals, everything else already has a fixed type.
+    fn emit_expression(&mut self, function: &mut FunctionBuilder, expression: &'ctx ExpressionAst<'base>, expected: Option<TypeLocation>) -> Result<TypeLocation, TirError> {
+        match expression {
+            ExpressionAst::Primitive { value, .. } => self.emit_literal(function, value, expected),
+            ExpressionAst::Ident(ident) => self.emit_local_get(function, ident),
+            ExpressionAst::Ref(reference) => {
+                let mut names = reference.names.iter();
+                let first = names.next().expect("Reference without name, but this is a bug");
+
+                if let Some(TypeValue::Enum(_)) = self.context.identifiers.get(&SpanKey::from(first)).and_then(|target| self.context.types.get_from_location(target.location)).map(|signature| signature.value.as_ref())
+                    && function.lookup(first.text).is_none() {
+                    return Err(unsupported("enums", &expression_position(expression)));
+                }
+
+                // `Counter.count` reads the global of a static field, the class name is not a local
+                let global = match (function.lookup(first.text), reference.names.get(1), self.context.identifiers.get(&SpanKey::from(first))) {
+                    (None, Some(field), Some(target)) => self.find_static(target.location, field.text).cloned(),
+                    _ => None,
+                };
+
+                let mut location = match global {
+                    Some((global, location)) => {
+                        names.next();
+                        function.push(format!("global.get ${global}"));
+                        location
+                    },
+                    None => self.emit_local_get(function, first)?,
+                };
+
+                for name in names {
+                    location = self.emit_field_load(function, location, name)?;
+                }
+
+                Ok(location)
+            },
+            ExpressionAst::Not(inner) => {
+                let bool_location = get_primitive_location(self.context, PrimitiveType::Bool);
+                self.emit_expression(function, inner, Some(bool_location))?;
+                function.push("i32.eqz");
+                Ok(bool_location)
+            },
+            ExpressionAst::FunctionCall(function_call) => self.emit_call(function, function_call),
+            ExpressionAst::Operation { left, operator, right } => self.emit_operation(function, expression, left, operator, right, expected),
+            ExpressionAst::Lambda(lambda) => Err(unsupported("lambda expressions", &(&lambda.span).into())),
+            ExpressionAst::Match(match_ast) => Err(unsupported("match expressions", &(&match_ast.span).into())),
+            ExpressionAst::Tuple(tuple) => Err(unsupported("tuples", &(&tuple.span).into())),
+        }
+    }
+
+    fn emit_literal(&mut self, function: &mut FunctionBuilder, value: &PrimitiveValue<'base>, expected: Option<TypeLocation>) -> Result<TypeLocation, TirError> {
+        let (instruction, location) = self.literal(value, expected);
+        function.push(instruction);
+        Ok(location)
+    }
+
+    /// Instruction that pushes a literal and the type of the pushed value
+    fn literal(&mut self, value: &PrimitiveValue<'_>, expected: Option<TypeLocation>) -> (String, TypeLocation) {
+        let (integer, float) = match value {
+            PrimitiveValue::String(text) => {
+                let address = self.intern_string(text);
+                return (format!("i32.const {address}"), get_primitive_location(self.context, PrimitiveType::String));
+            },
+            PrimitiveValue::Bool(value) => {
+                return (format!("i32.const {}", *value as i32), get_primitive_location(self.context, PrimitiveType::Bool));
+            },
+            PrimitiveValue::I8(number) => (*number as i128, *number as f64),
+            PrimitiveValue::U8(number) => (*number as i128, *number as f64),
+            PrimitiveValue::I16(number) => (*number as i128, *number as f64),
+            PrimitiveValue::U16(number) => (*number as i128, *number as f64),
+            PrimitiveValue::I32(number) => (*number as i128, *number as f64),
+            PrimitiveValue::U32(number) => (*number as i128, *number as f64),
+            PrimitiveValue::I64(number) => (*number as i128, *number as f64),
+            PrimitiveValue::U64(number) => (*number as i128, *number as f64),
+            PrimitiveValue::Float(number, _) | PrimitiveValue::Double(number, _) => (*number as i128, *number),
+        };
+
+        let location = match expected.and_then(|expected| get_primitive_type(self.context, expected).map(|primitive| (expected, primitive))) {
+            Some((expected, primitive)) if is_numeric(&primitive) => expected,
+            _ => get_primitive_location(self.context, default_literal_type(value)),
+        };
+
+        let instruction = match get_primitive_type(self.context, location) {
+            Some(PrimitiveType::I64 | PrimitiveType::U64) => format!("i64.const {}", integer as i64),
+            Some(PrimitiveType::Float) => format!("f32.const {float}"),
+            Some(PrimitiveType::Double) => format!("f64.const {float}"),
+            _ => format!("i32.const {}", integer as i32),
+        };
+
+        (instruction, location)
+    }
+
+    fn emit_local_get(&mut self, function: &mut FunctionBuilder, ident: &Span<'base>) -> Result<TypeLocation, TirError> {
+        match (function.lookup(ident.text), self.find_global(ident)) {
+            (Some(variable), _) => {
+                let location = variable.location;
+                function.push(format!("local.get ${}", variable.name));
+                Ok(location)
+            },
+            (None, Some((global, location))) => {
+                function.push(format!("global.get ${global}"));
+                Ok(*location)
+            },
+            (None, None) => Err(unsupported(format!("using `{}` as a value", ident.text), &ident.into())),
+        }
+    }
+
+    fn emit_field_load(&mut self, function: &mut FunctionBuilder, class_location: TypeLocation, name: &Span<'base>) -> Result<TypeLocation, TirError> {
+        let (offset, location) = match self.field_layout(class_location, name.text) {
+            Some(layout) => layout,
+            None => return Err(unsupported(format!("accessing `{}`", name.text), &name.into())),
+        };
+
+        let instruction = match get_primitive_type(self.context, location) {
+            Some(PrimitiveType::I8) => "i32.load8_s",
+            Some(PrimitiveType::U8 | PrimitiveType::Bool) => "i32.load8_u",
+            Some(PrimitiveType::I16) => "i32.load16_s",
+            Some(PrimitiveType::U16) => "i32.load16_u",
+            Some(PrimitiveType::I64 | PrimitiveType::U64) => "i64.load",
+            Some(PrimitiveType::Float) => "f32.load",
+            Some(PrimitiveType::Double) => "f64.load",
+            _ => "i32.load",
+        };
+
+        function.push(format!("{instruction} offset={offset}"));
+        Ok(location)
+    }
+
+    /// Stores a value into a field of the object on the stack
+    fn emit_field_store(&mut self, function: &mut FunctionBuilder, class_location: TypeLocation, name: &Span<'base>, expression: &'ctx ExpressionAst<'base>) -> Result<(), TirError> {
+        let (offset, location) = match self.field_layout(class_location, name.text) {
+            Some(layout) => layout,
+            None => return Err(unsupported(format!("assigning `{}`", name.text), &name.into())),
+        };
+
+        self.emit_expression(function, expression, Some(location))?;
+
+        let instruction = match get_primitive_type(self.context, location) {
+            Some(PrimitiveType::I8 | PrimitiveType::U8 | PrimitiveType::Bool) => "i32.store8",
+            Some(PrimitiveType::I16 | PrimitiveType::U16) => "i32.store16",
+            Some(PrimitiveType::I64 | PrimitiveType::U64) => "i64.store",
+            Some(PrimitiveType::Float) => "f32.store",
+            Some(PrimitiveType::Double) => "f64.store",
+            _ => "i32.store",
+        };
+
+        function.push(format!("{instruction} offset={offset}"));
+        Ok(())
+    }
+
+    fn emit_call(&mut self, function: &mut FunctionBuilder, function_call: &'ctx FunctionCallAst<'base>) -> Result<TypeLocation, TirError> {
+        let call_position: SpanInfo = (&function_call.call_span).into();
+        let target = match self.context.call_targets.get(&SpanKey::from(&function_call.call_span)).and_then(|location| self.context.types.get_from_location(*location)).map(|signature| signature.value.as_ref()) {
+            Some(target) => target,
+            None => return Err(unsupported("calling an unresolved function", &call_position)),
+        };
+
+        let (name, has_this) = match target {
+            TypeValue::Function(callee) => (callee.name.text, matches!(callee.ast.arguments.first(), Some(FunctionArgumentAst::This(_)))),
+            // Default functions call the other members of their interface through `this`
+            TypeValue::InterfaceFunction(callee) => (callee.name.text, callee.arguments.first().is_some_and(|argument| argument.name.text == "this")),
+            TypeValue::FunctionType(_) => return Err(unsupported("calling a function value", &call_position)),
+            TypeValue::EnumVariant(_) => return Err(unsupported("enums", &call_position)),
+            _ => return Err(unsupported("calling an unresolved function", &call_position)),
+        };
+
+        // Const calls with constant arguments were already evaluated while compiling
+        if let (TypeValue::Function(callee), Some(value)) = (target, self.context.const_values.get(&SpanKey::from(&function_call.call_span))) {
+            return self.emit_literal(function, value, Some(callee.return_type));
+        }
+
+        /* Methods receive the object they are called on as the first argument */
+        let mut receiver = None;
+        if has_this {
+            let (mut location, fields) = match &function_call.path {
+                FunctionCallType::This(paths) => match function.this {
+                    Some(location) => {
+                        function.push("local.get $this");
+                        (location, &paths[..paths.len() - 1])
+                    },
+                    None => return Err(unsupported("`this` outside of a method", &call_position)),
+                },
+                FunctionCallType::Direct(paths) if paths.len() == 1 => match function.this {
+                    Some(location) => {
+                        function.push("local.get $this");
+                        (location, &paths[..0])
+                    },
+                    None => return Err(unsupported("calling a method without an object", &call_position)),
+                },
+                FunctionCallType::Direct(paths) => (self.emit_local_get(function, &paths[0])?, &paths[1..paths.len() - 1]),
+            };
+
+            for field in fields.iter() {
+                location = self.emit_field_load(function, location, field)?;
+            }
+
+            if let Some(subclass) = self.overriding_class(location, name) {
+                return Err(unsupported(format!("virtual dispatch of `{name}` to `{subclass}`"), &call_position));
+            }
+
+            receiver = Some(location);
+        }
+
+        let (callee, path) = match (receiver.and_then(|location| self.implementation(location, name)), target) {
+            (Some(implementation), _) => implementation,
+            (None, TypeValue::Function(callee)) if self.interface_of_default(callee).is_none() => (callee.as_ref(), callee.signature_path.get_raw_path().to_string()),
+            // The object could be of any class implementing the interface
+            _ => return Err(unsupported(format!("calling the interface function `{name}` on a `{}` value", receiver.map(|location| self.type_name(location)).unwrap_or_default()), &call_position)),
+        };
+
+        let arguments = match has_this {
+            true => &callee.arguments[1..],
+            false => &callee.arguments[..],
+        };
+
+        for (argument, expression) in arguments.iter().zip(function_call.arguments.iter()) {
+            self.emit_expression(function, expression, Some(argument.field_type))?;
+        }
+
+        function.push(format!("call ${path}"));
+        Ok(callee.return_type)
+    }
+
+    fn emit_operation(&mut self, function: &mut FunctionBuilder, expression: &'ctx ExpressionAst<'base>, left: &'ctx ExpressionAst<'base>, operator: &ExpressionOperatorType, right: &'ctx ExpressionAst<'base>, expected: Option<TypeLocation>) -> Result<TypeLocation, TirError> {
+        let bool_location = get_primitive_location(self.context, PrimitiveType::Bool);
+
+        /* Logical operators short-circuit */
+        if matches!(operator, ExpressionOperatorType::And | ExpressionOperatorType::Or) {
+            self.emit_expression(function, left, Some(bool_location))?;
+            function.push("if (result i32)");
+            function.depth += 1;
+
+            match operator {
+                ExpressionOperatorType::And => self.emit_expression(function, right, Some(bool_location))?,
+                _ => {
+                    function.push("i32.const 1");
+                    bool_location
+                }
+            };
+
+            function.depth -= 1;
+            function.push("else");
+            function.depth += 1;
+
+            match operator {
+                ExpressionOperatorType::And => {
+                    function.push("i32.const 0");
+                    bool_location
+                },
+                _ => self.emit_expression(function, right, Some(bool_location))?,
+            };
+
+            function.depth -= 1;
+            function.push("end");
+            return Ok(bool_location);
+        }
+
+        let is_comparison = is_bool_operator(operator);
+
+        // Literals take the type of the other operand, or the expected type of the whole operation
+        let operand_location = operand_type(self.context, left, operator, right, expected, &|name| function.lookup(name).map(|variable| variable.location));
+
+        self.emit_expression(function, left, Some(operand_location))?;
+        self.emit_expression(function, right, Some(operand_location))?;
+
+        let position = expression_position(expression);
+        let primitive = match get_primitive_type(self.context, operand_location) {
+            Some(primitive) => primitive,
+            None => return Err(unsupported(format!("`{operator}` on objects"), &position)),
+        };
+
+        match (&primitive, operator) {
+            (PrimitiveType::String, ExpressionOperatorType::Add) => function.push("call $timu_string_concat"),
+            (PrimitiveType::String, ExpressionOperatorType::Equal) => function.push("call $timu_string_equal"),
+            (PrimitiveType::String, ExpressionOperatorType::NotEqual) => {
+                function.push("call $timu_string_equal");
+                function.push("i32.eqz");
+            },
+            _ => match binary_instruction(operator, &primitive) {
+                Some(instruction) => function.push(instruction),
+                None => return Err(unsupported(format!("`{operator}` on `{}`", self.type_name(operand_location)), &position)),
+            }
+        };
+
+        if is_comparison {
+            return Ok(bool_location);
+        }
+
+        // Keep narrow integers inside their range
+        match primitive {
+            PrimitiveType::I8 => function.push("i32.extend8_s"),
+            PrimitiveType::I16 => function.push("i32.extend16_s"),
+            PrimitiveType::U8 => {
+                function.push("i32.const 255");
+                function.push("i32.and");
+            },
+            PrimitiveType::U16 => {
+                function.push("i32.const 65535");
+                function.push("i32.and");
+            },
+            _ => (),
+        };
+
+        Ok(operand_location)
+    }
+
+    /// Finds a subclass of the receiver class that overrides the method
+    ///
+    /// The object can be of that subclass, so a static call could run the wrong function.
+    fn overriding_class(&self, receiver: TypeLocation, method: &str) -> Option<&'ctx str> {
+        self.context.types.iter().find_map(|(_, location, signature)| match signature.value.as_ref() {
+            TypeValue::Class(class) if location != receiver
+                && self.is_subclass(class.base, receiver)
+                && class.fields.get(method).is_some_and(|field| matches!(self.context.types.get_from_location(field.location).map(|signature| signature.value.as_ref()), Some(TypeValue::Function(_)))) => Some(class.name.text),
+            _ => None,
+        })
+    }
+
+    /// Finds the function a method call on an object of the class runs, and its function name
+    fn implementation(&self, class_location: TypeLocation, method: &str) -> Option<(&'ctx FunctionDefinition<'base>, String)> {
+        let mut class = Some(class_location);
+
+        while let Some(location) = class {
+            let Some(TypeValue::Class(definition)) = self.context.types.get_from_location(location).map(|signature| signature.value.as_ref()) else {
+                return None;
+            };
+
+            if let Some(TypeValue::Function(function)) = definition.fields.get(method).and_then(|field| self.context.types.get_from_location(field.location)).map(|signature| signature.value.as_ref()) {
+                let path = match self.defaults.get(&(location, method.to_string())) {
+                    Some(copy) => copy.clone(),
+                    None => function.signature_path.get_raw_path().to_string(),
+                };
+                return Some((function, path));
+            }
+
+            class = definition.base;
+        }
+
+        None
+    }
+
+    /// Checks if `base` is one of the classes starting from `class` up the inheritance chain
+    fn is_subclass(&self, mut class: Option<TypeLocation>, base: TypeLocation) -> bool {
+        while let Some(location) = class {
+            if location == base {
+                return true;
+            }
+
+            class = match self.context.types.get_from_location(location).map(|signature| signature.value.as_ref()) {
+                Some(TypeValue::Class(parent)) => parent.base,
+                _ => None,
+            };
+        }
+
+        false
+    }
+
+    /// Returns the offset and type of a class field
+    fn field_layout(&self, class_location: TypeLocation, field_name: &str) -> Option<(u32, TypeLocation)> {
+        let mut offset: u32 = 0;
+        self.find_field(class_location, field_name, &mut offset)
+    }
+
+    /// Lays out the fields of the class after the fields of its base classes
+    fn find_field(&self, class_location: TypeLocation, field_name: &str, offset: &mut u32) -> Option<(u32, TypeLocation)> {
+        let class = match self.context.types.get_from_location(class_location).map(|signature| signature.value.as_ref()) {
+            Some(TypeValue::Class(class)) => class,
+            _ => return None,
+        };
+
+        if let Some(base) = class.base
+            && let Some(layout) = self.find_field(base, field_name, offset) {
+            return Some(layout);
+        }
+
+        for (name, field) in class.fields.iter() {
+            // Methods and static fields are stored next to the fields but take no space in the object
+            if class.static_members.contains(name) || matches!(self.context.types.get_from_location(field.location).map(|signature| signature.value.as_ref()), Some(TypeValue::Function(_))) {
+                continue;
+            }
+
+            let size = self.field_size(field.location);
+            *offset = offset.div_ceil(size) * size;
+
+            if name.as_ref() == field_name {
+                return Some((*offset, field.location));
+            }
+
+            *offset += size;
+        }
+
+        None
+    }
+
+    fn field_size(&self, location: TypeLocation) -> u32 {
+        match get_primitive_type(self.context, location) {
+            Some(PrimitiveType::I8 | PrimitiveType::U8 | PrimitiveType::Bool) => 1,
+            Some(PrimitiveType::I16 | PrimitiveType::U16) => 2,
+            Some(PrimitiveType::I64 | PrimitiveType::U64 | PrimitiveType::Double) => 8,
+            _ => 4,
+        }
+    }
+
+    /// Maps a Timu type to its WebAssembly value type, `void` has no value
+    fn value_type(&self, location: TypeLocation, position: &SpanInfo) -> Result<Option<ValueType>, TirError> {
+        match self.context.types.get_from_location(location).map(|signature| signature.value.as_ref()) {
+            Some(TypeValue::PrimitiveType(primitive)) => Ok(match primitive {
+                PrimitiveType::Void => None,
+                PrimitiveType::I64 | PrimitiveType::U64 => Some(ValueType::I64),
+                PrimitiveType::Float => Some(ValueType::F32),
+                PrimitiveType::Double => Some(ValueType::F64),
+                _ => Some(ValueType::I32),
+            }),
+            Some(TypeValue::Class(_) | TypeValue::Interface(_)) => Ok(Some(ValueType::I32)),
+            _ => Err(unsupported(format!("values of type `{}`", self.type_name(location)), position)),
+        }
+    }
+
+    fn required_value_type(&self, location: TypeLocation, position: &SpanInfo) -> Result<ValueType, TirError> {
+        match self.value_type(location, position)? {
+            Some(value_type) => Ok(value_type),
+            None => Err(unsupported("a `void` value", position)),
+        }
+    }
+
+    fn type_name(&self, location: TypeLocation) -> Cow<'ctx, str> {
+        match self.context.types.get_from_location(location) {
+            Some(signature) => signature.value.get_name(),
+            None => Cow::Borrowed("<unknown>"),
+        }
+    }
+
+    /// Returns the address of a string literal, adding a data segment for new ones
+    fn intern_string(&mut self, text: &str) -> u32 {
+        if let Some(address) = self.strings.get(text) {
+            return *address;
+        }
+
+        let address = self.data_end;
+        self.strings.insert(text.to_string(), address);
+        self.data_end = (address + 4 + text.len() as u32).div_ceil(4) * 4;
+        address
+    }
+}
+
+fn unsupported(message: impl Into<String>, position: &SpanInfo) -> TirError {
+    BackendError::unsupported(BACKEND_NAME, message, position.position.clone(), &position.file)
+}
+
+fn binary_instruction(operator: &ExpressionOperatorType, primitive: &PrimitiveType) -> Option<String> {
+    let value_type = match primitive {
+        PrimitiveType::I64 | PrimitiveType::U64 => "i64",
+        PrimitiveType::Float => "f32",
+        PrimitiveType::Double => "f64",
+        PrimitiveType::Bool => "i32",
+        primitive if is_integer(primitive) => "i32",
+        _ => return None,
+    };
+
+    let is_float = matches!(primitive, PrimitiveType::Float | PrimitiveType::Double);
+    let is_signed = matches!(primitive, PrimitiveType::I8 | PrimitiveType::I16 | PrimitiveType::I32 | PrimitiveType::I64);
+    let sign = if is_signed { "_s" } else { "_u" };
+
+    let instruction = match operator {
+        ExpressionOperatorType::Add => "add".to_string(),
+        ExpressionOperatorType::Sub => "sub".to_string(),
+        ExpressionOperatorType::Mul => "mul".to_string(),
+        ExpressionOperatorType::Div if is_float => "div".to_string(),
+        ExpressionOperatorType::Div => format!("div{sign}"),
+        ExpressionOperatorType::Mod if is_float => return None,
+        ExpressionOperatorType::Mod => format!("rem{sign}"),
+        ExpressionOperatorType::Equal => "eq".to_string(),
+        ExpressionOperatorType::NotEqual => "ne".to_string(),
+        ExpressionOperatorType::LessThan if is_float => "lt".to_string(),
+        ExpressionOperatorType::LessThan => format!("lt{sign}"),
+        ExpressionOperatorType::LessEqualThan if is_float => "le".to_string(),
+        ExpressionOperatorType::LessEqualThan => format!("le{sign}"),
+        ExpressionOperatorType::GreaterThan if is_float => "gt".to_string(),
+        ExpressionOperatorType::GreaterThan => format!("gt{sign}"),
+        ExpressionOperatorType::GreaterEqualThan if is_float => "ge".to_string(),
+        ExpressionOperatorType::GreaterEqualThan => format!("ge{sign}"),
+        ExpressionOperatorType::LogicalAnd if !is_float => "and".to_string(),
+        ExpressionOperatorType::LogicalOr if !is_float => "or".to_string(),
+        ExpressionOperatorType::Xor if !is_float => "xor".to_string(),
+        ExpressionOperatorType::BitwiseShiftLeft if !is_float => "shl".to_string(),
+        ExpressionOperatorType::BitwiseShiftRight if !is_float => format!("shr{sign}"),
+        _ => return None,
+    };
+
+    Some(format!("{value_type}.{instruction}"))
+}
+
+fn escape_bytes(bytes: &[u8]) -> String {
+    let mut escaped = String::with_capacity(bytes.len());
+    for byte in bytes.iter() {
+        match byte {
+            b'"' | b'\\' => escaped.push_str(&format!("\\{byte:02x}")),
+            0x20..=0x7e => escaped.push(*byte as char),
+            _ => escaped.push_str(&format!("\\{byte:02x}")),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use wasmi::{Caller, Engine, Extern, Instance, Linker, Module, Store};
+
+    use crate::{file::SourceFile, nom_tools::State, process_ast, process_code, tir::TirError};
+
+    fn compile(code: &str) -> Result<String, TirError> {
+        let state = State::new(SourceFile::new(vec!["main".into()], code.to_string()));
+        let ast = process_code(&state)?;
+        let context = process_ast(vec![ast.into()])?;
+        super::generate(&context)
+    }
+
+    fn instantiate(code: &str) -> (Store<()>, Instance) {
+        let wat = compile(code).unwrap();
+        let wasm = wat::parse_str(&wat).unwrap_or_else(|error| panic!("{error}\n{wat}"));
+
+        let engine = Engine::default();
+        let module = Module::new(&engine, &wasm[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let instance = Linker::<()>::new(&engine).instantiate(&mut store, &module).unwrap().start(&mut store).unwrap();
+        (store, instance)
+    }
+
+    fn read_string(store: &Store<()>, instance: &Instance, address: i32) -> String {
+        let memory = instance.get_memory(store, "memory").unwrap();
+        let data = memory.data(store);
+        let address = address as usize;
+        let length = u32::from_le_bytes(data[address..address + 4].try_into().unwrap()) as usize;
+        String::from_utf8(data[address + 4..address + 4 + length].to_vec()).unwrap()
+    }
+
+    #[test]
+    fn empty_program() {
+        let wat = compile("").unwrap();
+        wat::parse_str(wat).unwrap();
+    }
+
+    #[test]
+    fn arithmetic() {
+        let (mut store, instance) = instantiate("pub func add(a: i32, b: i32): i32 { return a + b * 2; }");
+        let add = instance.get_typed_func::<(i32, i32), i32>(&store, "main.add").unwrap();
+        assert_eq!(add.call(&mut store, (3, 4)).unwrap(), 11);
+    }
+
+    #[test]
+    fn numeric_types() {
+        let (mut store, instance) = instantiate(r#"
+pub func wide(a: i64): i64 { return a * 3; }
+pub func single(a: float): float { return a / 2; }
+pub func double_value(a: double): double { return a - 0.5; }
+pub func unsigned_div(a: u32, b: u32): u32 { return a / b; }
+pub func narrow(a: i8): i8 { return a + 100; }
+"#);
+        let wide = instance.get_typed_func::<i64, i64>(&store, "main.wide").unwrap();
+        assert_eq!(wide.call(&mut store, 5_000_000_000).unwrap(), 15_000_000_000);
+
+        let single = instance.get_typed_func::<f32, f32>(&store, "main.single").unwrap();
+        assert_eq!(single.call(&mut store, 3.0).unwrap(), 1.5);
+
+        let double_value = instance.get_typed_func::<f64, f64>(&store, "main.double_value").unwrap();
+        assert_eq!(double_value.call(&mut store, 2.0).unwrap(), 1.5);
+
+        let unsigned_div = instance.get_typed_func::<i32, i32>(&store, "main.unsigned_div");
+        assert!(unsigned_div.is_err());
+        let unsigned_div = instance.get_typed_func::<(i32, i32), i32>(&store, "main.unsigned_div").unwrap();
+        assert_eq!(unsigned_div.call(&mut store, (-2, 2)).unwrap(), i32::MAX);
+
+        let narrow = instance.get_typed_func::<i32, i32>(&store, "main.narrow").unwrap();
+        assert_eq!(narrow.call(&mut store, 100).unwrap(), -56);
+    }
+
+    #[test]
+    fn variables_and_conditions() {
+        let (mut store, instance) = instantiate(r#"
+pub func classify(a: i32): i32 {
+    var result = 0;
+    if (a > 10) {
+        result = 1;
+    } else if (a > 5 && a != 7) {
+        result = 2;
+    } else {
+        const negative = a < 0;
+        if (negative || a == 7) {
+            return 3;
+        }
+    }
+    return result;
+}
+"#);
+        let classify = instance.get_typed_func::<i32, i32>(&store, "main.classify").unwrap();
+        assert_eq!(classify.call(&mut store, 20).unwrap(), 1);
+        assert_eq!(classify.call(&mut store, 6).unwrap(), 2);
+        assert_eq!(classify.call(&mut store, 7).unwrap(), 3);
+        assert_eq!(classify.call(&mut store, -1).unwrap(), 3);
+        assert_eq!(classify.call(&mut store, 1).unwrap(), 0);
+    }
+
+    #[test]
+    fn function_calls() {
+        let (mut store, instance) = instantiate(r#"
+func square(a: i32): i32 { return a * a; }
+func log(a: i32): void { }
+pub func sum_of_squares(a: i32, b: i32): i32 {
+    log(a);
+    square(b);
+    return square(a) + square(b);
+}
+"#);
+        let sum_of_squares = instance.get_typed_func::<(i32, i32), i32>(&store, "main.sum_of_squares").unwrap();
+        assert_eq!(sum_of_squares.call(&mut store, (3, 4)).unwrap(), 25);
+
+        // Private functions are not exported
+        assert!(instance.get_func(&store, "main.square").is_none());
+    }
+
+    #[test]
+    fn const_calls() {
+        let code = r#"
+const func square(a: u8): u8 { return a * a; }
+pub func test(x: u8): u8 {
+    const size = square(20);
+    return size + square(x);
+}
+"#;
+        let wat = compile(code).unwrap();
+        let test = &wat[wat.find("(func $main.test").unwrap()..];
+        assert!(test.contains("i32.const 144"), "{test}");
+        assert_eq!(test.matches("call $main.square").count(), 1, "{test}");
+
+        let (mut store, instance) = instantiate(code);
+        let function = instance.get_typed_func::<i32, i32>(&store, "main.test").unwrap();
+        assert_eq!(function.call(&mut store, 3).unwrap(), 153);
+    }
+
+    #[test]
+    fn strings() {
+        let (mut store, instance) = instantiate(r#"
+pub func greet(name: string): string { return "Hello, " + name + "!"; }
+pub func is_timu(name: string): bool { return name == "timu"; }
+pub func world(): string { return "world"; }
+"#);
+        let world = instance.get_typed_func::<(), i32>(&store, "main.world").unwrap();
+        let world_address = world.call(&mut store, ()).unwrap();
+        assert_eq!(read_string(&store, &instance, world_address), "world");
+
+        let greet = instance.get_typed_func::<i32, i32>(&store, "main.greet").unwrap();
+        let greeting = greet.call(&mut store, world_address).unwrap();
+        assert_eq!(read_string(&store, &instance, greeting), "Hello, world!");
+
+        let is_timu = instance.get_typed_func::<i32, i32>(&store, "main.is_timu").unwrap();
+        assert_eq!(is_timu.call(&mut store, world_address).unwrap(), 0);
+    }
+
+    #[test]
+    fn native_functions() {
+        let wat = compile(r#"
+use std.io.print;
+use std.io.println;
+use std.math.abs;
+use std.math.max;
+use std.math.min;
+use std.string.len;
+pub func test(): i32 {
+    print("Hello, ");
+    println("timu");
+    return max(len("timu"), 3) + abs(0 - 2) + min(1, 5);
+}
+"#).unwrap();
+        assert!(wat.contains(r#"(import "std" "std.io.println" (func $std.io.println (param i32)))"#), "{wat}");
+        assert!(wat.contains(r#"(import "std" "std.math.max" (func $std.math.max (param i32) (param i32) (result i32)))"#), "{wat}");
+
+        let engine = Engine::default();
+        let module = Module::new(&engine, &wat::parse_str(&wat).unwrap()[..]).unwrap();
+        let mut store = Store::new(&engine, String::new());
+        let mut linker = Linker::<String>::new(&engine);
+
+        fn text(caller: &Caller<'_, String>, address: i32) -> String {
+            let data = caller.get_export("memory").and_then(Extern::into_memory).unwrap().data(caller);
+            let address = address as usize;
+            let length = u32::from_le_bytes(data[address..address + 4].try_into().unwrap()) as usize;
+            String::from_utf8(data[address + 4..address + 4 + length].to_vec()).unwrap()
+        }
+
+        linker.func_wrap("std", "std.io.print", |mut caller: Caller<'_, String>, address: i32| {
+            let text = text(&caller, address);
+            caller.data_mut().push_str(&text);
+        }).unwrap();
+        linker.func_wrap("std", "std.io.println", |mut caller: Caller<'_, String>, address: i32| {
+            let text = text(&caller, address);
+            caller.data_mut().push_str(&format!("{text}\n"));
+        }).unwrap();
+        linker.func_wrap("std", "std.string.len", |caller: Caller<'_, String>, address: i32| text(&caller, address).len() as i32).unwrap();
+        linker.func_wrap("std", "std.math.abs", |value: i32| value.wrapping_abs()).unwrap();
+        linker.func_wrap("std", "std.math.min", |left: i32, right: i32| left.min(right)).unwrap();
+        linker.func_wrap("std", "std.math.max", |left: i32, right: i32| left.max(right)).unwrap();
+
+        let instance = linker.instantiate(&mut store, &module).unwrap().start(&mut store).unwrap();
+        let test = instance.get_typed_func::<(), i32>(&store, "main.test").unwrap();
+        assert_eq!(test.call(&mut store, ()).unwrap(), 7);
+        assert_eq!(store.data(), "Hello, timu\n");
+    }
+
+    #[test]
+    fn extern_functions() {
+        let wat = compile("extern func add_numbers(a: i64, b: i64): i64; pub func test(): i64 { return add_numbers(40, 2); }").unwrap();
+        assert!(wat.contains(r#"(import "env" "add_numbers" (func $main.add_numbers (param i64) (param i64) (result i64)))"#), "{wat}");
+
+        let engine = Engine::default();
+        let module = Module::new(&engine, &wat::parse_str(&wat).unwrap()[..]).unwrap();
+        let mut store = Store::new(&engine, ());
+        let mut linker = Linker::<()>::new(&engine);
+        linker.func_wrap("env", "add_numbers", |a: i64, b: i64| a + b).unwrap();
+
+        let instance = linker.instantiate(&mut store, &module).unwrap().start(&mut store).unwrap();
+        let test = instance.get_typed_func::<(), i64>(&store, "main.test").unwrap();
+        assert_eq!(test.call(&mut store, ()).unwrap(), 42);
+    }
+
+    #[test]
+    fn unused_native_functions_are_not_imported() {
+        let wat = compile("pub func test(): i32 { return 1; }").unwrap();
+        assert!(!wat.contains("(import"), "{wat}");
+    }
+
+    #[test]
+    fn string_literals_are_data_segments() {
+        let wat = compile(r#"func a(): string { return "hi"; } func b(): string { return "hi"; }"#).unwrap();
+        assert_eq!(wat.matches("(data ").count(), 1);
+        assert!(wat.contains(r#"(data (i32.const 8) "\02\00\00\00hi")"#), "{wat}");
+    }
+
+    #[test]
+    fn classes_in_linear_memory() {
+        let (mut store, instance) = instantiate(r#"
+class Point {
+    x: i32;
+    flag: bool;
+    y: i64;
+    func get_y(this): i64 { return ref this.y; }
+}
+
+class Line {
+    start: Point;
+    end: Point;
+    func length(this): i64 { return this.end.get_y() - this.start.get_y(); }
+}
+
+pub func length(line: Line): i64 { return line.length(); }
+"#);
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "timu_alloc").unwrap();
+        let start = alloc.call(&mut store, 16).unwrap();
+        let end = alloc.call(&mut store, 16).unwrap();
+        let line = alloc.call(&mut store, 8).unwrap();
+
+        let memory = instance.get_memory(&store, "memory").unwrap();
+        // `y` is aligned to 8 bytes after `x` and `flag`
+        memory.write(&mut store, start as usize + 8, &10i64.to_le_bytes()).unwrap();
+        memory.write(&mut store, end as usize + 8, &42i64.to_le_bytes()).unwrap();
+        memory.write(&mut store, line as usize, &start.to_le_bytes()).unwrap();
+        memory.write(&mut store, line as usize + 4, &end.to_le_bytes()).unwrap();
+
+        let length = instance.get_typed_func::<i32, i64>(&store, "main.length").unwrap();
+        assert_eq!(length.call(&mut store, line).unwrap(), 32);
+    }
+
+    #[test]
+    fn inherited_fields_and_methods() {
+        let (mut store, instance) = instantiate(r#"
+class Base {
+    x: i32;
+    flag: bool;
+    func get_x(this): i32 { return ref this.x; }
+}
+
+class Child: Base {
+    y: i32;
+    func sum(this): i32 { return this.get_x() + ref this.y; }
+}
+
+pub func sum(child: Child): i32 { return child.sum() + child.get_x(); }
+"#);
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "timu_alloc").unwrap();
+        let child = alloc.call(&mut store, 12).unwrap();
+
+        let memory = instance.get_memory(&store, "memory").unwrap();
+        // `y` follows the fields of `Base`
+        memory.write(&mut store, child as usize, &10i32.to_le_bytes()).unwrap();
+        memory.write(&mut store, child as usize + 8, &22i32.to_le_bytes()).unwrap();
+
+        let sum = instance.get_typed_func::<i32, i32>(&store, "main.sum").unwrap();
+        assert_eq!(sum.call(&mut store, child).unwrap(), 42);
+    }
+
+    #[test]
+    fn overridden_methods_are_not_dispatched_statically() {
+        let code = r#"
+class Base {
+    func area(this): i32 { return 1; }
+    func sides(this): i32 { return 4; }
+}
+
+class Circle: Base {
+    func area(this): i32 { return 3; }
+}
+
+pub func area(circle: Circle): i32 { return circle.area() + circle.sides(); }
+"#;
+        let (mut store, instance) = instantiate(code);
+        let area = instance.get_typed_func::<i32, i32>(&store, "main.area").unwrap();
+        assert_eq!(area.call(&mut store, 8).unwrap(), 7);
+
+        let error = compile(&format!("{code}\npub func base_area(shape: Base): i32 {{ return shape.sides() + shape.area(); }}")).unwrap_err();
+        assert_eq!(error.to_string(), "the wat backend does not support virtual dispatch of `area` to `Circle`");
+
+        let error = compile("class Base { func area(this): i32 { return 1; } func twice(this): i32 { return this.area() * 2; } }\nclass Circle: Base { func area(this): i32 { return 3; } }").unwrap_err();
+        assert_eq!(error.to_string(), "the wat backend does not support virtual dispatch of `area` to `Circle`");
+    }
+
+    #[test]
+    fn static_members() {
+        let (mut store, instance) = instantiate(r#"
+class Point {
+    static origin: i32;
+    x: i32;
+    func twice(value: i32): i32 { return value * 2; }
+    func get(this): i32 { return ref this.x + Point.twice(1) + ref Point.origin; }
+}
+
+pub func get(point: Point): i32 { return point.get(); }
+"#);
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "timu_alloc").unwrap();
+        let point = alloc.call(&mut store, 4).unwrap();
+
+        let memory = instance.get_memory(&store, "memory").unwrap();
+        // The static field takes no space in the object
+        memory.write(&mut store, point as usize, &40i32.to_le_bytes()).unwrap();
+
+        let get = instance.get_typed_func::<i32, i32>(&store, "main.get").unwrap();
+        assert_eq!(get.call(&mut store, point).unwrap(), 42);
+    }
+
+    #[test]
+    fn field_assignments() {
+        let (mut store, instance) = instantiate(r#"
+class Counter {
+    static count: i32;
+    last: i8;
+    func add(this, amount: i8): void {
+        count = count + 1;
+        this.last = amount;
+    }
+    func reset(): void { Counter.count = 0; }
+}
+
+pub func run(counter: Counter): i32 {
+    Counter.count = 40;
+    counter.add(7);
+    counter.add(-3);
+    return ref Counter.count;
+}
+pub func last(counter: Counter): i8 { return ref counter.last; }
+pub func reset(): i32 {
+    Counter.reset();
+    return ref Counter.count;
+}
+"#);
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "timu_alloc").unwrap();
+        let counter = alloc.call(&mut store, 4).unwrap();
+
+        let run = instance.get_typed_func::<i32, i32>(&store, "main.run").unwrap();
+        assert_eq!(run.call(&mut store, counter).unwrap(), 42);
+
+        let last = instance.get_typed_func::<i32, i32>(&store, "main.last").unwrap();
+        assert_eq!(last.call(&mut store, counter).unwrap(), -3);
+
+        let reset = instance.get_typed_func::<(), i32>(&store, "main.reset").unwrap();
+        assert_eq!(reset.call(&mut store, ()).unwrap(), 0);
+    }
+
+    #[test]
+    fn module_variables() {
+        let code = r#"
+const func square(a: i32): i32 { return a * a; }
+const SIDE: i32 = 4;
+const AREA = square(SIDE) + 1;
+const NAME: string = "timu";
+var counter: i64 = 10;
+
+pub func next(): i64 { counter = counter + 1; return counter; }
+pub func area(): i32 { return AREA * 2; }
+pub func name(): string { return NAME; }
+"#;
+        let wat = compile(code).unwrap();
+        assert!(wat.contains("(global $main.AREA i32 (i32.const 17))"), "{wat}");
+        assert!(wat.contains("(global $main.counter (mut i64) (i64.const 10))"), "{wat}");
+
+        let (mut store, instance) = instantiate(code);
+        let next = instance.get_typed_func::<(), i64>(&store, "main.next").unwrap();
+        assert_eq!(next.call(&mut store, ()).unwrap(), 11);
+        assert_eq!(next.call(&mut store, ()).unwrap(), 12);
+
+        let area = instance.get_typed_func::<(), i32>(&store, "main.area").unwrap();
+        assert_eq!(area.call(&mut store, ()).unwrap(), 34);
+
+        let name = instance.get_typed_func::<(), i32>(&store, "main.name").unwrap();
+        let address = name.call(&mut store, ()).unwrap();
+        assert_eq!(read_string(&store, &instance, address), "timu");
+    }
+
+    #[test]
+    fn interface_default_functions() {
+        let (mut store, instance) = instantiate(r#"
+interface Shape {
+    func area(this): i32;
+    func sides(this): i32 { return 4; }
+    func corners(this): i32 { return 4; }
+    func describe(this): i32 { return this.area() * 10 + this.sides(); }
+}
+
+class Square {}
+class Triangle {}
+
+extend Square: Shape {
+    func area(this): i32 { return 9; }
+}
+extend Triangle: Shape {
+    func area(this): i32 { return 6; }
+    func sides(this): i32 { return 3; }
+}
+
+pub func count(square: Square, triangle: Triangle): i32 { return square.sides() * 100 + triangle.sides() * 10 + triangle.corners(); }
+pub func describe(square: Square, triangle: Triangle): i32 { return square.describe() * 100 + triangle.describe(); }
+"#);
+        let count = instance.get_typed_func::<(i32, i32), i32>(&store, "main.count").unwrap();
+        assert_eq!(count.call(&mut store, (8, 8)).unwrap(), 434);
+
+        let describe = instance.get_typed_func::<(i32, i32), i32>(&store, "main.describe").unwrap();
+        assert_eq!(describe.call(&mut store, (8, 8)).unwrap(), 9463);
+    }
+
+    #[test]
+    fn allocator_grows_memory() {
+        let (mut store, instance) = instantiate("");
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "timu_alloc").unwrap();
+        let first = alloc.call(&mut store, 100_000).unwrap();
+        let second = alloc.call(&mut store, 4).unwrap();
+        assert!(second >= first + 100_000);
+
+        let memory = instance.get_memory(&store, "memory").unwrap();
+        assert!(memory.data(&store).len() >= second as usize + 4);
+    }
+
+    #[test]
+    fn float_remainder_is_unsupported() {
+        compile("func test(a: double): double { return a % 2; }").unwrap_err();
+    }
+}
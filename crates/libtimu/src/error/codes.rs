@@ -0,0 +1,158 @@
+//! Registry of the error codes.
+//!
+//! Every error of the compiler has a stable code like `T0001`, set with
+//! `#[diagnostic(code("T0001"))]` and shown in the reports. A code never
+//! changes its meaning and a removed error keeps its code unused. The long
+//! explanation of a code, with an example of the wrong code and the fix, is in
+//! `src/error/explanations/<code>.md` and is printed by `timuc explain <code>`.
+
+/// An error code and its explanation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCode {
+    pub code: &'static str,
+    /// Markdown explanation, the first line is the title
+    pub explanation: &'static str,
+}
+
+impl ErrorCode {
+    /// Short title of the error
+    pub fn title(&self) -> &'static str {
+        let line = self.explanation.lines().next().unwrap_or_default();
+        line.trim_start_matches('#').trim().strip_prefix(self.code).map_or(line, |title| title.trim_start_matches(':').trim())
+    }
+}
+
+macro_rules! error_codes {
+    ($($code:literal),* $(,)?) => {
+        /// Every error code in ascending order
+        pub static ERROR_CODES: &[ErrorCode] = &[
+            $(ErrorCode { code: $code, explanation: include_str!(concat!("explanations/", $code, ".md")) }),*
+        ];
+    };
+}
+
+error_codes!(
+    "T0000", "T0001", "T0002", "T0003", "T0004", "T0005", "T0006", "T0007", "T0008", "T0009",
+    "T0010", "T0011", "T0012", "T0013", "T0014", "T0015", "T0016", "T0017", "T0018", "T0019",
+    "T0020", "T0021", "T0022", "T0023", "T0024", "T0025", "T0026", "T0027", "T0028", "T0029",
+    "T0030", "T0031", "T0032", "T0033", "T0034", "T0035", "T0036", "T0037", "T0038", "T0039",
+    "T0040", "T0041", "T0042", "T0043", "T0044", "T0045", "T0046", "T0047", "T0048", "T0049",
+    "T0050", "T0051", "T0052", "T0053", "T0054", "T0055", "T0056", "T0057",
+);
+
+/// Finds an error code, the letter may be written in lowercase
+pub fn find(code: &str) -> Option<&'static ErrorCode> {
+    ERROR_CODES.iter().find(|error_code| error_code.code.eq_ignore_ascii_case(code.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use super::{find, ERROR_CODES};
+
+    /// Error types that only describe a part of another error and have no code of their own
+    static PARTS: &[&str] = &["ErrorCollection", "PrivateItemInfo", "ImportAttemptInfo", "TypeWithSpan", "ConstCallFrame", "OverriddenItemInfo"];
+
+    fn sources(directory: &Path, files: &mut Vec<(String, String)>) {
+        for entry in fs::read_dir(directory).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                sources(&path, files);
+            } else if path.extension().is_some_and(|extension| extension == "rs") {
+                files.push((path.display().to_string(), fs::read_to_string(&path).unwrap()));
+            }
+        }
+    }
+
+    /// Name of the item an attribute block belongs to, with the line index of the item
+    fn item(lines: &[&str], start: usize) -> (usize, String) {
+        let index = (start..lines.len()).find(|index| lines[*index].starts_with("pub struct") || lines[*index].starts_with("pub enum")).unwrap();
+        let name = lines[index].split_whitespace().nth(2).unwrap().trim_end_matches(['{', '(', ';']).to_string();
+        (index, name)
+    }
+
+    /// Every error type and enum variant without a code, and every code used in the sources
+    fn scan() -> (Vec<String>, Vec<String>) {
+        let mut files = Vec::new();
+        sources(&Path::new(env!("CARGO_MANIFEST_DIR")).join("src"), &mut files);
+
+        let mut missing = Vec::new();
+        let mut codes = Vec::new();
+        for (path, source) in files.iter() {
+            let lines = source.lines().collect::<Vec<_>>();
+            for (start, line) in lines.iter().enumerate() {
+                if let Some(code) = line.split("code(\"").nth(1) {
+                    codes.push(code.split('"').next().unwrap().to_string());
+                }
+
+                if !(line.starts_with("#[derive(") && line.contains("TimuError")) {
+                    continue;
+                }
+
+                let (index, name) = item(&lines, start);
+                if PARTS.contains(&name.as_str()) {
+                    continue;
+                }
+
+                if lines[index].starts_with("pub struct") {
+                    if !lines[start..index].iter().any(|line| line.contains("code(")) {
+                        missing.push(format!("{path}: {name}"));
+                    }
+                    continue;
+                }
+
+                let mut attributes = Vec::new();
+                for line in lines[index + 1..].iter().take_while(|line| !line.starts_with('}')) {
+                    let line = line.trim();
+                    if line.starts_with("#[") {
+                        attributes.push(line);
+                    } else if !line.is_empty() && !line.starts_with("//") {
+                        if !attributes.iter().any(|attribute| attribute.contains("code(") || attribute.contains("transparent")) {
+                            missing.push(format!("{path}: {name}::{line}"));
+                        }
+                        attributes.clear();
+                    }
+                }
+            }
+        }
+
+        (missing, codes)
+    }
+
+    #[test]
+    fn every_error_has_a_code() {
+        let (missing, _) = scan();
+        assert!(missing.is_empty(), "errors without a code:\n{}", missing.join("\n"));
+    }
+
+    #[test]
+    fn every_code_is_registered() {
+        let (_, codes) = scan();
+        for code in codes.iter().filter(|code| code.starts_with('T')) {
+            assert!(find(code).is_some(), "{code} has no explanation");
+        }
+
+        for error_code in ERROR_CODES.iter() {
+            assert!(codes.iter().any(|code| code == error_code.code), "{} is not used by any error", error_code.code);
+        }
+    }
+
+    #[test]
+    fn explanations() {
+        assert!(ERROR_CODES.windows(2).all(|codes| codes[0].code < codes[1].code));
+        for error_code in ERROR_CODES.iter() {
+            assert!(error_code.explanation.starts_with(&format!("# {}: ", error_code.code)), "{}", error_code.code);
+            assert!(!error_code.title().is_empty());
+
+            // An internal compiler error has no wrong code to show
+            if error_code.code != "T0000" {
+                assert!(error_code.explanation.contains("Erroneous code example:\n\n```timu\n"), "{} has no erroneous code example", error_code.code);
+                assert!(error_code.explanation.contains("Fixed code:\n\n```timu\n"), "{} has no fixed code example", error_code.code);
+            }
+        }
+
+        assert_eq!(find("t0004").unwrap().title(), "type not found");
+        assert!(find("T9999").is_none());
+    }
+}
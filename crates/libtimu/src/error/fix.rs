@@ -0,0 +1,144 @@
+//! Fix-it suggestions.
+//!
+//! Errors with a mechanical fix carry [`Suggestion`]s, edits that replace a
+//! range of a source file. A private item gets `pub`, a type of another module
+//! gets imported and a missing interface member gets a stub in the `extend`
+//! block. [`collect`] gathers the suggestions of an error and the errors it
+//! contains, [`apply`] rewrites a source file with them.
+
+use libtimu_macros_core::traits::{Suggestion, TimuErrorTrait};
+
+/// Suggestions of the error and of the errors it contains, without duplicates
+pub fn collect(error: &dyn TimuErrorTrait) -> Vec<Suggestion> {
+    let mut suggestions = Vec::new();
+    collect_into(error, &mut suggestions);
+    suggestions
+}
+
+fn collect_into(error: &dyn TimuErrorTrait, suggestions: &mut Vec<Suggestion>) {
+    for suggestion in error.suggestions().unwrap_or_default() {
+        if !suggestions.contains(&suggestion) {
+            suggestions.push(suggestion);
+        }
+    }
+
+    if let Some(errors) = error.errors() {
+        for inner_error in errors {
+            collect_into(inner_error, suggestions);
+        }
+    }
+}
+
+/// Applies the suggestions to the source and returns the new source and the number of applied suggestions
+///
+/// Suggestions are applied in the order of their ranges, insertions at the
+/// same position keep their order. A suggestion overlapping an applied one or
+/// out of the source is skipped.
+pub fn apply(source: &str, suggestions: &[&Suggestion]) -> (String, usize) {
+    let mut suggestions = suggestions.to_vec();
+    suggestions.sort_by_key(|suggestion| (suggestion.range.start, suggestion.range.end));
+
+    let mut fixed = String::with_capacity(source.len());
+    let mut cursor = 0;
+    let mut applied = 0;
+
+    for suggestion in suggestions {
+        let range = suggestion.range.clone();
+        if range.start < cursor || range.start > range.end || source.get(range.clone()).is_none() {
+            continue;
+        }
+
+        fixed.push_str(&source[cursor..range.start]);
+        fixed.push_str(&suggestion.replacement);
+        cursor = range.end;
+        applied += 1;
+    }
+
+    fixed.push_str(&source[cursor..]);
+    (fixed, applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use libtimu_macros_core::{traits::Suggestion, SourceCode};
+    use rstest::rstest;
+
+    use crate::{error::Diagnostic, file::SourceFile, nom_tools::State, process_ast, process_code, tir::TirError};
+
+    use super::{apply, collect};
+
+    fn suggestion(range: std::ops::Range<usize>, replacement: &str) -> Suggestion {
+        Suggestion {
+            message: String::new(),
+            code: SourceCode { source: String::new(), name: "main".into() },
+            range,
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Compiles the modules, the first one is `main`, and applies the suggestions to each of them
+    fn fix(modules: &[(&str, &str)]) -> Vec<String> {
+        let states = modules.iter()
+            .map(|(name, code)| State::new(SourceFile::new(vec![name.to_string()], code.to_string())))
+            .collect::<Vec<_>>();
+        let files = states.iter().map(|state| process_code(state).unwrap().into()).collect();
+        let error = process_ast(files).err().unwrap();
+        let suggestions = Diagnostic::from_error(error).iter().flat_map(|diagnostic| collect(&diagnostic.error)).collect::<Vec<_>>();
+
+        modules.iter().map(|(name, code)| {
+            let suggestions = suggestions.iter().filter(|suggestion| suggestion.code.name == *name).collect::<Vec<_>>();
+            apply(code, &suggestions).0
+        }).collect()
+    }
+
+    #[rstest]
+    #[case::insert(&[suggestion(3..3, "pub ")], "abcdef", "abcpub def", 1)]
+    #[case::replace(&[suggestion(1..3, "X")], "abcdef", "aXdef", 1)]
+    #[case::unordered(&[suggestion(5..6, "F"), suggestion(0..1, "A")], "abcdef", "AbcdeF", 2)]
+    #[case::same_position(&[suggestion(0..0, "1"), suggestion(0..0, "2")], "abc", "12abc", 2)]
+    #[case::overlapping(&[suggestion(0..3, "X"), suggestion(2..4, "Y")], "abcdef", "Xdef", 1)]
+    #[case::out_of_source(&[suggestion(4..9, "X")], "abc", "abc", 0)]
+    fn apply_suggestions(#[case] suggestions: &[Suggestion], #[case] source: &str, #[case] expected: &str, #[case] applied: usize) {
+        let suggestions = suggestions.iter().collect::<Vec<_>>();
+        assert_eq!(apply(source, &suggestions), (expected.to_string(), applied));
+    }
+
+    #[test]
+    fn duplicates() {
+        let error = TirError::multiple_errors(vec![
+            TirError::interface_field_not_defined(0..1, SourceFile::new(vec!["main".into()], "a".into()), Some(suggestion(0..0, "x"))),
+            TirError::interface_field_not_defined(0..1, SourceFile::new(vec!["main".into()], "a".into()), Some(suggestion(0..0, "x"))),
+        ]);
+        assert_eq!(collect(&error), [suggestion(0..0, "x")]);
+    }
+
+    #[rstest]
+    #[case::class("class Point {}", "pub class Point {}")]
+    #[case::function("\nfunc Point(): void {}", "\npub func Point(): void {}")]
+    #[case::modifier("const  func Point(): i32 { return 1; }", "pub const  func Point(): i32 { return 1; }")]
+    fn private_items(#[case] code: &str, #[case] expected: &str) {
+        assert_eq!(fix(&[("main", "use lib.Point;"), ("lib", code)]), ["use lib.Point;", expected]);
+    }
+
+    #[test]
+    fn missing_import() {
+        let fixed = fix(&[
+            ("main", "func test(point: Point): void {}"),
+            ("lib", "pub class Point {}"),
+        ]);
+        assert_eq!(fixed[0], "use lib.Point;\nfunc test(point: Point): void {}");
+    }
+
+    #[rstest]
+    #[case::own_line(
+        "interface Shape { func area(): i32; name: string; }\nclass Square {}\nextend Square: Shape {\n    func area(): i32 { return 1; }\n}",
+        "interface Shape { func area(): i32; name: string; }\nclass Square {}\nextend Square: Shape {\n    func area(): i32 { return 1; }\n    name: string;\n}"
+    )]
+    #[case::same_line(
+        "interface Shape { func area(): i32; }\nclass Square {}\nextend Square: Shape {}",
+        "interface Shape { func area(): i32; }\nclass Square {}\nextend Square: Shape {\n    func area(): i32 {}\n}"
+    )]
+    fn missing_interface_members(#[case] code: &str, #[case] expected: &str) {
+        assert_eq!(fix(&[("main", code)]), [expected]);
+    }
+}
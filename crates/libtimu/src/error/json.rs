@@ -0,0 +1,295 @@
+//! Machine-readable error reports.
+//!
+//! [`JsonReportGenerator`] writes one JSON object per line for every reported
+//! error, for continuous integration and editor integrations that read the
+//! errors instead of a person. A diagnostic has the shape
+//!
+//! ```json
+//! {
+//!   "severity": "error",
+//!   "code": "T0025",
+//!   "message": "expected `...` type, got `...`",
+//!   "help": "change the expression or the expected type so they match",
+//!   "file": "main.tim",
+//!   "range": { "start": 30, "end": 34 },
+//!   "start": { "line": 2, "column": 12 },
+//!   "end": { "line": 2, "column": 16 },
+//!   "labels": [{ "message": "...", "range": { ... }, "start": { ... }, "end": { ... } }],
+//!   "suggestions": [{ "message": "...", "file": "lib.tim", "range": { ... }, "start": { ... }, "end": { ... }, "replacement": "pub " }],
+//!   "children": []
+//! }
+//! ```
+//!
+//! The location of a diagnostic is its first label, `file`, `range`, `start`
+//! and `end` are missing for errors without a location. Lines and columns
+//! start at 1 and columns count characters. The severity is `error`,
+//! `warning` or `note`. The nested errors and the referenced errors become
+//! `children`, the references with the `note` severity. A suggestion replaces
+//! its range of its file with the replacement, see [`super::fix`].
+
+use std::ops::Range;
+
+use libtimu_macros_core::traits::TimuErrorTrait;
+use serde::Serialize;
+
+use crate::tir::TirError;
+
+use super::{hidden_errors_message, shown_diagnostics, Diagnostic, ReportGenerator, ReportOptions, Severity, TIMU_LANG_EXT};
+
+/// One reported error with its labels and the errors it contains
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonDiagnostic {
+    pub severity: Severity,
+    pub code: Option<String>,
+    pub message: String,
+    pub help: Option<String>,
+    /// Source file of the labels, `None` when the error has no location
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Location of the first label
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub location: Option<JsonLocation>,
+    pub labels: Vec<JsonLabel>,
+    pub suggestions: Vec<JsonSuggestion>,
+    pub children: Vec<JsonDiagnostic>,
+}
+
+/// A highlighted part of the source code
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonLabel {
+    pub message: String,
+    #[serde(flatten)]
+    pub location: JsonLocation,
+}
+
+/// An edit that fixes the error
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonSuggestion {
+    pub message: String,
+    pub file: String,
+    #[serde(flatten)]
+    pub location: JsonLocation,
+    pub replacement: String,
+}
+
+/// Byte range of a location and the lines and columns of its ends
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JsonLocation {
+    pub range: Range<usize>,
+    pub start: LineColumn,
+    pub end: LineColumn,
+}
+
+/// Position in a source file, both values start at 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl LineColumn {
+    /// Line and column of a byte offset, offsets inside of a character count as that character
+    pub fn of(source: &str, offset: usize) -> Self {
+        let offset = offset.min(source.len());
+        let before = source.as_bytes()[..offset].iter().rposition(|byte| *byte == b'\n').map_or(0, |position| position + 1);
+        let line = source.as_bytes()[..before].iter().filter(|byte| **byte == b'\n').count() + 1;
+        let column = source[before..].char_indices().take_while(|(index, _)| before + index < offset).count() + 1;
+        Self { line, column }
+    }
+}
+
+impl JsonLocation {
+    fn new(source: &str, range: Range<usize>) -> Self {
+        Self { start: LineColumn::of(source, range.start), end: LineColumn::of(source, range.end), range }
+    }
+}
+
+/// Error report generator writing one JSON object per diagnostic to the standard error
+pub struct JsonReportGenerator;
+
+impl JsonReportGenerator {
+    /// JSON form of the given diagnostics
+    ///
+    /// When more errors than the limit were collected, the remaining ones are
+    /// summarised in a single note.
+    pub fn diagnostics(diagnostics: &[Diagnostic], options: ReportOptions) -> Vec<JsonDiagnostic> {
+        let (shown, hidden) = shown_diagnostics(diagnostics, options);
+        let mut diagnostics = shown.into_iter().map(|diagnostic| Self::diagnostic(&diagnostic.error, diagnostic.severity)).collect::<Vec<_>>();
+
+        if hidden > 0 {
+            diagnostics.push(JsonDiagnostic {
+                severity: Severity::Note,
+                code: None,
+                message: hidden_errors_message(hidden),
+                help: None,
+                file: None,
+                location: None,
+                labels: Vec::new(),
+                suggestions: Vec::new(),
+                children: Vec::new(),
+            });
+        }
+
+        diagnostics
+    }
+
+    /// Writes the diagnostics of the given error, one line each
+    pub fn generate_with_options(error: TirError, options: ReportOptions) {
+        Self::generate_diagnostics(Diagnostic::from_error(error), options);
+    }
+
+    /// Writes the given diagnostics, one line each
+    pub fn generate_diagnostics(diagnostics: Vec<Diagnostic>, options: ReportOptions) {
+        for diagnostic in Self::diagnostics(&diagnostics, options) {
+            eprintln!("{}", serde_json::to_string(&diagnostic).unwrap());
+        }
+    }
+
+    fn diagnostic(error: &dyn TimuErrorTrait, severity: Severity) -> JsonDiagnostic {
+        let source_code = error.source_code();
+        let labels = match &source_code {
+            Some(source_code) => error.labels().unwrap_or_default().into_iter()
+                .map(|label| JsonLabel { message: label.label, location: JsonLocation::new(&source_code.source, label.position) })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        let suggestions = error.suggestions().unwrap_or_default().into_iter()
+            .map(|suggestion| JsonSuggestion {
+                message: suggestion.message,
+                file: format!("{}.{}", suggestion.code.name, TIMU_LANG_EXT),
+                location: JsonLocation::new(&suggestion.code.source, suggestion.range),
+                replacement: suggestion.replacement,
+            })
+            .collect();
+
+        let mut children = Vec::new();
+        for reference in error.references().unwrap_or_default() {
+            children.push(Self::diagnostic(*reference, Severity::Note));
+        }
+        if let Some(errors) = error.errors() {
+            children.extend(errors.map(|inner_error| Self::diagnostic(inner_error, severity)));
+        }
+
+        JsonDiagnostic {
+            severity,
+            code: error.error_code().map(|code| code.to_string()),
+            message: error.to_string(),
+            help: error.help().map(|help| help.to_string()),
+            file: source_code.filter(|_| !labels.is_empty()).map(|source_code| format!("{}.{}", source_code.name, TIMU_LANG_EXT)),
+            location: labels.first().map(|label: &JsonLabel| label.location.clone()),
+            labels,
+            suggestions,
+            children,
+        }
+    }
+}
+
+impl ReportGenerator for JsonReportGenerator {
+    fn generate(error: TirError) {
+        Self::generate_with_options(error, ReportOptions::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{error::{Diagnostic, ReportOptions, Severity}, file::SourceFile, nom_tools::State, process_ast, process_code, tir::TirError};
+
+    use super::{JsonDiagnostic, JsonReportGenerator, LineColumn};
+
+    fn error(code: &str) -> TirError {
+        let state = State::new(SourceFile::new(vec!["main".into()], code.to_string()));
+        process_code(&state).and_then(|ast| process_ast(vec![ast.into()])).err().unwrap()
+    }
+
+    fn diagnostics(error: TirError, options: ReportOptions) -> Vec<JsonDiagnostic> {
+        JsonReportGenerator::diagnostics(&Diagnostic::from_error(error), options)
+    }
+
+    #[test]
+    fn line_column() {
+        let source = "ab\nçd\n";
+        assert_eq!(LineColumn::of(source, 0), LineColumn { line: 1, column: 1 });
+        assert_eq!(LineColumn::of(source, 3), LineColumn { line: 2, column: 1 });
+        assert_eq!(LineColumn::of(source, 5), LineColumn { line: 2, column: 2 });
+        assert_eq!(LineColumn::of(source, 7), LineColumn { line: 3, column: 1 });
+    }
+
+    #[test]
+    fn type_error() {
+        let diagnostics = diagnostics(error("func test(): i32 {\n    return true;\n}"), ReportOptions::default());
+        assert_eq!(diagnostics.len(), 1);
+
+        let value = serde_json::to_value(&diagnostics[0]).unwrap();
+        assert_eq!(value["severity"], "error");
+        assert_eq!(value["file"], "main.tim");
+        assert_eq!(value["range"], json!({ "start": 30, "end": 34 }));
+        assert_eq!(value["start"], json!({ "line": 2, "column": 12 }));
+        assert_eq!(value["end"], json!({ "line": 2, "column": 16 }));
+        assert!(value["code"].is_string(), "{value}");
+        assert_eq!(value["labels"][0]["range"], value["range"]);
+    }
+
+    #[test]
+    fn syntax_error() {
+        let diagnostics = diagnostics(error("func test(: i32 {}"), ReportOptions::default());
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].location.is_none());
+        assert!(!diagnostics[0].children.is_empty());
+        assert!(diagnostics[0].children.iter().all(|child| child.severity == Severity::Error && child.file.as_deref() == Some("main.tim")));
+    }
+
+    #[test]
+    fn references() {
+        let code = "func add(a: i32): i32 { return a; }\nfunc test(): i32 { return add(1, 2); }";
+        let diagnostics = diagnostics(error(code), ReportOptions::default());
+        let notes = diagnostics[0].children.iter().filter(|child| child.severity == Severity::Note).collect::<Vec<_>>();
+        assert!(!notes.is_empty(), "{diagnostics:#?}");
+        assert!(notes.iter().all(|note| note.location.is_some()));
+    }
+
+    #[test]
+    fn suggestions() {
+        let main = State::new(SourceFile::new(vec!["main".into()], "use lib.Point;".to_string()));
+        let lib = State::new(SourceFile::new(vec!["lib".into()], "\nclass Point {}".to_string()));
+        let files = [&main, &lib].map(|state| process_code(state).unwrap().into());
+        let diagnostics = diagnostics(process_ast(files.to_vec()).err().unwrap(), ReportOptions::default());
+
+        let value = serde_json::to_value(&diagnostics[0].suggestions).unwrap();
+        assert_eq!(value, json!([{
+            "message": "make `Point` public",
+            "file": "lib.tim",
+            "range": { "start": 1, "end": 1 },
+            "start": { "line": 2, "column": 1 },
+            "end": { "line": 2, "column": 1 },
+            "replacement": "pub ",
+        }]));
+        assert!(diagnostics[0].children.iter().all(|child| child.suggestions.is_empty()));
+    }
+
+    #[test]
+    fn error_limit() {
+        let errors = ["func a(): i32 { return true; }", "func b(): bool { return 1; }", "func c(: i32 {}"].map(error);
+        let diagnostics = diagnostics(TirError::multiple_errors(errors.to_vec()), ReportOptions { error_limit: Some(1), ..Default::default() });
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[1].severity, Severity::Note);
+        assert_eq!(diagnostics[1].message, "2 more errors were not shown");
+
+        let line = serde_json::to_string(&diagnostics[1]).unwrap();
+        assert!(!line.contains("\"file\"") && !line.contains("\"range\""), "{line}");
+    }
+
+    #[test]
+    fn warnings_are_not_limited() {
+        let mut errors = Diagnostic::from_error(error("func a(): i32 { return true; }"));
+        errors.push(Diagnostic::warning(error("func b(): bool { return 1; }")));
+        errors.push(Diagnostic::error(error("func c(): bool { return 1; }")));
+
+        let diagnostics = JsonReportGenerator::diagnostics(&errors, ReportOptions { error_limit: Some(1), ..Default::default() });
+        assert_eq!(diagnostics.iter().map(|diagnostic| diagnostic.severity).collect::<Vec<_>>(), [Severity::Error, Severity::Warning, Severity::Note]);
+        assert_eq!(serde_json::to_value(&diagnostics[1]).unwrap()["severity"], "warning");
+        assert_eq!(diagnostics[2].message, "1 more error was not shown");
+    }
+}
@@ -0,0 +1,265 @@
+//! Error handling and reporting for the Timu language compiler.
+//!
+//! This module provides comprehensive error handling infrastructure including:
+//! - Type aliases for parse and TIR results
+//! - Diagnostics, errors reported with a [`Severity`]
+//! - Error reporting using `codespan-reporting` for rich diagnostics
+//! - Stable error codes and their explanations in [`codes`]
+//! - Fix-it suggestions and how to apply them in [`fix`]
+//! - Machine-readable reports in the [`json`] format
+//! - Static analysis reports in the [`sarif`] format
+//! - Parser error conversion utilities
+//! - Integration with the Timu error trait system
+
+use codespan_reporting::{diagnostic::{Diagnostic as CodeSpanDiagnostic, Label}, files::SimpleFiles, term::{self, termcolor::StandardStream}};
+use libtimu_macros_core::traits::TimuErrorTrait;
+use nom_language::error::VerboseErrorKind;
+use serde::Serialize;
+use simplelog::ColorChoice;
+use crate::{
+    ast::FileAst,
+    nom_tools::{State, ToRange},
+    tir::{error::SyntaxErrorItem, TirContext, TirError},
+};
+
+pub mod codes;
+pub mod fix;
+pub mod json;
+pub mod sarif;
+
+pub use json::JsonReportGenerator;
+pub use sarif::SarifReportGenerator;
+
+/// File extension for Timu language source files
+pub static TIMU_LANG_EXT: &str = "tim";
+
+/// Error type for parsing operations using nom combinators
+pub type ParseError<'base> = nom_language::error::VerboseError<nom_locate::LocatedSpan<&'base str, State>>;
+
+/// Result type for parsing operations that returns the remaining input and parsed AST
+pub type ParseResult<'base> = Result<(nom_locate::LocatedSpan<&'base str, State>, FileAst<'base>), ParseError<'base>>;
+
+/// Result type for TIR (Type Intermediate Representation) operations
+pub type TirResult<'base> = Result<TirContext<'base>, TirError>;
+
+/// How serious a diagnostic is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The program is not valid, compilation fails
+    Error,
+    /// The program is valid but probably not what was meant
+    Warning,
+    /// Additional information about another diagnostic
+    Note,
+}
+
+/// An error with the severity it is reported with
+///
+/// Compilation errors are always [`Severity::Error`], lints report their
+/// findings as warnings or as errors depending on their level.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub error: TirError,
+}
+
+impl Diagnostic {
+    pub fn error(error: TirError) -> Self {
+        Self { severity: Severity::Error, error }
+    }
+
+    pub fn warning(error: TirError) -> Self {
+        Self { severity: Severity::Warning, error }
+    }
+
+    /// Diagnostics of a failed compilation, a collection of errors becomes one diagnostic per error
+    pub fn from_error(error: TirError) -> Vec<Self> {
+        match error {
+            TirError::ErrorCollection(collection) => collection.errors.into_iter().map(Self::error).collect(),
+            error => vec![Self::error(error)],
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+/// Trait for generating error reports from TIR errors
+pub trait ReportGenerator {
+    /// Generate a formatted error report for the given TIR error
+    fn generate(error: TirError);
+}
+
+/// Options that control how errors are rendered
+#[derive(Debug, Clone, Copy)]
+pub struct ReportOptions {
+    /// Whether the report is coloured
+    pub color: ColorChoice,
+    /// Maximum number of errors to report, `None` reports all of them
+    pub error_limit: Option<usize>,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            color: ColorChoice::Always,
+            error_limit: None,
+        }
+    }
+}
+
+/// Error report generator using the `codespan-reporting` library for rich diagnostics
+pub struct CodeSpanReportGenerator;
+
+impl CodeSpanReportGenerator {
+    /// Internal helper to recursively generate diagnostics for errors and their references
+    fn inner_generate(files: &mut SimpleFiles<String, String>, diagnostics: &mut Vec<CodeSpanDiagnostic<usize>>, error: &dyn TimuErrorTrait, severity: Severity) {
+        let diagnostic: CodeSpanDiagnostic<usize> = match severity {
+            Severity::Error => CodeSpanDiagnostic::error(),
+            Severity::Warning => CodeSpanDiagnostic::warning(),
+            Severity::Note => CodeSpanDiagnostic::note(),
+        };
+        let mut diagnostic = diagnostic.with_message(error.to_string());
+
+        if let Some(code) = error.error_code() {
+            diagnostic = diagnostic.with_code(code.to_string());
+        }
+
+        if let Some(source_code) = error.source_code()  {
+            let file_id = files.add(format!("{}.{}", source_code.name, TIMU_LANG_EXT), source_code.source);
+            
+             if let Some(labels) = error.labels() {
+                let labels = labels.into_iter().map(|label| Label::primary(file_id, label.position).with_message(label.label)).collect::<Vec<_>>();
+                diagnostic = diagnostic.with_labels(labels);
+            }
+        }
+
+        for suggestion in error.suggestions().unwrap_or_default() {
+            let file_id = files.add(format!("{}.{}", suggestion.code.name, TIMU_LANG_EXT), suggestion.code.source);
+            diagnostic.labels.push(Label::secondary(file_id, suggestion.range).with_message(format!("suggestion: {}", suggestion.message)));
+        }
+
+        if let Some(help) = error.help() {
+            diagnostic = diagnostic.with_note(help.to_string());
+        }
+
+        diagnostics.push(diagnostic);
+
+        if let Some(references) = error.references() {
+            for reference in references.into_iter() {
+                Self::inner_generate(files, diagnostics, *reference, severity);
+            }   
+        }
+
+        if let Some(errors) = error.errors() {
+            for inner_error in errors {
+                Self::inner_generate(files, diagnostics, inner_error, severity);
+            }
+        }
+    }
+}
+
+impl CodeSpanReportGenerator {
+    /// Generates a report for the given error, rendered with the given options
+    ///
+    /// When the error is a collection of errors and more of them than the
+    /// limit were collected, the remaining ones are summarised in a single note.
+    pub fn generate_with_options(error: TirError, options: ReportOptions) {
+        Self::generate_diagnostics(Diagnostic::from_error(error), options);
+    }
+
+    /// Generates a report for the given diagnostics, rendered with the given options
+    ///
+    /// The error limit only applies to the errors, every warning is reported.
+    pub fn generate_diagnostics(diagnostics: Vec<Diagnostic>, options: ReportOptions) {
+        let (shown, hidden) = shown_diagnostics(&diagnostics, options);
+        let mut files = SimpleFiles::new();
+        let mut diagnostics = Vec::new();
+
+        for diagnostic in shown {
+            Self::inner_generate(&mut files, &mut diagnostics, &diagnostic.error, diagnostic.severity);
+        }
+
+        if hidden > 0 {
+            diagnostics.push(CodeSpanDiagnostic::note().with_message(hidden_errors_message(hidden)));
+        }
+
+        let writer = StandardStream::stderr(options.color);
+        let config = codespan_reporting::term::Config::default();
+
+        for diagnostic in diagnostics.into_iter() {
+            term::emit(&mut writer.lock(), &config, &files, &diagnostic).unwrap();
+        }
+    }
+}
+
+impl ReportGenerator for CodeSpanReportGenerator {
+    fn generate(error: TirError) {
+        Self::generate_with_options(error, ReportOptions::default());
+    }
+}
+
+/// Diagnostics of a report and the number of errors left out of it because of the error limit
+fn shown_diagnostics(diagnostics: &[Diagnostic], options: ReportOptions) -> (Vec<&Diagnostic>, usize) {
+    let limit = options.error_limit.unwrap_or(usize::MAX);
+    let mut errors = 0;
+    let shown = diagnostics.iter()
+        .filter(|diagnostic| !diagnostic.is_error() || { errors += 1; errors <= limit })
+        .collect();
+
+    (shown, errors.saturating_sub(limit))
+}
+
+/// Summary of the errors left out of a report because of the error limit
+fn hidden_errors_message(hidden: usize) -> String {
+    format!("{hidden} more error{} not shown", if hidden == 1 { " was" } else { "s were" })
+}
+
+/// Converts parser results into TIR-compatible results, handling parser errors
+/// 
+/// Takes a parser result and either returns the parsed AST or converts
+/// parser errors into TIR syntax errors with proper source location information.
+#[allow(clippy::result_unit_err)]
+pub fn handle_parser(result: ParseResult<'_>) -> Result<FileAst<'_>, TirError> {
+    match result {
+        Ok((_, parsed)) => Ok(parsed),
+    Err(error) => {
+        let mut errors =  Vec::new();
+            error.errors.iter().for_each(|(span, error_kind)| {
+                if let VerboseErrorKind::Context(error_message) = error_kind {
+                    errors.push(SyntaxErrorItem {
+                        position: span.to_range(),
+                        code: (&span.extra.file).into(),
+                        message: error_message
+                    });
+                }
+            });
+            Err(TirError::syntax_error(errors))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::Finish;
+
+    use crate::{file::SourceFile, nom_tools::State, parser};
+
+    use super::handle_parser;
+
+    #[test]
+    #[should_panic]
+    fn error_test() {
+        let source_file = SourceFile::new(vec!["<memory>".into()], "interface Myinterface : erhan {".to_string());
+
+        let state = State {
+            file: source_file.clone(),
+            indexer: Default::default(),
+        };
+
+        let response = parser::parse(&state).finish();
+        handle_parser(response).unwrap();
+    }
+}
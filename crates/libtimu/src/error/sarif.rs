@@ -0,0 +1,522 @@
+//! SARIF error reports.
+//!
+//! [`SarifReportGenerator`] writes the reported errors as a single
+//! [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! log, the format code-scanning dashboards read static analysis results in.
+//!
+//! Every error code is a rule of the `timuc` tool, see [`rules`]. The code
+//! is the ID of the rule, the title of its explanation the short description
+//! and the help of its `#[diagnostic]` attribute the help, the codes of the
+//! lints have the `warning` level. The invocation is successful when no error
+//! was reported. An error becomes a result of the rule of its code located at
+//! its first label, the remaining labels and the labels of the referenced
+//! errors are the related locations. Syntax errors and error collections
+//! report every error they contain as a separate result.
+//!
+//! Regions have both the byte range and the lines and columns of the error,
+//! columns count Unicode code points.
+
+use libtimu_macros_core::traits::{StaticDiagnostic, TimuErrorTrait};
+use serde::Serialize;
+use strum::IntoEnumIterator;
+
+use crate::{
+    backend::UnsupportedConstruct,
+    lint::{Lint, UnknownAttribute, UnknownLint, UnreachablePattern, UnusedFunction, UnusedImport, UnusedVariable},
+    tir::{
+        const_eval::{ConstEvaluationFailed, InvalidConstFunction, NotConstFunction, NotConstantInitializer},
+        error::{AccessibilityViolation, AlreadyDefined, CircularReference, ExtraAccessibilityIdentifier, ExtraFieldInExtend, ImportConflictsWithDefinition, ImportNotFound, InterfaceFieldNotDefined, InvalidOverride, InvalidType, ModuleAlreadyImported, ModuleLoadFailed, SyntaxError, SyntaxErrorItem, TypeNotFound, TypesDoNotMatch, TYPE_NOT_FOUND_HELP},
+        resolver::{
+            function::{ExternFunctionInClass, InstanceFieldInStaticFunction, NotFfiSafe, ThisNeedToDefineInClass, UnknownNativeFunction, VariableNotFound},
+            statement::{
+                ArgumentTypeMismatch, AssignToConstant, CallPathNotValid, ExpressionTypeMismatch, FunctionCallArgumentCountMismatch, InstanceMemberThroughType, MethodAsValue, MissingReturnValue, MutableCapture, NonExhaustiveMatch, NotATuple, NotCallable, OperatorTypeMismatch, PatternTypeMismatch, StaticMemberThroughInstance, TupleArityMismatch, TupleIndexOutOfRange, UnexpectedReturnValue, UnknownVariant, UnsupportedArgumentType, VariableTypeRequired, VariantBindingMismatch, VoidVariable,
+            },
+        },
+        TirError,
+    },
+};
+
+use super::{codes::{self, ERROR_CODES}, hidden_errors_message, json::LineColumn, shown_diagnostics, Diagnostic, ReportGenerator, ReportOptions, Severity, TIMU_LANG_EXT};
+
+/// Version of the SARIF format the reports are written in
+pub static SARIF_VERSION: &str = "2.1.0";
+
+/// JSON schema of the SARIF format
+pub static SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Static description of an error code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rule {
+    /// The error code
+    pub id: &'static str,
+    /// Name of the error type
+    pub name: &'static str,
+    /// Title of the explanation of the code
+    pub short_description: &'static str,
+    /// How to fix the error, `None` when it depends on the error
+    pub help: Option<&'static str>,
+    /// Level of the errors, `warning` for the lints
+    pub level: &'static str,
+}
+
+/// Code, name and help of an error type
+fn of<T: StaticDiagnostic>() -> (&'static str, &'static str, Option<&'static str>) {
+    let name = std::any::type_name::<T>().rsplit("::").next().unwrap_or_default();
+    (T::CODE.unwrap_or_default(), name, T::HELP)
+}
+
+/// Code, name and help of every error, errors sharing a code are described by the first one
+fn diagnostics() -> Vec<(&'static str, &'static str, Option<&'static str>)> {
+    // The help of a missing type lists the similar types when there are any
+    let (code, name, _) = of::<TypeNotFound>();
+
+    vec![
+        // Errors that are enum variants have no help
+        ("T0000", "TemporaryError", None),
+        ("T0014", "VariableAlreadyDefined", None),
+        ("T0015", "ThisArgumentMustBeFirst", None),
+        (code, name, Some(TYPE_NOT_FOUND_HELP)),
+        of::<ImportNotFound>(),
+        of::<ModuleLoadFailed>(),
+        of::<ModuleAlreadyImported>(),
+        of::<ImportConflictsWithDefinition>(),
+        of::<AlreadyDefined>(),
+        of::<ExtraAccessibilityIdentifier>(),
+        of::<InvalidType>(),
+        of::<InterfaceFieldNotDefined>(),
+        of::<TypesDoNotMatch>(),
+        of::<ExtraFieldInExtend>(),
+        of::<AccessibilityViolation>(),
+        of::<CircularReference>(),
+        of::<SyntaxError>(),
+        of::<SyntaxErrorItem>(),
+        of::<InvalidOverride>(),
+        of::<ThisNeedToDefineInClass>(),
+        of::<VariableNotFound>(),
+        of::<UnknownNativeFunction>(),
+        of::<NotFfiSafe>(),
+        of::<ExternFunctionInClass>(),
+        of::<InstanceFieldInStaticFunction>(),
+        of::<UnsupportedArgumentType>(),
+        of::<FunctionCallArgumentCountMismatch>(),
+        of::<CallPathNotValid>(),
+        of::<ArgumentTypeMismatch>(),
+        of::<InstanceMemberThroughType>(),
+        of::<StaticMemberThroughInstance>(),
+        of::<NotCallable>(),
+        of::<ExpressionTypeMismatch>(),
+        of::<OperatorTypeMismatch>(),
+        of::<AssignToConstant>(),
+        of::<VariableTypeRequired>(),
+        of::<VoidVariable>(),
+        of::<MissingReturnValue>(),
+        of::<UnexpectedReturnValue>(),
+        of::<ConstEvaluationFailed>(),
+        of::<NotConstFunction>(),
+        of::<InvalidConstFunction>(),
+        of::<NotConstantInitializer>(),
+        of::<UnsupportedConstruct>(),
+        of::<MutableCapture>(),
+        of::<MethodAsValue>(),
+        of::<NonExhaustiveMatch>(),
+        of::<PatternTypeMismatch>(),
+        of::<TupleArityMismatch>(),
+        of::<NotATuple>(),
+        of::<TupleIndexOutOfRange>(),
+        of::<UnknownVariant>(),
+        of::<VariantBindingMismatch>(),
+        of::<UnusedImport>(),
+        of::<UnusedFunction>(),
+        of::<UnusedVariable>(),
+        of::<UnreachablePattern>(),
+        of::<UnknownAttribute>(),
+        of::<UnknownLint>(),
+    ]
+}
+
+/// Rule of an error code, `None` when the code is not registered
+pub fn rule(code: &str) -> Option<Rule> {
+    let error_code = codes::find(code)?;
+    let (_, name, help) = diagnostics().into_iter().find(|(code, _, _)| *code == error_code.code)?;
+    let level = match Lint::iter().any(|lint| lint.code() == error_code.code) {
+        true => "warning",
+        false => "error",
+    };
+
+    Some(Rule { id: error_code.code, name, short_description: error_code.title(), help, level })
+}
+
+/// Rule of every error code, in the order of the codes
+pub fn rules() -> Vec<Rule> {
+    ERROR_CODES.iter().filter_map(|error_code| rule(error_code.code)).collect()
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+    }
+}
+
+/// Top level object of a SARIF file
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<SarifRun>,
+}
+
+/// One run of the compiler
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub column_kind: &'static str,
+    pub results: Vec<SarifResult>,
+    pub invocations: Vec<SarifInvocation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+/// The compiler and every rule it reports
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifDriver {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub rules: Vec<SarifRule>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRule {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub short_description: SarifMessage,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<SarifMessage>,
+    pub default_configuration: SarifConfiguration,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifConfiguration {
+    pub level: &'static str,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+/// Notes about the run itself, like the errors left out of the report
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifInvocation {
+    pub execution_successful: bool,
+    pub tool_execution_notifications: Vec<SarifNotification>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifNotification {
+    pub level: &'static str,
+    pub message: SarifMessage,
+}
+
+/// One reported error
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifResult {
+    pub rule_id: &'static str,
+    pub rule_index: usize,
+    pub level: &'static str,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+    pub related_locations: Vec<SarifLocation>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifLocation {
+    /// Identifier of a related location, unique in its result
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<usize>,
+    pub physical_location: SarifPhysicalLocation,
+    pub message: SarifMessage,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    pub region: SarifRegion,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SarifRegion {
+    pub start_line: usize,
+    pub start_column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+    pub byte_offset: usize,
+    pub byte_length: usize,
+}
+
+/// Error report generator writing a SARIF log to the standard error
+pub struct SarifReportGenerator;
+
+impl SarifReportGenerator {
+    /// SARIF log of the given diagnostics
+    ///
+    /// When more errors than the limit were collected, the remaining ones are
+    /// summarised in a notification of the invocation.
+    pub fn log(diagnostics: &[Diagnostic], options: ReportOptions) -> SarifLog {
+        let (shown, hidden) = shown_diagnostics(diagnostics, options);
+
+        let rules = rules();
+        let mut results = Vec::new();
+        for diagnostic in shown {
+            Self::results(&diagnostic.error, diagnostic.severity, &rules, &mut results);
+        }
+
+        let mut notifications = Vec::new();
+        if hidden > 0 {
+            notifications.push(SarifNotification { level: "note", message: SarifMessage { text: hidden_errors_message(hidden) } });
+        }
+
+        let rules = rules.into_iter().map(|rule| SarifRule {
+            id: rule.id,
+            name: rule.name,
+            short_description: SarifMessage { text: rule.short_description.to_string() },
+            help: rule.help.map(|help| SarifMessage { text: help.to_string() }),
+            default_configuration: SarifConfiguration { level: rule.level },
+        }).collect();
+        let execution_successful = !diagnostics.iter().any(Diagnostic::is_error);
+
+        SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![SarifRun {
+                tool: SarifTool { driver: SarifDriver { name: "timuc", version: env!("CARGO_PKG_VERSION"), rules } },
+                column_kind: "unicodeCodePoints",
+                results,
+                invocations: vec![SarifInvocation { execution_successful, tool_execution_notifications: notifications }],
+            }],
+        }
+    }
+
+    /// Writes the SARIF log of the given error
+    pub fn generate_with_options(error: TirError, options: ReportOptions) {
+        Self::generate_diagnostics(Diagnostic::from_error(error), options);
+    }
+
+    /// Writes the SARIF log of the given diagnostics
+    pub fn generate_diagnostics(diagnostics: Vec<Diagnostic>, options: ReportOptions) {
+        eprintln!("{}", serde_json::to_string_pretty(&Self::log(&diagnostics, options)).unwrap());
+    }
+
+    fn results(error: &TirError, severity: Severity, rules: &[Rule], results: &mut Vec<SarifResult>) {
+        if let TirError::ErrorCollection(collection) = error {
+            for error in collection.errors.iter() {
+                Self::results(error, severity, rules, results);
+            }
+            return;
+        }
+
+        match error.errors() {
+            Some(errors) => results.extend(errors.map(|inner_error| Self::result(rules, severity, inner_error))),
+            None => results.push(Self::result(rules, severity, error)),
+        }
+    }
+
+    fn result(rules: &[Rule], severity: Severity, error: &dyn TimuErrorTrait) -> SarifResult {
+        // Errors without a code are internal errors, the rule of `T0000` is the first one
+        let code = error.error_code().map(|code| code.to_string());
+        let rule_index = rules.iter().position(|rule| Some(rule.id) == code.as_deref()).unwrap_or_default();
+
+        let mut locations = Self::locations(error);
+        let primary = match locations.is_empty() {
+            true => Vec::new(),
+            false => vec![locations.remove(0)],
+        };
+
+        for reference in error.references().unwrap_or_default() {
+            locations.extend(Self::locations(*reference));
+        }
+
+        for (index, location) in locations.iter_mut().enumerate() {
+            location.id = Some(index);
+        }
+
+        SarifResult {
+            rule_id: rules[rule_index].id,
+            rule_index,
+            level: level(severity),
+            message: SarifMessage { text: error.to_string() },
+            locations: primary,
+            related_locations: locations,
+        }
+    }
+
+    fn locations(error: &dyn TimuErrorTrait) -> Vec<SarifLocation> {
+        let Some(source_code) = error.source_code() else {
+            return Vec::new();
+        };
+
+        let uri = format!("{}.{}", source_code.name, TIMU_LANG_EXT);
+        error.labels().unwrap_or_default().into_iter().map(|label| {
+            let start = LineColumn::of(&source_code.source, label.position.start);
+            let end = LineColumn::of(&source_code.source, label.position.end);
+            SarifLocation {
+                id: None,
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation { uri: uri.clone() },
+                    region: SarifRegion {
+                        start_line: start.line,
+                        start_column: start.column,
+                        end_line: end.line,
+                        end_column: end.column,
+                        byte_offset: label.position.start,
+                        byte_length: label.position.len(),
+                    },
+                },
+                message: SarifMessage { text: label.label },
+            }
+        }).collect()
+    }
+}
+
+impl ReportGenerator for SarifReportGenerator {
+    fn generate(error: TirError) {
+        Self::generate_with_options(error, ReportOptions::default());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use libtimu_macros_core::traits::TimuErrorTrait;
+    use strum::IntoEnumIterator;
+
+    use crate::{error::{codes::ERROR_CODES, Diagnostic, ReportOptions}, file::SourceFile, lint::{self, Lint, LintLevels}, nom_tools::State, process_ast, process_code, tir::TirError};
+
+    use super::{diagnostics, rule, rules, SarifLog, SarifRegion, SarifReportGenerator, SARIF_VERSION};
+
+    fn error(code: &str) -> TirError {
+        let state = State::new(SourceFile::new(vec!["main".into()], code.to_string()));
+        process_code(&state).and_then(|ast| process_ast(vec![ast.into()])).err().unwrap()
+    }
+
+    fn log(error: TirError, options: ReportOptions) -> SarifLog {
+        SarifReportGenerator::log(&Diagnostic::from_error(error), options)
+    }
+
+    #[test]
+    fn every_code_has_a_rule() {
+        let rules = rules();
+        assert_eq!(rules.iter().map(|rule| rule.id).collect::<Vec<_>>(), ERROR_CODES.iter().map(|error_code| error_code.code).collect::<Vec<_>>());
+        assert!(diagnostics().iter().all(|(code, _, _)| rule(code).is_some()), "an error has an unregistered code");
+        assert_eq!(rules.iter().map(|rule| rule.name).collect::<HashSet<_>>().len(), rules.len());
+        assert!(Lint::iter().all(|lint| rule(lint.code()).unwrap().level == "warning"));
+        assert_eq!(rules.iter().filter(|rule| rule.level == "warning").count(), Lint::iter().count());
+    }
+
+    #[rstest::rstest]
+    #[case("use missing;", "T0001", "ImportNotFound")]
+    #[case("func test(a: missing): i32 {}", "T0004", "TypeNotFound")]
+    #[case("class a {} class a {}", "T0005", "AlreadyDefined")]
+    #[case("func test(): i32 { return true; }", "T0025", "ExpressionTypeMismatch")]
+    fn rule_help_matches_error(#[case] code: &str, #[case] id: &str, #[case] name: &str) {
+        let error = error(code);
+        let rule = rule(&error.error_code().unwrap().to_string()).unwrap();
+        assert_eq!(rule.id, id);
+        assert_eq!(rule.name, name);
+        assert_eq!(rule.help, error.help().map(|help| help.to_string()).as_deref());
+    }
+
+    #[test]
+    fn type_error() {
+        let log = log(error("func test(): i32 {\n    return true;\n}"), ReportOptions::default());
+        assert_eq!(log.version, SARIF_VERSION);
+
+        let run = &log.runs[0];
+        assert_eq!(run.results.len(), 1);
+
+        let result = &run.results[0];
+        assert_eq!(result.rule_id, "T0025");
+        assert_eq!(run.tool.driver.rules[result.rule_index].id, result.rule_id);
+        assert_eq!(result.locations[0].physical_location.artifact_location.uri, "main.tim");
+        assert_eq!(result.locations[0].physical_location.region, SarifRegion {
+            start_line: 2,
+            start_column: 12,
+            end_line: 2,
+            end_column: 16,
+            byte_offset: 30,
+            byte_length: 4,
+        });
+    }
+
+    #[test]
+    fn syntax_error() {
+        let log = log(error("func test(: i32 {}"), ReportOptions::default());
+        let results = &log.runs[0].results;
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|result| result.rule_id == "T0013" && result.locations.len() == 1));
+    }
+
+    #[test]
+    fn related_locations() {
+        let code = "func add(a: i32): i32 { return a; }\nfunc test(): i32 { return add(1, 2); }";
+        let log = log(error(code), ReportOptions::default());
+        let result = &log.runs[0].results[0];
+        assert!(!result.related_locations.is_empty(), "{result:#?}");
+        assert!(result.related_locations.iter().enumerate().all(|(index, location)| location.id == Some(index)));
+    }
+
+    #[test]
+    fn error_limit() {
+        let errors = ["func a(): i32 { return true; }", "func b(): bool { return 1; }", "func c(: i32 {}"].map(error);
+        let log = log(TirError::multiple_errors(errors.to_vec()), ReportOptions { error_limit: Some(1), ..Default::default() });
+        assert_eq!(log.runs[0].results.len(), 1);
+        assert_eq!(log.runs[0].invocations[0].tool_execution_notifications[0].message.text, "2 more errors were not shown");
+
+        let value = serde_json::to_value(&log).unwrap();
+        assert_eq!(value["$schema"], super::SARIF_SCHEMA);
+        assert_eq!(value["runs"][0]["results"][0]["ruleId"], "T0025");
+        assert!(value["runs"][0]["tool"]["driver"]["rules"][0]["shortDescription"]["text"].is_string());
+    }
+
+    #[test]
+    fn empty_log() {
+        let value = serde_json::to_value(SarifReportGenerator::log(&[], ReportOptions::default())).unwrap();
+        assert_eq!(value["runs"][0]["results"], serde_json::json!([]));
+        assert_eq!(value["runs"][0]["invocations"][0]["executionSuccessful"], true);
+    }
+
+    #[test]
+    fn warnings() {
+        let state = State::new(SourceFile::new(vec!["main".into()], "func helper(): void { var a: i32 = 1; }".to_string()));
+        let context = process_ast(vec![process_code(&state).unwrap().into()]).unwrap();
+        let log = SarifReportGenerator::log(&lint::check(&context, &LintLevels::default()), ReportOptions::default());
+
+        let run = &log.runs[0];
+        assert!(run.invocations[0].execution_successful);
+        assert_eq!(run.results.iter().map(|result| result.rule_id).collect::<Vec<_>>(), ["T0037", "T0038"]);
+        for result in run.results.iter() {
+            assert_eq!(result.level, "warning");
+            assert_eq!(run.tool.driver.rules[result.rule_index].id, result.rule_id);
+            assert_eq!(run.tool.driver.rules[result.rule_index].default_configuration.level, "warning");
+        }
+        assert_eq!(run.tool.driver.rules.len(), rules().len());
+    }
+}
@@ -0,0 +1,1027 @@
+//! Canonical formatting of Timu source code.
+//!
+//! [`format`] prints a parsed file back to source code in the one style shared
+//! by every `.tim` file:
+//!
+//! - Four spaces of indentation per block and one declaration or statement per line
+//! - All `use` statements sorted at the top of the file, followed by a blank line
+//! - One blank line between the declarations of a file, blank lines between
+//!   members and statements are kept but never more than one in a row
+//! - Single spaces around binary operators and after commas and colons
+//! - Parentheses only where the precedence of the operators requires them
+//! - Empty blocks written as `{}`
+//!
+//! The syntax tree has no comments, so they are recovered from the source code
+//! of the file. A comment on its own line stays above the code that follows
+//! it, a comment after code stays at the end of that line, and the comments at
+//! the top of the file that are separated from the code by a blank line stay
+//! at the top as the file header. Comments move together with the `use`
+//! statement they belong to when the imports are sorted.
+//!
+//! Formatting does not change the meaning of a file: parsing the formatted
+//! code gives the same syntax tree, and formatting it again gives the same
+//! code.
+
+use std::{cell::Cell, ops::Range};
+
+use crate::{
+    ast::{
+        AttributeAst, BodyStatementAst, ClassDefinitionAst, ClassDefinitionFieldAst, EnumDefinitionAst, ExpressionAst, ExpressionOperatorType, ExtendDefinitionAst, ExtendDefinitionFieldAst,
+        FieldAst, FileAst, FileStatementAst, FunctionArgumentAst, FunctionCallAst, FunctionCallType, FunctionDefinitionAst, IfConditionAst,
+        InterfaceDefinitionAst, InterfaceDefinitionFieldAst, InterfaceFunctionDefinitionAst, LambdaAst, MatchArmAst, MatchArmBodyAst, MatchAst, ModuleVariableAst, PatternAst, TypeAliasAst, TypeNameAst, UseAst, VariableDefinitionAst, VariableDefinitionType,
+    },
+    nom_tools::Span,
+};
+
+/// Indentation of one block level
+const INDENT: &str = "    ";
+
+/// Formats a parsed file, the comments are taken from the source code of the file
+pub fn format(file: &FileAst<'_>) -> String {
+    let mut printer = Printer::new(file.file.code());
+    printer.file(&file.statements, &file.attributes);
+    printer.finish()
+}
+
+/// How blank lines before a declaration or statement are handled
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BlankLine {
+    /// Declarations of a file are always separated by a blank line
+    Always,
+    /// A blank line in the source code is kept
+    Keep,
+}
+
+/// Comments and punctuation of the source code, outside of string literals
+struct Source<'base> {
+    code: &'base str,
+    /// Comments in source order, line comments end before the line break
+    comments: Vec<Range<usize>>,
+    /// Positions of `{`, `}` and `;` that are not part of a comment or a string
+    punctuation: Vec<(usize, u8)>,
+}
+
+impl<'base> Source<'base> {
+    fn scan(code: &'base str) -> Self {
+        let bytes = code.as_bytes();
+        let mut comments = Vec::new();
+        let mut punctuation = Vec::new();
+        let mut index = 0;
+
+        while index < bytes.len() {
+            match (bytes[index], bytes.get(index + 1)) {
+                (b'/', Some(b'/')) => {
+                    let mut end = code[index..].find('\n').map_or(code.len(), |offset| index + offset);
+                    while end > index && bytes[end - 1] == b'\r' {
+                        end -= 1;
+                    }
+                    comments.push(index..end);
+                    index = end;
+                },
+                (b'/', Some(b'*')) => {
+                    let end = code[index + 2..].find("*/").map_or(code.len(), |offset| index + 2 + offset + 2);
+                    comments.push(index..end);
+                    index = end;
+                },
+                (b'"', _) => {
+                    index += 1;
+                    while index < bytes.len() && bytes[index] != b'"' {
+                        index += if bytes[index] == b'\\' { 2 } else { 1 };
+                    }
+                    index += 1;
+                },
+                (byte @ (b'{' | b'}' | b';'), _) => {
+                    punctuation.push((index, byte));
+                    index += 1;
+                },
+                _ => index += 1,
+            }
+        }
+
+        Self { code, comments, punctuation }
+    }
+
+    /// Position of the first `punctuation` character at or after `from`
+    ///
+    /// Blocks opened after `from`, like the bodies of lambdas, are skipped.
+    fn find(&self, from: usize, punctuation: u8) -> usize {
+        let start = self.punctuation.partition_point(|(position, _)| *position < from);
+        let mut depth = 0usize;
+        for (position, byte) in self.punctuation[start..].iter() {
+            if *byte == punctuation && depth == 0 {
+                return *position;
+            }
+            match byte {
+                b'{' => depth += 1,
+                b'}' => depth = depth.saturating_sub(1),
+                _ => (),
+            }
+        }
+        self.code.len()
+    }
+
+    /// Position of the `}` closing the block opened at `open`
+    fn matching(&self, open: usize) -> usize {
+        let start = self.punctuation.partition_point(|(position, _)| *position < open);
+        let mut depth = 0;
+        for (position, byte) in self.punctuation[start..].iter() {
+            match byte {
+                b'{' => depth += 1,
+                b'}' if depth == 1 => return *position,
+                b'}' => depth -= 1,
+                _ => (),
+            }
+        }
+        self.code.len()
+    }
+
+    /// Moves back from `position` over whitespace and comments
+    fn skip_back(&self, mut position: usize) -> usize {
+        loop {
+            position = self.code[..position].trim_end().len();
+            match self.comments.binary_search_by_key(&position, |comment| comment.end) {
+                Ok(index) => position = self.comments[index].start,
+                Err(_) => return position,
+            }
+        }
+    }
+
+    /// Start of the `keyword` in front of `position`, or `position` when there is none
+    fn keyword_before(&self, position: usize, keyword: &str) -> usize {
+        let before = self.skip_back(position);
+        match self.code[..before].ends_with(keyword) {
+            true => before - keyword.len(),
+            false => position,
+        }
+    }
+
+    /// Text of a literal without the whitespace and comments the parser includes in its span
+    fn literal(&self, span: &Span<'_>) -> &'base str {
+        let mut start = span.position.start;
+        let mut end = start + span.text.len();
+
+        loop {
+            start = end - self.code[start..end].trim_start().len();
+            match self.comments.binary_search_by_key(&start, |comment| comment.start) {
+                Ok(index) if start < end => start = self.comments[index].end,
+                _ => break,
+            }
+        }
+
+        end = self.skip_back(end).max(start);
+        &self.code[start..end]
+    }
+
+    /// Whether there is an empty line between `from` and `to`
+    fn has_blank_line(&self, from: usize, to: usize) -> bool {
+        if from >= to {
+            return false;
+        }
+
+        let lines = self.code[from..to].split('\n').collect::<Vec<_>>();
+        lines.len() > 2 && lines[1..lines.len() - 1].iter().any(|line| line.trim().is_empty())
+    }
+
+    /// Whether there is code before the comment on its line
+    fn is_trailing(&self, comment: &Range<usize>) -> bool {
+        let line_start = self.code[..comment.start].rfind('\n').map_or(0, |position| position + 1);
+        !self.code[line_start..comment.start].trim().is_empty()
+    }
+
+    /// Text of a comment without trailing whitespace on its lines
+    fn comment_text(&self, comment: &Range<usize>) -> String {
+        self.code[comment.clone()].lines().map(str::trim_end).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Writes the formatted code and keeps track of the comments already written
+struct Printer<'base> {
+    source: Source<'base>,
+    /// Comments already written, shared with the printers of lambda bodies
+    printed: Vec<Cell<bool>>,
+    /// End of the last written node or comment in the source code
+    last_end: usize,
+    indent: usize,
+    output: String,
+}
+
+impl<'base> Printer<'base> {
+    fn new(code: &'base str) -> Self {
+        let source = Source::scan(code);
+        Self { printed: vec![Cell::new(false); source.comments.len()], source, last_end: 0, indent: 0, output: String::new() }
+    }
+
+    fn finish(mut self) -> String {
+        let end = self.source.code.len();
+        self.trailing_comments(end);
+        self.comments_before(end);
+        while self.output.ends_with("\n\n") {
+            self.output.pop();
+        }
+        self.output
+    }
+
+    fn line(&mut self, text: &str) {
+        for _ in 0..self.indent {
+            self.output.push_str(INDENT);
+        }
+        self.output.push_str(text);
+        self.output.push('\n');
+    }
+
+    /// Continues the last line, used for the `else` branches after a `}`
+    fn continue_line(&mut self, text: &str) {
+        self.output.pop();
+        self.output.push(' ');
+        self.output.push_str(text);
+        self.output.push('\n');
+    }
+
+    /// Adds an empty line, never at the start of the file or of a block and never two in a row
+    fn blank_line(&mut self) {
+        if !self.output.is_empty() && !self.output.ends_with("\n\n") && !self.output.ends_with("{\n") {
+            self.output.push('\n');
+        }
+    }
+
+    fn comment(&mut self, index: usize) {
+        let comment = self.source.comments[index].clone();
+        let text = self.source.comment_text(&comment);
+        self.printed[index].set(true);
+
+        if self.source.is_trailing(&comment) && !self.output.is_empty() && !self.output.ends_with("\n\n") {
+            self.continue_line(&text);
+        } else {
+            if self.source.has_blank_line(self.last_end, comment.start) {
+                self.blank_line();
+            }
+            self.line(&text);
+        }
+        self.last_end = self.last_end.max(comment.end);
+    }
+
+    /// Writes the comments that are not written yet and start before `position`
+    fn comments_before(&mut self, position: usize) {
+        for index in 0..self.source.comments.len() {
+            if self.source.comments[index].start >= position {
+                break;
+            }
+            if !self.printed[index].get() {
+                self.comment(index);
+            }
+        }
+    }
+
+    /// Writes the comments before `position` that follow code on their line
+    fn trailing_comments(&mut self, position: usize) {
+        for index in 0..self.source.comments.len() {
+            let comment = &self.source.comments[index];
+            if comment.start >= position {
+                break;
+            }
+            if self.printed[index].get() {
+                continue;
+            }
+            if !self.source.is_trailing(comment) {
+                break;
+            }
+            self.comment(index);
+        }
+    }
+
+    /// Writes the comments in front of a node that starts at `key` and separates it from the previous one
+    fn node_start(&mut self, key: usize, blank_line: BlankLine) {
+        self.trailing_comments(key);
+        if blank_line == BlankLine::Always {
+            self.blank_line();
+        }
+        self.comments_before(key);
+        if self.source.has_blank_line(self.last_end, key) {
+            self.blank_line();
+        }
+    }
+
+    /// Writes a block opened at `open` with one node per item
+    ///
+    /// A block without items and comments is written as `{}` unless `expand` is set.
+    fn block<T>(&mut self, header: &str, chained: bool, open: usize, items: &[T], expand: bool, item: impl Fn(&mut Self, &T)) {
+        let close = self.source.matching(open);
+        let has_comments = (0..self.source.comments.len()).any(|index| !self.printed[index].get() && self.source.comments[index].start < close);
+
+        if items.is_empty() && !expand && !has_comments {
+            match chained {
+                true => self.continue_line(&format!("{header} {{}}")),
+                false => self.line(&format!("{header} {{}}")),
+            };
+            self.last_end = close + 1;
+            return;
+        }
+
+        match chained {
+            true => self.continue_line(&format!("{header} {{")),
+            false => self.line(&format!("{header} {{")),
+        };
+        self.last_end = open + 1;
+        self.indent += 1;
+        for value in items {
+            item(self, value);
+        }
+        self.trailing_comments(close);
+        self.comments_before(close);
+        self.indent -= 1;
+        self.line("}");
+        self.last_end = close + 1;
+    }
+
+    fn file(&mut self, statements: &[FileStatementAst<'_>], attributes: &[AttributeAst<'_>]) {
+        let keys = statements.iter().map(|statement| self.statement_key(statement)).collect::<Vec<_>>();
+        let ends = statements.iter().map(|statement| self.statement_end(statement)).collect::<Vec<_>>();
+        let attributes = keys.iter()
+            .map(|key| attributes.iter().filter(|attribute| attribute.target.start == *key).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        // A statement starts at its first attribute
+        let starts = keys.iter().zip(attributes.iter())
+            .map(|(key, attributes)| attributes.first().map_or(*key, |attribute| attribute.position.start))
+            .collect::<Vec<_>>();
+
+        if let Some(first_start) = starts.first() {
+            self.header(*first_start);
+        }
+
+        let mut uses = Vec::new();
+        for (index, statement) in statements.iter().enumerate() {
+            if let FileStatementAst::Use(import) = statement {
+                let previous_end = index.checked_sub(1).map_or(0, |previous| ends[previous]);
+                let next_start = starts.get(index + 1).copied().unwrap_or(self.source.code.len());
+                uses.push(self.use_lines(import, &attributes[index], previous_end, keys[index], ends[index], next_start));
+            }
+        }
+
+        uses.sort_by(|(left, _), (right, _)| left.cmp(right));
+        if !uses.is_empty() {
+            self.blank_line();
+        }
+        for (line, comments) in uses {
+            for comment in comments {
+                self.line(&comment);
+            }
+            self.line(&line);
+        }
+
+        for (index, statement) in statements.iter().enumerate() {
+            if index > 0 && matches!(statements[index - 1], FileStatementAst::Use(_)) {
+                self.last_end = self.last_end.max(ends[index - 1]);
+            }
+
+            if matches!(statement, FileStatementAst::Use(_)) {
+                continue;
+            }
+
+            self.node_start(starts[index], BlankLine::Always);
+            self.attributes(&attributes[index], keys[index]);
+            match statement {
+                FileStatementAst::Use(_) => unreachable!(),
+                FileStatementAst::Class(class) => self.class(class),
+                FileStatementAst::Function(function) => self.function(function),
+                FileStatementAst::Interface(interface) => self.interface(interface),
+                FileStatementAst::Extend(extend) => self.extend(extend),
+                FileStatementAst::TypeAlias(alias) => self.type_alias(alias),
+                FileStatementAst::Enum(definition) => self.enum_definition(definition),
+                FileStatementAst::Variable(variable) => self.module_variable(variable),
+            }
+        }
+    }
+
+    /// Writes the attributes of a declaration that starts at `key`, one per line
+    fn attributes(&mut self, attributes: &[&AttributeAst<'_>], key: usize) {
+        for (index, attribute) in attributes.iter().enumerate() {
+            if index > 0 {
+                self.trailing_comments(attribute.position.start);
+                self.comments_before(attribute.position.start);
+            }
+            self.line(&attribute.to_string());
+            self.last_end = attribute.position.end;
+        }
+
+        if !attributes.is_empty() {
+            self.trailing_comments(key);
+            self.comments_before(key);
+        }
+    }
+
+    /// Writes the comments at the top of the file that are separated from the code by a blank line
+    fn header(&mut self, first_key: usize) {
+        let candidates = self.source.comments.iter().take_while(|comment| comment.start < first_key).collect::<Vec<_>>();
+        let header = (0..candidates.len()).rev().find(|index| {
+            let next = candidates.get(index + 1).map_or(first_key, |comment| comment.start);
+            self.source.has_blank_line(candidates[*index].end, next)
+        });
+
+        if let Some(last) = header {
+            for index in 0..=last {
+                self.comment(index);
+            }
+            self.blank_line();
+        }
+    }
+
+    /// Line of a `use` statement and the lines that move with it when the imports are sorted
+    ///
+    /// The attributes and the comments on the lines above the statement come
+    /// first, the comments inside of it and after it on the same line are added
+    /// to its line.
+    fn use_lines(&mut self, import: &UseAst<'_>, attributes: &[&AttributeAst<'_>], previous_end: usize, key: usize, end: usize, next_key: usize) -> (String, Vec<String>) {
+        let mut line = import.to_string();
+
+        let mut leading = attributes.iter().map(|attribute| (attribute.position.start, attribute.to_string())).collect::<Vec<_>>();
+        for index in 0..self.source.comments.len() {
+            let comment = self.source.comments[index].clone();
+            if self.printed[index].get() || comment.start < previous_end {
+                continue;
+            }
+            if comment.start >= next_key {
+                break;
+            }
+
+            let attribute = leading.iter().rposition(|(start, _)| *start < comment.start && attributes.iter().any(|attribute| attribute.position.start == *start));
+            if comment.start < key && self.source.is_trailing(&comment) && let Some(attribute) = attribute {
+                leading[attribute].1.push(' ');
+                leading[attribute].1.push_str(&self.source.comment_text(&comment));
+            } else if comment.start < key && !self.source.is_trailing(&comment) {
+                leading.push((comment.start, self.source.comment_text(&comment)));
+            } else if comment.start >= key && (comment.start < end || !self.source.code[end..comment.start].contains('\n')) {
+                line.push(' ');
+                line.push_str(&self.source.comment_text(&comment));
+            } else {
+                continue;
+            }
+            self.printed[index].set(true);
+        }
+
+        leading.sort_by_key(|(start, _)| *start);
+        (line, leading.into_iter().map(|(_, text)| text).collect())
+    }
+
+    fn statement_key(&self, statement: &FileStatementAst<'_>) -> usize {
+        match statement {
+            FileStatementAst::Use(import) => match &import.is_public {
+                Some(is_public) => is_public.position.start,
+                None => self.source.keyword_before(import.import.paths[0].position.start, "use"),
+            },
+            FileStatementAst::Class(class) => match &class.is_public {
+                Some(is_public) => is_public.position.start,
+                None => self.source.keyword_before(class.name.position.start, "class"),
+            },
+            FileStatementAst::Function(function) => self.function_key(function),
+            FileStatementAst::Interface(interface) => self.source.keyword_before(interface.name.position.start, "interface"),
+            FileStatementAst::Extend(extend) => self.source.keyword_before(extend.name.names_span.position.start, "extend"),
+            FileStatementAst::TypeAlias(alias) => match &alias.is_public {
+                Some(is_public) => is_public.position.start,
+                None => self.source.keyword_before(alias.name.position.start, "type"),
+            },
+            FileStatementAst::Enum(definition) => match &definition.is_public {
+                Some(is_public) => is_public.position.start,
+                None => self.source.keyword_before(definition.name.position.start, "enum"),
+            },
+            FileStatementAst::Variable(variable) => match &variable.is_public {
+                Some(is_public) => is_public.position.start,
+                None => self.source.keyword_before(variable.definition.name.position.start, &variable.definition.variable_definition_type.to_string()),
+            },
+        }
+    }
+
+    fn statement_end(&self, statement: &FileStatementAst<'_>) -> usize {
+        match statement {
+            FileStatementAst::Use(import) => {
+                let last = import.alias.as_ref().or(import.import.paths.last()).map_or(0, end);
+                self.source.find(last, b';') + 1
+            },
+            FileStatementAst::Class(class) => self.source.matching(self.source.find(end(&class.name), b'{')) + 1,
+            FileStatementAst::Function(function) => self.function_end(function),
+            FileStatementAst::Interface(interface) => self.source.matching(self.source.find(end(&interface.name), b'{')) + 1,
+            FileStatementAst::Extend(extend) => self.source.matching(self.source.find(end(&extend.name.names_span), b'{')) + 1,
+            FileStatementAst::TypeAlias(alias) => self.source.find(end(&alias.target.names_span), b';') + 1,
+            FileStatementAst::Enum(definition) => self.source.matching(self.source.find(end(&definition.name), b'{')) + 1,
+            FileStatementAst::Variable(variable) => self.source.find(end(&variable.definition.name), b';') + 1,
+        }
+    }
+
+    fn class(&mut self, class: &ClassDefinitionAst<'_>) {
+        let header = format!("{}class {}{}", visibility(&class.is_public), class.name.text, base_types(class.base_class.as_slice()));
+        let open = self.source.find(end(&class.name), b'{');
+        self.block(&header, false, open, &class.fields, false, |printer, field| match field {
+            ClassDefinitionFieldAst::Field(field) => printer.field(field),
+            ClassDefinitionFieldAst::Function(function) => {
+                printer.node_start(printer.function_key(function), BlankLine::Keep);
+                printer.function(function);
+            },
+        });
+    }
+
+    fn interface(&mut self, interface: &InterfaceDefinitionAst<'_>) {
+        let header = format!("interface {}{}", interface.name.text, base_types(&interface.base_interfaces));
+        let open = self.source.find(end(&interface.name), b'{');
+        self.block(&header, false, open, &interface.fields, false, |printer, field| match field {
+            InterfaceDefinitionFieldAst::Field(field) => printer.field(field),
+            InterfaceDefinitionFieldAst::Function(function) => printer.interface_function(function),
+        });
+    }
+
+    fn extend(&mut self, extend: &ExtendDefinitionAst<'_>) {
+        let header = format!("extend {}{}", extend.name, base_types(&extend.base_interfaces));
+        let open = self.source.find(end(&extend.name.names_span), b'{');
+        self.block(&header, false, open, &extend.fields, false, |printer, field| match field {
+            ExtendDefinitionFieldAst::Field(field) => printer.field(field),
+            ExtendDefinitionFieldAst::Function(function) => {
+                printer.node_start(printer.function_key(function), BlankLine::Keep);
+                printer.function(function);
+            },
+        });
+    }
+
+    fn type_alias(&mut self, alias: &TypeAliasAst<'_>) {
+        self.line(&format!("{}type {} = {};", visibility(&alias.is_public), alias.name.text, alias.target));
+        self.last_end = self.source.find(end(&alias.target.names_span), b';') + 1;
+    }
+
+    fn enum_definition(&mut self, definition: &EnumDefinitionAst<'_>) {
+        let header = format!("{}enum {}", visibility(&definition.is_public), definition.name.text);
+        let open = self.source.find(end(&definition.name), b'{');
+        self.block(&header, false, open, &definition.variants, false, |printer, variant| {
+            printer.node_start(variant.name.position.start, BlankLine::Keep);
+            printer.line(&format!("{variant},"));
+            printer.last_end = match variant.payload.last() {
+                Some(last) => printer.source.find(end(&last.names_span), b')') + 1,
+                None => end(&variant.name),
+            };
+        });
+    }
+
+    fn field(&mut self, field: &FieldAst<'_>) {
+        let key = field.is_public.as_ref().or(field.is_static.as_ref()).unwrap_or(&field.name).position.start;
+        self.node_start(key, BlankLine::Keep);
+        let is_static = if field.is_static.is_some() { "static " } else { "" };
+        self.line(&format!("{}{is_static}{}: {};", visibility(&field.is_public), field.name.text, field.field_type));
+        self.last_end = self.source.find(end(&field.field_type.names_span), b';') + 1;
+    }
+
+    fn interface_function(&mut self, function: &InterfaceFunctionDefinitionAst<'_>) {
+        self.node_start(self.source.keyword_before(function.name.position.start, "func"), BlankLine::Keep);
+        let header = format!("func {}({}): {}", function.name.text, arguments(&function.arguments), function.return_type);
+
+        match &function.body {
+            Some(body) => {
+                let open = self.source.find(end(&function.return_type.names_span), b'{');
+                self.block(&header, false, open, &body.statements, false, Self::body_statement);
+            },
+            None => {
+                self.line(&format!("{header};"));
+                self.last_end = self.source.find(end(&function.return_type.names_span), b';') + 1;
+            },
+        }
+    }
+
+    fn function_key(&self, function: &FunctionDefinitionAst<'_>) -> usize {
+        [&function.is_public, &function.is_const, &function.is_native, &function.is_extern].into_iter()
+            .flatten()
+            .map(|modifier| modifier.position.start)
+            .min()
+            .unwrap_or_else(|| self.source.keyword_before(function.name.position.start, "func"))
+    }
+
+    fn function_end(&self, function: &FunctionDefinitionAst<'_>) -> usize {
+        let return_type_end = end(&function.return_type.names_span);
+        match function.has_body() {
+            true => self.source.matching(self.source.find(return_type_end, b'{')) + 1,
+            false => self.source.find(return_type_end, b';') + 1,
+        }
+    }
+
+    fn function(&mut self, function: &FunctionDefinitionAst<'_>) {
+        let modifier = match (&function.is_const, &function.is_native, &function.is_extern) {
+            (Some(_), _, _) => "const ",
+            (_, Some(_), _) => "native ",
+            (_, _, Some(_)) => "extern ",
+            _ => "",
+        };
+        let header = format!("{}{modifier}func {}({}): {}", visibility(&function.is_public), function.name.text, arguments(&function.arguments), function.return_type);
+
+        match function.has_body() {
+            true => {
+                let open = self.source.find(end(&function.return_type.names_span), b'{');
+                self.block(&header, false, open, &function.body.statements, false, Self::body_statement);
+            },
+            false => {
+                self.line(&format!("{header};"));
+                self.last_end = self.function_end(function);
+            },
+        }
+    }
+
+    fn module_variable(&mut self, variable: &ModuleVariableAst<'_>) {
+        self.variable_definition(visibility(&variable.is_public), &variable.definition);
+    }
+
+    fn variable_definition(&mut self, visibility: &str, variable: &VariableDefinitionAst<'_>) {
+        let name = match &variable.destructure {
+            Some(destructure) => format!("({})", destructure.iter().map(|name| name.text).collect::<Vec<_>>().join(", ")),
+            None => variable.name.text.to_string(),
+        };
+
+        let mut line = format!("{visibility}{} {name}", variable.variable_definition_type);
+        if let Some(expected_type) = &variable.expected_type {
+            line.push_str(&format!(": {expected_type}"));
+        }
+        if let Some(expression) = &variable.expression {
+            line.push_str(&format!(" = {}", self.expression(expression)));
+        }
+        line.push(';');
+        self.line(&line);
+        self.last_end = self.source.find(end(&variable.name), b';') + 1;
+    }
+
+    fn body_statement(&mut self, statement: &BodyStatementAst<'_>) {
+        match statement {
+            BodyStatementAst::VariableDefinition(variable) => {
+                let keyword = match variable.variable_definition_type {
+                    VariableDefinitionType::Var => "var",
+                    VariableDefinitionType::Const => "const",
+                };
+                self.node_start(self.source.keyword_before(variable.name.position.start, keyword), BlankLine::Keep);
+                self.variable_definition("", variable);
+            },
+            BodyStatementAst::VariableAssign(assign) => {
+                self.node_start(assign.path.first().unwrap_or(&assign.name).position.start, BlankLine::Keep);
+                let path = assign.path.iter().map(|name| format!("{}.", name.text)).collect::<String>();
+                self.line(&format!("{path}{} = {};", assign.name.text, self.expression(&assign.expression)));
+                self.last_end = self.source.find(end(&assign.name), b';') + 1;
+            },
+            BodyStatementAst::FunctionCall(call) => {
+                self.node_start(self.call_key(call), BlankLine::Keep);
+                self.line(&format!("{};", self.function_call(call)));
+                self.last_end = self.source.find(end(&call.arguments_span), b';') + 1;
+            },
+            BodyStatementAst::Return(statement) => {
+                self.node_start(statement.span.position.start, BlankLine::Keep);
+                match &statement.expression {
+                    Some(expression) => self.line(&format!("return {};", self.expression(expression))),
+                    None => self.line("return;"),
+                };
+                self.last_end = self.source.find(end(&statement.span), b';') + 1;
+            },
+            BodyStatementAst::IfCondition(condition) => self.if_condition(condition),
+            BodyStatementAst::Match(match_ast) => {
+                self.node_start(match_ast.span.position.start, BlankLine::Keep);
+                self.line(&self.match_expression(match_ast));
+                self.last_end = match_ast.span.position.end;
+            },
+        }
+    }
+
+    fn if_condition(&mut self, condition: &IfConditionAst<'_>) {
+        let mut key = self.expression_start(&condition.expression);
+        loop {
+            let before = self.source.skip_back(key);
+            match self.source.code[..before].ends_with(['(', '!']) {
+                true => key = before - 1,
+                false => break,
+            }
+        }
+        self.node_start(self.source.keyword_before(key, "if"), BlankLine::Keep);
+
+        let expand = !condition.else_ifs.is_empty() || condition.false_body.is_some();
+        let open = self.source.find(key, b'{');
+        let header = format!("if {}", self.expression(&condition.expression));
+        self.block(&header, false, open, &condition.true_body.statements, expand, Self::body_statement);
+
+        for (expression, body) in condition.else_ifs.iter() {
+            let open = self.source.find(self.last_end, b'{');
+            let header = format!("else if {}", self.expression(expression));
+            self.block(&header, true, open, &body.statements, expand, Self::body_statement);
+        }
+
+        if let Some(body) = &condition.false_body {
+            let open = self.source.find(self.last_end, b'{');
+            self.block("else", true, open, &body.statements, expand, Self::body_statement);
+        }
+    }
+
+    fn call_key(&self, call: &FunctionCallAst<'_>) -> usize {
+        match &call.path {
+            FunctionCallType::This(paths) => {
+                let start = paths.first().map_or(call.call_span.position.start, |path| path.position.start);
+                self.source.keyword_before(self.source.keyword_before(start, "."), "this")
+            },
+            FunctionCallType::Direct(paths) => paths.first().map_or(call.call_span.position.start, |path| path.position.start),
+        }
+    }
+
+    /// Position of the first token of an expression, opening parentheses are not included
+    fn expression_start(&self, expression: &ExpressionAst<'_>) -> usize {
+        match expression {
+            ExpressionAst::Primitive { span, .. } => {
+                let literal = self.source.literal(span);
+                literal.as_ptr() as usize - self.source.code.as_ptr() as usize
+            },
+            ExpressionAst::Ref(reference) => self.source.keyword_before(reference.names[0].position.start, "ref"),
+            ExpressionAst::Not(expression) => self.expression_start(expression),
+            ExpressionAst::Ident(ident) => ident.position.start,
+            ExpressionAst::FunctionCall(call) => self.call_key(call),
+            ExpressionAst::Operation { left, .. } => self.expression_start(left),
+            ExpressionAst::Lambda(lambda) => lambda.span.position.start,
+            ExpressionAst::Match(match_ast) => match_ast.span.position.start,
+            ExpressionAst::Tuple(tuple) => tuple.span.position.start,
+        }
+    }
+
+    fn expression(&self, expression: &ExpressionAst<'_>) -> String {
+        match expression {
+            ExpressionAst::Primitive { span, .. } => self.source.literal(span).to_string(),
+            ExpressionAst::Ref(reference) => format!("ref {}", names(&reference.names)),
+            ExpressionAst::Not(expression) => match expression.as_ref() {
+                ExpressionAst::Operation { .. } => format!("!({})", self.expression(expression)),
+                _ => format!("!{}", self.expression(expression)),
+            },
+            ExpressionAst::Ident(ident) => ident.text.to_string(),
+            ExpressionAst::FunctionCall(call) => self.function_call(call),
+            ExpressionAst::Operation { left, operator, right } => {
+                let precedence = precedence(*operator);
+                let left = self.operand(left, |inner| inner < precedence);
+                let right = self.operand(right, |inner| inner <= precedence);
+                format!("{left} {operator} {right}")
+            },
+            ExpressionAst::Lambda(lambda) => self.lambda(lambda),
+            ExpressionAst::Match(match_ast) => self.match_expression(match_ast),
+            ExpressionAst::Tuple(tuple) => format!("({})", tuple.elements.iter().map(|element| self.expression(element)).collect::<Vec<_>>().join(", ")),
+        }
+    }
+
+    /// Lambda with its body formatted like a block
+    ///
+    /// The lines after the first one are already indented for the line the lambda is written on.
+    fn lambda(&self, lambda: &LambdaAst<'_>) -> String {
+        let modifier = match lambda.is_ref {
+            Some(_) => "ref ",
+            None => "",
+        };
+        let header = format!("{modifier}func({}): {}", arguments(&lambda.arguments), lambda.return_type);
+        let open = self.source.find(end(&lambda.return_type.names_span), b'{');
+        self.nested(|printer| printer.block(&header, false, open, &lambda.body.statements, false, Self::body_statement))
+    }
+
+    /// Match with one arm per line, block arms are formatted like blocks
+    ///
+    /// Like lambdas, the lines after the first one are already indented.
+    fn match_expression(&self, match_ast: &MatchAst<'_>) -> String {
+        let header = format!("match ({})", self.expression(&match_ast.expression));
+        let open = self.source.find(match_ast.span.position.start, b'{');
+        let close = self.source.matching(open);
+
+        // An expression arm ends where the next arm starts, without the whitespace and comments in between
+        let keys = match_ast.arms.iter().map(|arm| self.pattern_start(&arm.pattern)).chain([close]).collect::<Vec<_>>();
+        let arms = match_ast.arms.iter().zip(keys.windows(2)).map(|(arm, keys)| (arm, keys[0], self.source.skip_back(keys[1]))).collect::<Vec<_>>();
+        self.nested(|printer| printer.block(&header, false, open, &arms, false, Self::match_arm))
+    }
+
+    fn match_arm(&mut self, (arm, key, end): &(&MatchArmAst<'_>, usize, usize)) {
+        self.node_start(*key, BlankLine::Keep);
+        let pattern = match &arm.pattern {
+            PatternAst::Literal { span, .. } => self.source.literal(span).to_string(),
+            pattern => pattern.to_string(),
+        };
+
+        match &arm.body {
+            MatchArmBodyAst::Expression(expression) => {
+                self.line(&format!("{pattern} => {},", self.expression(expression)));
+                self.last_end = *end;
+            },
+            MatchArmBodyAst::Block(body) => {
+                let open = self.source.find(*key, b'{');
+                self.block(&format!("{pattern} =>"), false, open, &body.statements, false, Self::body_statement);
+            },
+        }
+    }
+
+    /// Position of the first token of a pattern
+    fn pattern_start(&self, pattern: &PatternAst<'_>) -> usize {
+        match pattern {
+            PatternAst::Literal { span, .. } => {
+                let literal = self.source.literal(span);
+                literal.as_ptr() as usize - self.source.code.as_ptr() as usize
+            },
+            PatternAst::Null(span) | PatternAst::Wildcard(span) => span.position.start,
+            PatternAst::Variant { span, .. } => span.position.start,
+            PatternAst::Class { binding, .. } => binding.position.start,
+        }
+    }
+
+    /// Output of a printer that shares the written comments, used for blocks inside expressions
+    fn nested(&self, print: impl FnOnce(&mut Printer<'base>)) -> String {
+        let mut printer = Printer::new(self.source.code);
+        printer.printed = self.printed.clone();
+        printer.indent = self.indent;
+        print(&mut printer);
+        for (printed, nested) in self.printed.iter().zip(printer.printed.iter()) {
+            printed.set(printed.get() || nested.get());
+        }
+        printer.output.trim().to_string()
+    }
+
+    /// Operand of a binary operation, in parentheses when `needs_parentheses` holds for its precedence
+    fn operand(&self, expression: &ExpressionAst<'_>, needs_parentheses: impl Fn(u8) -> bool) -> String {
+        match expression {
+            ExpressionAst::Operation { operator, .. } if needs_parentheses(precedence(*operator)) => format!("({})", self.expression(expression)),
+            _ => self.expression(expression),
+        }
+    }
+
+    fn function_call(&self, call: &FunctionCallAst<'_>) -> String {
+        let path = match &call.path {
+            FunctionCallType::This(paths) if paths.is_empty() => "this".to_string(),
+            FunctionCallType::This(paths) => format!("this.{}", names(paths)),
+            FunctionCallType::Direct(paths) => names(paths),
+        };
+        let arguments = call.arguments.iter().map(|argument| self.expression(argument)).collect::<Vec<_>>();
+        format!("{path}({})", arguments.join(", "))
+    }
+}
+
+/// Binding strength of an operator, operators with a higher value bind tighter
+///
+/// The levels follow the expression parser, where every operator is left associative.
+fn precedence(operator: ExpressionOperatorType) -> u8 {
+    match operator {
+        ExpressionOperatorType::Or => 1,
+        ExpressionOperatorType::And => 2,
+        ExpressionOperatorType::Xor => 3,
+        ExpressionOperatorType::LogicalOr => 4,
+        ExpressionOperatorType::LogicalAnd => 5,
+        ExpressionOperatorType::Equal | ExpressionOperatorType::NotEqual => 6,
+        ExpressionOperatorType::LessThan | ExpressionOperatorType::GreaterThan | ExpressionOperatorType::LessEqualThan | ExpressionOperatorType::GreaterEqualThan => 7,
+        ExpressionOperatorType::BitwiseShiftLeft | ExpressionOperatorType::BitwiseShiftRight => 8,
+        ExpressionOperatorType::Add | ExpressionOperatorType::Sub => 9,
+        ExpressionOperatorType::Mul | ExpressionOperatorType::Div | ExpressionOperatorType::Mod => 10,
+    }
+}
+
+fn end(span: &Span<'_>) -> usize {
+    span.position.start + span.text.len()
+}
+
+fn names(names: &[Span<'_>]) -> String {
+    names.iter().map(|name| name.text).collect::<Vec<_>>().join(".")
+}
+
+fn visibility(is_public: &Option<Span<'_>>) -> &'static str {
+    match is_public {
+        Some(_) => "pub ",
+        None => "",
+    }
+}
+
+fn arguments(arguments: &[FunctionArgumentAst<'_>]) -> String {
+    arguments.iter().map(|argument| argument.to_string()).collect::<Vec<_>>().join(", ")
+}
+
+fn base_types(base_types: &[TypeNameAst<'_>]) -> String {
+    match base_types.is_empty() {
+        true => String::new(),
+        false => format!(": {}", base_types.iter().map(|base_type| base_type.to_string()).collect::<Vec<_>>().join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::{ast::FileStatementAst, file::SourceFile, nom_tools::State, process_code};
+
+    fn format(code: &str) -> String {
+        let state = State::new(SourceFile::new(vec!["main".into()], code.to_string()));
+        super::format(&process_code(&state).unwrap())
+    }
+
+    /// The declarations of a file without positions, the imports are sorted since formatting sorts them
+    fn shape(code: &str) -> Vec<String> {
+        let state = State::new(SourceFile::new(vec!["main".into()], code.to_string()));
+        let file = process_code(&state).unwrap();
+        let (mut uses, declarations): (Vec<_>, Vec<_>) = file.statements.iter().partition(|statement| matches!(statement, FileStatementAst::Use(_)));
+        uses.sort_by_key(|statement| statement.to_string());
+        uses.into_iter().chain(declarations).map(|statement| statement.to_string())
+            .chain(file.attributes.iter().map(|attribute| attribute.to_string()))
+            .collect()
+    }
+
+    #[rstest]
+    #[case("class   Point {x:i32;pub  y : ?i32;}", "class Point {\n    x: i32;\n    pub y: ?i32;\n}\n")]
+    #[case("class Empty {   }", "class Empty {}\n")]
+    #[case("class Counter {pub   static count:i32; func next(): i32 {return 1;}}", "class Counter {\n    pub static count: i32;\n    func next(): i32 {\n        return 1;\n    }\n}\n")]
+    #[case("pub  type Meters=lib.Length ;type Id = i32;", "pub type Meters = lib.Length;\n\ntype Id = i32;\n")]
+    #[case("pub  enum Shape{Circle( float ),Rect(float,float),Empty}enum Never{ }", "pub enum Shape {\n    Circle(float),\n    Rect(float, float),\n    Empty,\n}\n\nenum Never {}\n")]
+    #[case("enum Color { // Primary\n  Red, // First\n\n  Blue,\n}", "enum Color { // Primary\n    Red, // First\n\n    Blue,\n}\n")]
+    #[case("pub  const LIMIT:i32=10 ;var counter=LIMIT*2;", "pub const LIMIT: i32 = 10;\n\nvar counter = LIMIT * 2;\n")]
+    #[case("pub class Child:base.Point{z:i32;}", "pub class Child: base.Point {\n    z: i32;\n}\n")]
+    #[case("func test(): i32 {\nreturn 1+2;\n}", "func test(): i32 {\n    return 1 + 2;\n}\n")]
+    #[case("func test(a:i32,b:i32): i32 { return (a+b)*(a-b); }", "func test(a: i32, b: i32): i32 {\n    return (a + b) * (a - b);\n}\n")]
+    #[case("func test(): i32 { return ((1*2)+3)-(4-5); }", "func test(): i32 {\n    return 1 * 2 + 3 - (4 - 5);\n}\n")]
+    #[case("func test(): bool { return !(true&&false)||!true; }", "func test(): bool {\n    return !(true && false) || !true;\n}\n")]
+    #[case("func test(): i32 { var a:i32=1;a=a   +1; call(a,ref b); this.run(); return; }", "func test(): i32 {\n    var a: i32 = 1;\n    a = a + 1;\n    call(a, ref b);\n    this.run();\n    return;\n}\n")]
+    #[case("func test(): i32 { if (a) {} }", "func test(): i32 {\n    if a {}\n}\n")]
+    #[case("func test(): i32 { if a {return 1;} else if b {} else {return 2;} }", "func test(): i32 {\n    if a {\n        return 1;\n    } else if b {\n    } else {\n        return 2;\n    }\n}\n")]
+    #[case("pub   native func print(a: string): void;", "pub native func print(a: string): void;\n")]
+    #[case("interface Shape:Base,Other{func area(this):double;size:i32;}", "interface Shape: Base, Other {\n    func area(this): double;\n    size: i32;\n}\n")]
+    #[case("interface Shape{func area(this):i32;func twice(this):i32{return this.area()*2;}}", "interface Shape {\n    func area(this): i32;\n    func twice(this): i32 {\n        return this.area() * 2;\n    }\n}\n")]
+    #[case("extend Point:Shape{func area(this):double{return 1.50;}}", "extend Point: Shape {\n    func area(this): double {\n        return 1.50;\n    }\n}\n")]
+    #[case("class A {} func b(): void {}\n\n\n\nclass C {}", "class A {}\n\nfunc b(): void {}\n\nclass C {}\n")]
+    #[case("func test(): void {\n    a();\n\n\n\n    b();\n    c();\n}", "func test(): void {\n    a();\n\n    b();\n    c();\n}\n")]
+    #[case("class A {}\nuse std.io as io;\nuse net.http;\nuse a;", "use a;\nuse net.http;\nuse std.io as io;\n\nclass A {}\n")]
+    #[case("use b . { X,Y as Z, } ;\npub   use a.*;", "pub use a.*;\nuse b.{X, Y as Z};\n")]
+    #[case("func test(): string { return \"a  \\\"b\\\"  c\"; }", "func test(): string {\n    return \"a  \\\"b\\\"  c\";\n}\n")]
+    #[case("", "")]
+    fn style(#[case] code: &str, #[case] expected: &str) {
+        assert_eq!(format(code), expected, "{code}");
+    }
+
+    #[rstest]
+    #[case("// Header\n\nclass A {}", "// Header\n\nclass A {}\n")]
+    #[case("// About A\nclass A {} // After A\n/* About B */ class B {}", "// About A\nclass A {} // After A\n\n/* About B */\nclass B {}\n")]
+    #[case("class A { // Opening\n  // About x\n  x: i32; // After x\n  // Closing\n}", "class A { // Opening\n    // About x\n    x: i32; // After x\n    // Closing\n}\n")]
+    #[case("class A { /* Inside */ }", "class A { /* Inside */\n}\n")]
+    #[case("class A {\n  /* Inside */\n}", "class A {\n    /* Inside */\n}\n")]
+    #[case("func test(): i32 {\n    // First\n    var a = 1;\n\n    // Second\n    return a; // Done\n}", "func test(): i32 {\n    // First\n    var a = 1;\n\n    // Second\n    return a; // Done\n}\n")]
+    #[case("// Header\n\n// About b\nuse b; // After b\nuse a;\n", "// Header\n\nuse a;\n// About b\nuse b; // After b\n")]
+    #[case("func test(): i32 {\n    return 4 /* Half */ / 2;\n}", "func test(): i32 {\n    return 4 / 2; /* Half */\n}\n")]
+    #[case("class A {}\n/*\n * Block\n */\n// Last", "class A {}\n/*\n * Block\n */\n// Last\n")]
+    #[case("// Only a comment", "// Only a comment\n")]
+    fn comments(#[case] code: &str, #[case] expected: &str) {
+        assert_eq!(format(code), expected, "{code}");
+    }
+
+    #[rstest]
+    #[case("func apply(callback:func(i32,i32):i32, other: ?func ( ) : void):func(ref string):bool {}", "func apply(callback: func(i32, i32): i32, other: ?func(): void): func(ref string): bool {}\n")]
+    #[case("func test(): func(i32): i32 { return func(a:i32):i32{return a*2;}; }", "func test(): func(i32): i32 {\n    return func(a: i32): i32 {\n        return a * 2;\n    };\n}\n")]
+    #[case("func test(): void { var total = 0; var add = ref func(v: i32): void { total = total + v; // Sum\n }; run(func(): void {}, 1); }", "func test(): void {\n    var total = 0;\n    var add = ref func(v: i32): void {\n        total = total + v; // Sum\n    };\n    run(func(): void {}, 1);\n}\n")]
+    fn lambdas(#[case] code: &str, #[case] expected: &str) {
+        assert_eq!(format(code), expected, "{code}");
+        assert_eq!(format(expected), expected);
+    }
+
+    #[rstest]
+    #[case("func test(a: i32): string { return match (a) {1=>\"one\",2=>\"two\" ,_=>\"many\"}; }", "func test(a: i32): string {\n    return match (a) {\n        1 => \"one\",\n        2 => \"two\",\n        _ => \"many\",\n    };\n}\n")]
+    #[case("func test(a: ?Shape): void { match (a) { null=>{log(1);} // Empty\n shape:Circle => draw(shape), _ => {} }; }", "func test(a: ?Shape): void {\n    match (a) {\n        null => {\n            log(1);\n        } // Empty\n        shape: Circle => draw(shape),\n        _ => {}\n    }\n}\n")]
+    #[case("func test(a: Shape): double { return match (a) {Shape.Rect( w,h )=>w*h, Shape.Circle(_) => 0 ,Empty=>1}; }", "func test(a: Shape): double {\n    return match (a) {\n        Shape.Rect(w, h) => w * h,\n        Shape.Circle(_) => 0,\n        Empty => 1,\n    };\n}\n")]
+    #[case("func test(a: i32): i32 { return match (a) {}; }", "func test(a: i32): i32 {\n    return match (a) {};\n}\n")]
+    #[case("func test(a: i32): i32 { return match a { _ => 1 }; }", "func test(a: i32): i32 {\n    return match (a) {\n        _ => 1,\n    };\n}\n")]
+    fn matches(#[case] code: &str, #[case] expected: &str) {
+        assert_eq!(format(code), expected, "{code}");
+        assert_eq!(format(expected), expected);
+    }
+
+    #[rstest]
+    #[case("func divide(a:i32,b:i32):(i32,i32){return (a/b ,a%b);}", "func divide(a: i32, b: i32): (i32, i32) {\n    return (a / b, a % b);\n}\n")]
+    #[case("func test(): void { const (x,y):(double, ?(bool,string)) = ( 1,(true , \"a\")); }", "func test(): void {\n    const (x, y): (double, ?(bool, string)) = (1, (true, \"a\"));\n}\n")]
+    #[case("func test(pair: (i32, i32)): i32 { var first = ref pair.0; return (first + 1) * 2; }", "func test(pair: (i32, i32)): i32 {\n    var first = ref pair.0;\n    return (first + 1) * 2;\n}\n")]
+    fn tuples(#[case] code: &str, #[case] expected: &str) {
+        assert_eq!(format(code), expected, "{code}");
+        assert_eq!(format(expected), expected);
+    }
+
+    #[rstest]
+    #[case("@allow( unused_functions ) func test(): void {}", "@allow(unused_functions)\nfunc test(): void {}\n")]
+    #[case("class A {}\n// About test\n@allow(a,b)// After\n@allow(c)\n// Before\nfunc test(): void {}", "class A {}\n\n// About test\n@allow(a, b) // After\n@allow(c)\n// Before\nfunc test(): void {}\n")]
+    #[case("use b;\n// About a\n@allow(unused_imports) // After\nuse a;", "// About a\n@allow(unused_imports) // After\nuse a;\nuse b;\n")]
+    fn attributes(#[case] code: &str, #[case] expected: &str) {
+        assert_eq!(format(code), expected, "{code}");
+        assert_eq!(format(expected), expected);
+    }
+
+    #[rstest]
+    #[case("class   Point {x:i32;pub  y : ?i32; func len(this): i32 { return this.size(); }}")]
+    #[case("use b;use a as c; func test(a:i32): i32 { var x = ((1+2)*3) - (4-(5-6)); if (x>1&&x<10)||x==0 { return x<<1; } else if !(x!=2) { return x%3; } else { return 0 - x; } }")]
+    #[case("func test(): bool { return 1 ^ 2 | 3 & 4 == 5 < 6 >> 7 + 8 * 9; }")]
+    #[case("func test(): bool { return ((((1 ^ 2) | 3) & 4) == 5) < ((6 >> 7) + 8) * 9; }")]
+    #[case("func test(): bool { return 1 - (2 - (3 - 4)) / (5 / (6 % 7)); }")]
+    #[case("@allow(unused_imports) use b; use a; @allow(unused_functions)\n\n// c\n@allow(unused_variables) func test(): void {}")]
+    #[case("// Header\n\n/* a */ use x; // b\nclass A { // c\n // d\n x: i32; /* e */ }\n\n\n// f\nfunc g(/* h */): void { // i\n\n\n if a // j\n { } // k\n // l\n }")]
+    fn round_trip(#[case] code: &str) {
+        let formatted = format(code);
+        assert_eq!(shape(&formatted), shape(code), "{formatted}");
+        assert_eq!(format(&formatted), formatted);
+    }
+
+    #[test]
+    fn lang_files() {
+        let directory = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../lang");
+        let mut paths = std::fs::read_dir(directory).unwrap().map(|entry| entry.unwrap().path()).collect::<Vec<_>>();
+        paths.sort();
+
+        for path in paths.into_iter().filter(|path| path.extension().is_some_and(|extension| extension == "tim")) {
+            let code = std::fs::read_to_string(&path).unwrap();
+            let formatted = format(&code);
+            assert_eq!(shape(&formatted), shape(&code), "{}", path.display());
+            assert_eq!(format(&formatted), formatted, "{}", path.display());
+        }
+    }
+}
@@ -11,6 +11,8 @@
 //! 1. **Parsing**: Convert source code into an Abstract Syntax Tree (AST)
 //! 2. **Type Resolution**: Build Type Intermediate Representation (TIR) with full type information
 //! 3. **Error Reporting**: Collect and report compilation errors with source location information
+//! 4. **Optimisation**: Fold constants, remove dead code and inline small functions
+//! 5. **Code Generation**: Emit the type-checked program for a target, such as WebAssembly text
 //!
 //! # Architecture
 //!
@@ -19,8 +21,15 @@
 //! - [`parser`]: Converts source code text into structured AST nodes
 //! - [`ast`]: Abstract Syntax Tree definitions for all language constructs
 //! - [`tir`]: Type Intermediate Representation and type checking system
+//! - [`optimizer`]: Optimisation passes that rewrite the type-checked program
+//! - [`backend`]: Code generators that turn the TIR into target code
+//! - [`prelude`]: The `std` module that is available to every program
+//! - [`project`]: Project manifests and discovery of the modules of a project
+//! - [`provider`]: Module providers that load imported modules on demand
 //! - [`error`]: Error handling and reporting infrastructure
 //! - [`file`]: Source file management and location tracking
+//! - [`format`]: Canonical formatting of source code
+//! - [`lint`]: Lints that warn about valid but suspicious code
 //! - [`nom_tools`]: Parser combinator utilities built on nom
 //! - [`map`]: Specialized data structures for compiler use
 //!
@@ -51,16 +60,24 @@ use ast::FileAst;
 use error::handle_parser;
 use nom::Finish;
 use nom_tools::State;
+use provider::ModuleLoader;
 use tir::{TirContext, TirError};
 
 // Public modules that form the compiler's public API
 pub mod ast;
+pub mod backend;
 pub mod file;
+pub mod format;
+pub mod lint;
 
 #[rustfmt::skip]
 pub mod parser;
 pub mod error;
 pub mod nom_tools;
+pub mod optimizer;
+pub mod prelude;
+pub mod project;
+pub mod provider;
 pub mod tir;
 pub mod map;
 
@@ -153,3 +170,23 @@ pub fn process_code<'base>(state: &'base State) -> Result<FileAst<'base>, TirErr
 pub fn process_ast(files: Vec<Rc<FileAst<'_>>>) -> Result<TirContext<'_>, TirError> {
     crate::tir::build(files)
 }
+
+/// Type checks the given ASTs like [`process_ast`], loading imported modules on demand.
+///
+/// A `use` statement that refers to a module which is not part of `files`
+/// asks the provider of the loader for it. Found modules are parsed, stored in
+/// the arena of the loader and type checked together with `files`.
+///
+/// # Examples
+///
+/// ```ignore
+/// use libtimu::provider::{FileSystemProvider, ModuleLoader, SourceArena};
+///
+/// let provider = FileSystemProvider::new(vec!["src".into()]);
+/// let arena = SourceArena::default();
+/// let tir_context = process_ast_with_loader(vec![ast.into()], ModuleLoader::new(&provider, &arena))?;
+/// ```
+#[allow(clippy::result_unit_err)]
+pub fn process_ast_with_loader<'base>(files: Vec<Rc<FileAst<'base>>>, loader: ModuleLoader<'base>) -> Result<TirContext<'base>, TirError> {
+    crate::tir::build_with_loader(files, Some(loader))
+}
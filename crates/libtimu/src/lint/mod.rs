@@ -0,0 +1,745 @@
+//! Lints, checks for code that is valid but probably not what was meant.
+//!
+//! Lints run over the type-checked program in a [`TirContext`] after the
+//! compilation succeeded and report their findings as [`Diagnostic`]s. A
+//! lint never changes the program, a warning does not stop the compilation.
+//!
+//! # Available Lints
+//!
+//! | Lint                   | Reports                                                   |
+//! |------------------------|-----------------------------------------------------------|
+//! | `unused_imports`       | `use` statements whose name is never referenced           |
+//! | `unused_functions`     | Module functions without `pub` that are never called      |
+//! | `unused_variables`     | Local variables that are never read                       |
+//! | `unreachable_patterns` | Match arms whose values are matched by earlier arms       |
+//!
+//! Functions and variables whose names start with `_` are never reported, as
+//! is a function called `main`. Functions of `extend` blocks implement
+//! interfaces and are not reported either.
+//!
+//! # Levels
+//!
+//! Every lint has a [`LintLevel`]: `allow` silences it, `warn` reports its
+//! findings as warnings and `deny` reports them as errors. Lints warn by
+//! default, the `[lints]` table of the project manifest changes the default
+//! and the `-A`, `-W` and `-D` options of `timuc` override both:
+//!
+//! ```toml
+//! [lints]
+//! unused_variables = "deny"
+//! unused_imports = "allow"
+//! ```
+//!
+//! A lint is silenced for a single declaration with the `@allow` attribute:
+//!
+//! ```timu
+//! @allow(unused_imports)
+//! use std.io.println;
+//!
+//! @allow(unused_functions, unused_variables)
+//! func helper(): void {
+//!     var total: i32 = 0;
+//! }
+//! ```
+//!
+//! An attribute other than `@allow` and a lint name `@allow` does not know are
+//! reported as errors, whatever the levels of the lints are.
+//!
+//! # Usage
+//!
+//! ```ignore
+//! let context = process_ast(vec![ast.into()])?;
+//! let diagnostics = libtimu::lint::check(&context, &LintLevels::default());
+//! ```
+
+use std::{collections::{BTreeMap, HashSet}, ops::Range, str::FromStr};
+
+use libtimu_macros::TimuError;
+use libtimu_macros_core::{traits::StaticDiagnostic, SourceCode};
+use serde::Deserialize;
+use strum::IntoEnumIterator;
+use strum_macros::{EnumDiscriminants, EnumIter, EnumProperty, EnumString, IntoStaticStr};
+
+use crate::{
+    ast::{BodyAst, BodyStatementAst, ClassDefinitionFieldAst, ExpressionAst, ExtendDefinitionFieldAst, FileAst, FileStatementAst, FunctionArgumentAst, FunctionCallAst, FunctionCallType, FunctionDefinitionAst, FunctionDefinitionLocationAst, InterfaceDefinitionFieldAst, MatchArmBodyAst, MatchAst, PatternAst, TypeNameAst, VariableDefinitionAst},
+    error::Diagnostic,
+    file::SourceFile,
+    nom_tools::ToRange,
+    prelude,
+    tir::{context::SpanKey, module::Module, TirContext, TirError, TypeValue},
+};
+
+/// A check for code that is valid but probably not what was meant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, EnumIter, EnumString, IntoStaticStr)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Lint {
+    UnusedImports,
+    UnusedFunctions,
+    UnusedVariables,
+    UnreachablePatterns,
+}
+
+impl Lint {
+    /// Name of the lint, as written in `@allow` and on the command line
+    pub fn name(self) -> &'static str {
+        self.into()
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Lint::UnusedImports => "Imported name is never used",
+            Lint::UnusedFunctions => "Private function is never used",
+            Lint::UnusedVariables => "Local variable is never used",
+            Lint::UnreachablePatterns => "Match arm can never be reached",
+        }
+    }
+
+    /// Error code of the findings of the lint
+    pub fn code(self) -> &'static str {
+        let code = match self {
+            Lint::UnusedImports => UnusedImport::CODE,
+            Lint::UnusedFunctions => UnusedFunction::CODE,
+            Lint::UnusedVariables => UnusedVariable::CODE,
+            Lint::UnreachablePatterns => UnreachablePattern::CODE,
+        };
+
+        code.expect("lint errors have a code")
+    }
+}
+
+/// How the findings of a lint are reported
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Deserialize, EnumString)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum LintLevel {
+    /// The lint is not reported
+    Allow,
+    /// The findings are reported as warnings
+    #[default]
+    Warn,
+    /// The findings are reported as errors and the compilation fails
+    Deny,
+}
+
+/// Level of every lint, a lint without a level warns
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LintLevels {
+    levels: BTreeMap<Lint, LintLevel>,
+}
+
+impl LintLevels {
+    pub fn get(&self, lint: Lint) -> LintLevel {
+        self.levels.get(&lint).copied().unwrap_or_default()
+    }
+
+    pub fn set(&mut self, lint: Lint, level: LintLevel) {
+        self.levels.insert(lint, level);
+    }
+}
+
+impl From<BTreeMap<Lint, LintLevel>> for LintLevels {
+    fn from(levels: BTreeMap<Lint, LintLevel>) -> Self {
+        Self { levels }
+    }
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("unused import `{name}`")]
+#[diagnostic(code("T0036"), help("remove the import or add `@allow(unused_imports)` to it"))]
+pub struct UnusedImport {
+    pub name: String,
+
+    #[label("imported here but never used")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("function `{name}` is never used")]
+#[diagnostic(code("T0037"), help("remove the function, make it `pub` or start its name with `_`"))]
+pub struct UnusedFunction {
+    pub name: String,
+
+    #[label("never called")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("variable `{name}` is never used")]
+#[diagnostic(code("T0038"), help("remove the variable or start its name with `_`"))]
+pub struct UnusedVariable {
+    pub name: String,
+
+    #[label("defined here but never read")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("unreachable pattern `{pattern}`")]
+#[diagnostic(code("T0047"), help("remove the arm or move it before the arms that already match its values"))]
+pub struct UnreachablePattern {
+    pub pattern: String,
+
+    #[label("already matched by an earlier arm")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error, EnumDiscriminants, EnumProperty)]
+pub enum LintError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    UnusedImport(Box<UnusedImport>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    UnusedFunction(Box<UnusedFunction>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    UnusedVariable(Box<UnusedVariable>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    UnreachablePattern(Box<UnreachablePattern>),
+}
+
+impl LintError {
+    /// The lint that reported the error
+    pub fn lint(&self) -> Lint {
+        match self {
+            LintError::UnusedImport(_) => Lint::UnusedImports,
+            LintError::UnusedFunction(_) => Lint::UnusedFunctions,
+            LintError::UnusedVariable(_) => Lint::UnusedVariables,
+            LintError::UnreachablePattern(_) => Lint::UnreachablePatterns,
+        }
+    }
+}
+
+impl From<LintError> for TirError {
+    fn from(value: LintError) -> Self {
+        TirError::Lint(Box::new(value))
+    }
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("unknown attribute `@{name}`")]
+#[diagnostic(code("T0055"), help("`@allow` is the only attribute"))]
+pub struct UnknownAttribute {
+    pub name: String,
+
+    #[label("unknown attribute")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("unknown lint `{name}`")]
+#[diagnostic(code("T0056"))]
+pub struct UnknownLint {
+    pub name: String,
+
+    #[label("no lint with this name")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+
+    #[help]
+    pub advice: String,
+}
+
+/// Attributes of the sources the lints cannot apply
+#[derive(Clone, Debug, TimuError, thiserror::Error, EnumDiscriminants, EnumProperty)]
+pub enum AttributeError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    UnknownAttribute(Box<UnknownAttribute>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    UnknownLint(Box<UnknownLint>),
+}
+
+impl From<AttributeError> for TirError {
+    fn from(value: AttributeError) -> Self {
+        TirError::Attribute(Box::new(value))
+    }
+}
+
+/// Runs every lint that is not allowed over the type-checked program
+///
+/// # Arguments
+/// * `context` - The type-checked program
+/// * `levels` - Level of every lint, `@allow` attributes in the sources take precedence
+///
+/// # Returns
+/// The errors of the attributes, then the findings in source order, as
+/// warnings or as errors for the denied lints
+pub fn check(context: &TirContext<'_>, levels: &LintLevels) -> Vec<Diagnostic> {
+    let mut diagnostics = attributes(context).into_iter().map(|error| Diagnostic::error(error.into())).collect::<Vec<_>>();
+
+    let mut findings = Vec::new();
+    unused_imports(context, &mut findings);
+    unused_functions(context, &mut findings);
+    unused_variables(context, &mut findings);
+    unreachable_patterns(context, &mut findings);
+    findings.sort_by(|(left, _), (right, _)| left.path.cmp(&right.path).then(left.position.start.cmp(&right.position.start)));
+
+    diagnostics.extend(findings.into_iter()
+        .filter(|(key, error)| !is_allowed_in_source(context, key, error.lint()))
+        .filter_map(|(_, error)| match levels.get(error.lint()) {
+            LintLevel::Allow => None,
+            LintLevel::Warn => Some(Diagnostic::warning(error.into())),
+            LintLevel::Deny => Some(Diagnostic::error(error.into())),
+        }));
+    diagnostics
+}
+
+/// Reports the attributes other than `@allow` and the names `@allow` gets that are not lints
+fn attributes(context: &TirContext<'_>) -> Vec<AttributeError> {
+    let mut errors = Vec::new();
+    for (module, ast) in user_modules(context) {
+        for attribute in ast.attributes.iter() {
+            if attribute.name.text != "allow" {
+                errors.push(AttributeError::UnknownAttribute(UnknownAttribute {
+                    name: attribute.name.text.to_string(),
+                    position: attribute.position.clone(),
+                    code: (&module.file).into(),
+                }.into()));
+                continue;
+            }
+
+            for argument in attribute.arguments.iter().filter(|argument| Lint::from_str(argument.text).is_err()) {
+                errors.push(AttributeError::UnknownLint(UnknownLint {
+                    name: argument.text.to_string(),
+                    position: argument.position.start..argument.position.start + argument.text.len(),
+                    code: (&module.file).into(),
+                    advice: format!("the lints are {}", Lint::iter().map(|lint| format!("`{}`", lint.name())).collect::<Vec<_>>().join(", ")),
+                }.into()));
+            }
+        }
+    }
+
+    errors
+}
+
+/// Modules written by the user, the standard library is not linted
+fn user_modules<'a, 'base>(context: &'a TirContext<'base>) -> impl Iterator<Item = (&'a Module<'base>, &'a FileAst<'base>)> {
+    context.modules.values()
+        .filter(|module| !prelude::is_prelude_module(&module.file.path))
+        .filter_map(|module| module.ast.as_deref().map(|ast| (module, ast)))
+}
+
+fn file_of<'a>(context: &'a TirContext<'_>, key: &SpanKey) -> Option<&'a SourceFile> {
+    context.modules.values().map(|module| &module.file).find(|file| file.path == key.path)
+}
+
+/// Whether an `@allow` attribute of the declaration around the position silences the lint
+fn is_allowed_in_source(context: &TirContext<'_>, key: &SpanKey, lint: Lint) -> bool {
+    let Some(ast) = context.modules.values().find(|module| module.file.path == key.path).and_then(|module| module.ast.as_ref()) else {
+        return false;
+    };
+
+    ast.attributes.iter().any(|attribute| attribute.name.text == "allow"
+        && attribute.target.contains(&key.position.start)
+        && attribute.arguments.iter().any(|argument| argument.text == lint.name()))
+}
+
+/// Reports the entries of [`Module::ast_imported_modules`] whose name is never referenced in the module
+///
+/// Every item of a grouped import is reported on its own. Re-exports with
+/// `pub use` are used by other modules and wildcard imports are not expected
+/// to use every item, neither is reported.
+fn unused_imports(context: &TirContext<'_>, findings: &mut Vec<(SpanKey, LintError)>) {
+    for (module, ast) in user_modules(context) {
+        let mut used = HashSet::new();
+        for statement in ast.statements.iter() {
+            file_statement_names(statement, &mut used);
+        }
+
+        for import in ast.get_uses().filter(|import| import.is_public.is_none()) {
+            let prefix = if import.kind.is_group() { import.import.paths.len() } else { 0 };
+            for item in import.items() {
+                let name = item.alias.as_ref().unwrap_or(&item.ast_name()).text;
+                if used.contains(name) || !module.ast_imported_modules.contains_key(name) {
+                    continue;
+                }
+
+                let position = item.import.paths[prefix].position.start..item.ast_name().position.end;
+                findings.push((SpanKey { path: module.file.path.clone(), position: position.clone() }, LintError::UnusedImport(UnusedImport {
+                    name: name.to_string(),
+                    position,
+                    code: (&module.file).into(),
+                }.into())));
+            }
+        }
+    }
+}
+
+/// Reports the module functions without `pub` that are never called or referenced
+fn unused_functions(context: &TirContext<'_>, findings: &mut Vec<(SpanKey, LintError)>) {
+    let used = context.call_targets.values()
+        .chain(context.identifiers.values().map(|target| &target.location))
+        .collect::<HashSet<_>>();
+
+    for (_, location, signature) in context.types.iter() {
+        let TypeValue::Function(function) = signature.value.as_ref() else {
+            continue;
+        };
+
+        let file = &function.name.state.file;
+        let is_entry = function.ast.is_public.is_some() || function.ast.is_extern.is_some() || function.name.text == "main";
+        // Methods, including the functions of `extend` blocks, can be called through an interface
+        if is_entry
+            || !matches!(function.ast.location.as_ref(), FunctionDefinitionLocationAst::Module)
+            || function.name.text.starts_with('_')
+            || prelude::is_prelude_module(&file.path)
+            || used.contains(&location) {
+            continue;
+        }
+
+        findings.push(((&function.name).into(), LintError::UnusedFunction(UnusedFunction {
+            name: function.name.text.to_string(),
+            position: function.name.to_range(),
+            code: file.into(),
+        }.into())));
+    }
+}
+
+/// Reports the local variables and private module variables that no identifier refers to
+fn unused_variables(context: &TirContext<'_>, findings: &mut Vec<(SpanKey, LintError)>) {
+    let used = context.identifiers.values().filter_map(|target| target.definition.as_ref()).collect::<HashSet<_>>();
+    let is_public = |key: &SpanKey| context.globals.get(key).is_some_and(|global| global.is_public);
+
+    for key in context.variable_types.keys().filter(|key| !used.contains(key) && !is_public(key)) {
+        let Some(file) = file_of(context, key) else {
+            continue;
+        };
+
+        let name = &file.code()[key.position.clone()];
+        if name.starts_with('_') {
+            continue;
+        }
+
+        findings.push((key.clone(), LintError::UnusedVariable(UnusedVariable {
+            name: name.to_string(),
+            position: key.position.clone(),
+            code: file.into(),
+        }.into())));
+    }
+}
+
+/// Reports the match arms recorded as unreachable while type checking, see [`TirContext::matches`]
+fn unreachable_patterns(context: &TirContext<'_>, findings: &mut Vec<(SpanKey, LintError)>) {
+    for key in context.matches.values().flat_map(|information| information.unreachable.iter()) {
+        let Some(file) = file_of(context, key) else {
+            continue;
+        };
+
+        findings.push((key.clone(), LintError::UnreachablePattern(UnreachablePattern {
+            pattern: file.code()[key.position.clone()].trim().to_string(),
+            position: key.position.clone(),
+            code: file.into(),
+        }.into())));
+    }
+}
+
+/// Names a declaration refers to, only the first part of a path can be an imported name
+fn file_statement_names<'base>(statement: &FileStatementAst<'base>, names: &mut HashSet<&'base str>) {
+    match statement {
+        FileStatementAst::Class(class) => {
+            class.base_class.iter().for_each(|base| type_name(base, names));
+            for field in class.fields.iter() {
+                match field {
+                    ClassDefinitionFieldAst::Field(field) => type_name(&field.field_type, names),
+                    ClassDefinitionFieldAst::Function(function) => function_names(function, names),
+                }
+            }
+        },
+        FileStatementAst::Function(function) => function_names(function, names),
+        FileStatementAst::Interface(interface) => {
+            interface.base_interfaces.iter().for_each(|base| type_name(base, names));
+            for field in interface.fields.iter() {
+                match field {
+                    InterfaceDefinitionFieldAst::Function(function) => {
+                        arguments(&function.arguments, names);
+                        type_name(&function.return_type, names);
+                        function.body.iter().for_each(|function_body| body(function_body, names));
+                    },
+                    InterfaceDefinitionFieldAst::Field(field) => type_name(&field.field_type, names),
+                }
+            }
+        },
+        FileStatementAst::Extend(extend) => {
+            type_name(&extend.name, names);
+            extend.base_interfaces.iter().for_each(|base| type_name(base, names));
+            for field in extend.fields.iter() {
+                match field {
+                    ExtendDefinitionFieldAst::Function(function) => function_names(function, names),
+                    ExtendDefinitionFieldAst::Field(field) => type_name(&field.field_type, names),
+                }
+            }
+        },
+        FileStatementAst::TypeAlias(alias) => type_name(&alias.target, names),
+        FileStatementAst::Enum(definition) => definition.variants.iter().flat_map(|variant| variant.payload.iter()).for_each(|payload| type_name(payload, names)),
+        FileStatementAst::Variable(variable) => variable_definition(&variable.definition, names),
+        FileStatementAst::Use(_) => (),
+    }
+}
+
+fn type_name<'base>(type_name: &TypeNameAst<'base>, names: &mut HashSet<&'base str>) {
+    if let Some(function) = &type_name.function {
+        function.arguments.iter().for_each(|argument| self::type_name(argument, names));
+        self::type_name(&function.return_type, names);
+    } else if let Some(elements) = &type_name.tuple {
+        elements.iter().for_each(|element| self::type_name(element, names));
+    } else if let Some(name) = type_name.names.first() {
+        names.insert(name.text);
+    }
+}
+
+fn arguments<'base>(arguments: &[FunctionArgumentAst<'base>], names: &mut HashSet<&'base str>) {
+    for argument in arguments.iter() {
+        if let FunctionArgumentAst::Argument { field_type, .. } = argument {
+            type_name(field_type, names);
+        }
+    }
+}
+
+fn function_names<'base>(function: &FunctionDefinitionAst<'base>, names: &mut HashSet<&'base str>) {
+    arguments(&function.arguments, names);
+    type_name(&function.return_type, names);
+    body(&function.body, names);
+}
+
+fn variable_definition<'base>(definition: &VariableDefinitionAst<'base>, names: &mut HashSet<&'base str>) {
+    if let Some(expected_type) = &definition.expected_type {
+        type_name(expected_type, names);
+    }
+    if let Some(value) = &definition.expression {
+        expression(value, names);
+    }
+}
+
+fn body<'base>(body: &BodyAst<'base>, names: &mut HashSet<&'base str>) {
+    for statement in body.statements.iter() {
+        match statement {
+            BodyStatementAst::VariableDefinition(definition) => variable_definition(definition, names),
+            BodyStatementAst::VariableAssign(assign) => {
+                // Imported global variables and the static fields of imported classes can be assigned
+                names.insert(assign.path.first().unwrap_or(&assign.name).text);
+                expression(&assign.expression, names);
+            },
+            BodyStatementAst::FunctionCall(call) => function_call(call, names),
+            BodyStatementAst::IfCondition(condition) => {
+                expression(&condition.expression, names);
+                self::body(&condition.true_body, names);
+                for (else_if, else_if_body) in condition.else_ifs.iter() {
+                    expression(else_if, names);
+                    self::body(else_if_body, names);
+                }
+                if let Some(false_body) = &condition.false_body {
+                    self::body(false_body, names);
+                }
+            },
+            BodyStatementAst::Match(match_ast) => match_names(match_ast, names),
+            BodyStatementAst::Return(value) => {
+                if let Some(value) = &value.expression {
+                    expression(value, names);
+                }
+            },
+        }
+    }
+}
+
+fn function_call<'base>(call: &FunctionCallAst<'base>, names: &mut HashSet<&'base str>) {
+    if let FunctionCallType::Direct(paths) = &call.path && let Some(name) = paths.first() {
+        names.insert(name.text);
+    }
+    call.arguments.iter().for_each(|argument| expression(argument, names));
+}
+
+fn match_names<'base>(match_ast: &MatchAst<'base>, names: &mut HashSet<&'base str>) {
+    expression(&match_ast.expression, names);
+    for arm in match_ast.arms.iter() {
+        match &arm.pattern {
+            PatternAst::Variant { path, .. } => {
+                if let Some(name) = path.first() {
+                    names.insert(name.text);
+                }
+            },
+            PatternAst::Class { class, .. } => type_name(class, names),
+            PatternAst::Literal { .. } | PatternAst::Null(_) | PatternAst::Wildcard(_) => (),
+        };
+        match &arm.body {
+            MatchArmBodyAst::Expression(value) => expression(value, names),
+            MatchArmBodyAst::Block(block) => body(block, names),
+        };
+    }
+}
+
+fn expression<'base>(value: &ExpressionAst<'base>, names: &mut HashSet<&'base str>) {
+    match value {
+        ExpressionAst::Primitive { .. } => (),
+        ExpressionAst::Ref(reference) => {
+            if let Some(name) = reference.names.first() {
+                names.insert(name.text);
+            }
+        },
+        ExpressionAst::Not(inner) => expression(inner, names),
+        ExpressionAst::Ident(name) => {
+            names.insert(name.text);
+        },
+        ExpressionAst::FunctionCall(call) => function_call(call, names),
+        ExpressionAst::Operation { left, right, .. } => {
+            expression(left, names);
+            expression(right, names);
+        },
+        ExpressionAst::Lambda(lambda) => {
+            arguments(&lambda.arguments, names);
+            type_name(&lambda.return_type, names);
+            body(&lambda.body, names);
+        },
+        ExpressionAst::Match(match_ast) => match_names(match_ast, names),
+        ExpressionAst::Tuple(tuple) => tuple.elements.iter().for_each(|element| expression(element, names)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{ops::Range, str::FromStr};
+
+    use libtimu_macros_core::traits::TimuErrorTrait;
+    use rstest::rstest;
+
+    use crate::{error::Severity, tests::build_modules_with};
+
+    use super::{check, Lint, LintLevel, LintLevels};
+
+    /// Messages of the findings of the lints for the given modules, the last module is `main`
+    fn lint(modules: &[(&str, &str)], levels: &LintLevels) -> Vec<(Severity, String)> {
+        build_modules_with(modules, |context| {
+            check(context, levels).into_iter().map(|diagnostic| (diagnostic.severity, diagnostic.error.to_string())).collect()
+        }).unwrap()
+    }
+
+    fn warnings(code: &str) -> Vec<String> {
+        lint(&[("lib", "pub func add(a: i32, b: i32): i32 { return a + b; }\npub class Point { pub x: i32; }"), ("main", code)], &LintLevels::default())
+            .into_iter()
+            .map(|(severity, message)| {
+                assert_eq!(severity, Severity::Warning);
+                message
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case("use lib.add;\npub func main(): i32 { return add(1, 2); }", &[])]
+    #[case("use lib.add;\npub func main(): void {}", &["unused import `add`"])]
+    #[case("use lib.add as plus;\npub func main(): void {}", &["unused import `plus`"])]
+    #[case("use lib.add as plus;\npub func main(): i32 { return plus(1, 2); }", &[])]
+    #[case("use lib.Point;\npub func main(p: Point): void {}", &[])]
+    #[case("use lib;\npub func main(): i32 { return lib.add(1, 2); }", &[])]
+    #[case("@allow(unused_imports)\nuse lib.add;\npub func main(): void {}", &[])]
+    #[case("use lib.{add, Point};\npub func main(p: Point): void {}", &["unused import `add`"])]
+    #[case("use lib.*;\npub func main(): void {}", &[])]
+    #[case("pub use lib.add;\npub func main(): void {}", &[])]
+    #[case("use lib.Point;\npub type Location = Point;", &[])]
+    fn unused_imports(#[case] code: &str, #[case] expected: &[&str]) {
+        assert_eq!(warnings(code), expected);
+    }
+
+    #[rstest]
+    #[case("func helper(): void {}\npub func run(): void {}", &["function `helper` is never used"])]
+    #[case("func helper(): void {}\npub func run(): void { helper(); }", &[])]
+    #[case("func helper(): i32 { return 1; }\npub func run(): i32 { return helper() + 1; }", &[])]
+    #[case("func _helper(): void {}\nfunc main(): void {}", &[])]
+    #[case("@allow(unused_functions)\nfunc helper(): void {}", &[])]
+    #[case("class Point { func len(this): i32 { return 1; } }", &[])]
+    #[case("func helper(value: i32): i32 { return value; }\npub func run(): func(i32): i32 { return helper; }", &[])]
+    #[case("interface Shape { func area(this): i32; }\nclass Square {}\nextend Square: Shape { func area(this): i32 { return 1; } }\npub func run(shape: ref Shape): i32 { return shape.area(); }", &[])]
+    #[case("interface Shape { func area(this): i32; }\nclass Square {}\nextend Square: Shape { func area(this): i32 { return 1; } }", &[])]
+    fn unused_functions(#[case] code: &str, #[case] expected: &[&str]) {
+        assert_eq!(warnings(code), expected);
+    }
+
+    #[rstest]
+    #[case("pub func run(): void { var a: i32 = 1; }", &["variable `a` is never used"])]
+    #[case("pub func run(): i32 { var a: i32 = 1; return a; }", &[])]
+    #[case("pub func run(): func(): i32 { const a = 1; return func(): i32 { return a; }; }", &[])]
+    #[case("pub func run(): void { var callback = func(b: i32): void {}; }", &["variable `callback` is never used"])]
+    #[case("pub func run(): void { var a: i32 = 1; a = 2; }", &["variable `a` is never used"])]
+    #[case("pub func run(): void { var _a: i32 = 1; }", &[])]
+    #[case("pub func run(): bool { var a: i32 = 1; if a > 0 { return true; } return false; }", &[])]
+    #[case("class Counter { func get(this): i32 { return 1; } }\npub func run(counter: Counter): i32 { var other: Counter = counter; return other.get(); }", &[])]
+    #[case("@allow(unused_variables)\npub func run(): void { var a: i32 = 1; }", &[])]
+    #[case("@allow(unused_imports)\npub func run(): void { var a: i32 = 1; }", &["variable `a` is never used"])]
+    #[case("const LIMIT: i32 = 1;\npub func run(): void {}", &["variable `LIMIT` is never used"])]
+    #[case("const LIMIT: i32 = 1;\npub func run(): i32 { return LIMIT; }", &[])]
+    #[case("pub const LIMIT: i32 = 1;", &[])]
+    fn unused_variables(#[case] code: &str, #[case] expected: &[&str]) {
+        assert_eq!(warnings(code), expected);
+    }
+
+    #[rstest]
+    #[case("pub func run(a: i32): i32 { return match (a) { 1 => 1, _ => 0 }; }", &[])]
+    #[case("pub func run(a: i32): i32 { return match (a) { _ => 0, 1 => 1 }; }", &["unreachable pattern `1`"])]
+    #[case("pub func run(a: bool): i32 { return match (a) { true => 1, false => 0, _ => 2 }; }", &["unreachable pattern `_`"])]
+    #[case("pub func run(a: i32): void { match (a) { value: i32 => {}, 2 => {} } }", &["variable `value` is never used", "unreachable pattern `2`"])]
+    #[case("@allow(unreachable_patterns)\npub func run(a: i32): i32 { return match (a) { _ => 0, 1 => 1 }; }", &[])]
+    #[case("use lib.Point;\npub func run(point: Point): i32 { return match (point) { _point: Point => 1 }; }", &[])]
+    fn unreachable_patterns(#[case] code: &str, #[case] expected: &[&str]) {
+        assert_eq!(warnings(code), expected);
+    }
+
+    #[rstest]
+    #[case("@alow(unused_functions)\nfunc helper(): void {}", "unknown attribute `@alow`", 0..23)]
+    #[case("@allow(unused_function)\nfunc helper(): void {}", "unknown lint `unused_function`", 7..22)]
+    #[case("@allow(unused_variables, nonsense)\npub func run(): void {}", "unknown lint `nonsense`", 25..33)]
+    fn unknown_attributes(#[case] code: &str, #[case] message: &str, #[case] position: Range<usize>) {
+        let mut levels = LintLevels::default();
+        levels.set(Lint::UnusedFunctions, LintLevel::Allow);
+
+        let diagnostics = lint(&[("main", code)], &levels);
+        assert_eq!(diagnostics, [(Severity::Error, message.to_string())]);
+
+        let diagnostic = build_modules_with(&[("main", code)], |context| check(context, &levels).remove(0)).unwrap();
+        assert_eq!(diagnostic.error.labels().unwrap()[0].position, position);
+        assert!(diagnostic.error.help().is_some());
+    }
+
+    #[test]
+    fn levels() {
+        let code = [("main", "func helper(): void { var a: i32 = 1; }")];
+
+        let mut levels = LintLevels::default();
+        levels.set(Lint::UnusedVariables, LintLevel::Deny);
+        levels.set(Lint::UnusedFunctions, LintLevel::Allow);
+        assert_eq!(lint(&code, &levels), [(Severity::Error, "variable `a` is never used".to_string())]);
+
+        levels.set(Lint::UnusedVariables, LintLevel::Allow);
+        assert!(lint(&code, &levels).is_empty());
+    }
+
+    #[test]
+    fn names() {
+        assert_eq!(Lint::from_str("unused_imports").unwrap(), Lint::UnusedImports);
+        assert_eq!(Lint::UnusedVariables.name(), "unused_variables");
+        assert_eq!(LintLevel::from_str("deny").unwrap(), LintLevel::Deny);
+        assert!(Lint::from_str("unused").is_err());
+    }
+}
@@ -6,10 +6,11 @@
 
 use nom::branch::alt;
 use nom::bytes::complete::{tag, take_until};
-use nom::character::complete::{char, multispace0};
-use nom::combinator::cut;
-use nom::sequence::{preceded, terminated};
-use nom::{Parser, sequence::delimited};
+use nom::character::complete::{multispace1, not_line_ending};
+use nom::combinator::{cut, value};
+use nom::error::context;
+use nom::multi::many0;
+use nom::{IResult, Parser, sequence::delimited};
 use nom_locate::LocatedSpan;
 use std::fmt::Display;
 use std::hash::Hash;
@@ -166,13 +167,24 @@ impl<T: PartialOrd> Between<T> for std::ops::Range<T> {
     }
 }
 
+/// Skips whitespace and comments
+/// 
+/// Line comments start with `//` and run until the end of the line, block
+/// comments are enclosed in `/*` and `*/` and do not nest. A block comment
+/// that is never closed is a syntax error.
+pub fn trivia<'base>(input: NomSpan<'base>) -> IResult<NomSpan<'base>, (), TimuParserError<'base>> {
+    value((), many0(alt((
+        value((), multispace1),
+        value((), (tag("//"), not_line_ending)),
+        value((), (tag("/*"), context("Block comment is not closed", cut((take_until("*/"), tag("*/")))))),
+    )))).parse(input)
+}
+
 /// Parser combinator that wraps a parser with whitespace cleanup
 /// 
-/// This function removes leading and trailing whitespace around the given parser,
-/// making it easier to handle tokens that may be surrounded by whitespace.
+/// This function removes leading and trailing whitespace and comments around
+/// the given parser, making it easier to handle tokens that may be surrounded
+/// by whitespace.
 pub fn cleanup<'base, O, F: Parser<NomSpan<'base>, Output = O, Error = TimuParserError<'base>>>(f: F) -> impl Parser<NomSpan<'base>, Output = O, Error = TimuParserError<'base>> {
-    let _left = preceded(char::<NomSpan<'base>, TimuParserError<'base>>('/'), alt((preceded(char('*'), cut(terminated(take_until("*/"), tag("*/")))),)));
-    let _right = preceded(char::<NomSpan<'base>, TimuParserError<'base>>('/'), alt((preceded(char('*'), cut(terminated(take_until("*/"), tag("*/")))),)));
-
-    delimited(multispace0, f, multispace0)
+    delimited(trivia, f, trivia)
 }
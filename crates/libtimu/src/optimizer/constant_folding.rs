@@ -0,0 +1,226 @@
+//! Constant folding pass.
+//!
+//! Operations whose operands are literals are evaluated at compile time and
+//! replaced by a single literal. Literals have no type of their own, so each
+//! operation is evaluated in the type the backend would use for it, which
+//! keeps the result identical to the one computed at runtime:
+//!
+//! ```timu
+//! var a: u8 = 200 + 100;   // folded to 44, u8 arithmetic wraps around
+//! var b = 7 / 2;           // folded to 3
+//! var c = 1 / 0;           // kept, the division traps at runtime
+//! var d = !(2 > 1);        // folded to false
+//! ```
+//!
+//! Logical operators with a constant operand are simplified, such as
+//! `true && check()` or `check() && true` becoming `check()`.
+
+use crate::{
+    ast::{BodyAst, BodyStatementAst, ExpressionAst, ExpressionOperatorType, PrimitiveValue},
+    tir::{
+        const_eval::evaluate,
+        context::SpanKey,
+        resolver::{function::FunctionDefinition, statement::{get_primitive_location, get_primitive_type, operand_type}, TypeLocation},
+        PrimitiveType, TirContext,
+    },
+};
+
+use super::{call_target, LocalTypes};
+
+/// Folds every constant expression of a function body
+pub fn run(context: &TirContext<'_>, function: &FunctionDefinition<'_>, body: &mut BodyAst<'_>) {
+    let mut folder = ConstantFolder {
+        context,
+        locals: LocalTypes::new(function),
+        return_type: function.return_type,
+    };
+
+    folder.fold_body(body);
+}
+
+struct ConstantFolder<'ctx, 'base> {
+    context: &'ctx TirContext<'base>,
+    locals: LocalTypes,
+    return_type: TypeLocation,
+}
+
+impl ConstantFolder<'_, '_> {
+    fn fold_body(&mut self, body: &mut BodyAst<'_>) {
+        for statement in body.statements.iter_mut() {
+            self.fold_statement(statement);
+        }
+    }
+
+    fn fold_block(&mut self, body: &mut BodyAst<'_>) {
+        self.locals.push();
+        self.fold_body(body);
+        self.locals.pop();
+    }
+
+    fn fold_statement(&mut self, statement: &mut BodyStatementAst<'_>) {
+        let bool_location = get_primitive_location(self.context, PrimitiveType::Bool);
+
+        match statement {
+            BodyStatementAst::VariableDefinition(definition) => {
+                if let Some(expression) = definition.expression.as_mut() {
+                    let location = self.context.variable_types.get(&SpanKey::from(&definition.name)).copied();
+                    self.fold_expression(expression, location);
+                }
+                match &definition.destructure {
+                    Some(names) => names.iter().for_each(|name| self.locals.define(self.context, name)),
+                    None => self.locals.define(self.context, &definition.name),
+                };
+            },
+            BodyStatementAst::VariableAssign(assign) => {
+                let location = self.locals.lookup(assign.name.text).filter(|_| assign.path.is_empty());
+                self.fold_expression(&mut assign.expression, location);
+            },
+            BodyStatementAst::FunctionCall(function_call) => self.fold_arguments(function_call),
+            BodyStatementAst::IfCondition(if_condition) => {
+                self.fold_expression(&mut if_condition.expression, Some(bool_location));
+                self.fold_block(&mut if_condition.true_body);
+
+                for (expression, body) in if_condition.else_ifs.iter_mut() {
+                    self.fold_expression(expression, Some(bool_location));
+                    self.fold_block(body);
+                }
+
+                if let Some(false_body) = if_condition.false_body.as_mut() {
+                    self.fold_block(false_body);
+                }
+            },
+            // The arms bind names the local types do not know about, matches are kept as written
+            BodyStatementAst::Match(_) => (),
+            BodyStatementAst::Return(return_statement) => {
+                if let Some(expression) = return_statement.expression.as_mut() {
+                    self.fold_expression(expression, Some(self.return_type));
+                }
+            },
+        }
+    }
+
+    fn fold_arguments(&mut self, function_call: &mut crate::ast::FunctionCallAst<'_>) {
+        let parameters = match call_target(self.context, function_call) {
+            Some((_, callee)) => {
+                // The implicit `this` argument is not written at the call site
+                let skip = callee.arguments.len() - function_call.arguments.len();
+                callee.arguments[skip..].iter().map(|argument| argument.field_type).collect::<Vec<_>>()
+            },
+            None => return,
+        };
+
+        for (argument, location) in function_call.arguments.iter_mut().zip(parameters) {
+            self.fold_expression(argument, Some(location));
+        }
+    }
+
+    /// Folds an expression, `expected` is the type required by the surrounding code
+    fn fold_expression(&mut self, expression: &mut ExpressionAst<'_>, expected: Option<TypeLocation>) {
+        let bool_location = get_primitive_location(self.context, PrimitiveType::Bool);
+
+        match expression {
+            ExpressionAst::Primitive { .. } | ExpressionAst::Ident(_) | ExpressionAst::Ref(_) | ExpressionAst::Lambda(_) | ExpressionAst::Match(_) | ExpressionAst::Tuple(_) => (),
+            ExpressionAst::FunctionCall(function_call) => self.fold_arguments(function_call),
+            ExpressionAst::Not(inner) => {
+                self.fold_expression(inner, Some(bool_location));
+
+                if let ExpressionAst::Primitive { span, value: PrimitiveValue::Bool(value) } = inner.as_ref() {
+                    *expression = ExpressionAst::Primitive { span: span.clone(), value: PrimitiveValue::Bool(!value) };
+                }
+            },
+            ExpressionAst::Operation { left, operator: operator @ (ExpressionOperatorType::And | ExpressionOperatorType::Or), right } => {
+                self.fold_expression(left, Some(bool_location));
+                self.fold_expression(right, Some(bool_location));
+
+                // The right operand is only evaluated when the left one does not decide the result
+                let short_circuit = *operator == ExpressionOperatorType::Or;
+                match left.as_ref() {
+                    ExpressionAst::Primitive { value: PrimitiveValue::Bool(value), .. } if *value == short_circuit => *expression = left.as_ref().clone(),
+                    ExpressionAst::Primitive { value: PrimitiveValue::Bool(_), .. } => *expression = right.as_ref().clone(),
+                    // `a && true` and `a || false` are `a`, the other constants still need `a` to be evaluated
+                    _ => if let ExpressionAst::Primitive { value: PrimitiveValue::Bool(value), .. } = right.as_ref()
+                        && *value != short_circuit {
+                        *expression = left.as_ref().clone();
+                    },
+                };
+            },
+            ExpressionAst::Operation { left, operator, right } => {
+                let locals = &self.locals;
+                let location = operand_type(self.context, left, operator, right, expected, &|name| locals.lookup(name));
+
+                self.fold_expression(left, Some(location));
+                self.fold_expression(right, Some(location));
+
+                if let (ExpressionAst::Primitive { span, value: left_value }, ExpressionAst::Primitive { value: right_value, .. }) = (left.as_ref(), right.as_ref())
+                    && let Some(primitive) = get_primitive_type(self.context, location)
+                    && let Some(value) = evaluate(&primitive, operator, left_value, right_value) {
+                    *expression = ExpressionAst::Primitive { span: span.clone(), value };
+                }
+            },
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::{ExpressionOperatorType, PrimitiveValue},
+        optimizer::{tests::compile, OptimizerOptions},
+        tir::{const_eval::evaluate, PrimitiveType},
+    };
+
+    fn folded(code: &str) -> String {
+        let wat = compile(code, OptimizerOptions { constant_folding: true, ..Default::default() });
+        wat[wat.find("(func $main.test").unwrap()..].to_string()
+    }
+
+    #[test]
+    fn arithmetic() {
+        let wat = folded("pub func test(): i32 { return 1 + 2 * 3 - 4 / 2; }");
+        assert!(wat.contains("i32.const 5\n"), "{wat}");
+        assert!(!wat.contains("i32.add"), "{wat}");
+    }
+
+    #[test]
+    fn overflow_follows_the_type() {
+        assert!(folded("pub func test(): u8 { return 200 + 100; }").contains("i32.const 44\n"));
+        assert!(folded("pub func test(): i8 { return 100 + 100; }").contains("i32.const -56\n"));
+        assert!(folded("pub func test(): i32 { return 2147483647 + 1; }").contains("i32.const -2147483648\n"));
+        assert!(folded("pub func test(): i64 { return 2147483647 + 1; }").contains("i64.const 2147483648\n"));
+        assert!(folded("pub func test(): u32 { return 0 - 1; }").contains("i32.const -1\n"));
+    }
+
+    #[test]
+    fn operand_types_from_variables() {
+        let wat = folded("pub func test(a: u8): u8 { return a + (200 + 100); }");
+        assert!(wat.contains("i32.const 44\n"), "{wat}");
+    }
+
+    #[test]
+    fn traps_are_kept() {
+        let wat = folded("pub func test(): i32 { return 1 / 0; }");
+        assert!(wat.contains("i32.div_s"), "{wat}");
+        assert_eq!(evaluate(&PrimitiveType::I32, &ExpressionOperatorType::Div, &PrimitiveValue::I32(i32::MIN), &PrimitiveValue::I8(-1)), None);
+        assert_eq!(evaluate(&PrimitiveType::I8, &ExpressionOperatorType::Div, &PrimitiveValue::I8(i8::MIN), &PrimitiveValue::I8(-1)), Some(PrimitiveValue::I8(i8::MIN)));
+    }
+
+    #[test]
+    fn comparisons_and_logic() {
+        let wat = folded("pub func test(a: bool): bool { return !(2 > 1) || a; }");
+        assert!(wat.contains("local.get $a\n    return"), "{wat}");
+
+        let wat = folded(r#"pub func test(): bool { return "a" + "b" == "ab"; }"#);
+        assert!(wat.contains("i32.const 1\n"), "{wat}");
+        assert!(!wat.contains("call $timu_string_equal"), "{wat}");
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(evaluate(&PrimitiveType::Double, &ExpressionOperatorType::Div, &PrimitiveValue::Double(1.0, 1), &PrimitiveValue::I8(4)), Some(PrimitiveValue::Double(0.25, 2)));
+        assert_eq!(evaluate(&PrimitiveType::Double, &ExpressionOperatorType::Div, &PrimitiveValue::Double(1.0, 1), &PrimitiveValue::I8(0)), None);
+        assert_eq!(evaluate(&PrimitiveType::Double, &ExpressionOperatorType::Mod, &PrimitiveValue::Double(1.0, 1), &PrimitiveValue::I8(2)), None);
+
+        let wat = folded("pub func test(): float { return 0.5 * 3; }");
+        assert!(wat.contains("f32.const 1.5\n"), "{wat}");
+    }
+}
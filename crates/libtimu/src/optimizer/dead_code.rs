@@ -0,0 +1,180 @@
+//! Dead code elimination pass.
+//!
+//! Removes code that can never run:
+//!
+//! - Branches of an `if` statement whose condition is a constant. A branch
+//!   that is always taken replaces the whole statement.
+//! - Statements after a `return`, or after an `if` statement whose branches
+//!   all return.
+//!
+//! ```timu
+//! if (false) { a(); } else if (true) { b(); } else { c(); }   // becomes b();
+//! return 1;
+//! d();                                                          // removed
+//! ```
+//!
+//! Conditions are only inspected, not evaluated, so the pass works best after
+//! constant folding.
+
+use crate::ast::{BodyAst, BodyStatementAst, ExpressionAst, IfConditionAst, PrimitiveValue};
+
+/// Removes unreachable statements and constant branches from a function body
+pub fn run(body: &mut BodyAst<'_>) {
+    eliminate(body);
+}
+
+/// Cleans up a statement list, returns whether the list always returns
+fn eliminate(body: &mut BodyAst<'_>) -> bool {
+    let mut statements = Vec::with_capacity(body.statements.len());
+    let mut returns = false;
+
+    for statement in std::mem::take(&mut body.statements).into_iter() {
+        match statement {
+            BodyStatementAst::IfCondition(if_condition) => match simplify_if(if_condition) {
+                Simplified::Statement(mut if_condition) => {
+                    returns = eliminate_branches(&mut if_condition);
+                    statements.push(BodyStatementAst::IfCondition(*if_condition));
+                },
+                Simplified::Body(mut branch) => {
+                    returns = eliminate(&mut branch);
+
+                    // Variables of the branch have to stay in their own scope
+                    match branch.statements.iter().any(|statement| matches!(statement, BodyStatementAst::VariableDefinition(_))) {
+                        true => statements.push(BodyStatementAst::IfCondition(always(branch))),
+                        false => statements.extend(branch.statements),
+                    }
+                },
+                Simplified::Removed => (),
+            },
+            statement => {
+                returns = matches!(statement, BodyStatementAst::Return(_));
+                statements.push(statement);
+            },
+        };
+
+        if returns {
+            break;
+        }
+    }
+
+    body.statements = statements;
+    returns
+}
+
+/// Cleans up every branch, returns whether all branches return
+fn eliminate_branches(if_condition: &mut IfConditionAst<'_>) -> bool {
+    let mut returns = eliminate(&mut if_condition.true_body);
+
+    for (_, body) in if_condition.else_ifs.iter_mut() {
+        returns &= eliminate(body);
+    }
+
+    match if_condition.false_body.as_mut() {
+        Some(false_body) => eliminate(false_body) && returns,
+        None => false,
+    }
+}
+
+enum Simplified<'base> {
+    Statement(Box<IfConditionAst<'base>>),
+    Body(BodyAst<'base>),
+    Removed,
+}
+
+/// Drops branches with a constant `false` condition and everything after a constant `true` one
+fn simplify_if(if_condition: IfConditionAst<'_>) -> Simplified<'_> {
+    let IfConditionAst { expression, true_body, else_ifs, false_body } = if_condition;
+    let mut branches = vec![];
+
+    for (expression, body) in std::iter::once((expression, true_body)).chain(else_ifs) {
+        match constant_condition(&expression) {
+            Some(false) => continue,
+            Some(true) if branches.is_empty() => return Simplified::Body(body),
+            Some(true) => return Simplified::Statement(Box::new(build_if(branches, Some(body)))),
+            None => branches.push((expression, body)),
+        };
+    }
+
+    match (branches.is_empty(), false_body) {
+        (true, Some(false_body)) => Simplified::Body(false_body),
+        (true, None) => Simplified::Removed,
+        (false, false_body) => Simplified::Statement(Box::new(build_if(branches, false_body))),
+    }
+}
+
+fn build_if<'base>(branches: Vec<(ExpressionAst<'base>, BodyAst<'base>)>, false_body: Option<BodyAst<'base>>) -> IfConditionAst<'base> {
+    let mut branches = branches.into_iter();
+    let (expression, true_body) = branches.next().expect("If statement without branch, but this is a bug");
+
+    IfConditionAst {
+        expression,
+        true_body,
+        else_ifs: branches.collect(),
+        false_body,
+    }
+}
+
+/// Wraps a body into an `if (true)` statement so it keeps its own scope
+fn always(body: BodyAst<'_>) -> IfConditionAst<'_> {
+    let span = body.statements.iter().find_map(|statement| match statement {
+        BodyStatementAst::VariableDefinition(definition) => Some(definition.name.clone()),
+        _ => None,
+    }).expect("Only bodies with variable definitions keep their scope, but this is a bug");
+
+    IfConditionAst {
+        expression: ExpressionAst::Primitive { span, value: PrimitiveValue::Bool(true) },
+        true_body: body,
+        else_ifs: Vec::new(),
+        false_body: None,
+    }
+}
+
+fn constant_condition(expression: &ExpressionAst<'_>) -> Option<bool> {
+    match expression {
+        ExpressionAst::Primitive { value: PrimitiveValue::Bool(value), .. } => Some(*value),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::optimizer::{tests::compile, OptimizerOptions};
+
+    fn eliminated(code: &str) -> String {
+        let wat = compile(code, OptimizerOptions { dead_code_elimination: true, ..Default::default() });
+        wat[wat.find("(func $main.test").unwrap()..].to_string()
+    }
+
+    #[test]
+    fn constant_branches() {
+        let wat = eliminated("func a(): void { } func b(): void { } func c(): void { } pub func test(): void { if (false) { a(); } else if (true) { b(); } else { c(); } }");
+        assert!(!wat.contains("call $main.a"), "{wat}");
+        assert!(wat.contains("call $main.b"), "{wat}");
+        assert!(!wat.contains("call $main.c"), "{wat}");
+        assert!(!wat.contains("if"), "{wat}");
+    }
+
+    #[test]
+    fn removed_branches_keep_the_rest() {
+        let wat = eliminated("func a(): void { } func b(): void { } pub func test(x: bool): void { if (false) { a(); } else if (x) { b(); } }");
+        assert!(!wat.contains("call $main.a"), "{wat}");
+        assert!(wat.contains("local.get $x\n    if"), "{wat}");
+
+        let wat = eliminated("func a(): void { } pub func test(): void { if (false) { a(); } }");
+        assert!(!wat.contains("call $main.a"), "{wat}");
+    }
+
+    #[test]
+    fn branch_variables_keep_their_scope() {
+        let wat = eliminated("pub func test(): i32 { var a = 1; if (true) { var a = 2; } return a; }");
+        assert!(wat.contains("local.set $a_1"), "{wat}");
+        assert!(wat.contains("local.get $a\n"), "{wat}");
+    }
+
+    #[test]
+    fn unreachable_statements() {
+        let wat = eliminated("func a(): void { } pub func test(x: bool): i32 { if (x) { return 1; a(); } else { return 2; } a(); return 3; }");
+        assert!(!wat.contains("call $main.a"), "{wat}");
+        assert!(!wat.contains("i32.const 3"), "{wat}");
+    }
+}
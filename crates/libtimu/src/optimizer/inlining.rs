@@ -0,0 +1,310 @@
+//! Function inlining pass.
+//!
+//! Calls to small private functions are replaced with the function's body.
+//! A function can be inlined when:
+//!
+//! - it is a private module-level function,
+//! - its body is a single `return` statement,
+//! - the returned expression has at most [`MAX_INLINE_NODES`] nodes and calls
+//!   no other function.
+//!
+//! ```timu
+//! func square(a: i32): i32 { return a * a; }
+//! pub func area(side: i32): i32 { return square(side); }   // becomes side * side
+//! ```
+//!
+//! Only calls whose arguments are variables or literals are inlined, so every
+//! argument is evaluated exactly as often as before. Literals take their type
+//! from the surrounding code, so a call is kept when substituting them would
+//! change the type an operation is evaluated in.
+
+use std::collections::HashMap;
+
+use crate::{
+    ast::{BodyAst, BodyStatementAst, ExpressionAst, FunctionArgumentAst, FunctionCallAst, FunctionDefinitionLocationAst, RefAst},
+    tir::{
+        resolver::{function::FunctionDefinition, statement::{get_primitive_location, infer_expression_type, operand_type}, TypeLocation},
+        PrimitiveType, TirContext, TypeValue,
+    },
+};
+
+use super::{call_target, LocalTypes};
+
+/// Largest expression, counted in nodes, that is copied into the caller
+pub const MAX_INLINE_NODES: usize = 8;
+
+/// A function that can replace its calls
+#[derive(Debug)]
+pub struct Candidate<'base> {
+    parameters: Vec<(String, TypeLocation)>,
+    return_type: TypeLocation,
+    expression: ExpressionAst<'base>,
+    operand_types: Vec<TypeLocation>,
+}
+
+/// Finds every function of the program that can be inlined
+pub fn collect_candidates<'base>(context: &TirContext<'base>) -> HashMap<TypeLocation, Candidate<'base>> {
+    let mut candidates = HashMap::new();
+
+    for (_, location, signature) in context.types.iter() {
+        if let TypeValue::Function(function) = signature.value.as_ref()
+            && let Some(candidate) = build_candidate(context, function) {
+            candidates.insert(location, candidate);
+        }
+    }
+
+    candidates
+}
+
+fn build_candidate<'base>(context: &TirContext<'base>, function: &FunctionDefinition<'base>) -> Option<Candidate<'base>> {
+    if function.is_public || !matches!(function.ast.location.as_ref(), FunctionDefinitionLocationAst::Module) {
+        return None;
+    }
+
+    let expression = match function.ast.body.statements.as_slice() {
+        [BodyStatementAst::Return(return_statement)] => return_statement.expression.as_ref()?,
+        _ => return None,
+    };
+
+    if has_call(expression) || node_count(expression) > MAX_INLINE_NODES {
+        return None;
+    }
+
+    let mut parameters = Vec::new();
+    for (argument, argument_ast) in function.arguments.iter().zip(function.ast.arguments.iter()) {
+        match argument_ast {
+            FunctionArgumentAst::Argument { name, .. } => parameters.push((name.text.to_string(), argument.field_type)),
+            FunctionArgumentAst::This(_) => return None,
+        }
+    }
+
+    let lookup = |name: &str| parameters.iter().find(|(parameter, _)| parameter == name).map(|(_, location)| *location);
+
+    // The caller decides the type of literal only expressions, so the body needs a type of its own
+    if infer_expression_type(context, expression, &lookup) != Some(function.return_type) {
+        return None;
+    }
+
+    let mut operand_types = Vec::new();
+    collect_operand_types(context, expression, Some(function.return_type), &lookup, &mut operand_types);
+
+    Some(Candidate {
+        parameters,
+        return_type: function.return_type,
+        expression: expression.clone(),
+        operand_types,
+    })
+}
+
+/// Inlines the calls of a function body
+pub fn run<'base>(context: &TirContext<'base>, function: &FunctionDefinition<'base>, candidates: &HashMap<TypeLocation, Candidate<'base>>, body: &mut BodyAst<'base>) {
+    let mut inliner = Inliner {
+        context,
+        candidates,
+        locals: LocalTypes::new(function),
+    };
+
+    inliner.inline_body(body);
+}
+
+struct Inliner<'ctx, 'base> {
+    context: &'ctx TirContext<'base>,
+    candidates: &'ctx HashMap<TypeLocation, Candidate<'base>>,
+    locals: LocalTypes,
+}
+
+impl<'base> Inliner<'_, 'base> {
+    fn inline_body(&mut self, body: &mut BodyAst<'base>) {
+        for statement in body.statements.iter_mut() {
+            match statement {
+                BodyStatementAst::VariableDefinition(definition) => {
+                    if let Some(expression) = definition.expression.as_mut() {
+                        self.inline_expression(expression);
+                    }
+                    match &definition.destructure {
+                        Some(names) => names.iter().for_each(|name| self.locals.define(self.context, name)),
+                        None => self.locals.define(self.context, &definition.name),
+                    };
+                },
+                BodyStatementAst::VariableAssign(assign) => self.inline_expression(&mut assign.expression),
+                BodyStatementAst::FunctionCall(function_call) => self.inline_arguments(function_call),
+                BodyStatementAst::IfCondition(if_condition) => {
+                    self.inline_expression(&mut if_condition.expression);
+                    self.inline_block(&mut if_condition.true_body);
+
+                    for (expression, body) in if_condition.else_ifs.iter_mut() {
+                        self.inline_expression(expression);
+                        self.inline_block(body);
+                    }
+
+                    if let Some(false_body) = if_condition.false_body.as_mut() {
+                        self.inline_block(false_body);
+                    }
+                },
+                BodyStatementAst::Match(_) => (),
+                BodyStatementAst::Return(return_statement) => {
+                    if let Some(expression) = return_statement.expression.as_mut() {
+                        self.inline_expression(expression);
+                    }
+                },
+            };
+        }
+    }
+
+    fn inline_block(&mut self, body: &mut BodyAst<'base>) {
+        self.locals.push();
+        self.inline_body(body);
+        self.locals.pop();
+    }
+
+    fn inline_arguments(&mut self, function_call: &mut FunctionCallAst<'base>) {
+        for argument in function_call.arguments.iter_mut() {
+            self.inline_expression(argument);
+        }
+    }
+
+    fn inline_expression(&mut self, expression: &mut ExpressionAst<'base>) {
+        match expression {
+            ExpressionAst::Primitive { .. } | ExpressionAst::Ident(_) | ExpressionAst::Ref(_) | ExpressionAst::Lambda(_) | ExpressionAst::Match(_) => (),
+            ExpressionAst::Tuple(tuple) => tuple.elements.iter_mut().for_each(|element| self.inline_expression(element)),
+            ExpressionAst::Not(inner) => self.inline_expression(inner),
+            ExpressionAst::Operation { left, right, .. } => {
+                self.inline_expression(left);
+                self.inline_expression(right);
+            },
+            ExpressionAst::FunctionCall(function_call) => {
+                self.inline_arguments(function_call);
+
+                if let Some(inlined) = self.inline_call(function_call) {
+                    *expression = inlined;
+                }
+            },
+        };
+    }
+
+    /// Builds the expression that replaces a call, if the call can be inlined
+    fn inline_call(&self, function_call: &FunctionCallAst<'base>) -> Option<ExpressionAst<'base>> {
+        let (location, _) = call_target(self.context, function_call)?;
+        let candidate = self.candidates.get(&location)?;
+
+        // Arguments are copied to every use, so they have to be free of side effects and cheap
+        let mut arguments = HashMap::new();
+        for ((name, location), argument) in candidate.parameters.iter().zip(function_call.arguments.iter()) {
+            match argument {
+                ExpressionAst::Ident(ident) if self.locals.lookup(ident.text) == Some(*location) => (),
+                ExpressionAst::Primitive { .. } => (),
+                _ => return None,
+            };
+            arguments.insert(name.as_str(), argument);
+        }
+
+        let inlined = substitute(&candidate.expression, &arguments)?;
+        let lookup = |name: &str| self.locals.lookup(name);
+
+        if infer_expression_type(self.context, &inlined, &lookup) != Some(candidate.return_type) {
+            return None;
+        }
+
+        let mut operand_types = Vec::new();
+        collect_operand_types(self.context, &inlined, Some(candidate.return_type), &lookup, &mut operand_types);
+
+        match operand_types == candidate.operand_types {
+            true => Some(inlined),
+            false => None,
+        }
+    }
+}
+
+/// Copies the expression with every parameter replaced by its argument
+fn substitute<'base>(expression: &ExpressionAst<'base>, arguments: &HashMap<&str, &ExpressionAst<'base>>) -> Option<ExpressionAst<'base>> {
+    Some(match expression {
+        ExpressionAst::Primitive { .. } => expression.clone(),
+        ExpressionAst::Ident(ident) => (*arguments.get(ident.text)?).clone(),
+        ExpressionAst::Ref(reference) => {
+            let mut names = reference.names.clone();
+            names[0] = match arguments.get(names[0].text)? {
+                ExpressionAst::Ident(ident) => ident.clone(),
+                _ => return None,
+            };
+
+            ExpressionAst::Ref(RefAst { names })
+        },
+        ExpressionAst::Not(inner) => ExpressionAst::Not(Box::new(substitute(inner, arguments)?)),
+        ExpressionAst::Operation { left, operator, right } => ExpressionAst::Operation {
+            left: Box::new(substitute(left, arguments)?),
+            operator: *operator,
+            right: Box::new(substitute(right, arguments)?),
+        },
+        ExpressionAst::FunctionCall(_) | ExpressionAst::Lambda(_) | ExpressionAst::Match(_) | ExpressionAst::Tuple(_) => return None,
+    })
+}
+
+/// Lists the operand type of every operation, in evaluation order
+fn collect_operand_types(context: &TirContext<'_>, expression: &ExpressionAst<'_>, expected: Option<TypeLocation>, lookup: &dyn Fn(&str) -> Option<TypeLocation>, operand_types: &mut Vec<TypeLocation>) {
+    match expression {
+        ExpressionAst::Not(inner) => collect_operand_types(context, inner, Some(get_primitive_location(context, PrimitiveType::Bool)), lookup, operand_types),
+        ExpressionAst::Operation { left, operator, right } => {
+            let location = operand_type(context, left, operator, right, expected, lookup);
+
+            operand_types.push(location);
+            collect_operand_types(context, left, Some(location), lookup, operand_types);
+            collect_operand_types(context, right, Some(location), lookup, operand_types);
+        },
+        _ => (),
+    }
+}
+
+fn has_call(expression: &ExpressionAst<'_>) -> bool {
+    match expression {
+        ExpressionAst::FunctionCall(_) => true,
+        ExpressionAst::Not(inner) => has_call(inner),
+        ExpressionAst::Operation { left, right, .. } => has_call(left) || has_call(right),
+        _ => false,
+    }
+}
+
+fn node_count(expression: &ExpressionAst<'_>) -> usize {
+    match expression {
+        ExpressionAst::Not(inner) => 1 + node_count(inner),
+        ExpressionAst::Operation { left, right, .. } => 1 + node_count(left) + node_count(right),
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::optimizer::{tests::compile, OptimizerOptions};
+
+    fn inlined(code: &str) -> String {
+        let wat = compile(code, OptimizerOptions { inlining: true, ..Default::default() });
+        wat[wat.find("(func $main.test").unwrap()..].to_string()
+    }
+
+    #[test]
+    fn small_private_functions() {
+        let wat = inlined("func square(a: i32): i32 { return a * a; } pub func test(x: i32): i32 { return square(x) + 1; }");
+        assert!(!wat.contains("call $main.square"), "{wat}");
+        assert!(wat.contains("local.get $x\n    local.get $x\n    i32.mul"), "{wat}");
+    }
+
+    #[test]
+    fn kept_calls() {
+        // Public functions, larger bodies and bodies with calls stay as calls
+        assert!(inlined("pub func square(a: i32): i32 { return a * a; } pub func test(x: i32): i32 { return square(x); }").contains("call $main.square"));
+        assert!(inlined("func big(a: i32): i32 { return a + a + a + a + a; } pub func test(x: i32): i32 { return big(x); }").contains("call $main.big"));
+        assert!(inlined("func one(): i32 { return 1; } func wrap(): i32 { return one(); } pub func test(): i32 { return wrap(); }").contains("call $main.wrap"));
+        assert!(inlined("func two(a: i32): i32 { var b = a; return b; } pub func test(x: i32): i32 { return two(x); }").contains("call $main.two"));
+
+        // Arguments with side effects have to be evaluated exactly once
+        assert!(inlined("func one(): i32 { return 1; } func twice(a: i32): i32 { return a + a; } pub func test(): i32 { return twice(one()); }").contains("call $main.twice"));
+    }
+
+    #[test]
+    fn literal_arguments_keep_their_type() {
+        // `200 + 100` would be evaluated as `i32` instead of `u8` once inlined
+        assert!(inlined("func add(a: u8): u8 { return a + 100; } pub func test(): u8 { return add(200); }").contains("call $main.add"));
+
+        let wat = inlined("func add(a: u8, b: u8): u8 { return a + b; } pub func test(x: u8): u8 { return add(x, 200); }");
+        assert!(!wat.contains("call $main.add"), "{wat}");
+    }
+}
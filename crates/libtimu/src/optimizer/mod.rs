@@ -0,0 +1,269 @@
+//! Optimisation passes for type-checked Timu programs.
+//!
+//! The optimizer rewrites function bodies stored in a [`TirContext`] after type
+//! checking has finished and before a backend emits code. Every pass keeps the
+//! observable behaviour of the program, including the overflow semantics of
+//! each primitive type and runtime traps such as integer division by zero.
+//!
+//! # Passes
+//!
+//! - [`inlining`]: Replaces calls to small private functions with their body
+//! - [`constant_folding`]: Evaluates operations whose operands are literals
+//! - [`dead_code`]: Removes branches with constant conditions and statements
+//!   that can never run
+//!
+//! Passes run in that order, so constants exposed by inlining are folded and
+//! conditions that fold to a constant are removed in the same run.
+//!
+//! # Optimisation Levels
+//!
+//! | Level | Passes                                              |
+//! |-------|-----------------------------------------------------|
+//! | `-O0` | none                                                |
+//! | `-O1` | inlining, constant folding and dead code elimination |
+//!
+//! Each pass can also be switched on or off on top of the selected level
+//! through [`OptimizerOptions`].
+//!
+//! # Usage
+//!
+//! ```ignore
+//! let mut context = process_ast(vec![ast.into()])?;
+//! optimizer::optimize(&mut context, OptimizerOptions::from(OptimizationLevel::O1));
+//! let wat = backend::wat::generate(&context)?;
+//! ```
+
+use std::{collections::HashMap, rc::Rc};
+
+use crate::{
+    ast::{BodyAst, FunctionArgumentAst},
+    tir::{context::SpanKey, resolver::{function::FunctionDefinition, TypeLocation}, TirContext, TypeValue},
+};
+
+pub mod constant_folding;
+pub mod dead_code;
+pub mod inlining;
+
+/// Predefined sets of optimisation passes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OptimizationLevel {
+    /// No optimisation, the code is emitted as written
+    #[default]
+    O0,
+    /// All passes enabled
+    O1,
+}
+
+/// Selects the passes the optimizer runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OptimizerOptions {
+    pub inlining: bool,
+    pub constant_folding: bool,
+    pub dead_code_elimination: bool,
+}
+
+impl From<OptimizationLevel> for OptimizerOptions {
+    fn from(level: OptimizationLevel) -> Self {
+        let enabled = level == OptimizationLevel::O1;
+        Self {
+            inlining: enabled,
+            constant_folding: enabled,
+            dead_code_elimination: enabled,
+        }
+    }
+}
+
+/// Runs the enabled passes over every function of the program
+///
+/// # Arguments
+/// * `context` - The type-checked program, rewritten in place
+/// * `options` - The passes to run
+pub fn optimize(context: &mut TirContext<'_>, options: OptimizerOptions) {
+    let functions = context.types.iter()
+        .filter(|(_, _, signature)| matches!(signature.value.as_ref(), TypeValue::Function(_)))
+        .map(|(_, location, _)| location)
+        .collect::<Vec<_>>();
+
+    // Candidates are collected up front, so every call site sees the original callee
+    let candidates = match options.inlining {
+        true => inlining::collect_candidates(context),
+        false => HashMap::new(),
+    };
+
+    for location in functions.into_iter() {
+        let body = match context.types.get_from_location(location).map(|signature| signature.value.as_ref()) {
+            Some(TypeValue::Function(function)) => {
+                let mut body = BodyAst::clone(&function.ast.body);
+
+                if options.inlining {
+                    inlining::run(context, function, &candidates, &mut body);
+                }
+
+                if options.constant_folding {
+                    constant_folding::run(context, function, &mut body);
+                }
+
+                if options.dead_code_elimination {
+                    dead_code::run(&mut body);
+                }
+
+                body
+            },
+            _ => continue,
+        };
+
+        if let Some(TypeValue::Function(function)) = context.types.get_mut_from_location(location).map(|signature| signature.value.as_mut()) {
+            function.ast.body = Rc::new(body);
+        }
+    }
+}
+
+/// Types of the arguments and variables visible at a point of a function body
+///
+/// Passes walk the statements in order and mirror the block scopes of the
+/// type checker, so a name always resolves to the variable the backend will use.
+#[derive(Debug)]
+pub(crate) struct LocalTypes {
+    scopes: Vec<HashMap<String, TypeLocation>>,
+}
+
+impl LocalTypes {
+    pub fn new(function: &FunctionDefinition<'_>) -> Self {
+        let mut arguments = HashMap::new();
+        for (argument, argument_ast) in function.arguments.iter().zip(function.ast.arguments.iter()) {
+            let name = match argument_ast {
+                FunctionArgumentAst::This(_) => "this",
+                FunctionArgumentAst::Argument { name, .. } => name.text,
+            };
+            arguments.insert(name.to_string(), argument.field_type);
+        }
+
+        Self { scopes: vec![arguments] }
+    }
+
+    pub fn push(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    pub fn pop(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Adds a variable definition, its type is the one recorded by the type checker
+    pub fn define(&mut self, context: &TirContext<'_>, name: &crate::nom_tools::Span<'_>) {
+        if let Some(location) = context.variable_types.get(&SpanKey::from(name)) {
+            self.scopes.last_mut().expect("Function scope missing, but this is a bug").insert(name.text.to_string(), *location);
+        }
+    }
+
+    pub fn lookup(&self, name: &str) -> Option<TypeLocation> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name).copied())
+    }
+}
+
+/// Returns the function a call expression was resolved to
+pub(crate) fn call_target<'ctx, 'base>(context: &'ctx TirContext<'base>, function_call: &crate::ast::FunctionCallAst<'_>) -> Option<(TypeLocation, &'ctx FunctionDefinition<'base>)> {
+    let location = *context.call_targets.get(&SpanKey::from(&function_call.call_span))?;
+    match context.types.get_from_location(location).map(|signature| signature.value.as_ref()) {
+        Some(TypeValue::Function(function)) => Some((location, function)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use wasmi::{Engine, Linker, Module, Store};
+
+    use crate::{backend::wat, file::SourceFile, nom_tools::State, process_ast, process_code};
+
+    use super::{optimize, OptimizationLevel, OptimizerOptions};
+
+    pub fn compile(code: &str, options: OptimizerOptions) -> String {
+        let state = State::new(SourceFile::new(vec!["main".into()], code.to_string()));
+        let ast = process_code(&state).unwrap();
+        let mut context = process_ast(vec![ast.into()]).unwrap();
+        optimize(&mut context, options);
+        wat::generate(&context).unwrap()
+    }
+
+    #[test]
+    fn levels() {
+        assert_eq!(OptimizerOptions::from(OptimizationLevel::O0), OptimizerOptions::default());
+        let options = OptimizerOptions::from(OptimizationLevel::O1);
+        assert!(options.inlining && options.constant_folding && options.dead_code_elimination);
+    }
+
+    #[test]
+    fn o0_keeps_code() {
+        let code = "pub func test(): i32 { return 1 + 2; }";
+        let wat = compile(code, OptimizationLevel::O0.into());
+        let test = &wat[wat.find("(func $main.test").unwrap()..];
+        assert!(test.contains("i32.add"), "{test}");
+    }
+
+    #[test]
+    fn o1_pipeline() {
+        let code = r#"
+func double_it(a: i32): i32 { return a * 2; }
+pub func test(a: i32): i32 {
+    if (double_it(a) > 4 * 5 && true) {
+        return 1;
+    }
+    return 0;
+    return 7;
+}
+"#;
+        let wat = compile(code, OptimizationLevel::O1.into());
+        let test = &wat[wat.find("(func $main.test").unwrap()..];
+        assert!(test.contains("i32.const 20"), "{test}");
+        assert!(!test.contains("call $main.double_it"), "{test}");
+        assert!(!test.contains("i32.const 7"), "{test}");
+    }
+
+    #[test]
+    fn passes_are_independent() {
+        let code = r#"
+func double_it(a: i32): i32 { return a * 2; }
+pub func test(a: i32): i32 { return double_it(a) + 2 * 3; }
+"#;
+        let wat = compile(code, OptimizerOptions { constant_folding: true, ..Default::default() });
+        let test = &wat[wat.find("(func $main.test").unwrap()..];
+        assert!(test.contains("call $main.double_it"), "{test}");
+        assert!(test.contains("i32.const 6"), "{test}");
+
+        let wat = compile(code, OptimizerOptions { inlining: true, ..Default::default() });
+        let test = &wat[wat.find("(func $main.test").unwrap()..];
+        assert!(!test.contains("call $main.double_it"), "{test}");
+        assert!(test.contains("i32.const 3"), "{test}");
+    }
+
+    #[test]
+    fn results_do_not_change() {
+        let code = r#"
+func add(a: u8, b: u8): u8 { return a + b; }
+func is_big(a: i32): bool { return a > 1000; }
+pub func test(x: i32, y: u8): u8 {
+    var small: u8 = add(y, 250) + (200 + 100);
+    var wide = x * (65536 * 65536 + 3);
+    if (is_big(wide) || false) {
+        return small - 1;
+    } else if (!(small > 40) && true) {
+        return small;
+    }
+    return small + (255 + 2);
+}
+"#;
+        let run = |level: OptimizationLevel, x: i32, y: i32| {
+            let wasm = ::wat::parse_str(compile(code, level.into())).unwrap();
+            let engine = Engine::default();
+            let module = Module::new(&engine, &wasm[..]).unwrap();
+            let mut store = Store::new(&engine, ());
+            let instance = Linker::<()>::new(&engine).instantiate(&mut store, &module).unwrap().start(&mut store).unwrap();
+            instance.get_typed_func::<(i32, i32), i32>(&store, "main.test").unwrap().call(&mut store, (x, y)).unwrap()
+        };
+
+        for (x, y) in [(0, 0), (5, 10), (2000, 3), (-7, 255), (i32::MAX, 100)] {
+            assert_eq!(run(OptimizationLevel::O0, x, y), run(OptimizationLevel::O1, x, y), "x = {x}, y = {y}");
+        }
+    }
+}
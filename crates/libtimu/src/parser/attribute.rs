@@ -0,0 +1,69 @@
+//! Attribute parsing for the Timu language.
+//!
+//! Attributes are written in front of top-level statements and start with
+//! `@`, followed by the attribute name and a list of names in parentheses:
+//!
+//! ```timu
+//! @allow(unused_imports)
+//! use lib.Helper;
+//! ```
+//!
+//! The parser only reads the syntax, the meaning of an attribute is up to the
+//! pass that reads it, such as the [`lint`](crate::lint) pass for `@allow`.
+
+use std::fmt::{Display, Formatter};
+
+use nom::character::complete::char;
+use nom::combinator::{consumed, cut};
+use nom::error::context;
+use nom::multi::separated_list0;
+use nom::sequence::delimited;
+use nom::{IResult, Parser};
+
+use crate::ast::AttributeAst;
+use crate::nom_tools::{cleanup, trivia, NomSpan};
+
+use super::{expected_ident, ident, TimuParserError};
+
+impl AttributeAst<'_> {
+    /// Parses an attribute, the target is left empty for the caller to fill
+    ///
+    /// # Errors
+    /// Returns errors for a missing name, a missing list of names or an
+    /// unclosed list.
+    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, AttributeAst<'_>, TimuParserError<'_>> {
+        let (input, _) = trivia(input)?;
+        let (input, (span, (name, arguments))) = consumed(|input| {
+            let (input, _) = char('@').parse(input)?;
+            let (input, name) = expected_ident("Missing attribute name", input)?;
+            let (input, arguments) = context("Attribute's names missing", cut(delimited(
+                cleanup(char('(')),
+                separated_list0(cleanup(char(',')), ident()),
+                context("Attribute's closing ')' missing", cut(char(')'))),
+            ))).parse(input)?;
+            Ok((input, (name, arguments)))
+        }).parse(input)?;
+        let (input, _) = trivia(input)?;
+
+        let start = span.location_offset();
+        Ok((input, AttributeAst {
+            name: name.into(),
+            arguments: arguments.into_iter().map(|argument| argument.into()).collect(),
+            position: start..start + span.fragment().len(),
+            target: 0..0,
+        }))
+    }
+}
+
+impl Display for AttributeAst<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "@{}(", self.name.text)?;
+        for (index, argument) in self.arguments.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", argument.text)?;
+        }
+        write!(f, ")")
+    }
+}
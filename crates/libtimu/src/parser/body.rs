@@ -47,6 +47,8 @@
 //! ```timu
 //! if (condition) { /* body */ }
 //! if (condition) { /* if body */ } else { /* else body */ }
+//! match (value) { 1 => run(), _ => {} }
+//! return value;
 //! ```
 //!
 //! # Parsing Architecture
@@ -75,7 +77,7 @@ use std::fmt::{Display, Formatter};
 
 use nom::{branch::alt, character::complete::char, combinator::cut, error::context, multi::many0, IResult, Parser};
 
-use crate::{ast::{BodyAst, BodyStatementAst, FunctionCallAst, IfConditionAst, VariableAssignAst, VariableDefinitionAst}, nom_tools::{cleanup, NomSpan}};
+use crate::{ast::{BodyAst, BodyStatementAst, FunctionCallAst, IfConditionAst, MatchAst, ReturnAst, VariableAssignAst, VariableDefinitionAst}, nom_tools::{cleanup, NomSpan}};
 
 use super::TimuParserError;
 
@@ -96,9 +98,11 @@ impl BodyAst<'_> {
     /// 
     /// # Supported Statements
     /// - **If conditions**: Conditional execution with optional else clauses
+    /// - **Matches**: Match used as a statement, see `MatchAst::parse`
     /// - **Function calls**: Method invocations and function calls (as statements)
     /// - **Variable assignments**: Assignment of new values to existing variables
     /// - **Variable definitions**: Declaration of new variables and constants
+    /// - **Return statements**: Leaving the function with an optional value
     /// 
     /// # Arguments
     /// * `input` - The input span to parse from
@@ -135,10 +139,12 @@ impl BodyAst<'_> {
     pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, BodyAst<'_>, TimuParserError<'_>> {
         let (input, _) = context("Body's opening '{' missing", cut(cleanup(char('{')))).parse(input)?;
         let (input, statements) = many0(alt((
+            ReturnAst::parse_body_statement,
             IfConditionAst::parse_body_statement,
+            MatchAst::parse_body_statement,
+            VariableDefinitionAst::parse_body_statement,
             FunctionCallAst::parse_body_statement,
             VariableAssignAst::parse_body_statement,
-            VariableDefinitionAst::parse_body_statement,
         ))).parse(input)?;
         let (input, _) = context("Body's closing '}' missing", cut(cleanup(char('}')))).parse(input)?;
 
@@ -171,6 +177,8 @@ impl Display for BodyStatementAst<'_> {
             BodyStatementAst::VariableAssign(var) => write!(f, "{var}"),
             BodyStatementAst::FunctionCall(func) => write!(f, "{func};"),
             BodyStatementAst::IfCondition(if_condition) => write!(f, "{if_condition}"),
+            BodyStatementAst::Match(match_ast) => write!(f, "{match_ast}"),
+            BodyStatementAst::Return(return_statement) => write!(f, "{return_statement}"),
         }
     }
 }
@@ -7,7 +7,7 @@
 //! # Class Syntax
 //!
 //! ```timu
-//! class ClassName {
+//! class ClassName : BaseClass {
 //!     // Fields
 //!     field1: Type1;
 //!     field2: Type2;
@@ -43,18 +43,20 @@
 //! - **Instantiation**: Creating objects from class definitions
 //! - **Method calls**: Invoking methods on object instances
 //! - **Field access**: Reading and writing object field values
+//! - **Inheritance**: A class inherits the fields and methods of its single
+//!   base class and can override the methods with the same signature
 
 use std::fmt::{Display, Formatter};
 
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::char;
-use nom::combinator::{cut, peek};
+use nom::combinator::{cut, opt, peek};
 use nom::error::context;
 use nom::multi::many0;
 use nom::{IResult, Parser, sequence::delimited};
 
-use crate::ast::{AstIndex, ClassDefinitionFieldAst, FieldAst, FunctionDefinitionAst};
+use crate::ast::{AstIndex, ClassDefinitionFieldAst, FieldAst, FunctionDefinitionAst, TypeNameAst};
 use crate::{ast::{ClassDefinitionAst, FileStatementAst}, nom_tools::{cleanup, NomSpan}};
 
 use super::{expected_ident, is_public, TimuParserError};
@@ -73,6 +75,8 @@ impl ClassDefinitionAst<'_> {
     ///     field2: Type2;
     ///     func method(this, param: Type): ReturnType { }
     /// }
+    ///
+    /// class Child : ClassName { }
     /// ```
     /// 
     /// # Arguments
@@ -85,6 +89,7 @@ impl ClassDefinitionAst<'_> {
     /// # Errors
     /// Returns errors for:
     /// - Missing class name
+    /// - Missing base class after `:`
     /// - Missing opening brace
     /// - Missing closing brace
     /// - Invalid field or method syntax within class body
@@ -98,6 +103,15 @@ impl ClassDefinitionAst<'_> {
         let (input, is_public) = is_public(input)?;
         let (input, _) = cleanup(tag("class")).parse(input)?;
         let (input, name) = expected_ident("Missing class name", input)?;
+
+        let (input, base_class) = match cleanup(opt(char(':'))).parse(input)? {
+            (input, Some(_)) => {
+                let (input, base_class) = context("Missing base class", cut(TypeNameAst::parse)).parse(input)?;
+                (input, Some(base_class))
+            }
+            (input, None) => (input, None),
+        };
+
         let (input, _) = context("Class's opening '{' missing", cut(peek(cleanup(char('{'))))).parse(input)?;
         let (input, fields) = delimited(
             char('{'),
@@ -117,6 +131,7 @@ impl ClassDefinitionAst<'_> {
             FileStatementAst::Class(ClassDefinitionAst {
                 is_public: is_public.map(|item| item.into()),
                 name: name.into(),
+                base_class,
                 fields,
                 index
             }.into()),
@@ -126,9 +141,15 @@ impl ClassDefinitionAst<'_> {
 
 impl Display for ClassDefinitionAst<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}class {} {{", 
+        write!(f, "{}class {}", 
                if self.is_public.is_some() { "pub " } else { "" }, 
                self.name.text)?;
+
+        if let Some(base_class) = &self.base_class {
+            write!(f, ": {base_class}")?;
+        }
+
+        write!(f, " {{")?;
         for field in self.fields.iter() {
             write!(f, "{field}")?;
         }
@@ -0,0 +1,164 @@
+//! Enum definition parsing for the Timu language.
+//!
+//! An enum lists the variants its values can be. A variant can carry values,
+//! its payload, so enums model results and trees as tagged unions.
+//!
+//! # Enum Syntax
+//!
+//! ```timu
+//! enum Color { Red, Green, Blue }
+//!
+//! pub enum Shape {
+//!     Circle(float),
+//!     Rect(float, float),
+//!     Empty,
+//! }
+//! ```
+//!
+//! # Enum Components
+//!
+//! - **Visibility**: A `pub` enum can be imported from other modules
+//! - **Name**: The name of the enum type
+//! - **Variants**: Comma separated, a trailing comma is allowed
+//! - **Payload**: The types of the values a variant carries, in parentheses
+//!
+//! # Usage
+//!
+//! Variants with a payload are called like functions and plain variants are
+//! referenced like static fields. A match binds the payload of a variant:
+//!
+//! ```timu
+//! var shape = Shape.Rect(2.0, 3.0);
+//! var empty = ref Shape.Empty;
+//! var area = match (shape) {
+//!     Shape.Circle(radius) => radius * radius * 3.14,
+//!     Shape.Rect(width, height) => width * height,
+//!     Shape.Empty => 0.0,
+//! };
+//! ```
+
+use std::fmt::{Display, Formatter};
+
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, multispace1};
+use nom::combinator::{cut, opt};
+use nom::error::context;
+use nom::multi::separated_list0;
+use nom::multi::separated_list1;
+use nom::sequence::{preceded, terminated};
+use nom::{IResult, Parser};
+
+use crate::ast::{AstIndex, EnumDefinitionAst, EnumVariantAst, FileStatementAst, TypeNameAst};
+use crate::nom_tools::{cleanup, NomSpan};
+
+use super::{expected_ident, ident, is_public, TimuParserError};
+
+impl EnumDefinitionAst<'_> {
+    /// Parses an enum definition
+    ///
+    /// # Parsing Logic
+    /// 1. Parse the optional `pub` keyword and the `enum` keyword
+    /// 2. Parse the enum name (required)
+    /// 3. Parse the comma separated variants between braces
+    /// 4. Parse the payload types of every variant that has parentheses
+    ///
+    /// # Arguments
+    /// * `input` - The input span to parse from
+    ///
+    /// # Returns
+    /// * `Ok((remaining, statement))` - Successfully parsed enum
+    /// * `Err(error)` - Parse error with detailed context
+    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, FileStatementAst<'_>, TimuParserError<'_>> {
+        let (input, is_public) = is_public(input)?;
+        let (input, _) = cleanup(terminated(tag("enum"), multispace1)).parse(input)?;
+        let (input, name) = expected_ident("Missing enum name", input)?;
+        let (input, _) = context("Enum's opening '{' missing", cut(cleanup(char('{')))).parse(input)?;
+        let (input, variants) = separated_list0(cleanup(char(',')), EnumVariantAst::parse).parse(input)?;
+        let (input, _) = opt(cleanup(char(','))).parse(input)?;
+        let (input, _) = context("Enum's closing '}' missing", cut(cleanup(char('}')))).parse(input)?;
+        let index = AstIndex(input.extra.indexer.fetch_add(1, std::sync::atomic::Ordering::Relaxed));
+
+        Ok((
+            input,
+            FileStatementAst::Enum(EnumDefinitionAst {
+                is_public: is_public.map(|item| item.into()),
+                name: name.into(),
+                variants,
+                index,
+            }.into()),
+        ))
+    }
+}
+
+impl EnumVariantAst<'_> {
+    /// Parses a variant name and its optional payload types
+    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, EnumVariantAst<'_>, TimuParserError<'_>> {
+        let (input, name) = ident().parse(input)?;
+        let (input, payload) = opt(preceded(
+            char('('),
+            cut(terminated(
+                context("Missing payload type", separated_list1(cleanup(char(',')), cleanup(TypeNameAst::parse))),
+                context("Missing ')'", cleanup(char(')'))),
+            )),
+        )).parse(input)?;
+
+        Ok((
+            input,
+            EnumVariantAst {
+                name: name.into(),
+                payload: payload.unwrap_or_default(),
+            },
+        ))
+    }
+}
+
+impl Display for EnumDefinitionAst<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}enum {} {{{}}}",
+               if self.is_public.is_some() { "pub " } else { "" },
+               self.name.text,
+               self.variants.iter().map(|variant| variant.to_string()).collect::<Vec<_>>().join(", "))
+    }
+}
+
+impl Display for EnumVariantAst<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name.text)?;
+        if !self.payload.is_empty() {
+            write!(f, "({})", self.payload.iter().map(|field_type| field_type.to_string()).collect::<Vec<_>>().join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::Finish;
+    use rstest::rstest;
+
+    use crate::{file::SourceFile, nom_tools::State};
+
+    #[rstest]
+    #[case("enum Color { Red, Green, Blue }", "enum Color {Red, Green, Blue}")]
+    #[case(" pub  enum Shape{Circle( float ),Rect(float,float),Empty,} ", "pub enum Shape {Circle(float), Rect(float, float), Empty}")]
+    #[case("enum Never {}", "enum Never {}")]
+    #[case("enum Tree { Leaf(?lib.Value), Node(Tree, (i32, string), func(i32): bool) }", "enum Tree {Leaf(?lib.Value), Node(Tree, (i32, string), func(i32): bool)}")]
+    fn enum_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
+        let state = State::new(SourceFile::new(vec!["<memory>".into()], code.to_string()));
+        let (_, response) = crate::parser::parse(&state).finish().unwrap();
+        assert_eq!(response.to_string(), expected, "{code}");
+    }
+
+    #[rstest]
+    #[case("enum { Red }")]
+    #[case("enum Color Red, Green")]
+    #[case("enum Color { Red Green }")]
+    #[case("enum Color { Red, Green")]
+    #[case("enum Shape { Circle() }")]
+    #[case("enum Shape { Circle(float }")]
+    #[case("enum Shape { Circle(float,) }")]
+    fn invalid_enum_test<'base>(#[case] code: &'base str) {
+        let state = State::new(SourceFile::new(vec!["<memory>".into()], code.to_string()));
+        assert!(crate::parser::parse(&state).finish().is_err(), "{code}");
+    }
+}
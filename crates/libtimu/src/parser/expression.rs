@@ -9,12 +9,12 @@
 //! - Comparison operations: `==`, `!=`, `<`, `>`, `<=`, `>=`
 //! - Function calls and method invocations
 //! - Variable references and field access
-//! - Parenthesized expressions
+//! - Parenthesized expressions and tuples
 //!
 //! # Operator Precedence
 //!
 //! The parser implements the following precedence levels (highest to lowest):
-//! 1. **Primary expressions**: literals, identifiers, parentheses, function calls
+//! 1. **Primary expressions**: literals, identifiers, parentheses, function calls, lambdas, matches
 //! 2. **Multiplicative**: `*`, `/`, `%`
 //! 3. **Additive**: `+`, `-`
 //! 4. **Bitwise shift**: `<<`, `>>`
@@ -34,9 +34,11 @@
 
 use std::fmt::{Display, Formatter};
 
-use nom::{branch::alt, bytes::complete::tag, character::complete::char, combinator::{cut, not, value}, error::context, multi::many, sequence::{delimited, pair, preceded}, IResult, Parser};
+use nom::{branch::alt, bytes::complete::tag, character::complete::char, combinator::{consumed, cut, not, opt, value}, error::context, multi::{many, separated_list1}, sequence::{delimited, pair, preceded}, IResult, Parser};
 
-use crate::{ast::{ExpressionAst, ExpressionOperatorType, FunctionCallAst, PrimitiveValue, RefAst}, nom_tools::{cleanup, NomSpan}};
+use crate::{ast::{ExpressionAst, ExpressionOperatorType, FunctionCallAst, LambdaAst, MatchAst, PrimitiveValue, RefAst, TupleAst}, nom_tools::{cleanup, NomSpan}};
+
+use nom_language::error::{VerboseError, VerboseErrorKind};
 
 use super::{ident, TimuParserError};
 
@@ -52,7 +54,7 @@ pub type ControlExpressionGeneratorFn<'base, T> = fn(ExpressionAst<'base>, T, Ex
 /// interface for parsing expressions at that level.
 pub trait TimuExpressionParser {
     /// Parse an expression at this precedence level
-    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>>;
+    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>>;
 }
 
 // Precedence level parsers (listed from lowest to highest precedence)
@@ -91,37 +93,37 @@ struct MulDivModParser;
 struct InnerParser;
 
 impl TimuExpressionParser for OrParser {
-    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         ExpressionAst::single_parser::<'_, AndParser, _, _>(input, ExpressionOperatorType::Or, tag("||"), ExpressionAst::expr_builder)
     }
 }
 
 impl TimuExpressionParser for AndParser {
-    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         ExpressionAst::single_parser::<'_, BitwiseXorParser, _, _>(input, ExpressionOperatorType::And, tag("&&"), ExpressionAst::expr_builder)
     }
 }
 
 impl TimuExpressionParser for BitwiseXorParser {
-    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         ExpressionAst::single_parser::<'_, BitwiseOrParser, _, _>(input, ExpressionOperatorType::Xor, char('^'), ExpressionAst::expr_builder)
     }
 }
 
 impl TimuExpressionParser for BitwiseOrParser {
-    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         ExpressionAst::single_parser::<'_, BitwiseAndParser, _, _>(input, ExpressionOperatorType::LogicalOr, (char('|'), not(char('|'))), ExpressionAst::expr_builder)
     }
 }
 
 impl TimuExpressionParser for BitwiseAndParser {
-    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         ExpressionAst::single_parser::<'_, EqualParser, _, _>(input, ExpressionOperatorType::LogicalAnd, (char('&'), not(char('&'))), ExpressionAst::expr_builder)
     }
 }
 
 impl TimuExpressionParser for EqualParser {
-    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         ExpressionAst::value_parser::<'_, LessEqualParser, _, _>(input, alt((
             value(ExpressionOperatorType::Equal, tag("==")),
             value(ExpressionOperatorType::NotEqual, tag("!="))
@@ -130,7 +132,7 @@ impl TimuExpressionParser for EqualParser {
 }
 
 impl TimuExpressionParser for LessEqualParser {
-    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         ExpressionAst::value_parser::<'_, BitwiseShiftParser, _, _>(input, alt((
             value(ExpressionOperatorType::LessEqualThan, tag("<=")),
             value(ExpressionOperatorType::GreaterEqualThan, tag(">=")),
@@ -141,7 +143,7 @@ impl TimuExpressionParser for LessEqualParser {
 }
 
 impl TimuExpressionParser for BitwiseShiftParser {
-    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         ExpressionAst::value_parser::<'_, AddSubParser, _, _>(input, alt((
             value(ExpressionOperatorType::BitwiseShiftRight, tag(">>")),
             value(ExpressionOperatorType::BitwiseShiftLeft, tag("<<")),
@@ -150,7 +152,7 @@ impl TimuExpressionParser for BitwiseShiftParser {
 }
 
 impl TimuExpressionParser for AddSubParser {
-    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         ExpressionAst::value_parser::<'_, MulDivModParser, _, _>(input, alt((
             value(ExpressionOperatorType::Add, char('+')),
             value(ExpressionOperatorType::Sub, char('-'))
@@ -159,7 +161,7 @@ impl TimuExpressionParser for AddSubParser {
 }
 
 impl TimuExpressionParser for MulDivModParser {
-    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         ExpressionAst::value_parser::<'_, InnerParser, _, _>(input, alt((
             value(ExpressionOperatorType::Div, char('/')),
             value(ExpressionOperatorType::Mul, char('*')),
@@ -169,18 +171,20 @@ impl TimuExpressionParser for MulDivModParser {
 }
 
 impl TimuExpressionParser for InnerParser {
-    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         ExpressionAst::inner(input)
     }
 }
 
 impl ExpressionAst<'_> {
-    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         OrParser::parse(input)
     }
 
-    fn inner(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    fn inner(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         let (input, expression) = cleanup(alt((
+            LambdaAst::parse_for_expression,
+            MatchAst::parse_for_expression,
             RefAst::parse_for_expression,
             FunctionCallAst::parse_for_expression,
             PrimitiveValue::parse_for_expression,
@@ -192,12 +196,27 @@ impl ExpressionAst<'_> {
         Ok((input, expression))
     }
 
-    pub fn parentheses(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
-        let (input, expr) = delimited(char('('), cleanup(Self::parse), char(')')).parse(input)?;
-        Ok((input, expr))
+    /// Parses an expression in parentheses, or a tuple when the parentheses hold several elements
+    ///
+    /// A trailing comma is allowed after the last element of a tuple, but a
+    /// single element followed by a comma is rejected, tuples need at least two elements.
+    pub fn parentheses(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
+        let (rest, (span, (mut elements, comma))) = consumed(delimited(
+            char('('),
+            (cleanup(separated_list1(char(','), cleanup(Self::parse))), opt(cleanup(char(',')))),
+            char(')'),
+        )).parse(input.clone())?;
+
+        match (elements.len(), comma) {
+            (1, None) => Ok((rest, elements.remove(0))),
+            (1, Some(_)) => Err(nom::Err::Failure(VerboseError {
+                errors: vec![(input, VerboseErrorKind::Context("Tuples need at least two elements"))],
+            })),
+            _ => Ok((rest, ExpressionAst::Tuple(TupleAst { span: span.into(), elements }))),
+        }
     }
 
-    pub fn not(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst, TimuParserError<'_>> {
+    pub fn not(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
         let (input, _) = cleanup(char('!')).parse(input)?;
         let (input, expression) = context("Expression missing", cut(Self::inner)).parse(input)?;
         Ok((
@@ -266,10 +285,19 @@ impl Display for ExpressionAst<'_> {
             ExpressionAst::Not(expression) => {
                 write!(f, "!{expression}")
             },
+            ExpressionAst::Lambda(lambda) => write!(f, "{lambda}"),
+            ExpressionAst::Match(match_ast) => write!(f, "{match_ast}"),
+            ExpressionAst::Tuple(tuple) => write!(f, "{tuple}"),
         }
     }
 }
 
+impl Display for TupleAst<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({})", self.elements.iter().map(|element| element.to_string()).collect::<Vec<_>>().join(", "))
+    }
+}
+
 impl Display for ExpressionOperatorType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -369,4 +397,23 @@ mod tests {
         let (_, response) = ExpressionAst::parse(input).unwrap();
         assert_eq!(response.to_string(), expected, "{code}");
     }
+
+    #[rstest]
+    #[case("(1, \"one\")", Some("(1, one)"))]
+    #[case("( a , (b, call(1, 2)), )", Some("(a, (b, call(1, 2)))"))]
+    #[case("(1 + 2, ref pair.0) ", Some("((1 + 2), ref pair.0)"))]
+    #[case("((1))", Some("1"))]
+    #[case("(1,)", None)]
+    fn tuple_test(#[case] code: &str, #[case] expected: Option<&str>) {
+        let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+
+        let state = State {
+            file: source_file.clone(),
+            indexer: Default::default(),
+        };
+
+        let input = NomSpan::new_extra(source_file.code().as_str(), state);
+        let response = ExpressionAst::parse(input).ok().map(|(_, response)| response.to_string());
+        assert_eq!(response.as_deref(), expected, "{code}");
+    }
 }
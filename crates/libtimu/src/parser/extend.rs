@@ -72,7 +72,7 @@ use nom::character::complete::char;
 use nom::combinator::{cut, peek};
 use nom::error::context;
 use nom::multi::{many0, separated_list1};
-use nom::{IResult, Parser, sequence::delimited};
+use nom::{IResult, Parser};
 
 use crate::ast::{ExtendDefinitionAst, ExtendDefinitionFieldAst, FieldAst, FunctionDefinitionAst, TypeNameAst};
 use crate::{ast::FileStatementAst, nom_tools::{cleanup, NomSpan}};
@@ -137,13 +137,15 @@ impl ExtendDefinitionAst<'_> {
         let (input, base_interfaces) = context("Missing interface type(s)", cut(separated_list1(tag(","), TypeNameAst::parse))).parse(input)?;
 
         let (input, _) = context("Extend's opening '{' missing", cut(peek(cleanup(char('{'))))).parse(input)?;
-        let (input, fields) = delimited(
+        let (input, (_, fields, close_brace)) = (
             char('{'),
             cleanup(many0(alt((
-                FunctionDefinitionAst::parse_extend_function,
+                |input| {
+                    FunctionDefinitionAst::parse_extend_function(input, name.clone())
+                },
                 FieldAst::parse_extend_field
             )))),
-            context("Extend's closing '}' missing", cut(char('}'))),
+            context("Extend's closing '}' missing", cut(tag("}"))),
         )
         .parse(input)?;
     
@@ -151,7 +153,9 @@ impl ExtendDefinitionAst<'_> {
             reference: false,
             nullable: false,
             names: vec![name.clone().into()],
-            names_span: name.into()
+            names_span: name.into(),
+            function: None,
+            tuple: None,
         };
 
         Ok((
@@ -160,6 +164,7 @@ impl ExtendDefinitionAst<'_> {
                 name,
                 fields,
                 base_interfaces,
+                close_brace: close_brace.into(),
             }.into()),
         ))
     }
@@ -21,6 +21,7 @@
 //!     privateField: string;
 //!     pub publicField: i32;
 //!     optionalField: ?string;
+//!     static counter: i32;
 //! }
 //! ```
 //!
@@ -47,6 +48,11 @@
 //! - **`pub` (public)**: Fields accessible from outside the declaring type
 //! - **Extension fields**: Always implicitly public (explicit `pub` is an error)
 //!
+//! ## Static Modifier
+//! - **`static`**: The field belongs to the class and is accessed through the
+//!   type name (`MyClass.counter`), it is not part of the objects
+//! - **Classes only**: Interface and extension fields cannot be static
+//!
 //! ## Type Annotations
 //! - **Required**: All fields must have explicit type declarations
 //! - **Type modifiers**: Supports nullable (`?Type`) and reference (`ref Type`) types
@@ -84,7 +90,9 @@
 
 use std::fmt::{Display, Formatter};
 
-use nom::character::complete::char;
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, multispace1};
+use nom::combinator::opt;
 use nom::sequence::terminated;
 use nom::{IResult, Parser};
 use nom_language::error::{VerboseError, VerboseErrorKind};
@@ -95,15 +103,18 @@ use crate::nom_tools::{cleanup, NomSpan};
 use super::{ident, is_public, TimuParserError};
 
 impl FieldAst<'_> {
-    pub fn parse_field(input: NomSpan<'_>) -> IResult<NomSpan<'_>, (Option<NomSpan<'_>>, FieldAst<'_>), TimuParserError<'_>> {
-        let (input, (is_public, name, field_type, _)) =
-            (is_public, cleanup(terminated(ident(), cleanup(char(':')))), cleanup(TypeNameAst::parse), cleanup(char(';'))).parse(input)?;
+    /// Parses a field, returning the spans of the `pub` and `static` modifiers with it
+    pub fn parse_field(input: NomSpan<'_>) -> IResult<NomSpan<'_>, (Option<NomSpan<'_>>, Option<NomSpan<'_>>, FieldAst<'_>), TimuParserError<'_>> {
+        let (input, (is_public, is_static, name, field_type, _)) =
+            (is_public, opt(cleanup(terminated(tag("static"), multispace1))), cleanup(terminated(ident(), cleanup(char(':')))), cleanup(TypeNameAst::parse), cleanup(char(';'))).parse(input)?;
 
         let original_is_public = is_public.clone();
+        let original_is_static = is_static.clone();
         Ok((
             input,
-            (original_is_public, FieldAst {
+            (original_is_public, original_is_static, FieldAst {
                 is_public: is_public.map(|item| item.into()),
+                is_static: is_static.map(|item| item.into()),
                 name: name.into(),
                 field_type,
             },
@@ -111,17 +122,19 @@ impl FieldAst<'_> {
     }
 
     pub fn parse_class_field(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ClassDefinitionFieldAst<'_>, TimuParserError<'_>> {
-        let (input, (_, field)) = Self::parse_field(input)?;
+        let (input, (_, _, field)) = Self::parse_field(input)?;
         Ok((input, ClassDefinitionFieldAst::Field(field)))
     }
 
     pub fn parse_interface_field(input: NomSpan<'_>) -> IResult<NomSpan<'_>, InterfaceDefinitionFieldAst<'_>, TimuParserError<'_>> {
-        let (input, (_, field)) = Self::parse_field(input)?;
+        let (input, (_, is_static, field)) = Self::parse_field(input)?;
+        Self::not_static(is_static)?;
         Ok((input, InterfaceDefinitionFieldAst::Field(field)))
     }
 
     pub fn parse_extend_field(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExtendDefinitionFieldAst<'_>, TimuParserError<'_>> {
-        let (input, (is_public, field)) = Self::parse_field(input)?;
+        let (input, (is_public, is_static, field)) = Self::parse_field(input)?;
+        Self::not_static(is_static)?;
         if let Some(is_public) = is_public {
             let error = VerboseError {
                 errors: vec![(is_public, VerboseErrorKind::Context("All extended fields already public"))],
@@ -131,17 +144,31 @@ impl FieldAst<'_> {
         
         Ok((input, ExtendDefinitionFieldAst::Field(field)))
     }
+
+    /// Only classes have type level fields
+    fn not_static(is_static: Option<NomSpan<'_>>) -> Result<(), nom::Err<TimuParserError<'_>>> {
+        match is_static {
+            Some(is_static) => Err(nom::Err::Failure(VerboseError {
+                errors: vec![(is_static, VerboseErrorKind::Context("Only class fields can be static"))],
+            })),
+            None => Ok(()),
+        }
+    }
 }
 
 impl Display for FieldAst<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{}{}: {};",
+            "{}{}{}: {};",
             match self.is_public {
                 Some(_) => "pub ",
                 None => "",
             },
+            match self.is_static {
+                Some(_) => "static ",
+                None => "",
+            },
             self.name.text,
             self.field_type
         )
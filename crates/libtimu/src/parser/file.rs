@@ -13,6 +13,9 @@
 //! - **Interface definitions**: Contract specifications for implementing types
 //! - **Function definitions**: Standalone function declarations
 //! - **Extend definitions**: Extensions that add functionality to existing types
+//! - **Type aliases**: Alternative names for existing types
+//! - **Enums**: Types whose values are one of the listed variants
+//! - **Module variables**: Constants and global variables of the module
 //!
 //! # Iterator Methods
 //!
@@ -22,6 +25,9 @@
 //! - `get_functions()`: Returns all standalone function definitions
 //! - `get_interfaces()`: Returns all interface definitions
 //! - `get_extends()`: Returns all extend definitions
+//! - `get_type_aliases()`: Returns all type alias definitions
+//! - `get_enums()`: Returns all enum definitions
+//! - `get_variables()`: Returns all module-level constants and global variables
 //!
 //! These methods return iterators that yield `Rc<T>` references to the respective AST nodes,
 //! allowing efficient shared access to the parsed declarations.
@@ -29,7 +35,7 @@
 use std::{fmt::{Display, Formatter}, rc::Rc};
 
 
-use crate::ast::{ClassDefinitionAst, ExtendDefinitionAst, FileAst, FileStatementAst, FunctionDefinitionAst, InterfaceDefinitionAst, UseAst};
+use crate::ast::{ClassDefinitionAst, EnumDefinitionAst, ExtendDefinitionAst, FileAst, FileStatementAst, FunctionDefinitionAst, InterfaceDefinitionAst, ModuleVariableAst, TypeAliasAst, UseAst};
 
 impl<'base> FileAst<'base> {
     /// Returns an iterator over all use/import statements in the file
@@ -147,6 +153,66 @@ impl<'base> FileAst<'base> {
                 }
             })
     }
+
+    /// Returns an iterator over all type alias definitions in the file
+    /// 
+    /// # Returns
+    /// An iterator yielding `Rc<TypeAliasAst>` references to each type alias
+    /// 
+    /// # Example Usage
+    /// Used while building the module signatures, so aliases can be looked up
+    /// and imported like the other declarations.
+    pub fn get_type_aliases(&self) -> impl Iterator<Item = Rc<TypeAliasAst<'base>>> {
+        self.statements
+            .iter()
+            .filter_map(|statement| {
+                if let FileStatementAst::TypeAlias(alias) = statement {
+                    Some(alias.clone())
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Returns an iterator over all enum definitions in the file
+    ///
+    /// # Returns
+    /// An iterator yielding `Rc<EnumDefinitionAst>` references to each enum
+    ///
+    /// # Example Usage
+    /// Used while building the module signatures, so enums can be looked up
+    /// and imported like classes.
+    pub fn get_enums(&self) -> impl Iterator<Item = Rc<EnumDefinitionAst<'base>>> {
+        self.statements
+            .iter()
+            .filter_map(|statement| {
+                if let FileStatementAst::Enum(definition) = statement {
+                    Some(definition.clone())
+                } else {
+                    None
+                }
+            })
+    }
+
+    /// Returns an iterator over all module-level constants and global variables in the file
+    ///
+    /// # Returns
+    /// An iterator yielding `Rc<ModuleVariableAst>` references to each declaration
+    ///
+    /// # Example Usage
+    /// Used while building the module signatures, so public constants can be
+    /// imported like functions.
+    pub fn get_variables(&self) -> impl Iterator<Item = Rc<ModuleVariableAst<'base>>> {
+        self.statements
+            .iter()
+            .filter_map(|statement| {
+                if let FileStatementAst::Variable(variable) = statement {
+                    Some(variable.clone())
+                } else {
+                    None
+                }
+            })
+    }
 }
 
 impl Display for FileAst<'_> {
@@ -169,6 +235,9 @@ impl Display for FileStatementAst<'_> {
             FileStatementAst::Interface(interface) => write!(f, "{interface}"),
             FileStatementAst::Extend(extend) => write!(f, "{extend}"),
             FileStatementAst::Use(import) => write!(f, "{import}"),
+            FileStatementAst::TypeAlias(alias) => write!(f, "{alias}"),
+            FileStatementAst::Enum(definition) => write!(f, "{definition}"),
+            FileStatementAst::Variable(variable) => write!(f, "{variable}"),
         }
     }
 }
@@ -20,6 +20,31 @@
 //! }
 //! ```
 //!
+//! ## Const Function
+//! ```timu
+//! const func square(value: i32): i32 {
+//!     return value * value;
+//! }
+//! ```
+//!
+//! Const functions can be evaluated at compile time, see [`crate::tir::const_eval`].
+//!
+//! ## Native Function
+//! ```timu
+//! pub native func print(text: string): void;
+//! ```
+//!
+//! Native functions have no body, their implementation is provided by the host.
+//! Only the functions listed in [`crate::prelude::NATIVE_FUNCTIONS`] can be declared.
+//!
+//! ## Extern Function
+//! ```timu
+//! extern func puts(text: ref string): i32;
+//! ```
+//!
+//! Extern functions declare a symbol of a foreign library, such as a C function.
+//! They have no body and only take FFI-safe types.
+//!
 //! ## Method Definition (Class Context)
 //! ```timu
 //! func methodName(this, arg: Type): ReturnType {
@@ -49,6 +74,7 @@
 
 use std::fmt::{Display, Formatter};
 
+use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::char;
 use nom::combinator::{consumed, cut, map, opt, peek};
@@ -67,6 +93,11 @@ use crate::parser::{expected_ident, ident, is_public};
 use super::TimuParserError;
 
 impl<'base> FunctionDefinitionAst<'base> {
+    /// Returns whether the function is implemented in Timu, native and extern functions are only declarations
+    pub fn has_body(&self) -> bool {
+        self.is_native.is_none() && self.is_extern.is_none()
+    }
+
     /// Parses a function definition for use at file/module level
     /// 
     /// This parser variant handles function definitions that appear at the top level
@@ -125,6 +156,7 @@ impl<'base> FunctionDefinitionAst<'base> {
     /// 
     /// # Arguments
     /// * `input` - The input span to parse from
+    /// * `class_name` - The name of the extended class
     /// 
     /// # Returns
     /// * `Ok((remaining, field))` - Successfully parsed extension function
@@ -137,14 +169,15 @@ impl<'base> FunctionDefinitionAst<'base> {
     ///     func isEmpty(this): bool { }
     /// }
     /// ```
-    pub fn parse_extend_function(input: NomSpan<'base>) -> IResult<NomSpan<'base>, ExtendDefinitionFieldAst<'base>, TimuParserError<'base>> {
-        let (input, (is_public, function)) = Self::parse(input)?;
+    pub fn parse_extend_function(input: NomSpan<'base>, class_name: NomSpan<'base>) -> IResult<NomSpan<'base>, ExtendDefinitionFieldAst<'base>, TimuParserError<'base>> {
+        let (input, (is_public, mut function)) = Self::parse(input)?;
         if let Some(is_public) = is_public {
             let error = VerboseError {
                 errors: vec![(is_public, VerboseErrorKind::Context("All extended functions already public"))],
             };
             return Err(nom::Err::Failure(error));
         }
+        function.location = FunctionDefinitionLocationAst::Extend(class_name.into()).into();
         Ok((input, ExtendDefinitionFieldAst::Function(function)))
     }
 
@@ -161,26 +194,34 @@ impl<'base> FunctionDefinitionAst<'base> {
     /// # Returns
     /// A tuple containing:
     /// * `Option<NomSpan>` - The `pub` keyword span if present
-    /// * `FunctionDefinitionAst` - The complete function definition
+    /// * `FunctionDefinitionAst` - The complete function definition, including the optional `const`, `native` or `extern` modifier
     /// 
     /// # Errors
     /// Returns errors for:
     /// - Missing function name
     /// - Missing or malformed parameter list
     /// - Missing return type
-    /// - Missing function body
+    /// - Missing function body, or a body given to a native or extern function
     /// - Invalid syntax in any component
     /// 
     /// # Syntax
     /// ```timu
-    /// [pub] func name(arg1: Type1, arg2: Type2): ReturnType {
+    /// [pub] [const] func name(arg1: Type1, arg2: Type2): ReturnType {
     ///     // body
     /// }
+    /// [pub] native func name(arg1: Type1): ReturnType;
+    /// [pub] extern func name(arg1: Type1): ReturnType;
     /// ```
     pub fn parse(
         input: NomSpan<'base>,
     ) -> IResult<NomSpan<'base>, (Option<NomSpan<'base>>, FunctionDefinitionAst<'base>), TimuParserError<'base>> {
         let (input, is_public) = is_public(input)?;
+        let (input, modifier) = opt(terminated(alt((cleanup(tag("const")), cleanup(tag("native")), cleanup(tag("extern")))), peek(cleanup(tag("func"))))).parse(input)?;
+        let (is_const, is_native, is_extern) = match modifier {
+            Some(modifier) if *modifier.fragment() == "native" => (None, Some(modifier), None),
+            Some(modifier) if *modifier.fragment() == "extern" => (None, None, Some(modifier)),
+            modifier => (modifier, None, None),
+        };
         let (input, _) = cleanup(tag("func")).parse(input)?;
         let (input, name) = expected_ident("Missing function name", input)?;
         let (input, _) = context("Missing '('", cut(peek(cleanup(char('('))))).parse(input)?;
@@ -193,7 +234,10 @@ impl<'base> FunctionDefinitionAst<'base> {
         let (input, _) = context("Missing ':'", cleanup(opt(char(':')))).parse(input)?;
         let (input, return_type) = context("Missing function return type", cut(cleanup(cleanup(TypeNameAst::parse)))).parse(input)?;
 
-        let (input, body) = BodyAst::parse(input)?;
+        let (input, body) = match is_native.is_some() || is_extern.is_some() {
+            true => map(context("Native and extern functions have no body, missing ';'", cut(cleanup(char(';')))), |_| BodyAst { statements: Vec::new() }).parse(input)?,
+            false => BodyAst::parse(input)?,
+        };
         let index = AstIndex(input.extra.indexer.fetch_add(1, std::sync::atomic::Ordering::Relaxed));
         let original_is_public = is_public.clone();
 
@@ -201,6 +245,9 @@ impl<'base> FunctionDefinitionAst<'base> {
             input,
             (original_is_public, FunctionDefinitionAst {
                 is_public: is_public.map(|item| item.into()),
+                is_const: is_const.map(|item| item.into()),
+                is_native: is_native.map(|item| item.into()),
+                is_extern: is_extern.map(|item| item.into()),
                 name: name.into(),
                 arguments,
                 arguments_span: arguments_span.into(),
@@ -215,14 +262,24 @@ impl<'base> FunctionDefinitionAst<'base> {
 
 impl Display for FunctionDefinitionAst<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}func {}(", if self.is_public.is_some() { "pub " } else { "" }, self.name.text)?;
+        let modifier = match (self.is_const.is_some(), self.is_native.is_some(), self.is_extern.is_some()) {
+            (true, _, _) => "const ",
+            (_, true, _) => "native ",
+            (_, _, true) => "extern ",
+            _ => "",
+        };
+
+        write!(f, "{}{}func {}(", if self.is_public.is_some() { "pub " } else { "" }, modifier, self.name.text)?;
         for (index, arg) in self.arguments.iter().enumerate() {
             write!(f, "{arg}")?;
             if index < self.arguments.len() - 1 {
                 write!(f, ", ")?;
             }
         }
-        write!(f, "): {} {}", self.return_type, self.body)
+        match self.has_body() {
+            true => write!(f, "): {} {}", self.return_type, self.body),
+            false => write!(f, "): {};", self.return_type),
+        }
     }
 }
 
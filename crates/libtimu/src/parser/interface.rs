@@ -16,6 +16,14 @@
 //! }
 //! ```
 //!
+//! ## Default Methods
+//! ```timu
+//! interface Named {
+//!     func name(this): string;
+//!     func greeting(this): string { return "hello"; }
+//! }
+//! ```
+//!
 //! ## Interface with Fields
 //! ```timu
 //! interface Shape {
@@ -37,9 +45,10 @@
 //!
 //! ## Method Signatures
 //! - **Abstract methods** - Function signatures without implementations
+//! - **Default methods** - Methods with a body, extensions may omit them
 //! - **Parameters** - Typed parameters including optional `this` parameter
 //! - **Return types** - All methods must specify return types
-//! - **Termination** - Method signatures end with semicolons
+//! - **Termination** - Method signatures end with semicolons, default methods with their body
 //!
 //! ## Fields
 //! - **Abstract fields** - Field declarations that implementing types must provide
@@ -64,12 +73,12 @@ use std::fmt::{Display, Formatter};
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::character::complete::char;
-use nom::combinator::{cut, map, opt, peek};
+use nom::combinator::{consumed, cut, map, opt, peek};
 use nom::error::context;
 use nom::multi::{many0, separated_list0};
 use nom::{IResult, Parser, sequence::delimited};
 
-use crate::ast::{AstIndex, FieldAst, FunctionArgumentAst, InterfaceDefinitionAst, InterfaceDefinitionFieldAst, InterfaceFunctionDefinitionAst, TypeNameAst};
+use crate::ast::{AstIndex, BodyAst, FieldAst, FunctionArgumentAst, InterfaceDefinitionAst, InterfaceDefinitionFieldAst, InterfaceFunctionDefinitionAst, TypeNameAst};
 use crate::{ast::FileStatementAst, nom_tools::{cleanup, NomSpan}};
 
 use super::{expected_ident, TimuParserError};
@@ -189,17 +198,17 @@ impl Display for InterfaceDefinitionAst<'_> {
 impl InterfaceFunctionDefinitionAst<'_> {
     /// Parses an interface method signature declaration
     /// 
-    /// This function parses method signatures within interface definitions. Unlike
-    /// regular function definitions, interface methods have no implementation body
-    /// and end with a semicolon. These signatures define the contract that implementing
-    /// types must fulfill.
+    /// This function parses method signatures within interface definitions. Most
+    /// interface methods have no implementation body and end with a semicolon. These
+    /// signatures define the contract that implementing types must fulfill. A method
+    /// with a body is a default implementation that extensions may omit.
     /// 
     /// # Parsing Logic
     /// 1. Parse the `func` keyword
     /// 2. Parse the method name (identifier)
     /// 3. Parse the parameter list within parentheses
     /// 4. Parse the return type after colon
-    /// 5. Require terminating semicolon (no function body)
+    /// 5. Parse the default body, or require terminating semicolon
     /// 
     /// # Arguments
     /// * `input` - The input span to parse from
@@ -213,6 +222,7 @@ impl InterfaceFunctionDefinitionAst<'_> {
     /// func getName(this): string;
     /// func setName(this, name: string): void;
     /// func calculate(this, x: i32, y: i32): f64;
+    /// func isEmpty(this): bool { return false; }
     /// ```
     /// 
     /// # Errors
@@ -227,29 +237,43 @@ impl InterfaceFunctionDefinitionAst<'_> {
     /// 
     /// # Interface Contracts
     /// - **Abstract signatures** - No implementation body, only type contracts
+    /// - **Default bodies** - A body makes the method optional for extensions
     /// - **Mandatory parameters** - All parameters must have explicit types
     /// - **Return type required** - All interface methods must specify return types
-    /// - **Semicolon termination** - Interface methods end with `;` not `{}`
+    /// - **Semicolon termination** - Abstract methods end with `;`
     pub fn parse(
         input: NomSpan<'_>,
     ) -> IResult<NomSpan<'_>, InterfaceDefinitionFieldAst<'_>, TimuParserError<'_>> {
         let (input, _) = cleanup(tag("func")).parse(input)?;
         let (input, name) = expected_ident("Missing function name", input)?;
         let (input, _) = context("Missing '('", cut(peek(cleanup(char('('))))).parse(input)?;
-        let (input, arguments) =
-            map(delimited(char('('), cleanup(separated_list0(char(','), FunctionArgumentAst::parse)), context("Missing ')'", cut(char(')')))), |items| items)
+        let (input, (arguments_span, arguments)) =
+            consumed(map(delimited(char('('), cleanup(separated_list0(char(','), FunctionArgumentAst::parse)), context("Missing ')'", cut(char(')')))), |items| items))
                 .parse(input)?;
 
         let (input, _) = context("Missing ':'", cleanup(opt(char(':')))).parse(input)?;
         let (input, return_type) = context("Missing function return type", cut(cleanup(cleanup(TypeNameAst::parse)))).parse(input)?;
-        let (input, _) = cleanup(char(';')).parse(input)?;
+        let (input, body) = match cleanup(opt(peek(char('{')))).parse(input)? {
+            (input, Some(_)) => {
+                let (input, body) = BodyAst::parse(input)?;
+                (input, Some(body.into()))
+            }
+            (input, None) => {
+                let (input, _) = cleanup(char(';')).parse(input)?;
+                (input, None)
+            }
+        };
+        let index = AstIndex(input.extra.indexer.fetch_add(1, std::sync::atomic::Ordering::Relaxed));
 
         Ok((
             input,
             InterfaceDefinitionFieldAst::Function(InterfaceFunctionDefinitionAst {
                 name: name.into(),
                 arguments,
+                arguments_span: arguments_span.into(),
                 return_type,
+                body,
+                index,
             }),
         ))
     }
@@ -264,6 +288,9 @@ impl Display for InterfaceFunctionDefinitionAst<'_> {
                 write!(f, ", ")?;
             }
         }
-        write!(f, "): {};", self.return_type)
+        match &self.body {
+            Some(body) => write!(f, "): {} {}", self.return_type, body),
+            None => write!(f, "): {};", self.return_type),
+        }
     }
 }
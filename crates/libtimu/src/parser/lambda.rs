@@ -0,0 +1,163 @@
+//! Lambda expression parsing for the Timu language.
+//!
+//! This module handles parsing of lambda expressions, anonymous functions that
+//! are written inside expressions and can be stored in variables or passed to
+//! other functions.
+//!
+//! # Lambda Syntax
+//!
+//! ```timu
+//! func(a: i32, b: i32): i32 { return a + b; }
+//! ref func(): void { counter = counter + 1; }
+//! ```
+//!
+//! # Lambda Components
+//!
+//! - **Reference modifier**: `ref` allows capturing mutable locals by reference
+//! - **Arguments**: Same as function arguments, `this` is not allowed
+//! - **Return type**: Required, like in function definitions
+//! - **Body**: Statement block with access to the enclosing scopes
+//!
+//! # Type
+//!
+//! A lambda has a function type built from its signature, the lambda above
+//! has the type `func(i32, i32): i32`.
+
+use std::fmt::{Display, Formatter};
+
+use nom::bytes::complete::tag;
+use nom::character::complete::char;
+use nom::combinator::{consumed, cut, opt, peek};
+use nom::error::context;
+use nom::multi::separated_list0;
+use nom::sequence::{delimited, terminated};
+use nom::{IResult, Parser};
+
+use crate::ast::{AstIndex, BodyAst, ExpressionAst, FunctionArgumentAst, LambdaAst, TypeNameAst};
+use crate::nom_tools::{cleanup, NomSpan, Span};
+
+use super::TimuParserError;
+
+impl LambdaAst<'_> {
+    /// Parses a lambda expression
+    ///
+    /// The `func` keyword needs to be followed by `(`, so identifiers starting
+    /// with `func` are left for the other expression parsers.
+    ///
+    /// # Arguments
+    /// * `input` - The input span to parse from
+    ///
+    /// # Returns
+    /// * `Ok((remaining, lambda))` - Successfully parsed lambda expression
+    /// * `Err(error)` - Parse error with context information
+    ///
+    /// # Errors
+    /// Returns errors for:
+    /// - Missing `)` after the arguments
+    /// - Missing return type
+    /// - Missing or malformed body
+    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, LambdaAst<'_>, TimuParserError<'_>> {
+        let start = input.clone();
+        let (input, is_ref) = opt(terminated(cleanup(tag("ref")), peek(cleanup(terminated(tag("func"), cleanup(char('('))))))).parse(input)?;
+        let (input, _) = cleanup(terminated(tag("func"), peek(cleanup(char('('))))).parse(input)?;
+        let (input, (arguments_span, arguments)) =
+            consumed(delimited(cleanup(char('(')), cleanup(separated_list0(char(','), FunctionArgumentAst::parse)), context("Missing ')'", cut(char(')'))))).parse(input)?;
+
+        let (input, _) = context("Missing ':'", cleanup(opt(char(':')))).parse(input)?;
+        let (input, return_type) = context("Missing lambda return type", cut(cleanup(TypeNameAst::parse))).parse(input)?;
+        let (input, body) = BodyAst::parse(input)?;
+        let index = AstIndex(input.extra.indexer.fetch_add(1, std::sync::atomic::Ordering::Relaxed));
+
+        // The body consumes the whitespace after it, the span ends at the closing brace
+        let length = start.fragment()[..input.location_offset() - start.location_offset()].trim_end().len();
+        let span = Span {
+            text: &start.fragment()[..length],
+            position: start.location_offset()..start.location_offset() + length,
+            state: start.extra.clone(),
+        };
+
+        Ok((
+            input,
+            LambdaAst {
+                is_ref: is_ref.map(|item| item.into()),
+                span,
+                arguments,
+                arguments_span: arguments_span.into(),
+                return_type,
+                body: body.into(),
+                index,
+            },
+        ))
+    }
+
+    /// Parses a lambda expression and wraps it as an `ExpressionAst`
+    pub fn parse_for_expression(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
+        let (input, lambda) = Self::parse(input)?;
+        Ok((input, ExpressionAst::Lambda(lambda)))
+    }
+}
+
+impl Display for LambdaAst<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_ref.is_some() {
+            write!(f, "ref ")?;
+        }
+
+        write!(f, "func(")?;
+        for (index, argument) in self.arguments.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{argument}")?;
+        }
+        write!(f, "): {} {}", self.return_type, self.body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::{ast::ExpressionAst, file::SourceFile, nom_tools::{NomSpan, State}};
+
+    #[rstest]
+    #[case("func(): void {}", "func(): void {}")]
+    #[case("func (a: i32, b: i32) : i32 { return a + b; }", "func(a: i32, b: i32): i32 {return (a + b);}")]
+    #[case("ref func(): void { counter = 1; }", "ref func(): void {counter = 1;}")]
+    #[case("func(callback: func(i32): bool): ?func(): void { return other; }", "func(callback: func(i32): bool): ?func(): void {return other;}")]
+    #[case("call(func(a: i32): i32 { return a; }, 1)", "call(func(a: i32): i32 {return a;}, 1)")]
+    #[case("function(1)", "function(1)")]
+    #[case("ref funcs", "ref funcs")]
+    fn lambda_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
+        let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+
+        let state = State {
+            file: source_file.clone(),
+            indexer: Default::default(),
+        };
+
+        let input = NomSpan::new_extra(source_file.code().as_str(), state);
+        let (rest, response) = ExpressionAst::parse(input).unwrap();
+        assert_eq!(response.to_string(), expected, "{code}");
+        assert_eq!(rest.fragment().trim(), "", "{code}");
+    }
+
+    #[rstest]
+    #[case("func(a: i32): i32 { return a; }  ", "func(a: i32): i32 { return a; }")]
+    #[case("ref func(): void {}", "ref func(): void {}")]
+    fn lambda_span_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
+        let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+
+        let state = State {
+            file: source_file.clone(),
+            indexer: Default::default(),
+        };
+
+        let input = NomSpan::new_extra(source_file.code().as_str(), state);
+        let (_, response) = ExpressionAst::parse(input).unwrap();
+        match response {
+            ExpressionAst::Lambda(lambda) => assert_eq!(lambda.span.text, expected),
+            _ => panic!("Expected a lambda expression"),
+        }
+    }
+}
@@ -0,0 +1,310 @@
+//! Match expression parsing for the Timu language.
+//!
+//! A match compares a value against the patterns of its arms in order and
+//! evaluates the body of the first arm that matches. It can be used as an
+//! expression, where every arm produces a value, or as a statement.
+//!
+//! # Match Syntax
+//!
+//! ```timu
+//! var name = match (value) {
+//!     0 => "zero",
+//!     1 => "one",
+//!     _ => "many",
+//! };
+//!
+//! match (shape) {
+//!     circle: Circle => draw(circle),
+//!     null => {
+//!         log("nothing to draw");
+//!     }
+//!     _ => {}
+//! }
+//! ```
+//!
+//! # Patterns
+//!
+//! - **Literal**: `42`, `"text"`, `true`, matches equal values
+//! - **Null**: `null`, matches the missing value of a nullable type
+//! - **Wildcard**: `_`, matches every value
+//! - **Enum variant**: `Color.Red`, a dotted path to the variant. A variant
+//!   with a payload can bind its values, like `Shape.Rect(width, height)`
+//! - **Class type**: `name: Type`, matches objects of the class and binds them to `name`
+//!
+//! # Arms
+//!
+//! An arm body is either an expression or a block. Arms are separated by
+//! commas, the comma is optional after a block and after the last arm.
+
+use std::fmt::{Display, Formatter};
+
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, satisfy};
+use nom::combinator::{consumed, cut, not, opt, peek};
+use nom::error::context;
+use nom::multi::separated_list1;
+use nom::sequence::{preceded, terminated};
+use nom::{IResult, Parser};
+
+use crate::ast::{BodyAst, BodyStatementAst, ExpressionAst, MatchArmAst, MatchArmBodyAst, MatchAst, PatternAst, PrimitiveValue, TypeNameAst};
+use crate::nom_tools::{cleanup, NomSpan, Span};
+
+use super::{ident, TimuParserError};
+
+impl MatchAst<'_> {
+    /// Parses a match with its arms
+    ///
+    /// The `match` keyword must not be followed by an identifier character, so
+    /// names such as `matches` are still parsed as ordinary identifiers.
+    ///
+    /// # Arguments
+    /// * `input` - The input span to parse from
+    ///
+    /// # Returns
+    /// * `Ok((remaining, match_ast))` - Successfully parsed match
+    /// * `Err(error)` - Parse error with context information
+    ///
+    /// # Errors
+    /// Returns errors for:
+    /// - Missing matched expression or `{`
+    /// - Malformed patterns or a missing `=>`
+    /// - Missing `,` between two expression arms
+    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, MatchAst<'_>, TimuParserError<'_>> {
+        let start = input.clone();
+        let (input, (keyword, _)) = cleanup(consumed(terminated(tag("match"), not(satisfy(|c: char| c.is_alphanumeric() || c == '_'))))).parse(input)?;
+        let (input, expression) = context("Missing match expression", cut(ExpressionAst::parse)).parse(input)?;
+        let (mut input, _) = context("Missing '{'", cut(cleanup(char('{')))).parse(input)?;
+
+        let mut arms = Vec::new();
+        loop {
+            if let Ok((rest, _)) = cleanup(char::<NomSpan<'_>, TimuParserError<'_>>('}')).parse(input.clone()) {
+                input = rest;
+                break;
+            }
+
+            let (rest, pattern) = context("Missing match pattern", cut(PatternAst::parse)).parse(input)?;
+            let (rest, _) = context("Missing '=>'", cut(cleanup(tag("=>")))).parse(rest)?;
+            let (rest, body) = context("Missing match arm body", cut(MatchArmBodyAst::parse)).parse(rest)?;
+            let (rest, comma) = opt(cleanup(char(','))).parse(rest)?;
+            input = rest;
+
+            let is_expression = matches!(body, MatchArmBodyAst::Expression(_));
+            arms.push(MatchArmAst { pattern, body });
+
+            if comma.is_none() && is_expression {
+                let (rest, _) = context("Missing ',' or '}'", cut(cleanup(char('}')))).parse(input)?;
+                input = rest;
+                break;
+            }
+        }
+
+        // The closing brace consumes the whitespace after it, the span ends at the brace
+        let offset = keyword.location_offset() - start.location_offset();
+        let length = start.fragment()[..input.location_offset() - start.location_offset()].trim_end().len();
+        let span = Span {
+            text: &start.fragment()[offset..length],
+            position: keyword.location_offset()..start.location_offset() + length,
+            state: start.extra.clone(),
+        };
+
+        Ok((
+            input,
+            MatchAst {
+                span,
+                expression,
+                arms,
+            },
+        ))
+    }
+
+    /// Parses a match and wraps it as an `ExpressionAst`
+    pub fn parse_for_expression(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ExpressionAst<'_>, TimuParserError<'_>> {
+        let (input, match_ast) = Self::parse(input)?;
+        Ok((input, ExpressionAst::Match(Box::new(match_ast))))
+    }
+
+    /// Parses a match used as a statement, a trailing `;` is allowed
+    pub fn parse_body_statement(input: NomSpan<'_>) -> IResult<NomSpan<'_>, BodyStatementAst<'_>, TimuParserError<'_>> {
+        let (input, match_ast) = Self::parse(input)?;
+        let (input, _) = opt(cleanup(char(';'))).parse(input)?;
+        Ok((input, BodyStatementAst::Match(match_ast)))
+    }
+}
+
+impl MatchArmBodyAst<'_> {
+    /// Parses the body of an arm, a block when it starts with `{` and an expression otherwise
+    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, MatchArmBodyAst<'_>, TimuParserError<'_>> {
+        match peek(cleanup(char::<NomSpan<'_>, TimuParserError<'_>>('{'))).parse(input.clone()) {
+            Ok(_) => {
+                let (input, body) = BodyAst::parse(input)?;
+                Ok((input, MatchArmBodyAst::Block(body)))
+            },
+            Err(_) => {
+                let (input, expression) = ExpressionAst::parse(input)?;
+                Ok((input, MatchArmBodyAst::Expression(Box::new(expression))))
+            },
+        }
+    }
+}
+
+impl PatternAst<'_> {
+    /// Parses the pattern of a match arm
+    ///
+    /// Literals are tried first, `_` and `null` are reserved names. A name
+    /// followed by `:` is a class type pattern, any other name starts the path
+    /// of an enum variant, optionally followed by the names the payload is
+    /// bound to in parentheses.
+    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, PatternAst<'_>, TimuParserError<'_>> {
+        if let Ok((input, (span, value))) = terminated(PrimitiveValue::parse, not(satisfy(|c: char| c.is_alphanumeric() || c == '_'))).parse(input.clone()) {
+            return Ok((input, PatternAst::Literal { span: span.into(), value }));
+        }
+
+        let (rest, name) = ident().parse(input.clone())?;
+        match *name.fragment() {
+            "_" => return Ok((rest, PatternAst::Wildcard(name.into()))),
+            "null" => return Ok((rest, PatternAst::Null(name.into()))),
+            _ => (),
+        };
+
+        if let (rest, Some(_)) = opt(cleanup(char(':'))).parse(rest)? {
+            let (rest, class) = context("Missing pattern type", cut(TypeNameAst::parse)).parse(rest)?;
+            return Ok((rest, PatternAst::Class { binding: name.into(), class }));
+        }
+
+        let start = input.clone();
+        let (input, path) = separated_list1(char('.'), ident()).parse(input)?;
+        let (input, bindings) = opt(preceded(
+            char('('),
+            cut((
+                context("Missing binding name", separated_list1(char(','), ident())),
+                context("Missing ')'", tag(")")),
+            )),
+        )).parse(input)?;
+
+        let first = path.first().expect("Variant pattern without name, but this is a bug");
+        let end = match &bindings {
+            Some((_, close)) => close.location_offset() + 1,
+            None => path.last().map(|name| name.location_offset() + name.fragment().len()).unwrap_or_default(),
+        };
+        let span = Span {
+            text: &start.fragment()[first.location_offset() - start.location_offset()..end - start.location_offset()],
+            position: first.location_offset()..end,
+            state: start.extra.clone(),
+        };
+
+        Ok((input, PatternAst::Variant {
+            span,
+            path: path.into_iter().map(|name| name.into()).collect(),
+            bindings: bindings.map(|(names, _)| names.into_iter().map(|name| name.into()).collect()),
+        }))
+    }
+}
+
+impl Display for MatchAst<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "match ({}) {{", self.expression)?;
+        for (index, arm) in self.arms.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{arm}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl Display for MatchArmAst<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.body {
+            MatchArmBodyAst::Expression(expression) => write!(f, "{} => {expression}", self.pattern),
+            MatchArmBodyAst::Block(body) => write!(f, "{} => {body}", self.pattern),
+        }
+    }
+}
+
+impl Display for PatternAst<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatternAst::Literal { span, .. } => write!(f, "{}", span.text.trim()),
+            PatternAst::Null(_) => write!(f, "null"),
+            PatternAst::Wildcard(_) => write!(f, "_"),
+            PatternAst::Variant { path, bindings, .. } => {
+                write!(f, "{}", path.iter().map(|name| name.text).collect::<Vec<_>>().join("."))?;
+                match bindings {
+                    Some(bindings) => write!(f, "({})", bindings.iter().map(|name| name.text).collect::<Vec<_>>().join(", ")),
+                    None => Ok(()),
+                }
+            },
+            PatternAst::Class { binding, class } => write!(f, "{binding}: {class}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::{ast::{BodyAst, ExpressionAst}, file::SourceFile, nom_tools::{NomSpan, State}};
+
+    #[rstest]
+    #[case("match (a) { 1 => true, _ => false }", "match (a) {1 => true, _ => false}")]
+    #[case("match (a) { 1 => true, 2 => false, }", "match (a) {1 => true, 2 => false}")]
+    #[case("match (a) {}", "match (a) {}")]
+    #[case("match (call(1)) { \"one\" => 1, -2 => 2 }", "match (call(1)) {\"one\" => 1, -2 => 2}")]
+    #[case("match a { _ => 1 }", "match (a) {_ => 1}")]
+    #[case("match (a) { null => 0, value: Shape => value.area() }", "match (a) {null => 0, value: Shape => value.area()}")]
+    #[case("match (a) { Color.Red => 1, Color.Green => 2 }", "match (a) {Color.Red => 1, Color.Green => 2}")]
+    #[case("match (a) { Shape.Rect( w ,h ) => w * h, Shape.Circle(_) => 0, Empty => 1 }", "match (a) {Shape.Rect(w, h) => (w * h), Shape.Circle(_) => 0, Empty => 1}")]
+    #[case("match (a) { true => { run(); } false => {} }", "match (a) {true => {run();}, false => {}}")]
+    #[case("match (a) { truth: bool => truth }", "match (a) {truth: bool => truth}")]
+    #[case("match (a) { _ => match (b) { _ => 1 } } + 1", "(match (a) {_ => match (b) {_ => 1}} + 1)")]
+    #[case("matches(1)", "matches(1)")]
+    fn match_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
+        let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+
+        let state = State {
+            file: source_file.clone(),
+            indexer: Default::default(),
+        };
+
+        let input = NomSpan::new_extra(source_file.code().as_str(), state);
+        let (rest, response) = ExpressionAst::parse(input).unwrap();
+        assert_eq!(response.to_string(), expected, "{code}");
+        assert_eq!(rest.fragment().trim(), "", "{code}");
+    }
+
+    #[rstest]
+    #[case("{ match (a) { 1 => run(), _ => {} } }", "{match (a) {1 => run(), _ => {}}}")]
+    #[case("{ match (a) { _ => {} }; return; }", "{match (a) {_ => {}} return;}")]
+    fn match_statement_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
+        let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+
+        let state = State {
+            file: source_file.clone(),
+            indexer: Default::default(),
+        };
+
+        let input = NomSpan::new_extra(source_file.code().as_str(), state);
+        let (_, response) = BodyAst::parse(input).unwrap();
+        assert_eq!(response.to_string(), expected, "{code}");
+    }
+
+    #[rstest]
+    #[case("match (a) { 1 => true 2 => false }")]
+    #[case("match (a) { 1 true }")]
+    #[case("match (a) { value: => 1 }")]
+    #[case("match (a) { Shape.Circle() => 1 }")]
+    #[case("match (a) { Shape.Rect(w, h => 1 }")]
+    fn match_error_test(#[case] code: &str) {
+        let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+
+        let state = State {
+            file: source_file.clone(),
+            indexer: Default::default(),
+        };
+
+        let input = NomSpan::new_extra(source_file.code().as_str(), state);
+        assert!(ExpressionAst::parse(input).is_err(), "{code}");
+    }
+}
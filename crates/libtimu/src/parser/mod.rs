@@ -25,6 +25,7 @@
 //! - [`type_info`], [`ref_info`] - Type system support
 //! - [`if_condition`], [`body`] - Control flow and code blocks
 //! - [`module_use`] - Module system support
+//! - [`attribute`] - Attributes in front of top-level statements
 //! - [`splited_path`] - Qualified path handling
 //!
 //! # Usage
@@ -32,10 +33,10 @@
 //! The main entry point is the [`parse`] function which takes a [`State`] containing
 //! the source file and returns a [`FileAst`] representing the parsed program.
 
-use nom::bytes::complete::{tag, take_until};
+use nom::bytes::complete::tag;
 use nom::character::complete::{alpha1, alphanumeric1, char};
 use nom::combinator::{cut, map, opt, recognize};
-use nom::sequence::{pair, preceded, terminated};
+use nom::sequence::pair;
 use nom::Err;
 use nom::branch::alt;
 use nom::error::context;
@@ -43,11 +44,13 @@ use nom::multi::{many0, many0_count};
 use nom::{IResult, Parser};
 use nom_language::error::{VerboseError, VerboseErrorKind};
 
-use crate::ast::{ClassDefinitionAst, ExtendDefinitionAst, FileAst, FunctionDefinitionAst, InterfaceDefinitionAst, UseAst};
-use crate::nom_tools::{NomSpan, State, cleanup};
+use crate::ast::{AttributeAst, ClassDefinitionAst, EnumDefinitionAst, ExtendDefinitionAst, FileAst, FileStatementAst, FunctionDefinitionAst, InterfaceDefinitionAst, ModuleVariableAst, TypeAliasAst, UseAst};
+use crate::nom_tools::{NomSpan, State, cleanup, trivia};
 
+mod attribute;
 mod body;
 mod class;
+mod enum_definition;
 mod expression;
 mod extend;
 mod field;
@@ -56,9 +59,13 @@ mod function_definition;
 mod function_call;
 mod if_condition;
 mod interface;
+mod lambda;
+mod match_expression;
 mod module_use;
 mod primitive;
 mod ref_info;
+mod return_statement;
+mod type_alias;
 mod type_info;
 mod variable;
 pub mod splited_path;
@@ -96,15 +103,15 @@ pub fn parse<'base>(state: &'base State) -> IResult<NomSpan<'base>, FileAst<'bas
     let extra = state.clone();
 
     let input = NomSpan::new_extra(state.file.code().as_str(), extra);
-    let (remaining, statements) =
-        many0(alt((
-            cleanup(UseAst::parse_for_file),
-            cleanup(ClassDefinitionAst::parse),
-            cleanup(FunctionDefinitionAst::parse_for_file),
-            cleanup(InterfaceDefinitionAst::parse),
-            cleanup(ExtendDefinitionAst::parse),
-        )))
-        .parse(input)?;
+    let (remaining, items) = many0(statement).parse(input)?;
+    let (remaining, _) = trivia(remaining)?;
+
+    let mut statements = Vec::new();
+    let mut attributes = Vec::new();
+    for (statement, statement_attributes) in items {
+        statements.push(statement);
+        attributes.extend(statement_attributes);
+    }
 
     if remaining.len() > 0 {
         let error = VerboseError {
@@ -118,17 +125,43 @@ pub fn parse<'base>(state: &'base State) -> IResult<NomSpan<'base>, FileAst<'bas
         FileAst {
             file,
             statements,
+            attributes,
         },
     ))
 }
 
-/// Parses block comments in the form `/* ... */`
-/// 
-/// This parser recognizes multi-line comments that start with `/*` and end with `*/`.
-/// Currently unused in the main parser but available for future comment handling.
-#[allow(warnings)]
-pub fn comment<'base>(input: NomSpan<'base>) -> IResult<NomSpan<'base>, NomSpan<'base>, TimuParserError<'base>> {
-    preceded(char('/'), alt((preceded(char('*'), cut(terminated(take_until("*/"), tag("*/")))),))).parse(input)
+/// Parses a top-level statement with the attributes in front of it
+///
+/// The target of the attributes is the byte range of the statement.
+fn statement(input: NomSpan<'_>) -> IResult<NomSpan<'_>, (FileStatementAst<'_>, Vec<AttributeAst<'_>>), TimuParserError<'_>> {
+    let (input, mut attributes) = many0(AttributeAst::parse).parse(input)?;
+    let (input, _) = trivia(input)?;
+    let start = input.location_offset();
+    let source = input.clone();
+
+    let mut declaration = alt((
+        UseAst::parse_for_file,
+        ClassDefinitionAst::parse,
+        FunctionDefinitionAst::parse_for_file,
+        InterfaceDefinitionAst::parse,
+        ExtendDefinitionAst::parse,
+        TypeAliasAst::parse,
+        EnumDefinitionAst::parse,
+        ModuleVariableAst::parse,
+    ));
+    let (input, statement) = match attributes.is_empty() {
+        true => declaration.parse(input)?,
+        false => context("Attribute is not followed by a declaration", cut(declaration)).parse(input)?,
+    };
+
+    // Declarations consume the whitespace after them, the target ends at the last character
+    let end = start + source.fragment()[..input.location_offset() - start].trim_end().len();
+    let (input, _) = trivia(input)?;
+    for attribute in attributes.iter_mut() {
+        attribute.target = start..end;
+    }
+
+    Ok((input, (statement, attributes)))
 }
 
 /// Parses the optional `pub` visibility modifier
@@ -180,6 +213,7 @@ pub fn ident<'base>() -> impl Parser<NomSpan<'base>, Output = NomSpan<'base>, Er
 }
 
 #[cfg(test)]
+#[allow(clippy::approx_constant)]
 mod tests {
     use pretty_assertions::assert_eq;
     use std::vec;
@@ -594,6 +628,11 @@ mod tests {
     #[case(" string   .        base        . test", false, vec!["string", "base", "test"])]
     #[case(" ? string   .        base        . test", true, vec!["string", "base", "test"])]
     #[case("?string", true, vec!["string"])]
+    #[case("func(i32, string): bool", false, vec!["func"])]
+    #[case("?func(): void", true, vec!["func"])]
+    #[case("function", false, vec!["function"])]
+    #[case("(i32, string)", false, vec!["("])]
+    #[case("?( i32 , ?string )", true, vec!["("])]
     fn parse_type_name_test<'base>(#[case] code: &'base str, #[case] nullable: bool, #[case] expected: Vec<&str>) {
         let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
 
@@ -613,6 +652,20 @@ mod tests {
         assert_eq!(parsed, expected, "Parsed type name does not match expected");
     }
 
+    #[rstest]
+    #[case("(i32,string)", Some("(i32, string)"))]
+    #[case("( ref Point , (f64, ?f64) )", Some("(ref Point, (f64, ?f64))"))]
+    #[case("(i32)", None)]
+    #[case("(i32, )", None)]
+    #[case("()", None)]
+    fn parse_tuple_type_test(#[case] code: &str, #[case] expected: Option<&str>) {
+        let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+        let state = State { file: source_file.clone(), indexer: Default::default() };
+        let input = NomSpan::new_extra(code, state);
+        let result = TypeNameAst::parse(input).ok().map(|(_, parsed)| parsed.to_string());
+        assert_eq!(result.as_deref(), expected, "{code}");
+    }
+
     // ============================================================================
     // COMPREHENSIVE FLOAT TYPE INFERENCE TESTS
     // ============================================================================
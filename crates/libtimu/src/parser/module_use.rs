@@ -29,6 +29,25 @@
 //! ```
 //! Imports a module with a custom alias for convenience or to avoid naming conflicts.
 //!
+//! ## Grouped Import
+//! ```timu
+//! use ui.components.{Button, Label as Text, layout.Grid};
+//! ```
+//! Imports several items below the same path, every item can have its own alias
+//! and sub path.
+//!
+//! ## Wildcard Import
+//! ```timu
+//! use ui.components.*;
+//! ```
+//! Imports every public item of a module.
+//!
+//! ## Re-export
+//! ```timu
+//! pub use ui.components.Button;
+//! ```
+//! Imports an item and makes it importable from the current module as well.
+//!
 //! # Module Resolution
 //!
 //! Module paths in import statements correspond to the file system structure:
@@ -41,6 +60,8 @@
 //! The parser extracts several key components from import statements:
 //! - **Import path** - The dot-separated module path
 //! - **Optional alias** - A custom name for the imported module
+//! - **Import kind** - A single item, a group of items or a wildcard
+//! - **Public modifier** - Whether the imported items are re-exported
 //! - **Source location** - Position information for error reporting
 //!
 //! # Integration with Module System
@@ -52,17 +73,20 @@
 //! - Build symbol tables for name resolution
 //! - Enable qualified access to imported functionality
 
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 
+use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::char;
+use nom::character::complete::{char, multispace1};
 use nom::combinator::{consumed, cut, opt};
 use nom::error::context;
 use nom::multi::separated_list1;
+use nom::sequence::terminated;
 use nom::{IResult, Parser};
 
-use crate::ast::{FileStatementAst, UseAst};
-use crate::nom_tools::{NomSpan, cleanup};
+use crate::ast::{FileStatementAst, UseAst, UseKindAst};
+use crate::nom_tools::{NomSpan, Span, cleanup};
 use crate::parser::ident;
 
 use super::splited_path::SplitedPath;
@@ -72,15 +96,17 @@ impl UseAst<'_> {
     /// Parses a complete `use` statement with optional aliasing
     /// 
     /// This is the main parser for import statements. It handles the full syntax
-    /// including the `use` keyword, qualified module path, optional `as` alias,
-    /// and terminating semicolon. The parser extracts both the import path and
-    /// any alias for later module resolution.
+    /// including the optional `pub` keyword, the `use` keyword, qualified module
+    /// path, optional `as` alias, group or wildcard, and terminating semicolon.
+    /// The parser extracts both the import path and any alias for later module
+    /// resolution.
     /// 
     /// # Parsing Logic
-    /// 1. Parse the `use` keyword
+    /// 1. Parse the optional `pub` keyword followed by the `use` keyword
     /// 2. Parse the dot-separated module path (required)
-    /// 3. Optionally parse `as` keyword followed by alias identifier
-    /// 4. Require terminating semicolon
+    /// 3. Optionally parse `.{...}` with the grouped items or `.*`
+    /// 4. Otherwise optionally parse `as` keyword followed by alias identifier
+    /// 5. Require terminating semicolon
     /// 
     /// # Arguments
     /// * `input` - The input span to parse from
@@ -94,25 +120,37 @@ impl UseAst<'_> {
     /// use module;                    // Basic import
     /// use std.collections.HashMap;   // Qualified import
     /// use long.module.name as Short; // Aliased import
+    /// use lib.{A, B as C, sub.D};    // Grouped import
+    /// use lib.*;                     // Wildcard import
+    /// pub use lib.A;                 // Re-export
     /// ```
     /// 
     /// # Errors
     /// Returns errors for:
     /// - Missing module path after `use`
     /// - Missing alias after `as` keyword
+    /// - Empty or unclosed import group
     /// - Missing terminating semicolon
     /// - Invalid identifier syntax in path or alias
     pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, UseAst<'_>, TimuParserError<'_>> {
+        let (input, is_public) = opt(cleanup(terminated(tag("pub"), multispace1))).parse(input)?;
         let (input, _) = cleanup(tag("use")).parse(input)?;
-        let (input, (import_span, splited_import)) = context("Module path missing", cut(consumed(cleanup(separated_list1(char('.'), ident()))))).parse(input)?;
-        let import = SplitedPath::new(import_span.into(), splited_import.into_iter().map(|item| item.into()).collect::<Vec<_>>());
-        
-        let (input, alias) = match opt(cleanup(tag("as"))).parse(input)? {
-            (input, Some(_)) => {
-                let (input, alias) = context("Module alias missing", cut(cleanup(ident()))).parse(input)?;
-                (input, Some(alias))
-            }
-            (input, None) => (input, None),
+        let (input, import) = context("Module path missing", cut(Self::parse_path)).parse(input)?;
+
+        let (input, kind) = match opt(cleanup(char('.'))).parse(input)? {
+            (input, Some(_)) => context("Import group or '*' missing", cut(alt((
+                |input| Self::parse_group(input, &import),
+                |input| {
+                    let (input, wildcard) = cleanup(tag("*")).parse(input)?;
+                    Ok((input, UseKindAst::Wildcard(wildcard.into())))
+                },
+            )))).parse(input)?,
+            (input, None) => (input, UseKindAst::Single),
+        };
+
+        let (input, alias) = match kind.is_single() {
+            true => Self::parse_alias(input)?,
+            false => (input, None),
         };
         
         let (input, _) = context("Missing ';'", cut(cleanup(char(';')))).parse(input)?;
@@ -120,12 +158,56 @@ impl UseAst<'_> {
         Ok((
             input,
             UseAst {
+                is_public: is_public.map(|item| item.into()),
                 import,
-                alias: alias.map(|item| item.into()),
+                alias,
+                kind,
             },
         ))
     }
 
+    /// Parses a dot-separated path like `std.collections.HashMap`
+    fn parse_path(input: NomSpan<'_>) -> IResult<NomSpan<'_>, SplitedPath<'_>, TimuParserError<'_>> {
+        let (input, (import_span, splited_import)) = consumed(cleanup(separated_list1(char('.'), ident()))).parse(input)?;
+        Ok((input, SplitedPath::new(import_span.into(), splited_import.into_iter().map(|item| item.into()).collect::<Vec<_>>())))
+    }
+
+    /// Parses the optional `as alias` part of an import
+    fn parse_alias(input: NomSpan<'_>) -> IResult<NomSpan<'_>, Option<Span<'_>>, TimuParserError<'_>> {
+        match opt(cleanup(tag("as"))).parse(input)? {
+            (input, Some(_)) => {
+                let (input, alias) = context("Module alias missing", cut(cleanup(ident()))).parse(input)?;
+                Ok((input, Some(alias.into())))
+            }
+            (input, None) => Ok((input, None)),
+        }
+    }
+
+    /// Parses the `{A, B as C, sub.D}` items of a grouped import
+    /// 
+    /// Every item becomes a single import whose path starts with the path in
+    /// front of the group, so `use lib.{sub.D};` imports `lib.sub.D`. A trailing
+    /// comma is allowed.
+    fn parse_group<'base>(input: NomSpan<'base>, prefix: &SplitedPath<'base>) -> IResult<NomSpan<'base>, UseKindAst<'base>, TimuParserError<'base>> {
+        let (input, _) = cleanup(char('{')).parse(input)?;
+        let (input, items) = context("Import group is empty", cut(separated_list1(cleanup(char(',')), |input| {
+            let (input, path) = Self::parse_path(input)?;
+            let (input, alias) = Self::parse_alias(input)?;
+
+            let paths = prefix.paths.iter().chain(path.paths.iter()).cloned().collect::<Vec<_>>();
+            let text = paths.iter().map(|path| path.text).collect::<Vec<_>>().join(".");
+            Ok((input, UseAst {
+                is_public: None,
+                alias,
+                import: SplitedPath { paths, text: Cow::Owned(text) },
+                kind: UseKindAst::Single,
+            }))
+        }))).parse(input)?;
+        let (input, _) = opt(cleanup(char(','))).parse(input)?;
+        let (input, _) = context("Import group's closing '}' missing", cut(cleanup(char('}')))).parse(input)?;
+        Ok((input, UseKindAst::Group(items)))
+    }
+
     /// Parses a use statement for inclusion in file-level statement lists
     /// 
     /// This parser variant wraps the main use statement parser for integration
@@ -152,8 +234,29 @@ impl UseAst<'_> {
 
 impl Display for UseAst<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_public.is_some() {
+            write!(f, "pub ")?;
+        }
         write!(f, "use ")?;
         write!(f, "{}", self.import.text)?;
+        match &self.kind {
+            UseKindAst::Single => (),
+            UseKindAst::Group(items) => {
+                write!(f, ".{{")?;
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    let path = item.import.paths[self.import.paths.len()..].iter().map(|path| path.text).collect::<Vec<_>>();
+                    write!(f, "{}", path.join("."))?;
+                    if let Some(alias) = &item.alias {
+                        write!(f, " as {}", alias.text)?;
+                    }
+                }
+                write!(f, "}}")?;
+            }
+            UseKindAst::Wildcard(_) => write!(f, ".*")?,
+        }
         if let Some(alias) = &self.alias {
             write!(f, " as {}", alias.text)?;
         }
@@ -177,6 +280,12 @@ mod tests {
     #[case(r#"use foo1.foo2.foo3;
 use bar1.bar2.bar3;"#, r#"use foo1.foo2.foo3;
 use bar1.bar2.bar3;"#)]
+    #[case("use lib.{A, B as C, sub.D};", "use lib.{A, B as C, sub.D};")]
+    #[case("use lib . { A ,B, } ;", "use lib.{A, B};")]
+    #[case("use lib.sub.*;", "use lib.sub.*;")]
+    #[case("pub use lib.A as B;", "pub use lib.A as B;")]
+    #[case("pub use lib.{A, B};", "pub use lib.{A, B};")]
+    #[case("pub  use lib.*;", "pub use lib.*;")]
     fn module_use_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
         let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
 
@@ -188,4 +297,16 @@ use bar1.bar2.bar3;"#)]
         let (_, response) = crate::parser::parse(&state).finish().unwrap();
         assert_eq!(response.to_string(), expected, "{code}");
     }
+
+    #[rstest]
+    #[case("use lib.{};")]
+    #[case("use lib.{A;")]
+    #[case("use lib.;")]
+    #[case("use lib.* as A;")]
+    #[case("use lib.{A} as B;")]
+    fn invalid_module_use_test<'base>(#[case] code: &'base str) {
+        let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+        let state = State::new(source_file);
+        assert!(crate::parser::parse(&state).finish().is_err(), "{code}");
+    }
 }
\ No newline at end of file
@@ -96,7 +96,7 @@ fn character(input: NomSpan<'_>) -> IResult<NomSpan<'_>, char, TimuParserError<'
 /// - `"hello"` → `PrimitiveValue::String("hello")`
 /// - `"hello\nworld"` → `PrimitiveValue::String("hello\nworld")`
 /// - `"path/to/file"` → `PrimitiveValue::String("path/to/file")`
-pub fn string(input: NomSpan<'_>) -> IResult<NomSpan<'_>, PrimitiveValue, TimuParserError<'_>> {
+pub fn string(input: NomSpan<'_>) -> IResult<NomSpan<'_>, PrimitiveValue<'_>, TimuParserError<'_>> {
     let (input, string) = delimited(
         char('"'),
         fold(0.., character, String::new, |mut string, c| {
@@ -228,7 +228,7 @@ pub fn number<'base>(input: NomSpan<'base>) -> IResult<NomSpan<'base>, Primitive
 }
 
 impl PrimitiveValue<'_> {
-    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, (NomSpan<'_>, PrimitiveValue), TimuParserError<'_>> {
+    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, (NomSpan<'_>, PrimitiveValue<'_>), TimuParserError<'_>> {
         let (input, value) =
             consumed(cleanup(alt((
                 number, 
@@ -12,6 +12,7 @@
 //! ref variableName        // Simple reference
 //! ref object.field        // Field reference
 //! ref module.object.field // Qualified reference
+//! ref pair.0              // Tuple element, counted from zero
 //! ```
 //!
 //! # Reference Types
@@ -24,6 +25,7 @@
 //! - **Field references**: `ref object.field` - References to object fields
 //! - **Qualified references**: `ref module.Type.field` - References with module qualification
 //! - **Nested references**: `ref outer.inner.field` - References through multiple levels
+//! - **Tuple elements**: `ref pair.0` - Elements of a tuple by their position
 //!
 //! # Integration with Type System
 //!
@@ -35,7 +37,7 @@
 
 use std::fmt::{Display, Formatter};
 
-use nom::{bytes::complete::tag, character::complete::char, combinator::cut, error::context, multi::separated_list1, IResult, Parser};
+use nom::{branch::alt, bytes::complete::tag, character::complete::{char, digit1}, combinator::cut, error::context, multi::many0, sequence::preceded, IResult, Parser};
 
 use crate::{ast::{ExpressionAst, RefAst}, nom_tools::{cleanup, NomSpan, Span}};
 
@@ -75,11 +77,12 @@ impl RefAst<'_> {
     /// - **Whitespace handling**: Automatically handles whitespace around components
     pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, RefAst<'_>, TimuParserError<'_>> {
         let (input, _) = cleanup(tag("ref")).parse(input)?;
-        let (input, names) = context("Reference name missing", cut(separated_list1(cleanup(char('.')), ident()))).parse(input)?;
+        let (input, first) = context("Reference name missing", cut(ident())).parse(input)?;
+        let (input, rest) = many0(preceded(cleanup(char('.')), alt((ident(), cleanup(digit1))))).parse(input)?;
         Ok((
             input,
             RefAst {
-                names: names.into_iter().map(Span::from).collect::<Vec<_>>(),
+                names: std::iter::once(first).chain(rest).map(Span::from).collect::<Vec<_>>(),
             },
         ))
     }
@@ -137,6 +140,7 @@ mod tests {
     #[case("ref a.b", "ref a.b")]
     #[case(" ref  a ", "ref a")]
     #[case("ref a . b  ", "ref a.b")]
+    #[case("ref pair.0.1", "ref pair.0.1")]
     fn reference_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
         let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
 
@@ -0,0 +1,124 @@
+//! Return statement parsing for the Timu language.
+//!
+//! A return statement leaves the enclosing function. It may carry a value,
+//! which must match the declared return type of the function; functions
+//! returning `void` use the bare form.
+//!
+//! # Syntax
+//!
+//! ```timu
+//! return a + b;    // Return a value
+//! return;          // Return from a void function
+//! ```
+//!
+//! # Error Handling
+//!
+//! The parser reports an error when the terminating `;` is missing or the
+//! returned expression is malformed.
+
+use std::fmt::{Display, Formatter};
+
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, satisfy};
+use nom::combinator::{consumed, cut, not, opt};
+use nom::error::context;
+use nom::sequence::terminated;
+use nom::{IResult, Parser};
+
+use crate::ast::{BodyStatementAst, ExpressionAst, ReturnAst};
+use crate::nom_tools::{NomSpan, cleanup};
+
+use super::TimuParserError;
+
+impl ReturnAst<'_> {
+    /// Parses a `return` statement with an optional value
+    ///
+    /// The keyword must not be followed by an identifier character, so names
+    /// such as `returnValue` are still parsed as ordinary identifiers.
+    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, ReturnAst<'_>, TimuParserError<'_>> {
+        let (input, (span, _)) = cleanup(consumed(terminated(tag("return"), not(satisfy(|c: char| c.is_alphanumeric() || c == '_'))))).parse(input)?;
+        let (input, expression) = opt(ExpressionAst::parse).parse(input)?;
+        let (input, _) = context("Missing ';'", cut(cleanup(char(';')))).parse(input)?;
+
+        Ok((
+            input,
+            ReturnAst {
+                span: span.into(),
+                expression,
+            },
+        ))
+    }
+
+    pub fn parse_body_statement(input: NomSpan<'_>) -> IResult<NomSpan<'_>, BodyStatementAst<'_>, TimuParserError<'_>> {
+        let (input, return_statement) = Self::parse(input)?;
+        Ok((input, BodyStatementAst::Return(return_statement)))
+    }
+}
+
+impl Display for ReturnAst<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.expression {
+            Some(expression) => write!(f, "return {expression};"),
+            None => write!(f, "return;"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::{ast::{BodyAst, ReturnAst}, file::SourceFile, nom_tools::State};
+
+    use super::NomSpan;
+
+    #[rstest]
+    #[case("return;", "return;")]
+    #[case("return 1;", "return 1;")]
+    #[case("return   a + b ;", "return (a + b);")]
+    #[case("return call(1, true);", "return call(1, true);")]
+    #[case("return !a;", "return !a;")]
+    fn return_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
+        let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+
+        let state = State {
+            file: source_file.clone(),
+            indexer: Default::default(),
+        };
+
+        let input = NomSpan::new_extra(source_file.code().as_str(), state);
+        let (_, response) = ReturnAst::parse(input).unwrap();
+        assert_eq!(response.to_string(), expected, "{}", code);
+    }
+
+    #[rstest]
+    #[case("{ return 1; }", "{return 1;}")]
+    #[case("{ returnValue = 1; }", "{returnValue = 1;}")]
+    #[case("{ var a = 1; if a == 1 { return a; } return 2; }", "{var a = 1; if (a == 1) {return a;} return 2;}")]
+    fn return_in_body_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
+        let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+
+        let state = State {
+            file: source_file.clone(),
+            indexer: Default::default(),
+        };
+
+        let input = NomSpan::new_extra(source_file.code().as_str(), state);
+        let (_, response) = BodyAst::parse(input).unwrap();
+        assert_eq!(response.to_string(), expected, "{}", code);
+    }
+
+    #[test]
+    fn missing_semicolon() {
+        let source_file = SourceFile::new(vec!["<memory>".into()], "return 1".to_string());
+
+        let state = State {
+            file: source_file.clone(),
+            indexer: Default::default(),
+        };
+
+        let input = NomSpan::new_extra(source_file.code().as_str(), state);
+        assert!(ReturnAst::parse(input).is_err());
+    }
+}
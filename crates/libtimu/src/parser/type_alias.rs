@@ -0,0 +1,123 @@
+//! Type alias parsing for the Timu language.
+//!
+//! This module handles parsing of type aliases, which give an existing type
+//! another name. Aliases shorten long qualified names that show up repeatedly
+//! in signatures.
+//!
+//! # Type Alias Syntax
+//!
+//! ```timu
+//! type Point = geometry.shapes.Point;
+//! pub type Meters = i32;
+//! ```
+//!
+//! # Alias Components
+//!
+//! - **Visibility**: A `pub` alias can be imported from other modules
+//! - **Name**: The new name of the type
+//! - **Target**: The aliased type, a plain type name without `?` or `ref`
+//!
+//! # Resolution
+//!
+//! Aliases are transparent, the type resolver replaces every use of an alias
+//! with the aliased type. The modifiers stay at the place the alias is used,
+//! like `?Meters`, so the target itself can not be nullable or a reference.
+
+use std::fmt::{Display, Formatter};
+
+use nom::bytes::complete::tag;
+use nom::character::complete::{char, multispace1};
+use nom::combinator::cut;
+use nom::error::context;
+use nom::sequence::terminated;
+use nom::{IResult, Parser};
+use nom_language::error::{VerboseError, VerboseErrorKind};
+
+use crate::ast::{AstIndex, FileStatementAst, TypeAliasAst, TypeNameAst};
+use crate::nom_tools::{cleanup, NomSpan};
+
+use super::{expected_ident, is_public, TimuParserError};
+
+impl TypeAliasAst<'_> {
+    /// Parses a type alias definition
+    ///
+    /// # Parsing Logic
+    /// 1. Parse the optional `pub` keyword and the `type` keyword
+    /// 2. Parse the alias name (required)
+    /// 3. Require `=` followed by the aliased type
+    /// 4. Reject nullable and reference targets
+    /// 5. Require terminating semicolon
+    ///
+    /// # Arguments
+    /// * `input` - The input span to parse from
+    ///
+    /// # Returns
+    /// * `Ok((remaining, statement))` - Successfully parsed type alias
+    /// * `Err(error)` - Parse error with detailed context
+    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, FileStatementAst<'_>, TimuParserError<'_>> {
+        let (input, is_public) = is_public(input)?;
+        let (input, _) = cleanup(terminated(tag("type"), multispace1)).parse(input)?;
+        let (input, name) = expected_ident("Missing type alias name", input)?;
+        let (input, _) = context("Missing '='", cut(cleanup(char('=')))).parse(input)?;
+
+        let target_start = input.clone();
+        let (input, target) = context("Missing aliased type", cut(TypeNameAst::parse)).parse(input)?;
+        if target.nullable || target.reference {
+            return Err(nom::Err::Failure(VerboseError {
+                errors: vec![(target_start, VerboseErrorKind::Context("Aliased type can not be nullable or a reference"))],
+            }));
+        }
+
+        let (input, _) = context("Missing ';'", cut(cleanup(char(';')))).parse(input)?;
+        let index = AstIndex(input.extra.indexer.fetch_add(1, std::sync::atomic::Ordering::Relaxed));
+
+        Ok((
+            input,
+            FileStatementAst::TypeAlias(TypeAliasAst {
+                is_public: is_public.map(|item| item.into()),
+                name: name.into(),
+                target,
+                index,
+            }.into()),
+        ))
+    }
+}
+
+impl Display for TypeAliasAst<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}type {} = {};",
+               if self.is_public.is_some() { "pub " } else { "" },
+               self.name.text,
+               self.target)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::Finish;
+    use rstest::rstest;
+
+    use crate::{file::SourceFile, nom_tools::State};
+
+    #[rstest]
+    #[case("type Meters = i32;", "type Meters = i32;")]
+    #[case(" pub  type   Point=geometry.shapes.Point ; ", "pub type Point = geometry.shapes.Point;")]
+    #[case("type A = B;\ntype B = i32;", "type A = B;\ntype B = i32;")]
+    fn type_alias_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
+        let state = State::new(SourceFile::new(vec!["<memory>".into()], code.to_string()));
+        let (_, response) = crate::parser::parse(&state).finish().unwrap();
+        assert_eq!(response.to_string(), expected, "{code}");
+    }
+
+    #[rstest]
+    #[case("type = i32;")]
+    #[case("type Meters i32;")]
+    #[case("type Meters = ;")]
+    #[case("type Meters = i32")]
+    #[case("type Meters = ?i32;")]
+    #[case("type Meters = ref i32;")]
+    fn invalid_type_alias_test<'base>(#[case] code: &'base str) {
+        let state = State::new(SourceFile::new(vec!["<memory>".into()], code.to_string()));
+        assert!(crate::parser::parse(&state).finish().is_err(), "{code}");
+    }
+}
@@ -22,6 +22,20 @@
 //! ref ?string     // Reference to nullable type
 //! ```
 //!
+//! ## Function Types
+//! ```timu
+//! func(): void               // Function without arguments
+//! func(i32, i32): i32        // Function taking two integers
+//! ?func(string): bool        // Nullable function value
+//! ```
+//!
+//! ## Tuple Types
+//! ```timu
+//! (i32, string)              // Pair of an integer and a string
+//! (f64, (i32, i32))          // Tuples can be nested
+//! ?(i32, ?string)            // Nullable tuple with a nullable element
+//! ```
+//!
 //! ## Qualified Names
 //! ```timu
 //! std.collections.HashMap     // Fully qualified type
@@ -34,6 +48,8 @@
 //! - **Nullable types**: Optional types marked with `?` prefix
 //! - **Reference types**: Reference semantics marked with `ref` keyword
 //! - **Qualified names**: Dot-separated module and type hierarchies
+//! - **Function types**: Argument and return types of callable values
+//! - **Tuple types**: Fixed size groups of values with their own types
 //! - **Function path conversion**: Types used in function call contexts
 //!
 //! # Type System Integration
@@ -46,9 +62,9 @@
 
 use std::fmt::{Display, Formatter};
 
-use nom::{character::complete::char, combinator::{consumed, map}, multi::separated_list1, IResult, Parser};
+use nom::{bytes::complete::tag, character::complete::char, combinator::{consumed, cut, map, opt, peek}, error::context, multi::{separated_list0, separated_list1}, sequence::{delimited, terminated}, IResult, Parser};
 
-use crate::{ast::{FunctionCallPathAst, TypeNameAst}, nom_tools::NomSpan, parser::is_reference};
+use crate::{ast::{FunctionCallPathAst, FunctionTypeAst, TypeNameAst}, nom_tools::{cleanup, NomSpan}, parser::is_reference};
 
 use super::{ident, is_nullable, TimuParserError};
 
@@ -64,7 +80,7 @@ impl TypeNameAst<'_> {
     /// # Parsing Order
     /// 1. Reference modifier (`ref`)
     /// 2. Nullable modifier (`?`)
-    /// 3. Qualified type name (dot-separated identifiers)
+    /// 3. Function type (`func(...)`), tuple type (`(...)`) or qualified type name (dot-separated identifiers)
     /// 
     /// # Arguments
     /// * `input` - The input span to parse from
@@ -81,6 +97,8 @@ impl TypeNameAst<'_> {
     /// ref ?string               // Reference to nullable string
     /// module.CustomType         // Qualified type name
     /// ref ?module.CustomType    // Complex qualified nullable reference
+    /// func(i32, i32): i32       // Function type
+    /// (i32, string)             // Tuple type
     /// ```
     /// 
     /// # Errors
@@ -88,9 +106,21 @@ impl TypeNameAst<'_> {
     /// - Invalid identifier syntax in type names
     /// - Malformed qualified names
     /// - Missing type name after modifiers
+    /// - Missing `)` or return type in function types
+    /// - Tuple types with less than two elements
     pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, TypeNameAst<'_>, TimuParserError<'_>> {
         let (input, reference) = is_reference(input)?;
         let (input, nullable) = is_nullable(input)?;
+        let (input, function) = opt(Self::parse_function_type(reference, nullable)).parse(input)?;
+        if let Some(function) = function {
+            return Ok((input, function));
+        }
+
+        let (input, tuple) = opt(Self::parse_tuple_type(reference, nullable)).parse(input)?;
+        if let Some(tuple) = tuple {
+            return Ok((input, tuple));
+        }
+
         let (input, (names_span, names)) = consumed(map(separated_list1(char('.'), ident()), |items| items)).parse(input)?;
         Ok((
             input,
@@ -99,10 +129,67 @@ impl TypeNameAst<'_> {
                 nullable,
                 names: names.into_iter().map(|item| item.into()).collect::<Vec<_>>(),
                 names_span: names_span.into(),
+                function: None,
+                tuple: None,
             },
         ))
     }
 
+    /// Parses a function type such as `func(i32, i32): i32`
+    /// 
+    /// The `func` keyword becomes the only name of the type, the argument and
+    /// return types are stored in [`FunctionTypeAst`]. The keyword needs to be
+    /// followed by `(`, otherwise the input is left for the regular type name parser.
+    fn parse_function_type<'base>(reference: bool, nullable: bool) -> impl Parser<NomSpan<'base>, Output = TypeNameAst<'base>, Error = TimuParserError<'base>> {
+        map(
+            consumed((
+                cleanup(terminated(tag("func"), peek(cleanup(char('('))))),
+                consumed(delimited(char('('), cleanup(separated_list0(char(','), cleanup(TypeNameAst::parse))), context("Missing ')'", cut(char(')'))))),
+                context("Missing ':'", cut(cleanup(char(':')))),
+                context("Missing function return type", cut(cleanup(TypeNameAst::parse))),
+            )),
+            move |(names_span, (keyword, (arguments_span, arguments), _, return_type))| TypeNameAst {
+                reference,
+                nullable,
+                names: vec![keyword.into()],
+                names_span: names_span.into(),
+                function: Some(Box::new(FunctionTypeAst {
+                    arguments,
+                    arguments_span: arguments_span.into(),
+                    return_type,
+                })),
+                tuple: None,
+            },
+        )
+    }
+
+    /// Parses a tuple type such as `(i32, string)`
+    ///
+    /// The opening parenthesis becomes the only name of the type, the element
+    /// types are stored in the `tuple` field. A tuple needs at least two elements.
+    fn parse_tuple_type<'base>(reference: bool, nullable: bool) -> impl Parser<NomSpan<'base>, Output = TypeNameAst<'base>, Error = TimuParserError<'base>> {
+        map(
+            consumed((
+                cleanup(tag("(")),
+                context("Missing tuple element type", cut(cleanup(TypeNameAst::parse))),
+                context("Tuple types need at least two elements", cut(char(','))),
+                context("Tuple types need at least two elements", cut(separated_list1(char(','), cleanup(TypeNameAst::parse)))),
+                context("Missing ')'", cut(char(')'))),
+            )),
+            move |(names_span, (open, first, _, rest, _))| {
+                let elements = std::iter::once(first).chain(rest).collect();
+                TypeNameAst {
+                    reference,
+                    nullable,
+                    names: vec![open.into()],
+                    names_span: names_span.into(),
+                    function: None,
+                    tuple: Some(elements),
+                }
+            },
+        )
+    }
+
     /// Parses a type name for use in function call path resolution
     /// 
     /// This parser variant converts a parsed type name into a function call path
@@ -145,6 +232,21 @@ impl Display for TypeNameAst<'_> {
             write!(f, "?")?;
         }
 
+        if let Some(function) = &self.function {
+            write!(f, "func(")?;
+            for (i, argument) in function.arguments.iter().enumerate() {
+                if i > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{argument}")?;
+            }
+            return write!(f, "): {}", function.return_type);
+        }
+
+        if let Some(elements) = &self.tuple {
+            return write!(f, "({})", elements.iter().map(|element| element.to_string()).collect::<Vec<_>>().join(", "));
+        }
+
         for (i, name) in self.names.iter().enumerate() {
             if i > 0 {
                 write!(f, ".")?;
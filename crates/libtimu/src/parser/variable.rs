@@ -20,6 +20,12 @@
 //! const name: Type = expression;   // Explicit type with initialization
 //! ```
 //!
+//! ## Module Variables
+//! ```timu
+//! pub const MAX_SIZE: i32 = 1000;  // Importable module constant
+//! var counter: i32 = 0;            // Global variable of the module
+//! ```
+//!
 //! ## Variable Assignments
 //! ```timu
 //! variableName = newValue;         // Assign new value to existing variable
@@ -39,6 +45,10 @@
 //! Constants must always be initialized and cannot be reassigned after declaration.
 //! They provide compile-time guarantees about value immutability.
 //!
+//! ## Module Variables
+//! Declarations at the top level of a file always need an initial value,
+//! because there is no statement that could assign one before the first use.
+//!
 //! # Error Handling
 //!
 //! The parser provides detailed error messages for common mistakes:
@@ -52,15 +62,17 @@ use std::fmt::{Display, Formatter};
 
 use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::char;
-use nom::combinator::{cut, map, opt};
+use nom::character::complete::{char, satisfy};
+use nom::combinator::{consumed, cut, map, not, opt};
+use nom::multi::{many0, separated_list1};
+use nom::sequence::{delimited, terminated};
 use nom::error::context;
 use nom::{IResult, Parser};
 use nom_language::error::{VerboseError, VerboseErrorKind};
 
-use crate::ast::{BodyStatementAst, ExpressionAst, TypeNameAst, VariableAssignAst, VariableDefinitionAst, VariableDefinitionType};
-use crate::nom_tools::{NomSpan, cleanup};
-use crate::parser::{expected_ident, ident};
+use crate::ast::{AstIndex, BodyStatementAst, ExpressionAst, FileStatementAst, ModuleVariableAst, TypeNameAst, VariableAssignAst, VariableDefinitionAst, VariableDefinitionType};
+use crate::nom_tools::{NomSpan, Span, cleanup};
+use crate::parser::{expected_ident, ident, is_public};
 
 use super::TimuParserError;
 
@@ -82,7 +94,7 @@ impl VariableDefinitionAst<'_> {
     /// 
     /// # Parsing Logic
     /// 1. Parse variable kind (`var` or `const`)
-    /// 2. Parse variable name (identifier)
+    /// 2. Parse variable name (identifier), or the parenthesized names of a destructuring definition
     /// 3. Optionally parse type annotation after `:`
     /// 4. Optionally parse initialization expression after `=`
     /// 5. Validate combination according to language rules
@@ -92,6 +104,7 @@ impl VariableDefinitionAst<'_> {
     /// - Constants must have initialization expressions
     /// - Variables without type annotations must have initialization
     /// - Nullable types can be declared without initialization
+    /// - Destructuring definitions need at least two names and an initialization
     /// 
     /// # Arguments
     /// * `input` - The input span to parse from
@@ -107,13 +120,18 @@ impl VariableDefinitionAst<'_> {
     /// var optional: ?i32;                 // Nullable without init
     /// const PI = 3.14159;                 // Constant with inference
     /// const MAX_SIZE: i32 = 1000;         // Constant with explicit type
+    /// var (x, y) = position();            // Destructuring a tuple
     /// ```
     pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, VariableDefinitionAst<'_>, TimuParserError<'_>> {
-        let (input, variable_definition_type) = cleanup(alt((
+        let (input, variable_definition_type) = cleanup(terminated(alt((
             map(tag("var"), |_| VariableDefinitionType::Var),
             map(tag("const"), |_| VariableDefinitionType::Const))
-        )).parse(input)?;
-        let (input, name) = expected_ident("Missing variable name", input)?;
+        ), not(satisfy(|c: char| c.is_alphanumeric() || c == '_')))).parse(input)?;
+        let (input, destructure) = opt(destructure).parse(input)?;
+        let (input, name) = match &destructure {
+            Some((names_span, _)) => (input, names_span.clone()),
+            None => expected_ident("Missing variable name", input)?,
+        };
         
         let (input, expected_type, expression) = match cleanup(opt(char(':'))).parse(input)? {
             (input, Some(_)) => {
@@ -145,6 +163,12 @@ impl VariableDefinitionAst<'_> {
             }
         };
 
+        if destructure.is_some() && expression.is_none() {
+            return Err(nom::Err::Failure(VerboseError {
+                errors: vec![(input, VerboseErrorKind::Context("Destructuring must have an assignment"))],
+            }));
+        }
+
         let (input, _) = context("Missing ';'", cleanup(char(';'))).parse(input)?;
 
         Ok((
@@ -152,6 +176,7 @@ impl VariableDefinitionAst<'_> {
             VariableDefinitionAst {
                 variable_definition_type,
                 name: name.into(),
+                destructure: destructure.map(|(_, names)| names.into_iter().map(|name| name.into()).collect()),
                 expected_type,
                 expression,
             },
@@ -159,9 +184,31 @@ impl VariableDefinitionAst<'_> {
     }
 }
 
+/// Parses the names of a destructuring definition, like `(x, y)`
+///
+/// Returns the span of the whole list with the parentheses and the names.
+fn destructure<'base>(input: NomSpan<'base>) -> IResult<NomSpan<'base>, (NomSpan<'base>, Vec<NomSpan<'base>>), TimuParserError<'base>> {
+    let (rest, (names_span, names)) = cleanup(consumed(delimited(
+        char('('),
+        context("Missing variable name", cut(separated_list1(char(','), ident()))),
+        context("Missing ')'", cut(char(')'))),
+    ))).parse(input)?;
+
+    if names.len() < 2 {
+        return Err(nom::Err::Failure(VerboseError {
+            errors: vec![(names_span, VerboseErrorKind::Context("Destructuring needs at least two names"))],
+        }));
+    }
+
+    Ok((rest, (names_span, names)))
+}
+
 impl Display for VariableDefinitionAst<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {}", self.variable_definition_type, self.name.text)?;
+        match &self.destructure {
+            Some(names) => write!(f, "{} ({})", self.variable_definition_type, names.iter().map(|name| name.text).collect::<Vec<_>>().join(", "))?,
+            None => write!(f, "{} {}", self.variable_definition_type, self.name.text)?,
+        }
         if let Some(expected_type) = &self.expected_type {
             write!(f, ": {expected_type}")?;
         }
@@ -172,6 +219,58 @@ impl Display for VariableDefinitionAst<'_> {
     }
 }
 
+impl ModuleVariableAst<'_> {
+    /// Parses a constant or global variable at the top level of a file
+    ///
+    /// # Parsing Logic
+    /// 1. Parse the optional `pub` keyword
+    /// 2. Parse the declaration like a variable definition in a function body
+    /// 3. Require an initial value and a single name, destructuring is only allowed in function bodies
+    ///
+    /// # Arguments
+    /// * `input` - The input span to parse from
+    ///
+    /// # Returns
+    /// * `Ok((remaining, statement))` - Successfully parsed module variable
+    /// * `Err(error)` - Parse error with detailed context
+    pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, FileStatementAst<'_>, TimuParserError<'_>> {
+        let (input, is_public) = is_public(input)?;
+        let (input, definition) = VariableDefinitionAst::parse(input)?;
+
+        if definition.expression.is_none() {
+            return Err(nom::Err::Failure(VerboseError {
+                errors: vec![(input, VerboseErrorKind::Context("Module variable must have an assignment"))],
+            }));
+        }
+
+        if definition.destructure.is_some() {
+            return Err(nom::Err::Failure(VerboseError {
+                errors: vec![(input, VerboseErrorKind::Context("Module variables cannot be destructured"))],
+            }));
+        }
+
+        let index = AstIndex(input.extra.indexer.fetch_add(1, std::sync::atomic::Ordering::Relaxed));
+
+        Ok((
+            input,
+            FileStatementAst::Variable(ModuleVariableAst {
+                is_public: is_public.map(|item| item.into()),
+                definition,
+                index,
+            }.into()),
+        ))
+    }
+}
+
+impl Display for ModuleVariableAst<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.is_public.is_some() {
+            write!(f, "pub ")?;
+        }
+        write!(f, "{}", self.definition)
+    }
+}
+
 impl VariableAssignAst<'_> {
     /// Parses a variable assignment for use as a statement
     /// 
@@ -191,6 +290,7 @@ impl VariableAssignAst<'_> {
     /// # Syntax
     /// ```timu
     /// variableName = expression;
+    /// object.field = expression;
     /// ```
     /// 
     /// # Arguments
@@ -213,6 +313,7 @@ impl VariableAssignAst<'_> {
     /// name = "new name";
     /// result = calculateValue();
     /// flag = !flag;
+    /// Counter.count = 0;
     /// ```
     /// 
     /// # Type Checking
@@ -220,6 +321,7 @@ impl VariableAssignAst<'_> {
     /// the variable and the assigned expression is validated during semantic
     /// analysis in the TIR phase.
     pub fn parse(input: NomSpan<'_>) -> IResult<NomSpan<'_>, VariableAssignAst<'_>, TimuParserError<'_>> {
+        let (input, path) = many0(terminated(ident(), cleanup(char('.')))).parse(input)?;
         let (input, name) = ident().parse(input)?;
         let (input, _) = context("Missing '='", cleanup(char('='))).parse(input)?;
         let (input, expression) = context("Invalid expression", cut(ExpressionAst::parse)).parse(input)?;
@@ -228,6 +330,7 @@ impl VariableAssignAst<'_> {
         Ok((
             input,
             VariableAssignAst {
+                path: path.into_iter().map(Span::from).collect(),
                 name: name.into(),
                 expression,
             },
@@ -237,6 +340,9 @@ impl VariableAssignAst<'_> {
 
 impl Display for VariableAssignAst<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for name in self.path.iter() {
+            write!(f, "{}.", name.text)?;
+        }
         write!(f, "{} = {};", self.name.text, self.expression)
     }
 }
@@ -252,6 +358,7 @@ impl Display for VariableDefinitionType {
 
 #[cfg(test)]
 mod tests {
+    use nom::Finish;
     use nom_language::error::VerboseErrorKind;
     use rstest::rstest;
 
@@ -272,6 +379,8 @@ mod tests {
     #[case("const a = -1.2;", "const a = -1.2;")]
     #[case("const a: f64 = -1.2;", "const a: f64 = -1.2;")]
     #[case("const a: ?f64 = -1.2;", "const a: ?f64 = -1.2;")]
+    #[case("var ( x,y ) = point();", "var (x, y) = point();")]
+    #[case("const (a, b, c): (i32, string, bool) = (1, \"b\", true);", "const (a, b, c): (i32, string, bool) = (1, b, true);")]
     fn custom_variable_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
         let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
 
@@ -292,6 +401,9 @@ mod tests {
     #[case("var a;", "Missing '='")]
     #[case("var a: ;", "Missing variable type")]
     #[case("const a: ?i32;", "Const variable must have an assignment")]
+    #[case("var (a) = pair;", "Destructuring needs at least two names")]
+    #[case("var (a, ) = pair;", "Missing ')'")]
+    #[case("var (a, b): ?(i32, i32);", "Destructuring must have an assignment")]
     fn invalid_variable_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
         let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
 
@@ -314,4 +426,24 @@ mod tests {
             panic!("Expected an error, but got: {error:#?}");
         }
     }
-}
\ No newline at end of file
+
+    #[rstest]
+    #[case("const LIMIT: i32 = 10;", "const LIMIT: i32 = 10;")]
+    #[case(" pub  const   LIMIT=10 ; ", "pub const LIMIT = 10;")]
+    #[case("var counter: i32 = 0;\npub func get(): i32 { return counter; }", "var counter: i32 = 0;\npub func get(): i32 {return counter;}")]
+    #[case("const func one(): i32 { return 1; }\nconst ONE = one();", "const func one(): i32 {return 1;}\nconst ONE = one();")]
+    fn module_variable_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
+        let state = State::new(SourceFile::new(vec!["<memory>".into()], code.to_string()));
+        let (_, response) = crate::parser::parse(&state).finish().unwrap();
+        assert_eq!(response.to_string(), expected, "{code}");
+    }
+
+    #[rstest]
+    #[case("var counter: ?i32;")]
+    #[case("const LIMIT = ;")]
+    #[case("pub const = 1;")]
+    fn invalid_module_variable_test<'base>(#[case] code: &'base str) {
+        let state = State::new(SourceFile::new(vec!["<memory>".into()], code.to_string()));
+        assert!(crate::parser::parse(&state).finish().is_err(), "{code}");
+    }
+}
@@ -0,0 +1,97 @@
+//! The `std` module, the standard library every Timu program can use.
+//!
+//! The standard library is written as Timu declarations that are embedded in
+//! the compiler and added to every [`TirContext`] by [`crate::tir::build`].
+//! Its functions are imported like the functions of any other module:
+//!
+//! ```timu
+//! use std.io.println;
+//! use std.math.max;
+//!
+//! pub func main(): void {
+//!     println("Hello");
+//! }
+//! ```
+//!
+//! # Modules
+//!
+//! | Module       | Functions                                                      |
+//! |--------------|----------------------------------------------------------------|
+//! | `std.io`     | `print(text: string): void`, `println(text: string): void`     |
+//! | `std.math`   | `abs(value: i32): i32`, `min(left: i32, right: i32): i32`, `max(left: i32, right: i32): i32` |
+//! | `std.string` | `len(text: string): i32`                                       |
+//!
+//! # Native Functions
+//!
+//! The functions are declared with `native func` and have no body. Backends
+//! bind every native function in [`NATIVE_FUNCTIONS`] to an implementation of
+//! the host, see [`crate::backend::wat`] for the WebAssembly imports.
+//!
+//! [`TirContext`]: crate::tir::TirContext
+
+use std::{rc::Rc, sync::LazyLock};
+
+use crate::{ast::FileAst, file::SourceFile, nom_tools::State, process_code};
+
+/// Path and source of every module of the standard library
+pub const MODULES: &[(&[&str], &str)] = &[
+    (&["std", "io"], include_str!("io.tim")),
+    (&["std", "math"], include_str!("math.tim")),
+    (&["std", "string"], include_str!("string.tim")),
+];
+
+/// Full names of the functions the host implements
+pub const NATIVE_FUNCTIONS: &[&str] = &[
+    "std.io.print",
+    "std.io.println",
+    "std.math.abs",
+    "std.math.min",
+    "std.math.max",
+    "std.string.len",
+];
+
+/// Parser states of the standard library, the parsed files borrow from them
+static STATES: LazyLock<Vec<State>> = LazyLock::new(|| MODULES.iter()
+    .map(|(path, code)| State::new(SourceFile::new(path.iter().map(|name| name.to_string()).collect(), code.to_string())))
+    .collect());
+
+/// Returns whether a module path belongs to the standard library
+pub fn is_prelude_module(path: &[String]) -> bool {
+    MODULES.iter().any(|(module, _)| module.len() == path.len() && module.iter().zip(path).all(|(left, right)| left == right))
+}
+
+/// Returns whether a function with the given full name is implemented by the host
+pub fn is_native_function(full_name: &str) -> bool {
+    NATIVE_FUNCTIONS.contains(&full_name)
+}
+
+/// Parses the modules of the standard library
+pub(crate) fn files() -> Vec<Rc<FileAst<'static>>> {
+    STATES.iter()
+        .map(|state| Rc::new(process_code(state).expect("Standard library does not parse, but this is a bug")))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{files, is_native_function, MODULES, NATIVE_FUNCTIONS};
+
+    #[test]
+    fn modules_parse() {
+        assert_eq!(files().len(), MODULES.len());
+    }
+
+    #[test]
+    fn native_functions_are_declared() {
+        let declared = MODULES.iter()
+            .flat_map(|(path, code)| code.lines().filter_map(move |line| {
+                let name = line.split_once("native func ")?.1.split_once('(')?.0;
+                Some(format!("{}.{name}", path.join(".")))
+            }))
+            .collect::<Vec<_>>();
+
+        assert_eq!(declared, NATIVE_FUNCTIONS);
+        assert!(is_native_function("std.io.print"));
+        assert!(!is_native_function("main.print"));
+    }
+}
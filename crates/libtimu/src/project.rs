@@ -0,0 +1,369 @@
+//! Timu projects, a manifest and a directory tree of source files.
+//!
+//! A project is a directory with a `timu.toml` manifest. The manifest names the
+//! directory holding the sources and the module the program starts from:
+//!
+//! ```toml
+//! [project]
+//! name = "hello"
+//! source-root = "src"   # optional, defaults to "src"
+//! entry = "main"        # optional, defaults to "main"
+//! search-paths = ["vendor"]  # optional, where imported modules outside the project are loaded from
+//! std-root = "/usr/lib/timu/std"  # optional, where the modules below `std` are loaded from
+//!
+//! [lints]               # optional, the level of each lint
+//! unused_variables = "deny"
+//! ```
+//!
+//! Every `.tim` file below the source root is a module. Its path relative to
+//! the source root, without the extension, is the module path, so
+//! `src/net/http.tim` is the module `net.http` and can be imported with
+//! `use net.http.Client;`. Dots in file names separate path segments as well,
+//! so `src/net.http.tim` would be the same module, which is reported as a
+//! [`ProjectError::DuplicateModule`].
+//!
+//! # Usage
+//!
+//! ```ignore
+//! let project = Project::load("path/to/project")?;
+//! let states = project.sources()?.into_iter().map(State::new).collect::<Vec<_>>();
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{error::TIMU_LANG_EXT, file::SourceFile, lint::{Lint, LintLevel}};
+
+/// File name of the project manifest
+pub static MANIFEST_FILE: &str = "timu.toml";
+
+/// Errors raised while loading a project
+#[derive(Debug, thiserror::Error)]
+pub enum ProjectError {
+    #[error("could not find `{MANIFEST_FILE}` in `{}` or any parent directory", .0.display())]
+    ManifestNotFound(PathBuf),
+
+    #[error("invalid manifest `{}`: {message}", path.display())]
+    InvalidManifest { path: PathBuf, message: String },
+
+    #[error("could not read `{}`: {error}", path.display())]
+    Io { path: PathBuf, error: std::io::Error },
+
+    #[error("`{}` is not a valid module name, every part of the path needs to be an identifier", path.display())]
+    InvalidModuleName { path: PathBuf },
+
+    #[error("module `{name}` is defined twice, in `{}` and `{}`", first.display(), second.display())]
+    DuplicateModule { name: String, first: PathBuf, second: PathBuf },
+
+    #[error("entry module `{entry}` not found in `{}`", source_root.display())]
+    EntryNotFound { entry: String, source_root: PathBuf },
+}
+
+/// Contents of a `timu.toml` file
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub project: ProjectSection,
+    /// Level of the lints, see [`crate::lint`]
+    #[serde(default)]
+    pub lints: BTreeMap<Lint, LintLevel>,
+}
+
+/// The `[project]` table of the manifest
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct ProjectSection {
+    /// Name of the project
+    pub name: String,
+    /// Directory of the sources, relative to the manifest
+    #[serde(default = "default_source_root")]
+    pub source_root: PathBuf,
+    /// Path of the module the program starts from, such as `main` or `app.main`
+    #[serde(default = "default_entry")]
+    pub entry: String,
+    /// Directories imported modules that are not part of the project are loaded from, relative to the manifest
+    #[serde(default)]
+    pub search_paths: Vec<PathBuf>,
+    /// Directory the modules below `std` are loaded from, relative to the manifest
+    #[serde(default)]
+    pub std_root: Option<PathBuf>,
+}
+
+fn default_source_root() -> PathBuf {
+    PathBuf::from("src")
+}
+
+fn default_entry() -> String {
+    "main".to_string()
+}
+
+impl Manifest {
+    /// Parses the contents of a manifest
+    pub fn parse(content: &str) -> Result<Self, String> {
+        toml::from_str(content).map_err(|error| error.message().to_string())
+    }
+}
+
+/// A source file of a project and the module it defines
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleFile {
+    /// Path of the file
+    pub path: PathBuf,
+    /// Module path, such as `["net", "http"]` for `src/net/http.tim`
+    pub module: Vec<String>,
+}
+
+/// A loaded project with the modules found below its source root
+#[derive(Debug, Clone)]
+pub struct Project {
+    /// Directory containing the manifest
+    pub root: PathBuf,
+    pub manifest: Manifest,
+    /// Modules of the project, sorted by module path
+    pub modules: Vec<ModuleFile>,
+}
+
+impl Project {
+    /// Loads the project whose manifest is in the given directory
+    pub fn load(root: impl AsRef<Path>) -> Result<Self, ProjectError> {
+        let root = root.as_ref().to_path_buf();
+        let manifest_path = root.join(MANIFEST_FILE);
+        let content = std::fs::read_to_string(&manifest_path).map_err(|error| match error.kind() {
+            std::io::ErrorKind::NotFound => ProjectError::ManifestNotFound(root.clone()),
+            _ => ProjectError::Io { path: manifest_path.clone(), error },
+        })?;
+
+        let manifest = Manifest::parse(&content).map_err(|message| ProjectError::InvalidManifest { path: manifest_path, message })?;
+        let source_root = root.join(&manifest.project.source_root);
+        let modules = discover_modules(&source_root)?;
+
+        if !modules.iter().any(|module| module.module.join(".") == manifest.project.entry) {
+            return Err(ProjectError::EntryNotFound { entry: manifest.project.entry.clone(), source_root });
+        }
+
+        Ok(Self { root, manifest, modules })
+    }
+
+    /// Loads the project of the given directory or of the closest parent directory with a manifest
+    pub fn find(directory: impl AsRef<Path>) -> Result<Self, ProjectError> {
+        let directory = directory.as_ref();
+        match directory.ancestors().find(|ancestor| ancestor.join(MANIFEST_FILE).is_file()) {
+            Some(root) => Self::load(root),
+            None => Err(ProjectError::ManifestNotFound(directory.to_path_buf())),
+        }
+    }
+
+    /// Directory of the sources
+    pub fn source_root(&self) -> PathBuf {
+        self.root.join(&self.manifest.project.source_root)
+    }
+
+    /// Directories imported modules are loaded from, the source root first
+    pub fn search_paths(&self) -> Vec<PathBuf> {
+        std::iter::once(self.source_root())
+            .chain(self.manifest.project.search_paths.iter().map(|path| self.root.join(path)))
+            .collect()
+    }
+
+    /// Directory the modules below `std` are loaded from
+    pub fn std_root(&self) -> Option<PathBuf> {
+        self.manifest.project.std_root.as_ref().map(|path| self.root.join(path))
+    }
+
+    /// Module path of the entry module, split into its segments
+    pub fn entry(&self) -> Vec<String> {
+        self.manifest.project.entry.split('.').map(str::to_string).collect()
+    }
+
+    /// Reads every module of the project
+    pub fn sources(&self) -> Result<Vec<SourceFile>, ProjectError> {
+        self.modules.iter()
+            .map(|module| std::fs::read_to_string(&module.path)
+                .map(|code| SourceFile::new(module.module.clone(), code))
+                .map_err(|error| ProjectError::Io { path: module.path.clone(), error }))
+            .collect()
+    }
+}
+
+/// Finds every source file below the source root and derives its module path
+///
+/// Files are visited in name order, so the result does not depend on the
+/// order the file system lists a directory in.
+pub fn discover_modules(source_root: &Path) -> Result<Vec<ModuleFile>, ProjectError> {
+    let mut files = Vec::new();
+    collect_files(source_root, &mut files)?;
+    files.sort();
+
+    let mut modules: Vec<ModuleFile> = Vec::new();
+    let mut names: HashMap<String, usize> = HashMap::new();
+
+    for path in files.into_iter() {
+        let module = module_path(source_root, &path).ok_or_else(|| ProjectError::InvalidModuleName { path: path.clone() })?;
+        let name = module.join(".");
+
+        if let Some(index) = names.get(&name) {
+            return Err(ProjectError::DuplicateModule { name, first: modules[*index].path.clone(), second: path });
+        }
+
+        names.insert(name, modules.len());
+        modules.push(ModuleFile { path, module });
+    }
+
+    modules.sort_by(|left, right| left.module.cmp(&right.module));
+    Ok(modules)
+}
+
+fn collect_files(directory: &Path, files: &mut Vec<PathBuf>) -> Result<(), ProjectError> {
+    let entries = std::fs::read_dir(directory).map_err(|error| ProjectError::Io { path: directory.to_path_buf(), error })?;
+
+    for entry in entries {
+        let path = entry.map_err(|error| ProjectError::Io { path: directory.to_path_buf(), error })?.path();
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if path.extension().is_some_and(|extension| extension == TIMU_LANG_EXT) {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds the module path of a file below the source root, `None` when a
+/// segment is not an identifier or the file is outside of the source root
+pub fn module_path(source_root: &Path, path: &Path) -> Option<Vec<String>> {
+    let relative = path.strip_prefix(source_root).ok()?.with_extension("");
+    let mut module = Vec::new();
+
+    for component in relative.components() {
+        for segment in component.as_os_str().to_str()?.split('.') {
+            let mut characters = segment.chars();
+            let valid = characters.next().is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+                && characters.all(|character| character.is_ascii_alphanumeric() || character == '_');
+
+            if !valid {
+                return None;
+            }
+
+            module.push(segment.to_string());
+        }
+    }
+
+    Some(module)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use crate::{lint::{Lint, LintLevel}, nom_tools::State, process_ast, process_code};
+
+    use super::{Manifest, Project, ProjectError};
+
+    fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("timu-project-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+
+        for (path, content) in files.iter() {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        root
+    }
+
+    #[test]
+    fn manifest() {
+        let manifest = Manifest::parse("[project]\nname = \"hello\"").unwrap();
+        assert_eq!(manifest.project.name, "hello");
+        assert_eq!(manifest.project.source_root, Path::new("src"));
+        assert_eq!(manifest.project.entry, "main");
+        assert!(manifest.project.search_paths.is_empty() && manifest.project.std_root.is_none());
+
+        let manifest = Manifest::parse("[project]\nname = \"hello\"\nsource-root = \"lib\"\nentry = \"app.start\"").unwrap();
+        assert_eq!(manifest.project.source_root, Path::new("lib"));
+        assert_eq!(manifest.project.entry, "app.start");
+
+        let manifest = Manifest::parse("[project]\nname = \"hello\"\nsearch-paths = [\"vendor\", \"../shared\"]\nstd-root = \"std\"").unwrap();
+        assert_eq!(manifest.project.search_paths, [Path::new("vendor"), Path::new("../shared")]);
+        assert_eq!(manifest.project.std_root.as_deref(), Some(Path::new("std")));
+
+        assert!(Manifest::parse("[project]\nsource-root = \"lib\"").is_err());
+        assert!(Manifest::parse("[project]\nname = \"hello\"\nunknown = 1").is_err());
+    }
+
+    #[test]
+    fn lints() {
+        let manifest = Manifest::parse("[project]\nname = \"hello\"").unwrap();
+        assert!(manifest.lints.is_empty());
+
+        let manifest = Manifest::parse("[project]\nname = \"hello\"\n\n[lints]\nunused_variables = \"deny\"\nunused_imports = \"allow\"").unwrap();
+        assert_eq!(manifest.lints.get(&Lint::UnusedVariables), Some(&LintLevel::Deny));
+        assert_eq!(manifest.lints.get(&Lint::UnusedImports), Some(&LintLevel::Allow));
+        assert_eq!(manifest.lints.get(&Lint::UnusedFunctions), None);
+
+        assert!(Manifest::parse("[project]\nname = \"hello\"\n\n[lints]\nunused = \"deny\"").is_err());
+        assert!(Manifest::parse("[project]\nname = \"hello\"\n\n[lints]\nunused_variables = \"error\"").is_err());
+    }
+
+    #[test]
+    fn nested_modules() {
+        let root = project("nested", &[
+            ("timu.toml", "[project]\nname = \"web\""),
+            ("src/main.tim", "use net.http.Client; use net.Address; pub func main(): i32 { return 0; }"),
+            ("src/net/http.tim", "pub class Client {}"),
+            ("src/net.tim", "pub class Address {}"),
+            ("src/notes.txt", "not a module"),
+        ]);
+
+        let project = Project::find(root.join("src").join("net")).unwrap();
+        assert_eq!(project.root, root);
+        assert_eq!(project.entry(), vec!["main"]);
+        let modules = project.modules.iter().map(|module| module.module.join(".")).collect::<Vec<_>>();
+        assert_eq!(modules, vec!["main", "net", "net.http"]);
+        assert_eq!(project.modules[2].path, root.join("src").join("net").join("http.tim"));
+
+        let states = project.sources().unwrap().into_iter().map(State::new).collect::<Vec<_>>();
+        let files = states.iter().map(|state| process_code(state).unwrap().into()).collect::<Vec<_>>();
+        let context = process_ast(files).unwrap();
+        assert!(context.modules.contains_key("net.http"));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn duplicate_modules() {
+        let root = project("duplicate", &[
+            ("timu.toml", "[project]\nname = \"web\""),
+            ("src/main.tim", ""),
+            ("src/net/http.tim", ""),
+            ("src/net.http.tim", ""),
+        ]);
+
+        match Project::load(&root) {
+            Err(ProjectError::DuplicateModule { name, first, second }) => {
+                assert_eq!(name, "net.http");
+                assert_eq!(first, root.join("src").join("net").join("http.tim"));
+                assert_eq!(second, root.join("src").join("net.http.tim"));
+            },
+            result => panic!("{result:?}"),
+        };
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn invalid_projects() {
+        let root = project("invalid", &[("timu.toml", "[project]\nname = \"web\"\nentry = \"app\""), ("src/main.tim", "")]);
+        assert!(matches!(Project::load(&root), Err(ProjectError::EntryNotFound { entry, .. }) if entry == "app"));
+
+        std::fs::write(root.join("src").join("my-app.tim"), "").unwrap();
+        assert!(matches!(Project::load(&root), Err(ProjectError::InvalidModuleName { .. })));
+
+        std::fs::remove_file(root.join("timu.toml")).unwrap();
+        assert!(matches!(Project::load(&root), Err(ProjectError::ManifestNotFound(_))));
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,282 @@
+//! On-demand loading of modules that were not passed to the compiler.
+//!
+//! [`process_ast`](crate::process_ast) only knows the files it was given, so a
+//! `use` of any other module is an [`ImportNotFound`](crate::tir::error::ImportNotFound)
+//! error. With [`process_ast_with_loader`](crate::process_ast_with_loader) the
+//! compiler asks a [`ModuleProvider`] for every module a `use` statement refers
+//! to but that is not known yet. Found modules are parsed and registered while
+//! the imports are resolved, and their own imports are loaded the same way.
+//!
+//! # Providers
+//!
+//! - [`FileSystemProvider`]: Looks modules up below a list of search paths, and
+//!   `std` modules below an optional standard library root
+//! - [`MemoryProvider`]: Serves modules from sources held in memory, such as
+//!   unsaved editor buffers
+//! - [`LayeredProvider`]: Asks a list of providers in order, the first one that
+//!   knows a module wins
+//!
+//! # Module Paths
+//!
+//! A `use a.b.C;` statement names a module followed by an item, but the split
+//! between the two is not known before the module is found. The loader asks for
+//! the longest path first, `a.b.C`, then `a.b` and `a`, and stops at the first
+//! module that is found.
+//!
+//! # Usage
+//!
+//! ```ignore
+//! let provider = FileSystemProvider::new(vec!["src".into()]).with_std_root("/usr/lib/timu/std");
+//! let arena = SourceArena::default();
+//! let context = process_ast_with_loader(vec![main.into()], ModuleLoader::new(&provider, &arena))?;
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    io,
+    path::{Path, PathBuf},
+};
+
+use typed_arena::Arena;
+
+use crate::{error::TIMU_LANG_EXT, file::SourceFile, nom_tools::State};
+
+/// A source of modules that are loaded when they are imported
+pub trait ModuleProvider {
+    /// Returns the source of the module with the given path, such as
+    /// `["net", "http"]` for `net.http`, or `None` when the module is unknown
+    fn load(&self, path: &[String]) -> io::Result<Option<SourceFile>>;
+}
+
+/// Loads modules from `.tim` files below a list of directories
+///
+/// The module `net.http` is searched as `net/http.tim` in every search path, in
+/// order. Modules below `std` are searched in the standard library root
+/// instead, so `std.collections.list` is `<std root>/collections/list.tim`.
+#[derive(Debug, Clone, Default)]
+pub struct FileSystemProvider {
+    search_paths: Vec<PathBuf>,
+    std_root: Option<PathBuf>,
+}
+
+impl FileSystemProvider {
+    pub fn new(search_paths: Vec<PathBuf>) -> Self {
+        Self {
+            search_paths,
+            std_root: None,
+        }
+    }
+
+    /// Sets the directory the modules below `std` are loaded from
+    pub fn with_std_root(mut self, std_root: impl Into<PathBuf>) -> Self {
+        self.std_root = Some(std_root.into());
+        self
+    }
+
+    pub fn search_paths(&self) -> &[PathBuf] {
+        &self.search_paths
+    }
+
+    fn read(directory: &Path, path: &[String], module: &[String]) -> io::Result<Option<SourceFile>> {
+        let file = directory.join(PathBuf::from_iter(path)).with_extension(TIMU_LANG_EXT);
+        match std::fs::read_to_string(&file) {
+            Ok(code) => Ok(Some(SourceFile::new(module.to_vec(), code))),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(io::Error::new(error.kind(), format!("{}: {error}", file.display()))),
+        }
+    }
+}
+
+impl ModuleProvider for FileSystemProvider {
+    fn load(&self, path: &[String]) -> io::Result<Option<SourceFile>> {
+        if let (Some(std_root), Some((first, rest))) = (&self.std_root, path.split_first())
+            && first == "std" {
+            return match rest.is_empty() {
+                true => Ok(None),
+                false => Self::read(std_root, rest, path),
+            };
+        }
+
+        for search_path in self.search_paths.iter() {
+            if let Some(file) = Self::read(search_path, path, path)? {
+                return Ok(Some(file));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Serves modules from sources held in memory
+#[derive(Debug, Clone, Default)]
+pub struct MemoryProvider {
+    modules: HashMap<String, String>,
+}
+
+impl MemoryProvider {
+    /// Adds or replaces the source of a module, `path` is the dotted module path
+    pub fn insert(&mut self, path: impl Into<String>, code: impl Into<String>) {
+        self.modules.insert(path.into(), code.into());
+    }
+
+    pub fn remove(&mut self, path: &str) -> Option<String> {
+        self.modules.remove(path)
+    }
+}
+
+impl ModuleProvider for MemoryProvider {
+    fn load(&self, path: &[String]) -> io::Result<Option<SourceFile>> {
+        Ok(self.modules.get(&path.join(".")).map(|code| SourceFile::new(path.to_vec(), code.clone())))
+    }
+}
+
+/// Asks a list of providers in order, the first one that knows a module wins
+///
+/// Putting a [`MemoryProvider`] in front of a [`FileSystemProvider`] lets
+/// unsaved buffers shadow the files on disk.
+#[derive(Default)]
+pub struct LayeredProvider<'a> {
+    layers: Vec<&'a dyn ModuleProvider>,
+}
+
+impl<'a> LayeredProvider<'a> {
+    pub fn new(layers: Vec<&'a dyn ModuleProvider>) -> Self {
+        Self { layers }
+    }
+
+    /// Adds a provider that is asked after the existing ones
+    pub fn push(&mut self, layer: &'a dyn ModuleProvider) {
+        self.layers.push(layer);
+    }
+}
+
+impl ModuleProvider for LayeredProvider<'_> {
+    fn load(&self, path: &[String]) -> io::Result<Option<SourceFile>> {
+        for layer in self.layers.iter() {
+            if let Some(file) = layer.load(path)? {
+                return Ok(Some(file));
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Storage for the parser states of loaded modules
+///
+/// Syntax trees borrow from the state they were parsed from, so the states of
+/// modules loaded during type checking need to live as long as the
+/// [`TirContext`](crate::tir::TirContext). The arena is created by the caller
+/// next to the states of the files passed in.
+#[derive(Default)]
+pub struct SourceArena {
+    states: Arena<State>,
+}
+
+impl SourceArena {
+    pub fn alloc(&self, file: SourceFile) -> &State {
+        self.states.alloc(State::new(file))
+    }
+}
+
+/// Connects the type checker to a provider and the arena loaded modules are stored in
+#[derive(Clone, Copy)]
+pub struct ModuleLoader<'base> {
+    pub provider: &'base dyn ModuleProvider,
+    pub arena: &'base SourceArena,
+}
+
+impl<'base> ModuleLoader<'base> {
+    pub fn new(provider: &'base dyn ModuleProvider, arena: &'base SourceArena) -> Self {
+        Self { provider, arena }
+    }
+}
+
+impl Debug for ModuleLoader<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ModuleLoader").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::{file::SourceFile, nom_tools::State, process_ast_with_loader, process_code, tir::TirError};
+
+    use super::{FileSystemProvider, LayeredProvider, MemoryProvider, ModuleLoader, ModuleProvider, SourceArena};
+
+    fn path(module: &str) -> Vec<String> {
+        module.split('.').map(str::to_string).collect()
+    }
+
+    fn directory(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("timu-provider-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+
+        for (file, code) in files.iter() {
+            let file = root.join(file);
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(file, code).unwrap();
+        }
+
+        root
+    }
+
+    fn check(code: &str, provider: &dyn ModuleProvider) -> Result<Vec<String>, TirError> {
+        let state = State::new(SourceFile::new(vec!["main".into()], code.to_string()));
+        let arena = SourceArena::default();
+        let context = process_ast_with_loader(vec![process_code(&state)?.into()], ModuleLoader::new(provider, &arena))?;
+        Ok(context.modules.keys().filter(|name| !name.starts_with("std")).map(|name| name.to_string()).collect())
+    }
+
+    #[test]
+    fn file_system() {
+        let root = directory("file-system", &[
+            ("first/net/http.tim", "use net.url.parse; pub func status(): i32 { return parse(); }"),
+            ("second/net/url.tim", "pub func parse(): i32 { return 1; }"),
+            ("second/net/http.tim", "this file is shadowed by the first search path"),
+            ("std/collections.tim", "pub class List {}"),
+        ]);
+
+        let provider = FileSystemProvider::new(vec![root.join("first"), root.join("second")]).with_std_root(root.join("std"));
+        assert_eq!(provider.load(&path("net.http")).unwrap().unwrap().code.as_str(), "use net.url.parse; pub func status(): i32 { return parse(); }");
+        assert!(provider.load(&path("net.missing")).unwrap().is_none());
+        assert!(provider.load(&path("std.collections")).unwrap().is_some());
+        assert!(provider.load(&path("collections")).unwrap().is_none());
+
+        let modules = check("use net.http.status; use std.collections.List; pub func main(): i32 { return status(); }", &provider).unwrap();
+        assert_eq!(modules, vec!["main", "net", "net.http", "net.url"]);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn layered() {
+        let mut memory = MemoryProvider::default();
+        memory.insert("lib", "pub class Shadowed {}");
+        let mut fallback = MemoryProvider::default();
+        fallback.insert("lib", "pub class Original {}");
+        fallback.insert("other", "pub class Other {}");
+
+        let provider = LayeredProvider::new(vec![&memory, &fallback]);
+        assert!(check("use lib.Shadowed; use other.Other;", &provider).is_ok());
+        assert!(matches!(check("use lib.Original;", &provider), Err(TirError::ImportNotFound(_))));
+
+        memory.remove("lib");
+        let provider = LayeredProvider::new(vec![&memory, &fallback]);
+        assert!(check("use lib.Original;", &provider).is_ok());
+    }
+
+    #[test]
+    fn loaded_modules_are_checked() {
+        let mut provider = MemoryProvider::default();
+        provider.insert("lib", "pub func broken(): i32 { return true; }");
+        assert!(check("use lib.broken;", &provider).is_err());
+
+        provider.insert("lib", "pub func broken(: i32 {}");
+        assert!(matches!(check("use lib.broken;", &provider), Err(TirError::SyntaxError(_))));
+
+        assert!(matches!(check("use missing.Type;", &provider), Err(TirError::ImportNotFound(_))));
+    }
+}
@@ -1,3 +1,34 @@
+#[allow(clippy::needless_borrows_for_generic_args, clippy::useless_conversion)]
 mod lang_files;
 mod parser;
 mod tir;
+
+use crate::{file::SourceFile, nom_tools::State, process_code, tir::{TirContext, TirError}};
+
+/// Builds `code` as the only module of the program
+pub(crate) fn build(code: &str) -> Result<(), TirError> {
+    build_with(code, |_| ())
+}
+
+/// Builds `code` as the only module of the program and inspects the resulting context
+pub(crate) fn build_with<T>(code: &str, inspect: impl FnOnce(&TirContext<'_>) -> T) -> Result<T, TirError> {
+    let state = State::new(SourceFile::new(vec!["source".into()], code.to_string()));
+    let ast = process_code(&state)?;
+    let context = crate::tir::build(vec![ast.into()])?;
+    Ok(inspect(&context))
+}
+
+/// Builds the given modules, each one named by its dotted path like `lib.sub`
+pub(crate) fn build_modules(modules: &[(&str, &str)]) -> Result<(), TirError> {
+    build_modules_with(modules, |_| ())
+}
+
+/// Builds the given modules like [`build_modules`] and inspects the resulting context
+pub(crate) fn build_modules_with<T>(modules: &[(&str, &str)], inspect: impl FnOnce(&TirContext<'_>) -> T) -> Result<T, TirError> {
+    let states = modules.iter()
+        .map(|(path, code)| State::new(SourceFile::new(path.split('.').map(|part| part.to_string()).collect(), code.to_string())))
+        .collect::<Vec<_>>();
+    let files = states.iter().map(|state| process_code(state).map(|ast| ast.into())).collect::<Result<Vec<_>, _>>()?;
+    let context = crate::tir::build(files)?;
+    Ok(inspect(&context))
+}
@@ -0,0 +1,51 @@
+//! Tests for the attributes in front of top-level statements
+
+use pretty_assertions::assert_eq;
+use rstest::*;
+
+use crate::{file::SourceFile, nom_tools::State};
+
+fn state(code: &str) -> State {
+    State {
+        file: SourceFile::new(vec!["<memory>".into()], code.to_string()),
+        indexer: Default::default(),
+    }
+}
+
+#[rstest]
+#[case("@allow(unused_imports)\nuse a.b;", &["@allow(unused_imports)"], "use a.b;")]
+#[case("@allow(unused_variables, unused_functions) func test(): i32 {}", &["@allow(unused_variables, unused_functions)"], "func test(): i32 {}")]
+#[case("@allow()\nclass Myclass {}", &["@allow()"], "class Myclass {}")]
+#[case("@allow(a) // comment\n@allow( b )\npub class Myclass {}", &["@allow(a)", "@allow(b)"], "pub class Myclass {}")]
+fn attribute_test(#[case] code: &str, #[case] attributes: &[&str], #[case] statement: &str) {
+    let state = state(code);
+    let (_, file) = crate::parser::parse(&state).unwrap();
+
+    assert_eq!(file.attributes.iter().map(|attribute| attribute.to_string()).collect::<Vec<_>>(), attributes);
+    assert_eq!(file.to_string(), statement);
+
+    for attribute in file.attributes.iter() {
+        assert_eq!(&code[attribute.target.clone()], statement);
+        assert!(code[attribute.position.clone()].starts_with('@'));
+    }
+}
+
+#[test]
+fn attribute_targets() {
+    let code = "class A {}\n@allow(unused_functions)\nfunc test(): i32 {}\nclass B {}";
+    let state = state(code);
+    let (_, file) = crate::parser::parse(&state).unwrap();
+
+    assert_eq!(file.attributes.len(), 1);
+    assert_eq!(&code[file.attributes[0].target.clone()], "func test(): i32 {}");
+}
+
+#[rstest]
+#[case("@allow(unused_imports)")]
+#[case("@allow(unused_imports")]
+#[case("@allow unused_imports\nuse a;")]
+#[case("@(unused_imports)\nuse a;")]
+fn invalid_attribute_test(#[case] code: &str) {
+    let state = state(code);
+    assert!(crate::parser::parse(&state).is_err(), "{}", code);
+}
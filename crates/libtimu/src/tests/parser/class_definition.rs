@@ -9,6 +9,8 @@ use crate::{file::SourceFile, nom_tools::State};
 #[case("    class     Myclass    \r\n\t{\r\n\t} ", "class Myclass {}")]
 #[case("class ___MyType___ {}", "class ___MyType___ {}")]
 #[case("class Myclass { a: string; }", "class Myclass {a: string;}")]
+#[case("class Child : Base {}", "class Child: Base {}")]
+#[case("class Child:base.Base{ a: string; }", "class Child: base.Base {a: string;}")]
 #[case("class Myclass { \r\n\ta\r\n\t: \r\n\tstring ;\r\n\t}", "class Myclass {a: string;}")]
 #[case("class Myclass { \r\n\t\r\n\t\r\n\t\r\n\t}", "class Myclass {}")]
 #[case("class Myclass { pub a: string; }", "class Myclass {pub a: string;}")]
@@ -16,6 +18,7 @@ use crate::{file::SourceFile, nom_tools::State};
 #[case("class Myclass { a: ?string; }", "class Myclass {a: ?string;}")]
 #[case("class Myclass { a: ?string.base; }", "class Myclass {a: ?string.base;}")]
 #[case("class Myclass { a: string; b: string; }", "class Myclass {a: string;b: string;}")]
+#[case("class Myclass { static a: string; pub  static\tb: string; static_c: i32; }", "class Myclass {static a: string;pub static b: string;static_c: i32;}")]
 #[case("class Myclass { func init(): MyType {} }", "class Myclass {func init(): MyType {}}")]
 #[case("class Myclass { func init(): MyType {} func init(): MyType {} }", "class Myclass {func init(): MyType {}func init(): MyType {}}")]
 #[case(
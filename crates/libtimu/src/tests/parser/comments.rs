@@ -0,0 +1,47 @@
+//! Tests for comments between tokens
+//!
+//! Comments are skipped wherever whitespace is allowed, so they never show up
+//! in the syntax tree.
+
+use pretty_assertions::assert_eq;
+use rstest::*;
+
+use crate::{file::SourceFile, nom_tools::State};
+
+#[rstest]
+#[case("// header\nclass Myclass {}", "class Myclass {}")]
+#[case("/* header */ class Myclass {}", "class Myclass {}")]
+#[case("class Myclass { // trailing\n}", "class Myclass {}")]
+#[case("class Myclass {} // end of file", "class Myclass {}")]
+#[case("class /* name */ Myclass { a /* field */ : string; }", "class Myclass {a: string;}")]
+#[case("/* multi\n   line\n*/\nfunc test(): i32 { return 1; }", "func test(): i32 {return 1;}")]
+#[case("func test(): i32 {\n    // before\n    var a = 1; // after\n    return a;\n}", "func test(): i32 {var a = 1; return a;}")]
+#[case("func test(): i32 { return 4 / /* divided */ 2; }", "func test(): i32 {return (4 / 2);}")]
+#[case("func test(): i32 { return 4 // not a division\n / 2; }", "func test(): i32 {return (4 / 2);}")]
+#[case("func test(): string { return \"// not a comment\"; }", "func test(): string {return // not a comment;}")]
+#[case("use a.b; // imported\nuse c;", "use a.b;\nuse c;")]
+fn comment_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
+    let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+
+    let state = State {
+        file: source_file.clone(),
+        indexer: Default::default(),
+    };
+
+    let (_, response) = crate::parser::parse(&state).unwrap();
+    assert_eq!(response.to_string(), expected, "{}", code);
+}
+
+#[rstest]
+#[case("/* never closed\nclass Myclass {}")]
+#[case("class Myclass { /* never closed }")]
+fn unclosed_block_comment_test(#[case] code: &str) {
+    let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+
+    let state = State {
+        file: source_file.clone(),
+        indexer: Default::default(),
+    };
+
+    assert!(crate::parser::parse(&state).is_err(), "{}", code);
+}
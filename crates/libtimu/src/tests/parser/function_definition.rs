@@ -25,6 +25,10 @@ use crate::{file::SourceFile, nom_tools::State};
     "func init(this): string {if (true || false) {} else if false {} else if false {} else if false {} else {}}"
 )]
 #[case("func init(a: ref ?string): string {}", "func init(a: ref ?string): string {}")]
+#[case("const func square(a: i32): i32 { return a * a; }", "const func square(a: i32): i32 {return (a * a);}")]
+#[case("pub const   func square(): i32 {}", "pub const func square(): i32 {}")]
+#[case("pub native func print(text: string): void ;", "pub native func print(text: string): void;")]
+#[case("extern func puts(text: ref string): i32;", "extern func puts(text: ref string): i32;")]
 fn custom_function_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
     let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
 
@@ -24,6 +24,11 @@ use crate::{file::SourceFile, nom_tools::State};
     "interface Myinterface { a: ?string.base; func init(): MyType; func init(): MyType; }",
     "interface Myinterface {a: ?string.base;func init(): MyType;func init(): MyType;}"
 )]
+#[case("interface Myinterface { func init(this): i32 { return 1; } }", "interface Myinterface {func init(this): i32 {return 1;}}")]
+#[case(
+    "interface Myinterface { func size(this): i32; func init(this): i32 {} }",
+    "interface Myinterface {func size(this): i32;func init(this): i32 {}}"
+)]
 fn custom_interface_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
     let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
 
@@ -35,3 +40,13 @@ fn custom_interface_test<'base>(#[case] code: &'base str, #[case] expected: &'ba
     let (_, response) = crate::parser::parse(&state).finish().unwrap();
     assert_eq!(response.statements[0].to_string(), expected, "{}", code);
 }
+
+#[rstest]
+#[case("interface Myinterface { func init(): MyType }")]
+#[case("interface Myinterface { func init(): MyType { }")]
+#[case("interface Myinterface { static a: i32; }")]
+#[case("extend Myclass: Myinterface { static a: i32; }")]
+fn invalid_interface_test(#[case] code: &str) {
+    let state = State::new(SourceFile::new(vec!["<memory>".into()], code.to_string()));
+    assert!(crate::parser::parse(&state).finish().is_err(), "{}", code);
+}
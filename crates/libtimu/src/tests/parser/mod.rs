@@ -3,7 +3,12 @@ mod interface_definition;
 mod function_definition;
 mod primitive;
 mod variable_assign;
+#[allow(clippy::collapsible_if, clippy::collapsible_match)]
 mod expression;
 mod module_use;
+#[allow(clippy::assertions_on_constants)]
 mod type_info;
+#[allow(clippy::assertions_on_constants)]
 mod edge_cases;
+mod comments;
+mod attributes;
@@ -7,7 +7,7 @@ use pretty_assertions::assert_eq;
 use rstest::*;
 
 use crate::{
-    ast::UseAst,
+    ast::{UseAst, UseKindAst},
     file::SourceFile,
     nom_tools::{NomSpan, State},
 };
@@ -85,11 +85,48 @@ fn test_various_use_patterns() {
     }
 }
 
+#[test]
+fn test_grouped_use_statement() {
+    let code = "pub use module.{Class as Alias, nested.Other};";
+    let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+    let state = State::new(source_file);
+
+    let input = NomSpan::new_extra(code, state);
+    let (_, use_stmt) = UseAst::parse(input).unwrap();
+
+    assert!(use_stmt.is_public.is_some());
+    assert_eq!(use_stmt.import.text.as_ref(), "module");
+    let items = use_stmt.items().collect::<Vec<_>>();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].import.text.as_ref(), "module.Class");
+    assert_eq!(items[0].alias.as_ref().map(|alias| alias.text), Some("Alias"));
+    assert_eq!(items[1].import.text.as_ref(), "module.nested.Other");
+    assert_eq!(items[1].ast_name().text, "Other");
+}
+
+#[test]
+fn test_wildcard_use_statement() {
+    let code = "use module.nested.*;";
+    let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
+    let state = State::new(source_file);
+
+    let input = NomSpan::new_extra(code, state);
+    let (_, use_stmt) = UseAst::parse(input).unwrap();
+
+    assert_eq!(use_stmt.import.text.as_ref(), "module.nested");
+    assert!(matches!(use_stmt.kind, UseKindAst::Wildcard(ref wildcard) if wildcard.text == "*"));
+    assert_eq!(use_stmt.items().count(), 0);
+}
+
 #[rstest]
 #[case("use;")]
 #[case("use ;")]
 #[case("use module")]
 #[case("invalid syntax")]
+#[case("use module.{};")]
+#[case("use module.{Class")]
+#[case("use module.*.Class;")]
+#[case("pub module.Class;")]
 fn test_invalid_use_statements(#[case] code: &str) {
     let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
     let state = State {
@@ -18,6 +18,8 @@ use crate::{file::SourceFile, nom_tools::State};
 #[case("a = 1.2;", "a = 1.2;")]
 #[case("a = -1.2;", "a = -1.2;")]
 #[case("a = b(-1.2);", "a = b(-1.2);")]
+#[case("Counter.count = 1;", "Counter.count = 1;")]
+#[case("this . a.b = 1;", "this.a.b = 1;")]
 fn custom_variable_test<'base>(#[case] code: &'base str, #[case] expected: &'base str) {
     let source_file = SourceFile::new(vec!["<memory>".into()], code.to_string());
 
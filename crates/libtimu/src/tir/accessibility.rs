@@ -15,6 +15,8 @@
 //! - **Interfaces**: Always public (they define contracts)
 //! - **Modules**: Always accessible for import
 //! - **Extensions**: Cannot be imported directly
+//! - **Type aliases**: Like classes, importable when marked `pub`
+//! - **Module variables**: Constants and globals, importable when marked `pub`
 //!
 //! # Usage
 //!
@@ -40,7 +42,10 @@
 
 use std::ops::Range;
 
+use libtimu_macros_core::traits::Suggestion;
+
 use crate::{
+    ast::VariableDefinitionType,
     file::SourceFile, 
     nom_tools::{Span, ToRange}, 
     tir::{
@@ -112,6 +117,18 @@ pub fn check_import_accessibility<'base>(
             // Extensions cannot be imported directly
             false
         },
+        AstSignatureValue::TypeAlias(type_alias_ast) => {
+            // Type aliases are accessible if they are marked as public
+            type_alias_ast.is_public.is_some()
+        },
+        AstSignatureValue::Enum(enum_ast) => {
+            // Enums are accessible if they are marked as public
+            enum_ast.is_public.is_some()
+        },
+        AstSignatureValue::Variable(variable_ast) => {
+            // Module constants and globals are accessible if they are marked as public
+            variable_ast.is_public.is_some()
+        },
     };
     
     // If the item is not accessible, return an accessibility violation error
@@ -124,7 +141,8 @@ pub fn check_import_accessibility<'base>(
             import_span.to_range(),
             import_source.clone(),
             item_position,
-            item_source
+            item_source,
+            public_suggestion(ast_signature.value.as_ref())
         ));
     }
 
@@ -166,9 +184,64 @@ fn get_item_location_info<'base>(ast_value: &AstSignatureValue<'base>) -> (Range
         AstSignatureValue::Extend(extend_definition_ast) => {
             (extend_definition_ast.name.to_range(), extend_definition_ast.name.names.last().unwrap().state.file.clone())
         },
+        AstSignatureValue::TypeAlias(type_alias_ast) => {
+            (type_alias_ast.name.to_range(), type_alias_ast.name.state.file.clone())
+        },
+        AstSignatureValue::Enum(enum_ast) => {
+            (enum_ast.name.to_range(), enum_ast.name.state.file.clone())
+        },
+        AstSignatureValue::Variable(variable_ast) => {
+            (variable_ast.definition.name.to_range(), variable_ast.definition.name.state.file.clone())
+        },
     }
 }
 
+/// Builds the edit that marks a private class, function, type alias, enum or module variable as `pub`.
+///
+/// The modifier goes in front of the definition, before the `const`, `native`
+/// or `extern` modifier of a function. Other items cannot be made importable.
+///
+/// # Arguments
+///
+/// * `ast_value` - The AST signature value of the private item
+///
+/// # Returns
+///
+/// The suggestion inserting `pub `, or `None` if the item cannot be made importable
+fn public_suggestion(ast_value: &AstSignatureValue<'_>) -> Option<Suggestion> {
+    let (keyword, name, modifier) = match ast_value {
+        AstSignatureValue::Class(class_definition_ast) => ("class", &class_definition_ast.name, None),
+        AstSignatureValue::Function(function_definition_ast) => {
+            let modifier = function_definition_ast.is_const.as_ref()
+                .or(function_definition_ast.is_native.as_ref())
+                .or(function_definition_ast.is_extern.as_ref());
+            ("func", &function_definition_ast.name, modifier)
+        },
+        AstSignatureValue::TypeAlias(type_alias_ast) => ("type", &type_alias_ast.name, None),
+        AstSignatureValue::Enum(enum_ast) => ("enum", &enum_ast.name, None),
+        AstSignatureValue::Variable(variable_ast) => {
+            let keyword = match variable_ast.definition.variable_definition_type {
+                VariableDefinitionType::Const => "const",
+                VariableDefinitionType::Var => "var",
+            };
+            (keyword, &variable_ast.definition.name, None)
+        },
+        _ => return None,
+    };
+
+    let position = match modifier {
+        Some(modifier) => modifier.position.start,
+        None => name.state.file.code()[..name.position.start].trim_end().strip_suffix(keyword)?.len(),
+    };
+
+    Some(Suggestion {
+        message: format!("make `{}` public", name.text),
+        code: (&name.state.file).into(),
+        range: position..position,
+        replacement: "pub ".to_string(),
+    })
+}
+
 /// Checks if an individual AST definition has public accessibility.
 ///
 /// This is a convenience function for checking the accessibility of specific
@@ -188,6 +261,9 @@ pub fn is_definition_public<'base>(definition: &AstSignatureValue<'base>) -> boo
         AstSignatureValue::Function(function) => function.is_public.is_some(), 
         AstSignatureValue::Interface(_) => true,
         AstSignatureValue::Extend(_) => false,
+        AstSignatureValue::TypeAlias(alias) => alias.is_public.is_some(),
+        AstSignatureValue::Enum(definition) => definition.is_public.is_some(),
+        AstSignatureValue::Variable(variable) => variable.is_public.is_some(),
     }
 }
 
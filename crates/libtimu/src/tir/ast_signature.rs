@@ -22,6 +22,7 @@
 //! - **Functions** - Callable procedures with parameters and return types
 //! - **Interfaces** - Abstract type contracts
 //! - **Extensions** - Type augmentations that add methods to existing types
+//! - **Type aliases** - Alternative names for existing types
 //!
 //! # Module Building Process
 //!
@@ -54,7 +55,7 @@ use simplelog::debug;
 use strum_macros::EnumDiscriminants;
 
 use crate::{
-    ast::{ClassDefinitionAst, ExtendDefinitionAst, FileAst, FunctionDefinitionAst, InterfaceDefinitionAst}, map::TimuHashMap, nom_tools::ToRange, tir::{scope::AstVariableInformation, TypeSignature, TypeValue}
+    ast::{ClassDefinitionAst, EnumDefinitionAst, ExtendDefinitionAst, FileAst, FunctionDefinitionAst, InterfaceDefinitionAst, ModuleVariableAst, TypeAliasAst, UseKindAst}, map::TimuHashMap, nom_tools::ToRange, tir::{scope::AstVariableInformation, TypeSignature, TypeValue}
 };
 
 use super::{
@@ -74,6 +75,9 @@ use super::{
 /// - **Function** - A callable procedure with parameters and return type
 /// - **Interface** - An abstract type contract defining required methods
 /// - **Extend** - A type extension that adds methods to existing types
+/// - **TypeAlias** - Another name for an existing type
+/// - **Enum** - A type whose values are one of its variants
+/// - **Variable** - A module-level constant or global variable
 /// 
 /// # Usage
 /// 
@@ -93,6 +97,11 @@ pub enum AstSignatureValue<'base> {
     Interface(#[allow(dead_code)] Rc<InterfaceDefinitionAst<'base>>),
     /// An extension definition adding methods to existing types
     Extend(#[allow(dead_code)] Rc<ExtendDefinitionAst<'base>>),
+    TypeAlias(#[allow(dead_code)] Rc<TypeAliasAst<'base>>),
+    /// An enum definition with its variants
+    Enum(#[allow(dead_code)] Rc<EnumDefinitionAst<'base>>),
+    /// A module-level constant or global variable
+    Variable(#[allow(dead_code)] Rc<ModuleVariableAst<'base>>),
 }
 
 impl<'base> AsRef<AstSignatureValue<'base>> for AstSignatureValue<'base> {
@@ -129,6 +138,9 @@ impl<'base> ResolveAst<'base> for AstSignatureValue<'base> {
             AstSignatureValue::Function(function) => function.resolve(context, scope_location),
             AstSignatureValue::Interface(interface) => interface.resolve(context, scope_location),
             AstSignatureValue::Extend(extend) => extend.resolve(context, scope_location),
+            AstSignatureValue::TypeAlias(alias) => alias.resolve(context, scope_location),
+            AstSignatureValue::Enum(definition) => definition.resolve(context, scope_location),
+            AstSignatureValue::Variable(variable) => variable.resolve(context, scope_location),
         }
     }
 
@@ -161,6 +173,9 @@ impl<'base> ResolveAst<'base> for AstSignatureValue<'base> {
             AstSignatureValue::Function(function) => function.name(),
             AstSignatureValue::Interface(interface) => interface.name(),
             AstSignatureValue::Extend(extend) => extend.name(),
+            AstSignatureValue::TypeAlias(alias) => alias.name(),
+            AstSignatureValue::Enum(definition) => definition.name(),
+            AstSignatureValue::Variable(variable) => variable.name(),
         }
     }
 }
@@ -282,6 +297,9 @@ pub fn build_module<'base>(context: &mut TirContext<'base>, ast: Rc<FileAst<'bas
 /// 2. **Extensions** - Type augmentations that modify existing types
 /// 3. **Classes** - Object-oriented type definitions
 /// 4. **Functions** - Callable procedures and methods
+/// 5. **Type aliases** - Alternative names, resolved to the aliased type
+/// 6. **Re-exports** - `pub use` statements, registered in the re-export tables
+///    of the context
 /// 
 /// # Arguments
 /// * `context` - The TIR context to register signatures in
@@ -337,6 +355,44 @@ pub fn build_module_signature<'base>(context: &mut TirContext<'base>, mut module
 
             ast_signature.validate_insert(SignaturePath::borrowed(func.name.text), variable)?;
         }
+
+        // Type alias signatures
+        for alias in ast.get_type_aliases() {
+            let signature = Signature::from((alias.clone(), module.get_ref()));
+            let location = context.add_ast_signature(format!("{}.{}", module.path.clone(), alias.name.text).into(), signature)?;
+            let variable = AstVariableInformation::basic(alias.name.clone(), location);
+
+            ast_signature.validate_insert(SignaturePath::borrowed(alias.name.text), variable)?;
+        }
+
+        // Enum signatures
+        for definition in ast.get_enums() {
+            let signature = Signature::from((definition.clone(), module.get_ref()));
+            let location = context.add_ast_signature(format!("{}.{}", module.path.clone(), definition.name.text).into(), signature)?;
+            let variable = AstVariableInformation::basic(definition.name.clone(), location);
+
+            ast_signature.validate_insert(SignaturePath::borrowed(definition.name.text), variable)?;
+        }
+
+        // Module constant and global variable signatures
+        for variable in ast.get_variables() {
+            let name = variable.definition.name.clone();
+            let signature = Signature::from((variable, module.get_ref()));
+            let location = context.add_ast_signature(format!("{}.{}", module.path.clone(), name.text).into(), signature)?;
+
+            ast_signature.validate_insert(SignaturePath::borrowed(name.text), AstVariableInformation::basic(name, location))?;
+        }
+
+        // Re-exports, resolved when the re-exported path is looked up
+        for import in ast.get_uses().filter(|import| import.is_public.is_some()) {
+            match &import.kind {
+                UseKindAst::Wildcard(_) => context.wildcard_reexports.entry(module_name.clone()).or_default().push(import.import.text.to_string()),
+                _ => for item in import.items() {
+                    let name = item.alias.as_ref().unwrap_or_else(|| item.import.paths.last().unwrap()).text;
+                    context.reexports.insert(format!("{module_name}.{name}"), item.import.text.to_string());
+                }
+            }
+        }
     }
 
     module.ast_signatures = ast_signature;
@@ -451,3 +507,61 @@ impl<'base> From<(Rc<ExtendDefinitionAst<'base>>, ModuleRef<'base>)> for Signatu
         Signature::new_with_extra(AstSignatureValue::Extend(extend), file, position, module)
     }
 }
+
+impl<'base> From<(Rc<TypeAliasAst<'base>>, ModuleRef<'base>)> for Signature<AstSignatureValue<'base>, ModuleRef<'base>> {
+    /// Creates a signature for a type alias within a module
+    /// 
+    /// The source location is the alias name, so errors about the alias point
+    /// at its definition.
+    /// 
+    /// # Arguments
+    /// * `value` - A tuple of (type alias AST, module reference)
+    /// 
+    /// # Returns
+    /// A signature containing the type alias information and source location
+    fn from(value: (Rc<TypeAliasAst<'base>>, ModuleRef<'base>)) -> Self {
+        let (alias, module) = value;
+
+        let position = alias.name.to_range();
+        let file = alias.name.state.file.clone();
+        Signature::new_with_extra(AstSignatureValue::TypeAlias(alias), file, position, module)
+    }
+}
+
+impl<'base> From<(Rc<EnumDefinitionAst<'base>>, ModuleRef<'base>)> for Signature<AstSignatureValue<'base>, ModuleRef<'base>> {
+    /// Creates a signature for an enum within a module
+    /// 
+    /// The source location is the enum name, like for classes.
+    /// 
+    /// # Arguments
+    /// * `value` - A tuple of (enum AST, module reference)
+    /// 
+    /// # Returns
+    /// A signature containing the enum information and source location
+    fn from(value: (Rc<EnumDefinitionAst<'base>>, ModuleRef<'base>)) -> Self {
+        let (definition, module) = value;
+
+        let position = definition.name.to_range();
+        let file = definition.name.state.file.clone();
+        Signature::new_with_extra(AstSignatureValue::Enum(definition), file, position, module)
+    }
+}
+
+impl<'base> From<(Rc<ModuleVariableAst<'base>>, ModuleRef<'base>)> for Signature<AstSignatureValue<'base>, ModuleRef<'base>> {
+    /// Creates a signature for a module-level constant or global variable
+    /// 
+    /// The source location is the variable name, like for type aliases.
+    /// 
+    /// # Arguments
+    /// * `value` - A tuple of (module variable AST, module reference)
+    /// 
+    /// # Returns
+    /// A signature containing the variable information and source location
+    fn from(value: (Rc<ModuleVariableAst<'base>>, ModuleRef<'base>)) -> Self {
+        let (variable, module) = value;
+
+        let position = variable.definition.name.to_range();
+        let file = variable.definition.name.state.file.clone();
+        Signature::new_with_extra(AstSignatureValue::Variable(variable), file, position, module)
+    }
+}
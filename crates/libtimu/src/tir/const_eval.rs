@@ -0,0 +1,1086 @@
+//! Compile-time evaluation of `const func` functions.
+//!
+//! A function marked with `const` can be evaluated while the program is
+//! compiled. Every call to a const function whose arguments are constant is
+//! interpreted after type checking and its result is recorded in
+//! [`TirContext::const_values`], so backends emit the value instead of a call:
+//!
+//! ```timu
+//! const func square(value: i32): i32 { return value * value; }
+//!
+//! pub func area(): i32 {
+//!     const side = square(4);      // evaluated at compile time, side is 16
+//!     return square(side) + 1;     // square(side) is 256 as well
+//! }
+//! ```
+//!
+//! An argument is constant when it is a literal, an operation on constants,
+//! a `const` variable initialised with a constant, or another const call with
+//! constant arguments.
+//!
+//! # Module Variables
+//!
+//! The initial values of module-level constants and global variables have to
+//! be constant, they are evaluated before the functions and recorded in
+//! [`TirContext::global_values`]:
+//!
+//! ```timu
+//! const SIDE: i32 = 4;
+//! const AREA = square(SIDE);       // 16
+//! var counter: i32 = AREA + 1;     // starts at 17, changed at runtime
+//! ```
+//!
+//! A module constant is a constant everywhere, also in the arguments of const
+//! calls and in the bodies of const functions. The value of a global variable
+//! is only known at runtime.
+//!
+//! # Restrictions
+//!
+//! Const functions are checked before they are evaluated:
+//!
+//! - they cannot take `this`,
+//! - their parameters and return value have to be primitive types,
+//! - they can only call other const functions.
+//!
+//! # Evaluation
+//!
+//! Operations are evaluated in the same types and with the same overflow rules
+//! the backend uses, so a compile-time result is always identical to the
+//! runtime one. Operations that would trap at runtime, such as a division by
+//! zero, stop the compilation with a backtrace of the const calls that led to
+//! them.
+//!
+//! The interpreter is bounded by [`MAX_STEPS`] evaluated statements and
+//! expressions per call and [`MAX_CALL_DEPTH`] nested calls, so unbounded
+//! recursion is reported instead of hanging the compiler.
+
+use std::{borrow::Cow, collections::HashMap, ops::Range};
+
+use indexmap::IndexMap;
+use libtimu_macros::TimuError;
+use libtimu_macros_core::SourceCode;
+use strum_macros::{EnumDiscriminants, EnumProperty};
+
+use crate::{
+    ast::{BodyAst, BodyStatementAst, ExpressionAst, ExpressionOperatorType, FunctionArgumentAst, FunctionCallAst, MatchArmBodyAst, MatchAst, PrimitiveValue},
+    nom_tools::{Span, SpanInfo},
+    tir::{
+        context::SpanKey,
+        resolver::{function::FunctionDefinition, module_variable::GlobalVariable, statement::{default_literal_type, expression_position, get_primitive_location, get_primitive_type, is_integer, is_numeric, operand_type}, TypeLocation},
+        PrimitiveType, TirContext, TirError, TypeValue,
+    },
+};
+
+/// Largest number of statements and expressions evaluated for one compile-time call
+pub const MAX_STEPS: usize = 100_000;
+
+/// Deepest chain of nested const calls
+pub const MAX_CALL_DEPTH: usize = 64;
+
+/// Number of innermost calls shown in the backtrace of a failed evaluation
+const MAX_BACKTRACE_FRAMES: usize = 8;
+
+/// Help of a failure inside a call to a `const` function
+const CALL_HELP: &str = "the call is evaluated while compiling because all of its arguments are constant";
+/// Help of a failure in the initial value of a module variable itself
+const INITIALIZER_HELP: &str = "the initial value of a module variable is evaluated while compiling, change it so the evaluation succeeds";
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("Compile-time evaluation failed: {reason}")]
+#[diagnostic(code("T0032"))]
+pub struct ConstEvaluationFailed {
+    pub reason: String,
+
+    #[label("{reason}")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+
+    #[help]
+    pub advice: String,
+
+    #[errors]
+    pub backtrace: Vec<ConstCallFrame>,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("While evaluating `{name}`")]
+pub struct ConstCallFrame {
+    pub name: String,
+
+    #[label("`{name}` called here")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{name}` is not a const function")]
+#[diagnostic(code("T0033"), help("mark the function as `const func` or call it at runtime"))]
+pub struct NotConstFunction {
+    pub name: String,
+
+    #[label("const functions can only call const functions")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("Invalid const function")]
+#[diagnostic(code("T0034"), help("const functions only work on primitive values and cannot take `this`"))]
+pub struct InvalidConstFunction {
+    pub reason: &'static str,
+
+    #[label("{reason}")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("Initial value of `{name}` is not a compile-time constant")]
+#[diagnostic(code("T0042"), help("module variables can only be initialised with literals, module constants and const function calls"))]
+pub struct NotConstantInitializer {
+    pub name: String,
+    pub reason: String,
+
+    #[label("{reason}")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error, EnumDiscriminants, EnumProperty)]
+pub enum ConstEvalError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ConstEvaluationFailed(Box<ConstEvaluationFailed>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    NotConstFunction(Box<NotConstFunction>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    InvalidConstFunction(Box<InvalidConstFunction>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    NotConstantInitializer(Box<NotConstantInitializer>),
+}
+
+impl From<ConstEvalError> for TirError {
+    fn from(value: ConstEvalError) -> Self {
+        TirError::ConstEval(Box::new(value))
+    }
+}
+
+fn invalid_const_function(reason: &'static str, span: &Span<'_>) -> TirError {
+    ConstEvalError::InvalidConstFunction(InvalidConstFunction {
+        reason,
+        position: span.position.clone(),
+        code: (&span.state.file).into(),
+    }.into()).into()
+}
+
+/// Checks every const function and evaluates the const calls of the program
+///
+/// # Arguments
+/// * `context` - The type-checked program, results are stored in [`TirContext::const_values`]
+///
+/// # Errors
+/// Returns the first invalid const function or the first failed evaluation
+pub fn evaluate_const_calls(context: &mut TirContext<'_>) -> Result<(), TirError> {
+    let functions = context.types.iter()
+        .filter_map(|(_, _, signature)| match signature.value.as_ref() {
+            TypeValue::Function(function) => Some(function),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for function in functions.iter().filter(|function| function.ast.is_const.is_some()) {
+        check_const_function(context, function)?;
+    }
+
+    for global in context.globals.values() {
+        if let Some(expression) = global.definition.expression.as_ref() {
+            check_initializer(context, global, expression)?;
+        }
+    }
+
+    let mut evaluator = Evaluator {
+        context,
+        results: IndexMap::new(),
+        globals: IndexMap::new(),
+        steps: 0,
+        calls: Vec::new(),
+        initializer: None,
+    };
+
+    for key in context.globals.keys() {
+        evaluator.global(key)?;
+    }
+
+    for function in functions.iter() {
+        let mut locals = Locals::new(function, None);
+        evaluator.body(&mut locals, &function.ast.body)?;
+    }
+
+    let Evaluator { results, globals, .. } = evaluator;
+    context.const_values = results;
+    context.global_values = globals;
+    Ok(())
+}
+
+/// Module variable an identifier refers to
+fn global_target<'ctx, 'base>(context: &'ctx TirContext<'base>, ident: &Span<'_>) -> Option<(&'ctx SpanKey, &'ctx GlobalVariable<'base>)> {
+    let definition = context.identifiers.get(&SpanKey::from(ident))?.definition.as_ref()?;
+    context.globals.get_key_value(definition)
+}
+
+/// Checks that the initial value of a module variable only uses constants
+fn check_initializer(context: &TirContext<'_>, global: &GlobalVariable<'_>, expression: &ExpressionAst<'_>) -> Result<(), TirError> {
+    let not_constant = |reason: String, position: SpanInfo| -> TirError {
+        ConstEvalError::NotConstantInitializer(NotConstantInitializer {
+            name: global.definition.name.text.to_string(),
+            reason,
+            position: position.position,
+            code: (&position.file).into(),
+        }.into()).into()
+    };
+
+    match expression {
+        ExpressionAst::Primitive { .. } => Ok(()),
+        ExpressionAst::Ident(ident) => match global_target(context, ident) {
+            Some((_, target)) if target.is_const() => Ok(()),
+            Some(_) => Err(not_constant(format!("`{}` is a global variable, its value is only known at runtime", ident.text), ident.into())),
+            None => Err(not_constant(format!("`{}` is not a constant", ident.text), ident.into())),
+        },
+        ExpressionAst::Ref(_) => Err(not_constant("references are only known at runtime".to_string(), expression_position(expression))),
+        ExpressionAst::Lambda(_) => Err(not_constant("lambda expressions are only known at runtime".to_string(), expression_position(expression))),
+        ExpressionAst::Match(_) => Err(not_constant("match expressions are only known at runtime".to_string(), expression_position(expression))),
+        ExpressionAst::Tuple(_) => Err(not_constant("tuples are only known at runtime".to_string(), expression_position(expression))),
+        ExpressionAst::Not(inner) => check_initializer(context, global, inner),
+        ExpressionAst::Operation { left, right, .. } => {
+            check_initializer(context, global, left)?;
+            check_initializer(context, global, right)
+        },
+        ExpressionAst::FunctionCall(function_call) => {
+            for argument in function_call.arguments.iter() {
+                check_initializer(context, global, argument)?;
+            }
+
+            match call_target(context, function_call) {
+                Some(callee) if callee.ast.is_const.is_some() => Ok(()),
+                _ => Err(not_constant(format!("`{}` is not a const function", function_call.call_span.text), (&function_call.call_span).into())),
+            }
+        },
+    }
+}
+
+fn check_const_function(context: &TirContext<'_>, function: &FunctionDefinition<'_>) -> Result<(), TirError> {
+    let is_value = |location: TypeLocation| get_primitive_type(context, location).is_some_and(|primitive| primitive != PrimitiveType::Void);
+
+    for (argument, argument_ast) in function.arguments.iter().zip(function.ast.arguments.iter()) {
+        match argument_ast {
+            FunctionArgumentAst::This(this) => return Err(invalid_const_function("const functions cannot take `this`", this)),
+            FunctionArgumentAst::Argument { name, .. } if !is_value(argument.field_type) => return Err(invalid_const_function("parameters of const functions have to be primitive types", name)),
+            FunctionArgumentAst::Argument { .. } => (),
+        };
+    }
+
+    if !is_value(function.return_type) {
+        return Err(invalid_const_function("const functions have to return a primitive value", &function.name));
+    }
+
+    check_body_calls(context, &function.ast.body)
+}
+
+fn check_body_calls(context: &TirContext<'_>, body: &BodyAst<'_>) -> Result<(), TirError> {
+    for statement in body.statements.iter() {
+        match statement {
+            BodyStatementAst::VariableDefinition(definition) => if let Some(expression) = definition.expression.as_ref() {
+                check_expression_calls(context, expression)?;
+            },
+            BodyStatementAst::VariableAssign(assign) => check_expression_calls(context, &assign.expression)?,
+            BodyStatementAst::FunctionCall(function_call) => check_call(context, function_call)?,
+            BodyStatementAst::IfCondition(if_condition) => {
+                check_expression_calls(context, &if_condition.expression)?;
+                check_body_calls(context, &if_condition.true_body)?;
+
+                for (expression, body) in if_condition.else_ifs.iter() {
+                    check_expression_calls(context, expression)?;
+                    check_body_calls(context, body)?;
+                }
+
+                if let Some(false_body) = if_condition.false_body.as_ref() {
+                    check_body_calls(context, false_body)?;
+                }
+            },
+            BodyStatementAst::Match(match_ast) => check_match_calls(context, match_ast)?,
+            BodyStatementAst::Return(return_statement) => if let Some(expression) = return_statement.expression.as_ref() {
+                check_expression_calls(context, expression)?;
+            },
+        };
+    }
+
+    Ok(())
+}
+
+fn check_expression_calls(context: &TirContext<'_>, expression: &ExpressionAst<'_>) -> Result<(), TirError> {
+    match expression {
+        ExpressionAst::Not(inner) => check_expression_calls(context, inner),
+        ExpressionAst::Operation { left, right, .. } => {
+            check_expression_calls(context, left)?;
+            check_expression_calls(context, right)
+        },
+        ExpressionAst::FunctionCall(function_call) => check_call(context, function_call),
+        ExpressionAst::Match(match_ast) => check_match_calls(context, match_ast),
+        ExpressionAst::Tuple(tuple) => tuple.elements.iter().try_for_each(|element| check_expression_calls(context, element)),
+        _ => Ok(()),
+    }
+}
+
+fn check_match_calls(context: &TirContext<'_>, match_ast: &MatchAst<'_>) -> Result<(), TirError> {
+    check_expression_calls(context, &match_ast.expression)?;
+    for arm in match_ast.arms.iter() {
+        match &arm.body {
+            MatchArmBodyAst::Expression(expression) => check_expression_calls(context, expression)?,
+            MatchArmBodyAst::Block(body) => check_body_calls(context, body)?,
+        };
+    }
+
+    Ok(())
+}
+
+fn check_call(context: &TirContext<'_>, function_call: &FunctionCallAst<'_>) -> Result<(), TirError> {
+    for argument in function_call.arguments.iter() {
+        check_expression_calls(context, argument)?;
+    }
+
+    match call_target(context, function_call) {
+        Some(callee) if callee.ast.is_const.is_some() => Ok(()),
+        _ => Err(ConstEvalError::NotConstFunction(NotConstFunction {
+            name: function_call.call_span.text.to_string(),
+            position: function_call.call_span.position.clone(),
+            code: (&function_call.call_span.state.file).into(),
+        }.into()).into()),
+    }
+}
+
+fn call_target<'ctx, 'base>(context: &'ctx TirContext<'base>, function_call: &FunctionCallAst<'_>) -> Option<&'ctx FunctionDefinition<'base>> {
+    let location = context.call_targets.get(&SpanKey::from(&function_call.call_span))?;
+    match context.types.get_from_location(*location).map(|signature| signature.value.as_ref()) {
+        Some(TypeValue::Function(function)) => Some(function),
+        _ => None,
+    }
+}
+
+#[derive(Debug)]
+struct Variable<'base> {
+    location: Option<TypeLocation>,
+    value: Option<PrimitiveValue<'base>>,
+}
+
+/// Variables of the function being walked
+///
+/// Const calls are interpreted with every variable known. Other functions are
+/// only walked to find const calls, there only `const` variables have a value.
+#[derive(Debug)]
+struct Locals<'base> {
+    scopes: Vec<HashMap<&'base str, Variable<'base>>>,
+    return_type: TypeLocation,
+    interpreting: bool,
+}
+
+impl<'base> Locals<'base> {
+    fn new(function: &FunctionDefinition<'base>, arguments: Option<Vec<PrimitiveValue<'base>>>) -> Self {
+        let interpreting = arguments.is_some();
+        let mut values = arguments.unwrap_or_default().into_iter();
+        let mut scope = HashMap::new();
+
+        for (argument, argument_ast) in function.arguments.iter().zip(function.ast.arguments.iter()) {
+            let name = match argument_ast {
+                FunctionArgumentAst::This(_) => "this",
+                FunctionArgumentAst::Argument { name, .. } => name.text,
+            };
+            scope.insert(name, Variable { location: Some(argument.field_type), value: values.next() });
+        }
+
+        Self { scopes: vec![scope], return_type: function.return_type, interpreting }
+    }
+
+    /// Variables of the initial value of a module variable, it is always interpreted
+    fn global() -> Self {
+        Self { scopes: vec![HashMap::new()], return_type: TypeLocation::UNDEFINED, interpreting: true }
+    }
+
+    fn define(&mut self, name: &'base str, variable: Variable<'base>) {
+        self.scopes.last_mut().expect("Function scope missing, but this is a bug").insert(name, variable);
+    }
+
+    fn get(&self, name: &str) -> Option<&Variable<'base>> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    fn get_mut(&mut self, name: &str) -> Option<&mut Variable<'base>> {
+        self.scopes.iter_mut().rev().find_map(|scope| scope.get_mut(name))
+    }
+}
+
+struct Evaluator<'ctx, 'base> {
+    context: &'ctx TirContext<'base>,
+    results: IndexMap<SpanKey, PrimitiveValue<'base>>,
+    /// Initial values of the module variables evaluated so far
+    globals: IndexMap<SpanKey, PrimitiveValue<'base>>,
+    steps: usize,
+    calls: Vec<ConstCallFrame>,
+    /// Number of calls in progress when the initial value of a module variable started evaluating
+    initializer: Option<usize>,
+}
+
+impl<'ctx, 'base> Evaluator<'ctx, 'base> {
+    /// Builds the error of a failed evaluation, the backtrace starts at the innermost call
+    fn fail(&self, reason: impl Into<String>, position: SpanInfo) -> TirError {
+        // A failure in the initial value itself is not caused by a call
+        let advice = match self.initializer {
+            Some(calls) if calls == self.calls.len() => INITIALIZER_HELP,
+            _ => CALL_HELP,
+        };
+
+        ConstEvalError::ConstEvaluationFailed(ConstEvaluationFailed {
+            reason: reason.into(),
+            position: position.position,
+            code: (&position.file).into(),
+            advice: advice.to_string(),
+            backtrace: self.calls.iter().rev().take(MAX_BACKTRACE_FRAMES).cloned().collect(),
+        }.into()).into()
+    }
+
+    fn step(&mut self, locals: &Locals<'base>, position: impl FnOnce() -> SpanInfo) -> Result<(), TirError> {
+        if locals.interpreting {
+            self.steps += 1;
+            if self.steps > MAX_STEPS {
+                return Err(self.fail(format!("evaluation exceeded the limit of {MAX_STEPS} steps"), position()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Runs the statements of a body, returns the value of an executed `return`
+    fn body(&mut self, locals: &mut Locals<'base>, body: &'ctx BodyAst<'base>) -> Result<Option<PrimitiveValue<'base>>, TirError> {
+        for statement in body.statements.iter() {
+            if let Some(value) = self.statement(locals, statement)? {
+                return Ok(Some(value));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn block(&mut self, locals: &mut Locals<'base>, body: &'ctx BodyAst<'base>) -> Result<Option<PrimitiveValue<'base>>, TirError> {
+        locals.scopes.push(HashMap::new());
+        let result = self.body(locals, body);
+        locals.scopes.pop();
+        result
+    }
+
+    fn statement(&mut self, locals: &mut Locals<'base>, statement: &'ctx BodyStatementAst<'base>) -> Result<Option<PrimitiveValue<'base>>, TirError> {
+        let bool_location = get_primitive_location(self.context, PrimitiveType::Bool);
+
+        match statement {
+            BodyStatementAst::VariableDefinition(definition) => {
+                self.step(locals, || (&definition.name).into())?;
+
+                // Tuples have no compile time value, the elements stay unknown
+                if let Some(names) = &definition.destructure {
+                    if let Some(expression) = definition.expression.as_ref() {
+                        self.expression(locals, expression, None)?;
+                    }
+
+                    for name in names.iter() {
+                        let location = self.context.variable_types.get(&SpanKey::from(name)).copied();
+                        locals.define(name.text, Variable { location, value: None });
+                    }
+                    return Ok(None);
+                }
+
+                let location = self.context.variable_types.get(&SpanKey::from(&definition.name)).copied();
+                let value = match definition.expression.as_ref() {
+                    Some(expression) => self.expression(locals, expression, location)?,
+                    None => None,
+                };
+
+                // Outside of a const call only constants keep their value
+                let value = value.filter(|_| locals.interpreting || definition.variable_definition_type == crate::ast::VariableDefinitionType::Const);
+                locals.define(definition.name.text, Variable { location, value });
+            },
+            BodyStatementAst::VariableAssign(assign) if !assign.path.is_empty() => {
+                self.step(locals, || (&assign.name).into())?;
+                self.expression(locals, &assign.expression, None)?;
+
+                if locals.interpreting {
+                    return Err(self.fail("fields cannot be changed at compile time", (&assign.name).into()));
+                }
+            },
+            BodyStatementAst::VariableAssign(assign) => {
+                self.step(locals, || (&assign.name).into())?;
+                let location = locals.get(assign.name.text).and_then(|variable| variable.location);
+                let value = self.expression(locals, &assign.expression, location)?.filter(|_| locals.interpreting);
+
+                let interpreting = locals.interpreting;
+                match locals.get_mut(assign.name.text) {
+                    Some(variable) => variable.value = value,
+                    None if interpreting => return Err(self.fail("global variables cannot be changed at compile time", (&assign.name).into())),
+                    None => (),
+                };
+            },
+            BodyStatementAst::FunctionCall(function_call) => {
+                self.function_call(locals, function_call)?;
+            },
+            BodyStatementAst::IfCondition(if_condition) => {
+                let branches = std::iter::once((&if_condition.expression, &if_condition.true_body)).chain(if_condition.else_ifs.iter().map(|(expression, body)| (expression, body)));
+
+                for (expression, body) in branches {
+                    let condition = self.expression(locals, expression, Some(bool_location))?;
+
+                    if !locals.interpreting {
+                        self.block(locals, body)?;
+                        continue;
+                    }
+
+                    if let Some(PrimitiveValue::Bool(true)) = condition {
+                        return self.block(locals, body);
+                    }
+                }
+
+                if let Some(false_body) = if_condition.false_body.as_ref() {
+                    let result = self.block(locals, false_body)?;
+                    if locals.interpreting {
+                        return Ok(result);
+                    }
+                }
+            },
+            BodyStatementAst::Match(match_ast) if locals.interpreting => return Err(self.fail("match expressions cannot be evaluated at compile time", (&match_ast.span).into())),
+            BodyStatementAst::Match(_) => (),
+            BodyStatementAst::Return(return_statement) => {
+                if let Some(expression) = return_statement.expression.as_ref() {
+                    let value = self.expression(locals, expression, Some(locals.return_type))?;
+                    if locals.interpreting {
+                        return Ok(value);
+                    }
+                }
+            },
+        };
+
+        Ok(None)
+    }
+
+    /// Evaluates an expression, `None` means the value is only known at runtime
+    fn expression(&mut self, locals: &mut Locals<'base>, expression: &'ctx ExpressionAst<'base>, expected: Option<TypeLocation>) -> Result<Option<PrimitiveValue<'base>>, TirError> {
+        self.step(locals, || expression_position(expression))?;
+        let bool_location = get_primitive_location(self.context, PrimitiveType::Bool);
+
+        match expression {
+            ExpressionAst::Primitive { value, .. } => {
+                let primitive = expected
+                    .and_then(|expected| get_primitive_type(self.context, expected))
+                    .filter(|primitive| is_numeric(primitive) && is_numeric(&default_literal_type(value)))
+                    .unwrap_or_else(|| default_literal_type(value));
+                Ok(Some(cast(&primitive, value)))
+            },
+            ExpressionAst::Ident(ident) => match locals.get(ident.text) {
+                Some(variable) => Ok(variable.value.clone()),
+                None => self.global_ident(locals, ident),
+            }.and_then(|value| match value {
+                Some(value) => Ok(Some(value)),
+                None if locals.interpreting => Err(self.fail(format!("`{}` is used before a value is assigned", ident.text), ident.into())),
+                None => Ok(None),
+            }),
+            ExpressionAst::Ref(_) if locals.interpreting => Err(self.fail("references cannot be evaluated at compile time", expression_position(expression))),
+            ExpressionAst::Ref(_) => Ok(None),
+            ExpressionAst::Lambda(_) if locals.interpreting => Err(self.fail("lambda expressions cannot be evaluated at compile time", expression_position(expression))),
+            ExpressionAst::Lambda(_) => Ok(None),
+            ExpressionAst::Match(_) if locals.interpreting => Err(self.fail("match expressions cannot be evaluated at compile time", expression_position(expression))),
+            ExpressionAst::Match(_) => Ok(None),
+            ExpressionAst::Tuple(_) if locals.interpreting => Err(self.fail("tuples cannot be evaluated at compile time", expression_position(expression))),
+            ExpressionAst::Tuple(_) => Ok(None),
+            ExpressionAst::Not(inner) => match self.expression(locals, inner, Some(bool_location))? {
+                Some(PrimitiveValue::Bool(value)) => Ok(Some(PrimitiveValue::Bool(!value))),
+                _ => Ok(None),
+            },
+            ExpressionAst::Operation { left, operator: operator @ (ExpressionOperatorType::And | ExpressionOperatorType::Or), right } => {
+                let short_circuit = *operator == ExpressionOperatorType::Or;
+                let left = self.expression(locals, left, Some(bool_location))?;
+
+                // A const call only evaluates the right operand when it is needed, like the runtime does
+                if locals.interpreting && left == Some(PrimitiveValue::Bool(short_circuit)) {
+                    return Ok(left);
+                }
+
+                let right = self.expression(locals, right, Some(bool_location))?;
+                match (left, right) {
+                    (Some(PrimitiveValue::Bool(left)), _) if left == short_circuit => Ok(Some(PrimitiveValue::Bool(left))),
+                    (Some(PrimitiveValue::Bool(_)), right) => Ok(right),
+                    _ => Ok(None),
+                }
+            },
+            ExpressionAst::Operation { left, operator, right } => {
+                let location = {
+                    let locals = &*locals;
+                    operand_type(self.context, left, operator, right, expected, &|name| locals.get(name).and_then(|variable| variable.location))
+                };
+
+                let left_value = self.expression(locals, left, Some(location))?;
+                let right_value = self.expression(locals, right, Some(location))?;
+
+                let (Some(left_value), Some(right_value), Some(primitive)) = (left_value, right_value, get_primitive_type(self.context, location)) else {
+                    return Ok(None);
+                };
+
+                match evaluate(&primitive, operator, &left_value, &right_value) {
+                    Some(value) => Ok(Some(value)),
+                    None if locals.interpreting => Err(self.fail(failure_reason(&primitive, operator, &right_value), expression_position(expression))),
+                    None => Ok(None),
+                }
+            },
+            ExpressionAst::FunctionCall(function_call) => self.function_call(locals, function_call),
+        }
+    }
+
+    /// Value of an identifier that is not a local variable, only module constants have one
+    fn global_ident(&mut self, locals: &Locals<'base>, ident: &Span<'base>) -> Result<Option<PrimitiveValue<'base>>, TirError> {
+        match global_target(self.context, ident) {
+            Some((key, global)) if global.is_const() => self.global(key),
+            Some(_) if locals.interpreting => Err(self.fail(format!("`{}` is a global variable, its value is only known at runtime", ident.text), ident.into())),
+            _ => Ok(None),
+        }
+    }
+
+    /// Evaluates the initial value of a module variable, every variable is evaluated once
+    fn global(&mut self, key: &'ctx SpanKey) -> Result<Option<PrimitiveValue<'base>>, TirError> {
+        if let Some(value) = self.globals.get(key) {
+            return Ok(Some(value.clone()));
+        }
+
+        let Some(global) = self.context.globals.get(key) else {
+            return Ok(None);
+        };
+
+        let Some(expression) = global.definition.expression.as_ref() else {
+            return Ok(None);
+        };
+
+        // The initial value gets its own budget, it can be evaluated in the middle of a function
+        let steps = std::mem::take(&mut self.steps);
+        let initializer = self.initializer.replace(self.calls.len());
+        let mut locals = Locals::global();
+        let value = match self.expression(&mut locals, expression, Some(global.location))? {
+            Some(value) => value,
+            None => return Err(self.fail("the value cannot be evaluated at compile time", expression_position(expression))),
+        };
+        self.steps = steps;
+        self.initializer = initializer;
+
+        let value = match get_primitive_type(self.context, global.location) {
+            Some(primitive) => cast(&primitive, &value),
+            None => value,
+        };
+
+        self.globals.insert(key.clone(), value.clone());
+        Ok(Some(value))
+    }
+
+    /// Evaluates a call, const functions with constant arguments are interpreted
+    fn function_call(&mut self, locals: &mut Locals<'base>, function_call: &'ctx FunctionCallAst<'base>) -> Result<Option<PrimitiveValue<'base>>, TirError> {
+        let Some(callee) = call_target(self.context, function_call) else {
+            return match locals.interpreting {
+                true => Err(self.fail("the function cannot be evaluated at compile time", (&function_call.call_span).into())),
+                false => Ok(None),
+            };
+        };
+
+        // The implicit `this` argument is not written at the call site
+        let skip = callee.arguments.len() - function_call.arguments.len();
+        let mut arguments = Vec::with_capacity(function_call.arguments.len());
+        for (argument, expression) in callee.arguments[skip..].iter().zip(function_call.arguments.iter()) {
+            arguments.push(self.expression(locals, expression, Some(argument.field_type))?);
+        }
+
+        if callee.ast.is_const.is_none() {
+            return Ok(None);
+        }
+
+        let Some(arguments) = arguments.into_iter().collect::<Option<Vec<_>>>() else {
+            return Ok(None);
+        };
+
+        // Every compile-time call from the program gets its own budget
+        if !locals.interpreting {
+            self.steps = 0;
+        }
+
+        let value = self.call(callee, function_call, arguments)?;
+
+        if !locals.interpreting {
+            self.results.insert(SpanKey::from(&function_call.call_span), value.clone());
+        }
+
+        Ok(Some(value))
+    }
+
+    fn call(&mut self, callee: &'ctx FunctionDefinition<'base>, function_call: &FunctionCallAst<'base>, arguments: Vec<PrimitiveValue<'base>>) -> Result<PrimitiveValue<'base>, TirError> {
+        if self.calls.len() >= MAX_CALL_DEPTH {
+            return Err(self.fail(format!("recursion limit of {MAX_CALL_DEPTH} nested calls exceeded"), (&function_call.call_span).into()));
+        }
+
+        self.calls.push(ConstCallFrame {
+            name: callee.name.text.to_string(),
+            position: function_call.call_span.position.clone(),
+            code: (&function_call.call_span.state.file).into(),
+        });
+
+        let mut locals = Locals::new(callee, Some(arguments));
+        let value = match self.body(&mut locals, &callee.ast.body)? {
+            Some(value) => value,
+            None => return Err(self.fail(format!("`{}` ended without returning a value", callee.name.text), (&callee.name).into())),
+        };
+
+        self.calls.pop();
+
+        Ok(match get_primitive_type(self.context, callee.return_type) {
+            Some(primitive) => cast(&primitive, &value),
+            None => value,
+        })
+    }
+}
+
+fn failure_reason(primitive: &PrimitiveType, operator: &ExpressionOperatorType, right: &PrimitiveValue<'_>) -> &'static str {
+    let is_division = matches!(operator, ExpressionOperatorType::Div | ExpressionOperatorType::Mod);
+
+    match primitive {
+        primitive if is_integer(primitive) && is_division && integer_value(right) == Some(0) => "division by zero",
+        primitive if is_integer(primitive) && is_division => "integer overflow",
+        PrimitiveType::Float | PrimitiveType::Double if *operator == ExpressionOperatorType::Mod => "`%` is not supported for floating point numbers",
+        PrimitiveType::Float | PrimitiveType::Double => "the result is not a finite number",
+        _ => "the operation cannot be evaluated at compile time",
+    }
+}
+
+/// Converts a value to the given primitive type, like an implicit conversion at runtime
+pub fn cast<'base>(primitive: &PrimitiveType, value: &PrimitiveValue<'base>) -> PrimitiveValue<'base> {
+    let converted = match primitive {
+        PrimitiveType::Float => float_value(value).and_then(|value| float_literal(true, value)),
+        PrimitiveType::Double => float_value(value).and_then(|value| float_literal(false, value)),
+        primitive if is_integer(primitive) => integer_value(value).and_then(|value| integer_literal(primitive, wrap(primitive, value))),
+        _ => None,
+    };
+
+    converted.unwrap_or_else(|| value.clone())
+}
+
+/// Evaluates a binary operation on two literals in the given operand type
+///
+/// Returns `None` when the result has to be computed at runtime, for example
+/// because the operation traps or the backend does not support it.
+pub fn evaluate<'base>(primitive: &PrimitiveType, operator: &ExpressionOperatorType, left: &PrimitiveValue<'base>, right: &PrimitiveValue<'base>) -> Option<PrimitiveValue<'base>> {
+    match (primitive, left, right) {
+        (PrimitiveType::String, PrimitiveValue::String(left), PrimitiveValue::String(right)) => match operator {
+            ExpressionOperatorType::Add => Some(PrimitiveValue::String(Cow::Owned(format!("{left}{right}")))),
+            ExpressionOperatorType::Equal => Some(PrimitiveValue::Bool(left == right)),
+            ExpressionOperatorType::NotEqual => Some(PrimitiveValue::Bool(left != right)),
+            _ => None,
+        },
+        (PrimitiveType::Bool, PrimitiveValue::Bool(left), PrimitiveValue::Bool(right)) => match operator {
+            ExpressionOperatorType::Equal => Some(PrimitiveValue::Bool(left == right)),
+            ExpressionOperatorType::NotEqual | ExpressionOperatorType::Xor => Some(PrimitiveValue::Bool(left != right)),
+            ExpressionOperatorType::LogicalAnd => Some(PrimitiveValue::Bool(*left && *right)),
+            ExpressionOperatorType::LogicalOr => Some(PrimitiveValue::Bool(*left || *right)),
+            _ => None,
+        },
+        (PrimitiveType::Float | PrimitiveType::Double, _, _) => evaluate_float(primitive, operator, float_value(left)?, float_value(right)?),
+        (primitive, _, _) if is_integer(primitive) => evaluate_integer(primitive, operator, integer_value(left)?, integer_value(right)?),
+        _ => None,
+    }
+}
+
+fn evaluate_integer<'base>(primitive: &PrimitiveType, operator: &ExpressionOperatorType, left: i128, right: i128) -> Option<PrimitiveValue<'base>> {
+    // Narrow integers are computed in 32 bits and wrapped afterwards, like the backend does
+    let shift_mask = match primitive {
+        PrimitiveType::I64 | PrimitiveType::U64 => 63,
+        _ => 31,
+    };
+
+    let result = match operator {
+        ExpressionOperatorType::Add => left.wrapping_add(right),
+        ExpressionOperatorType::Sub => left.wrapping_sub(right),
+        ExpressionOperatorType::Mul => left.wrapping_mul(right),
+        ExpressionOperatorType::Div | ExpressionOperatorType::Mod if right == 0 => return None,
+        // The only signed division that overflows traps at runtime
+        ExpressionOperatorType::Div if matches!(primitive, PrimitiveType::I32 | PrimitiveType::I64) && right == -1 && wrap(primitive, left.wrapping_neg()) != left.wrapping_neg() => return None,
+        ExpressionOperatorType::Div => left / right,
+        ExpressionOperatorType::Mod => left % right,
+        ExpressionOperatorType::LogicalAnd => left & right,
+        ExpressionOperatorType::LogicalOr => left | right,
+        ExpressionOperatorType::Xor => left ^ right,
+        ExpressionOperatorType::BitwiseShiftLeft => left << (right & shift_mask),
+        ExpressionOperatorType::BitwiseShiftRight => left >> (right & shift_mask),
+        ExpressionOperatorType::Equal => return Some(PrimitiveValue::Bool(left == right)),
+        ExpressionOperatorType::NotEqual => return Some(PrimitiveValue::Bool(left != right)),
+        ExpressionOperatorType::LessThan => return Some(PrimitiveValue::Bool(left < right)),
+        ExpressionOperatorType::LessEqualThan => return Some(PrimitiveValue::Bool(left <= right)),
+        ExpressionOperatorType::GreaterThan => return Some(PrimitiveValue::Bool(left > right)),
+        ExpressionOperatorType::GreaterEqualThan => return Some(PrimitiveValue::Bool(left >= right)),
+        ExpressionOperatorType::And | ExpressionOperatorType::Or => return None,
+    };
+
+    integer_literal(primitive, wrap(primitive, result))
+}
+
+fn evaluate_float<'base>(primitive: &PrimitiveType, operator: &ExpressionOperatorType, left: f64, right: f64) -> Option<PrimitiveValue<'base>> {
+    let is_single = *primitive == PrimitiveType::Float;
+    let (left, right) = match is_single {
+        true => (left as f32 as f64, right as f32 as f64),
+        false => (left, right),
+    };
+
+    let result = match operator {
+        ExpressionOperatorType::Add => left + right,
+        ExpressionOperatorType::Sub => left - right,
+        ExpressionOperatorType::Mul => left * right,
+        ExpressionOperatorType::Div => left / right,
+        ExpressionOperatorType::Equal => return Some(PrimitiveValue::Bool(left == right)),
+        ExpressionOperatorType::NotEqual => return Some(PrimitiveValue::Bool(left != right)),
+        ExpressionOperatorType::LessThan => return Some(PrimitiveValue::Bool(left < right)),
+        ExpressionOperatorType::LessEqualThan => return Some(PrimitiveValue::Bool(left <= right)),
+        ExpressionOperatorType::GreaterThan => return Some(PrimitiveValue::Bool(left > right)),
+        ExpressionOperatorType::GreaterEqualThan => return Some(PrimitiveValue::Bool(left >= right)),
+        _ => return None,
+    };
+
+    float_literal(is_single, result)
+}
+
+fn float_literal<'base>(is_single: bool, value: f64) -> Option<PrimitiveValue<'base>> {
+    let value = match is_single {
+        true => value as f32 as f64,
+        false => value,
+    };
+
+    // Infinity and NaN have no literal form
+    if !value.is_finite() {
+        return None;
+    }
+
+    let decimals = value.to_string().split_once('.').map(|(_, fraction)| fraction.len() as u8).unwrap_or_default();
+    match is_single {
+        true => Some(PrimitiveValue::Float(value, decimals)),
+        false => Some(PrimitiveValue::Double(value, decimals)),
+    }
+}
+
+/// Wraps a value into the range of the integer type
+fn wrap(primitive: &PrimitiveType, value: i128) -> i128 {
+    match primitive {
+        PrimitiveType::I8 => value as i8 as i128,
+        PrimitiveType::U8 => value as u8 as i128,
+        PrimitiveType::I16 => value as i16 as i128,
+        PrimitiveType::U16 => value as u16 as i128,
+        PrimitiveType::I32 => value as i32 as i128,
+        PrimitiveType::U32 => value as u32 as i128,
+        PrimitiveType::I64 => value as i64 as i128,
+        PrimitiveType::U64 => value as u64 as i128,
+        _ => value,
+    }
+}
+
+fn integer_literal<'base>(primitive: &PrimitiveType, value: i128) -> Option<PrimitiveValue<'base>> {
+    match primitive {
+        PrimitiveType::I8 => Some(PrimitiveValue::I8(value as i8)),
+        PrimitiveType::U8 => Some(PrimitiveValue::U8(value as u8)),
+        PrimitiveType::I16 => Some(PrimitiveValue::I16(value as i16)),
+        PrimitiveType::U16 => Some(PrimitiveValue::U16(value as u16)),
+        PrimitiveType::I32 => Some(PrimitiveValue::I32(value as i32)),
+        PrimitiveType::U32 => Some(PrimitiveValue::U32(value as u32)),
+        PrimitiveType::I64 => Some(PrimitiveValue::I64(value as i64)),
+        PrimitiveType::U64 => Some(PrimitiveValue::U64(value as u64)),
+        _ => None,
+    }
+}
+
+fn integer_value(value: &PrimitiveValue<'_>) -> Option<i128> {
+    match value {
+        PrimitiveValue::I8(number) => Some(*number as i128),
+        PrimitiveValue::U8(number) => Some(*number as i128),
+        PrimitiveValue::I16(number) => Some(*number as i128),
+        PrimitiveValue::U16(number) => Some(*number as i128),
+        PrimitiveValue::I32(number) => Some(*number as i128),
+        PrimitiveValue::U32(number) => Some(*number as i128),
+        PrimitiveValue::I64(number) => Some(*number as i128),
+        PrimitiveValue::U64(number) => Some(*number as i128),
+        _ => None,
+    }
+}
+
+fn float_value(value: &PrimitiveValue<'_>) -> Option<f64> {
+    match value {
+        PrimitiveValue::Float(number, _) | PrimitiveValue::Double(number, _) => Some(*number),
+        _ => integer_value(value).map(|number| number as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::{ExpressionOperatorType, PrimitiveValue},
+        file::SourceFile,
+        nom_tools::State,
+        process_ast, process_code,
+        tir::{PrimitiveType, TirContext, TirError},
+    };
+
+    use super::{evaluate, integer_value, ConstEvalError, MAX_CALL_DEPTH};
+
+    fn build<T>(code: &str, check: impl FnOnce(Result<TirContext<'_>, TirError>) -> T) -> T {
+        let state = State::new(SourceFile::new(vec!["main".into()], code.to_string()));
+        let ast = process_code(&state).unwrap();
+        check(process_ast(vec![ast.into()]))
+    }
+
+    fn values(code: &str) -> Vec<i128> {
+        build(code, |context| context.unwrap().const_values.values().map(|value| integer_value(value).unwrap()).collect())
+    }
+
+    fn const_error(code: &str) -> ConstEvalError {
+        build(code, |context| match context {
+            Err(TirError::ConstEval(error)) => *error,
+            other => panic!("Expected a const evaluation error, got {:?}", other.err()),
+        })
+    }
+
+    #[test]
+    fn evaluates_const_calls() {
+        assert_eq!(values("const func square(a: i32): i32 { return a * a; } pub func test(): i32 { const side = square(4); return square(side); }"), vec![16, 256]);
+    }
+
+    #[test]
+    fn recursion_and_branches() {
+        let values = values(r#"
+const func fib(n: i32): i32 {
+    if (n < 2) {
+        return n;
+    }
+    return fib(n - 1) + fib(n - 2);
+}
+pub func test(): i32 { return fib(15); }
+"#);
+        assert_eq!(values, vec![610]);
+    }
+
+    #[test]
+    fn runtime_arguments_are_not_evaluated() {
+        assert!(values("const func square(a: i32): i32 { return a * a; } pub func test(x: i32): i32 { var y = 3; return square(x) + square(y); }").is_empty());
+    }
+
+    #[test]
+    fn results_keep_the_return_type() {
+        build("const func add(a: u8, b: u8): u8 { return a + b; } pub func test(): u8 { return add(200, 100); }", |context| {
+            let context = context.unwrap();
+            let (key, value) = context.const_values.first().unwrap();
+            assert_eq!(value, &PrimitiveValue::U8(44));
+            assert_eq!(key.position, 80..83);
+        });
+    }
+
+    #[test]
+    fn division_by_zero_has_a_backtrace() {
+        let error = const_error("const func divide(a: i32, b: i32): i32 { return a / b; } const func half(a: i32): i32 { return divide(a, a - a); } pub func test(): i32 { return half(4); }");
+        let ConstEvalError::ConstEvaluationFailed(error) = error else { panic!("{error:?}") };
+        assert_eq!(error.reason, "division by zero");
+        assert_eq!(error.backtrace.iter().map(|frame| frame.name.as_str()).collect::<Vec<_>>(), vec!["divide", "half"]);
+    }
+
+    #[test]
+    fn recursion_budget() {
+        let error = const_error("const func forever(a: i32): i32 { return forever(a + 1); } pub func test(): i32 { return forever(0); }");
+        let ConstEvalError::ConstEvaluationFailed(error) = error else { panic!("{error:?}") };
+        assert_eq!(error.reason, format!("recursion limit of {MAX_CALL_DEPTH} nested calls exceeded"));
+
+        let error = const_error(r#"
+const func fib(n: i32): i32 {
+    if (n < 2) {
+        return n;
+    }
+    return fib(n - 1) + fib(n - 2);
+}
+pub func test(): i32 { return fib(40); }
+"#);
+        let ConstEvalError::ConstEvaluationFailed(error) = error else { panic!("{error:?}") };
+        assert!(error.reason.starts_with("evaluation exceeded the limit"), "{}", error.reason);
+    }
+
+    #[test]
+    fn const_functions_only_call_const_functions() {
+        let error = const_error("func runtime(): i32 { return 1; } const func test(): i32 { return runtime(); }");
+        assert!(matches!(error, ConstEvalError::NotConstFunction(ref error) if error.name == "runtime"), "{error:?}");
+    }
+
+    #[test]
+    fn invalid_const_functions() {
+        assert!(matches!(const_error("const func test(): void { }"), ConstEvalError::InvalidConstFunction(_)));
+        assert!(matches!(const_error("class a { const func test(this): i32 { return 1; } }"), ConstEvalError::InvalidConstFunction(_)));
+        assert!(matches!(const_error("class a { } const func test(b: a): i32 { return 1; }"), ConstEvalError::InvalidConstFunction(_)));
+    }
+
+    #[test]
+    fn module_constants() {
+        build("const func square(a: i32): i32 { return a * a; } const AREA: i32 = SIDE * SIDE; const SIDE: i32 = square(3) + 1; pub func test(): i32 { return square(SIDE); }", |context| {
+            let context = context.unwrap();
+            assert_eq!(context.global_values.values().cloned().collect::<Vec<_>>(), vec![PrimitiveValue::I32(10), PrimitiveValue::I32(100)]);
+            assert_eq!(context.const_values.values().cloned().collect::<Vec<_>>(), vec![PrimitiveValue::I32(100)]);
+        });
+    }
+
+    #[test]
+    fn module_constants_in_const_functions() {
+        assert_eq!(values("const LIMIT: i32 = 7; const func limit(): i32 { return LIMIT; } pub func test(): i32 { return limit(); }"), vec![7]);
+
+        let error = const_error("var counter: i32 = 0; const func next(): i32 { return counter + 1; } pub func test(): i32 { return next(); }");
+        let ConstEvalError::ConstEvaluationFailed(error) = error else { panic!("{error:?}") };
+        assert!(error.reason.contains("global variable"), "{}", error.reason);
+    }
+
+    #[test]
+    fn runtime_initial_values() {
+        let error = const_error("func runtime(): i32 { return 1; } const LIMIT: i32 = runtime();");
+        assert!(matches!(error, ConstEvalError::NotConstantInitializer(ref error) if error.name == "LIMIT"), "{error:?}");
+
+        let error = const_error("var counter: i32 = 0; const START: i32 = counter;");
+        assert!(matches!(error, ConstEvalError::NotConstantInitializer(ref error) if error.reason.contains("global variable")), "{error:?}");
+
+        let error = const_error("const ZERO: i32 = 0; const BROKEN: i32 = 1 / ZERO;");
+        assert!(matches!(error, ConstEvalError::ConstEvaluationFailed(ref error) if error.reason == "division by zero"), "{error:?}");
+
+        // The help of a failed initial value is about the initial value, a failed call keeps the help of calls
+        let error = const_error("const Y: i32 = 1 / 0;");
+        assert!(matches!(error, ConstEvalError::ConstEvaluationFailed(ref error) if error.advice.starts_with("the initial value of a module variable")), "{error:?}");
+
+        let error = const_error("const func divide(a: i32, b: i32): i32 { return a / b; } const Y: i32 = divide(1, 0);");
+        assert!(matches!(error, ConstEvalError::ConstEvaluationFailed(ref error) if error.advice.starts_with("the call is evaluated")), "{error:?}");
+    }
+
+    #[test]
+    fn shifts_and_bitwise() {
+        assert_eq!(evaluate(&PrimitiveType::I32, &ExpressionOperatorType::BitwiseShiftLeft, &PrimitiveValue::I8(1), &PrimitiveValue::I8(33)), Some(PrimitiveValue::I32(2)));
+        assert_eq!(evaluate(&PrimitiveType::U8, &ExpressionOperatorType::BitwiseShiftLeft, &PrimitiveValue::U8(255), &PrimitiveValue::I8(1)), Some(PrimitiveValue::U8(254)));
+        assert_eq!(evaluate(&PrimitiveType::I32, &ExpressionOperatorType::BitwiseShiftRight, &PrimitiveValue::I8(-8), &PrimitiveValue::I8(1)), Some(PrimitiveValue::I32(-4)));
+        assert_eq!(evaluate(&PrimitiveType::I32, &ExpressionOperatorType::Xor, &PrimitiveValue::I8(6), &PrimitiveValue::I8(3)), Some(PrimitiveValue::I32(5)));
+    }
+}
@@ -27,16 +27,16 @@
 //! The context uses atomic operations for temporary type generation,
 //! making it safe to use in multi-threaded compilation scenarios.
 
-use std::{borrow::Cow, ops::Range, sync::atomic::{AtomicUsize, Ordering}};
+use std::{borrow::Cow, ops::Range, sync::{atomic::{AtomicUsize, Ordering}, Arc}};
 
 use indexmap::IndexMap;
 use simplelog::debug;
 
-use crate::{ast::AstIndex, file::SourceFile, tir::object_signature::TypeValueDiscriminants};
+use crate::{ast::{AstIndex, PrimitiveValue}, file::SourceFile, nom_tools::Span, provider::ModuleLoader, tir::object_signature::TypeValueDiscriminants};
 
 use super::{
     module::ModuleRef, 
-    resolver::{AstSignatureLocation, ResolveAst, TypeLocation}, 
+    resolver::{module_variable::GlobalVariable, statement::{LambdaInformation, MatchInformation}, AstSignatureLocation, ResolveAst, TypeLocation}, 
     scope::{Scope, ScopeLocation}, 
     signature::SignaturePath, 
     AstSignature, 
@@ -47,6 +47,36 @@ use super::{
     TypeSignatureHolder
 };
 
+/// Source location key used by the side tables of [`TirContext`]
+/// 
+/// Body statements do not carry an [`AstIndex`], so information gathered while
+/// type checking function bodies is keyed by the file path and byte range of the
+/// span it belongs to. Backends use the same key to look the information up again.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpanKey {
+    pub path: Arc<Vec<String>>,
+    pub position: Range<usize>,
+}
+
+impl From<&Span<'_>> for SpanKey {
+    fn from(span: &Span<'_>) -> Self {
+        Self {
+            path: span.state.file.path.clone(),
+            position: span.position.clone(),
+        }
+    }
+}
+
+/// What an identifier used in a function body refers to, see [`TirContext::identifiers`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdentifierTarget {
+    /// Resolved type of the identifier
+    pub location: TypeLocation,
+    /// Name span of the variable or argument, `None` when the identifier names a
+    /// module level item such as a function or a class
+    pub definition: Option<SpanKey>,
+}
+
 /// The central context for Type Intermediate Representation (TIR) compilation.
 /// 
 /// This structure maintains all the state needed during the type resolution
@@ -67,6 +97,16 @@ use super::{
 /// * `ast_type` - Mapping from AST nodes to their resolved types
 /// * `tmp_type_indexer` - Atomic counter for generating unique temporary type names
 /// * `errors` - Collection of all compilation errors encountered
+/// * `call_targets` - Resolved callee of every function call, keyed by the call span
+/// * `variable_types` - Resolved type of every local variable, keyed by the name span
+/// * `identifiers` - Target of every identifier used in an expression, keyed by the identifier span
+/// * `reexports` - Items re-exported with `pub use`, from the new path to the imported path
+/// * `wildcard_reexports` - Modules re-exported with `pub use module.*`, keyed by the re-exporting module
+/// * `globals` - Module-level constants and global variables, keyed by the name span
+/// * `global_values` - Initial values of the module-level constants and globals
+/// * `lambdas` - Resolved lambda expressions and their captures, keyed by the lambda span
+/// * `matches` - Result types and unreachable arms of match expressions, keyed by the match span
+/// * `tuples` - Types of the tuple literals, keyed by the tuple span
 /// 
 /// # Examples
 /// 
@@ -99,6 +139,27 @@ pub struct TirContext<'base> {
     pub tmp_type_indexer: AtomicUsize,
     /// Collection of all compilation errors
     pub errors: Vec<TirError>,
+    pub call_targets: IndexMap<SpanKey, TypeLocation>,
+    pub variable_types: IndexMap<SpanKey, TypeLocation>,
+    pub identifiers: IndexMap<SpanKey, IdentifierTarget>,
+    /// Results of const function calls evaluated at compile time, keyed by the call span
+    pub const_values: IndexMap<SpanKey, PrimitiveValue<'base>>,
+    /// Loads imported modules that were not passed in, see [`crate::provider`]
+    pub loader: Option<ModuleLoader<'base>>,
+    /// Items re-exported with `pub use`, `"b.A"` points to `"lib.A"` for `pub use lib.A;` in `b`
+    pub reexports: IndexMap<String, String>,
+    /// Modules whose public items are re-exported with `pub use lib.*;`, keyed by the re-exporting module
+    pub wildcard_reexports: IndexMap<String, Vec<String>>,
+    /// Module-level constants and global variables, keyed by the span of their name
+    pub globals: IndexMap<SpanKey, GlobalVariable<'base>>,
+    /// Initial values of the module-level constants and globals, evaluated at compile time
+    pub global_values: IndexMap<SpanKey, PrimitiveValue<'base>>,
+    /// Resolved lambda expressions with their captured variables, keyed by the span of the lambda
+    pub lambdas: IndexMap<SpanKey, LambdaInformation>,
+    /// Resolved match expressions with their unreachable arms, keyed by the span of the match
+    pub matches: IndexMap<SpanKey, MatchInformation>,
+    /// Types of the tuple literals, keyed by the span of the literal
+    pub tuples: IndexMap<SpanKey, TypeLocation>,
 }
 
 impl<'base> TirContext<'base> {
@@ -155,8 +216,38 @@ impl<'base> TirContext<'base> {
     /// # Usage
     /// Used when you need the location identifier rather than the full signature,
     /// typically for establishing references between different parts of the AST.
+    /// Re-exported paths are followed to the item they import.
     pub fn get_ast_location<T: AsRef<str>>(&self, key: T) -> Option<AstSignatureLocation> {
-        self.ast_signatures.location(key.as_ref())
+        let mut key = Cow::Borrowed(key.as_ref());
+
+        // Every step follows one re-export, more steps than re-exports means a cycle
+        for _ in 0..=self.reexports.len() + self.wildcard_reexports.len() {
+            if let Some(location) = self.ast_signatures.location(&key) {
+                return Some(location);
+            }
+
+            key = Cow::Owned(self.reexport_target(&key)?);
+        }
+        None
+    }
+
+    /// Returns the path a re-exported path points to
+    /// 
+    /// A `pub use lib.A;` in module `b` makes `b.A` point to `lib.A` and a
+    /// `pub use lib.*;` makes every `b.X` point to `lib.X`. Targets of wildcard
+    /// re-exports are only returned when they exist, or are re-exported again.
+    /// 
+    /// # Returns
+    /// `Some(path)` with the imported path, `None` if the path is not re-exported
+    pub fn reexport_target(&self, key: &str) -> Option<String> {
+        if let Some(target) = self.reexports.get(key) {
+            return Some(target.clone());
+        }
+
+        let (module, name) = key.rsplit_once('.')?;
+        self.wildcard_reexports.get(module)?.iter()
+            .map(|target| format!("{target}.{name}"))
+            .find(|target| self.ast_signatures.location(target).is_some() || self.reexports.contains_key(target))
     }
 
     pub fn add_ast_signature(&mut self, key: Cow<'base, str>, signature: AstSignature<'base>) -> Result<AstSignatureLocation, TirError> {
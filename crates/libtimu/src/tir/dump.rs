@@ -0,0 +1,107 @@
+//! Text rendering of the resolved types of a program.
+//!
+//! The rendering lists every module with its functions, classes, interfaces,
+//! enums, constants and global variables, using the type names of diagnostics:
+//!
+//! ```text
+//! module main
+//!     pub func add(a: i32, b: i32): i32
+//!     class Point
+//!         x: i32
+//!     pub const LIMIT: i32 = 100
+//! ```
+
+use std::fmt::Write;
+
+use super::{resolver::{function::FunctionArgument, statement::type_name, TypeLocation}, TirContext, TypeValue};
+
+/// Renders the resolved types of every module, the standard library is left out
+pub fn render(context: &TirContext<'_>) -> String {
+    let mut output = String::new();
+    let modules = context.modules.values().filter(|module| module.ast.is_some() && module.path != "std" && !module.path.starts_with("std."));
+
+    for module in modules {
+        let _ = writeln!(output, "module {}", module.path);
+        for (name, location) in module.types.iter() {
+            let signature = match context.types.get_from_location(*location) {
+                Some(signature) => signature,
+                None => continue,
+            };
+
+            let _ = match signature.value.as_ref() {
+                TypeValue::Function(function) => writeln!(output, "    {}func {}", if function.is_public { "pub " } else { "" }, function_signature(context, name.get_name(), &function.arguments, function.return_type)),
+                TypeValue::Class(class) => {
+                    let _ = match class.base {
+                        Some(base) => writeln!(output, "    class {}: {}", name.get_name(), type_name(context, base)),
+                        None => writeln!(output, "    class {}", name.get_name()),
+                    };
+                    for (field_name, field) in class.fields.iter() {
+                        let is_static = if class.static_members.contains(field_name) { "static " } else { "" };
+                        let _ = writeln!(output, "        {is_static}{field_name}: {}", type_name(context, field.location));
+                    }
+                    Ok(())
+                },
+                TypeValue::Interface(interface) => {
+                    let _ = writeln!(output, "    interface {}", name.get_name());
+                    for (field_name, field) in interface.fields.iter() {
+                        let _ = writeln!(output, "        {}: {}", field_name.text, type_name(context, field.location));
+                    }
+                    Ok(())
+                },
+                TypeValue::Enum(definition) => {
+                    let _ = writeln!(output, "    enum {}", name.get_name());
+                    for (variant_name, variant) in definition.variants.iter() {
+                        let payload = match context.types.get_from_location(variant.location).map(|signature| signature.value.as_ref()) {
+                            Some(TypeValue::EnumVariant(variant)) if !variant.payload.is_empty() => format!("({})", variant.payload.iter().map(|payload| format!("{}{}", if payload.is_nullable { "?" } else { "" }, type_name(context, payload.field_type))).collect::<Vec<_>>().join(", ")),
+                            _ => String::new(),
+                        };
+                        let _ = writeln!(output, "        {variant_name}{payload}");
+                    }
+                    Ok(())
+                },
+                TypeValue::InterfaceFunction(function) => writeln!(output, "    func {}", function_signature(context, name.get_name(), &function.arguments, function.return_type)),
+                _ => writeln!(output, "    {}: {}", name.get_name(), type_name(context, *location)),
+            };
+        }
+
+        let prefix = format!("{}.", module.path);
+        let globals = context.globals.iter().filter(|(_, global)| global.path.strip_prefix(&prefix).is_some_and(|name| !name.contains('.')));
+        for (key, global) in globals {
+            let visibility = if global.is_public { "pub " } else { "" };
+            let _ = write!(output, "    {visibility}{} {}: {}", global.definition.variable_definition_type, global.definition.name.text, type_name(context, global.location));
+            let _ = match context.global_values.get(key) {
+                Some(value) => writeln!(output, " = {value}"),
+                None => writeln!(output),
+            };
+        }
+    }
+
+    output
+}
+
+pub(super) fn function_signature(context: &TirContext<'_>, name: &str, arguments: &[FunctionArgument<'_>], return_type: TypeLocation) -> String {
+    let arguments = arguments.iter()
+        .map(|argument| format!("{}: {}{}{}", argument.name.text, if argument.is_reference { "ref " } else { "" }, if argument.is_nullable { "?" } else { "" }, type_name(context, argument.field_type)))
+        .collect::<Vec<_>>();
+
+    format!("{name}({}): {}", arguments.join(", "), type_name(context, return_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{file::SourceFile, nom_tools::State, process_ast, process_code};
+
+    #[test]
+    fn tir() {
+        let state = State::new(SourceFile::new(vec!["main".into()], "use std.io.println; pub func test(a: i32, b: ref Point): bool { return true; } class Point { x: i32; static origin: i32; } enum Shape { Circle(double), Empty } pub const LIMIT: i32 = 100; var counter = LIMIT * 2;".to_string()));
+        let ast = process_code(&state).unwrap();
+        let context = process_ast(vec![ast.into()]).unwrap();
+        let output = super::render(&context);
+        assert!(output.starts_with("module main\n"), "{output}");
+        assert!(output.contains("    pub func test(a: i32, b: ref Point): bool\n"), "{output}");
+        assert!(output.contains("    class Point\n        x: i32\n        static origin: i32\n"), "{output}");
+        assert!(output.contains("    enum Shape\n        Circle(double)\n        Empty\n"), "{output}");
+        assert!(output.contains("    pub const LIMIT: i32 = 100\n    var counter: i32 = 200\n"), "{output}");
+        assert!(!output.contains("module std"), "{output}");
+    }
+}
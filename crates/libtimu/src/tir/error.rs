@@ -19,7 +19,9 @@
 //!
 //! ## Import and Module Errors
 //! - [`ImportNotFound`]: Missing module imports
+//! - [`ModuleLoadFailed`]: A module provider could not read an imported module
 //! - [`ModuleAlreadyImported`]: Duplicate module imports
+//! - [`ImportConflictsWithDefinition`]: Imported names that the module defines too
 //!
 //! ## Type System Errors
 //! - [`TypeNotFound`]: Undefined type references
@@ -39,6 +41,10 @@
 //! - [`ErrorCollection`]: Multiple accumulated errors
 //! - [`SyntaxError`]: Parser-level syntax errors
 //!
+//! ## Lints
+//! - [`LintError`]: Findings of the lints, reported as warnings unless the lint is denied
+//! - [`AttributeError`]: Attributes of the sources the lints cannot apply
+//!
 //! # Usage
 //!
 //! ```ignore
@@ -66,12 +72,12 @@
 
 use std::ops::Range;
 
-use strum_macros::{EnumDiscriminants, EnumProperty};
+use strum_macros::{EnumDiscriminants, EnumIter, EnumProperty};
 
 use libtimu_macros::TimuError;
-use libtimu_macros_core::{traits::LabeledSpan, SourceCode};
+use libtimu_macros_core::{traits::{LabeledSpan, Suggestion}, SourceCode};
 
-use crate::{file::SourceFile, tir::{resolver::ResolverError, TirContext}};
+use crate::{backend::BackendError, file::SourceFile, lint::{AttributeError, LintError}, tir::{accessibility::is_definition_public, ast_signature::AstSignatureValue, const_eval::ConstEvalError, resolver::ResolverError, TirContext}};
 
 /// Error for when an imported module cannot be found.
 ///
@@ -91,9 +97,28 @@ use crate::{file::SourceFile, tir::{resolver::ResolverError, TirContext}};
 /// ```timu
 /// use nonexistent.Module;  // Error: 'nonexistent' not found
 /// ```
+#[derive(Clone, Debug, thiserror::Error, TimuError)]
+#[error("module '{module}' could not be loaded: {reason}")]
+#[diagnostic(code("T0002"), help("check that the module file can be read"))]
+pub struct ModuleLoadFailed {
+    /// The path of the module that was being loaded
+    pub module: String,
+
+    /// Why the module provider failed
+    pub reason: String,
+
+    /// Source location of the import that requested the module
+    #[label("imported here")]
+    pub position: Range<usize>,
+
+    /// Source code context for the error
+    #[source_code]
+    pub code: SourceCode
+}
+
 #[derive(Clone, Debug, thiserror::Error, TimuError)]
 #[error("'{module}' not found")]
-#[diagnostic(code("timu::error::import_not_found"), help("try to remove the import or check the module name"))]
+#[diagnostic(code("T0001"), help("try to remove the import or check the module name"))]
 pub struct ImportNotFound {
     /// The name of the module that could not be found
     pub module: String,
@@ -140,7 +165,7 @@ pub static TYPE_NOT_FOUND_HELP: &str = "try to import the type, or maybe you nee
 /// ```
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("'{type_name}' type not found")]
-#[diagnostic(code("timu::error::type_not_found"))]
+#[diagnostic(code("T0004"))]
 pub struct TypeNotFound {
     /// The name of the type that could not be found
     pub type_name: String,
@@ -156,6 +181,10 @@ pub struct TypeNotFound {
     /// Contextual advice including similar type suggestions
     #[help]
     pub advice: String,
+
+    /// Import of the only similar type, if there is one
+    #[suggestions]
+    pub suggestions: Vec<Suggestion>,
 }
 
 /// Error for when a module is imported multiple times.
@@ -171,7 +200,7 @@ pub struct TypeNotFound {
 /// use module.Class;  // Error: Module already imported
 /// ```
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
-#[diagnostic(code("timu::error::module_already_imported"), help("try to remove one of the import"))]
+#[diagnostic(code("T0003"), help("try to remove one of the import"))]
 #[error("Module already defined")]
 pub struct ModuleAlreadyImported {
     /// Location of the original import
@@ -187,6 +216,36 @@ pub struct ModuleAlreadyImported {
     pub code: SourceCode,
 }
 
+/// Error for when an import brings in a name that the module defines itself.
+///
+/// Wildcard imports bring in names without listing them, so the error names
+/// the item and shows both the import and the definition.
+///
+/// # Example
+///
+/// ```timu
+/// use shapes.*;    // shapes has a public `Circle`
+/// class Circle {}  // Error: `Circle` is imported and defined
+/// ```
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[diagnostic(code("T0053"), help("rename the definition, or import the other items one by one"))]
+#[error("`{name}` is imported and also defined in this module")]
+pub struct ImportConflictsWithDefinition {
+    pub name: String,
+
+    /// Location of the import bringing in the name
+    #[label("`{name}` is imported here")]
+    pub import_position: Range<usize>,
+
+    /// Location of the definition in the module
+    #[label("and defined here")]
+    pub definition_position: Range<usize>,
+
+    /// Source code context showing the import and the definition
+    #[source_code]
+    pub code: SourceCode,
+}
+
 /// Error for when a symbol (class, function, variable) is defined multiple times.
 ///
 /// This error enforces unique naming within scopes, preventing ambiguous
@@ -208,7 +267,7 @@ pub struct ModuleAlreadyImported {
 /// ```
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("Already defined")]
-#[diagnostic(code("timu::error::already_imported"), help("change one of the names or remove the definition"))]
+#[diagnostic(code("T0005"), help("change one of the names or remove the definition"))]
 pub struct AlreadyDefined {
     /// Location of the original definition
     #[label("Already defined here")]
@@ -243,7 +302,7 @@ pub struct AlreadyDefined {
 /// ```
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("Extra accessibility identifier")]
-#[diagnostic(code("timu::error::extra_accessibility_identifier"), help("remove pub"))]
+#[diagnostic(code("T0006"), help("remove pub"))]
 pub struct ExtraAccessibilityIdentifier { 
     /// Location of the invalid `pub` keyword
     #[label("pub identifier is not allowed here")]
@@ -256,7 +315,7 @@ pub struct ExtraAccessibilityIdentifier {
 
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("Invalid type")]
-#[diagnostic(code("timu::error::invalid_type"))]
+#[diagnostic(code("T0007"))]
 pub struct InvalidType {
     #[label(collection, "")]
     pub position: Vec<LabeledSpan>,
@@ -267,7 +326,7 @@ pub struct InvalidType {
 
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("Circular reference detected")]
-#[diagnostic(code("timu::error::circular_reference"), help("to fix this, you need to remove the circular reference"))]
+#[diagnostic(code("T0012"), help("to fix this, you need to remove the circular reference"))]
 pub struct CircularReference {
     #[label("Has a circular reference here")]
     pub position: Range<usize>,
@@ -285,6 +344,7 @@ pub struct ErrorCollection {
 
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("{} syntax error(s) detected", .errors.len())]
+#[diagnostic(code("T0013"))]
 pub struct SyntaxError {
     #[errors]
     pub errors: Vec<SyntaxErrorItem>
@@ -292,6 +352,7 @@ pub struct SyntaxError {
 
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("Syntax error")]
+#[diagnostic(code("T0013"))]
 pub struct SyntaxErrorItem {
     #[label("{message}")]
     pub position: Range<usize>,
@@ -304,18 +365,21 @@ pub struct SyntaxErrorItem {
 
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("Interface field(s) not defined")]
-#[diagnostic(code("timu::error::interface_field_not_defined"), help("to fix this, you need to define field(s) in the interface"))]
+#[diagnostic(code("T0008"), help("to fix this, you need to define field(s) in the interface"))]
 pub struct InterfaceFieldNotDefined { 
     #[label("Interface field(s) not defined here")]
     pub position: Range<usize>,
     
     #[source_code]
     pub code: SourceCode,
+
+    #[suggestions]
+    pub suggestions: Vec<Suggestion>,
  }
 
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("Types do not match")]
-#[diagnostic(code("timu::error::types_do_not_match"), help("to fix this, you need to change the type(s) to match"))]
+#[diagnostic(code("T0009"), help("to fix this, you need to change the type(s) to match"))]
 pub struct TypesDoNotMatch {
     #[label("This type not matching")]
     pub position: Range<usize>,
@@ -326,7 +390,7 @@ pub struct TypesDoNotMatch {
 
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("Extra field in interface")]
-#[diagnostic(code("timu::error::extra_field_in_interface"), help("remove the field(s) not defined in the interface"))]
+#[diagnostic(code("T0010"), help("remove the field(s) not defined in the interface"))]
 pub struct ExtraFieldInExtend { 
     #[label("This field is not defined in the extend")]
     pub position: Range<usize>,
@@ -384,7 +448,7 @@ pub struct ImportAttemptInfo {
 /// ```
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("'{item_name}' is private and cannot be imported")]
-#[diagnostic(code("timu::error::accessibility_violation"), help("mark the item as 'pub' in its definition module or remove the import"))]
+#[diagnostic(code("T0011"), help("mark the item as 'pub' in its definition module or remove the import"))]
 pub struct AccessibilityViolation {
     /// Name of the private item being imported
     pub item_name: String,
@@ -396,21 +460,76 @@ pub struct AccessibilityViolation {
     /// Information about the import attempt
     #[reference]
     pub import_info: ImportAttemptInfo,
+
+    /// Adds `pub` to the definition of the item
+    #[suggestions]
+    pub suggestions: Vec<Suggestion>,
+}
+
+/// Error for a class member that does not match the base class member it overrides.
+///
+/// A field of the base class cannot be defined again and an overriding
+/// function must have the signature of the base class function.
+///
+/// # Example
+///
+/// ```timu
+/// class Shape { pub func area(this): i32 {} }
+/// class Square: Shape { pub func area(this, scale: i32): i32 {} }
+/// ```
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("'{member_name}' does not match the member of the base class")]
+#[diagnostic(code("T0039"), help("use the signature of the base class member or rename the member"))]
+pub struct InvalidOverride {
+    /// Name of the overriding member
+    pub member_name: String,
+
+    #[label("overridden here")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+
+    /// Information about the base class member
+    #[reference]
+    pub base_info: OverriddenItemInfo,
+}
+
+/// Information about the base class member of an invalid override.
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("base class member")]
+pub struct OverriddenItemInfo {
+    /// Location of the base class member
+    #[label("base class member defined here")]
+    pub position: Range<usize>,
+
+    /// Source code context of the base class
+    #[source_code]
+    pub code: SourceCode,
 }
 
 #[derive(Clone, Debug, TimuError, thiserror::Error, EnumDiscriminants, EnumProperty)]
+#[strum_discriminants(derive(EnumIter))]
 pub enum TirError {
     #[error("Temporary error")]
-    #[diagnostic(code("Temporary error"))]
+    #[diagnostic(code("T0000"))]
     TemporaryError,
 
     #[error(transparent)]
     #[diagnostic(transparent)]
     ImportNotFound(Box<ImportNotFound>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ModuleLoadFailed(Box<ModuleLoadFailed>),
     
     #[error(transparent)]
     #[diagnostic(transparent)]
     ModuleAlreadyImported(Box<ModuleAlreadyImported>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ImportConflictsWithDefinition(Box<ImportConflictsWithDefinition>),
     
     #[error(transparent)]
     #[diagnostic(transparent)]
@@ -459,6 +578,26 @@ pub enum TirError {
     #[error(transparent)]
     #[diagnostic(transparent)]
     SyntaxError(#[from] Box<SyntaxError>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Backend(#[from] Box<BackendError>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ConstEval(#[from] Box<ConstEvalError>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    InvalidOverride(Box<InvalidOverride>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Lint(#[from] Box<LintError>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Attribute(#[from] Box<AttributeError>),
 }
 
 impl TirError {
@@ -478,10 +617,11 @@ impl TirError {
         }.into())
     }
 
-    pub fn interface_field_not_defined(position: Range<usize>, source: SourceFile) -> Self {
+    pub fn interface_field_not_defined(position: Range<usize>, source: SourceFile, suggestion: Option<Suggestion>) -> Self {
         TirError::InterfaceFieldNotDefined(InterfaceFieldNotDefined {
             position,
             code: source.into(),
+            suggestions: suggestion.into_iter().collect(),
         }.into())
     }
 
@@ -511,7 +651,8 @@ impl TirError {
         import_position: Range<usize>, 
         import_source: SourceFile,
         item_position: Range<usize>,
-        item_source: SourceFile
+        item_source: SourceFile,
+        suggestion: Option<Suggestion>
     ) -> Self {
         TirError::AccessibilityViolation(AccessibilityViolation {
             item_name,
@@ -523,6 +664,19 @@ impl TirError {
                 position: import_position,
                 code: import_source.into(),
             },
+            suggestions: suggestion.into_iter().collect(),
+        }.into())
+    }
+
+    pub fn invalid_override(member_name: String, position: Range<usize>, source: SourceFile, base_position: Range<usize>, base_source: SourceFile) -> Self {
+        TirError::InvalidOverride(InvalidOverride {
+            member_name,
+            position,
+            code: source.into(),
+            base_info: OverriddenItemInfo {
+                position: base_position,
+                code: base_source.into(),
+            },
         }.into())
     }
 
@@ -550,11 +704,29 @@ impl TirError {
             TYPE_NOT_FOUND_HELP.to_string()
         };
 
+        // Importing is only a fix when exactly one public type of another module has the missing name
+        let module = source.path().join(".");
+        let suffix = format!(".{missing_type_name}");
+        let mut imports = context.ast_signatures.iter()
+            .filter(|(_, _, signature)| matches!(signature.value.as_ref(), AstSignatureValue::Class(_) | AstSignatureValue::Interface(_)) && is_definition_public(signature.value.as_ref()))
+            .map(|(path, _, _)| path.get_raw_path().as_ref())
+            .filter(|path| path.ends_with(suffix.as_str()) && path[..path.len() - suffix.len()] != module);
+        let suggestions = match (imports.next(), imports.next()) {
+            (Some(type_name), None) if !missing_type_name.contains('.') => vec![Suggestion {
+                message: format!("import `{type_name}`"),
+                code: (&source).into(),
+                range: 0..0,
+                replacement: format!("use {type_name};\n"),
+            }],
+            _ => Vec::new(),
+        };
+
         TirError::TypeNotFound(TypeNotFound {
             position,
             code: source.into(),
             type_name: missing_type_name,
             advice,
+            suggestions,
         }.into())
     }
 
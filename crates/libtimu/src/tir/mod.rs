@@ -19,6 +19,7 @@
 //! - **Implementation Validation**: Ensure interface implementations are complete
 //! - **Cross-References**: Validate references between modules and types
 //! - **Error Collection**: Gather and report semantic errors
+//! - **Const Evaluation**: Evaluate `const func` calls with constant arguments, see [`const_eval`]
 //!
 //! # Key Components
 //!
@@ -29,6 +30,7 @@
 //! - [`scope`] - Hierarchical scope management for variables and types
 //! - [`module`] - Module system and import/export handling
 //! - [`error`] - Rich error reporting with source locations
+//! - [`query`] - Hover and go-to-definition lookups for editors
 //!
 //! # Type System
 //!
@@ -56,22 +58,25 @@ pub use context::TirContext;
 pub use error::TirError;
 use module::{Module, ModuleRef};
 pub use object_signature::{PrimitiveType, TypeValue};
-use resolver::{build_file, AstSignatureLocation, ObjectLocation, ResolveAst, TypeLocation};
+use resolver::{build_file, AstSignatureLocation, BuildPhase, ObjectLocation, ResolveAst, TypeLocation};
 use scope::ScopeLocation;
 use signature::{Signature, SignatureHolder, SignaturePath};
 
-use crate::{ast::{FileAst, FileStatementAst}, file::SourceFile, tir::{ast_signature::AstSignatureValueDiscriminants, object_signature::TypeValueDiscriminants}};
+use crate::{ast::{FileAst, FileStatementAst}, file::SourceFile, provider::ModuleLoader, tir::{ast_signature::AstSignatureValueDiscriminants, object_signature::TypeValueDiscriminants}};
 
 pub mod accessibility;
 mod ast_signature;
-mod context;
+pub mod const_eval;
+pub(crate) mod context;
+pub mod dump;
 //pub mod error;
 pub mod error;
-mod module;
-mod object_signature;
-mod resolver;
-mod signature;
-mod scope;
+pub(crate) mod module;
+pub mod query;
+pub(crate) mod object_signature;
+pub(crate) mod resolver;
+pub(crate) mod signature;
+pub(crate) mod scope;
 
 
 /// Type alias for AST-based signatures used during the resolve phase
@@ -122,6 +127,7 @@ fn build_primitive_types(context: &mut TirContext<'_>) {
     context.types.add_signature(SignaturePath::borrowed("i64"), TypeSignature::new(TypeValue::PrimitiveType(object_signature::PrimitiveType::I64), SourceFile::new(vec!["<standart>".into()], "<native-code>".to_string()), 0..0, None)).unwrap();
     context.types.add_signature(SignaturePath::borrowed("u64"), TypeSignature::new(TypeValue::PrimitiveType(object_signature::PrimitiveType::U64), SourceFile::new(vec!["<standart>".into()], "<native-code>".to_string()), 0..0, None)).unwrap();
     context.types.add_signature(SignaturePath::borrowed("float"), TypeSignature::new(TypeValue::PrimitiveType(object_signature::PrimitiveType::Float), SourceFile::new(vec!["<standart>".into()], "<native-code>".to_string()), 0..0, None)).unwrap();
+    context.types.add_signature(SignaturePath::borrowed("double"), TypeSignature::new(TypeValue::PrimitiveType(object_signature::PrimitiveType::Double), SourceFile::new(vec!["<standart>".into()], "<native-code>".to_string()), 0..0, None)).unwrap();
     context.types.add_signature(SignaturePath::borrowed("bool"), TypeSignature::new(TypeValue::PrimitiveType(object_signature::PrimitiveType::Bool), SourceFile::new(vec!["<standart>".into()], "<native-code>".to_string()), 0..0, None)).unwrap();
     context.types.add_signature(SignaturePath::borrowed("string"), TypeSignature::new(TypeValue::PrimitiveType(object_signature::PrimitiveType::String), SourceFile::new(vec!["<standart>".into()], "<native-code>".to_string()), 0..0, None)).unwrap();
     context.types.add_signature(SignaturePath::borrowed("void"), TypeSignature::new(TypeValue::PrimitiveType(object_signature::PrimitiveType::Void), SourceFile::new(vec!["<standart>".into()], "<native-code>".to_string()), 0..0, None)).unwrap();
@@ -135,6 +141,9 @@ impl<'base> ResolveAst<'base> for FileStatementAst<'base> {
             FileStatementAst::Interface(interface_definition_ast) => interface_definition_ast.resolve(context, scope_location),
             FileStatementAst::Extend(extend_definition_ast) => extend_definition_ast.resolve(context, scope_location),
             FileStatementAst::Use(use_ast) => use_ast.resolve(context, scope_location),
+            FileStatementAst::TypeAlias(alias) => alias.resolve(context, scope_location),
+            FileStatementAst::Enum(definition) => definition.resolve(context, scope_location),
+            FileStatementAst::Variable(variable) => variable.resolve(context, scope_location),
         }
     }
 
@@ -145,6 +154,9 @@ impl<'base> ResolveAst<'base> for FileStatementAst<'base> {
             FileStatementAst::Interface(interface_definition_ast) => interface_definition_ast.finish(context, scope_location),
             FileStatementAst::Extend(extend_definition_ast) => extend_definition_ast.finish(context, scope_location),
             FileStatementAst::Use(use_ast) => use_ast.finish(context, scope_location),
+            FileStatementAst::TypeAlias(alias) => alias.finish(context, scope_location),
+            FileStatementAst::Enum(definition) => definition.finish(context, scope_location),
+            FileStatementAst::Variable(variable) => variable.finish(context, scope_location),
         }
     }
 
@@ -155,6 +167,9 @@ impl<'base> ResolveAst<'base> for FileStatementAst<'base> {
             FileStatementAst::Interface(interface_definition_ast) => interface_definition_ast.name(),
             FileStatementAst::Extend(extend_definition_ast) => extend_definition_ast.name(),
             FileStatementAst::Use(use_ast) => use_ast.name(),
+            FileStatementAst::TypeAlias(alias) => alias.name(),
+            FileStatementAst::Enum(definition) => definition.name(),
+            FileStatementAst::Variable(variable) => variable.name(),
         }
     }
 }
@@ -175,11 +190,12 @@ impl<'base> ResolveAst<'base> for FileStatementAst<'base> {
 /// * `Err(TirError)` - Semantic analysis errors with source locations
 /// 
 /// # Process
-/// 1. Initialize primitive types in the context
+/// 1. Initialize primitive types in the context and add the `std` module
 /// 2. Build module structure from file paths
 /// 3. Resolve all signatures (classes, functions, interfaces)
 /// 4. Finish phase validation and type checking
-/// 5. Return completed context or collected errors
+/// 5. Evaluate const function calls with constant arguments
+/// 6. Return completed context or collected errors
 /// 
 /// # Examples
 /// ```ignore
@@ -188,15 +204,26 @@ impl<'base> ResolveAst<'base> for FileStatementAst<'base> {
 /// // tir_context now contains complete semantic information
 /// ```
 pub fn build(files: Vec<Rc<FileAst<'_>>>) -> Result<TirContext<'_>, TirError> {
+    build_with_loader(files, None)
+}
+
+/// Builds the TIR like [`build`], loading imported modules that are missing through the loader
+///
+/// Modules loaded while the imports of a module are resolved are appended to
+/// the module list, so they are built in the same run as the files passed in.
+pub fn build_with_loader<'base>(files: Vec<Rc<FileAst<'base>>>, loader: Option<ModuleLoader<'base>>) -> Result<TirContext<'base>, TirError> {
     //let mut has_error = false;
-    let mut context: TirContext<'_> = TirContext::default();
+    let mut context: TirContext<'_> = TirContext {
+        loader,
+        ..Default::default()
+    };
 
     /*simplelog::debug!("Adding base module");
     let base_module = Module::phantom("<root>".into(), "<root>".into(), Rc::new(SourceFile::new(vec!["<memory>".into()], "")));
     build_module_signature(&mut context, base_module)?;*/
     build_primitive_types(&mut context);
 
-    for ast in files.into_iter() {
+    for ast in crate::prelude::files().into_iter().chain(files) {
         if let Err(error) = build_module(&mut context, ast.clone()) {
             if !context.errors.is_empty() {
                 return Err(TirError::multiple_errors(context.errors.clone()));
@@ -207,16 +234,20 @@ pub fn build(files: Vec<Rc<FileAst<'_>>>) -> Result<TirContext<'_>, TirError> {
         }
     }
 
-    #[allow(clippy::iter_kv_map)]
-    let modules = context.modules.iter().map(|(_, module)| module.get_ref()).collect::<Vec<_>>(); 
-    for module in modules.into_iter() {
-        if let Err(error) = build_file(&mut context, module) {
-            if !context.errors.is_empty() {
-                return Err(TirError::multiple_errors(context.errors.clone()));
-            } else {
-                return Err(error);
+    for phase in [BuildPhase::Resolve, BuildPhase::Variables, BuildPhase::Finish] {
+        let mut index = 0;
+
+        // Loaded modules are appended while the loop runs
+        while let Some(module) = context.modules.get_index(index).map(|(_, module)| module.get_ref()) {
+            index += 1;
+            if let Err(error) = build_file(&mut context, module, phase) {
+                if !context.errors.is_empty() {
+                    return Err(TirError::multiple_errors(context.errors.clone()));
+                } else {
+                    return Err(error);
+                }
+                //has_error = true;
             }
-            //has_error = true;
         }
     }
 
@@ -224,6 +255,7 @@ pub fn build(files: Vec<Rc<FileAst<'_>>>) -> Result<TirContext<'_>, TirError> {
         return Err(TirError::multiple_errors(context.errors.clone()));
     }
 
+    const_eval::evaluate_const_calls(&mut context)?;
     Ok(context)
 }
 
@@ -251,6 +283,7 @@ mod tests {
             ast: Some(Rc::new(FileAst {
                 file: source_file.clone(),
                 statements: vec![],
+                attributes: vec![],
             })),
             scope_location: ScopeLocation::UNDEFINED
         };
@@ -266,6 +299,7 @@ mod tests {
             ast: Some(Rc::new(FileAst {
                 file: source_file.clone(),
                 statements: vec![],
+                attributes: vec![],
             })),
             scope_location: ScopeLocation::UNDEFINED
         };
@@ -281,6 +315,7 @@ mod tests {
             ast: Some(Rc::new(FileAst {
                 file: source_file.clone(),
                 statements: vec![],
+                attributes: vec![],
             })),
             scope_location: ScopeLocation::UNDEFINED
         };
@@ -18,7 +18,10 @@
 //! ## Complex Types
 //! - **Classes**: User-defined object types with fields and methods
 //! - **Interfaces**: Contract definitions for class implementations
-//! - **Functions**: First-class function types with signatures
+//! - **Functions**: Function definitions with signatures
+//! - **Function types**: Structural types of callable values, like `func(i32): bool`
+//! - **Tuples**: Structural fixed size groups of values, like `(i32, string)`
+//! - **Enums**: Nominal types whose values are one of their variants
 //! - **Modules**: Namespace containers for organizing code
 //! - **References**: Pointer types using `ref` keyword
 //!
@@ -45,7 +48,7 @@ use strum_macros::{EnumDiscriminants, EnumIs};
 
 use crate::tir::{module::ModuleRef, resolver::TypeLocation};
 
-use super::{resolver::{class::ClassDefinition, function::FunctionDefinition, interface::{InterfaceDefinition, InterfaceFunctionDefinition}}, TirContext};
+use super::{resolver::{class::ClassDefinition, enum_definition::{EnumDefinition, EnumVariantDefinition}, function::FunctionDefinition, function_type::FunctionTypeDefinition, interface::{InterfaceDefinition, InterfaceFunctionDefinition}, tuple_type::TupleDefinition}, TirContext};
 
 /// Enumeration of primitive data types in the Timu language
 /// 
@@ -102,6 +105,10 @@ impl GetItem for PrimitiveType {
 /// - **Module**: Namespace containers that group related definitions
 /// - **Interface**: Contract specifications that classes can implement
 /// - **InterfaceFunction**: Function signatures defined within interfaces
+/// - **FunctionType**: Types of callable values such as lambdas and functions used as values
+/// - **Tuple**: Fixed size groups of values, their elements are accessed by position
+/// - **Enum**: Types whose values are one of the listed variants
+/// - **EnumVariant**: A variant of an enum with the types of its payload
 /// - **Reference**: Pointer/reference types that refer to other types
 /// 
 /// # Type Operations
@@ -138,6 +145,22 @@ pub enum TypeValue<'base> {
     #[allow(dead_code)]
     InterfaceFunction(InterfaceFunctionDefinition<'base>),
 
+    /// A structural function type, the type of a callable value
+    #[allow(dead_code)]
+    FunctionType(FunctionTypeDefinition),
+
+    /// A structural tuple type, like `(i32, string)`
+    #[allow(dead_code)]
+    Tuple(TupleDefinition),
+
+    /// An enum type, its members are the variants
+    #[allow(dead_code)]
+    Enum(EnumDefinition<'base>),
+
+    /// A variant of an enum, creating it produces a value of the enum
+    #[allow(dead_code)]
+    EnumVariant(EnumVariantDefinition<'base>),
+
     /// A reference type that points to another type
     #[allow(dead_code)]
     Reference(Box<TypeValue<'base>>),
@@ -200,6 +223,10 @@ impl GetItem for TypeValue<'_> {
             TypeValue::Module(module_ref) => module_ref.get_item_location(context, path),
             TypeValue::Interface(interface_definition) => interface_definition.get_item_location(context, path),
             TypeValue::InterfaceFunction(interface_function_definition) => interface_function_definition.get_item_location(context, path),
+            TypeValue::FunctionType(function_type) => function_type.get_item_location(context, path),
+            TypeValue::Tuple(tuple) => tuple.get_item_location(context, path),
+            TypeValue::Enum(definition) => definition.get_item_location(context, path),
+            TypeValue::EnumVariant(variant) => variant.get_item_location(context, path),
             TypeValue::Reference(reference) => reference.get_item_location(context, path),
         }
     }
@@ -221,7 +248,10 @@ impl TypeValue<'_> {
     /// # Type Compatibility Rules
     /// - **Primitives**: Exact primitive type match required
     /// - **Functions**: Signature compatibility (parameters and return type)
-    /// - **Classes**: Name-based equality comparison
+    /// - **Function types**: Same argument types, modifiers and return type
+    /// - **Tuples**: Same length, every element accepts the element at the same position
+    /// - **Enums**: Full name comparison, every enum is a type of its own
+    /// - **Classes**: Identity comparison, a subclass is accepted where its base class is expected
     /// - **Interfaces**: Full name comparison for interface equality
     /// - **Interface-Class**: Compatibility when class implements interface via extension
     /// - **Interface-Function**: Signature compatibility checking
@@ -231,18 +261,22 @@ impl TypeValue<'_> {
     /// - `i32` matches `i32` but not `i64`
     /// - `string` matches `string` but not `String` (if they're different types)
     /// - Class implementing interface matches interface type in compatible contexts
+    /// - `Child` matches `Base` when `class Child: Base`, but `Base` does not match `Child`
     /// - Function types match if parameters and return types are compatible
     pub fn is_same_type(&self, context: &TirContext<'_>, other: &Self) -> bool {
         match (self, other) {
             (TypeValue::PrimitiveType(left), TypeValue::PrimitiveType(right)) => Self::compare_primitive_types(left, right),
             (TypeValue::Function(left_function), TypeValue::Function(right_function)) => Self::compare_functions(left_function, right_function),
-            (TypeValue::Class(left_class), TypeValue::Class(right_class)) => Self::compare_classes(left_class, right_class),
+            (TypeValue::Class(left_class), TypeValue::Class(right_class)) => Self::compare_classes(left_class, right_class) || Self::is_subclass(context, right_class, left_class),
             (TypeValue::Module(_), TypeValue::Module(_)) => false,
             (TypeValue::InterfaceFunction(interface_function), TypeValue::Function(function)) => Self::compare_interface_function_and_function(interface_function, function),
             (TypeValue::Function(function), TypeValue::InterfaceFunction(interface_function)) => Self::compare_interface_function_and_function(interface_function, function),
             (TypeValue::InterfaceFunction(left_function), TypeValue::InterfaceFunction(right_function)) => Self::compare_interface_functions(left_function, right_function),
             (TypeValue::Interface(interface), TypeValue::Class(class)) => Self::compare_interface_and_class(context, interface, class),
             (TypeValue::Interface(left), TypeValue::Interface(right)) => left.full_name == right.full_name,
+            (TypeValue::FunctionType(left), TypeValue::FunctionType(right)) => left.arguments == right.arguments && left.return_type == right.return_type,
+            (TypeValue::Tuple(left), TypeValue::Tuple(right)) => Self::compare_tuples(context, left, right),
+            (TypeValue::Enum(left), TypeValue::Enum(right)) => left.full_name == right.full_name,
             _ => false,
         }
     }
@@ -257,16 +291,19 @@ impl TypeValue<'_> {
     /// A string containing the type's display name, borrowed when possible for efficiency
     /// 
     /// # Type Name Mappings
-    /// - **Primitives**: Standard type names (e.g., "String", "I32", "Bool")
-    /// - **Functions**: Function signature representation with parameters and return type
+    /// - **Primitives**: The keyword used in source code (e.g., "string", "i32", "bool")
+    /// - **Functions**: The function name as defined in source code
+    /// - **Function types**: The signature, like `func(i32): bool`
+    /// - **Tuples**: The element types, like `(i32, string)`
+    /// - **Enums**: The enum name, variants are prefixed with it, like `Shape.Circle`
     /// - **Classes**: The class name as defined in source code
     /// - **Interfaces**: The interface name as defined in source code
     /// - **Modules**: The module path/name
     /// - **References**: The name of the referenced type
     /// 
     /// # Examples
-    /// - `PrimitiveType::String` → `"String"`
-    /// - `PrimitiveType::I32` → `"I32"`
+    /// - `PrimitiveType::String` → `"string"`
+    /// - `PrimitiveType::I32` → `"i32"`
     /// - Class named "MyClass" → `"MyClass"`
     /// - Interface named "IMyInterface" → `"IMyInterface"`
     /// 
@@ -279,25 +316,29 @@ impl TypeValue<'_> {
     pub fn get_name(&self) -> Cow<'_, str> {
         match self {
             TypeValue::PrimitiveType(primitive) => match primitive {
-                PrimitiveType::String => "String".into(),
-                PrimitiveType::Bool => "Bool".into(),
-                PrimitiveType::I8 => "I8".into(),
-                PrimitiveType::U8 => "U8".into(),
-                PrimitiveType::I16 => "I16".into(),
-                PrimitiveType::U16 => "U16".into(),
-                PrimitiveType::I32 => "I32".into(),
-                PrimitiveType::U32 => "U32".into(),
-                PrimitiveType::I64 => "I64".into(),
-                PrimitiveType::U64 => "U64".into(),
-                PrimitiveType::Float => "Float".into(),
-                PrimitiveType::Double => "Double".into(),
-                PrimitiveType::Void => "Void".into(),
+                PrimitiveType::String => "string".into(),
+                PrimitiveType::Bool => "bool".into(),
+                PrimitiveType::I8 => "i8".into(),
+                PrimitiveType::U8 => "u8".into(),
+                PrimitiveType::I16 => "i16".into(),
+                PrimitiveType::U16 => "u16".into(),
+                PrimitiveType::I32 => "i32".into(),
+                PrimitiveType::U32 => "u32".into(),
+                PrimitiveType::I64 => "i64".into(),
+                PrimitiveType::U64 => "u64".into(),
+                PrimitiveType::Float => "float".into(),
+                PrimitiveType::Double => "double".into(),
+                PrimitiveType::Void => "void".into(),
             },
             TypeValue::Function(function) => function.name.text.into(),
             TypeValue::Class(class) => class.name.text.into(),
             TypeValue::Module(_) => "Module".into(),
             TypeValue::Interface(interface) => interface.name.text.into(),
             TypeValue::InterfaceFunction(interface_function) => interface_function.name.text.into(),
+            TypeValue::FunctionType(function_type) => function_type.name.as_str().into(),
+            TypeValue::Tuple(tuple) => tuple.name.as_str().into(),
+            TypeValue::Enum(definition) => definition.name.text.into(),
+            TypeValue::EnumVariant(variant) => format!("{}.{}", variant.enum_name.text, variant.name.text).into(),
             TypeValue::Reference(reference) => format!("ref {}", reference.get_name()).into()
         }
     }
@@ -310,6 +351,20 @@ impl TypeValue<'_> {
         std::ptr::eq(left, right)
     }
 
+    /// Checks if `base` is one of the base classes of `class`
+    fn is_subclass(context: &TirContext<'_>, class: &ClassDefinition, base: &ClassDefinition) -> bool {
+        let mut current = class.base;
+        while let Some(location) = current {
+            match context.types.get_from_location(location).map(|signature| signature.value.as_ref()) {
+                Some(TypeValue::Class(parent)) if std::ptr::eq(parent, base) => return true,
+                Some(TypeValue::Class(parent)) => current = parent.base,
+                _ => return false,
+            }
+        }
+
+        false
+    }
+
     fn compare_interface_functions(left: &InterfaceFunctionDefinition, right: &InterfaceFunctionDefinition) -> bool {
         if left.name.text != right.name.text ||
             left.arguments.len() != right.arguments.len() ||
@@ -318,6 +373,11 @@ impl TypeValue<'_> {
         }
 
         for (left_arg, right_arg) in left.arguments.iter().zip(right.arguments.iter()) {
+            // `this` is always the interface of its own function
+            if left_arg.name.text == "this" && right_arg.name.text == "this" {
+                continue;
+            }
+
             if left_arg.name.text != right_arg.name.text || left_arg.field_type != right_arg.field_type {
                 return false;
             }
@@ -326,16 +386,31 @@ impl TypeValue<'_> {
         true
     }
 
+    /// Element-wise comparison, a nullable element accepts a non-nullable one
+    fn compare_tuples(context: &TirContext<'_>, left: &TupleDefinition, right: &TupleDefinition) -> bool {
+        left.elements.len() == right.elements.len() && left.elements.iter().zip(right.elements.iter()).all(|(left, right)| {
+            let same_type = left.field_type == right.field_type || match (context.types.get_from_location(left.field_type), context.types.get_from_location(right.field_type)) {
+                (Some(left), Some(right)) => left.value.is_same_type(context, &right.value),
+                _ => false,
+            };
+
+            same_type && (left.is_nullable || !right.is_nullable)
+        })
+    }
+
     fn compare_interface_and_class(context: &TirContext<'_>, interface: &InterfaceDefinition, class: &ClassDefinition) -> bool {
         for type_location in class.extends.iter() {
-            if let Some(TypeValue::Interface(class_interface)) = context.types.get_from_location(*type_location).map(|signature| signature.value.as_ref()) {
-                if class_interface.full_name == interface.full_name {
+            if let Some(TypeValue::Interface(class_interface)) = context.types.get_from_location(*type_location).map(|signature| signature.value.as_ref())
+                && class_interface.full_name == interface.full_name {
                     return true;
                 }
-            }
         }
-        
-        false
+
+        // Interfaces implemented by a base class are implemented by its subclasses too
+        match class.base.and_then(|base| context.types.get_from_location(base)).map(|signature| signature.value.as_ref()) {
+            Some(TypeValue::Class(base)) => Self::compare_interface_and_class(context, interface, base),
+            _ => false,
+        }
     }
 
     fn compare_interface_function_and_function(left: &InterfaceFunctionDefinition, right: &FunctionDefinition) -> bool {
@@ -346,6 +421,11 @@ impl TypeValue<'_> {
         }
 
         for (left_arg, right_arg) in left.arguments.iter().zip(right.arguments.iter()) {
+            // `this` is the interface in the signature and the class in the implementation
+            if left_arg.name.text == "this" && right_arg.name.text == "this" {
+                continue;
+            }
+
             if left_arg.name.text != right_arg.name.text || left_arg.field_type != right_arg.field_type {
                 return false;
             }
@@ -0,0 +1,182 @@
+//! Questions about a source position of a type checked program.
+//!
+//! Editors point at a byte offset of a module and want to know what is there.
+//! The answers come from the side tables the type checker fills while it
+//! resolves function bodies, and from the positions stored in every
+//! [`TypeSignature`](super::TypeSignature):
+//!
+//! - Identifiers used in expressions, see [`TirContext::identifiers`]
+//! - Function calls, see [`TirContext::call_targets`]
+//! - Local variable definitions, see [`TirContext::variable_types`]
+//! - Names of module level functions, classes and interfaces
+//!
+//! # Usage
+//!
+//! ```ignore
+//! let context = process_ast(files)?;
+//! if let Some(hover) = query::hover(&context, &["main".to_string()], 42) {
+//!     println!("{}", hover.text); // a: i32
+//! }
+//! ```
+
+use std::{ops::Range, sync::Arc};
+
+use super::{context::SpanKey, dump::function_signature, resolver::{statement::type_name, TypeLocation}, TirContext, TypeValue};
+
+/// Description of the item at a source position
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hover {
+    /// Byte range of the name the description belongs to
+    pub position: Range<usize>,
+    /// Declaration of the item, such as `a: i32` or `func add(a: i32): i32`
+    pub text: String,
+}
+
+/// Place an item is defined at
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition {
+    /// Module path of the file the item is defined in
+    pub path: Arc<Vec<String>>,
+    /// Byte range of the name of the item
+    pub position: Range<usize>,
+}
+
+enum Target {
+    Identifier { definition: Option<SpanKey>, location: TypeLocation },
+    Call(TypeLocation),
+    Variable(TypeLocation),
+    Declaration(TypeLocation),
+}
+
+/// Describes the identifier, call or declaration at the given offset of a module
+pub fn hover(context: &TirContext<'_>, path: &[String], offset: usize) -> Option<Hover> {
+    let (key, target) = find(context, path, offset)?;
+    let name = source_text(context, &key)?;
+
+    let text = match target {
+        Target::Identifier { definition: Some(_), location } | Target::Variable(location) => describe_variable(context, name, location),
+        Target::Identifier { definition: None, location } | Target::Call(location) | Target::Declaration(location) => describe(context, name, location),
+    };
+
+    Some(Hover { position: key.position, text })
+}
+
+/// Finds where the identifier, call or declaration at the given offset of a module is defined
+pub fn definition(context: &TirContext<'_>, path: &[String], offset: usize) -> Option<Definition> {
+    let (key, target) = find(context, path, offset)?;
+
+    let location = match target {
+        Target::Identifier { definition: Some(definition), .. } => return Some(Definition { path: definition.path, position: definition.position }),
+        Target::Variable(_) => return Some(Definition { path: key.path, position: key.position }),
+        Target::Identifier { location, .. } | Target::Call(location) | Target::Declaration(location) => location,
+    };
+
+    let signature = context.types.get_from_location(location)?;
+    match signature.position.is_empty() {
+        true => None,
+        false => Some(Definition { path: signature.file.path.clone(), position: signature.position.clone() }),
+    }
+}
+
+fn find(context: &TirContext<'_>, path: &[String], offset: usize) -> Option<(SpanKey, Target)> {
+    let matches = |key: &SpanKey| key.path.as_slice() == path && key.position.contains(&offset);
+
+    if let Some((key, target)) = context.identifiers.iter().find(|(key, _)| matches(key)) {
+        return Some((key.clone(), Target::Identifier { definition: target.definition.clone(), location: target.location }));
+    }
+
+    if let Some((key, location)) = context.call_targets.iter().find(|(key, _)| matches(key)) {
+        return Some((key.clone(), Target::Call(*location)));
+    }
+
+    if let Some((key, location)) = context.variable_types.iter().find(|(key, _)| matches(key)) {
+        return Some((key.clone(), Target::Variable(*location)));
+    }
+
+    context.types.iter()
+        .find(|(_, _, signature)| signature.file.path.as_slice() == path && signature.position.contains(&offset))
+        .map(|(_, location, signature)| (SpanKey { path: signature.file.path.clone(), position: signature.position.clone() }, Target::Declaration(location)))
+}
+
+fn source_text<'a>(context: &'a TirContext<'_>, key: &SpanKey) -> Option<&'a str> {
+    context.modules.values()
+        .find(|module| module.file.path == key.path)
+        .and_then(|module| module.file.code.get(key.position.clone()))
+}
+
+fn describe_variable(context: &TirContext<'_>, name: &str, location: TypeLocation) -> String {
+    match context.types.get_from_location(location).map(|signature| signature.value.as_ref()) {
+        Some(TypeValue::Function(_)) => describe(context, name, location),
+        _ => format!("{name}: {}", type_name(context, location)),
+    }
+}
+
+fn describe(context: &TirContext<'_>, name: &str, location: TypeLocation) -> String {
+    match context.types.get_from_location(location).map(|signature| signature.value.as_ref()) {
+        Some(TypeValue::Function(function)) => format!("{}func {}", if function.is_public { "pub " } else { "" }, function_signature(context, function.name.text, &function.arguments, function.return_type)),
+        Some(TypeValue::Class(_)) => format!("class {name}"),
+        Some(TypeValue::Interface(_)) => format!("interface {name}"),
+        Some(TypeValue::Enum(_)) => format!("enum {name}"),
+        Some(TypeValue::Module(_)) => format!("module {name}"),
+        _ => format!("{name}: {}", type_name(context, location)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{file::SourceFile, nom_tools::State, process_ast, process_code};
+
+    use super::{definition, hover};
+
+    static CODE: &str = "pub func add(a: i32, b: i32): i32 { var total = a + b; return total; } class Point { x: i32; } func main(): i32 { return add(1, 2); }";
+
+    fn offset(pattern: &str, nth: usize) -> usize {
+        CODE.match_indices(pattern).nth(nth).unwrap().0
+    }
+
+    #[test]
+    fn hover_text() {
+        let state = State::new(SourceFile::new(vec!["main".into()], CODE.to_string()));
+        let context = process_ast(vec![process_code(&state).unwrap().into()]).unwrap();
+        let path = vec!["main".to_string()];
+
+        let text = |offset| hover(&context, &path, offset).map(|hover| hover.text);
+        assert_eq!(text(offset("total", 0)).as_deref(), Some("total: i32"));
+        assert_eq!(text(offset("total", 1) + 2).as_deref(), Some("total: i32"));
+        assert_eq!(text(offset("a + b", 0)).as_deref(), Some("a: i32"));
+        assert_eq!(text(offset("add", 1)).as_deref(), Some("pub func add(a: i32, b: i32): i32"));
+        assert_eq!(text(offset("add", 0)).as_deref(), Some("pub func add(a: i32, b: i32): i32"));
+        assert_eq!(text(offset("Point", 0)).as_deref(), Some("class Point"));
+        assert_eq!(text(offset("return", 0)), None);
+        assert_eq!(hover(&context, &["other".to_string()], offset("total", 0)), None);
+
+        let hover = hover(&context, &path, offset("total", 1)).unwrap();
+        assert_eq!(hover.position, offset("total", 1)..offset("total", 1) + 5);
+    }
+
+    #[test]
+    fn definitions() {
+        let state = State::new(SourceFile::new(vec!["main".into()], CODE.to_string()));
+        let context = process_ast(vec![process_code(&state).unwrap().into()]).unwrap();
+        let path = vec!["main".to_string()];
+
+        let position = |offset| definition(&context, &path, offset).map(|definition| definition.position);
+        assert_eq!(position(offset("total", 1)), Some(offset("total", 0)..offset("total", 0) + 5));
+        assert_eq!(position(offset("total", 0)), Some(offset("total", 0)..offset("total", 0) + 5));
+        assert_eq!(position(offset("a + b", 0)), Some(offset("a:", 0)..offset("a:", 0) + 1));
+        assert_eq!(position(offset("add", 1)), Some(offset("add", 0)..offset("add", 0) + 3));
+        assert_eq!(position(offset("return", 0)), None);
+        assert_eq!(definition(&context, &path, offset("add", 1)).unwrap().path.as_slice(), path.as_slice());
+    }
+
+    #[test]
+    fn imported_definition() {
+        let lib = State::new(SourceFile::new(vec!["lib".into()], "pub func one(): i32 { return 1; }".to_string()));
+        let main = State::new(SourceFile::new(vec!["main".into()], "use lib.one; func main(): i32 { return one(); }".to_string()));
+        let context = process_ast(vec![process_code(&lib).unwrap().into(), process_code(&main).unwrap().into()]).unwrap();
+
+        let definition = definition(&context, &["main".to_string()], 39).unwrap();
+        assert_eq!(definition.path.as_slice(), &["lib".to_string()]);
+        assert_eq!(definition.position, 9..12);
+    }
+}
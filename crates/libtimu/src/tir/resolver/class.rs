@@ -22,20 +22,25 @@
 //! 4. **Type validation**: Ensure all referenced types exist
 //!
 //! ## Phase 3: Finalization
-//! 1. **Method completion**: Complete method body analysis in finish phase
-//! 2. **Cross-reference validation**: Validate method calls and field access
-//! 3. **Inheritance preparation**: Prepare for extension and interface implementation
+//! 1. **Base class validation**: The base class must be a class
+//! 2. **Override validation**: Overridden members must match the base class members
+//! 3. **Method completion**: Complete method body analysis in finish phase
+//! 4. **Cross-reference validation**: Validate method calls and field access
 //!
 //! # Class Components
 //!
 //! ## Fields
 //! - **Instance variables**: Data members that belong to each class instance
+//! - **Static fields**: `static` fields belong to the class and are accessed
+//!   through the type name, like `Counter.count = 1;`
 //! - **Type annotations**: All fields must have explicit type declarations
 //! - **Visibility**: Support for public and private field access
 //! - **Initialization**: Fields are initialized during object construction
 //!
 //! ## Methods
 //! - **Instance methods**: Functions that operate on class instances
+//! - **Static methods**: Functions without `this` are called through the type
+//!   name, like `Counter.create()`
 //! - **Constructor patterns**: Special handling for initialization methods
 //! - **Parameter validation**: Type checking for method parameters
 //! - **Return types**: All methods must specify return types
@@ -63,10 +68,19 @@
 //! - **Data hiding**: Private members inaccessible outside class
 //! - **Interface contracts**: Support for implementing interfaces
 //!
+//! ## Inheritance
+//! A class can inherit from a single base class with `class Child: Base { }`:
+//! - **Inherited members**: Fields and methods of the base classes are found
+//!   through [`ClassDefinition::get_item_location`] after the own members
+//! - **Overriding**: A method can be redefined with the same signature, which
+//!   is checked with the function comparison of the type system. Fields cannot
+//!   be redefined
+//! - **Assignability**: A subclass is accepted where its base class is expected
+//! - **Cycles**: `class A: B {}` with `class B: A {}` is a circular reference
+//!
 //! ## Future Extensibility
 //! The architecture supports future object-oriented features:
-//! - **Inheritance**: Single inheritance from base classes
-//! - **Polymorphism**: Method overriding and virtual dispatch
+//! - **Polymorphism**: Virtual dispatch of overridden methods
 //! - **Abstract classes**: Classes that cannot be instantiated
 //! - **Generic classes**: Parameterized types for code reuse
 //!
@@ -82,10 +96,10 @@
 use std::{borrow::Cow, collections::HashSet, rc::Rc};
 
 use crate::{
-    ast::{ClassDefinitionAst, ClassDefinitionFieldAst}, map::TimuHashMap, nom_tools::{Span, ToRange}, tir::{context::TirContext, object_signature::{GetItem, TypeValue, TypeValueDiscriminants}, resolver::{get_object_location_or_resolve, BuildFullNameLocater}, scope::{ScopeLocation, TypeVariableInformation, VariableInformation}, signature::SignaturePath, TirError, TypeSignature}
+    ast::{ClassDefinitionAst, ClassDefinitionFieldAst, FunctionArgumentAst}, map::TimuHashMap, nom_tools::{Span, ToRange}, tir::{ast_signature::AstSignatureValue, context::TirContext, module::ModuleRef, object_signature::{GetItem, TypeValue, TypeValueDiscriminants}, resolver::{build_type_name, get_object_location_or_resolve, BuildFullNameLocater}, scope::{ScopeLocation, TypeVariableInformation, VariableInformation}, signature::SignaturePath, TirError, TypeSignature}
 };
 
-use super::{TypeLocation, ResolveAst};
+use super::{build_signature_path, find_ast_signature, TypeLocation, ResolveAst};
 
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -100,6 +114,10 @@ pub struct ClassDefinition<'base> {
     pub name: Span<'base>,
     pub fields: TimuHashMap<'base, Cow<'base, str>, TypeVariableInformation<'base>>,
     pub extends: HashSet<TypeLocation>,
+    /// The class the fields and methods are inherited from
+    pub base: Option<TypeLocation>,
+    /// Static fields and the functions without `this`, they belong to the class instead of its objects
+    pub static_members: HashSet<Cow<'base, str>>,
 }
 
 impl PartialEq for ClassDefinition<'_> {
@@ -108,12 +126,39 @@ impl PartialEq for ClassDefinition<'_> {
     }
 }
 
+impl<'base> ClassDefinition<'base> {
+    /// Finds a member of the class, falling back to the members of the base classes
+    pub fn get_member<'a>(&'a self, context: &'a TirContext<'base>, path: &str) -> Option<&'a TypeVariableInformation<'base>> {
+        match self.fields.get(path) {
+            Some(member) => Some(member),
+            None => match self.base.and_then(|base| context.types.get_from_location(base)).map(|signature| signature.value.as_ref()) {
+                Some(TypeValue::Class(base)) => base.get_member(context, path),
+                _ => None,
+            },
+        }
+    }
+
+    /// Whether the member is accessed through the type instead of an object, `None` for unknown members
+    pub fn is_static_member(&self, context: &TirContext<'base>, path: &str) -> Option<bool> {
+        match self.fields.get(path) {
+            Some(_) => Some(self.static_members.contains(path)),
+            None => match self.base.and_then(|base| context.types.get_from_location(base)).map(|signature| signature.value.as_ref()) {
+                Some(TypeValue::Class(base)) => base.is_static_member(context, path),
+                _ => None,
+            },
+        }
+    }
+}
+
 impl GetItem for ClassDefinition<'_> {
-    fn get_item_location(&self, _: &TirContext<'_>, path: &str) -> Option<TypeLocation> {
-        self
-            .fields
-            .get(path)
-            .map(|item| item.location)
+    fn get_item_location(&self, context: &TirContext<'_>, path: &str) -> Option<TypeLocation> {
+        match self.fields.get(path) {
+            Some(item) => Some(item.location),
+            None => match self.base.and_then(|base| context.types.get_from_location(base)).map(|signature| signature.value.as_ref()) {
+                Some(TypeValue::Class(base)) => base.get_item_location(context, path),
+                _ => None,
+            },
+        }
     }
 }
 
@@ -124,8 +169,15 @@ impl<'base> ResolveAst<'base> for ClassDefinitionAst<'base> {
         let full_name = self.build_full_name(context, BuildFullNameLocater::Scope(scope_location), None);
         let module_ref = context.get_scope(scope_location).expect("Scope not found").module_ref.clone();
 
+        self.check_circular_inheritance(context, &module_ref)?;
+
         let (signature_path, class_location) = context.reserve_object_location(self.name(), TypeValueDiscriminants::Class, SignaturePath::owned(full_name), &module_ref, self.name.to_range(), self.name.state.file.clone())?;
+        let base = match &self.base_class {
+            Some(base_class) => Some(get_object_location_or_resolve(context, base_class, &module_ref, scope_location)?),
+            None => None,
+        };
         let mut fields = TimuHashMap::<'base, Cow<'_, str>, TypeVariableInformation<'base>>::default();
+        let mut static_members = HashSet::new();
 
         context.get_mut_scope(scope_location).expect("Scope not found, it is a bug").set_current_type(class_location);
 
@@ -137,9 +189,13 @@ impl<'base> ResolveAst<'base> for ClassDefinitionAst<'base> {
                     let field_type = get_object_location_or_resolve(context, &field.field_type, &module_ref, scope_location)?;
                     let is_public = field.is_public.is_some();
 
-                    let variable = TypeVariableInformation::new_with_visibility(field.name.clone(), field_type, false, false, false, is_public);
+                    let nullable = field.field_type.nullable;
+                    let variable = TypeVariableInformation::new_with_visibility(field.name.clone(), field_type, nullable, false, false, is_public);
                     fields.validate_insert(Cow::Borrowed(field.name.text), variable)?;
-                    context.get_mut_scope(scope_location).expect("Scope not found, it is a bug").add_variable(VariableInformation::new_with_visibility(field.name.clone(), field_type, false, false, false, is_public))?;
+                    if field.is_static.is_some() {
+                        static_members.insert(Cow::Borrowed(field.name.text));
+                    }
+                    context.get_mut_scope(scope_location).expect("Scope not found, it is a bug").add_variable(VariableInformation::new_with_visibility(field.name.clone(), field_type, nullable, false, false, is_public))?;
                 }
                 ClassDefinitionFieldAst::Function(function) => {
                     let type_name = function.build_full_name(context, BuildFullNameLocater::Module(&module_ref), None);
@@ -153,6 +209,9 @@ impl<'base> ResolveAst<'base> for ClassDefinitionAst<'base> {
 
                     let variable = TypeVariableInformation::new_with_visibility(function.name.clone(), function_type_location, false, false, false, is_public);
                     fields.validate_insert((*function.name.text).into(), variable)?;
+                    if !matches!(function.arguments.first(), Some(FunctionArgumentAst::This(_))) {
+                        static_members.insert(Cow::Borrowed(function.name.text));
+                    }
                     context.get_mut_scope(scope_location).expect("Scope not found, it is a bug").add_variable(VariableInformation::new_with_visibility(function.name.clone(), function_type_location, false, false, false, is_public))?;
                     function_signatures.push((function_type_location, function));
                 }
@@ -163,6 +222,8 @@ impl<'base> ResolveAst<'base> for ClassDefinitionAst<'base> {
             name: self.name.clone(),
             fields,
             extends: Default::default(),
+            base,
+            static_members,
         }), self.name.state.file.clone(), self.name.to_range(), None);
 
         context.publish_object_location(signature_path.clone(), class_signature);
@@ -171,6 +232,8 @@ impl<'base> ResolveAst<'base> for ClassDefinitionAst<'base> {
     }
     
     fn finish(&self, context: &mut TirContext<'base>, scope: ScopeLocation) -> Result<(), TirError> {
+        let class_location = context.get_scope(scope).expect("Scope not found, it is a bug").current_type;
+        self.check_overrides(context, class_location)?;
 
         for field in self.fields.iter() {
             if let ClassDefinitionFieldAst::Function(function) = field {
@@ -189,8 +252,78 @@ impl<'base> ResolveAst<'base> for ClassDefinitionAst<'base> {
     }
 }
 
+impl<'base> ClassDefinitionAst<'base> {
+    /// Walks the base classes of the class and reports a cycle that comes back to it
+    fn check_circular_inheritance(&self, context: &mut TirContext<'base>, module_ref: &ModuleRef<'base>) -> Result<(), TirError> {
+        let mut current = self.base_class.clone().map(|base_class| (base_class, module_ref.clone()));
+        let mut visited = HashSet::new();
+
+        while let Some((base_class, module)) = current {
+            let base_path = build_signature_path(context, build_type_name(&base_class).as_str(), &module);
+
+            // A missing base class is reported while resolving it
+            let Some(location) = find_ast_signature(context, &module, base_path) else {
+                return Ok(());
+            };
+
+            // A cycle between the base classes is reported by one of them
+            if !visited.insert(location) {
+                return Ok(());
+            }
+
+            current = match context.ast_signatures.get_from_location(location) {
+                Some(signature) => match signature.value.as_ref() {
+                    AstSignatureValue::Class(class) if class.index == self.index => return Err(TirError::circular_reference(self.name.to_range(), self.name.state.file.clone())),
+                    AstSignatureValue::Class(class) => class.base_class.clone().zip(signature.extra.clone()),
+                    _ => None,
+                },
+                None => None,
+            };
+        }
+
+        Ok(())
+    }
+
+    /// Validates the base class and the members overriding the members of the base classes
+    fn check_overrides(&self, context: &TirContext<'base>, class_location: TypeLocation) -> Result<(), TirError> {
+        let Some(TypeValue::Class(class)) = context.types.get_from_location(class_location).map(|signature| signature.value.as_ref()) else {
+            return Ok(());
+        };
+
+        let (Some(base_location), Some(base_class)) = (class.base, self.base_class.as_ref()) else {
+            return Ok(());
+        };
+
+        let Some(TypeValue::Class(base)) = context.types.get_from_location(base_location).map(|signature| signature.value.as_ref()) else {
+            return Err(TirError::invalid_type(base_class.to_range(), "only class type is valid", base_class.names.last().unwrap().state.file.clone()));
+        };
+
+        for (name, member) in class.fields.iter() {
+            let Some(base_member) = base.get_member(context, name) else {
+                continue;
+            };
+
+            let member_value = context.types.get_from_location(member.location).map(|signature| signature.value.as_ref());
+            let base_member_value = context.types.get_from_location(base_member.location).map(|signature| signature.value.as_ref());
+
+            let is_valid = match (member_value, base_member_value) {
+                (Some(TypeValue::Function(function)), Some(TypeValue::Function(base_function))) => function.overrides(base_function),
+                _ => false,
+            };
+
+            if !is_valid {
+                return Err(TirError::invalid_override(name.to_string(), member.span.to_range(), member.span.state.file.clone(), base_member.span.to_range(), base_member.span.state.file.clone()));
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use rstest::rstest;
+
     use crate::{file::SourceFile, nom_tools::State, process_code, tir::TirError};
 
     #[test]
@@ -259,4 +392,135 @@ class TestClass {
         crate::tir::build(vec![ast.into()]).unwrap();
         Ok(())
     }
+
+    #[test]
+    fn inherited_members() -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], r#"
+class Base {
+    pub x: i32;
+    pub func get_x(this): i32 { return ref this.x; }
+}
+
+class Child: Base {
+    pub y: i32;
+    pub func sum(this): i32 { return ref this.x + this.get_x() + ref this.y; }
+}
+
+func total(child: Child): i32 {
+    return ref child.x + child.get_x() + child.sum();
+}
+    "#.to_string()));
+        let ast = process_code(&state)?;
+        crate::tir::build(vec![ast.into()]).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn subclass_is_assignable_to_base() -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], r#"
+class Base {}
+class Middle: Base {}
+class Child: Middle {}
+
+func base(value: Base): Base { return value; }
+func child(value: Child): Base { return base(value); }
+    "#.to_string()));
+        let ast = process_code(&state)?;
+        crate::tir::build(vec![ast.into()]).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn base_is_not_assignable_to_subclass() -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], r#"
+class Base {}
+class Child: Base {}
+
+func child(value: Child): Child { return value; }
+func base(value: Base): Child { return child(value); }
+    "#.to_string()));
+        let ast = process_code(&state)?;
+        crate::tir::build(vec![ast.into()]).unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn base_class_from_other_module() -> Result<(), TirError> {
+        let state_1 = State::new(SourceFile::new(vec!["lib".into()], "pub class Base { pub x: i32; }".to_string()));
+        let state_2 = State::new(SourceFile::new(vec!["main".into()], "use lib; class Child: lib.Base { func get(this): i32 { return ref this.x; } }".to_string()));
+        let ast_1 = process_code(&state_1)?;
+        let ast_2 = process_code(&state_2)?;
+        crate::tir::build(vec![ast_1.into(), ast_2.into()]).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn valid_override() -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], r#"
+class Base { pub func area(this, scale: i32): i32 { return scale; } }
+class Child: Base { pub func area(this, scale: i32): i32 { return scale * 2; } }
+    "#.to_string()));
+        let ast = process_code(&state)?;
+        crate::tir::build(vec![ast.into()]).unwrap();
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("class Base { pub func area(this): i32 {} } class Child: Base { pub func area(this, scale: i32): i32 {} }")]
+    #[case("class Base { pub func area(this): i32 {} } class Child: Base { pub func area(this): i64 {} }")]
+    #[case("class Base { pub func area(this): i32 {} } class Child: Base { func area(this): i32 {} }")]
+    #[case("class Base { pub func area(this): i32 {} } class Child: Base { area: i32; }")]
+    #[case("class Base { x: i32; } class Child: Base { x: i32; }")]
+    #[case("class Base { x: i32; } class Middle: Base {} class Child: Middle { func x(): i32 {} }")]
+    fn invalid_override(#[case] code: &str) -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], code.to_string()));
+        let ast = process_code(&state)?;
+
+        match crate::tir::build(vec![ast.into()]).unwrap_err() {
+            TirError::InvalidOverride(_) => {},
+            error => panic!("Expected InvalidOverride error, got {error:?}"),
+        }
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("class A: A {}")]
+    #[case("class A: B {} class B: A {}")]
+    #[case("class A: B {} class B: C {} class C: A {}")]
+    fn circular_inheritance(#[case] code: &str) -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], code.to_string()));
+        let ast = process_code(&state)?;
+
+        match crate::tir::build(vec![ast.into()]).unwrap_err() {
+            TirError::CircularReference(_) => {},
+            error => panic!("Expected CircularReference error, got {error:?}"),
+        }
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("interface IBase {} class Child: IBase {}")]
+    #[case("func base(): i32 {} class Child: base {}")]
+    fn base_must_be_class(#[case] code: &str) -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], code.to_string()));
+        let ast = process_code(&state)?;
+
+        match crate::tir::build(vec![ast.into()]).unwrap_err() {
+            TirError::InvalidType(_) => {},
+            error => panic!("Expected InvalidType error, got {error:?}"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn missing_base_class() -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], "class Child: Base {}".to_string()));
+        let ast = process_code(&state)?;
+
+        match crate::tir::build(vec![ast.into()]).unwrap_err() {
+            TirError::TypeNotFound(_) => {},
+            error => panic!("Expected TypeNotFound error, got {error:?}"),
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,210 @@
+//! Enum definition resolution for the Timu TIR system.
+//!
+//! An enum is a nominal type whose values are one of its variants. Variants
+//! can carry a payload, the values given when the variant is created:
+//!
+//! ```timu
+//! enum Shape {
+//!     Circle(float),
+//!     Rect(float, float),
+//!     Empty,
+//! }
+//!
+//! var circle = Shape.Circle(1.5);    // Payload variants are called like functions
+//! var empty = ref Shape.Empty;       // Plain variants are values of the enum
+//! ```
+//!
+//! # Type Registration
+//!
+//! The enum is registered like a class, under its name in the module. Every
+//! variant gets a type of its own below the enum, `module.Shape.Circle`, that
+//! holds the payload types and the position of the variant. Variants are only
+//! reachable through their enum, they are not types of the module.
+//!
+//! Creating a variant always produces a value of the enum type. Variants
+//! belong to the enum itself, using them through a value of the enum is
+//! reported like other static members used through an object.
+//!
+//! # Matching
+//!
+//! Every variant is a case of a match on the enum, the match has to cover all
+//! of them. A variant pattern binds the payload values to readonly names, see
+//! [`super::statement`].
+
+use std::borrow::Cow;
+
+use crate::{
+    ast::EnumDefinitionAst,
+    map::TimuHashMap,
+    nom_tools::{Span, ToRange},
+    tir::{context::TirContext, object_signature::{GetItem, TypeValue, TypeValueDiscriminants}, resolver::{get_object_location_or_resolve, BuildFullNameLocater}, scope::{ScopeLocation, TypeVariableInformation}, signature::SignaturePath, TirError, TypeSignature},
+};
+
+use super::{ResolveAst, TypeLocation};
+
+/// Resolved enum type
+///
+/// # Fields
+///
+/// - `name`: The enum name as written in the definition
+/// - `full_name`: Module qualified name, like `main.Shape`
+/// - `variants`: Types of the variants, in declaration order
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumDefinition<'base> {
+    pub name: Span<'base>,
+    pub full_name: String,
+    pub variants: TimuHashMap<'base, Cow<'base, str>, TypeVariableInformation<'base>>,
+}
+
+impl GetItem for EnumDefinition<'_> {
+    /// Variants are the only members of an enum
+    fn get_item_location(&self, _: &TirContext<'_>, path: &str) -> Option<TypeLocation> {
+        self.variants.get(path).map(|variant| variant.location)
+    }
+}
+
+/// Value carried by an enum variant
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumPayload<'base> {
+    pub field_type: TypeLocation,
+    pub field_type_span: Span<'base>,
+    pub is_nullable: bool,
+    pub is_reference: bool,
+}
+
+/// Resolved variant of an enum
+///
+/// # Fields
+///
+/// - `name`: The variant name
+/// - `enum_name`: Name of the enum the variant belongs to
+/// - `enum_type`: Location of the enum, the type of every created variant
+/// - `discriminant`: Position of the variant in the enum, starting from `0`
+/// - `payload`: Types of the carried values, empty for a plain variant
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumVariantDefinition<'base> {
+    pub name: Span<'base>,
+    pub enum_name: Span<'base>,
+    pub enum_type: TypeLocation,
+    pub discriminant: usize,
+    pub payload: Vec<EnumPayload<'base>>,
+}
+
+impl GetItem for EnumVariantDefinition<'_> {
+    fn get_item_location(&self, _: &TirContext<'_>, _: &str) -> Option<TypeLocation> {
+        None
+    }
+}
+
+impl<'base> ResolveAst<'base> for EnumDefinitionAst<'base> {
+    /// Registers the enum and the types of its variants
+    fn resolve(&self, context: &mut TirContext<'base>, scope_location: ScopeLocation) -> Result<TypeLocation, TirError> {
+        simplelog::debug!("Resolving enum: <u><b>{}</b></u>", self.name.text);
+
+        let full_name = self.build_full_name(context, BuildFullNameLocater::Scope(scope_location), None);
+        let module_ref = context.get_scope(scope_location).expect("Scope not found").module_ref.clone();
+        let (signature_path, enum_location) = context.reserve_object_location(self.name(), TypeValueDiscriminants::Enum, SignaturePath::owned(full_name.clone()), &module_ref, self.name.to_range(), self.name.state.file.clone())?;
+
+        let mut variants = TimuHashMap::<'base, Cow<'base, str>, TypeVariableInformation<'base>>::default();
+
+        for (discriminant, variant) in self.variants.iter().enumerate() {
+            let variant_path = SignaturePath::owned(format!("{full_name}.{}", variant.name.text));
+            let variant_location = context.types.reserve(variant_path.clone(), Cow::Borrowed(variant.name.text), TypeValueDiscriminants::EnumVariant, variant.name.state.file.clone(), variant.name.to_range())?;
+            variants.validate_insert(Cow::Borrowed(variant.name.text), TypeVariableInformation::basic(variant.name.clone(), variant_location))?;
+
+            // Payloads can hold the enum itself, it is reserved already
+            let mut payload = Vec::new();
+            for field_type in variant.payload.iter() {
+                payload.push(EnumPayload {
+                    field_type: get_object_location_or_resolve(context, field_type, &module_ref, scope_location)?,
+                    field_type_span: field_type.names_span.clone(),
+                    is_nullable: field_type.nullable,
+                    is_reference: field_type.reference,
+                });
+            }
+
+            let signature = TypeSignature::new(TypeValue::EnumVariant(EnumVariantDefinition {
+                name: variant.name.clone(),
+                enum_name: self.name.clone(),
+                enum_type: enum_location,
+                discriminant,
+                payload,
+            }), variant.name.state.file.clone(), variant.name.to_range(), None);
+            context.types.update(variant_path, signature);
+        }
+
+        let signature = TypeSignature::new(TypeValue::Enum(EnumDefinition {
+            name: self.name.clone(),
+            full_name,
+            variants,
+        }), self.name.state.file.clone(), self.name.to_range(), None);
+
+        context.publish_object_location(signature_path, signature);
+        Ok(enum_location)
+    }
+
+    /// Enums have no bodies, everything is done while resolving them
+    fn finish(&self, _: &mut TirContext<'base>, _: ScopeLocation) -> Result<(), TirError> {
+        Ok(())
+    }
+
+    fn name(&self) -> Cow<'base, str> {
+        Cow::Borrowed(self.name.text)
+    }
+}
+
+/// Variant stored at the given location, if it is one
+pub fn enum_variant<'ctx, 'base>(context: &'ctx TirContext<'base>, location: TypeLocation) -> Option<&'ctx EnumVariantDefinition<'base>> {
+    match context.types.get_from_location(location).map(|signature| signature.value.as_ref()) {
+        Some(TypeValue::EnumVariant(variant)) => Some(variant),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libtimu_macros_core::traits::TimuErrorTrait;
+    use rstest::rstest;
+
+    use crate::{tests::build_modules, tir::TirError};
+
+    #[rstest]
+    #[case("enum Color { Red, Green, Blue }\nfunc red(): Color { return ref Color.Red; }")]
+    #[case("enum Shape { Circle(float), Rect(float, float), Empty }\nfunc circle(): Shape { return Shape.Circle(1.5); }")]
+    #[case("func test(): void { var shape = Shape.Rect(2, 3); var copy: Shape = shape; }\nenum Shape { Rect(double, double) }")]
+    #[case("enum Tree { Leaf(i32), Node(Tree, Tree) }\nfunc tree(): Tree { return Tree.Node(Tree.Leaf(1), Tree.Leaf(2)); }")]
+    #[case("class Point { x: i32; }\nenum Result { Ok(Point), Error(string) }\nfunc fail(): Result { return Result.Error(\"failed\"); }")]
+    #[case("enum Maybe { Some(?string), Nothing }\nfunc test(name: ?string): Maybe { return Maybe.Some(name); }")]
+    #[case("enum Shape { Circle(float) }\nfunc apply(create: func(float): Shape): Shape { return create(1.0); }\nfunc test(): Shape { return apply(ref Shape.Circle); }")]
+    #[case("use lib.Direction;\nfunc up(): Direction { return ref Direction.Up; }")]
+    #[case("use lib;\nfunc up(): lib.Direction { return ref lib.Direction.Up; }")]
+    fn valid_enum(#[case] code: &str) -> Result<(), TirError> {
+        build_modules(&[("lib", "pub enum Direction { Up, Down }\nenum Hidden { Value }"), ("main", code)])
+    }
+
+    #[rstest]
+    #[case("enum Color { Red, Red }", "Already defined")]
+    #[case("class Color {}\nenum Color { Red }", "Already defined")]
+    #[case("enum Shape { Circle(Missing) }", "'Missing' type not found")]
+    #[case("enum Shape { Circle(float) }\nfunc test(): Shape { return Shape.Circle(\"a\"); }", "expected `float` type, got `string`")]
+    #[case("enum Shape { Circle(float) }\nfunc test(): Shape { return Shape.Circle(); }", "expects 1 argument, but 0 were provided")]
+    #[case("enum Shape { Circle(float) }\nfunc test(): Shape { return Shape.Square(1.0); }", "`Square` is not a variant of `Shape`")]
+    #[case("enum Color { Red }\nfunc test(): Color { return ref Color.Blue; }", "`Blue` is not a variant of `Color`")]
+    #[case("enum Color { Red }\nfunc test(): i32 { return ref Color.Red; }", "expected `i32` type, got `Color`")]
+    #[case("enum Color { Red, Blue }\nfunc test(color: Color): Color { return ref color.Blue; }", "`Blue` is a static member of `Color`")]
+    #[case("enum Shape { Circle(float) }\nfunc test(shape: Shape): Shape { return shape.Circle(1.0); }", "`Circle` is a static member of `Shape`")]
+    #[case("use lib.Hidden;", "private")]
+    fn enum_errors(#[case] code: &str, #[case] expected: &str) {
+        let error = build_modules(&[("lib", "pub enum Direction { Up, Down }\nenum Hidden { Value }"), ("main", code)]).unwrap_err();
+        assert!(error.to_string().contains(expected), "{error}");
+    }
+
+    /// Constructing an unknown variant is reported like matching one
+    #[test]
+    fn unknown_variant() {
+        let code = "enum Shape { Circle(float) }\nfunc test(): Shape { return Shape.Square(1.0); }";
+        let error = build_modules(&[("main", code)]).unwrap_err();
+        assert_eq!(error.error_code().unwrap().to_string(), "T0050");
+        assert_eq!(&code[error.labels().unwrap().remove(0).position], "Square");
+    }
+}
@@ -43,7 +43,7 @@
 //! # Validation Rules
 //!
 //! ## Interface Contract Validation
-//! - **Complete implementation**: All interface members must be implemented
+//! - **Complete implementation**: All interface members without a default must be implemented
 //! - **Type compatibility**: Implementation types must match interface signatures exactly
 //! - **Method signatures**: Parameter counts and types must match interface declarations
 //! - **Return types**: Method return types must match interface specifications
@@ -73,6 +73,8 @@
 //! - **Extension set**: Classes track which interfaces they implement
 //! - **Type compatibility**: Interface types become compatible with class types
 //! - **Method resolution**: Interface methods resolve to implementation methods
+//! - **Default implementations**: Interface methods with a body may be omitted,
+//!   the class gets the default implementation of the interface
 //! - **Polymorphism support**: Classes can be used where interfaces are expected
 //!
 //! # Architectural Benefits
@@ -85,7 +87,6 @@
 //!
 //! ## Future Extensibility
 //! The extension system supports future enhancements:
-//! - **Default implementations**: Interface methods with default bodies
 //! - **Extension inheritance**: Extensions that build on other extensions
 //! - **Conditional extensions**: Extensions that apply based on type parameters
 //! - **Mixin patterns**: Shared functionality across multiple classes
@@ -104,6 +105,7 @@ use core::panic;
 use std::{borrow::Cow, collections::HashSet};
 
 use indexmap::IndexMap;
+use libtimu_macros_core::traits::Suggestion;
 
 use crate::{
     ast::{ExtendDefinitionAst, ExtendDefinitionFieldAst}, map::TimuHashMap, nom_tools::{Span, ToRange}, tir::{context::TirContext, module::ModuleRef, object_signature::TypeValue, resolver::{get_object_location_or_resolve, try_resolve_signature}, scope::{ScopeLocation, TypeVariableInformation}, TirError}
@@ -158,7 +160,24 @@ impl<'base> ResolveAst<'base> for ExtendDefinitionAst<'base> {
         Ok(TypeLocation::UNDEFINED)
     }
     
-    fn finish(&self, _: &mut TirContext<'base>, _: ScopeLocation) -> Result<(), TirError> { Ok(()) }
+    fn finish(&self, context: &mut TirContext<'base>, scope_location: ScopeLocation) -> Result<(), TirError> {
+        let module_ref = context.get_scope(scope_location).unwrap().module_ref.clone();
+        let class_location = get_object_location_or_resolve(context, &self.name, &module_ref, scope_location)?;
+
+        let class_name = context.types.get_from_location(class_location).unwrap().value.get_name();
+        let class_name = format!("{}.{}", module_ref.as_ref(), class_name);
+
+        /* Function scopes are created under the class scope while resolving the fields */
+        for field in self.fields.iter() {
+            if let ExtendDefinitionFieldAst::Function(function) = field {
+                let full_name = format!("{}::{}", class_name, function.name.text);
+                let function_scope_location = *context.types_scope.get(full_name.as_str()).unwrap();
+                function.finish(context, function_scope_location)?;
+            }
+        }
+
+        Ok(())
+    }
     
     fn name(&self) -> Cow<'base, str> {
         let name = self.name.names.first().unwrap().text;
@@ -173,12 +192,41 @@ impl<'base> ResolveAst<'base> for ExtendDefinitionAst<'base> {
 }
 
 impl<'base> ExtendDefinitionAst<'base> {
+    /// Builds the edit that adds a missing interface member to the end of the extension
+    ///
+    /// The declaration is copied from the interface, a function gets an empty body.
+    fn missing_member_suggestion(&self, interface_field: &Span<'base>) -> Option<Suggestion> {
+        let interface_code = interface_field.state.file.code();
+        let declaration = &interface_code[interface_field.position.start..];
+        let declaration = declaration[..declaration.find(';')?].trim_end();
+        let member = match interface_code[..interface_field.position.start].trim_end().ends_with("func") {
+            true => format!("func {declaration} {{}}"),
+            false => format!("{declaration};"),
+        };
+
+        // A closing brace on its own line keeps its indentation, otherwise the member gets a line of its own
+        let code = self.close_brace.state.file.code();
+        let position = self.close_brace.position.start;
+        let line_start = code[..position].rfind('\n').map_or(0, |index| index + 1);
+        let (position, replacement) = match code[line_start..position].trim().is_empty() {
+            true => (line_start, format!("    {member}\n")),
+            false => (position, format!("\n    {member}\n")),
+        };
+
+        Some(Suggestion {
+            message: format!("add the missing `{}` member", interface_field.text),
+            code: (&self.close_brace.state.file).into(),
+            range: position..position,
+            replacement,
+        })
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn resolve_fields(&self, context: &mut TirContext<'base>, class_name: &str, class_scope_location: ScopeLocation, module: &ModuleRef<'base>, extend_fields: &mut TimuHashMap<'base, Cow<'base, str>, TypeVariableInformation<'base>>, extend_fields_for_track: &mut IndexMap<Cow<'base, str>, Span<'base>>, _: TypeLocation) -> Result<(), TirError> {
         for field in self.fields.iter() {
             match field {
                 ExtendDefinitionFieldAst::Function(function) => {
-                    let full_name = format!("{}::{}", class_name, function.name()); 
+                    let full_name = format!("{}::{}", class_name, function.name.text); 
                     let child_scope_location = context.create_child_scope(full_name.into(), class_scope_location, None);
                     let class_type_location = function.resolve(context, child_scope_location)?;
                     let variable = TypeVariableInformation::basic(function.name.clone(), class_type_location);
@@ -187,8 +235,8 @@ impl<'base> ExtendDefinitionAst<'base> {
                     extend_fields_for_track.insert((function.name.text).into(), function.name.clone());
                 }
                 ExtendDefinitionFieldAst::Field(field) => {
-                    if field.is_public.is_some() {
-                        return Err(TirError::extra_accessibility_identifier(field.is_public.as_ref().unwrap().to_range(), field.name.state.file.clone()));
+                    if let Some(is_public) = &field.is_public {
+                        return Err(TirError::extra_accessibility_identifier(is_public.to_range(), field.name.state.file.clone()));
                     }
 
                     let field_type = get_object_location_or_resolve(context, &field.field_type, module, class_scope_location)?;
@@ -206,6 +254,7 @@ impl<'base> ExtendDefinitionAst<'base> {
     fn resolve_interfaces(&self, context: &mut TirContext<'base>, class_scope_location: ScopeLocation, module: &ModuleRef<'base>, extend_fields: &TimuHashMap<'base, Cow<'base, str>, TypeVariableInformation<'base>>, extend_fields_for_track: &mut IndexMap<Cow<'base, str>, Span<'base>>) -> Result<(), TirError> {
         let mut errors = Vec::new();
         let mut extends = HashSet::new();
+        let mut defaults = Vec::new();
 
         for interface_ast in self.base_interfaces.iter() {
             // Find the inferface signature
@@ -237,9 +286,14 @@ impl<'base> ExtendDefinitionAst<'base> {
                 let extend_field = match extend_fields.get(interface_field.0.text) {
                     Some(defined_field) => defined_field,
 
-                    // Field not defined in the extend
+                    // Field not defined in the extend, the default implementation is used if there is one
                     None => {
-                        errors.push(TirError::interface_field_not_defined(self.name.to_range(), self.name.names.last().unwrap().state.file.clone()));
+                        if let Some(TypeValue::Function(_)) = context.types.get_from_location(interface_field.1.location).map(|signature| signature.value.as_ref()) {
+                            defaults.push(TypeVariableInformation::basic(interface_field.0.clone(), interface_field.1.location));
+                            continue;
+                        }
+
+                        errors.push(TirError::interface_field_not_defined(self.name.to_range(), self.name.names.last().unwrap().state.file.clone(), self.missing_member_suggestion(interface_field.0)));
                         continue;
                     }
                 };
@@ -261,7 +315,13 @@ impl<'base> ExtendDefinitionAst<'base> {
                     }
                 };
 
-                if !defined_field_type.value.is_same_type(context, &interface_field_type.value) {
+                let is_same_type = match (defined_field_type.value.as_ref(), interface_field_type.value.as_ref()) {
+                    // The class overrides the default implementation of the interface
+                    (TypeValue::Function(function), TypeValue::Function(default)) => function.overrides(default),
+                    (defined, interface) => defined.is_same_type(context, interface),
+                };
+
+                if !is_same_type {
                     errors.push(TirError::types_do_not_match(interface_field.0.to_range(), interface_field.0.state.file.clone()));
                 }
                 else {
@@ -280,6 +340,15 @@ impl<'base> ExtendDefinitionAst<'base> {
         };
         class.extends.extend(&mut extends.into_iter());
 
+        for default in defaults.iter() {
+            class.fields.validate_insert(Cow::Borrowed(default.span.text), default.clone())?;
+        }
+
+        let class_scope = context.get_mut_scope(class_scope_location).expect("Scope not found, it is a bug");
+        for default in defaults.into_iter() {
+            class_scope.add_variable(default)?;
+        }
+
         if !errors.is_empty() {
             context.add_errors(errors);
         }
@@ -607,5 +676,73 @@ class TestClass { }
         crate::tir::build(vec![ast.into()]).unwrap();
         Ok(())
     }
+
+    #[test]
+    fn default_function_can_be_omitted() -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], r#"
+interface IShape {
+    func area(this): i32;
+    func twice(this): i32 { return this.area() * 2; }
 }
+extend Square: IShape {
+    func area(this): i32 { return ref this.size; }
+}
+class Square {
+    size: i32;
+    func total(this): i32 { return this.twice(); }
+}
+func total(square: Square): i32 { return square.twice() + square.area(); }
+    "#.to_string()));
+        let ast = process_code(&state)?;
+        crate::tir::build(vec![ast.into()]).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn default_function_can_be_overridden() -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], r#"
+interface IShape {
+    func sides(this): i32 { return 4; }
+}
+extend Triangle: IShape {
+    func sides(this): i32 { return 3; }
+}
+class Triangle {}
+    "#.to_string()));
+        let ast = process_code(&state)?;
+        crate::tir::build(vec![ast.into()]).unwrap();
+        Ok(())
+    }
 
+    #[test]
+    fn default_function_override_must_match() -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], r#"
+interface IShape {
+    func sides(this): i32 { return 4; }
+}
+extend Triangle: IShape {
+    func sides(this): i64 { return 3; }
+}
+class Triangle {}
+    "#.to_string()));
+        let ast = process_code(&state)?;
+        crate::tir::build(vec![ast.into()]).unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn missing_function_without_default() -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], r#"
+interface IShape {
+    func area(this): i32;
+    func twice(this): i32 { return this.area() * 2; }
+}
+extend Square: IShape {}
+class Square {}
+    "#.to_string()));
+        let ast = process_code(&state)?;
+        let error = crate::tir::build(vec![ast.into()]).unwrap_err();
+        assert!(format!("{error:?}").contains("InterfaceFieldNotDefined"), "{error:?}");
+        Ok(())
+    }
+}
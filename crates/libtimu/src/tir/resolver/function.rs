@@ -102,7 +102,7 @@ use libtimu_macros_core::SourceCode;
 use strum_macros::{EnumDiscriminants, EnumProperty};
 
 use crate::{
-    ast::{FunctionArgumentAst, FunctionDefinitionAst, FunctionDefinitionLocationAst}, nom_tools::{Span, SpanInfo, ToRange}, tir::{context::TirContext, module::ModuleRef, object_signature::{GetItem, TypeValue, TypeValueDiscriminants}, resolver::get_object_location_or_resolve, scope::{ScopeLocation, TypeVariableInformation, VariableInformation}, signature::{SignatureInfo, SignaturePath}, TirError, TypeSignature}
+    ast::{FunctionArgumentAst, FunctionDefinitionAst, FunctionDefinitionLocationAst, TypeNameAst}, nom_tools::{Span, SpanInfo, ToRange}, prelude::is_native_function, tir::{context::TirContext, module::ModuleRef, object_signature::{GetItem, PrimitiveType, TypeValue, TypeValueDiscriminants}, resolver::{get_object_location_or_resolve, statement::{get_primitive_type, is_numeric}}, scope::{ScopeLocation, TypeVariableInformation, VariableInformation}, signature::{SignatureInfo, SignaturePath}, TirError, TypeSignature}
 };
 
 use super::{build_type_name, try_resolve_signature, BuildFullNameLocater, ResolveAst, ResolverError, TypeLocation};
@@ -155,6 +155,34 @@ pub struct FunctionDefinition<'base> {
     pub ast: FunctionDefinitionAst<'base>
 }
 
+impl FunctionDefinition<'_> {
+    /// Whether the function is defined in a class without `this`, so it is called through the type name
+    pub fn is_static(&self) -> bool {
+        matches!(self.ast.location.as_ref(), FunctionDefinitionLocationAst::Class(_)) && !self.has_this()
+    }
+
+    fn has_this(&self) -> bool {
+        matches!(self.ast.arguments.first(), Some(FunctionArgumentAst::This(_)))
+    }
+
+    /// Checks if the function has the signature of the `base` function it overrides
+    ///
+    /// `this` is always the class or interface of its own function, so it is
+    /// the only argument whose type differs between the two.
+    pub fn overrides(&self, base: &FunctionDefinition<'_>) -> bool {
+        if self.name.text != base.name.text
+            || self.arguments.len() != base.arguments.len()
+            || self.return_type != base.return_type
+            || self.is_public != base.is_public
+            || self.has_this() != base.has_this() {
+            return false;
+        }
+
+        self.arguments.iter().zip(base.arguments.iter()).skip(self.has_this() as usize)
+            .all(|(argument, base_argument)| argument.name.text == base_argument.name.text && argument.field_type == base_argument.field_type)
+    }
+}
+
 impl GetItem for FunctionDefinition<'_> {
     fn get_item_location(&self, _: &TirContext<'_>, path: &str) -> Option<TypeLocation> {
         self
@@ -192,7 +220,8 @@ pub fn unwrap_for_this<'base>(parent: &Option<TypeLocation>, this: &Span<'base>)
 /// 
 /// # Returns
 /// * `Some(TypeLocation)` - The type location of the containing class
-/// * `None` - No containing class found (function is at module level)
+/// * `None` - No containing class found (function is at module level), or the
+///   containing function is static and has no object
 /// 
 /// # Usage
 /// 
@@ -207,47 +236,82 @@ pub fn unwrap_for_this<'base>(parent: &Option<TypeLocation>, this: &Span<'base>)
 /// - **Completed types**: Uses the final type information from `context.types`
 /// - **Reserved types**: Uses type shadows from `context.types.get_reserve_from_location()`
 pub fn find_class_location<'base>(context: &TirContext<'base>, scope_location: ScopeLocation) -> Option<TypeLocation> {
-    let mut scope_location = scope_location;
-
-    loop {
-        let scope =  context.get_scope(scope_location).unwrap();
-        if scope.current_type == TypeLocation::UNDEFINED && scope.parent_scope.is_some() {
-            scope_location = scope.parent_scope.unwrap();
-            continue;
-        }
+    find_class_scope(context, scope_location).map(|scope_location| context.get_scope(scope_location).unwrap().current_type)
+}
 
-        match context.types.get_from_location(scope.current_type).cloned().map(|signature| signature.value) {
-            // This scope belong to class so return the type location
-            Some(TypeValue::Class(_)) => return Some(scope.current_type),
+/// Searches the scope chain for the scope that belongs to the enclosing class
+/// 
+/// Works like [`find_class_location`] but returns the class scope itself, which
+/// holds the class fields and methods as variables. Method calls through `this`
+/// use it so they resolve correctly from nested blocks such as `if` bodies.
+/// Default methods of an interface are in the interface scope, so `this` is
+/// the interface there. Static functions are called without an object, so
+/// there is no class scope to reach from their bodies.
+pub fn find_class_scope<'base>(context: &TirContext<'base>, scope_location: ScopeLocation) -> Option<ScopeLocation> {
+    // Bodies are resolved after the function is published, so a static function is known here
+    let is_static = find_scope_by_type(context, scope_location, &[TypeValueDiscriminants::Function])
+        .and_then(|function_scope| context.types.get_from_location(context.get_scope(function_scope).unwrap().current_type))
+        .is_some_and(|signature| matches!(signature.value.as_ref(), TypeValue::Function(function) if function.is_static()));
+
+    match is_static {
+        true => None,
+        false => find_scope_by_type(context, scope_location, &[TypeValueDiscriminants::Class, TypeValueDiscriminants::Interface]),
+    }
+}
 
-            // We are still in some of the child scope, continue to search
-            Some(_) => if let Some(parent_scope) = scope.parent_scope {
-                scope_location = parent_scope;
-                continue;
-            },
+/// Checks that a variable found by name is not an instance field used in a static function
+///
+/// The fields of a class are variables of the class scope, so the bodies of its
+/// static functions find them too, but there is no object to read them from.
+pub fn check_static_access<'base>(context: &TirContext<'base>, scope_location: ScopeLocation, variable: &TypeVariableInformation<'base>, span: SpanInfo) -> Result<(), TirError> {
+    let Some(class_scope) = find_scope_by_type(context, scope_location, &[TypeValueDiscriminants::Class]) else {
+        return Ok(());
+    };
+
+    if find_class_scope(context, scope_location).is_some() {
+        return Ok(());
+    }
 
-            // There is no more scope to search, so, we can stop
-            None => ()
-        };
+    let class_location = context.get_scope(class_scope).expect("Scope not found, it is a bug").current_type;
+    if let Some(TypeValue::Class(class)) = context.types.get_from_location(class_location).map(|signature| signature.value.as_ref())
+        && let Some(field) = class.fields.get(variable.span.text)
+        && field.span.position == variable.span.position
+        && !class.static_members.contains(variable.span.text)
+        && !matches!(context.types.get_from_location(field.location).map(|signature| signature.value.as_ref()), Some(TypeValue::Function(_))) {
+        return Err(FunctionResolveError::instance_field_in_static_function(class.name.text, &variable.span, span));
+    }
 
-        // The type not saved yet, lets check the reservations
-        match context.types.get_reserve_from_location(scope.current_type).map(|reservation| reservation.type_shadow) {
-            // This scope belong to class so return the type location
-            Some(TypeValueDiscriminants::Class) => return Some(scope.current_type),
+    Ok(())
+}
 
-            // We are still in some of the child scope, continue to search
-            Some(_) => match scope.parent_scope {
+/// Searches the scope chain for the type location of the enclosing function
+/// 
+/// Block scopes created for `if` bodies have no type of their own, so the search
+/// walks up until it reaches the scope of the function definition. Return
+/// statements use it to validate the returned value against the declared type.
+/// Inside a lambda the lambda's function type is returned.
+pub fn find_function_location<'base>(context: &TirContext<'base>, scope_location: ScopeLocation) -> Option<TypeLocation> {
+    find_scope_by_type(context, scope_location, &[TypeValueDiscriminants::Function, TypeValueDiscriminants::FunctionType]).map(|scope_location| context.get_scope(scope_location).unwrap().current_type)
+}
 
-                // There is parent scope, lets continue to search
-                Some(parent_scope) => scope_location = parent_scope,
+fn find_scope_by_type<'base>(context: &TirContext<'base>, scope_location: ScopeLocation, expected: &[TypeValueDiscriminants]) -> Option<ScopeLocation> {
+    let mut scope_location = scope_location;
 
-                // End of the search, not found, return None
-                None => return None
-            },
+    loop {
+        let scope = context.get_scope(scope_location).unwrap();
 
-            // There is no more scope to search, so, we can stop and return the error
-            None => return None
+        // Published types are checked first, reservations cover the types that are still being resolved
+        let found = match context.types.get_from_location(scope.current_type) {
+            Some(signature) => Some(TypeValueDiscriminants::from(signature.value.as_ref())),
+            None => context.types.get_reserve_from_location(scope.current_type).map(|reservation| reservation.type_shadow),
         };
+
+        if found.is_some_and(|found| expected.contains(&found)) {
+            return Some(scope.location);
+        }
+
+        // We are still in some of the child scope, continue to search
+        scope_location = scope.parent_scope?;
     }
 }
 
@@ -256,13 +320,24 @@ impl<'base> ResolveAst<'base> for FunctionDefinitionAst<'base> {
         let full_name = self.build_full_name(context, BuildFullNameLocater::Scope(scope_location), None);
         simplelog::debug!("Resolving function: <u><b>{}</b></u>", full_name.as_str());
 
+        if let Some(native) = self.is_native.as_ref() && !is_native_function(full_name.as_str()) {
+            return Err(FunctionResolveError::unknown_native_function(full_name, native.into()));
+        }
+
         let (module_ref, parent_type, parent_scope,) = {
             let scope = context.get_scope(scope_location).expect("Scope not found, it is a bug");
             (scope.module_ref.clone(), scope.parent_type, scope.parent_scope)
         };
         let (signature_path, signature_location) = context.reserve_object_location(self.name(), TypeValueDiscriminants::Function, SignaturePath::owned(full_name), &module_ref, self.name.to_range(), self.name.state.file.clone())?;
         
-        let definition = self.build_definition(context, scope_location, parent_scope, &module_ref, parent_type, signature_path.clone())?;
+        // Body statements look up the enclosing function through the scope's type
+        context.get_mut_scope(scope_location).expect("Scope not found, it is a bug").set_current_type(signature_location);
+
+        let definition = self.build_definition(context, scope_location, &module_ref, parent_type, signature_path.clone())?;
+
+        if self.is_extern.is_some() {
+            self.check_extern(context, &definition)?;
+        }
                 
         /* Add function information as a variable */
         let parent_scope = context.get_mut_scope(parent_scope.expect("Parent scope not found, it is a bug")).expect("Scope not found, it is a bug");
@@ -299,7 +374,7 @@ impl<'base> ResolveAst<'base> for FunctionDefinitionAst<'base> {
     fn name(&self) -> Cow<'base, str> {
         match self.location.as_ref() {
             FunctionDefinitionLocationAst::Module => (*self.name.text).into(),
-            FunctionDefinitionLocationAst::Class(class) => format!("{}::{}", class.text, self.name.text).into(),
+            FunctionDefinitionLocationAst::Class(class) | FunctionDefinitionLocationAst::Extend(class) => format!("{}::{}", class.text, self.name.text).into(),
         }
     }
 
@@ -314,27 +389,27 @@ impl<'base> ResolveAst<'base> for FunctionDefinitionAst<'base> {
         
         match self.location.as_ref() {
             FunctionDefinitionLocationAst::Module => format!("{}.{}", module.path, self.name.text),
-            FunctionDefinitionLocationAst::Class(class) => format!("{}.{}::{}", module.path, class.text, self.name.text),
+            FunctionDefinitionLocationAst::Class(class) | FunctionDefinitionLocationAst::Extend(class) => format!("{}.{}::{}", module.path, class.text, self.name.text),
         }
     }
 }
 
 impl<'base> FunctionDefinitionAst<'base> {
-    fn build_definition(&self, context: &mut TirContext<'base>, scope_location: ScopeLocation, parent_scope: Option<ScopeLocation>, module: &ModuleRef<'base>, parent_type: Option<TypeLocation>, signature_path: SignaturePath<'base>) -> Result<FunctionDefinition<'base>, TirError> {
+    fn build_definition(&self, context: &mut TirContext<'base>, scope_location: ScopeLocation, module: &ModuleRef<'base>, parent_type: Option<TypeLocation>, signature_path: SignaturePath<'base>) -> Result<FunctionDefinition<'base>, TirError> {
         let mut arguments = vec![];
         let return_type = get_object_location_or_resolve(context, &self.return_type, module, scope_location)?;
 
         /* Parse arguments */
         for (index, argument) in self.arguments.iter().enumerate() {
-            let (argument_name, range, file) = match argument {
+            let (argument_name, range, file, argument_type) = match argument {
                 FunctionArgumentAst::This(this) => {
                     let class_type_location = match find_class_location(context, scope_location) {
                         Some(location) => location,
                         None => return Err(FunctionResolveError::this_need_to_define_in_class(this.into()))
                     };
 
-                    let parent_scope = context.get_mut_scope(parent_scope.unwrap()).unwrap();
-                    parent_scope.add_variable(VariableInformation::basic(this.clone(), class_type_location))?;
+                    let scope = context.get_mut_scope(scope_location).unwrap();
+                    scope.add_variable(VariableInformation::basic(this.clone(), class_type_location))?;
 
                     if index != 0 {
                         return Err(FunctionResolveError::this_need_to_define_in_class(this.into()));
@@ -343,19 +418,20 @@ impl<'base> FunctionDefinitionAst<'base> {
                     match context.types.get_signature_from_location(unwrap_for_this(&Some(class_type_location), this)?).unwrap() {
                         SignatureInfo::Reserved(reservation) => {
                             let reservation = reservation.clone();
-                            (reservation.name, reservation.position, reservation.file)
+                            (reservation.name, reservation.position, reservation.file, None)
                         },
                         SignatureInfo::Value(value) => {
-                            (Cow::Owned(value.value.get_name().to_string()), this.to_range(), this.state.file.clone())
+                            (Cow::Owned(value.value.get_name().to_string()), this.to_range(), this.state.file.clone(), None)
                         }
                     }
                 },
                 FunctionArgumentAst::Argument { name, field_type } => {
+                    let nullable = field_type.nullable;
                     let field_type = get_object_location_or_resolve(context, field_type, module, scope_location)?;
                     let scope = context.get_mut_scope(scope_location).unwrap();
 
-                    scope.add_variable(VariableInformation::basic(name.clone(), field_type))?;
-                    (Cow::Borrowed(name.text), name.to_range(), name.state.file.clone())
+                    scope.add_variable(VariableInformation::new(name.clone(), field_type, nullable, false, false))?;
+                    (Cow::Borrowed(name.text), name.to_range(), name.state.file.clone(), Some(field_type))
                 }
             };
             
@@ -369,9 +445,13 @@ impl<'base> FunctionDefinitionAst<'base> {
                 FunctionArgumentAst::Argument { field_type, .. } => (field_type.names_span.clone(), Cow::Owned(build_type_name(field_type))),
             };
 
-            let field_type = match try_resolve_signature(context, module, scope_location, type_name.as_ref())? {
+            // Argument types are already resolved, function types have no name to look up
+            let field_type = match argument_type {
                 Some(field_type) => field_type,
-                None => return Err(TirError::type_not_found(context, type_name.to_string(), range, file))
+                None => match try_resolve_signature(context, module, scope_location, type_name.as_ref())? {
+                    Some(field_type) => field_type,
+                    None => return Err(TirError::type_not_found(context, type_name.to_string(), range, file))
+                },
             };
 
             if let Some(old) = arguments.iter().find(|item: &&FunctionArgument| *item.name.text == argument_name) {
@@ -406,10 +486,38 @@ impl<'base> FunctionDefinitionAst<'base> {
         })
     }
 
+    /// Validates that a foreign function can be called through the C ABI
+    fn check_extern(&self, context: &TirContext<'base>, definition: &FunctionDefinition<'base>) -> Result<(), TirError> {
+        if let FunctionDefinitionLocationAst::Class(_) = self.location.as_ref() {
+            return Err(FunctionResolveError::extern_function_in_class((&self.name).into()));
+        }
+
+        let is_ffi_safe = |location: TypeLocation, type_name: &TypeNameAst<'_>, is_return: bool| {
+            type_name.reference || (!type_name.nullable && match get_primitive_type(context, location) {
+                Some(PrimitiveType::Void) => is_return,
+                Some(primitive) => is_numeric(&primitive) || primitive == PrimitiveType::Bool,
+                None => false,
+            })
+        };
+
+        for (argument, argument_ast) in definition.arguments.iter().zip(self.arguments.iter()) {
+            if let FunctionArgumentAst::Argument { field_type, .. } = argument_ast && !is_ffi_safe(argument.field_type, field_type, false) {
+                return Err(FunctionResolveError::not_ffi_safe(field_type.to_string(), (&field_type.names_span).into()));
+            }
+        }
+
+        if !is_ffi_safe(definition.return_type, &self.return_type, true) {
+            return Err(FunctionResolveError::not_ffi_safe(self.return_type.to_string(), (&self.return_type.names_span).into()));
+        }
+
+        Ok(())
+    }
+
 }
 
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("This argument need to be defined in class function")]
+#[diagnostic(code("T0016"))]
 pub struct ThisNeedToDefineInClass {
     #[label("`this` defined out of the class function")]
     pub position: Range<usize>,
@@ -420,6 +528,7 @@ pub struct ThisNeedToDefineInClass {
 
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("Variable not found")]
+#[diagnostic(code("T0017"))]
 pub struct VariableNotFound {
     #[label("Maybe not defined")]
     pub position: Range<usize>,
@@ -428,9 +537,64 @@ pub struct VariableNotFound {
     pub code: SourceCode,
 }
 
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{name}` has no native implementation")]
+#[diagnostic(code("T0018"), help("only the functions of the standard library can be native, give the function a body instead"))]
+pub struct UnknownNativeFunction {
+    pub name: String,
+
+    #[label("declared as native here")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{type_name}` is not FFI-safe")]
+#[diagnostic(code("T0019"), help("extern functions only take and return integers, floats, `bool` and `ref` types"))]
+pub struct NotFfiSafe {
+    pub type_name: String,
+
+    #[label("used in an extern function")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("Extern functions have to be declared at module level")]
+#[diagnostic(code("T0020"), help("move the declaration out of the class and pass the object as a `ref` argument"))]
+pub struct ExternFunctionInClass {
+    #[label("declared in a class")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{name}` is an instance field of `{class}`, a static function has no object")]
+#[diagnostic(code("T0052"), help("add `this` to the function or make the field `static`"))]
+pub struct InstanceFieldInStaticFunction {
+    pub name: String,
+    pub class: String,
+
+    #[label("defined here")]
+    pub definition: Range<usize>,
+
+    #[label("used in a static function")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
 #[derive(Clone, Debug, TimuError, thiserror::Error, EnumDiscriminants, EnumProperty)]
 pub enum FunctionResolveError {
     #[error("`this` needs to be first argument in function definition")]
+    #[diagnostic(code("T0015"))]
     ThisArgumentMustBeFirst(SpanInfo),
 
     #[error(transparent)]
@@ -440,6 +604,22 @@ pub enum FunctionResolveError {
     #[error(transparent)]
     #[diagnostic(transparent)]
     VariableNotFound(Box<VariableNotFound>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    UnknownNativeFunction(Box<UnknownNativeFunction>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    NotFfiSafe(Box<NotFfiSafe>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ExternFunctionInClass(Box<ExternFunctionInClass>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    InstanceFieldInStaticFunction(Box<InstanceFieldInStaticFunction>),
 }
 
 impl From<FunctionResolveError> for TirError {
@@ -455,12 +635,45 @@ impl FunctionResolveError {
             code: span.file.clone().into(),
         }.into()).into()
     }
+    pub fn unknown_native_function(name: String, span: SpanInfo) -> TirError {
+        FunctionResolveError::UnknownNativeFunction(UnknownNativeFunction {
+            name,
+            position: span.position.clone(),
+            code: span.file.clone().into(),
+        }.into()).into()
+    }
+
+    pub fn not_ffi_safe(type_name: String, span: SpanInfo) -> TirError {
+        FunctionResolveError::NotFfiSafe(NotFfiSafe {
+            type_name,
+            position: span.position.clone(),
+            code: span.file.clone().into(),
+        }.into()).into()
+    }
+
+    pub fn extern_function_in_class(span: SpanInfo) -> TirError {
+        FunctionResolveError::ExternFunctionInClass(ExternFunctionInClass {
+            position: span.position.clone(),
+            code: span.file.clone().into(),
+        }.into()).into()
+    }
+
     pub fn variable_not_found(span: SpanInfo) -> TirError {
         FunctionResolveError::VariableNotFound(VariableNotFound {
             position: span.position.clone(),
             code: span.file.clone().into(),
         }.into()).into()
     }
+
+    pub fn instance_field_in_static_function(class: &str, definition: &Span<'_>, span: SpanInfo) -> TirError {
+        FunctionResolveError::InstanceFieldInStaticFunction(InstanceFieldInStaticFunction {
+            name: definition.text.to_string(),
+            class: class.to_string(),
+            definition: definition.to_range(),
+            position: span.position.clone(),
+            code: span.file.clone().into(),
+        }.into()).into()
+    }
 }
 
 #[cfg(test)]
@@ -491,6 +704,32 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn native_functions() {
+        let state = State::new(SourceFile::new(vec!["main".into()], "use std.math.max; pub func test(): i32 { return max(1, 2); }".to_string()));
+        process_ast(vec![process_code(&state).unwrap().into()]).unwrap();
+
+        let state = State::new(SourceFile::new(vec!["main".into()], "pub native func max(left: i32, right: i32): i32;".to_string()));
+        let error = process_ast(vec![process_code(&state).unwrap().into()]).unwrap_err();
+        assert_eq!(error.to_string(), "`main.max` has no native implementation");
+    }
+
+    #[test]
+    fn extern_functions() {
+        let build = |code: &str| {
+            let state = State::new(SourceFile::new(vec!["main".into()], code.to_string()));
+            process_ast(vec![process_code(&state).unwrap().into()]).map(|_| ()).map_err(|error| error.to_string())
+        };
+
+        build("extern func puts(text: ref string): i32; extern func exit(code: i32): void; extern func sqrt(value: double): double;").unwrap();
+        assert_eq!(build("extern func puts(text: string): i32;").unwrap_err(), "`string` is not FFI-safe");
+        assert_eq!(build("extern func parse(text: ref string): ?i32;").unwrap_err(), "`?i32` is not FFI-safe");
+        assert_eq!(build("extern func parse(value: ?i32): void;").unwrap_err(), "`?i32` is not FFI-safe");
+        assert_eq!(build("class Point { } extern func point(): Point;").unwrap_err(), "`Point` is not FFI-safe");
+        assert_eq!(build("extern func ignore(value: void): i32;").unwrap_err(), "`void` is not FFI-safe");
+        assert_eq!(build("class Point { extern func length(): i32; }").unwrap_err(), "Extern functions have to be declared at module level");
+    }
+
     #[test]
     fn valid_types() -> Result<(), TirError> {
         let state_1 = State::new(SourceFile::new(vec!["lib".into()], " pub class testclass1 {} ".to_string()));
@@ -502,7 +741,8 @@ mod tests {
 
 
         let context = process_ast(vec![source_2.into(), source_1.into()])?;
-        assert_eq!(context.modules.len(), 2);
+        // The standard library adds its modules and their `std` parent
+        assert_eq!(context.modules.len(), 2 + crate::prelude::MODULES.len() + 1);
 
         let main_module = context.modules.iter().find(|(name, _)| *name == "main").unwrap();
         let lib_module = context.modules.iter().find(|(name, _)| *name == "lib").unwrap();
@@ -518,6 +758,15 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn call_function_of_later_module() -> Result<(), TirError> {
+        let state_1 = State::new(SourceFile::new(vec!["main".into()], "use net.http.status; pub func main(): i32 { return status(); }".to_string()));
+        let state_2 = State::new(SourceFile::new(vec!["net".into(), "http".into()], "pub func status(): i32 { return 200; }".to_string()));
+
+        process_ast(vec![process_code(&state_1)?.into(), process_code(&state_2)?.into()])?;
+        Ok(())
+    }
+
     #[test]
     fn missing_type_2() -> Result<(), TirError> {
         let state = State::new(SourceFile::new(vec!["source".into()], "func test(a: a): test {}".to_string()));
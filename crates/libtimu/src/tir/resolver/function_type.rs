@@ -0,0 +1,138 @@
+//! Function type resolution for the TIR system.
+//!
+//! Function types describe callable values: named functions used as values,
+//! lambda expressions, and the variables, fields and arguments holding them.
+//!
+//! ```timu
+//! func twice(callback: func(i32): i32, value: i32): i32 {
+//!     return callback(callback(value));
+//! }
+//!
+//! func increment(value: i32): i32 {
+//!     return value + 1;
+//! }
+//!
+//! var result = twice(increment, 1);
+//! ```
+//!
+//! # Structural Types
+//!
+//! Function types are compared by their signature, argument names do not
+//! matter. Every distinct signature is registered once in the type table under
+//! a key built from the locations of its parts, so two function types are the
+//! same type exactly when they have the same location.
+
+use crate::{
+    ast::{FunctionTypeAst, TypeNameAst},
+    file::SourceFile,
+    tir::{context::TirContext, module::ModuleRef, object_signature::{GetItem, TypeValue}, scope::ScopeLocation, signature::SignaturePath, TirError, TypeSignature},
+};
+
+use super::{function::FunctionArgument, get_object_location_or_resolve, statement::type_name, TypeLocation};
+
+/// Argument of a function type, function types have no argument names
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FunctionTypeArgument {
+    pub field_type: TypeLocation,
+    pub is_reference: bool,
+    pub is_nullable: bool,
+}
+
+/// Resolved function type such as `func(i32, i32): i32`
+///
+/// # Fields
+///
+/// - `name`: User facing name, like `func(i32, i32): i32`
+/// - `arguments`: Argument types in declaration order
+/// - `return_type`: The resolved return type location
+#[derive(Debug, Clone, PartialEq)]
+pub struct FunctionTypeDefinition {
+    pub name: String,
+    pub arguments: Vec<FunctionTypeArgument>,
+    pub return_type: TypeLocation,
+}
+
+impl From<&FunctionArgument<'_>> for FunctionTypeArgument {
+    fn from(argument: &FunctionArgument<'_>) -> Self {
+        Self {
+            field_type: argument.field_type,
+            is_reference: argument.is_reference,
+            is_nullable: argument.is_nullable,
+        }
+    }
+}
+
+impl GetItem for FunctionTypeDefinition {
+    fn get_item_location(&self, _: &TirContext<'_>, _: &str) -> Option<TypeLocation> {
+        None
+    }
+}
+
+/// Resolves the argument and return types of a function type name
+pub fn resolve_function_type<'base>(context: &mut TirContext<'base>, function: &FunctionTypeAst<'base>, module: &ModuleRef<'base>, scope_location: ScopeLocation) -> Result<TypeLocation, TirError> {
+    let mut arguments = Vec::new();
+    for argument in function.arguments.iter() {
+        arguments.push(function_type_argument(context, argument, module, scope_location)?);
+    }
+
+    let return_type = get_object_location_or_resolve(context, &function.return_type, module, scope_location)?;
+    Ok(function_type_location(context, arguments, return_type))
+}
+
+fn function_type_argument<'base>(context: &mut TirContext<'base>, argument: &TypeNameAst<'base>, module: &ModuleRef<'base>, scope_location: ScopeLocation) -> Result<FunctionTypeArgument, TirError> {
+    Ok(FunctionTypeArgument {
+        field_type: get_object_location_or_resolve(context, argument, module, scope_location)?,
+        is_reference: argument.reference,
+        is_nullable: argument.nullable,
+    })
+}
+
+/// Location of the function type with the given signature, registered on first use
+pub fn function_type_location(context: &mut TirContext<'_>, arguments: Vec<FunctionTypeArgument>, return_type: TypeLocation) -> TypeLocation {
+    let modifiers = |argument: &FunctionTypeArgument| format!("{}{}", if argument.is_reference { "ref " } else { "" }, if argument.is_nullable { "?" } else { "" });
+    let key = format!("func({}): {}", arguments.iter().map(|argument| format!("{}{}", modifiers(argument), argument.field_type.0)).collect::<Vec<_>>().join(", "), return_type.0);
+
+    if let Some(location) = context.types.location(&key) {
+        return location;
+    }
+
+    let name = format!("func({}): {}", arguments.iter().map(|argument| format!("{}{}", modifiers(argument), type_name(context, argument.field_type))).collect::<Vec<_>>().join(", "), type_name(context, return_type));
+    let signature = TypeSignature::new(
+        TypeValue::FunctionType(FunctionTypeDefinition { name, arguments, return_type }),
+        SourceFile::new(vec!["<function-type>".into()], String::new()),
+        0..0,
+        None,
+    );
+
+    context.types.add_signature(SignaturePath::owned(key), signature).expect("Function type registered twice, but this is a bug")
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::tests::build;
+
+    #[rstest]
+    #[case("func apply(callback: func(i32): i32, value: i32): i32 { return callback(value); }")]
+    #[case("func increment(value: i32): i32 { return value + 1; } func test(): i32 { var callback: func(i32): i32 = increment; return callback(1); }")]
+    #[case("func increment(value: i32): i32 { return value + 1; } func apply(callback: func(i32): i32): i32 { return callback(1); } func test(): i32 { return apply(increment); }")]
+    #[case("func test(): func(): bool { return func(): bool { return true; }; }")]
+    #[case("type Callback = func(string): void; func run(callback: Callback): void { callback(\"timu\"); }")]
+    #[case("class Button { handler: func(): void; func click(this): void { this.handler(); } }")]
+    #[case("func check(predicate: ?func(i32): bool, handler: func(ref string): void): void {}")]
+    fn function_types(#[case] code: &str) {
+        build(code).unwrap();
+    }
+
+    #[rstest]
+    #[case("func increment(value: i32): i32 { return value + 1; } func test(): void { var callback: func(i32): bool = increment; }", "expected `func(i32): bool` type, got `func(i32): i32`")]
+    #[case("func apply(callback: func(i32): i32): i32 { return callback(\"one\"); }", "expected `i32` type, got `string`")]
+    #[case("func apply(callback: func(i32): i32): i32 { return callback(); }", "Function `callback` expects 1 argument, but 0 were provided")]
+    #[case("func apply(callback: func(i32): i32): bool { return callback(1); }", "expected `bool` type, got `i32`")]
+    #[case("func apply(callback: func(missing): void): void {}", "'missing' type not found")]
+    fn function_type_errors(#[case] code: &str, #[case] expected: &str) {
+        let error = build(code).unwrap_err();
+        assert!(error.to_string().contains(expected), "{error}");
+    }
+}
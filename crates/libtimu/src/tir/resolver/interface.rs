@@ -40,7 +40,15 @@
 //! - **Signature requirements**: Method signatures that implementing classes must match
 //! - **Parameter specifications**: Exact parameter types and counts required
 //! - **Return type contracts**: Return types that implementations must provide
-//! - **This parameter support**: Interface methods can include `this` parameters
+//! - **This parameter support**: Interface methods can include `this` parameters,
+//!   typed as the interface itself
+//!
+//! ## Default Methods
+//! - **Default bodies**: An interface method with a body is a default implementation
+//! - **Interface context**: The body is type-checked like a class method, with `this`
+//!   typed as the interface, so it can only use the members of the interface
+//! - **Optional members**: Extensions may omit a method with a default, the class
+//!   then gets the default implementation
 //!
 //! # Interface Inheritance
 //!
@@ -79,7 +87,7 @@
 //!
 //! ## Extension Validation
 //! When classes implement interfaces via extensions:
-//! - **Completeness checking**: All interface members must be implemented
+//! - **Completeness checking**: All interface members without a default must be implemented
 //! - **Type compatibility**: Implementation types must match interface contracts
 //! - **Signature validation**: Method signatures must match exactly
 //! - **Field requirement validation**: All required fields must be provided
@@ -131,7 +139,7 @@ use core::panic;
 use std::borrow::Cow;
 
 use crate::{
-    ast::{FunctionArgumentAst, InterfaceDefinitionAst, InterfaceDefinitionFieldAst, InterfaceFunctionDefinitionAst}, map::TimuHashMap, nom_tools::{Span, ToRange}, tir::{ast_signature::AstSignatureValue, context::TirContext, module::ModuleRef, object_signature::{GetItem, TypeValue, TypeValueDiscriminants}, resolver::{build_type_name, function::{unwrap_for_this, FunctionArgument}, get_object_location_or_resolve, try_resolve_signature, BuildFullNameLocater}, scope::{ScopeLocation, TypeVariableInformation}, signature::SignaturePath, TirError, TypeSignature}
+    ast::{FunctionArgumentAst, FunctionDefinitionAst, FunctionDefinitionLocationAst, InterfaceDefinitionAst, InterfaceDefinitionFieldAst, InterfaceFunctionDefinitionAst}, map::TimuHashMap, nom_tools::{Span, ToRange}, tir::{ast_signature::AstSignatureValue, context::TirContext, module::ModuleRef, object_signature::{GetItem, TypeValue, TypeValueDiscriminants}, resolver::{build_type_name, function::{unwrap_for_this, FunctionArgument}, get_object_location_or_resolve, try_resolve_signature, BuildFullNameLocater}, scope::{ScopeLocation, TypeVariableInformation}, signature::SignaturePath, TirError, TypeSignature}
 };
 
 use super::{build_signature_path, find_ast_signature, TypeLocation, ResolveAst};
@@ -145,8 +153,12 @@ pub struct InterfaceDefinition<'base> {
 }
 
 impl GetItem for InterfaceDefinition<'_> {
-    fn get_item_location(&self, _: &TirContext<'_>, _: &str) -> Option<TypeLocation> {
-        None
+    fn get_item_location(&self, _: &TirContext<'_>, path: &str) -> Option<TypeLocation> {
+        self
+            .fields
+            .iter()
+            .find(|(name, _)| name.text == path)
+            .map(|(_, item)| item.location)
     }
 }
 
@@ -155,6 +167,7 @@ impl GetItem for InterfaceDefinition<'_> {
 pub struct InterfaceFunctionDefinition<'base> {
     pub name: Span<'base>,
     pub arguments: Vec<FunctionArgument<'base>>,
+    pub arguments_span: Span<'base>,
     pub return_type: TypeLocation,
 }
 
@@ -177,8 +190,11 @@ impl<'base> ResolveAst<'base> for InterfaceDefinitionAst<'base> {
 
         let mut fields = TimuHashMap::<'base, Span<'_>, TypeVariableInformation<'base>>::default();
         let mut base_interfaces = TimuHashMap::<'base, Cow<'_, str>, TypeVariableInformation<'base>>::default();
+
+        // `this` of the interface functions is the interface itself
+        context.get_mut_scope(scope_location).expect("Scope not found, it is a bug").set_current_type(signature_location);
         
-        Self::resolve_interface(context, self, self, &mut fields, &mut base_interfaces, &module_ref, scope_location, parent)?;
+        Self::resolve_interface(context, self, self, &mut fields, &mut base_interfaces, &module_ref, scope_location, Some(signature_location))?;
 
         let signature = TypeSignature::new(TypeValue::Interface(InterfaceDefinition {
             name: self.name.clone(),
@@ -190,13 +206,48 @@ impl<'base> ResolveAst<'base> for InterfaceDefinitionAst<'base> {
         Ok(signature_location)
     }
     
-    fn finish(&self, _: &mut TirContext<'base>, _: ScopeLocation) -> Result<(), TirError> { Ok(()) }
+    fn finish(&self, context: &mut TirContext<'base>, scope_location: ScopeLocation) -> Result<(), TirError> {
+        let module_ref = context.get_scope(scope_location).expect("Scope not found, it is a bug").module_ref.clone();
+
+        /* Default bodies are checked in the scopes created while resolving the interface */
+        for field in self.fields.iter() {
+            if let InterfaceDefinitionFieldAst::Function(function) = field
+                && let Some(default) = function.default_function(&self.name) {
+                let full_name = format!("{}.{}", module_ref.as_cow(), default.name());
+                let function_scope_location = *context.types_scope.get(full_name.as_str()).expect("Default function scope not found, it is a bug");
+                default.finish(context, function_scope_location)?;
+            }
+        }
+
+        Ok(())
+    }
     
     fn name(&self) -> Cow<'base, str> {
         Cow::Borrowed(self.name.text)
     }
 }
 
+impl<'base> InterfaceFunctionDefinitionAst<'base> {
+    /// Builds the function of the default implementation, it is a method of the interface
+    pub fn default_function(&self, interface_name: &Span<'base>) -> Option<FunctionDefinitionAst<'base>> {
+        let body = self.body.clone()?;
+
+        Some(FunctionDefinitionAst {
+            is_public: None,
+            is_const: None,
+            is_native: None,
+            is_extern: None,
+            name: self.name.clone(),
+            arguments: self.arguments.clone(),
+            arguments_span: self.arguments_span.clone(),
+            return_type: self.return_type.clone(),
+            body,
+            location: FunctionDefinitionLocationAst::Class(interface_name.clone()).into(),
+            index: self.index,
+        })
+    }
+}
+
 impl<'base> InterfaceDefinitionAst<'base> {
     #[allow(clippy::only_used_in_recursion)]
     #[allow(clippy::too_many_arguments)]
@@ -215,14 +266,21 @@ impl<'base> InterfaceDefinitionAst<'base> {
         for field in interface.fields.iter() {
             match field {
                 InterfaceDefinitionFieldAst::Function(function) => {
-                    let signature = interface.resolve_function(context, module, scope_location, function, parent)?;
+                    let signature = match function.default_function(&interface.name) {
+                        Some(default) => {
+                            let full_name = format!("{}.{}", module.as_cow(), default.name());
+                            let function_scope_location = context.create_child_scope(full_name.into(), scope_location, None);
+                            default.resolve(context, function_scope_location)?
+                        },
+                        None => interface.resolve_function(context, module, scope_location, function, parent)?,
+                    };
                     let variable = TypeVariableInformation::basic(function.name.clone(), signature);
                     
                     fields.validate_insert(function.name.clone(), variable)?;
                 }
                 InterfaceDefinitionFieldAst::Field(field) => {
-                    if field.is_public.is_some() {
-                        return Err(TirError::extra_accessibility_identifier(field.is_public.as_ref().unwrap().to_range(), field.name.state.file.clone()));
+                    if let Some(is_public) = &field.is_public {
+                        return Err(TirError::extra_accessibility_identifier(is_public.to_range(), field.name.state.file.clone()));
                     }
 
                     let field_type = get_object_location_or_resolve(context, &field.field_type, module, scope_location)?;
@@ -277,24 +335,20 @@ impl<'base> InterfaceDefinitionAst<'base> {
 
         for argument in interface_function.arguments.iter() {
             let (argument_name, range, file) = match argument {
-                FunctionArgumentAst::This(this) => {
-                    let parent = context.types.get_from_location(unwrap_for_this(&parent, this)?).unwrap();
-                    (Cow::Owned(parent.value.get_name().to_string()), this.to_range(), this.state.file.clone())
-                },
+                FunctionArgumentAst::This(this) => (Cow::Borrowed(this.text), this.to_range(), this.state.file.clone()),
                 FunctionArgumentAst::Argument { name, .. } => (Cow::Borrowed(name.text), name.to_range(), name.state.file.clone())
             };
             
-            let (field_type_span, type_name) = match argument {
-                FunctionArgumentAst::This(this) => {
-                    let parent = context.types.get_from_location(unwrap_for_this(&parent, this)?).unwrap();
-                    (this.clone(), parent.value.get_name().to_string())
+            let (field_type_span, field_type) = match argument {
+                FunctionArgumentAst::This(this) => (this.clone(), unwrap_for_this(&parent, this)?),
+                FunctionArgumentAst::Argument { field_type, .. } if field_type.function.is_some() || field_type.tuple.is_some() => (field_type.names_span.clone(), get_object_location_or_resolve(context, field_type, module, scope_location)?),
+                FunctionArgumentAst::Argument { field_type, .. } => {
+                    let type_name = build_type_name(field_type);
+                    match try_resolve_signature(context, module, scope_location, type_name.as_str())? {
+                        Some(location) => (field_type.names_span.clone(), location),
+                        None => return Err(TirError::type_not_found(context, type_name, range, file))
+                    }
                 },
-                FunctionArgumentAst::Argument { field_type, .. } => (field_type.names_span.clone(), build_type_name(field_type)),
-            };
-
-            let field_type = match try_resolve_signature(context, module, scope_location, type_name.as_str())? {
-                Some(field_type) => field_type,
-                None => return Err(TirError::type_not_found(context, type_name, range, file))
             };
 
             if let Some(old) = arguments.iter().find(|item: &&FunctionArgument<'_>| *item.name.text == argument_name) {
@@ -326,6 +380,7 @@ impl<'base> InterfaceDefinitionAst<'base> {
                 InterfaceFunctionDefinition {
                     name: interface_function.name.clone(),
                     arguments,
+                    arguments_span: interface_function.arguments_span.clone(),
                     return_type,
                 },
             ),
@@ -443,5 +498,56 @@ mod tests {
         crate::tir::build(vec![ast_1.into(), ast_2.into()]).unwrap_err();
         Ok(())
     }
-}
 
+    #[test]
+    fn this_is_the_interface() -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], r#"
+    interface Myinterface {
+        func test(this, a: i32): i32;
+    }"#.to_string()));
+        let ast = process_code(&state)?;
+
+        crate::tir::build(vec![ast.into()]).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn default_function() -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], r#"
+    interface Myinterface {
+        func size(this): i32;
+        func double(this, a: i32): i32 { return this.size() * a; }
+        func other(this): i32 { return this.double(2); }
+    }"#.to_string()));
+        let ast = process_code(&state)?;
+
+        crate::tir::build(vec![ast.into()]).unwrap();
+        Ok(())
+    }
+
+    #[test]
+    fn default_function_is_type_checked() -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], r#"
+    interface Myinterface {
+        func size(this): i32;
+        func text(this): string { return this.size(); }
+    }"#.to_string()));
+        let ast = process_code(&state)?;
+
+        crate::tir::build(vec![ast.into()]).unwrap_err();
+        Ok(())
+    }
+
+    #[test]
+    fn default_function_argument_mismatch() -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], r#"
+    interface Myinterface {
+        func double(this, a: i32): i32;
+        func other(this): i32 { return this.double("text"); }
+    }"#.to_string()));
+        let ast = process_code(&state)?;
+
+        crate::tir::build(vec![ast.into()]).unwrap_err();
+        Ok(())
+    }
+}
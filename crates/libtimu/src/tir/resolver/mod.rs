@@ -17,7 +17,10 @@
 //! - **Creates**: Type locations, scope hierarchies, symbol tables
 //! - **Registers**: Type names, function signatures, class declarations
 //! - **Builds**: Forward reference tables for later resolution
-//! - **Order**: Uses, Interfaces, Extensions, Classes, Functions
+//! - **Order**: Uses, Type aliases, Enums, Interfaces, Extensions, Classes, Functions
+//!
+//! Module variables are resolved in a phase of their own between the two, once
+//! the imports of every module are known.
 //!
 //! ## Phase 2: Finish Phase  
 //! ```ignore
@@ -31,15 +34,18 @@
 //! # Resolution Process Flow
 //!
 //! ```text
-//! Module AST → build_file() → {
+//! Module AST → build_file(Resolve), build_file(Finish) → {
 //!     1. Collect statements by type
 //!     2. Phase 1 Resolution:
 //!        ├── Uses (imports)
+//!        ├── Type aliases (alternative type names)
+//!        ├── Enums (variants and their payloads)
 //!        ├── Interfaces (type contracts)
 //!        ├── Extensions (type augmentations)
 //!        ├── Classes (concrete types)
 //!        └── Functions (procedures)
-//!     3. Phase 2 Finishing:
+//!     3. Module variables (constants and globals)
+//!     4. Phase 2 Finishing:
 //!        ├── Validate implementations
 //!        ├── Check type compatibility
 //!        └── Resolve cross-references
@@ -94,7 +100,7 @@ use std::{borrow::Cow, fmt::Debug};
 use function::FunctionResolveError;
 use libtimu_macros::TimuError;
 use simplelog::debug;
-use statement::FunctionCallError;
+use statement::{FunctionCallError, StatementError};
 use strum_macros::{EnumDiscriminants, EnumProperty};
 
 use crate::{ast::{FileStatementAst, TypeNameAst}, nom_tools::ToRange};
@@ -102,12 +108,17 @@ use crate::{ast::{FileStatementAst, TypeNameAst}, nom_tools::ToRange};
 use super::{ast_signature::AstSignatureValue, context::TirContext, error::TirError, module::ModuleRef, scope::{ScopeError, ScopeLocation}, signature::{LocationTrait, SignaturePath}};
 
 pub mod class;
+pub mod enum_definition;
 pub mod extend;
 pub mod function;
+pub mod function_type;
 pub mod interface;
 pub mod module;
 pub mod module_use;
+pub mod module_variable;
 pub mod statement;
+pub mod tuple_type;
+pub mod type_alias;
 
 /// Unique identifier for types within the TIR type system
 /// 
@@ -213,6 +224,14 @@ fn build_type_name(type_name: &TypeNameAst) -> String {
 }
 
 fn get_object_location_or_resolve<'base>(context: &mut TirContext<'base>, type_name: &TypeNameAst<'base>, module: &ModuleRef<'base>, scope_location: ScopeLocation) -> Result<TypeLocation, TirError> {
+    if let Some(function) = &type_name.function {
+        return function_type::resolve_function_type(context, function, module, scope_location);
+    }
+
+    if let Some(elements) = &type_name.tuple {
+        return tuple_type::resolve_tuple_type(context, elements, module, scope_location);
+    }
+
     let type_name_str = build_type_name(type_name);
     let field_type = match try_resolve_signature(context, module, scope_location, type_name_str.as_str())? {
         Some(field_type) => field_type,
@@ -243,10 +262,16 @@ pub fn build_signature_path<'base>(context: &TirContext<'base>, name: &str, modu
 /// 
 /// ## Phase 1: Resolution
 /// 1. **Uses**: Import statements and module dependencies
-/// 2. **Interfaces**: Type contracts and abstract method signatures  
-/// 3. **Extensions**: Type augmentations and interface implementations
-/// 4. **Classes**: Concrete type definitions and inheritance
-/// 5. **Functions**: Procedure signatures and method definitions
+/// 2. **Type aliases**: Alternative names resolved to the aliased types
+/// 3. **Interfaces**: Type contracts and abstract method signatures  
+/// 4. **Extensions**: Type augmentations and interface implementations
+/// 5. **Classes**: Concrete type definitions and inheritance
+/// 6. **Functions**: Procedure signatures and method definitions
+/// 
+/// ## Module Variables
+/// Constants and global variables are type checked after every module is
+/// resolved, so their initial values can use constants imported from any
+/// module.
 /// 
 /// ## Phase 2: Finishing
 /// 1. **Validation**: Check implementations against interfaces
@@ -277,8 +302,23 @@ pub fn build_signature_path<'base>(context: &TirContext<'base>, name: &str, modu
 /// - Interfaces must be resolved before classes that implement them
 /// - Extensions require their target types to exist first
 /// - Functions are resolved last as they may reference all other types
-pub fn build_file<'base>(context: &mut TirContext<'base>, module_ref: ModuleRef<'base>) -> Result<(), TirError> {
-    simplelog::debug!("<on-red>Building file: {:?}</>", module_ref.as_ref());
+///
+/// Every module runs the resolution phase before any module is finished, so
+/// function bodies can call functions imported from modules built later.
+/// Phases of [`build_file`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildPhase {
+    /// Registers the types and signatures of every declaration
+    Resolve,
+    /// Type checks the module variables, their initial values can use the
+    /// imports of every module
+    Variables,
+    /// Type checks the declarations and function bodies
+    Finish,
+}
+
+pub fn build_file<'base>(context: &mut TirContext<'base>, module_ref: ModuleRef<'base>, phase: BuildPhase) -> Result<(), TirError> {
+    simplelog::debug!("<on-red>Building file: {:?} ({phase:?})</>", module_ref.as_ref());
     
     if let Some(ast) = context.modules.get(module_ref.as_ref()).and_then(|module| module.ast.clone()) {
         let uses = ast.statements.iter().filter(|statement| statement.is_use()).collect::<Vec<_>>();
@@ -286,32 +326,54 @@ pub fn build_file<'base>(context: &mut TirContext<'base>, module_ref: ModuleRef<
         let functions = ast.statements.iter().filter(|statement| statement.is_function()).collect::<Vec<_>>();
         let classes = ast.statements.iter().filter(|statement| statement.is_class()).collect::<Vec<_>>();
         let extends = ast.statements.iter().filter(|statement| statement.is_extend()).collect::<Vec<_>>();
+        let aliases = ast.statements.iter().filter(|statement| statement.is_type_alias()).collect::<Vec<_>>();
+        let enums = ast.statements.iter().filter(|statement| statement.is_enum()).collect::<Vec<_>>();
+        let variables = ast.statements.iter().filter(|statement| statement.is_variable()).collect::<Vec<_>>();
 
-        /* Resolving */
-        simplelog::debug!(" - Resolving all uses");
-        execute_vector_resolve(context, module_ref.clone(), &uses)?;
+        if phase == BuildPhase::Resolve {
+            simplelog::debug!(" - Resolving all uses");
+            execute_vector_resolve(context, module_ref.clone(), &uses)?;
 
-        simplelog::debug!(" - Resolving all interfaces");
-        execute_vector_resolve(context, module_ref.clone(), &interfaces)?;
+            simplelog::debug!(" - Resolving all type aliases");
+            execute_vector_resolve(context, module_ref.clone(), &aliases)?;
 
-        simplelog::debug!(" - Resolving all extends");
-        execute_extend_vector_resolve(context, module_ref.clone(), &extends)?;
+            simplelog::debug!(" - Resolving all enums");
+            execute_vector_resolve(context, module_ref.clone(), &enums)?;
 
-        simplelog::debug!(" - Resolving all classes");
-        execute_vector_resolve(context, module_ref.clone(), &classes)?;
+            simplelog::debug!(" - Resolving all interfaces");
+            execute_vector_resolve(context, module_ref.clone(), &interfaces)?;
 
-        simplelog::debug!(" - Resolving all functions");
-        execute_vector_resolve(context, module_ref.clone(), &functions)?;
-        
-        /* Finish */
-        simplelog::debug!(" - Resolving all uses");
+            simplelog::debug!(" - Resolving all extends");
+            execute_extend_vector_resolve(context, module_ref.clone(), &extends)?;
+
+            simplelog::debug!(" - Resolving all classes");
+            execute_vector_resolve(context, module_ref.clone(), &classes)?;
+
+            simplelog::debug!(" - Resolving all functions");
+            execute_vector_resolve(context, module_ref.clone(), &functions)?;
+            return Ok(());
+        }
+
+        if phase == BuildPhase::Variables {
+            simplelog::debug!(" - Resolving all module variables");
+            execute_variable_vector_resolve(context, module_ref.clone(), &variables)?;
+            return Ok(());
+        }
+
+        simplelog::debug!(" - Finishing all uses");
         execute_vector_finish(context, module_ref.clone(), uses)?;
 
+        simplelog::debug!(" - Finishing all type aliases");
+        execute_vector_finish(context, module_ref.clone(), aliases)?;
+
+        simplelog::debug!(" - Finishing all enums");
+        execute_vector_finish(context, module_ref.clone(), enums)?;
+
         simplelog::debug!(" - Finishing all interfaces");
         execute_vector_finish(context, module_ref.clone(), interfaces)?;
 
         simplelog::debug!(" - Finishing all extends");
-        // execute_vector_finish(context, module_ref.clone(), extends)?;
+        execute_extend_vector_finish(context, module_ref.clone(), extends)?;
 
         simplelog::debug!(" - Finishing all classes");
         execute_vector_finish(context, module_ref.clone(), classes)?;
@@ -355,6 +417,25 @@ fn execute_extend_vector_resolve<'base>(context: &mut TirContext<'base>, module_
     Ok(())
 }
 
+fn execute_variable_vector_resolve<'base>(context: &mut TirContext<'base>, module_ref: ModuleRef<'base>, asts: &Vec<&FileStatementAst<'base>>) -> Result<(), TirError> {
+    /* Module variables live in the module scope, the ones used by an earlier
+       initial value are already resolved */
+    let module_scope_location = module_ref.upgrade(context).unwrap().scope_location;
+    for item in asts.iter() {
+        item.resolve(context, module_scope_location)?;
+    }
+    Ok(())
+}
+
+fn execute_extend_vector_finish<'base>(context: &mut TirContext<'base>, module_ref: ModuleRef<'base>, asts: Vec<&FileStatementAst<'base>>) -> Result<(), TirError> {
+    /* Extends do not have scopes of their own, they are finished from the module scope */
+    let module_scope_location = module_ref.upgrade(context).unwrap().scope_location;
+    for item in asts.into_iter() {
+        item.finish(context, module_scope_location)?;
+    }
+    Ok(())
+}
+
 fn execute_resolve<'base, T: ResolveAst<'base>>(context: &mut TirContext<'base>, module_ref: ModuleRef<'base>, ast: &T) -> Result<(), TirError> {
     let module = module_ref.upgrade(context).unwrap();
 
@@ -446,6 +527,11 @@ pub fn try_resolve_direct_signature<'base, K: AsRef<str>>(context: &mut TirConte
         None => return Ok(None),
     };
 
+    // Module variables are values, they can not be used as a type
+    if matches!(signature.value.as_ref(), AstSignatureValue::Variable(_)) {
+        return Ok(None);
+    }
+
     let module = signature.extra.as_ref().unwrap().upgrade(context).unwrap();
     debug!("Module: <on-red>{}</>, Type values: <on-blue>{:?}</>", module.path.as_ref(), module.types.values());
 
@@ -547,6 +633,10 @@ pub enum ResolverError {
     #[error(transparent)]
     #[diagnostic(transparent)]
     FunctionResolve(#[from] Box<FunctionResolveError>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Statement(#[from] Box<StatementError>),
 }
 
 impl From<ResolverError> for TirError {
@@ -11,6 +11,9 @@
 //! use lib.SomeClass;           // Import SomeClass from lib module
 //! use lib.SomeClass as SC;     // Import with alias
 //! use utils.helper;            // Import helper function
+//! use lib.{A, B as C, sub.D};  // Import several items below lib
+//! use lib.*;                   // Import every public item of lib
+//! pub use lib.SomeClass;       // Import and re-export SomeClass
 //! ```
 //!
 //! The resolution process:
@@ -18,15 +21,30 @@
 //! 2. Determines the local name (either alias or original name)
 //! 3. Adds the import to the current module's import registry
 //! 4. Handles duplicate import detection and error reporting
+//!
+//! Grouped imports run these steps for every item of the group. Wildcard
+//! imports run them for every item of the module that passes
+//! [`check_import_accessibility`], including the items the module re-exports.
+//! Re-exports are registered while the module signatures are built, see
+//! [`TirContext::reexport_target`].
+//!
+//! When the imported item is unknown and the context has a module loader, the
+//! module the import refers to is loaded through its provider first, see
+//! [`crate::provider`].
 
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashSet, ops::Range, rc::Rc};
 
 use crate::{
-    ast::UseAst, 
+    ast::{UseAst, UseKindAst}, 
+    file::SourceFile,
+    nom_tools::Span,
+    process_code,
     tir::{
         accessibility::check_import_accessibility, 
+        ast_signature::build_module,
         context::TirContext, 
-        error::{ImportNotFound, ModuleAlreadyImported}, 
+        error::{ImportConflictsWithDefinition, ImportNotFound, ModuleAlreadyImported, ModuleLoadFailed}, 
+        resolver::AstSignatureLocation,
         scope::ScopeLocation, 
         TirError
     }
@@ -40,7 +58,7 @@ use super::{ResolveAst, TypeLocation};
 /// 1. **Resolve phase**: Validates imports and registers them in the module
 /// 2. **Finish phase**: No additional work needed for use statements
 impl<'base> ResolveAst<'base> for UseAst<'base> {
-    /// Resolves a `use` statement by importing the specified item into the current module.
+    /// Resolves a `use` statement by importing the specified items into the current module.
     /// 
     /// # Resolution Process
     /// 
@@ -49,6 +67,9 @@ impl<'base> ResolveAst<'base> for UseAst<'base> {
     /// 3. **Module registration**: Adds the import to the current module's import map
     /// 4. **Duplicate detection**: Checks for conflicting imports with the same local name
     /// 
+    /// Grouped imports resolve every item of the group, wildcard imports every
+    /// accessible item of the module.
+    /// 
     /// # Arguments
     /// 
     /// * `context` - Mutable reference to the TIR context containing all modules and signatures
@@ -66,49 +87,18 @@ impl<'base> ResolveAst<'base> for UseAst<'base> {
     /// use lib.Calculator;        // Imports Calculator class from lib module
     /// use utils.sort as mysort;  // Imports sort function with alias 'mysort'
     /// use utils;                 // Imports utils module
+    /// use utils.{sort, find};    // Imports sort and find functions
+    /// use utils.*;               // Imports every public item of utils
     /// ```
     fn resolve(&self, context: &mut TirContext<'base>, scope_location: ScopeLocation) -> Result<TypeLocation, TirError> {
-        // Step 1: Attempt to find the imported item in the AST signature registry
-        if let Some(signature_location) = context.get_ast_location(&self.import.text) {
-            // Step 2: Determine the local name for this import
-            // Use alias if provided, otherwise extract the last part of the import path
-            let name = match &self.alias {
-                Some(alias) => std::borrow::Cow::Borrowed(alias.text),
-                None => std::borrow::Cow::Borrowed(self.ast_name().text),
-            };
-
-            // Step 3: Get the current module and register the import
-            let module_ref = context.get_scope(scope_location).unwrap().module_ref.clone();
-            let module = context.modules.get_mut(module_ref.as_ref())
-                .unwrap_or_else(|| panic!("Module({}) not found, but this is a bug", module_ref.as_ref()));
-            
-            // Step 4: Check for duplicate imports with the same local name
-            if let Some(old) = module.ast_imported_modules.insert(name, signature_location) {
-                let old_signature = context.ast_signatures.get_from_location(old).unwrap();
-                return Err(TirError::ModuleAlreadyImported(ModuleAlreadyImported {
-                    new_position: self.import.to_range(),
-                    old_position: old_signature.position.clone(),
-                    code: self.ast_name().state.file.clone().into(),
-                }.into()));
+        match &self.kind {
+            UseKindAst::Single => import_item(context, scope_location, self, 0)?,
+            UseKindAst::Group(items) => {
+                for item in items.iter() {
+                    import_item(context, scope_location, item, self.import.paths.len())?;
+                }
             }
-
-            // Step 4.5: Check accessibility of the imported item
-            let ast_signature = context.ast_signatures.get_from_location(signature_location)
-                .unwrap_or_else(|| panic!("Signature not found for location: {signature_location:?}"));
-            
-            // Use the dedicated accessibility module to check import permissions
-            check_import_accessibility(
-                ast_signature,
-                self.import.paths.last().unwrap(),
-                &self.import.paths.last().unwrap().state.file
-            )?;
-        } else {
-            // Step 1 failed: The imported item doesn't exist in the signature registry
-            return Err(TirError::ImportNotFound(ImportNotFound {
-                module: self.import.text.to_string(),
-                position: self.import.to_range(),
-                code: self.ast_name().state.file.into(),
-            }.into()));
+            UseKindAst::Wildcard(wildcard) => import_wildcard(context, scope_location, self, wildcard)?,
         }
 
         // Use statements don't have a specific type location in the type system
@@ -135,7 +125,9 @@ impl<'base> ResolveAst<'base> for UseAst<'base> {
     /// Returns the local name that this import will be known by in the current scope.
     /// 
     /// This is the name that other code in the same module will use to reference
-    /// the imported item. It prioritizes aliases over the original name.
+    /// the imported item. It prioritizes aliases over the original name. Grouped
+    /// and wildcard imports return the last component of their path, the items
+    /// of a group have their own names.
     /// 
     /// # Returns
     /// 
@@ -158,6 +150,183 @@ impl<'base> ResolveAst<'base> for UseAst<'base> {
     }
 }
 
+/// Imports a single item, or one item of a group, into the module of the scope
+///
+/// `prefix` is the number of path components in front of a group, they are
+/// left out of the reported positions so errors point at the item itself.
+fn import_item<'base>(context: &mut TirContext<'base>, scope_location: ScopeLocation, import: &UseAst<'base>, prefix: usize) -> Result<(), TirError> {
+    let position = import.import.paths[prefix].position.start..import.ast_name().position.end;
+    let file = import.ast_name().state.file.clone();
+
+    // Step 1: Attempt to find the imported item in the AST signature registry,
+    // loading its module when it is not known yet
+    let signature_location = match find_import(context, import)? {
+        Some(signature_location) => signature_location,
+        None => return Err(TirError::ImportNotFound(ImportNotFound {
+            module: import.import.text.to_string(),
+            position,
+            code: file.into(),
+        }.into())),
+    };
+
+    // Step 2: Register the import under the alias or the last part of the import path
+    insert_import(context, scope_location, import.name(), signature_location, position, &file)?;
+
+    // Step 3: Check accessibility of the imported item
+    let ast_signature = context.ast_signatures.get_from_location(signature_location)
+        .unwrap_or_else(|| panic!("Signature not found for location: {signature_location:?}"));
+    
+    // Use the dedicated accessibility module to check import permissions
+    check_import_accessibility(ast_signature, import.import.paths.last().unwrap(), &file)
+}
+
+/// Imports every accessible item of a module, `use lib.*;`
+///
+/// Items the module re-exports are imported as well. Items that are not
+/// accessible from other modules are skipped instead of reported, a name that
+/// is already imported or defined is reported at the path of the import.
+fn import_wildcard<'base>(context: &mut TirContext<'base>, scope_location: ScopeLocation, import: &UseAst<'base>, wildcard: &Span<'base>) -> Result<(), TirError> {
+    let module_path = import.import.text.to_string();
+    let file = wildcard.state.file.clone();
+    let position = import.import.to_range().start..wildcard.position.end;
+
+    if !context.modules.contains_key(module_path.as_str()) {
+        load_imported_module(context, &module_path, import)?;
+    }
+
+    if !context.modules.contains_key(module_path.as_str()) {
+        return Err(TirError::ImportNotFound(ImportNotFound {
+            module: module_path,
+            position: import.import.to_range(),
+            code: file.into(),
+        }.into()));
+    }
+
+    for (name, signature_location) in wildcard_items(context, &module_path, &mut HashSet::new()) {
+        let ast_signature = context.ast_signatures.get_from_location(signature_location)
+            .unwrap_or_else(|| panic!("Signature not found for location: {signature_location:?}"));
+        if check_import_accessibility(ast_signature, wildcard, &file).is_err() {
+            continue;
+        }
+
+        insert_import(context, scope_location, Cow::Owned(name), signature_location, position.clone(), &file)?;
+    }
+    Ok(())
+}
+
+/// Collects the items of a module and the items it re-exports, by their name in the module
+fn wildcard_items(context: &TirContext<'_>, module_path: &str, visited: &mut HashSet<String>) -> Vec<(String, AstSignatureLocation)> {
+    let mut items = Vec::new();
+    if !visited.insert(module_path.to_string()) {
+        return items;
+    }
+
+    if let Some(module) = context.modules.get(module_path) {
+        items.extend(module.ast_signatures.iter().map(|(name, variable)| (name.get_name().to_string(), variable.location)));
+    }
+
+    let prefix = format!("{module_path}.");
+    for path in context.reexports.keys() {
+        if let Some(name) = path.strip_prefix(&prefix)
+            && let Some(signature_location) = context.get_ast_location(path) {
+            items.push((name.to_string(), signature_location));
+        }
+    }
+
+    for target in context.wildcard_reexports.get(module_path).into_iter().flatten() {
+        items.extend(wildcard_items(context, target, visited));
+    }
+    items
+}
+
+/// Adds an import to the module of the scope, reporting a name that is already imported or defined
+fn insert_import<'base>(context: &mut TirContext<'base>, scope_location: ScopeLocation, name: Cow<'base, str>, signature_location: AstSignatureLocation, position: Range<usize>, file: &SourceFile) -> Result<(), TirError> {
+    let module_ref = context.get_scope(scope_location).unwrap().module_ref.clone();
+    let module = context.modules.get_mut(module_ref.as_ref())
+        .unwrap_or_else(|| panic!("Module({}) not found, but this is a bug", module_ref.as_ref()));
+
+    if let Some(definition) = module.get_ast_signature(name.as_ref()) {
+        let definition = context.ast_signatures.get_from_location(definition).unwrap();
+        return Err(TirError::ImportConflictsWithDefinition(ImportConflictsWithDefinition {
+            name: name.to_string(),
+            import_position: position,
+            definition_position: definition.position.clone(),
+            code: file.clone().into(),
+        }.into()));
+    }
+
+    if let Some(old) = module.ast_imported_modules.insert(name, signature_location) {
+        let old_signature = context.ast_signatures.get_from_location(old).unwrap();
+        return Err(TirError::ModuleAlreadyImported(ModuleAlreadyImported {
+            new_position: position,
+            old_position: old_signature.position.clone(),
+            code: file.clone().into(),
+        }.into()));
+    }
+    Ok(())
+}
+
+/// Finds the signature of an imported path
+///
+/// Re-exports are followed to the item they import and the modules of the
+/// paths are loaded through the module loader when they are not known yet.
+fn find_import<'base>(context: &mut TirContext<'base>, import: &UseAst<'base>) -> Result<Option<AstSignatureLocation>, TirError> {
+    let mut path = import.import.text.to_string();
+    let mut visited = HashSet::new();
+
+    while visited.insert(path.clone()) {
+        if let Some(signature_location) = context.ast_signatures.location(&path) {
+            return Ok(Some(signature_location));
+        }
+
+        if let Some(target) = context.reexport_target(&path) {
+            path = target;
+        } else if load_imported_module(context, &path, import)? {
+            // The loaded module may define or re-export the path, look it up again
+            visited.remove(&path);
+        }
+    }
+    Ok(None)
+}
+
+/// Asks the module loader of the context for the module an import path refers to
+///
+/// Prefixes of the path are tried from the longest to the shortest and
+/// modules that are already known are skipped. Returns whether a module was
+/// loaded, it is registered like the files passed to the compiler.
+fn load_imported_module<'base>(context: &mut TirContext<'base>, path: &str, import: &UseAst<'base>) -> Result<bool, TirError> {
+    let loader = match context.loader {
+        Some(loader) => loader,
+        None => return Ok(false),
+    };
+
+    let segments = path.split('.').map(str::to_string).collect::<Vec<_>>();
+    for length in (1..=segments.len()).rev() {
+        let path = &segments[..length];
+        if context.modules.contains_key(path.join(".").as_str()) {
+            continue;
+        }
+
+        let file = match loader.provider.load(path) {
+            Ok(Some(file)) => file,
+            Ok(None) => continue,
+            Err(error) => return Err(TirError::ModuleLoadFailed(ModuleLoadFailed {
+                module: path.join("."),
+                reason: error.to_string(),
+                position: import.import.to_range(),
+                code: import.ast_name().state.file.into(),
+            }.into())),
+        };
+
+        simplelog::debug!("Loaded module <u><b>{}</b></u> from provider", path.join("."));
+        let ast = process_code(loader.arena.alloc(file))?;
+        build_module(context, Rc::new(ast))?;
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
 #[cfg(test)]
 mod tests {
     //! Unit tests for module use statement resolution.
@@ -172,6 +341,7 @@ mod tests {
         file::SourceFile, 
         nom_tools::{Span, State}, 
         parser::splited_path::SplitedPath, 
+        tests::build_modules_with,
         tir::{context::TirContext, module::Module, scope::ScopeLocation}
     };
 
@@ -218,6 +388,8 @@ mod tests {
         let import_path = create_test_splited_path("lib.TestClass", &state);
         
         let use_ast = UseAst {
+            is_public: None,
+            kind: UseKindAst::Single,
             import: import_path,
             alias: None,
         };
@@ -235,6 +407,8 @@ mod tests {
         let import_path = create_test_splited_path("lib.TestClass", &state);
         
         let use_ast = UseAst {
+            is_public: None,
+            kind: UseKindAst::Single,
             import: import_path,
             alias: Some(Span {
                 text: "TC",
@@ -256,6 +430,8 @@ mod tests {
         let import_path = create_test_splited_path("lib.TestClass", &state);
         
         let use_ast = UseAst {
+            is_public: None,
+            kind: UseKindAst::Single,
             import: import_path,
             alias: None,
         };
@@ -274,6 +450,8 @@ mod tests {
         let import_path = create_test_splited_path("lib.TestClass", &state);
         
         let use_ast = UseAst {
+            is_public: None,
+            kind: UseKindAst::Single,
             import: import_path,
             alias: None,
         };
@@ -300,6 +478,8 @@ mod tests {
         
         // Create UseAst for non-existent import
         let use_ast = UseAst {
+            is_public: None,
+            kind: UseKindAst::Single,
             import: import_path,
             alias: None,
         };
@@ -331,6 +511,8 @@ mod tests {
         let import_path = create_test_splited_path("lib.TestClass", &state);
         
         let use_ast = UseAst {
+            is_public: None,
+            kind: UseKindAst::Single,
             import: import_path,
             alias: None,
         };
@@ -360,6 +542,8 @@ mod tests {
         let import_path = create_test_splited_path("module.submodule.Class", &state);
         
         let use_ast = UseAst {
+            is_public: None,
+            kind: UseKindAst::Single,
             import: import_path,
             alias: None,
         };
@@ -443,6 +627,8 @@ mod tests {
         let import_path = create_test_splited_path("very.long.path.ClassName", &state);
         
         let use_ast = UseAst {
+            is_public: None,
+            kind: UseKindAst::Single,
             import: import_path,
             alias: Some(Span {
                 text: "Short",
@@ -912,4 +1098,84 @@ class PrivateClass {
             other => panic!("Expected AccessibilityViolation error, got: {:?}", other),
         }
     }
+    /// Builds the given modules and returns the names `main` imports
+    fn main_imports(modules: &[(&str, &str)]) -> Result<Vec<String>, TirError> {
+        build_modules_with(modules, |context| context.modules.get("main").unwrap().ast_imported_modules.keys().map(|name| name.to_string()).collect())
+    }
+
+    const LIB: (&str, &str) = ("lib", "pub class A {}\npub func b(): void {}\ninterface C {}\nclass Private {}");
+
+    /// Grouped imports register every item under its name or alias
+    #[test]
+    fn use_ast_group_imports() {
+        let imports = main_imports(&[LIB, ("lib.sub", "pub class D {}"), ("main", "use lib.{A, b as c, C, sub.D};")]).unwrap();
+        assert_eq!(imports, vec!["A", "c", "C", "D"]);
+    }
+
+    /// Wildcard imports register every accessible item of the module
+    #[test]
+    fn use_ast_wildcard_imports() {
+        let imports = main_imports(&[LIB, ("main", "use lib.*;")]).unwrap();
+        assert_eq!(imports, vec!["C", "A", "b"]);
+    }
+
+    /// Items re-exported with `pub use` can be imported from the re-exporting module
+    #[test]
+    fn use_ast_reexports() {
+        let middle = ("middle", "pub use lib.A;\npub use lib.{b as c};\npub use lib.sub.*;");
+        let imports = main_imports(&[LIB, ("lib.sub", "pub class D {}"), middle, ("main", "use middle.{A, c, D};")]).unwrap();
+        assert_eq!(imports, vec!["A", "c", "D"]);
+
+        let imports = main_imports(&[LIB, ("lib.sub", "pub class D {}"), middle, ("main", "use middle.*;")]).unwrap();
+        assert_eq!(imports, vec!["A", "c", "D"]);
+    }
+
+    /// Every form of import reports names that are imported twice
+    #[rstest::rstest]
+    #[case("use lib.{A, A};")]
+    #[case("use lib.A;\nuse lib.{A};")]
+    #[case("use lib.{b as A, A};")]
+    #[case("use lib.A;\nuse lib.*;")]
+    #[case("use lib.*;\nuse other.*;")]
+    fn use_ast_already_imported(#[case] code: &str) {
+        let result = main_imports(&[LIB, ("other", "pub class A {}"), ("main", code)]);
+        assert!(matches!(result, Err(TirError::ModuleAlreadyImported(_))), "{code}: {result:?}");
+    }
+
+    /// Imports of a name the module defines name the item, and point at the import and the definition
+    #[rstest::rstest]
+    #[case("use lib.*;\nclass A {}", "lib.*")]
+    #[case("class A {}\nuse lib.*;", "lib.*")]
+    #[case("use lib.A;\nclass A {}", "lib.A")]
+    #[case("use lib.{A};\nfunc A(): void {}", "A")]
+    fn use_ast_conflicts_with_definition(#[case] code: &str, #[case] import: &str) {
+        match main_imports(&[LIB, ("main", code)]) {
+            Err(TirError::ImportConflictsWithDefinition(error)) => {
+                assert_eq!(error.name, "A");
+                assert_eq!(&code[error.import_position.clone()], import);
+                assert_eq!(&code[error.definition_position.clone()], "A");
+            },
+            result => panic!("{code}: {result:?}"),
+        }
+    }
+
+    /// Grouped imports and re-exports check the accessibility of every item
+    #[rstest::rstest]
+    #[case("use lib.{A, Private};")]
+    #[case("pub use lib.Private;")]
+    fn use_ast_group_accessibility(#[case] code: &str) {
+        let result = main_imports(&[LIB, ("main", code)]);
+        assert!(matches!(result, Err(TirError::AccessibilityViolation(_))), "{code}: {result:?}");
+    }
+
+    /// Unknown items, unknown modules and items that are imported without `pub` are not found
+    #[rstest::rstest]
+    #[case(&[LIB, ("main", "use lib.{A, Missing};")])]
+    #[case(&[LIB, ("main", "use missing.*;")])]
+    #[case(&[LIB, ("middle", "use lib.A;"), ("main", "use middle.A;")])]
+    #[case(&[LIB, ("middle", "pub use middle.A;"), ("main", "use middle.A;")])]
+    fn use_ast_import_not_found(#[case] modules: &[(&str, &str)]) {
+        let result = main_imports(modules);
+        assert!(matches!(result, Err(TirError::ImportNotFound(_))), "{result:?}");
+    }
 }
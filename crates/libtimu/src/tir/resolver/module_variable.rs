@@ -0,0 +1,170 @@
+//! Module-level constant and global variable resolution for the Timu language compiler.
+//!
+//! Constants and global variables are declared at the top level of a file:
+//! ```timu
+//! pub const MAX_USERS: i32 = 100;
+//! const LIMIT = MAX_USERS * 2;
+//! var counter: i32 = 0;
+//! ```
+//!
+//! They are registered in the module scope, so every function of the module
+//! sees them like its own variables, and a `pub` one can be imported with
+//! `use`. The initial value is type checked like a variable definition in a
+//! function body and evaluated at compile time, see
+//! [`crate::tir::const_eval`].
+//!
+//! Initial values can refer to constants that are declared later or in other
+//! modules, those are resolved on demand. A constant whose value depends on
+//! itself, directly or through other constants, is reported as a
+//! [`crate::tir::error::CircularReference`].
+
+use std::borrow::Cow;
+
+use crate::{
+    ast::{BodyStatementAst, ModuleVariableAst, VariableDefinitionAst, VariableDefinitionType},
+    nom_tools::{Span, ToRange},
+    tir::{ast_signature::AstSignatureValue, context::{SpanKey, TirContext}, scope::{ScopeLocation, TypeVariableInformation}, TirError},
+};
+
+use super::{ResolveAst, TypeLocation};
+
+/// A resolved module-level constant or global variable, see [`TirContext::globals`]
+#[derive(Debug, Clone)]
+pub struct GlobalVariable<'base> {
+    /// Full path of the variable, such as `main.LIMIT`
+    pub path: String,
+    /// Type of the variable, [`TypeLocation::UNDEFINED`] while its initial value is resolved
+    pub location: TypeLocation,
+    /// Whether the variable can be imported from other modules
+    pub is_public: bool,
+    /// The declaration of the variable
+    pub definition: VariableDefinitionAst<'base>,
+}
+
+impl GlobalVariable<'_> {
+    /// Whether the variable is a constant, only constants can be used in other initial values
+    pub fn is_const(&self) -> bool {
+        self.definition.variable_definition_type == VariableDefinitionType::Const
+    }
+}
+
+impl<'base> ResolveAst<'base> for ModuleVariableAst<'base> {
+    /// Type checks the initial value and registers the variable in the module scope
+    fn resolve(&self, context: &mut TirContext<'base>, scope_location: ScopeLocation) -> Result<TypeLocation, TirError> {
+        let name = &self.definition.name;
+        let key = SpanKey::from(name);
+
+        // A variable that is still being resolved is needed by its own initial value
+        match context.globals.get(&key) {
+            Some(global) if global.location == TypeLocation::UNDEFINED => return Err(TirError::circular_reference(name.to_range(), name.state.file.clone())),
+            Some(global) => return Ok(global.location),
+            None => (),
+        };
+
+        simplelog::debug!("Resolving module variable: <u><b>{}</b></u>", name.text);
+        let module_ref = context.get_scope(scope_location).expect("Scope not found").module_ref.clone();
+        let module = module_ref.upgrade(context).unwrap_or_else(|| panic!("Module({}) not found, but this is a bug", module_ref.as_ref()));
+        let module_scope_location = module.scope_location;
+
+        context.globals.insert(key.clone(), GlobalVariable {
+            path: format!("{}.{}", module.path, name.text),
+            location: TypeLocation::UNDEFINED,
+            is_public: self.is_public.is_some(),
+            definition: self.definition.clone(),
+        });
+
+        let location = BodyStatementAst::resolve_variable_definition(context, module_scope_location, &self.definition)?;
+        if let Some(global) = context.globals.get_mut(&key) {
+            global.location = location;
+        }
+
+        Ok(location)
+    }
+
+    /// Module variables have no body, everything is done while resolving them
+    fn finish(&self, _: &mut TirContext<'base>, _: ScopeLocation) -> Result<(), TirError> {
+        Ok(())
+    }
+
+    fn name(&self) -> Cow<'base, str> {
+        Cow::Borrowed(self.definition.name.text)
+    }
+}
+
+/// Finds a module variable that is not resolved yet and resolves it
+///
+/// The name is looked up in the declarations and imports of the module the
+/// scope belongs to. Returns `None` when the name is not a module variable.
+pub fn resolve_global<'base>(context: &mut TirContext<'base>, scope_location: ScopeLocation, name: &Span<'base>) -> Result<Option<TypeVariableInformation<'base>>, TirError> {
+    let module_ref = context.get_scope(scope_location).expect("Scope not found, it is a bug").module_ref.clone();
+    let module = module_ref.upgrade(context).unwrap_or_else(|| panic!("Module({}) not found, but this is a bug", module_ref.as_ref()));
+
+    let Some(signature_location) = module.get_ast_signature(name.text).or_else(|| module.ast_imported_modules.get(name.text).copied()) else {
+        return Ok(None);
+    };
+
+    let (variable, owner) = match context.ast_signatures.get_from_location(signature_location) {
+        Some(signature) => match (signature.value.as_ref(), signature.extra.as_ref()) {
+            (AstSignatureValue::Variable(variable), Some(owner)) => (variable.clone(), owner.clone()),
+            _ => return Ok(None),
+        },
+        None => return Ok(None),
+    };
+
+    let owner_scope_location = owner.upgrade(context).unwrap_or_else(|| panic!("Module({}) not found, but this is a bug", owner.as_ref())).scope_location;
+    variable.resolve(context, owner_scope_location)?;
+
+    let scope = context.get_scope(scope_location).expect("Scope not found, it is a bug");
+    Ok(scope.get_variable(context, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::{tests::build_modules, tir::TirError};
+
+    #[rstest]
+    #[case("const LIMIT: i32 = 10;\nfunc get(): i32 { return LIMIT; }")]
+    #[case("func get(): i32 { return DOUBLE; }\nconst DOUBLE = LIMIT * 2;\nconst LIMIT: i32 = 10;")]
+    #[case("const NAME: string = \"timu\";\nconst GREETING = \"hello \" + NAME;")]
+    #[case("var counter: i32 = 0;\nfunc increment(): void { counter = counter + 1; }")]
+    #[case("type Meters = i32;\nconst WIDTH: Meters = 4;")]
+    #[case("use lib.MAX;\nconst LIMIT = MAX + 1;")]
+    #[case("use lib.{MAX as UPPER};\nfunc get(): i32 { return UPPER; }")]
+    #[case("use lib.*;\nfunc get(): i32 { return MAX; }")]
+    fn valid_module_variable(#[case] code: &str) -> Result<(), TirError> {
+        build_modules(&[("lib", "pub const MAX: i32 = 100;\nconst HIDDEN: i32 = 1;"), ("main", code)])
+    }
+
+    #[rstest]
+    #[case("const A: i32 = A;")]
+    #[case("const A: i32 = B;\nconst B: i32 = A + 1;")]
+    #[case("const A: i32 = B;\nconst B: i32 = C;\nconst C: i32 = A;\nfunc get(): i32 { return B; }")]
+    fn circular_module_variable(#[case] code: &str) {
+        let result = build_modules(&[("main", code)]);
+        assert!(matches!(result, Err(TirError::CircularReference(_))), "{code}: {result:?}");
+    }
+
+    #[test]
+    fn cross_module_cycle() {
+        let result = build_modules(&[("lib", "use main.B;\npub const A: i32 = B + 1;"), ("main", "use lib.A;\npub const B: i32 = A * 2;")]);
+        assert!(matches!(result, Err(TirError::CircularReference(_))), "{result:?}");
+    }
+
+    #[test]
+    fn private_module_variable_import() {
+        let result = build_modules(&[("lib", "const HIDDEN: i32 = 1;"), ("main", "use lib.HIDDEN;")]);
+        assert!(matches!(result, Err(TirError::AccessibilityViolation(_))), "{result:?}");
+    }
+
+    #[rstest]
+    #[case("const LIMIT: i32 = \"ten\";")]
+    #[case("const LIMIT: i32 = 10;\nfunc set(): void { LIMIT = 20; }")]
+    #[case("const LIMIT: i32 = 10;\nfunc get(value: LIMIT): void { }")]
+    #[case("const LIMIT: i32 = MISSING;")]
+    #[case("const LIMIT: i32 = 10;\nfunc LIMIT(): void { }")]
+    fn invalid_module_variable(#[case] code: &str) {
+        assert!(build_modules(&[("main", code)]).is_err(), "{code}");
+    }
+}
@@ -0,0 +1,570 @@
+//! Expression type resolution for the Timu TIR system.
+//!
+//! This module determines the type of every expression that can appear inside
+//! a function body and validates the operands of unary and binary operators.
+//! Statements such as variable definitions, assignments, conditions and returns
+//! build on top of it to check that values are used with compatible types.
+//!
+//! # Expression Types
+//!
+//! ```timu
+//! 42                  // Literal, typed by its value
+//! name                // Variable or argument
+//! ref this.field      // Reference to a field, typed by the field
+//! !flag               // Negation, requires `bool`
+//! calculate(1, 2)     // Function call, typed by the return type
+//! a + b * 2           // Binary operation
+//! func(a: i32): i32 { return a; }  // Lambda, typed by its signature
+//! match (a) { 1 => "one", _ => "many" }  // Match, typed by its arms
+//! (1, "one")          // Tuple, typed by its elements
+//! ref Shape.Empty     // Enum variant, typed by its enum
+//! ```
+//!
+//! A named function used as a value has the function type of its signature,
+//! see [`super::lambda`].
+//!
+//! # Numeric Literals
+//!
+//! Numeric literals do not have a fixed type. The parser picks the smallest
+//! type that can hold the value, but a literal is accepted wherever any
+//! numeric type that can represent it is expected:
+//!
+//! ```timu
+//! var small: i64 = 42;     // `42` is parsed as i8 but fits into i64
+//! var ratio: double = 2;   // Integer literals are valid floating point values
+//! var total: u8 = 300;     // Error: 300 does not fit into u8
+//! ```
+//!
+//! Variables defined without an explicit type fall back to `i32` for integer
+//! literals, unless the value needs a wider type.
+//!
+//! # Operators
+//!
+//! - **Arithmetic** (`+ - * / %`): numeric operands, `+` also joins strings
+//! - **Comparison** (`== != < <= > >=`): operands of the same type, result is `bool`
+//! - **Logical** (`&& ||`): `bool` operands
+//! - **Bitwise** (`& | ^ << >>`): integer operands, `& | ^` also accept `bool`
+
+use std::ops::Range;
+
+use libtimu_macros::TimuError;
+use libtimu_macros_core::SourceCode;
+
+use crate::{
+    ast::{BodyStatementAst, ExpressionAst, ExpressionOperatorType, MatchArmBodyAst, PrimitiveValue},
+    nom_tools::{SpanInfo, ToRange},
+    tir::{
+        object_signature::{GetItem, PrimitiveType},
+        context::{IdentifierTarget, SpanKey},
+        resolver::{enum_definition::enum_variant, function::{check_static_access, find_class_location, FunctionResolveError}, module_variable::resolve_global, statement::{try_resolve_primitive, FunctionCallError, StatementError}, TypeLocation},
+        scope::ScopeLocation,
+        TirContext, TirError, TypeValue,
+    },
+};
+
+use super::{function_call::CallPathNotValid, tuple::tuple_elements};
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("expected `{expected}` type, got `{got}`")]
+#[diagnostic(code("T0025"), help("change the expression or the expected type so they match"))]
+pub struct ExpressionTypeMismatch {
+    pub expected: String,
+    pub got: String,
+
+    #[label("this expression is `{got}`")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{operator}` cannot be applied to `{left}` and `{right}`")]
+#[diagnostic(code("T0026"), help("check the operand types of the operator"))]
+pub struct OperatorTypeMismatch {
+    pub operator: String,
+    pub left: String,
+    pub right: String,
+
+    #[label("unsupported operand types")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+impl StatementError {
+    pub fn expression_type_mismatch(context: &TirContext<'_>, expected: TypeLocation, got: TypeLocation, span: SpanInfo) -> TirError {
+        StatementError::ExpressionTypeMismatch(ExpressionTypeMismatch {
+            expected: type_name(context, expected),
+            got: type_name(context, got),
+            position: span.position,
+            code: span.file.into(),
+        }.into()).into()
+    }
+
+    pub fn operator_type_mismatch(context: &TirContext<'_>, operator: &ExpressionOperatorType, left: TypeLocation, right: TypeLocation, span: SpanInfo) -> TirError {
+        StatementError::OperatorTypeMismatch(OperatorTypeMismatch {
+            operator: operator.to_string(),
+            left: type_name(context, left),
+            right: type_name(context, right),
+            position: span.position,
+            code: span.file.into(),
+        }.into()).into()
+    }
+}
+
+/// Returns the user facing name of the type stored at the given location
+pub fn type_name(context: &TirContext<'_>, location: TypeLocation) -> String {
+    match context.types.get_from_location(location) {
+        Some(signature) => signature.value.get_name().to_string(),
+        None => context.types.get_reserve_from_location(location).map(|reservation| reservation.name.to_string()).unwrap_or_default(),
+    }
+}
+
+/// Returns the primitive type stored at the given location, if it is one
+pub fn get_primitive_type(context: &TirContext<'_>, location: TypeLocation) -> Option<PrimitiveType> {
+    match context.types.get_from_location(location).map(|signature| signature.value.as_ref()) {
+        Some(TypeValue::PrimitiveType(primitive)) => Some(primitive.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the location of a built-in primitive type
+///
+/// Primitive types are registered before any module is built, so a missing
+/// primitive is a compiler bug rather than a user error.
+pub fn get_primitive_location(context: &TirContext<'_>, primitive: PrimitiveType) -> TypeLocation {
+    context.types.find_by_value(&TypeValue::PrimitiveType(primitive)).expect("Primitive type not registered, but this is a bug")
+}
+
+pub fn is_integer(primitive: &PrimitiveType) -> bool {
+    matches!(primitive, PrimitiveType::I8 | PrimitiveType::U8 | PrimitiveType::I16 | PrimitiveType::U16 | PrimitiveType::I32 | PrimitiveType::U32 | PrimitiveType::I64 | PrimitiveType::U64)
+}
+
+pub fn is_numeric(primitive: &PrimitiveType) -> bool {
+    is_integer(primitive) || matches!(primitive, PrimitiveType::Float | PrimitiveType::Double)
+}
+
+/// Computes the source range covered by an expression
+///
+/// Only leaf expressions carry spans, so compound expressions are measured
+/// from their left-most to their right-most leaf.
+pub fn expression_position(expression: &ExpressionAst<'_>) -> SpanInfo {
+    match expression {
+        ExpressionAst::Primitive { span, .. } => span.into(),
+        ExpressionAst::Ident(ident) => ident.into(),
+        ExpressionAst::Ref(reference) => {
+            let first = reference.names.first().expect("Reference without name, but this is a bug");
+            let last = reference.names.last().expect("Reference without name, but this is a bug");
+            SpanInfo::new(first.position.start..last.position.end, first.state.file.clone())
+        },
+        ExpressionAst::Not(inner) => expression_position(inner),
+        ExpressionAst::FunctionCall(function_call) => SpanInfo::new(function_call.call_span.position.start..function_call.arguments_span.position.end.max(function_call.call_span.position.end), function_call.call_span.state.file.clone()),
+        ExpressionAst::Operation { left, right, .. } => {
+            let left = expression_position(left);
+            let right = expression_position(right);
+            SpanInfo::new(left.position.start.min(right.position.start)..left.position.end.max(right.position.end), left.file)
+        },
+        ExpressionAst::Lambda(lambda) => (&lambda.span).into(),
+        ExpressionAst::Match(match_ast) => (&match_ast.span).into(),
+        ExpressionAst::Tuple(tuple) => (&tuple.span).into(),
+    }
+}
+
+/// Whether the expression only consists of numeric literals combined with arithmetic operators
+///
+/// A match whose arms are all numeric literals counts as one.
+pub fn is_numeric_literal(expression: &ExpressionAst<'_>) -> bool {
+    match expression {
+        ExpressionAst::Primitive { value, .. } => is_numeric(&value.to_type()),
+        ExpressionAst::Operation { left, operator, right } => matches!(operator, ExpressionOperatorType::Add | ExpressionOperatorType::Sub | ExpressionOperatorType::Mul | ExpressionOperatorType::Div | ExpressionOperatorType::Mod) && is_numeric_literal(left) && is_numeric_literal(right),
+        ExpressionAst::Match(match_ast) => !match_ast.arms.is_empty() && match_ast.arms.iter().all(|arm| matches!(&arm.body, MatchArmBodyAst::Expression(expression) if is_numeric_literal(expression))),
+        _ => false,
+    }
+}
+
+/// Whether a numeric literal expression contains a floating point literal
+fn has_float_literal(expression: &ExpressionAst<'_>) -> bool {
+    match expression {
+        ExpressionAst::Primitive { value, .. } => matches!(value, PrimitiveValue::Float(..) | PrimitiveValue::Double(..)),
+        ExpressionAst::Operation { left, right, .. } => has_float_literal(left) || has_float_literal(right),
+        ExpressionAst::Match(match_ast) => match_ast.arms.iter().any(|arm| matches!(&arm.body, MatchArmBodyAst::Expression(expression) if has_float_literal(expression))),
+        _ => false,
+    }
+}
+
+/// Whether a literal value can be represented by the given integer type
+fn literal_fits(value: &PrimitiveValue<'_>, primitive: &PrimitiveType) -> bool {
+    let number: i128 = match value {
+        PrimitiveValue::I8(number) => *number as i128,
+        PrimitiveValue::U8(number) => *number as i128,
+        PrimitiveValue::I16(number) => *number as i128,
+        PrimitiveValue::U16(number) => *number as i128,
+        PrimitiveValue::I32(number) => *number as i128,
+        PrimitiveValue::U32(number) => *number as i128,
+        PrimitiveValue::I64(number) => *number as i128,
+        PrimitiveValue::U64(number) => *number as i128,
+        _ => return true,
+    };
+
+    match primitive {
+        PrimitiveType::I8 => i8::try_from(number).is_ok(),
+        PrimitiveType::U8 => u8::try_from(number).is_ok(),
+        PrimitiveType::I16 => i16::try_from(number).is_ok(),
+        PrimitiveType::U16 => u16::try_from(number).is_ok(),
+        PrimitiveType::I32 => i32::try_from(number).is_ok(),
+        PrimitiveType::U32 => u32::try_from(number).is_ok(),
+        PrimitiveType::I64 => i64::try_from(number).is_ok(),
+        PrimitiveType::U64 => u64::try_from(number).is_ok(),
+        _ => true,
+    }
+}
+
+/// Checks whether a value of type `got`, produced by `expression`, can be stored where `expected` is required
+///
+/// Besides exact type matches this accepts classes for the interfaces they
+/// implement and numeric literals for any numeric type that can hold them.
+/// Tuple literals are checked element by element.
+pub fn is_assignable(context: &TirContext<'_>, expected: TypeLocation, got: TypeLocation, expression: &ExpressionAst<'_>) -> bool {
+    if expected == got {
+        return true;
+    }
+
+    if let (Some(expected_signature), Some(got_signature)) = (context.types.get_from_location(expected), context.types.get_from_location(got))
+        && expected_signature.value.is_same_type(context, &got_signature.value) {
+        return true;
+    }
+
+    if let ExpressionAst::Tuple(tuple) = expression {
+        return match (tuple_elements(context, expected), tuple_elements(context, got)) {
+            (Some(expected), Some(got)) => expected.len() == tuple.elements.len() && expected.iter().zip(got.iter()).zip(tuple.elements.iter())
+                .all(|((expected, got), element)| is_assignable(context, expected.field_type, got.field_type, element) && (expected.is_nullable || !got.is_nullable)),
+            _ => false,
+        };
+    }
+
+    if !is_numeric_literal(expression) {
+        return false;
+    }
+
+    match get_primitive_type(context, expected) {
+        Some(PrimitiveType::Float | PrimitiveType::Double) => true,
+        Some(primitive) if is_integer(&primitive) => match expression {
+            ExpressionAst::Primitive { value, .. } => !has_float_literal(expression) && literal_fits(value, &primitive),
+            ExpressionAst::Match(match_ast) => match_ast.arms.iter().all(|arm| matches!(&arm.body, MatchArmBodyAst::Expression(arm) if is_assignable(context, expected, got, arm))),
+            _ => !has_float_literal(expression),
+        },
+        _ => false,
+    }
+}
+
+/// Picks the type of a variable that is defined without an explicit type
+///
+/// Integer literals default to `i32` unless the value needs a wider type,
+/// every other expression keeps its resolved type.
+pub fn default_expression_type(context: &TirContext<'_>, location: TypeLocation, expression: &ExpressionAst<'_>) -> TypeLocation {
+    if !is_numeric_literal(expression) || has_float_literal(expression) {
+        return location;
+    }
+
+    match get_primitive_type(context, location) {
+        Some(PrimitiveType::I8 | PrimitiveType::U8 | PrimitiveType::I16 | PrimitiveType::U16) => get_primitive_location(context, PrimitiveType::I32),
+        _ => location,
+    }
+}
+
+/// Type used for a numeric literal when the surrounding code does not require one
+///
+/// Literals that fit into 32 bits become `i32`, wider literals keep their own type.
+pub fn default_literal_type(value: &PrimitiveValue<'_>) -> PrimitiveType {
+    match value {
+        PrimitiveValue::String(_) => PrimitiveType::String,
+        PrimitiveValue::Bool(_) => PrimitiveType::Bool,
+        PrimitiveValue::U32(_) => PrimitiveType::U32,
+        PrimitiveValue::I64(_) => PrimitiveType::I64,
+        PrimitiveValue::U64(_) => PrimitiveType::U64,
+        PrimitiveValue::Float(..) => PrimitiveType::Float,
+        PrimitiveValue::Double(..) => PrimitiveType::Double,
+        _ => PrimitiveType::I32,
+    }
+}
+
+/// Type of a literal only expression, decided by its left-most literal
+pub fn literal_type(expression: &ExpressionAst<'_>) -> PrimitiveType {
+    match expression {
+        ExpressionAst::Primitive { value, .. } => default_literal_type(value),
+        ExpressionAst::Operation { left, .. } => literal_type(left),
+        ExpressionAst::Match(match_ast) => match match_ast.arms.first().map(|arm| &arm.body) {
+            Some(MatchArmBodyAst::Expression(expression)) => literal_type(expression),
+            _ => PrimitiveType::I32,
+        },
+        _ => PrimitiveType::I32,
+    }
+}
+
+/// Finds the type of an already checked expression
+///
+/// Numeric literals have no type of their own, so expressions made only of
+/// numeric literals return `None` and take their type from the surrounding
+/// code. `lookup` returns the type of a local variable or argument.
+pub fn infer_expression_type(context: &TirContext<'_>, expression: &ExpressionAst<'_>, lookup: &dyn Fn(&str) -> Option<TypeLocation>) -> Option<TypeLocation> {
+    match expression {
+        ExpressionAst::Primitive { value, .. } => match value {
+            PrimitiveValue::String(_) => Some(get_primitive_location(context, PrimitiveType::String)),
+            PrimitiveValue::Bool(_) => Some(get_primitive_location(context, PrimitiveType::Bool)),
+            _ => None,
+        },
+        ExpressionAst::Ident(ident) => lookup(ident.text),
+        ExpressionAst::Ref(reference) => {
+            let mut names = reference.names.iter();
+            let mut location = lookup(names.next()?.text)?;
+
+            for name in names {
+                location = context.types.get_from_location(location)?.value.get_item_location(context, name.text)?;
+            }
+
+            // Plain variants are values of their enum
+            match enum_variant(context, location) {
+                Some(variant) if variant.payload.is_empty() => Some(variant.enum_type),
+                _ => Some(location),
+            }
+        },
+        ExpressionAst::Not(_) => Some(get_primitive_location(context, PrimitiveType::Bool)),
+        ExpressionAst::FunctionCall(function_call) => match context.call_targets.get(&SpanKey::from(&function_call.call_span)).and_then(|location| context.types.get_from_location(*location)).map(|signature| signature.value.as_ref()) {
+            Some(TypeValue::Function(callee)) => Some(callee.return_type),
+            Some(TypeValue::FunctionType(callee)) => Some(callee.return_type),
+            Some(TypeValue::EnumVariant(variant)) => Some(variant.enum_type),
+            _ => None,
+        },
+        ExpressionAst::Operation { left, operator, right } => match is_bool_operator(operator) {
+            true => Some(get_primitive_location(context, PrimitiveType::Bool)),
+            false => infer_expression_type(context, left, lookup).or_else(|| infer_expression_type(context, right, lookup)),
+        },
+        ExpressionAst::Lambda(lambda) => context.lambdas.get(&SpanKey::from(&lambda.span)).map(|lambda| lambda.location),
+        ExpressionAst::Match(_) if is_numeric_literal(expression) => None,
+        ExpressionAst::Match(match_ast) => context.matches.get(&SpanKey::from(&match_ast.span)).map(|information| information.location),
+        ExpressionAst::Tuple(tuple) => context.tuples.get(&SpanKey::from(&tuple.span)).copied(),
+    }
+}
+
+/// Whether the operator always produces a `bool`
+pub fn is_bool_operator(operator: &ExpressionOperatorType) -> bool {
+    matches!(operator, ExpressionOperatorType::Equal | ExpressionOperatorType::NotEqual | ExpressionOperatorType::GreaterEqualThan | ExpressionOperatorType::GreaterThan |
+        ExpressionOperatorType::LessEqualThan | ExpressionOperatorType::LessThan | ExpressionOperatorType::And | ExpressionOperatorType::Or)
+}
+
+/// Picks the type both operands of a binary operation are evaluated in
+///
+/// Literals take the type of the other operand. When both operands are
+/// literals, arithmetic uses the type expected by the surrounding code and
+/// comparisons fall back to the type of the left-most literal.
+pub fn operand_type(context: &TirContext<'_>, left: &ExpressionAst<'_>, operator: &ExpressionOperatorType, right: &ExpressionAst<'_>, expected: Option<TypeLocation>, lookup: &dyn Fn(&str) -> Option<TypeLocation>) -> TypeLocation {
+    let expected = match is_bool_operator(operator) {
+        true => None,
+        false => expected.filter(|expected| get_primitive_type(context, *expected).as_ref().is_some_and(is_numeric)),
+    };
+
+    infer_expression_type(context, left, lookup)
+        .or_else(|| infer_expression_type(context, right, lookup))
+        .or(expected)
+        .unwrap_or_else(|| get_primitive_location(context, literal_type(left)))
+}
+
+impl<'base> BodyStatementAst<'base> {
+    /// Resolves the type of an expression within the given scope
+    ///
+    /// # Arguments
+    /// * `context` - Mutable TIR context for type system access
+    /// * `scope_location` - The scope the expression is evaluated in
+    /// * `expression` - The expression to resolve
+    ///
+    /// # Returns
+    /// * `Ok(TypeLocation)` - Location of the expression's type
+    /// * `Err(TirError)` - Unknown identifiers, invalid paths or operand type errors
+    pub fn resolve_expression(context: &mut TirContext<'base>, scope_location: ScopeLocation, expression: &ExpressionAst<'base>) -> Result<TypeLocation, TirError> {
+        match expression {
+            ExpressionAst::Primitive { span, value } => try_resolve_primitive(context, value, span),
+            ExpressionAst::Ident(ident) => {
+                let location = Self::resolve_ident(context, scope_location, ident)?;
+                Self::function_value(context, location, ident.into())
+            },
+            ExpressionAst::Ref(reference) => {
+                let mut names = reference.names.iter();
+                let first = names.next().expect("Reference without name, but this is a bug");
+                let mut location = Self::resolve_ident(context, scope_location, first)?;
+
+                // Types and modules have no definition to point to, see `resolve_ident`
+                let mut names_type = context.identifiers.get(&SpanKey::from(first)).is_some_and(|target| target.definition.is_none());
+
+                for name in names {
+                    let receiver = context.types.get_from_location(location).map(|signature| signature.value.as_ref());
+                    match receiver {
+                        Some(TypeValue::Class(class)) => match (class.is_static_member(context, name.text), names_type) {
+                            (Some(false), true) => return Err(FunctionCallError::instance_member_through_type(name.text, class.name.text, name.into())),
+                            (Some(true), false) => return Err(FunctionCallError::static_member_through_instance(name.text, class.name.text, name.into())),
+                            _ => (),
+                        },
+                        // Variants belong to their enum like static members
+                        Some(TypeValue::Enum(definition)) if !names_type && definition.variants.get(name.text).is_some() => {
+                            return Err(FunctionCallError::static_member_through_instance(name.text, definition.name.text, name.into()));
+                        },
+                        _ => (),
+                    }
+
+                    names_type = names_type && matches!(receiver, Some(TypeValue::Module(_)));
+                    location = match receiver.and_then(|value| value.get_item_location(context, name.text)) {
+                        Some(location) => location,
+                        None if let Some(TypeValue::Tuple(tuple)) = receiver => return Err(StatementError::tuple_index_out_of_range(tuple, name)),
+                        None if let Some(TypeValue::Enum(definition)) = receiver => return Err(StatementError::unknown_variant(definition, name)),
+                        None => return Err(FunctionCallError::CallPathNotValid(CallPathNotValid {
+                            path: name.text.to_string(),
+                            position: name.to_range(),
+                            code: (&name.state.file).into()
+                        }.into()).into()),
+                    };
+                }
+
+                Self::function_value(context, location, expression_position(expression))
+            },
+            ExpressionAst::Not(inner) => {
+                let location = Self::resolve_expression(context, scope_location, inner)?;
+                let bool_location = get_primitive_location(context, PrimitiveType::Bool);
+
+                match location == bool_location {
+                    true => Ok(bool_location),
+                    false => Err(StatementError::expression_type_mismatch(context, bool_location, location, expression_position(inner))),
+                }
+            },
+            ExpressionAst::FunctionCall(function_call) => Self::resolve_function_call(context, scope_location, function_call),
+            ExpressionAst::Operation { left, operator, right } => Self::resolve_operation(context, scope_location, expression, left, operator, right),
+            ExpressionAst::Lambda(lambda) => Self::resolve_lambda(context, scope_location, lambda),
+            ExpressionAst::Match(match_ast) => Self::resolve_match(context, scope_location, match_ast, true, None),
+            ExpressionAst::Tuple(tuple) => Self::resolve_tuple(context, scope_location, tuple),
+        }
+    }
+
+    /// Resolves an expression whose value is stored where `expected` is required
+    ///
+    /// The arms of a match are checked against `expected` instead of against
+    /// each other, the caller still checks the type of the whole expression.
+    pub fn resolve_expected_expression(context: &mut TirContext<'base>, scope_location: ScopeLocation, expression: &ExpressionAst<'base>, expected: TypeLocation) -> Result<TypeLocation, TirError> {
+        match expression {
+            ExpressionAst::Match(match_ast) => Self::resolve_match(context, scope_location, match_ast, true, Some(expected)),
+            _ => Self::resolve_expression(context, scope_location, expression),
+        }
+    }
+
+    fn resolve_ident(context: &mut TirContext<'base>, scope_location: ScopeLocation, ident: &crate::nom_tools::Span<'base>) -> Result<TypeLocation, TirError> {
+        if ident.text == "this" {
+            return match find_class_location(context, scope_location) {
+                Some(location) => Ok(location),
+                None => Err(FunctionResolveError::this_need_to_define_in_class(ident.into())),
+            };
+        }
+
+        let scope = context.get_scope(scope_location).expect("Scope not found, it is a bug");
+        let variable = match scope.get_variable(context, ident) {
+            Some(variable) => Some(variable),
+            None => resolve_global(context, scope_location, ident)?,
+        };
+
+        match variable {
+            Some(variable) => {
+                check_static_access(context, scope_location, &variable, ident.into())?;
+                Self::capture_variable(context, scope_location, &variable, ident, false)?;
+                let definition = (SpanKey::from(&variable.span) != SpanKey::from(ident)).then(|| SpanKey::from(&variable.span));
+                context.identifiers.insert(SpanKey::from(ident), IdentifierTarget { location: variable.location, definition });
+                Ok(variable.location)
+            },
+            None => Err(FunctionResolveError::variable_not_found(ident.into())),
+        }
+    }
+
+    fn resolve_operation(context: &mut TirContext<'base>, scope_location: ScopeLocation, expression: &ExpressionAst<'base>, left: &ExpressionAst<'base>, operator: &ExpressionOperatorType, right: &ExpressionAst<'base>) -> Result<TypeLocation, TirError> {
+        let left_location = Self::resolve_expression(context, scope_location, left)?;
+        let right_location = Self::resolve_expression(context, scope_location, right)?;
+
+        // Literals take the type of the other operand
+        let operand_location = if is_assignable(context, right_location, left_location, left) {
+            right_location
+        } else if is_assignable(context, left_location, right_location, right) {
+            left_location
+        } else {
+            return Err(StatementError::operator_type_mismatch(context, operator, left_location, right_location, expression_position(expression)));
+        };
+
+        let primitive = get_primitive_type(context, operand_location);
+        let is_valid = match operator {
+            ExpressionOperatorType::Add => primitive.as_ref().is_some_and(|primitive| is_numeric(primitive) || *primitive == PrimitiveType::String),
+            ExpressionOperatorType::Sub | ExpressionOperatorType::Mul | ExpressionOperatorType::Div | ExpressionOperatorType::Mod => primitive.as_ref().is_some_and(is_numeric),
+            ExpressionOperatorType::Equal | ExpressionOperatorType::NotEqual => primitive.as_ref().is_some_and(|primitive| *primitive != PrimitiveType::Void),
+            ExpressionOperatorType::GreaterEqualThan | ExpressionOperatorType::GreaterThan | ExpressionOperatorType::LessEqualThan | ExpressionOperatorType::LessThan => primitive.as_ref().is_some_and(is_numeric),
+            ExpressionOperatorType::And | ExpressionOperatorType::Or => primitive == Some(PrimitiveType::Bool),
+            ExpressionOperatorType::Xor | ExpressionOperatorType::LogicalOr | ExpressionOperatorType::LogicalAnd => primitive.as_ref().is_some_and(|primitive| is_integer(primitive) || *primitive == PrimitiveType::Bool),
+            ExpressionOperatorType::BitwiseShiftLeft | ExpressionOperatorType::BitwiseShiftRight => primitive.as_ref().is_some_and(is_integer),
+        };
+
+        if !is_valid {
+            return Err(StatementError::operator_type_mismatch(context, operator, left_location, right_location, expression_position(expression)));
+        }
+
+        match operator {
+            ExpressionOperatorType::Equal | ExpressionOperatorType::NotEqual | ExpressionOperatorType::GreaterEqualThan | ExpressionOperatorType::GreaterThan |
+            ExpressionOperatorType::LessEqualThan | ExpressionOperatorType::LessThan | ExpressionOperatorType::And | ExpressionOperatorType::Or => Ok(get_primitive_location(context, PrimitiveType::Bool)),
+            _ => Ok(operand_location),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::build;
+
+    #[test]
+    fn arithmetic_with_literals() {
+        build("func test(a: i32): i32 { return a * 2 + 1; }").unwrap();
+        build("func test(a: double): double { return a / 2; }").unwrap();
+        build("func test(a: u8): u8 { return a + 200; }").unwrap();
+    }
+
+    #[test]
+    fn string_concatenation() {
+        build(r#"func test(a: string): string { return a + "!"; }"#).unwrap();
+    }
+
+    #[test]
+    fn comparison_is_bool() {
+        build("func test(a: i32, b: i32): bool { return a < b; }").unwrap();
+        build("func test(a: bool, b: bool): bool { return a && !b; }").unwrap();
+    }
+
+    #[test]
+    fn operand_mismatch() {
+        build("func test(a: i32, b: i64): i32 { return a + b; }").unwrap_err();
+        build("func test(a: bool): bool { return a + a; }").unwrap_err();
+        build(r#"func test(a: string): bool { return a < "b"; }"#).unwrap_err();
+    }
+
+    #[test]
+    fn literal_out_of_range() {
+        build("func test(): u8 { return 300; }").unwrap_err();
+        build("func test(): i32 { return 1.5; }").unwrap_err();
+    }
+
+    #[test]
+    fn not_requires_bool() {
+        build("func test(a: i32): bool { return !a; }").unwrap_err();
+    }
+
+    #[test]
+    fn reference() {
+        build("class Point { x: i32; func get(this): i32 { return ref this.x; } }").unwrap();
+        build("class Point { x: i32; func get(this): i32 { return ref this.y; } }").unwrap_err();
+    }
+
+    #[test]
+    fn call_through_field() {
+        build("class A { b: B; func get(this): i32 { return this.b.value() + 1; } } class B { func value(this): i32 { return 1; } }").unwrap();
+    }
+}
@@ -19,6 +19,9 @@
 //! - **This methods**: `this.methodName(args)` within class context
 //! - **Chained calls**: `object.field.method(args)`
 //!
+//! ## Enum Variants
+//! - **Variant creation**: `Shape.Circle(1.0)`, typed by the enum of the variant
+//!
 //! ## Interface Methods
 //! - **Extension methods**: Methods added via `extend` declarations
 //! - **Interface requirements**: Methods defined in interface contracts
@@ -55,7 +58,9 @@ use libtimu_macros::TimuError;
 use libtimu_macros_core::SourceCode;
 use strum_macros::{EnumDiscriminants, EnumProperty};
 
-use crate::{ast::{BodyStatementAst, ExpressionAst, FunctionCallAst, FunctionCallType}, nom_tools::ToRange, tir::{object_signature::GetItem, resolver::{function::{find_class_location, FunctionResolveError}, statement::try_resolve_primitive, ResolverError, TypeLocation}, scope::{ScopeLocation, TypeVariableInformation, VariableInformation}, TirContext, TirError, TypeValue}};
+use crate::{ast::{BodyStatementAst, ExpressionAst, FunctionArgumentAst, FunctionCallAst, FunctionCallType, FunctionDefinitionLocationAst}, nom_tools::{SpanInfo, ToRange}, tir::{context::{IdentifierTarget, SpanKey}, object_signature::GetItem, resolver::{function::{find_class_scope, FunctionArgument, FunctionResolveError}, statement::StatementError, ResolverError, TypeLocation}, scope::ScopeLocation, TirContext, TirError, TypeValue}};
+
+use super::expression::{expression_position, is_assignable, type_name};
 
 #[derive(thiserror::Error, TimuError, Debug, Clone, PartialEq)]
 #[error("{ty}")]
@@ -73,7 +78,7 @@ pub struct TypeWithSpan {
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("Function `{function_name}` expects {expected_size} argument{expected_plural}, but {got_size} {got_plural} provided")]
 #[diagnostic(
-    code("timu::error::function_call_argument_count_mismatch"),
+    code("T0022"),
     help("Check the function definition and provide the correct number of arguments")
 )]
 pub struct FunctionCallArgumentCountMismatch {
@@ -92,7 +97,7 @@ pub struct FunctionCallArgumentCountMismatch {
 
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("`{path}` not valid call path. It is not class or pointer")]
-#[diagnostic(code("timu::error::call_path_not_valid"))]
+#[diagnostic(code("T0023"))]
 pub struct CallPathNotValid {
     pub path: String,
 
@@ -103,8 +108,22 @@ pub struct CallPathNotValid {
     pub code: SourceCode,
 }
 
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{name}` cannot be called")]
+#[diagnostic(code("T0054"), help("only functions, function values and enum variants can be called"))]
+pub struct NotCallable {
+    pub name: String,
+
+    #[label("this is not a function")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("expected `{expected}` type, got `{got}`")]
+#[diagnostic(code("T0024"))]
 pub struct ArgumentTypeMismatch {
     #[reference]
     pub expected: TypeWithSpan,
@@ -115,6 +134,7 @@ pub struct ArgumentTypeMismatch {
 
 #[derive(Clone, Debug, TimuError, thiserror::Error)]
 #[error("Unsupported argument type in function call")]
+#[diagnostic(code("T0021"))]
 pub struct UnsupportedArgumentType {
     #[label("Unsupported argument type")]
     pub position: Range<usize>,
@@ -123,6 +143,40 @@ pub struct UnsupportedArgumentType {
     pub code: SourceCode,
 }
 
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{member}` is an instance member of `{class}`, it needs an object")]
+#[diagnostic(code("T0040"))]
+pub struct InstanceMemberThroughType {
+    pub member: String,
+    pub class: String,
+
+    #[label("used through the type `{class}`")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+
+    #[help]
+    pub advice: String,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{member}` is a static member of `{class}`, it is used through the type")]
+#[diagnostic(code("T0041"))]
+pub struct StaticMemberThroughInstance {
+    pub member: String,
+    pub class: String,
+
+    #[label("used through an object")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+
+    #[help]
+    pub advice: String,
+}
+
 #[derive(Clone, Debug, TimuError, thiserror::Error, EnumDiscriminants, EnumProperty)]
 pub enum FunctionCallError {
     #[error(transparent)]
@@ -140,6 +194,40 @@ pub enum FunctionCallError {
     #[error(transparent)]
     #[diagnostic(transparent)]
     ArgumentTypeMismatch(Box<ArgumentTypeMismatch>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    InstanceMemberThroughType(Box<InstanceMemberThroughType>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    StaticMemberThroughInstance(Box<StaticMemberThroughInstance>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    NotCallable(Box<NotCallable>),
+}
+
+impl FunctionCallError {
+    pub fn instance_member_through_type(member: &str, class: &str, span: SpanInfo) -> TirError {
+        FunctionCallError::InstanceMemberThroughType(InstanceMemberThroughType {
+            member: member.to_string(),
+            class: class.to_string(),
+            position: span.position.clone(),
+            code: span.file.clone().into(),
+            advice: format!("use it through an object of `{class}`"),
+        }.into()).into()
+    }
+
+    pub fn static_member_through_instance(member: &str, class: &str, span: SpanInfo) -> TirError {
+        FunctionCallError::StaticMemberThroughInstance(StaticMemberThroughInstance {
+            member: member.to_string(),
+            class: class.to_string(),
+            position: span.position.clone(),
+            code: span.file.clone().into(),
+            advice: format!("use `{class}.{member}` instead"),
+        }.into()).into()
+    }
 }
 
 impl From<FunctionCallArgumentCountMismatch> for FunctionCallError {
@@ -155,76 +243,6 @@ impl From<FunctionCallError> for TirError {
 }
 
 impl<'base> BodyStatementAst<'base> {
-    /// Analyzes and resolves type information for function call arguments
-    /// 
-    /// This method processes individual arguments in function calls, determining their types
-    /// and validating that they can be used as function arguments. It handles various
-    /// expression types including function calls, primitives, identifiers, and `this` references.
-    /// 
-    /// # Arguments
-    /// * `context` - Mutable TIR context for type resolution
-    /// * `scope_location` - Current scope location for variable resolution
-    /// * `function_scope_location` - Function-specific scope for parameter resolution
-    /// * `function_call` - The containing function call for error reporting
-    /// * `argument` - The expression argument to analyze
-    /// 
-    /// # Returns
-    /// * `Ok(TypeVariableInformation)` - Successfully resolved argument type information
-    /// * `Err(TirError)` - Type resolution error or unsupported argument type
-    /// 
-    /// # Supported Argument Types
-    /// - **Function calls**: Nested function call expressions
-    /// - **Primitives**: String, number, and boolean literals
-    /// - **Identifiers**: Variable references and `this` keyword
-    /// 
-    /// # Special Handling
-    /// - **`this` keyword**: Resolves to the current class instance within method contexts
-    /// - **Variable lookup**: Searches the function scope for variable definitions
-    /// - **Type validation**: Ensures all arguments have valid, resolvable types
-    /// 
-    /// # Errors
-    /// Returns errors for:
-    /// - `this` used outside of class method context
-    /// - Undefined variables or identifiers
-    /// - Unsupported expression types as arguments
-    /// - Type resolution failures for nested expressions
-    pub fn get_type_information_from_expression(context: &mut TirContext<'base>, scope_location: ScopeLocation, function_scope_location: ScopeLocation, function_call: &FunctionCallAst<'base>, argument: &ExpressionAst<'base>) -> Result<TypeVariableInformation<'base>, TirError> {        
-        let value = match argument {
-            ExpressionAst::FunctionCall(func_call) => VariableInformation::basic(func_call.call_span.clone(), Self::resolve_function_call(context, scope_location, func_call)?),
-            ExpressionAst::Primitive { span, value } => VariableInformation::basic(span.clone(), try_resolve_primitive(context, value, span)?),
-            ExpressionAst::Ident(ident) => {
-                match ident.text == "this" {
-                    true => {
-                        // Find the class
-                        let class_search = find_class_location(context, scope_location)
-                            .and_then(|location| context.types.get_from_location(location).map(|item| (location, item)))
-                            .map(|(location, signature)| (location, &signature.value));
-
-                        match class_search {
-                            Some((location, TypeValue::Class(_))) => VariableInformation::basic(ident.clone(), location),
-                            _ => return Err(FunctionResolveError::this_need_to_define_in_class(ident.into()))
-                        }
-                    }
-                    false => {
-                        let scope = context.get_scope(function_scope_location).unwrap();
-                        match scope.get_variable(context, ident) {
-                            Some(variable) => variable,
-                            None => return Err(FunctionResolveError::variable_not_found(ident.into()))
-                        }
-                    }
-                }
-            },
-            _ => {
-                return Err(FunctionCallError::UnsupportedArgumentType(UnsupportedArgumentType {
-                    position: function_call.call_span.to_range(),
-                    code: (&function_call.call_span.state.file).into()
-                }.into()).into());
-            }
-        };
-
-        Ok(value)
-    }
-
     /// Resolves and validates a complete function call expression
     /// 
     /// This is the main entry point for function call resolution in the TIR system.
@@ -267,7 +285,6 @@ impl<'base> BodyStatementAst<'base> {
     /// - **Type mismatch**: Argument types don't match parameter types
     /// - **Invalid call path**: Calling methods on non-objects or undefined functions
     /// - **Scope errors**: Variables or functions not found in current scope
-    /// - **Unsupported expressions**: Complex argument expressions not yet supported
     /// 
     /// # Integration
     /// This method integrates with:
@@ -280,28 +297,63 @@ impl<'base> BodyStatementAst<'base> {
         let (scope, paths, mut callee_object_location) = match &function_call.path {
             FunctionCallType::Direct(paths) => (context.get_scope(scope_location).unwrap(), paths, TypeLocation::UNDEFINED),
             FunctionCallType::This(paths) => {
-                let scope =  context.get_scope(scope_location).unwrap();
-                let parent_scope = context.get_scope(scope.parent_scope.unwrap()).expect("Parent scope not found, but this is a bug");
-                (parent_scope, paths, parent_scope.current_type)
+                let class_scope_location = match find_class_scope(context, scope_location) {
+                    Some(class_scope_location) => class_scope_location,
+                    None => return Err(FunctionResolveError::this_need_to_define_in_class((&function_call.call_span).into())),
+                };
+                let class_scope = context.get_scope(class_scope_location).expect("Class scope not found, but this is a bug");
+                (class_scope, paths, class_scope.current_type)
             }
         };
 
+        let mut receiver = None;
+        let mut captured = None;
+
+        // Whether the path so far names a type or a module instead of a value, and what the callee is used through
+        let mut names_type = false;
+        let mut through_type = None;
+
         for (index, span) in paths.iter().enumerate() {
             let path = span.text;
 
             if index == 0 {
-                if let Some(argument) = scope.get_variable(context, span) {
+                // Default functions of an interface call the members of the interface, they are not scope variables
+                let interface_member = match context.types.get_from_location(callee_object_location).map(|signature| signature.value.as_ref()) {
+                    Some(TypeValue::Interface(interface)) => interface.get_item_location(context, path),
+                    _ => None,
+                };
+
+                if let Some(location) = interface_member {
+                    callee_object_location = location;
+                } else if let Some(argument) = scope.get_variable(context, span) {
                     callee_object_location = argument.location;
+
+                    // Variables of enclosing functions called in a lambda are captured by it
+                    if function_call.path.is_direct() {
+                        captured = Some(argument.clone());
+                    }
+
+                    // Types and modules are found by the name at the call, variables point to their definition
+                    names_type = function_call.path.is_direct() && argument.span.position == span.position;
+                    let definition = (argument.span.position != span.position).then(|| SpanKey::from(&argument.span));
+                    receiver = Some((SpanKey::from(span), IdentifierTarget { location: argument.location, definition }));
+                } else if let Some(location) = matches!(function_call.path, FunctionCallType::This(_))
+                    .then(|| context.types.get_from_location(callee_object_location).and_then(|signature| signature.value.get_item_location(context, path)))
+                    .flatten() {
+                    // Inherited methods are not variables of the class scope
+                    callee_object_location = location;
                 } else {
-                    panic!("Function argument or object not found: '{path}'");
+                    return Err(FunctionResolveError::variable_not_found(span.into()));
                 }
             } else {
-                callee_object_location = match context
-                        .types
-                        .get_from_location(callee_object_location)
-                        .map(|signature| signature.value.as_ref())
-                        .and_then(|value| value.get_item_location(context, path)) {
+                let receiver_value = context.types.get_from_location(callee_object_location).map(|signature| signature.value.as_ref());
+                through_type = Some(names_type);
+                names_type = names_type && matches!(receiver_value, Some(TypeValue::Module(_)));
+
+                callee_object_location = match receiver_value.and_then(|value| value.get_item_location(context, path)) {
                         Some(type_location) => type_location,
+                    None if let Some(TypeValue::Tuple(tuple)) = receiver_value => return Err(StatementError::tuple_index_out_of_range(tuple, span)),
+                    None if let Some(TypeValue::Enum(definition)) = receiver_value => return Err(StatementError::unknown_variant(definition, span)),
                     _ => return Err(FunctionCallError::CallPathNotValid(CallPathNotValid {
                         path: path.to_string(),
                         position: span.to_range(),
@@ -311,39 +363,93 @@ impl<'base> BodyStatementAst<'base> {
             }
         }
 
-        let function_scope_location = scope.location;
+        // The first part of the path is an identifier like the ones in expressions
+        if let Some((key, target)) = receiver {
+            context.identifiers.insert(key, target);
+        }
+
+        if let (Some(variable), Some(name)) = (captured, paths.first()) {
+            Self::capture_variable(context, scope_location, &variable, name, false)?;
+        }
+
         let mut arguments = Vec::new();
         for argument in function_call.arguments.iter() {
-            let type_information = Self::get_type_information_from_expression(context, scope_location, function_scope_location, function_call, argument)?;
-            arguments.push(type_information);
+            let type_location = Self::resolve_expression(context, scope_location, argument)?;
+            arguments.push((type_location, argument));
         }
 
+        context.call_targets.insert(SpanKey::from(&function_call.call_span), callee_object_location);
+
         let callee_object = context.types.get_from_location(callee_object_location).expect("Compiler bug");
 
-        let callee = match callee_object.value.as_ref() {
-            TypeValue::Function(function) => function,
-            _ => panic!("Expected a function signature, but got {:?}", callee_object.value)
+        /* `this` calls are made through the object, a single name has no receiver */
+        let through_type = match function_call.path.is_this() {
+            true => Some(false),
+            false => through_type,
+        };
+
+        if let (TypeValue::Function(function), Some(through_type)) = (callee_object.value.as_ref(), through_type)
+            && let FunctionDefinitionLocationAst::Class(class) = function.ast.location.as_ref() {
+            match (function.is_static(), through_type) {
+                (false, true) => return Err(FunctionCallError::instance_member_through_type(function.name.text, class.text, (&function_call.call_span).into())),
+                (true, false) => return Err(FunctionCallError::static_member_through_instance(function.name.text, class.text, (&function_call.call_span).into())),
+                _ => (),
+            }
+        }
+
+        // Variants belong to their enum, a value of the enum does not create them
+        if let (TypeValue::EnumVariant(variant), Some(false)) = (callee_object.value.as_ref(), through_type) {
+            return Err(FunctionCallError::static_member_through_instance(variant.name.text, variant.enum_name.text, (&function_call.call_span).into()));
+        }
+
+        let (function_name, all_arguments, has_this, arguments_span, return_type) = match callee_object.value.as_ref() {
+            TypeValue::Function(function) => (function.ast.name.text, Self::parameters(&function.arguments), matches!(function.ast.arguments.first(), Some(FunctionArgumentAst::This(_))), SpanInfo::from(&function.ast.arguments_span), function.return_type),
+            // Interface functions without a default are called through `this` in default functions
+            TypeValue::InterfaceFunction(function) => (function.name.text, Self::parameters(&function.arguments), function.arguments.first().is_some_and(|argument| argument.name.text == "this"), SpanInfo::from(&function.arguments_span), function.return_type),
+            // Function values have no argument names, their types are shown at the called name
+            TypeValue::FunctionType(function_type) => {
+                let name = paths.last().expect("Call path without name, but this is a bug");
+                let parameters = function_type.arguments.iter().map(|argument| (argument.field_type, type_name(context, argument.field_type), SpanInfo::from(name))).collect();
+                (name.text, parameters, false, SpanInfo::from(name), function_type.return_type)
+            },
+            // Creating a variant gives a value of its enum
+            TypeValue::EnumVariant(variant) => {
+                let parameters = variant.payload.iter().map(|payload| (payload.field_type, payload.field_type_span.text.to_string(), SpanInfo::from(&payload.field_type_span))).collect();
+                (variant.name.text, parameters, false, SpanInfo::from(&variant.name), variant.enum_type)
+            },
+            _ => {
+                let name = paths.last().expect("Call path without name, but this is a bug");
+                return Err(FunctionCallError::NotCallable(NotCallable {
+                    name: name.text.to_string(),
+                    position: name.to_range(),
+                    code: (&name.state.file).into(),
+                }.into()).into());
+            },
+        };
+
+        /* `this` is passed implicitly by the caller, so it is not part of the argument list */
+        let callee_arguments = match has_this {
+            true => &all_arguments[1..],
+            false => &all_arguments[..],
         };
-        
-        let return_type = callee.return_type;
 
         /* Validate parameters */
-        if callee.arguments.len() != arguments.len() {
-            let function_name = callee.ast.name.text.to_string();
-            let expected_plural = if callee.arguments.len() == 1 { "" } else { "s" };
+        if callee_arguments.len() != arguments.len() {
+            let function_name = function_name.to_string();
+            let expected_plural = if callee_arguments.len() == 1 { "" } else { "s" };
             let got_plural = if arguments.len() == 1 { "was" } else { "were" };
             let function_call_path = function_call.path.call();
             
             return Err(FunctionCallError::FunctionCallArgumentCountMismatch(FunctionCallArgumentCountMismatch {
                 function_name,
-                expected_size: callee.arguments.len(),
+                expected_size: callee_arguments.len(),
                 got_size: arguments.len(),
                 expected_plural: expected_plural.to_string(),
                 got_plural: got_plural.to_string(),
                 expected: TypeWithSpan {
-                        ty: format!("this function expects {} argument{}", callee.arguments.len(), expected_plural),
-                        at: callee.ast.arguments_span.to_range(),
-                        source_code: (&callee.ast.arguments_span.state.file).into()
+                        ty: format!("this function expects {} argument{}", callee_arguments.len(), expected_plural),
+                        at: arguments_span.position.clone(),
+                        source_code: arguments_span.file.clone().into()
                     },
                 got: TypeWithSpan {
                     ty: if arguments.is_empty() {
@@ -358,21 +464,24 @@ impl<'base> BodyStatementAst<'base> {
             }.into()).into());
         }
 
-        for (callee_arg, call_information_type) in callee.arguments.iter().zip(arguments.iter()) {
-            let callee_argument_signature = context.types.get_from_location(callee_arg.field_type).unwrap();
-            let call_argument_signature = context.types.get_from_location(call_information_type.location).unwrap();
+        for ((field_type, field_type_name, field_type_span), (argument_location, argument)) in callee_arguments.iter().zip(arguments.iter()) {
+            if !is_assignable(context, *field_type, *argument_location, argument) {
+                // Tuple literals point at the element that does not fit
+                if let ExpressionAst::Tuple(_) = argument {
+                    return Err(StatementError::value_type_mismatch(context, *field_type, *argument_location, argument));
+                }
 
-            if !callee_argument_signature.value.is_same_type(context, &call_argument_signature.value) {
+                let argument_position = expression_position(argument);
                 return Err(FunctionCallError::ArgumentTypeMismatch(ArgumentTypeMismatch {
                     expected: TypeWithSpan {
-                        ty: callee_arg.field_type_span.text.to_string(),
-                        at: callee_arg.field_type_span.to_range(),
-                        source_code: (&callee_arg.field_type_span.state.file).into()
+                        ty: field_type_name.clone(),
+                        at: field_type_span.position.clone(),
+                        source_code: field_type_span.file.clone().into()
                     },
                     got: TypeWithSpan {
-                        ty: call_argument_signature.value.get_name().to_string(),
-                        at: call_information_type.span.position.clone(),
-                        source_code: (&call_information_type.span.state.file).into()
+                        ty: type_name(context, *argument_location),
+                        at: argument_position.position,
+                        source_code: argument_position.file.into()
                     }
                 }.into()).into());
             }
@@ -380,11 +489,18 @@ impl<'base> BodyStatementAst<'base> {
 
         Ok(return_type)
     }
+
+    /// Expected type, its name as written and its position for each argument of a definition
+    fn parameters(arguments: &[FunctionArgument<'_>]) -> Vec<(TypeLocation, String, SpanInfo)> {
+        arguments.iter().map(|argument| (argument.field_type, argument.field_type_span.text.to_string(), SpanInfo::from(&argument.field_type_span))).collect()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{file::SourceFile, nom_tools::State, process_ast, process_code, tir::TirError};
+    use rstest::rstest;
+
+    use crate::{file::SourceFile, nom_tools::State, process_ast, process_code, tests::build, tir::TirError};
 
     #[test]
     fn func_call_1() -> Result<(), TirError> {
@@ -414,7 +530,7 @@ class TestClass {
         abc();
     }
 
-    func abc(): string {
+    func abc(this): string {
     }
 }
 
@@ -435,7 +551,7 @@ class TestClass {
         this.abc("hello");
     }
 
-    func abc(a: string): string {
+    func abc(this, a: string): string {
     }
 }
 "#.to_string()));
@@ -448,7 +564,7 @@ class TestClass {
             func init(this): string {
                 this.abc("hello", "world");
             }
-            func abc(a: string, b: string): string {
+            func abc(this, a: string, b: string): string {
             }
         }
         "#.to_string()));
@@ -466,7 +582,7 @@ class TestClass {
         this.abc();
     }
 
-    func abc(a: string): string {
+    func abc(this, a: string): string {
     }
 }
 "#.to_string()));
@@ -483,7 +599,7 @@ class TestClass {
         this.abc("hello");
     }
 
-    func abc(): string {
+    func abc(this): string {
     }
 }
 "#.to_string()));
@@ -492,7 +608,6 @@ class TestClass {
     }
 
     #[test]
-    #[should_panic]
     fn func_call_6() {
         let state = State::new(SourceFile::new(vec!["source".into()], r#"
 
@@ -501,12 +616,13 @@ class TestClass {
         this.nope();
     }
 
-    func abc(): string {
+    func abc(this): string {
     }
 }
 "#.to_string()));
         let ast = process_code(&state).unwrap();
-        crate::tir::build(vec![ast.into()]).unwrap_err();
+        let error = crate::tir::build(vec![ast.into()]).unwrap_err();
+        assert_eq!(error.to_string(), "Variable not found");
     }
 
     #[test]
@@ -550,7 +666,7 @@ class TestClass {
         this.abc("");
     }
 
-    func abc(a: i32): string {
+    func abc(this, a: i32): string {
     }
 }
 "#.to_string()));
@@ -568,7 +684,7 @@ class TestClass {
         abc();
     }
 
-    func abc(): string {
+    func abc(this): string {
     }
 }
 
@@ -835,7 +951,7 @@ class TestClass {
         this.validate("test");
     }
     
-    func validate(input: string): bool {
+    func validate(this, input: string): bool {
     }
 }
 "#.to_string()));
@@ -852,7 +968,7 @@ class TestClass {
         this.validate();
     }
     
-    func validate(input: string): bool {
+    func validate(this, input: string): bool {
     }
 }
 "#.to_string()));
@@ -1032,4 +1148,60 @@ class DataProcessor {
         let result = process_ast(vec![ast1.into(), ast2.into()]);
         assert!(result.is_ok());
     }
+
+    #[rstest]
+    #[case("class Counter { func create(): i32 { return 1; } } func test(): i32 { return Counter.create(); }")]
+    #[case("class Counter { static count: i32; } func test(): i32 { return ref Counter.count; }")]
+    #[case("class Counter { static count: i32; func next(): i32 { return ref Counter.count + 1; } func get(this): i32 { return Counter.next(); } }")]
+    #[case("class Base { static count: i32; func create(): i32 { return 1; } } class Child: Base {} func test(): i32 { return Child.create() + ref Child.count; }")]
+    #[case("class Counter { value: i32; func get(this): i32 { return 1; } } func test(counter: Counter): i32 { return counter.get() + ref counter.value; }")]
+    #[case("class Counter { static count: i32; func reset(): void { count = 0; Counter.count = count; } } func test(): void { Counter.count = 1; }")]
+    #[case("class Counter { value: i32; func set(this): void { value = 1; this.value = 2; } } func test(counter: Counter): void { counter.value = 3; }")]
+    fn static_members(#[case] code: &str) -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], code.to_string()));
+        let ast = process_code(&state)?;
+        crate::tir::build(vec![ast.into()])?;
+        Ok(())
+    }
+
+    #[test]
+    fn static_function_from_another_module() -> Result<(), TirError> {
+        let state1 = State::new(SourceFile::new(vec!["lib".into()], "pub class Counter { pub func create(): i32 { return 1; } }".to_string()));
+        let state2 = State::new(SourceFile::new(vec!["main".into()], "use lib; func test(): i32 { return lib.Counter.create(); }".to_string()));
+
+        let ast1 = process_code(&state1)?;
+        let ast2 = process_code(&state2)?;
+        process_ast(vec![ast1.into(), ast2.into()])?;
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("class Counter { func get(this): i32 { return 1; } } func test(): i32 { return Counter.get(); }", "`get` is an instance member of `Counter`, it needs an object")]
+    #[case("class Counter { value: i32; } func test(): i32 { return ref Counter.value; }", "`value` is an instance member of `Counter`, it needs an object")]
+    #[case("class Counter { func create(): i32 { return 1; } func get(this): i32 { return this.create(); } }", "`create` is a static member of `Counter`, it is used through the type")]
+    #[case("class Counter { func create(): i32 { return 1; } } func test(counter: Counter): i32 { return counter.create(); }", "`create` is a static member of `Counter`, it is used through the type")]
+    #[case("class Counter { static count: i32; } func test(counter: Counter): i32 { return ref counter.count; }", "`count` is a static member of `Counter`, it is used through the type")]
+    #[case("class Counter { value: i32; func create(): i32 { return ref this.value; } }", "This argument need to be defined in class function")]
+    #[case("class Counter { value: i32; func get(): i32 { return value; } }", "`value` is an instance field of `Counter`, a static function has no object")]
+    #[case("class Counter { value: i32; func reset(): void { value = 5; } }", "`value` is an instance field of `Counter`, a static function has no object")]
+    #[case("class Counter { value: i32; } func test(): void { Counter.value = 5; }", "`value` is an instance member of `Counter`, it needs an object")]
+    #[case("class Counter { static count: i32; } func test(counter: Counter): void { counter.count = 5; }", "`count` is a static member of `Counter`, it is used through the type")]
+    #[case("class Counter { static count: i32; } func test(): void { Counter.count = \"five\"; }", "expected `i32` type, got `string`")]
+    fn invalid_static_usage(#[case] code: &str, #[case] expected: &str) -> Result<(), TirError> {
+        let state = State::new(SourceFile::new(vec!["source".into()], code.to_string()));
+        let ast = process_code(&state)?;
+
+        let error = crate::tir::build(vec![ast.into()]).unwrap_err();
+        assert_eq!(error.to_string(), expected);
+        Ok(())
+    }
+
+    #[rstest]
+    #[case("pub func f(): i32 { return undefined_fn(); }", "Variable not found")]
+    #[case("class Point {} func test(): void { Point(); }", "`Point` cannot be called")]
+    #[case("interface Shape {} func test(): void { Shape(); }", "`Shape` cannot be called")]
+    #[case("func test(a: i32): void { a(); }", "`a` cannot be called")]
+    fn invalid_callee(#[case] code: &str, #[case] expected: &str) {
+        assert_eq!(build(code).unwrap_err().to_string(), expected);
+    }
 }
@@ -0,0 +1,80 @@
+//! Conditional statement resolution for the TIR system.
+//!
+//! Every condition of an `if`/`else if` chain has to be a `bool` expression.
+//! Each branch body gets its own child scope, so variables defined inside a
+//! branch are not visible after the conditional statement.
+
+use crate::{
+    ast::{BodyAst, BodyStatementAst, ExpressionAst, IfConditionAst},
+    tir::{object_signature::PrimitiveType, resolver::{statement::StatementError, ResolveAst, TypeLocation}, scope::ScopeLocation, TirContext, TirError},
+};
+
+use super::expression::{expression_position, get_primitive_location};
+
+impl<'base> BodyStatementAst<'base> {
+    /// Resolves all conditions and branch bodies of an if statement
+    pub fn resolve_if_condition(context: &mut TirContext<'base>, scope_location: ScopeLocation, if_condition: &IfConditionAst<'base>) -> Result<TypeLocation, TirError> {
+        Self::resolve_condition(context, scope_location, &if_condition.expression)?;
+        Self::resolve_block(context, scope_location, &if_condition.true_body)?;
+
+        for (expression, body) in if_condition.else_ifs.iter() {
+            Self::resolve_condition(context, scope_location, expression)?;
+            Self::resolve_block(context, scope_location, body)?;
+        }
+
+        if let Some(false_body) = &if_condition.false_body {
+            Self::resolve_block(context, scope_location, false_body)?;
+        }
+
+        Ok(get_primitive_location(context, PrimitiveType::Void))
+    }
+
+    fn resolve_condition(context: &mut TirContext<'base>, scope_location: ScopeLocation, expression: &ExpressionAst<'base>) -> Result<(), TirError> {
+        let location = Self::resolve_expression(context, scope_location, expression)?;
+        let bool_location = get_primitive_location(context, PrimitiveType::Bool);
+
+        match location == bool_location {
+            true => Ok(()),
+            false => Err(StatementError::expression_type_mismatch(context, bool_location, location, expression_position(expression))),
+        }
+    }
+
+    /// Resolves the statements of a nested block in a new child scope
+    pub fn resolve_block(context: &mut TirContext<'base>, scope_location: ScopeLocation, body: &BodyAst<'base>) -> Result<(), TirError> {
+        let block_scope_location = context.create_child_scope(context.create_tmp_type().into(), scope_location, None);
+
+        for statement in body.statements.iter() {
+            statement.resolve(context, block_scope_location)?;
+            statement.finish(context, block_scope_location)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::build;
+
+    #[test]
+    fn if_condition() {
+        build("func test(a: i32): i32 { if (a > 10) { return 1; } else if (a > 5) { return 2; } else { return 3; } }").unwrap();
+    }
+
+    #[test]
+    fn condition_must_be_bool() {
+        build("func test(a: i32): void { if (a) { } }").unwrap_err();
+        build("func test(a: i32): void { if (a > 1) { } else if (a) { } }").unwrap_err();
+    }
+
+    #[test]
+    fn block_scope() {
+        build("func test(a: bool): void { var b = 1; if (a) { var c = b; c = 2; } }").unwrap();
+        build("func test(a: bool): void { if (a) { var c = 1; } c = 2; }").unwrap_err();
+    }
+
+    #[test]
+    fn method_call_in_block() {
+        build("class A { func a(this): void { if (true) { this.b(); } } func b(this): void { } }").unwrap();
+    }
+}
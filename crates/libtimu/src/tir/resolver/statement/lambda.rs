@@ -0,0 +1,281 @@
+//! Lambda expression and function value resolution for the TIR system.
+//!
+//! A lambda is typed by its signature and its body is resolved in a child
+//! scope of the expression, so it can use the variables around it:
+//!
+//! ```timu
+//! const step = 2;
+//! var total = 0;
+//! var next = func(value: i32): i32 { return value + step; };  // `step` is copied
+//! var add = ref func(value: i32): void { total = total + value; };
+//! ```
+//!
+//! # Captures
+//!
+//! Variables of enclosing functions used in a lambda body are its captures,
+//! found by walking the [`crate::tir::scope::Scope`] chain from the use to the
+//! scope that defines the variable. Constants and arguments that are only read
+//! are captured by value. Mutable locals, and anything the lambda assigns,
+//! are captured by reference, which needs a `ref` lambda. Module variables and
+//! class members are not captures.
+//!
+//! # Function Values
+//!
+//! A named function used as a value has the function type of its signature.
+//! Methods need their object, so they cannot be used as values.
+
+use std::ops::Range;
+
+use indexmap::IndexMap;
+use libtimu_macros::TimuError;
+use libtimu_macros_core::SourceCode;
+
+use crate::{
+    ast::{BodyStatementAst, FunctionArgumentAst, LambdaAst},
+    nom_tools::{Span, SpanInfo},
+    tir::{
+        context::SpanKey,
+        object_signature::TypeValue,
+        resolver::{function::FunctionResolveError, function_type::{function_type_location, FunctionTypeArgument}, get_object_location_or_resolve, statement::StatementError, ResolveAst, TypeLocation},
+        scope::{ScopeLocation, TypeVariableInformation, VariableInformation},
+        TirContext, TirError,
+    },
+};
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{name}` is mutable, it can only be captured by a `ref` lambda")]
+#[diagnostic(code("T0043"), help("declare the lambda with `ref func`, or make the captured variable a constant"))]
+pub struct MutableCapture {
+    pub name: String,
+
+    #[label("captured here")]
+    pub position: Range<usize>,
+
+    #[label("this lambda is not `ref`")]
+    pub lambda: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{name}` is a method, it cannot be used as a value")]
+#[diagnostic(code("T0044"), help("call the method inside a lambda instead"))]
+pub struct MethodAsValue {
+    pub name: String,
+
+    #[label("needs an object to be called")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+/// A variable of an enclosing function used by a lambda, see [`LambdaInformation::captures`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capture {
+    /// Name of the captured variable
+    pub name: String,
+    /// Type of the captured variable
+    pub location: TypeLocation,
+    /// Whether the lambda shares the variable instead of copying its value
+    pub by_reference: bool,
+}
+
+/// A resolved lambda expression, see [`TirContext::lambdas`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct LambdaInformation {
+    /// Function type of the lambda
+    pub location: TypeLocation,
+    /// Scope the lambda body is resolved in, it holds the arguments
+    pub scope: ScopeLocation,
+    /// Whether the lambda is declared with `ref func`
+    pub is_ref: bool,
+    /// Captured variables, keyed by the span of their definition
+    pub captures: IndexMap<SpanKey, Capture>,
+}
+
+impl<'base> BodyStatementAst<'base> {
+    /// Resolves a lambda expression and its body, returning its function type
+    pub fn resolve_lambda(context: &mut TirContext<'base>, scope_location: ScopeLocation, lambda: &LambdaAst<'base>) -> Result<TypeLocation, TirError> {
+        let module_ref = context.get_scope(scope_location).expect("Scope not found, it is a bug").module_ref.clone();
+
+        let mut arguments = Vec::new();
+        for argument in lambda.arguments.iter() {
+            match argument {
+                FunctionArgumentAst::This(this) => return Err(FunctionResolveError::this_need_to_define_in_class(this.into())),
+                FunctionArgumentAst::Argument { name, field_type } => arguments.push((name.clone(), FunctionTypeArgument {
+                    field_type: get_object_location_or_resolve(context, field_type, &module_ref, scope_location)?,
+                    is_reference: field_type.reference,
+                    is_nullable: field_type.nullable,
+                })),
+            }
+        }
+
+        let return_type = get_object_location_or_resolve(context, &lambda.return_type, &module_ref, scope_location)?;
+        let location = function_type_location(context, arguments.iter().map(|(_, argument)| argument.clone()).collect(), return_type);
+
+        // Return statements in the body find the lambda through the type of its scope
+        let lambda_scope_location = context.create_child_scope(context.create_tmp_type().into(), scope_location, Some(location));
+        let lambda_scope = context.get_mut_scope(lambda_scope_location).expect("Scope not found, it is a bug");
+        for (name, argument) in arguments.into_iter() {
+            lambda_scope.add_variable(VariableInformation::new(name, argument.field_type, argument.is_nullable, false, false))?;
+        }
+
+        context.lambdas.insert(SpanKey::from(&lambda.span), LambdaInformation {
+            location,
+            scope: lambda_scope_location,
+            is_ref: lambda.is_ref.is_some(),
+            captures: IndexMap::new(),
+        });
+
+        for statement in lambda.body.statements.iter() {
+            statement.resolve(context, lambda_scope_location)?;
+            statement.finish(context, lambda_scope_location)?;
+        }
+
+        Ok(location)
+    }
+
+    /// Type of an expression used as a value, named functions and enum variants become their function type
+    pub fn function_value(context: &mut TirContext<'base>, location: TypeLocation, span: SpanInfo) -> Result<TypeLocation, TirError> {
+        let (arguments, return_type) = match context.types.get_from_location(location).map(|signature| signature.value.as_ref()) {
+            Some(TypeValue::Function(function)) if matches!(function.ast.arguments.first(), Some(FunctionArgumentAst::This(_))) => {
+                return Err(StatementError::MethodAsValue(MethodAsValue {
+                    name: function.name.text.to_string(),
+                    position: span.position,
+                    code: span.file.into(),
+                }.into()).into());
+            },
+            Some(TypeValue::Function(function)) => (function.arguments.iter().map(FunctionTypeArgument::from).collect(), function.return_type),
+            // Plain variants are values of their enum, the others create one when called
+            Some(TypeValue::EnumVariant(variant)) if variant.payload.is_empty() => return Ok(variant.enum_type),
+            Some(TypeValue::EnumVariant(variant)) => (variant.payload.iter().map(|payload| FunctionTypeArgument {
+                field_type: payload.field_type,
+                is_reference: payload.is_reference,
+                is_nullable: payload.is_nullable,
+            }).collect(), variant.enum_type),
+            _ => return Ok(location),
+        };
+
+        Ok(function_type_location(context, arguments, return_type))
+    }
+
+    /// Records the variable as a capture of every lambda between its use and its definition
+    ///
+    /// `assigned` is set when the lambda writes to the variable, which always
+    /// needs a `ref` lambda.
+    pub fn capture_variable(context: &mut TirContext<'base>, scope_location: ScopeLocation, variable: &TypeVariableInformation<'base>, used: &Span<'base>, assigned: bool) -> Result<(), TirError> {
+        let mut lambdas = Vec::new();
+        let mut current = Some(scope_location);
+
+        let owner = loop {
+            let Some(location) = current else {
+                return Ok(());
+            };
+
+            let scope = context.get_scope(location).expect("Scope not found, it is a bug");
+            if scope.has_variable(&variable.span) {
+                break scope;
+            }
+
+            if let Some(key) = context.lambdas.iter().find(|(_, lambda)| lambda.scope == location).map(|(key, _)| key.clone()) {
+                lambdas.push(key);
+            }
+
+            current = scope.parent_scope;
+        };
+
+        // Module variables and class members are reached without capturing them
+        let definition = SpanKey::from(&variable.span);
+        let is_local = context.variable_types.contains_key(&definition);
+        let is_argument = context.types.get_from_location(owner.current_type).is_some_and(|signature| matches!(signature.value.as_ref(), TypeValue::Function(_) | TypeValue::FunctionType(_)));
+        if lambdas.is_empty() || owner.parent_scope.is_none() || !(is_local || is_argument) {
+            return Ok(());
+        }
+
+        let by_reference = assigned || (is_local && !variable.readonly);
+        for key in lambdas.into_iter() {
+            let lambda = context.lambdas.get_mut(&key).expect("Lambda not found, but this is a bug");
+            if by_reference && !lambda.is_ref {
+                return Err(StatementError::MutableCapture(MutableCapture {
+                    name: used.text.to_string(),
+                    position: used.position.clone(),
+                    lambda: key.position.clone(),
+                    code: (&used.state.file).into(),
+                }.into()).into());
+            }
+
+            lambda.captures.insert(definition.clone(), Capture {
+                name: used.text.to_string(),
+                location: variable.location,
+                by_reference,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::{tests::{build, build_with}, tir::{resolver::{statement::StatementError, ResolverError}, TirError}};
+
+    /// Name and capture kind of the captures of the first lambda
+    fn captures(code: &str) -> Vec<(String, bool)> {
+        build_with(code, |context| {
+            let lambda = context.lambdas.values().next().expect("No lambda resolved");
+            lambda.captures.values().map(|capture| (capture.name.clone(), capture.by_reference)).collect()
+        }).unwrap()
+    }
+
+    #[rstest]
+    #[case("func test(): void { var add = func(a: i32, b: i32): i32 { return a + b; }; var sum: i32 = add(1, 2); }")]
+    #[case("func test(): func(i32): i32 { const step = 2; return func(value: i32): i32 { return value + step; }; }")]
+    #[case("func test(): void { var total = 0; var add = ref func(value: i32): void { total = total + value; }; add(1); }")]
+    #[case("func test(limit: i32): func(i32): bool { return func(value: i32): bool { return value < limit; }; }")]
+    #[case("func apply(callback: func(i32): i32): i32 { return callback(1); } func test(): i32 { return apply(func(value: i32): i32 { return value * 2; }); }")]
+    #[case("var counter = 0; func test(): func(): void { return func(): void { counter = counter + 1; }; }")]
+    #[case("class Counter { value: i32; func add(this): func(): i32 { return func(): i32 { return ref this.value; }; } }")]
+    #[case("func test(): void { var run = func(): void { var inner = 1; inner = 2; }; }")]
+    fn valid_lambdas(#[case] code: &str) {
+        build(code).unwrap();
+    }
+
+    #[test]
+    fn capture_kinds() {
+        assert_eq!(captures("func test(limit: i32): void { const step = 1; var check = func(value: i32): bool { return value + step < limit; }; }"), vec![("step".to_string(), false), ("limit".to_string(), false)]);
+        assert_eq!(captures("func test(): void { var total = 0; var add = ref func(): void { total = 1; }; }"), vec![("total".to_string(), true)]);
+        assert_eq!(captures("func test(): void { var add = func(value: i32): i32 { var local = value; return local; }; }"), vec![]);
+    }
+
+    #[rstest]
+    #[case("func test(): void { var total = 0; var read = func(): i32 { return total; }; }")]
+    #[case("func test(): void { var total = 0; var add = func(): void { total = 1; }; }")]
+    #[case("func test(limit: i32): void { var reset = func(): void { limit = 0; }; }")]
+    #[case("func test(): void { var total = 0; var outer = func(): void { var inner = ref func(): void { total = 1; }; }; }")]
+    #[case("func test(): void { var callback = func(): void {}; var run = func(): void { callback(); }; }")]
+    fn mutable_capture(#[case] code: &str) {
+        let error = build(code).unwrap_err();
+        assert!(matches!(&error, TirError::ResolverError(error) if matches!(error.as_ref(), ResolverError::Statement(error) if matches!(error.as_ref(), StatementError::MutableCapture(_)))), "{code}: {error:?}");
+    }
+
+    #[rstest]
+    #[case("class A { func value(this): i32 { return 1; } func get(this): func(): i32 { return ref this.value; } }")]
+    #[case("class A { func value(this): i32 { return 1; } } func get(a: A): func(): i32 { return ref a.value; }")]
+    fn method_as_value(#[case] code: &str) {
+        let error = build(code).unwrap_err();
+        assert!(matches!(&error, TirError::ResolverError(error) if matches!(error.as_ref(), ResolverError::Statement(error) if matches!(error.as_ref(), StatementError::MethodAsValue(_)))), "{code}: {error:?}");
+    }
+
+    #[rstest]
+    #[case("func test(): void { var run = func(this): void {}; }")]
+    #[case("func test(): void { var run = func(): i32 { return \"text\"; }; }")]
+    #[case("func test(): void { var run: func(): bool = func(): i32 { return 1; }; }")]
+    #[case("func test(): void { var run = func(): void { return missing; }; }")]
+    fn invalid_lambdas(#[case] code: &str) {
+        build(code).unwrap_err();
+    }
+}
@@ -0,0 +1,520 @@
+//! Match expression resolution for the TIR system.
+//!
+//! Every pattern is checked against the type of the matched value and every
+//! arm body is resolved in a child scope, which holds the name bound by a
+//! class type pattern:
+//!
+//! ```timu
+//! func area(shape: ?Shape): f64 {
+//!     return match (shape) {
+//!         circle: Circle => circle.area(),
+//!         null => 0.0,
+//!         _ => 1.0,
+//!     };
+//! }
+//! ```
+//!
+//! # Result Type
+//!
+//! Used as an expression, the arms have to produce values of a common type.
+//! When the match is returned or stored in a typed variable, every arm is
+//! checked against that type. Otherwise it is the type of the first arm that
+//! is not a numeric literal, numeric literals take the type of the other arms
+//! like in binary operations. A block arm has no value. Used as a statement,
+//! the values of the arms are dropped.
+//!
+//! # Exhaustiveness
+//!
+//! The cases a value can be in are `null` for nullable values, `true` and
+//! `false` for `bool`, every variant for enums, and any other value. A match
+//! has to cover all cases, the error lists the missing ones. Other literals
+//! never cover a whole type, a `_` arm or a class type pattern of the matched
+//! type is needed for them.
+//!
+//! # Enum Variants
+//!
+//! A variant pattern names a variant of the matched enum, with or without the
+//! enum name in front of it. It binds the payload values to readonly names,
+//! `_` skips a value and leaving out the parentheses ignores the whole payload:
+//!
+//! ```timu
+//! var area = match (shape) {
+//!     Shape.Rect(width, height) => width * height,
+//!     Circle(_) => 1.0,
+//!     Empty => 0.0,
+//! };
+//! ```
+//!
+//! An arm whose cases are already covered by the arms before it can never be
+//! evaluated, it is recorded in [`MatchInformation::unreachable`] and reported
+//! by the `unreachable_patterns` lint.
+
+use std::{fmt::{Display, Formatter}, ops::Range};
+
+use libtimu_macros::TimuError;
+use libtimu_macros_core::SourceCode;
+
+use crate::{
+    ast::{BodyStatementAst, ExpressionAst, MatchArmAst, MatchArmBodyAst, MatchAst, PatternAst, PrimitiveValue},
+    nom_tools::{Span, SpanInfo},
+    tir::{
+        context::SpanKey,
+        object_signature::{GetItem, PrimitiveType, TypeValue},
+        resolver::{enum_definition::{enum_variant, EnumDefinition}, function::find_class_location, get_object_location_or_resolve, statement::{try_resolve_primitive, StatementError}, try_resolve_signature, TypeLocation},
+        scope::{ScopeLocation, VariableInformation},
+        TirContext, TirError,
+    },
+};
+
+use super::expression::{expression_position, get_primitive_location, is_assignable, is_numeric_literal, literal_type, type_name};
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("match is not exhaustive, {missing} not covered")]
+#[diagnostic(code("T0045"), help("add arms for the missing cases or a `_` arm"))]
+pub struct NonExhaustiveMatch {
+    pub missing: String,
+
+    #[label("{missing} not covered")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{pattern}` cannot match a `{expected}` value")]
+#[diagnostic(code("T0046"), help("use a pattern of the matched type, `null` only matches nullable values"))]
+pub struct PatternTypeMismatch {
+    pub pattern: String,
+    pub expected: String,
+
+    #[label("this pattern does not fit `{expected}`")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{variant}` is not a variant of `{enum_name}`")]
+#[diagnostic(code("T0050"), help("use one of the variants listed in the definition of the enum"))]
+pub struct UnknownVariant {
+    pub variant: String,
+    pub enum_name: String,
+
+    #[label("unknown variant")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("expected {expected} bindings for `{variant}`, got {got}")]
+#[diagnostic(code("T0051"), help("bind one name for every payload value, `_` skips a value"))]
+pub struct VariantBindingMismatch {
+    pub variant: String,
+    pub expected: usize,
+    pub got: usize,
+
+    #[label("`{variant}` carries {expected} values")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+/// A resolved match, see [`TirContext::matches`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchInformation {
+    /// Common type of the arms, `void` when the match is used as a statement
+    pub location: TypeLocation,
+    /// Patterns of the arms that can never be evaluated
+    pub unreachable: Vec<SpanKey>,
+}
+
+/// A group of values a match has to cover
+#[derive(Debug, Clone, PartialEq)]
+enum Case {
+    Null,
+    Bool(bool),
+    /// A variant of an enum, named like `Shape.Circle`
+    Variant(String),
+    Other,
+}
+
+impl Display for Case {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Case::Null => write!(f, "null"),
+            Case::Bool(value) => write!(f, "{value}"),
+            Case::Variant(name) => write!(f, "{name}"),
+            Case::Other => write!(f, "_"),
+        }
+    }
+}
+
+/// Computes the source range covered by a pattern
+pub fn pattern_position(pattern: &PatternAst<'_>) -> SpanInfo {
+    match pattern {
+        PatternAst::Literal { span, .. } | PatternAst::Null(span) | PatternAst::Wildcard(span) => span.into(),
+        PatternAst::Variant { span, .. } => span.into(),
+        PatternAst::Class { binding, class } => SpanInfo::new(binding.position.start..class.names_span.position.end, binding.state.file.clone()),
+    }
+}
+
+impl StatementError {
+    pub fn unknown_variant(definition: &EnumDefinition<'_>, name: &Span<'_>) -> TirError {
+        StatementError::UnknownVariant(UnknownVariant {
+            variant: name.text.to_string(),
+            enum_name: definition.name.text.to_string(),
+            position: name.position.start..name.position.start + name.text.len(),
+            code: (&name.state.file).into(),
+        }.into()).into()
+    }
+
+    pub fn pattern_type_mismatch(context: &TirContext<'_>, pattern: &PatternAst<'_>, expected: TypeLocation) -> TirError {
+        let span = pattern_position(pattern);
+        StatementError::PatternTypeMismatch(PatternTypeMismatch {
+            pattern: match pattern {
+                PatternAst::Class { class, .. } => class.to_string(),
+                _ => pattern.to_string(),
+            },
+            expected: type_name(context, expected),
+            position: span.position,
+            code: span.file.into(),
+        }.into()).into()
+    }
+}
+
+impl<'base> BodyStatementAst<'base> {
+    /// Resolves a match and its arms, returning the common type of the arms
+    ///
+    /// `is_expression` is set when the value of the match is used, otherwise
+    /// the arms can have different types and the match is `void`. `expected`
+    /// is the type the value is stored as, when the surrounding code requires one.
+    pub fn resolve_match(context: &mut TirContext<'base>, scope_location: ScopeLocation, match_ast: &MatchAst<'base>, is_expression: bool, expected: Option<TypeLocation>) -> Result<TypeLocation, TirError> {
+        let location = Self::resolve_expression(context, scope_location, &match_ast.expression)?;
+
+        let mut cases = Vec::new();
+        if Self::is_nullable(context, scope_location, &match_ast.expression) {
+            cases.push(Case::Null);
+        }
+        match context.types.get_from_location(location).map(|signature| signature.value.as_ref()) {
+            Some(TypeValue::PrimitiveType(PrimitiveType::Bool)) => cases.extend([Case::Bool(true), Case::Bool(false)]),
+            Some(TypeValue::Enum(definition)) => cases.extend(definition.variants.keys().map(|variant| Case::Variant(format!("{}.{variant}", definition.name.text)))),
+            _ => cases.push(Case::Other),
+        };
+
+        let mut remaining = cases.clone();
+        let mut literals: Vec<&PrimitiveValue<'base>> = Vec::new();
+        let mut classes = Vec::new();
+        let mut unreachable = Vec::new();
+        let mut arm_types = Vec::new();
+
+        for arm in match_ast.arms.iter() {
+            let arm_scope_location = context.create_child_scope(context.create_tmp_type().into(), scope_location, None);
+
+            // The cases the pattern can match and whether it matches all of their values
+            let (matched, covers, duplicate) = match &arm.pattern {
+                PatternAst::Wildcard(_) => (cases.clone(), true, false),
+                PatternAst::Null(_) => match cases.contains(&Case::Null) {
+                    true => (vec![Case::Null], true, false),
+                    false => return Err(StatementError::pattern_type_mismatch(context, &arm.pattern, location)),
+                },
+                PatternAst::Literal { span, value } => {
+                    let literal = try_resolve_primitive(context, value, span)?;
+                    if !is_assignable(context, location, literal, &ExpressionAst::Primitive { span: span.clone(), value: value.clone() }) {
+                        return Err(StatementError::pattern_type_mismatch(context, &arm.pattern, location));
+                    }
+
+                    match value {
+                        PrimitiveValue::Bool(value) => (vec![Case::Bool(*value)], true, false),
+                        _ => {
+                            let duplicate = literals.contains(&value);
+                            literals.push(value);
+                            (vec![Case::Other], false, duplicate)
+                        },
+                    }
+                },
+                PatternAst::Variant { path, bindings, .. } => {
+                    let variant = Self::resolve_variant_pattern(context, scope_location, arm_scope_location, &arm.pattern, location, path, bindings.as_deref())?;
+                    (vec![variant], true, false)
+                },
+                PatternAst::Class { binding, class } => {
+                    let module_ref = context.get_scope(scope_location).expect("Scope not found, it is a bug").module_ref.clone();
+                    let class_location = get_object_location_or_resolve(context, class, &module_ref, scope_location)?;
+                    if !Self::is_same_pattern_type(context, location, class_location) {
+                        return Err(StatementError::pattern_type_mismatch(context, &arm.pattern, location));
+                    }
+
+                    let scope = context.get_mut_scope(arm_scope_location).expect("Scope not found, it is a bug");
+                    scope.add_variable(VariableInformation::new(binding.clone(), class_location, false, false, true))?;
+                    context.variable_types.insert(SpanKey::from(binding), class_location);
+
+                    let duplicate = classes.iter().any(|earlier| Self::is_same_pattern_type(context, class_location, *earlier));
+                    classes.push(class_location);
+
+                    let covers = Self::is_same_pattern_type(context, class_location, location);
+                    (cases.iter().filter(|case| **case != Case::Null).cloned().collect(), covers, duplicate)
+                },
+            };
+
+            if duplicate || !matched.iter().any(|case| remaining.contains(case)) {
+                let span = pattern_position(&arm.pattern);
+                unreachable.push(SpanKey { path: span.file.path.clone(), position: span.position });
+            }
+            if covers {
+                remaining.retain(|case| !matched.contains(case));
+            }
+
+            arm_types.push(match &arm.body {
+                MatchArmBodyAst::Expression(expression) => Self::resolve_expression(context, arm_scope_location, expression)?,
+                MatchArmBodyAst::Block(body) => {
+                    Self::resolve_block(context, arm_scope_location, body)?;
+                    get_primitive_location(context, PrimitiveType::Void)
+                },
+            });
+        }
+
+        if !remaining.is_empty() {
+            let span = expression_position(&match_ast.expression);
+            return Err(StatementError::NonExhaustiveMatch(NonExhaustiveMatch {
+                missing: remaining.iter().map(|case| format!("`{case}`")).collect::<Vec<_>>().join(", "),
+                position: span.position,
+                code: span.file.into(),
+            }.into()).into());
+        }
+
+        let location = match is_expression {
+            true => Self::match_type(context, match_ast, &arm_types, expected)?,
+            false => get_primitive_location(context, PrimitiveType::Void),
+        };
+
+        context.matches.insert(SpanKey::from(&match_ast.span), MatchInformation { location, unreachable });
+        Ok(location)
+    }
+
+    /// Checks a variant pattern against the matched enum and binds its payload in the arm scope
+    ///
+    /// Returns the case of the variant.
+    #[allow(clippy::too_many_arguments)]
+    fn resolve_variant_pattern(context: &mut TirContext<'base>, scope_location: ScopeLocation, arm_scope_location: ScopeLocation, pattern: &PatternAst<'base>, location: TypeLocation, path: &[Span<'base>], bindings: Option<&[Span<'base>]>) -> Result<Case, TirError> {
+        let (name, prefix) = path.split_last().expect("Variant pattern without name, but this is a bug");
+
+        // The enum name in front of the variant has to name the matched enum
+        if !prefix.is_empty() {
+            let module_ref = context.get_scope(scope_location).expect("Scope not found, it is a bug").module_ref.clone();
+            let enum_name = prefix.iter().map(|name| name.text).collect::<Vec<_>>().join(".");
+            if try_resolve_signature(context, &module_ref, scope_location, enum_name)? != Some(location) {
+                return Err(StatementError::pattern_type_mismatch(context, pattern, location));
+            }
+        }
+
+        let Some(TypeValue::Enum(definition)) = context.types.get_from_location(location).map(|signature| signature.value.as_ref()) else {
+            return Err(StatementError::pattern_type_mismatch(context, pattern, location));
+        };
+
+        let Some(variant) = definition.get_item_location(context, name.text).and_then(|variant| enum_variant(context, variant)).cloned() else {
+            return Err(StatementError::unknown_variant(definition, name));
+        };
+
+        let case = Case::Variant(format!("{}.{}", variant.enum_name.text, variant.name.text));
+        let Some(bindings) = bindings else {
+            return Ok(case);
+        };
+
+        if bindings.len() != variant.payload.len() {
+            let span = pattern_position(pattern);
+            return Err(StatementError::VariantBindingMismatch(VariantBindingMismatch {
+                variant: case.to_string(),
+                expected: variant.payload.len(),
+                got: bindings.len(),
+                position: span.position,
+                code: span.file.into(),
+            }.into()).into());
+        }
+
+        for (binding, payload) in bindings.iter().zip(variant.payload.iter()).filter(|(binding, _)| binding.text != "_") {
+            let scope = context.get_mut_scope(arm_scope_location).expect("Scope not found, it is a bug");
+            scope.add_variable(VariableInformation::new(binding.clone(), payload.field_type, payload.is_nullable, false, true))?;
+            context.variable_types.insert(SpanKey::from(binding), payload.field_type);
+        }
+
+        Ok(case)
+    }
+
+    /// Common type of the arms of a match used as an expression
+    ///
+    /// Every arm is checked against the expected type when there is one.
+    /// Otherwise the arms must agree with the first arm, numeric literal arms
+    /// take the type of the first arm that is not one.
+    fn match_type(context: &TirContext<'base>, match_ast: &MatchAst<'base>, arm_types: &[TypeLocation], expected: Option<TypeLocation>) -> Result<TypeLocation, TirError> {
+        if let Some(expected) = expected {
+            for (arm, arm_type) in match_ast.arms.iter().zip(arm_types.iter()) {
+                Self::check_arm_type(context, arm, expected, *arm_type)?;
+            }
+
+            return Ok(expected);
+        }
+
+        let typed = match_ast.arms.iter().position(|arm| !matches!(&arm.body, MatchArmBodyAst::Expression(expression) if is_numeric_literal(expression)));
+        let first_type = match match_ast.arms.first().map(|arm| &arm.body) {
+            Some(MatchArmBodyAst::Expression(expression)) if is_numeric_literal(expression) => get_primitive_location(context, literal_type(expression)),
+            _ => arm_types.first().copied().unwrap_or_else(|| get_primitive_location(context, PrimitiveType::Void)),
+        };
+        let location = typed.map_or(first_type, |typed| arm_types[typed]);
+
+        for (index, (arm, arm_type)) in match_ast.arms.iter().zip(arm_types.iter()).enumerate() {
+            // A literal arm before the first typed arm only fails because of that arm, so that arm disagrees with the first one
+            if let Err(error) = Self::check_arm_type(context, arm, location, *arm_type) {
+                return match typed.filter(|typed| index < *typed) {
+                    Some(typed) => Err(Self::check_arm_type(context, &match_ast.arms[typed], first_type, location).err().unwrap_or(error)),
+                    None => Err(error),
+                };
+            }
+        }
+
+        Ok(location)
+    }
+
+    /// Checks that the value of an arm can be stored where `expected` is required
+    fn check_arm_type(context: &TirContext<'base>, arm: &MatchArmAst<'base>, expected: TypeLocation, arm_type: TypeLocation) -> Result<(), TirError> {
+        match &arm.body {
+            MatchArmBodyAst::Expression(expression) if !is_assignable(context, expected, arm_type, expression.as_ref()) => {
+                Err(StatementError::value_type_mismatch(context, expected, arm_type, expression.as_ref()))
+            },
+            MatchArmBodyAst::Block(_) if arm_type != expected => {
+                Err(StatementError::expression_type_mismatch(context, expected, arm_type, pattern_position(&arm.pattern)))
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether a value of type `pattern` can be stored where `expected` is required
+    fn is_same_pattern_type(context: &TirContext<'base>, expected: TypeLocation, pattern: TypeLocation) -> bool {
+        if expected == pattern {
+            return true;
+        }
+
+        match (context.types.get_from_location(expected), context.types.get_from_location(pattern)) {
+            (Some(expected), Some(pattern)) => expected.value.is_same_type(context, &pattern.value),
+            _ => false,
+        }
+    }
+
+    /// Whether the matched value can be `null`
+    ///
+    /// Nullability is not part of the type, it comes from the declaration of
+    /// the variable, field or function return type.
+    fn is_nullable(context: &TirContext<'base>, scope_location: ScopeLocation, expression: &ExpressionAst<'base>) -> bool {
+        let scope = context.get_scope(scope_location).expect("Scope not found, it is a bug");
+        match expression {
+            ExpressionAst::Ident(ident) => scope.get_variable(context, ident).is_some_and(|variable| variable.nullable),
+            ExpressionAst::Ref(reference) => {
+                let mut names = reference.names.iter();
+                let Some(first) = names.next() else {
+                    return false;
+                };
+
+                let (mut location, mut nullable) = match first.text {
+                    "this" => (find_class_location(context, scope_location), false),
+                    _ => match scope.get_variable(context, first) {
+                        Some(variable) => (Some(variable.location), variable.nullable),
+                        None => (None, false),
+                    },
+                };
+
+                for name in names {
+                    let member = location.and_then(|location| context.types.get_from_location(location)).and_then(|signature| match signature.value.as_ref() {
+                        TypeValue::Class(class) => class.get_member(context, name.text),
+                        _ => None,
+                    });
+                    nullable = member.is_some_and(|member| member.nullable);
+                    location = member.map(|member| member.location);
+                }
+
+                nullable
+            },
+            ExpressionAst::FunctionCall(function_call) => match context.call_targets.get(&SpanKey::from(&function_call.call_span)).and_then(|location| context.types.get_from_location(*location)).map(|signature| signature.value.as_ref()) {
+                Some(TypeValue::Function(function)) => function.ast.return_type.nullable,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libtimu_macros_core::traits::TimuErrorTrait;
+    use rstest::rstest;
+
+    use crate::tests::{build, build_with};
+
+    /// Source text of the unreachable patterns of the only match in the code
+    fn unreachable(code: &str) -> Vec<String> {
+        build_with(code, |context| context.matches.values().flat_map(|information| information.unreachable.iter().map(|key| code[key.position.clone()].trim().to_string())).collect()).unwrap()
+    }
+
+    #[rstest]
+    #[case("func test(a: i32): string { return match (a) { 1 => \"one\", 2 => \"two\", _ => \"many\" }; }")]
+    #[case("func test(a: bool): i32 { return match (a) { true => 1, false => 0 }; }")]
+    #[case("func test(a: ?bool): i32 { return match (a) { true => 1, false => 0, null => -1 }; }")]
+    #[case("func test(a: ?i32): i32 { return match (a) { null => 0, value: i32 => value }; }")]
+    #[case("func test(a: i64): i64 { var b: i64 = 2; return match (a) { 1 => b, _ => 3 }; }")]
+    #[case("func test(a: i32): i64 { return match (a) { 1 => 10, _ => 20 }; }")]
+    #[case("func test(a: i32): void { match (a) { 1 => test(2), _ => { return; } } }")]
+    #[case("func test(a: i32): void { match (a) { _ => {} }; var b = match (a) { _ => true }; if b {} }")]
+    #[case("class Shape {} class Circle: Shape { func radius(this): i32 { return 1; } } func test(shape: Shape): i32 { return match (shape) { circle: Circle => circle.radius(), _ => 0 }; }")]
+    #[case("class Shape {} func test(shape: ?Shape): bool { return match (shape) { null => false, other: Shape => true }; }")]
+    #[case("class Shape { next: ?Shape; func test(this): bool { return match (ref this.next) { null => false, _ => true }; } }")]
+    #[case("func find(): ?string { return \"a\"; } func test(): i32 { return match (find()) { null => 0, _ => 1 }; }")]
+    fn valid_match(#[case] code: &str) {
+        build(code).unwrap();
+    }
+
+    #[rstest]
+    #[case("func test(a: bool): i32 { return match (a) { true => 1 }; }", "match is not exhaustive, `false` not covered")]
+    #[case("func test(a: ?bool): i32 { return match (a) { true => 1, false => 0 }; }", "match is not exhaustive, `null` not covered")]
+    #[case("func test(a: ?i32): i32 { return match (a) { 1 => 1 }; }", "match is not exhaustive, `null`, `_` not covered")]
+    #[case("func test(a: i32): i32 { return match (a) {}; }", "match is not exhaustive, `_` not covered")]
+    #[case("class Shape {} class Circle: Shape {} func test(shape: Shape): i32 { return match (shape) { circle: Circle => 1 }; }", "match is not exhaustive, `_` not covered")]
+    #[case("func test(a: i32): i32 { return match (a) { null => 1, _ => 0 }; }", "`null` cannot match a `i32` value")]
+    #[case("func test(a: i32): i32 { return match (a) { \"one\" => 1, _ => 0 }; }", "`\"one\"` cannot match a `i32` value")]
+    #[case("func test(a: u8): i32 { return match (a) { 300 => 1, _ => 0 }; }", "`300` cannot match a `u8` value")]
+    #[case("class Shape {} class Square {} func test(shape: Shape): i32 { return match (shape) { square: Square => 1, _ => 0 }; }", "`Square` cannot match a `Shape` value")]
+    #[case("func test(a: i32): i32 { return match (a) { Color.Red => 1, _ => 0 }; }", "`Color.Red` cannot match a `i32` value")]
+    #[case("func test(a: i32): i32 { return match (a) { 1 => \"one\", _ => 0 }; }", "expected `i32` type, got `string`")]
+    #[case("func test(a: i32): i32 { return match (a) { 1 => 1, _ => {} }; }", "expected `i32` type, got `void`")]
+    #[case("func test(a: i32): void { var b = match (a) { 1 => 1, _ => \"s\" }; }", "expected `i32` type, got `string`")]
+    #[case("func test(a: i32): void { var b = match (a) { 1 => \"one\", _ => true }; }", "expected `string` type, got `bool`")]
+    #[case("func test(a: i32): i32 { return match (a) { value: i32 => value, _ => value }; }", "Variable not found")]
+    fn match_errors(#[case] code: &str, #[case] expected: &str) {
+        let error = build(code).unwrap_err();
+        assert!(error.to_string().contains(expected), "{error}");
+    }
+
+    /// The arm that does not have the expected type, or the type of the first arm, is reported
+    #[rstest]
+    #[case("func test(x: i32): i32 { return match (x) { 1 => 1, _ => \"s\" }; }")]
+    #[case("func test(x: i32): void { var value: i32 = 0; value = match (x) { 1 => 1, _ => \"s\" }; }")]
+    #[case("func test(x: i32): void { var value = match (x) { 1 => 1, 2 => 2, _ => \"s\" }; }")]
+    fn mismatched_arm(#[case] code: &str) {
+        let error = build(code).unwrap_err();
+        let position = error.labels().unwrap()[0].position.clone();
+        assert_eq!(code[position].trim(), "\"s\"", "{error}");
+    }
+
+    #[rstest]
+    #[case("func test(a: i32): i32 { return match (a) { 1 => 1, _ => 0 }; }", &[])]
+    #[case("func test(a: i32): i32 { return match (a) { _ => 0, 1 => 1 }; }", &["1"])]
+    #[case("func test(a: i32): i32 { return match (a) { 1 => 1, 1 => 2, _ => 0 }; }", &["1"])]
+    #[case("func test(a: bool): i32 { return match (a) { true => 1, false => 0, _ => 2 }; }", &["_"])]
+    #[case("func test(a: bool): i32 { return match (a) { true => 1, true => 2, false => 0 }; }", &["true"])]
+    #[case("func test(a: ?bool): i32 { return match (a) { _ => 1, null => 2 }; }", &["null"])]
+    #[case("class Shape {} class Circle: Shape {} func test(shape: Shape): i32 { return match (shape) { s: Shape => 1, c: Circle => 2 }; }", &["c: Circle"])]
+    fn unreachable_arms(#[case] code: &str, #[case] expected: &[&str]) {
+        assert_eq!(unreachable(code), expected);
+    }
+}
@@ -15,12 +15,27 @@
 //! - **Method calls**: `object.method(args)`
 //! - **Module function calls**: `module.function(args)`
 //!
-//! ## Future Statement Types
-//! The architecture supports expansion for additional statement types:
-//! - Variable assignments
-//! - Control flow statements (if, while, for)
-//! - Return statements
-//! - Expression statements
+//! ## Variables
+//! - **Definitions**: `var name: Type = value;` and `const name = value;`
+//! - **Destructuring**: `var (a, b) = pair;`, one variable per tuple element
+//! - **Assignments**: `name = value;`, rejected for constants
+//!
+//! ## Control Flow
+//! - **Conditions**: `if`/`else if`/`else` with `bool` conditions and block scopes
+//! - **Returns**: `return value;` checked against the function's return type
+//!
+//! ## Matches
+//! - **Match expressions**: `match (value) { pattern => body }`, typed by their arms
+//! - **Exhaustiveness**: Missing `null`, `bool`, enum variant and wildcard cases are reported
+//! - **Variant patterns**: `Shape.Circle(radius)` binds the payload of a variant
+//!
+//! ## Tuples
+//! - **Tuple literals**: `(1, "one")`, typed by their elements
+//! - **Element checks**: Mismatches inside a literal point at the offending element
+//!
+//! ## Lambdas
+//! - **Lambda expressions**: `func(a: i32): i32 { return a; }`, resolved with their body
+//! - **Captures**: Variables of enclosing functions, mutable ones need `ref func`
 //!
 //! # Resolution Process
 //!
@@ -57,17 +72,106 @@
 //! - **Function call resolver**: For detailed function call analysis
 //! - **Error system**: For comprehensive error reporting
 
-use core::panic;
 use std::borrow::Cow;
 
+use libtimu_macros::TimuError;
+use strum_macros::{EnumDiscriminants, EnumProperty};
+
 use crate::{
     ast::{BodyStatementAst, PrimitiveValue}, nom_tools::{Span, ToRange}, tir::{context::TirContext, object_signature::TypeValue, scope::ScopeLocation, signature::SignaturePath, TirError}
 };
 
-use super::{ResolveAst, TypeLocation};
+use super::{ResolveAst, ResolverError, TypeLocation};
+mod expression;
 mod function_call;
+mod if_condition;
+mod lambda;
+mod match_expression;
+mod return_statement;
+mod tuple;
+mod variable;
+
+pub use expression::{default_literal_type, expression_position, get_primitive_location, get_primitive_type, infer_expression_type, is_bool_operator, is_integer, is_numeric, operand_type, type_name, ExpressionTypeMismatch, OperatorTypeMismatch};
+pub use function_call::{ArgumentTypeMismatch, CallPathNotValid, FunctionCallArgumentCountMismatch, FunctionCallError, InstanceMemberThroughType, NotCallable, StaticMemberThroughInstance, UnsupportedArgumentType};
+pub use lambda::{LambdaInformation, MethodAsValue, MutableCapture};
+pub use match_expression::{MatchInformation, NonExhaustiveMatch, PatternTypeMismatch, UnknownVariant, VariantBindingMismatch};
+pub use return_statement::{MissingReturnValue, UnexpectedReturnValue};
+pub use tuple::{NotATuple, TupleArityMismatch, TupleIndexOutOfRange};
+pub use variable::{AssignToConstant, VariableTypeRequired, VoidVariable};
+
+/// Errors raised while type checking the statements of a function body
+#[derive(Clone, Debug, TimuError, thiserror::Error, EnumDiscriminants, EnumProperty)]
+pub enum StatementError {
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    ExpressionTypeMismatch(Box<ExpressionTypeMismatch>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    OperatorTypeMismatch(Box<OperatorTypeMismatch>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    AssignToConstant(Box<AssignToConstant>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    VariableTypeRequired(Box<VariableTypeRequired>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    VoidVariable(Box<VoidVariable>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    MissingReturnValue(Box<MissingReturnValue>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    UnexpectedReturnValue(Box<UnexpectedReturnValue>),
 
-pub use function_call::FunctionCallError;
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    MutableCapture(Box<MutableCapture>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    MethodAsValue(Box<MethodAsValue>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    NonExhaustiveMatch(Box<NonExhaustiveMatch>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    PatternTypeMismatch(Box<PatternTypeMismatch>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    UnknownVariant(Box<UnknownVariant>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    VariantBindingMismatch(Box<VariantBindingMismatch>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    TupleArityMismatch(Box<TupleArityMismatch>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    NotATuple(Box<NotATuple>),
+
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    TupleIndexOutOfRange(Box<TupleIndexOutOfRange>),
+}
+
+impl From<StatementError> for TirError {
+    fn from(value: StatementError) -> Self {
+        ResolverError::Statement(Box::new(value)).into()
+    }
+}
 
 /// Represents a function parameter with its name and type information
 /// 
@@ -98,7 +202,11 @@ impl<'base> ResolveAst<'base> for BodyStatementAst<'base> {
     fn resolve(&self, context: &mut TirContext<'base>, scope_location: ScopeLocation) -> Result<TypeLocation, TirError> {
         match self {
             BodyStatementAst::FunctionCall(function_call) => Self::resolve_function_call(context, scope_location, function_call),
-            _ => panic!("Unsupported BodyStatementAst variant: {:?}", self),
+            BodyStatementAst::VariableDefinition(definition) => Self::resolve_variable_definition(context, scope_location, definition),
+            BodyStatementAst::VariableAssign(assign) => Self::resolve_variable_assign(context, scope_location, assign),
+            BodyStatementAst::IfCondition(if_condition) => Self::resolve_if_condition(context, scope_location, if_condition),
+            BodyStatementAst::Match(match_ast) => Self::resolve_match(context, scope_location, match_ast, false, None),
+            BodyStatementAst::Return(return_statement) => Self::resolve_return(context, scope_location, return_statement),
         }
     }
     
@@ -176,7 +284,8 @@ mod tests {
         let source_2 = process_code(&state_2)?;
 
         let context = process_ast(vec![source_2.into(), source_1.into()])?;
-        assert_eq!(context.modules.len(), 2);
+        // The standard library adds its modules and their `std` parent
+        assert_eq!(context.modules.len(), 2 + crate::prelude::MODULES.len() + 1);
 
         let main_module = context.modules.iter().find(|(name, _)| *name == "main").unwrap();
         let lib_module = context.modules.iter().find(|(name, _)| *name == "lib").unwrap();
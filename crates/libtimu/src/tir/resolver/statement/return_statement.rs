@@ -0,0 +1,121 @@
+//! Return statement resolution for the TIR system.
+//!
+//! The returned value is checked against the return type of the enclosing
+//! function. Functions returning `void` may only use `return;`, every other
+//! function has to return a value of a compatible type. Inside a lambda the
+//! value is checked against the lambda's return type.
+
+use std::ops::Range;
+
+use libtimu_macros::TimuError;
+use libtimu_macros_core::SourceCode;
+
+use crate::{
+    ast::{BodyStatementAst, ReturnAst},
+    nom_tools::ToRange,
+    tir::{
+        object_signature::PrimitiveType,
+        resolver::{function::find_function_location, statement::StatementError, TypeLocation},
+        scope::ScopeLocation,
+        TirContext, TirError, TypeValue,
+    },
+};
+
+use super::expression::{expression_position, get_primitive_location, is_assignable, type_name};
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("function needs to return a `{expected}` value")]
+#[diagnostic(code("T0030"), help("add a value to the return statement"))]
+pub struct MissingReturnValue {
+    pub expected: String,
+
+    #[label("return without a value")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("function does not return a value")]
+#[diagnostic(code("T0031"), help("remove the value or change the return type of the function"))]
+pub struct UnexpectedReturnValue {
+    #[label("the function's return type is `void`")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+impl<'base> BodyStatementAst<'base> {
+    /// Resolves a return statement against the enclosing function's return type
+    pub fn resolve_return(context: &mut TirContext<'base>, scope_location: ScopeLocation, return_statement: &ReturnAst<'base>) -> Result<TypeLocation, TirError> {
+        let function_location = find_function_location(context, scope_location).expect("Return statement outside of function, but this is a bug");
+        let return_type = match context.types.get_from_location(function_location).map(|signature| signature.value.as_ref()) {
+            Some(TypeValue::Function(function)) => function.return_type,
+            Some(TypeValue::FunctionType(function_type)) => function_type.return_type,
+            _ => panic!("Expected a function signature, but this is a bug"),
+        };
+
+        let void_location = get_primitive_location(context, PrimitiveType::Void);
+
+        match &return_statement.expression {
+            Some(expression) => {
+                let value_location = Self::resolve_expected_expression(context, scope_location, expression, return_type)?;
+
+                if return_type == void_location {
+                    return Err(StatementError::UnexpectedReturnValue(UnexpectedReturnValue {
+                        position: expression_position(expression).position,
+                        code: (&return_statement.span.state.file).into(),
+                    }.into()).into());
+                }
+
+                if !is_assignable(context, return_type, value_location, expression) {
+                    return Err(StatementError::value_type_mismatch(context, return_type, value_location, expression));
+                }
+            },
+            None if return_type != void_location => {
+                return Err(StatementError::MissingReturnValue(MissingReturnValue {
+                    expected: type_name(context, return_type),
+                    position: return_statement.span.to_range(),
+                    code: (&return_statement.span.state.file).into(),
+                }.into()).into());
+            },
+            None => (),
+        };
+
+        Ok(return_type)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::build;
+
+    #[test]
+    fn return_value() {
+        build("func test(): i32 { return 1; }").unwrap();
+        build("func test(): void { return; }").unwrap();
+        build(r#"func test(a: string): string { return a; }"#).unwrap();
+    }
+
+    #[test]
+    fn return_type_mismatch() {
+        build(r#"func test(): i32 { return "hello"; }"#).unwrap_err();
+    }
+
+    #[test]
+    fn missing_return_value() {
+        build("func test(): i32 { return; }").unwrap_err();
+    }
+
+    #[test]
+    fn unexpected_return_value() {
+        build("func test(): void { return 1; }").unwrap_err();
+    }
+
+    #[test]
+    fn return_in_method() {
+        build("class A { func get(this): bool { return this.check(); } func check(this): bool { return true; } }").unwrap();
+    }
+}
@@ -0,0 +1,249 @@
+//! Tuple literal and destructuring resolution for the TIR system.
+//!
+//! A tuple literal is typed by its elements, integer literals default to
+//! `i32` like they do for variables. Where a tuple type is expected, the
+//! elements of a literal are checked one by one, so numeric literals can take
+//! the expected element types and errors point at the offending element.
+//!
+//! ```timu
+//! var pair = (1, "one");                 // (i32, string)
+//! var wide: (i64, string) = (1, "one");  // Literals take the expected element types
+//! var (number, name) = pair;             // Destructuring into two variables
+//! var wrong: (i32, string) = (1, 2);     // Error: points at `2`
+//! var (a, b, c) = pair;                  // Error: points at `c`
+//! var third = ref pair.2;                // Error: the tuple has 2 elements
+//! ```
+
+use std::ops::Range;
+
+use libtimu_macros::TimuError;
+use libtimu_macros_core::SourceCode;
+
+use crate::{
+    ast::{BodyStatementAst, ExpressionAst, TupleAst, VariableDefinitionAst, VariableDefinitionType},
+    nom_tools::{Span, SpanInfo},
+    tir::{
+        context::{SpanKey, TirContext},
+        object_signature::TypeValue,
+        resolver::{statement::StatementError, tuple_type::{tuple_type_location, TupleDefinition, TupleElement}, TypeLocation},
+        scope::{ScopeLocation, VariableInformation},
+        TirError,
+    },
+};
+
+use super::expression::{default_expression_type, expression_position, is_assignable, type_name};
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("expected a tuple with {expected} elements, got {got}")]
+#[diagnostic(code("T0048"), help("both sides need the same number of elements"))]
+pub struct TupleArityMismatch {
+    pub expected: usize,
+    pub got: usize,
+    pub reason: String,
+
+    #[label("{reason}")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{got}` cannot be destructured")]
+#[diagnostic(code("T0049"), help("only tuples can be destructured into several variables"))]
+pub struct NotATuple {
+    pub got: String,
+
+    #[label("this expression is `{got}`")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("tuple has {count} elements, `{index}` is not one of them")]
+#[diagnostic(code("T0057"), help("elements are numbered from `0`"))]
+pub struct TupleIndexOutOfRange {
+    pub index: String,
+    pub count: usize,
+    pub tuple: String,
+
+    #[label("not an element of `{tuple}`")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+impl StatementError {
+    pub fn tuple_index_out_of_range(tuple: &TupleDefinition, index: &Span<'_>) -> TirError {
+        StatementError::TupleIndexOutOfRange(TupleIndexOutOfRange {
+            index: index.text.to_string(),
+            count: tuple.elements.len(),
+            tuple: tuple.name.clone(),
+            position: index.position.start..index.position.start + index.text.len(),
+            code: (&index.state.file).into(),
+        }.into()).into()
+    }
+
+    pub fn tuple_arity_mismatch(expected: usize, got: usize, reason: String, span: SpanInfo) -> TirError {
+        StatementError::TupleArityMismatch(TupleArityMismatch {
+            expected,
+            got,
+            reason,
+            position: span.position,
+            code: span.file.into(),
+        }.into()).into()
+    }
+
+    /// Type mismatch of a value stored where `expected` is required
+    ///
+    /// For tuple literals the error points at the first element that does not
+    /// fit, or reports the different number of elements.
+    pub fn value_type_mismatch(context: &TirContext<'_>, expected: TypeLocation, got: TypeLocation, expression: &ExpressionAst<'_>) -> TirError {
+        if let ExpressionAst::Tuple(tuple) = expression
+            && let (Some(expected_elements), Some(got_elements)) = (tuple_elements(context, expected), tuple_elements(context, got)) {
+            if expected_elements.len() != tuple.elements.len() {
+                return arity_mismatch(expected_elements.len(), tuple, (&tuple.span).into());
+            }
+
+            for ((expected, got), element) in expected_elements.iter().zip(got_elements.iter()).zip(tuple.elements.iter()) {
+                if !is_assignable(context, expected.field_type, got.field_type, element) {
+                    return Self::value_type_mismatch(context, expected.field_type, got.field_type, element);
+                }
+            }
+        }
+
+        Self::expression_type_mismatch(context, expected, got, expression_position(expression))
+    }
+}
+
+/// Elements of the tuple type stored at the given location, if it is one
+pub fn tuple_elements<'ctx>(context: &'ctx TirContext<'_>, location: TypeLocation) -> Option<&'ctx [TupleElement]> {
+    match context.types.get_from_location(location).map(|signature| signature.value.as_ref()) {
+        Some(TypeValue::Tuple(tuple)) => Some(&tuple.elements),
+        _ => None,
+    }
+}
+
+/// Reports a tuple literal with a different number of elements than expected
+///
+/// Points at the first extra element, or at the whole literal when elements are missing.
+fn arity_mismatch(expected: usize, tuple: &TupleAst<'_>, span: SpanInfo) -> TirError {
+    match tuple.elements.get(expected) {
+        Some(extra) => StatementError::tuple_arity_mismatch(expected, tuple.elements.len(), "extra element".to_string(), expression_position(extra)),
+        None => StatementError::tuple_arity_mismatch(expected, tuple.elements.len(), format!("needs {expected} elements"), span),
+    }
+}
+
+impl<'base> BodyStatementAst<'base> {
+    /// Resolves a tuple literal and registers its type, see [`TirContext::tuples`]
+    pub fn resolve_tuple(context: &mut TirContext<'base>, scope_location: ScopeLocation, tuple: &TupleAst<'base>) -> Result<TypeLocation, TirError> {
+        let mut elements = Vec::new();
+        for element in tuple.elements.iter() {
+            let location = Self::resolve_expression(context, scope_location, element)?;
+            elements.push(TupleElement {
+                field_type: default_expression_type(context, location, element),
+                is_nullable: false,
+            });
+        }
+
+        let location = tuple_type_location(context, elements);
+        context.tuples.insert(SpanKey::from(&tuple.span), location);
+        Ok(location)
+    }
+
+    /// Defines one variable for every element of the tuple stored at `location`
+    ///
+    /// The number of names has to match the number of elements. The variables
+    /// keep the nullability of the elements and are constants for `const`.
+    pub fn resolve_destructure(context: &mut TirContext<'base>, scope_location: ScopeLocation, definition: &VariableDefinitionAst<'base>, names: &[Span<'base>], location: TypeLocation) -> Result<(), TirError> {
+        let position = match &definition.expression {
+            Some(expression) => expression_position(expression),
+            None => (&definition.name).into(),
+        };
+
+        let Some(elements) = tuple_elements(context, location).map(|elements| elements.to_vec()) else {
+            return Err(StatementError::NotATuple(NotATuple {
+                got: type_name(context, location),
+                position: position.position,
+                code: position.file.into(),
+            }.into()).into());
+        };
+
+        if names.len() != elements.len() {
+            return Err(match (names.get(elements.len()), &definition.expression) {
+                (Some(name), _) => StatementError::tuple_arity_mismatch(names.len(), elements.len(), "no element for this variable".to_string(), name.into()),
+                (None, Some(ExpressionAst::Tuple(tuple))) => arity_mismatch(names.len(), tuple, position),
+                (None, _) => StatementError::tuple_arity_mismatch(names.len(), elements.len(), format!("has {} elements", elements.len()), position),
+            });
+        }
+
+        let readonly = definition.variable_definition_type == VariableDefinitionType::Const;
+        for (name, element) in names.iter().zip(elements.iter()) {
+            let scope = context.get_mut_scope(scope_location).expect("Scope not found, it is a bug");
+            scope.add_variable(VariableInformation::new(name.clone(), element.field_type, element.is_nullable, false, readonly))?;
+            context.variable_types.insert(SpanKey::from(name), element.field_type);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libtimu_macros_core::traits::TimuErrorTrait;
+    use rstest::rstest;
+
+    use crate::tests::build;
+
+    /// Source text the first label of the error points at
+    fn label(code: &str) -> String {
+        let error = build(code).unwrap_err();
+        let label = error.labels().unwrap().remove(0);
+        code[label.position].to_string()
+    }
+
+    #[rstest]
+    #[case("func test(): void { var pair = (1, \"one\"); var number: i32 = ref pair.0; var name: string = ref pair.1; }")]
+    #[case("func test(): (i64, string) { return (1, \"one\"); }")]
+    #[case("func divide(a: i32, b: i32): (i32, i32) { return (a / b, a % b); } func test(): i32 { var (quotient, remainder) = divide(7, 2); return quotient + remainder; }")]
+    #[case("func test(): void { const (a, b): (double, ?string) = (1, \"b\"); var c: double = a; }")]
+    #[case("func test(): void { var nested = (1, (true, \"a\")); var (number, inner) = nested; var flag: bool = ref inner.0; }")]
+    #[case("func test(): void { var (x, y) = (1, 2); x = 3; }")]
+    #[case("class Shape {} class Circle: Shape {} func test(circle: Circle): (Shape, i32) { return (circle, 1); }")]
+    #[case("func take(pair: (u8, bool)): void {} func test(): void { take((1, true)); }")]
+    #[case("func variable(value: i32): void {} func test(): void { var constant = 1; variable(constant); constant = 2; }")]
+    fn valid_tuples(#[case] code: &str) {
+        build(code).unwrap();
+    }
+
+    #[rstest]
+    #[case("func test(): (i32, string) { return (1, 2); }", "expected `string` type, got `i32`")]
+    #[case("func test(): (u8, string) { return (300, \"a\"); }", "expected `u8` type, got `i32`")]
+    #[case("func test(): (i32, string) { return (1, \"a\", true); }", "expected a tuple with 2 elements, got 3")]
+    #[case("func test(): (i32, string, bool) { return (1, \"a\"); }", "expected a tuple with 3 elements, got 2")]
+    #[case("func test(): void { var (a, b) = 1; }", "`i32` cannot be destructured")]
+    #[case("func test(): void { var (a, b, c) = (1, 2); }", "expected a tuple with 3 elements, got 2")]
+    #[case("func test(pair: (i32, i32, i32)): void { var (a, b) = pair; }", "expected a tuple with 2 elements, got 3")]
+    #[case("func test(): void { const (a, b) = (1, 2); a = 3; }", "`a` is a constant and cannot be assigned")]
+    #[case("func test(): void { var (a, a) = (1, 2); }", "Already defined")]
+    #[case("func take(pair: (i32, bool)): void {} func test(): void { take((1, 2)); }", "expected `bool` type, got `i32`")]
+    fn tuple_errors(#[case] code: &str, #[case] expected: &str) {
+        let error = build(code).unwrap_err();
+        assert!(error.to_string().contains(expected), "{error}");
+    }
+
+    #[rstest]
+    #[case("func test(): (i32, string) { return (1, 2); }", "2")]
+    #[case("func test(): (i32, (bool, string)) { return (1, (true, false)); }", "false")]
+    #[case("func test(): (i32, string) { return (1, \"a\", true); }", "true")]
+    #[case("func test(): (i32, string, bool) { return (1, \"a\"); }", "(1, \"a\")")]
+    #[case("func test(): void { var (a, b, c) = (1, 2); }", "c")]
+    #[case("func test(): void { var (a, b) = (1, 2, 3); }", "3")]
+    #[case("func test(): void { var pair: (i32, i32) = (1, \"a\"); }", "\"a\"")]
+    fn error_positions(#[case] code: &str, #[case] expected: &str) {
+        assert_eq!(label(code), expected, "{code}");
+    }
+}
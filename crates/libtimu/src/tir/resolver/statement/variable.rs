@@ -0,0 +1,229 @@
+//! Variable definition and assignment resolution for the TIR system.
+//!
+//! Variables are stored in the scope they are defined in, so they are visible
+//! to the following statements of the same block and to all nested blocks.
+//!
+//! ```timu
+//! var counter: i32 = 0;    // Explicit type, the value must match it
+//! var name = "timu";       // Type taken from the value
+//! const limit = 10;        // Integer literals default to i32
+//! counter = counter + 1;   // Assignments are checked against the variable type
+//! limit = 20;              // Error: constants cannot be assigned
+//! Counter.count = 1;       // Fields are assigned through the type or an object
+//! var (x, y) = (1, 2);     // One variable per tuple element, see [`super::tuple`]
+//! ```
+//!
+//! Module-level constants and global variables use the same rules, see
+//! [`crate::tir::resolver::module_variable`].
+
+use std::ops::Range;
+
+use libtimu_macros::TimuError;
+use libtimu_macros_core::SourceCode;
+
+use crate::{
+    ast::{BodyStatementAst, ExpressionAst, RefAst, VariableAssignAst, VariableDefinitionAst, VariableDefinitionType},
+    nom_tools::ToRange,
+    tir::{
+        context::{IdentifierTarget, SpanKey},
+        object_signature::PrimitiveType,
+        resolver::{function::{check_static_access, FunctionResolveError}, get_object_location_or_resolve, statement::StatementError, TypeLocation},
+        scope::{ScopeLocation, VariableInformation},
+        TirContext, TirError,
+    },
+};
+
+use super::expression::{default_expression_type, get_primitive_location, is_assignable};
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{name}` is a constant and cannot be assigned")]
+#[diagnostic(code("T0027"), help("define it with `var` to make it mutable"))]
+pub struct AssignToConstant {
+    pub name: String,
+
+    #[label("defined here")]
+    pub definition: Range<usize>,
+
+    #[label("assigned here")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("type of `{name}` cannot be determined")]
+#[diagnostic(code("T0028"), help("add a type annotation or an initial value"))]
+pub struct VariableTypeRequired {
+    pub name: String,
+
+    #[label("needs a type or a value")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+#[derive(Clone, Debug, TimuError, thiserror::Error)]
+#[error("`{name}` cannot have a `void` type")]
+#[diagnostic(code("T0029"))]
+pub struct VoidVariable {
+    pub name: String,
+
+    #[label("this variable would hold no value")]
+    pub position: Range<usize>,
+
+    #[source_code]
+    pub code: SourceCode,
+}
+
+impl<'base> BodyStatementAst<'base> {
+    /// Resolves a variable definition and registers the variable in the current scope
+    ///
+    /// A destructuring definition registers one variable per tuple element instead.
+    pub fn resolve_variable_definition(context: &mut TirContext<'base>, scope_location: ScopeLocation, definition: &VariableDefinitionAst<'base>) -> Result<TypeLocation, TirError> {
+        let module_ref = context.get_scope(scope_location).expect("Scope not found, it is a bug").module_ref.clone();
+
+        let expected_location = match &definition.expected_type {
+            Some(expected_type) => Some(get_object_location_or_resolve(context, expected_type, &module_ref, scope_location)?),
+            None => None,
+        };
+
+        let value_location = match &definition.expression {
+            Some(expression) => Some(match expected_location {
+                Some(expected) => Self::resolve_expected_expression(context, scope_location, expression, expected)?,
+                None => Self::resolve_expression(context, scope_location, expression)?,
+            }),
+            None => None,
+        };
+
+        let location = match (expected_location, value_location, &definition.expression) {
+            (Some(expected), Some(value), Some(expression)) => match is_assignable(context, expected, value, expression) {
+                true => expected,
+                false => return Err(StatementError::value_type_mismatch(context, expected, value, expression)),
+            },
+            (None, Some(value), Some(expression)) => default_expression_type(context, value, expression),
+            (Some(expected), _, _) => expected,
+            _ => return Err(StatementError::VariableTypeRequired(VariableTypeRequired {
+                name: definition.name.text.to_string(),
+                position: definition.name.to_range(),
+                code: (&definition.name.state.file).into(),
+            }.into()).into()),
+        };
+
+        if location == get_primitive_location(context, PrimitiveType::Void) {
+            return Err(StatementError::VoidVariable(VoidVariable {
+                name: definition.name.text.to_string(),
+                position: definition.name.to_range(),
+                code: (&definition.name.state.file).into(),
+            }.into()).into());
+        }
+
+        if let Some(names) = &definition.destructure {
+            Self::resolve_destructure(context, scope_location, definition, names, location)?;
+            return Ok(location);
+        }
+
+        let (nullable, reference) = match &definition.expected_type {
+            Some(expected_type) => (expected_type.nullable, expected_type.reference),
+            None => (false, false),
+        };
+
+        let readonly = definition.variable_definition_type == VariableDefinitionType::Const;
+        let scope = context.get_mut_scope(scope_location).expect("Scope not found, it is a bug");
+        scope.add_variable(VariableInformation::new(definition.name.clone(), location, nullable, reference, readonly))?;
+        context.variable_types.insert(SpanKey::from(&definition.name), location);
+
+        Ok(location)
+    }
+
+    /// Resolves an assignment to an already defined variable
+    pub fn resolve_variable_assign(context: &mut TirContext<'base>, scope_location: ScopeLocation, assign: &VariableAssignAst<'base>) -> Result<TypeLocation, TirError> {
+        /* `Counter.count = 1;` is checked like the `ref Counter.count` expression */
+        if !assign.path.is_empty() {
+            let names = assign.path.iter().chain(std::iter::once(&assign.name)).cloned().collect();
+            let location = Self::resolve_expression(context, scope_location, &ExpressionAst::Ref(RefAst { names }))?;
+            let value_location = Self::resolve_expected_expression(context, scope_location, &assign.expression, location)?;
+            if !is_assignable(context, location, value_location, &assign.expression) {
+                return Err(StatementError::value_type_mismatch(context, location, value_location, &assign.expression));
+            }
+
+            return Ok(location);
+        }
+
+        let variable = match context.get_scope(scope_location).expect("Scope not found, it is a bug").get_variable(context, &assign.name) {
+            Some(variable) => variable,
+            None => return Err(FunctionResolveError::variable_not_found((&assign.name).into())),
+        };
+
+        check_static_access(context, scope_location, &variable, (&assign.name).into())?;
+
+        // Backends need to know which global variable or static field is assigned, assigning a local variable is not a use of it
+        let definition = SpanKey::from(&variable.span);
+        if context.globals.contains_key(&definition) || !context.variable_types.contains_key(&definition) {
+            context.identifiers.insert(SpanKey::from(&assign.name), IdentifierTarget { location: variable.location, definition: Some(definition) });
+        }
+
+        if variable.readonly {
+            return Err(StatementError::AssignToConstant(AssignToConstant {
+                name: assign.name.text.to_string(),
+                definition: variable.span.to_range(),
+                position: assign.name.to_range(),
+                code: (&assign.name.state.file).into(),
+            }.into()).into());
+        }
+
+        Self::capture_variable(context, scope_location, &variable, &assign.name, true)?;
+
+        let value_location = Self::resolve_expected_expression(context, scope_location, &assign.expression, variable.location)?;
+        if !is_assignable(context, variable.location, value_location, &assign.expression) {
+            return Err(StatementError::value_type_mismatch(context, variable.location, value_location, &assign.expression));
+        }
+
+        Ok(variable.location)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::build;
+
+    #[test]
+    fn variable_definition() {
+        build(r#"func test(): void { var a: i32 = 10; var b = a; const c = "hello"; var d: i64; }"#).unwrap();
+    }
+
+    #[test]
+    fn variable_type_mismatch() {
+        build(r#"func test(): void { var a: i32 = "hello"; }"#).unwrap_err();
+        build("func test(a: bool): void { var b: string = a; }").unwrap_err();
+    }
+
+    #[test]
+    fn variable_without_type() {
+        build("func test(): void { var a; }").unwrap_err();
+    }
+
+    #[test]
+    fn variable_redefinition() {
+        build("func test(): void { var a = 1; var a = 2; }").unwrap_err();
+    }
+
+    #[test]
+    fn variable_assign() {
+        build("func test(): void { var a = 1; a = a + 1; }").unwrap();
+        build("func test(): void { var a = 1; a = true; }").unwrap_err();
+        build("func test(): void { a = 1; }").unwrap_err();
+    }
+
+    #[test]
+    fn assign_to_constant() {
+        let error = build("func test(): void { const a = 1; a = 2; }").unwrap_err();
+        assert!(error.to_string().contains("constant"), "{error}");
+    }
+
+    #[test]
+    fn untyped_integer_defaults_to_i32() {
+        build("func test(): void { var a = 1; a = 100000; }").unwrap();
+    }
+}
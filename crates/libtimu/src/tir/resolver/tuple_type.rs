@@ -0,0 +1,132 @@
+//! Tuple type resolution for the TIR system.
+//!
+//! Tuples group a fixed number of values of possibly different types. They
+//! are mostly used to return several values from a function without
+//! declaring a class for them.
+//!
+//! ```timu
+//! func divide(a: i32, b: i32): (i32, i32) {
+//!     return (a / b, a % b);
+//! }
+//!
+//! var (quotient, remainder) = divide(7, 2);
+//! var result = divide(9, 4);
+//! var first = ref result.0;
+//! ```
+//!
+//! # Structural Types
+//!
+//! Like function types, tuple types are compared by their elements. Every
+//! distinct tuple type is registered once in the type table under a key built
+//! from the locations of its elements, so two tuple types are the same type
+//! exactly when they have the same location.
+
+use crate::{
+    ast::TypeNameAst,
+    file::SourceFile,
+    tir::{context::TirContext, module::ModuleRef, object_signature::{GetItem, TypeValue}, scope::ScopeLocation, signature::SignaturePath, TirError, TypeSignature},
+};
+
+use super::{get_object_location_or_resolve, statement::type_name, TypeLocation};
+
+/// Element of a tuple type
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TupleElement {
+    pub field_type: TypeLocation,
+    pub is_nullable: bool,
+}
+
+/// Resolved tuple type such as `(i32, string)`
+///
+/// # Fields
+///
+/// - `name`: User facing name, like `(i32, string)`
+/// - `elements`: Element types in order
+#[derive(Debug, Clone, PartialEq)]
+pub struct TupleDefinition {
+    pub name: String,
+    pub elements: Vec<TupleElement>,
+}
+
+impl GetItem for TupleDefinition {
+    /// Elements are accessed by their position, `0` is the first element
+    fn get_item_location(&self, _: &TirContext<'_>, path: &str) -> Option<TypeLocation> {
+        self.elements.get(path.parse::<usize>().ok()?).map(|element| element.field_type)
+    }
+}
+
+/// Resolves the element types of a tuple type name
+pub fn resolve_tuple_type<'base>(context: &mut TirContext<'base>, elements: &[TypeNameAst<'base>], module: &ModuleRef<'base>, scope_location: ScopeLocation) -> Result<TypeLocation, TirError> {
+    let mut resolved = Vec::new();
+    for element in elements.iter() {
+        resolved.push(TupleElement {
+            field_type: get_object_location_or_resolve(context, element, module, scope_location)?,
+            is_nullable: element.nullable,
+        });
+    }
+
+    Ok(tuple_type_location(context, resolved))
+}
+
+/// Location of the tuple type with the given elements, registered on first use
+pub fn tuple_type_location(context: &mut TirContext<'_>, elements: Vec<TupleElement>) -> TypeLocation {
+    let nullable = |element: &TupleElement| if element.is_nullable { "?" } else { "" };
+    let key = format!("({})", elements.iter().map(|element| format!("{}{}", nullable(element), element.field_type.0)).collect::<Vec<_>>().join(", "));
+
+    if let Some(location) = context.types.location(&key) {
+        return location;
+    }
+
+    let name = format!("({})", elements.iter().map(|element| format!("{}{}", nullable(element), type_name(context, element.field_type))).collect::<Vec<_>>().join(", "));
+    let signature = TypeSignature::new(
+        TypeValue::Tuple(TupleDefinition { name, elements }),
+        SourceFile::new(vec!["<tuple-type>".into()], String::new()),
+        0..0,
+        None,
+    );
+
+    context.types.add_signature(SignaturePath::owned(key), signature).expect("Tuple type registered twice, but this is a bug")
+}
+
+#[cfg(test)]
+mod tests {
+    use libtimu_macros_core::traits::TimuErrorTrait;
+    use rstest::rstest;
+
+    use crate::tests::build;
+
+    #[rstest]
+    #[case("func pair(): (i32, string) { return (1, \"one\"); }")]
+    #[case("func first(pair: (i32, string)): i32 { return ref pair.0; }")]
+    #[case("func second(pair: (i32, (bool, string))): string { return ref pair.1.1; }")]
+    #[case("type Pair = (i64, i64); func sum(pair: Pair): i64 { return ref pair.0 + ref pair.1; }")]
+    #[case("class Point { position: (double, double); func x(this): double { return ref this.position.0; } }")]
+    #[case("func check(value: ?(i32, ?string)): void {}")]
+    #[case("func apply(callback: func((i32, i32)): (i32, i32)): (i32, i32) { return callback((1, 2)); }")]
+    fn tuple_types(#[case] code: &str) {
+        build(code).unwrap();
+    }
+
+    #[rstest]
+    #[case("func first(pair: (i32, string)): string { return ref pair.0; }", "expected `string` type, got `i32`")]
+    #[case("func third(pair: (i32, string)): i32 { return ref pair.2; }", "tuple has 2 elements, `2` is not one of them")]
+    #[case("func name(pair: (i32, string)): i32 { return ref pair.name; }", "tuple has 2 elements, `name` is not one of them")]
+    #[case("func call(pair: (i32, func(): i32)): i32 { return pair.call(); }", "tuple has 2 elements, `call` is not one of them")]
+    #[case("func check(pair: (i32, missing)): void {}", "'missing' type not found")]
+    #[case("func check(pair: (i32, string)): (string, i32) { return pair; }", "expected `(string, i32)` type, got `(i32, string)`")]
+    fn tuple_type_errors(#[case] code: &str, #[case] expected: &str) {
+        let error = build(code).unwrap_err();
+        assert!(error.to_string().contains(expected), "{error}");
+    }
+
+    #[test]
+    fn tuple_index_out_of_range() {
+        let code = "func third(pair: (i32, string)): i32 { return ref pair.2; }";
+        let error = build(code).unwrap_err();
+        assert_eq!(error.error_code().unwrap().to_string(), "T0057");
+
+        let label = error.labels().unwrap().remove(0);
+        assert_eq!(&code[label.position], "2");
+        assert_eq!(label.label, "not an element of `(i32, string)`");
+    }
+}
@@ -0,0 +1,127 @@
+//! Type alias resolution for the Timu language compiler.
+//!
+//! A type alias gives an existing type another name:
+//! ```timu
+//! type Point = geometry.shapes.Point;
+//! pub type Meters = i32;
+//! ```
+//!
+//! Aliases are transparent. Resolving an alias resolves the aliased type and
+//! registers its location under the alias name in the module, so every lookup
+//! of the alias, local or imported, returns the aliased type itself. A chain
+//! of aliases that comes back to the first one is reported as a
+//! [`crate::tir::error::CircularReference`].
+
+use std::{borrow::Cow, collections::HashSet};
+
+use crate::{
+    ast::TypeAliasAst,
+    nom_tools::ToRange,
+    tir::{ast_signature::AstSignatureValue, context::TirContext, module::ModuleRef, scope::ScopeLocation, signature::SignaturePath, TirError},
+};
+
+use super::{build_signature_path, build_type_name, find_ast_signature, get_object_location_or_resolve, ResolveAst, TypeLocation};
+
+impl<'base> ResolveAst<'base> for TypeAliasAst<'base> {
+    /// Resolves the aliased type and registers it under the alias name
+    fn resolve(&self, context: &mut TirContext<'base>, scope_location: ScopeLocation) -> Result<TypeLocation, TirError> {
+        simplelog::debug!("Resolving type alias: <u><b>{}</b></u>", self.name.text);
+        let module_ref = context.get_scope(scope_location).expect("Scope not found").module_ref.clone();
+
+        self.check_circular_alias(context, &module_ref)?;
+        let location = get_object_location_or_resolve(context, &self.target, &module_ref, scope_location)?;
+
+        let module = context.modules.get_mut(module_ref.as_ref())
+            .unwrap_or_else(|| panic!("Module({}) not found, but this is a bug", module_ref.as_ref()));
+        module.types.insert(SignaturePath::borrowed(self.name.text), location);
+        Ok(location)
+    }
+
+    /// Aliases have no body, everything is done while resolving them
+    fn finish(&self, _: &mut TirContext<'base>, _: ScopeLocation) -> Result<(), TirError> {
+        Ok(())
+    }
+
+    fn name(&self) -> Cow<'base, str> {
+        Cow::Borrowed(self.name.text)
+    }
+}
+
+impl<'base> TypeAliasAst<'base> {
+    /// Follows the targets that are aliases themselves and reports a chain that comes back to the alias
+    fn check_circular_alias(&self, context: &mut TirContext<'base>, module_ref: &ModuleRef<'base>) -> Result<(), TirError> {
+        let mut current = Some((self.target.clone(), module_ref.clone()));
+        let mut visited = HashSet::new();
+
+        while let Some((target, module)) = current {
+            let target_path = build_signature_path(context, build_type_name(&target).as_str(), &module);
+
+            // A missing target is reported while resolving it
+            let Some(location) = find_ast_signature(context, &module, target_path) else {
+                return Ok(());
+            };
+
+            // A cycle between other aliases is reported by one of them
+            if !visited.insert(location) {
+                return Ok(());
+            }
+
+            current = match context.ast_signatures.get_from_location(location) {
+                Some(signature) => match signature.value.as_ref() {
+                    AstSignatureValue::TypeAlias(alias) if alias.index == self.index => return Err(TirError::circular_reference(self.name.to_range(), self.name.state.file.clone())),
+                    AstSignatureValue::TypeAlias(alias) => signature.extra.clone().map(|module| (alias.target.clone(), module)),
+                    _ => None,
+                },
+                None => None,
+            };
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use crate::{tests::build_modules, tir::TirError};
+
+    #[rstest]
+    #[case("type Meters = i32;\nfunc double(value: Meters): Meters { return value * 2; }")]
+    #[case("func first(value: Km): i32 { return value; }\ntype Km = Meters;\ntype Meters = i32;")]
+    #[case("type Maybe = i32;\nfunc get(value: ?Maybe): ?i32 { return value; }")]
+    #[case("class Point { x: i32; }\ntype P = Point;\nfunc x(point: P): i32 { return ref point.x; }")]
+    #[case("use lib.Length;\nfunc get(value: Length): i32 { return value; }")]
+    #[case("use lib;\nfunc get(value: lib.Length): i32 { return value; }")]
+    #[case("type Other = lib.Shape;\nfunc get(value: Other): lib.Shape { return value; }")]
+    fn valid_alias(#[case] code: &str) -> Result<(), TirError> {
+        build_modules(&[("lib", "pub class Shape {}\npub type Length = i32;\ntype Hidden = i32;"), ("main", code)])
+    }
+
+    #[rstest]
+    #[case("type A = A;")]
+    #[case("type A = B;\ntype B = A;")]
+    #[case("type A = B;\ntype B = C;\ntype C = A;\nfunc get(value: B): void {}")]
+    fn circular_alias(#[case] code: &str) {
+        let result = build_modules(&[("main", code)]);
+        assert!(matches!(result, Err(TirError::CircularReference(_))), "{code}: {result:?}");
+    }
+
+    #[test]
+    fn private_alias_import() {
+        let result = build_modules(&[("lib", "type Hidden = i32;"), ("main", "use lib.Hidden;")]);
+        assert!(matches!(result, Err(TirError::AccessibilityViolation(_))), "{result:?}");
+    }
+
+    #[test]
+    fn missing_alias_target() {
+        let result = build_modules(&[("main", "type Meters = Missing;")]);
+        assert!(matches!(result, Err(TirError::TypeNotFound(_))), "{result:?}");
+    }
+
+    #[test]
+    fn duplicate_alias_name() {
+        let result = build_modules(&[("main", "class Meters {}\ntype Meters = i32;")]);
+        assert!(result.is_err());
+    }
+}
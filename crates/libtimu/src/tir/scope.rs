@@ -42,7 +42,7 @@ use strum_macros::{EnumDiscriminants, EnumProperty};
 use crate::{
     map::{TimuHashMap, ValueTrait}, 
     nom_tools::{Span, SpanInfo}, 
-    tir::resolver::{AstSignatureLocation, BuildFullNameLocater, ResolveAst}
+    tir::{ast_signature::AstSignatureValue, resolver::{AstSignatureLocation, BuildFullNameLocater, ResolveAst}}
 };
 
 use super::{
@@ -304,11 +304,10 @@ impl<'base> Scope<'base> {
         let module = self.module_ref.upgrade(context).unwrap();
         let module_scope = context.get_scope(module.scope_location).unwrap_or_else(|| panic!("Module scope not found for module: {}", module.path));
 
-        if module_scope.location != self.location {
-            if let Some(variable_info) = module_scope.get_variable(context, name_span) {
+        if module_scope.location != self.location
+            && let Some(variable_info) = module_scope.get_variable(context, name_span) {
                 return Some(variable_info);
             }
-        }
 
         /* Handle type and module resolution */
         if let Some(type_location) = module.types.get(name) {
@@ -316,35 +315,46 @@ impl<'base> Scope<'base> {
         }
 
         /* Search as a module name  */
-        if let Some(module_ref) = context.modules.get(name) {
-            if let Some(type_location) = module.types.get(module_ref.path.as_ref()) {
+        if let Some(module_ref) = context.modules.get(name)
+            && let Some(type_location) = module.types.get(module_ref.path.as_ref()) {
                 return Some(TypeVariableInformation::basic(name_span.clone(), *type_location));
             }
-        }
 
         if let Some(type_location) = context.types.location(name) {
             return Some(TypeVariableInformation::basic(name_span.clone(), type_location));
         }
 
-        if let Some(ast_location) = module.ast_imported_modules.get(name) {
-            if let Some(signature) = context.ast_signatures.get_from_location(*ast_location) {
+        if let Some(ast_location) = module.ast_imported_modules.get(name)
+            && let Some(signature) = context.ast_signatures.get_from_location(*ast_location) {
+                // Imported module variables live in the scope of their module
+                if let AstSignatureValue::Variable(variable) = signature.value.as_ref() {
+                    return signature.extra.as_ref()
+                        .and_then(|owner| owner.upgrade(context))
+                        .and_then(|owner| context.get_scope(owner.scope_location))
+                        .and_then(|scope| scope.variables.get(variable.definition.name.text))
+                        .cloned();
+                }
+
                 let full_name = signature.value.build_full_name(context, BuildFullNameLocater::Module(signature.extra.as_ref().unwrap()), None);
 
                 if let Some(type_location) = context.types.location(full_name.as_str()) {
                     return Some(TypeVariableInformation::basic(name_span.clone(), type_location));
                 }
             }
-        }
 
-        if let Some(module_ref) = context.modules.get(name) {
-            if let Some(type_location) = module.types.get(module_ref.path.as_ref()) {
+        if let Some(module_ref) = context.modules.get(name)
+            && let Some(type_location) = module.types.get(module_ref.path.as_ref()) {
                 return Some(TypeVariableInformation::basic(name_span.clone(), *type_location));
             }
-        }
 
         None
     }
 
+    /// Whether the variable defined at the given span belongs to this scope itself
+    pub fn has_variable(&self, definition: &Span<'base>) -> bool {
+        self.variables.get(definition.text).is_some_and(|variable| variable.span.position == definition.position && variable.span.state.file.path == definition.state.file.path)
+    }
+
     pub fn add_variable(&mut self, variable: TypeVariableInformation<'base>) -> Result<(), TirError> {
         simplelog::debug!("Adding variable: <u><b><on-green>{}</></b></u>, location <u><b>{:?}</b></u>, scope: {}", variable.span.text, variable.location, self.location.0);
         self.variables.validate_insert((*variable.span.text).into(), variable)?;
@@ -359,9 +369,16 @@ impl<'base> Scope<'base> {
 #[derive(Clone, Debug, TimuError, thiserror::Error, EnumDiscriminants, EnumProperty)]
 pub enum ScopeError {
     #[error("Variable already defined")]
+    #[diagnostic(code("T0014"))]
     VariableAlreadyDefined(SpanInfo),
 }
 
+impl From<ScopeError> for TirError {
+    fn from(value: ScopeError) -> Self {
+        ResolverError::Scope(Box::new(value)).into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -430,9 +447,3 @@ mod tests {
         assert_eq!(ScopeLocation::UNDEFINED.0, usize::MAX);
     }
 }
-
-impl From<ScopeError> for TirError {
-    fn from(value: ScopeError) -> Self {
-        ResolverError::Scope(Box::new(value)).into()
-    }
-}
@@ -244,11 +244,10 @@ where
 
     pub fn find_by_value(&self, value: &T) -> Option<L> {
         for (index, signature) in self.signatures.iter().enumerate() {
-            if let Some(SignatureInfo::Value(signature)) = signature {
-                if &signature.value == value {
+            if let Some(SignatureInfo::Value(signature)) = signature
+                && &signature.value == value {
                     return Some(index.into())
                 }
-            }
         }
 
         None
@@ -302,6 +301,14 @@ where
     pub fn location(&self, name: &str) -> Option<L> {
         self.locations.get(name).map(|index| (*index).into())
     }
+
+    /// Iterates over all resolved signatures in registration order, reservations are skipped
+    pub fn iter(&self) -> impl Iterator<Item = (&SignaturePath<'base>, L, &Signature<T, E>)> {
+        self.locations.iter().filter_map(|(path, index)| match self.signatures.get(*index) {
+            Some(Some(SignatureInfo::Value(signature))) => Some((path, (*index).into(), signature)),
+            _ => None,
+        })
+    }
 }
 
 
@@ -421,19 +428,16 @@ impl<'base> SignaturePath<'base> {
         match full_path.find('.') {
             Some(index) => {
 
-                let mut position = 0;
-                let mut start_index = 0;
+                let mut start_index;
                 let mut end_index = index;
 
                 let mut modules = Vec::new();
-                modules.push(start_index..end_index);
+                modules.push(0..end_index);
                 end_index += 1; // Skip the dot
 
                 while let Some(new_index) = full_path[end_index..].find('.') {
                     start_index = end_index;
-                    
-                    position += new_index + 1;
-                    end_index = position + new_index;
+                    end_index = start_index + new_index;
 
                     modules.push(start_index..end_index);
                     end_index += 1; // Skip the dot
@@ -573,6 +577,11 @@ mod tests {
         assert_eq!(path.build_string(path.get_modules()[1].clone()), "module2");
         assert_eq!(path.build_string(path.get_modules()[2].clone()), "module3");
         assert_eq!(path.build_string(path.get_modules()[3].clone()), "module4");
+
+        let path = SignaturePath::borrowed("std.collections.List");
+        assert_eq!(path.get_name(), "List");
+        assert_eq!(path.build_string(path.get_modules()[0].clone()), "std");
+        assert_eq!(path.build_string(path.get_modules()[1].clone()), "collections");
         
         Ok(())
     }
@@ -0,0 +1,106 @@
+//! Conversion of compiler errors into protocol diagnostics.
+//!
+//! An error becomes one diagnostic placed on its first label. The labels and
+//! the referenced errors in the same document are attached as related
+//! information, so editors show them next to the error the way the terminal
+//! report does.
+
+use std::path::PathBuf;
+
+use libtimu::tir::TirError;
+use libtimu_macros_core::traits::TimuErrorTrait;
+use lsp_types::{Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString, Url};
+
+use crate::position::LineIndex;
+
+/// Diagnostics of the errors reported for the given module
+pub fn convert(error: &TirError, uri: &Url, module: &[String], index: &LineIndex<'_>) -> Vec<Diagnostic> {
+    let name = PathBuf::from_iter(module).to_string_lossy().to_string();
+    let mut diagnostics = Vec::new();
+    collect(error, uri, &name, index, &mut diagnostics);
+    diagnostics
+}
+
+fn collect(error: &dyn TimuErrorTrait, uri: &Url, name: &str, index: &LineIndex<'_>, diagnostics: &mut Vec<Diagnostic>) {
+    if let Some(errors) = error.errors() {
+        for inner_error in errors {
+            collect(inner_error, uri, name, index, diagnostics);
+        }
+    }
+
+    let labels = match (error.source_code(), error.labels()) {
+        (Some(source_code), Some(labels)) if source_code.name == name && !labels.is_empty() => labels,
+        _ => return,
+    };
+
+    let mut related_information = labels.iter()
+        .filter(|label| !label.label.is_empty())
+        .map(|label| DiagnosticRelatedInformation { location: Location::new(uri.clone(), index.range(label.position.clone())), message: label.label.clone() })
+        .collect::<Vec<_>>();
+
+    for reference in error.references().unwrap_or_default() {
+        let Some(source_code) = reference.source_code() else { continue };
+        if source_code.name != name {
+            continue;
+        }
+
+        for label in reference.labels().unwrap_or_default() {
+            let message = if label.label.is_empty() { reference.to_string() } else { label.label };
+            related_information.push(DiagnosticRelatedInformation { location: Location::new(uri.clone(), index.range(label.position)), message });
+        }
+    }
+
+    let message = match error.help() {
+        Some(help) => format!("{error}\nhelp: {help}"),
+        None => error.to_string(),
+    };
+
+    diagnostics.push(Diagnostic {
+        range: index.range(labels[0].position.clone()),
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: error.error_code().map(|code| NumberOrString::String(code.to_string())),
+        source: Some("timu".to_string()),
+        message,
+        related_information: (!related_information.is_empty()).then_some(related_information),
+        ..Default::default()
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use libtimu::{file::SourceFile, nom_tools::State, process_ast, process_code};
+    use lsp_types::{Position, Url};
+
+    use crate::position::LineIndex;
+
+    fn diagnostics(code: &str) -> Vec<lsp_types::Diagnostic> {
+        let state = State::new(SourceFile::new(vec!["main".into()], code.to_string()));
+        let error = process_code(&state).and_then(|ast| process_ast(vec![ast.into()])).err().unwrap();
+        super::convert(&error, &Url::parse("file:///main.tim").unwrap(), &["main".to_string()], &LineIndex::new(code))
+    }
+
+    #[test]
+    fn type_error() {
+        let diagnostics = diagnostics("func test(): i32 {\n    return true;\n}");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range.start, Position::new(1, 11));
+        assert_eq!(diagnostics[0].source.as_deref(), Some("timu"));
+        assert!(diagnostics[0].code.is_some());
+        assert!(diagnostics[0].related_information.as_ref().is_some_and(|information| !information.is_empty()));
+    }
+
+    #[test]
+    fn syntax_error() {
+        let diagnostics = diagnostics("func test(: i32 {}");
+        assert!(!diagnostics.is_empty());
+        assert_eq!(diagnostics[0].range.start.line, 0);
+    }
+
+    #[test]
+    fn other_modules_are_skipped() {
+        let code = "func test(): i32 { return true; }";
+        let state = State::new(SourceFile::new(vec!["main".into()], code.to_string()));
+        let error = process_code(&state).and_then(|ast| process_ast(vec![ast.into()])).err().unwrap();
+        assert!(super::convert(&error, &Url::parse("file:///lib.tim").unwrap(), &["lib".to_string()], &LineIndex::new(code)).is_empty());
+    }
+}
@@ -0,0 +1,27 @@
+//! Language server of the Timu language.
+//!
+//! The server talks the Language Server Protocol over stdin and stdout and
+//! offers:
+//!
+//! - Diagnostics of every open document, published after each change
+//! - Hover with the resolved type of identifiers, calls and declarations
+//! - Go to definition
+//! - Document symbols
+//!
+//! Documents are checked with their unsaved text, imported modules are taken
+//! from the other open documents or loaded from disk, see [`workspace`].
+
+mod diagnostics;
+mod position;
+mod server;
+mod symbols;
+mod workspace;
+
+use lsp_server::Connection;
+
+fn main() -> server::ServerResult<()> {
+    let (connection, io_threads) = Connection::stdio();
+    server::run(connection)?;
+    io_threads.join()?;
+    Ok(())
+}
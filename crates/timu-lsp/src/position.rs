@@ -0,0 +1,78 @@
+//! Conversion between byte offsets and editor positions.
+//!
+//! The compiler reports byte ranges, the protocol addresses text by line and
+//! UTF-16 code unit, so every range crossing the boundary goes through a
+//! [`LineIndex`] of the document it belongs to.
+
+use lsp_types::{Position, Range};
+
+/// Start offsets of the lines of a document
+pub struct LineIndex<'a> {
+    text: &'a str,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0).chain(text.match_indices('\n').map(|(index, _)| index + 1)).collect();
+        Self { text, line_starts }
+    }
+
+    /// Position of a byte offset, offsets past the end are clamped to the end of the document
+    pub fn position(&self, offset: usize) -> Position {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        let character = self.text[self.line_starts[line]..offset].encode_utf16().count();
+        Position::new(line as u32, character as u32)
+    }
+
+    /// Byte offset of a position, positions past the end of a line are clamped to the end of the line
+    pub fn offset(&self, position: Position) -> usize {
+        let Some(start) = self.line_starts.get(position.line as usize).copied() else {
+            return self.text.len();
+        };
+
+        let end = self.line_starts.get(position.line as usize + 1).map(|next| next - 1).unwrap_or(self.text.len());
+        let mut units = 0;
+        for (index, character) in self.text[start..end].char_indices() {
+            if units >= position.character as usize {
+                return start + index;
+            }
+            units += character.len_utf16();
+        }
+
+        end
+    }
+
+    pub fn range(&self, range: std::ops::Range<usize>) -> Range {
+        Range::new(self.position(range.start), self.position(range.end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lsp_types::Position;
+
+    use super::LineIndex;
+
+    #[test]
+    fn positions() {
+        let index = LineIndex::new("func a() {\n    var ğ = \"𝄞\";\n}");
+        assert_eq!(index.position(0), Position::new(0, 0));
+        assert_eq!(index.position(11), Position::new(1, 0));
+        assert_eq!(index.position(19), Position::new(1, 8));
+        assert_eq!(index.position(27), Position::new(1, 13));
+        assert_eq!(index.position(1000), Position::new(2, 1));
+
+        for offset in [0, 5, 11, 19, 23, 29, 32] {
+            assert_eq!(index.offset(index.position(offset)), offset);
+        }
+
+        assert_eq!(index.offset(Position::new(0, 100)), 10);
+        assert_eq!(index.offset(Position::new(10, 0)), 33);
+    }
+}
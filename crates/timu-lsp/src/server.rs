@@ -0,0 +1,115 @@
+//! Message loop of the language server.
+//!
+//! Requests are answered in the order they arrive, one at a time. After every
+//! change to the open documents all of them are checked again and their
+//! diagnostics are published, since an edit in one module can break the
+//! modules importing it.
+
+use std::error::Error;
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, Notification as _, PublishDiagnostics},
+    request::{DocumentSymbolRequest, GotoDefinition, HoverRequest, Request as _},
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams, DocumentSymbolParams, DocumentSymbolResponse, GotoDefinitionParams, GotoDefinitionResponse, HoverParams, HoverProviderCapability, OneOf, PublishDiagnosticsParams, ServerCapabilities, TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+use serde::de::DeserializeOwned;
+
+use crate::workspace::Workspace;
+
+pub type ServerResult<T> = Result<T, Box<dyn Error + Sync + Send>>;
+
+/// Features announced to the client during initialisation
+pub fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        document_symbol_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    }
+}
+
+/// Runs the server until the client asks it to shut down
+pub fn run(connection: Connection) -> ServerResult<()> {
+    connection.initialize(serde_json::to_value(capabilities())?)?;
+    let mut workspace = Workspace::default();
+
+    for message in &connection.receiver {
+        match message {
+            Message::Request(request) => {
+                if connection.handle_shutdown(&request)? {
+                    return Ok(());
+                }
+                connection.sender.send(handle_request(&workspace, request).into())?;
+            },
+            Message::Notification(notification) => {
+                for notification in handle_notification(&mut workspace, notification)? {
+                    connection.sender.send(notification.into())?;
+                }
+            },
+            Message::Response(_) => (),
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_request(workspace: &Workspace, request: Request) -> Response {
+    let result = match request.method.as_str() {
+        HoverRequest::METHOD => params::<HoverParams>(request.params)
+            .map(|params| serde_json::to_value(workspace.hover(&params.text_document_position_params.text_document.uri, params.text_document_position_params.position))),
+        GotoDefinition::METHOD => params::<GotoDefinitionParams>(request.params)
+            .map(|params| serde_json::to_value(workspace.definition(&params.text_document_position_params.text_document.uri, params.text_document_position_params.position).map(GotoDefinitionResponse::Scalar))),
+        DocumentSymbolRequest::METHOD => params::<DocumentSymbolParams>(request.params)
+            .map(|params| serde_json::to_value(workspace.symbols(&params.text_document.uri).map(DocumentSymbolResponse::Nested))),
+        method => return Response::new_err(request.id, ErrorCode::MethodNotFound as i32, format!("unknown request `{method}`")),
+    };
+
+    match result {
+        Ok(Ok(value)) => Response { id: request.id, result: Some(value), error: None },
+        Ok(Err(error)) => Response::new_err(request.id, ErrorCode::InternalError as i32, error.to_string()),
+        Err(error) => Response::new_err(request.id, ErrorCode::InvalidParams as i32, error.to_string()),
+    }
+}
+
+/// Applies a document change and returns the diagnostics to publish
+fn handle_notification(workspace: &mut Workspace, notification: Notification) -> ServerResult<Vec<Notification>> {
+    let mut closed = None;
+
+    match notification.method.as_str() {
+        DidOpenTextDocument::METHOD => {
+            let params = params::<DidOpenTextDocumentParams>(notification.params)?;
+            workspace.update(params.text_document.uri, params.text_document.text);
+        },
+        DidChangeTextDocument::METHOD => {
+            let mut params = params::<DidChangeTextDocumentParams>(notification.params)?;
+            if let Some(change) = params.content_changes.pop() {
+                workspace.update(params.text_document.uri, change.text);
+            }
+        },
+        DidCloseTextDocument::METHOD => {
+            let params = params::<DidCloseTextDocumentParams>(notification.params)?;
+            workspace.close(&params.text_document.uri);
+            closed = Some(params.text_document.uri);
+        },
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut notifications = closed.into_iter().map(|uri| publish(uri, Vec::new())).collect::<Vec<_>>();
+    let mut documents = workspace.documents().collect::<Vec<_>>();
+    documents.sort();
+    for uri in documents {
+        notifications.push(publish(uri.clone(), workspace.diagnostics(uri)));
+    }
+
+    Ok(notifications)
+}
+
+fn publish(uri: Url, diagnostics: Vec<lsp_types::Diagnostic>) -> Notification {
+    Notification::new(PublishDiagnostics::METHOD.to_string(), PublishDiagnosticsParams { uri, diagnostics, version: None })
+}
+
+fn params<P: DeserializeOwned>(params: serde_json::Value) -> Result<P, serde_json::Error> {
+    serde_json::from_value(params)
+}
@@ -0,0 +1,122 @@
+//! Outline of a document, built from its syntax tree.
+//!
+//! The outline only needs the declarations of a file, so it is available as
+//! soon as the document parses, even when it does not type check.
+
+use libtimu::{
+    ast::{ClassDefinitionFieldAst, ExtendDefinitionFieldAst, FieldAst, FileAst, FileStatementAst, FunctionDefinitionAst, InterfaceDefinitionFieldAst, TypeNameAst, VariableDefinitionType},
+    nom_tools::Span,
+};
+use lsp_types::{DocumentSymbol, SymbolKind};
+
+use crate::position::LineIndex;
+
+/// Symbols of the declarations of a file, in source order
+pub fn collect(file: &FileAst<'_>, index: &LineIndex<'_>) -> Vec<DocumentSymbol> {
+    file.statements.iter().filter_map(|statement| match statement {
+        FileStatementAst::Function(function) => Some(function_symbol(function, SymbolKind::FUNCTION, index)),
+        FileStatementAst::Class(class) => {
+            let children = class.fields.iter().map(|field| match field {
+                ClassDefinitionFieldAst::Field(field) => field_symbol(field, index),
+                ClassDefinitionFieldAst::Function(function) => function_symbol(function, SymbolKind::METHOD, index),
+            });
+            Some(symbol(&class.name, class.name.text.to_string(), SymbolKind::CLASS, class.base_class.as_ref().map(type_name), children.collect(), index))
+        },
+        FileStatementAst::Interface(interface) => {
+            let children = interface.fields.iter().map(|field| match field {
+                InterfaceDefinitionFieldAst::Field(field) => field_symbol(field, index),
+                InterfaceDefinitionFieldAst::Function(function) => symbol(&function.name, function.name.text.to_string(), SymbolKind::METHOD, Some(type_name(&function.return_type)), Vec::new(), index),
+            });
+            Some(symbol(&interface.name, interface.name.text.to_string(), SymbolKind::INTERFACE, None, children.collect(), index))
+        },
+        FileStatementAst::Extend(extend) => {
+            let children = extend.fields.iter().map(|field| match field {
+                ExtendDefinitionFieldAst::Field(field) => field_symbol(field, index),
+                ExtendDefinitionFieldAst::Function(function) => function_symbol(function, SymbolKind::METHOD, index),
+            });
+            Some(symbol(&extend.name.names_span, format!("extend {}", type_name(&extend.name)), SymbolKind::OBJECT, None, children.collect(), index))
+        },
+        FileStatementAst::TypeAlias(alias) => Some(symbol(&alias.name, alias.name.text.to_string(), SymbolKind::TYPE_PARAMETER, Some(type_name(&alias.target)), Vec::new(), index)),
+        FileStatementAst::Enum(definition) => {
+            let children = definition.variants.iter().map(|variant| {
+                let payload = (!variant.payload.is_empty()).then(|| format!("({})", variant.payload.iter().map(type_name).collect::<Vec<_>>().join(", ")));
+                symbol(&variant.name, variant.name.text.to_string(), SymbolKind::ENUM_MEMBER, payload, Vec::new(), index)
+            });
+            Some(symbol(&definition.name, definition.name.text.to_string(), SymbolKind::ENUM, None, children.collect(), index))
+        },
+        FileStatementAst::Variable(variable) => {
+            let definition = &variable.definition;
+            let kind = match definition.variable_definition_type {
+                VariableDefinitionType::Const => SymbolKind::CONSTANT,
+                VariableDefinitionType::Var => SymbolKind::VARIABLE,
+            };
+            Some(symbol(&definition.name, definition.name.text.to_string(), kind, definition.expected_type.as_ref().map(type_name), Vec::new(), index))
+        },
+        FileStatementAst::Use(_) => None,
+    }).collect()
+}
+
+fn function_symbol(function: &FunctionDefinitionAst<'_>, kind: SymbolKind, index: &LineIndex<'_>) -> DocumentSymbol {
+    symbol(&function.name, function.name.text.to_string(), kind, Some(type_name(&function.return_type)), Vec::new(), index)
+}
+
+fn field_symbol(field: &FieldAst<'_>, index: &LineIndex<'_>) -> DocumentSymbol {
+    symbol(&field.name, field.name.text.to_string(), SymbolKind::FIELD, Some(type_name(&field.field_type)), Vec::new(), index)
+}
+
+fn type_name(type_name: &TypeNameAst<'_>) -> String {
+    match (&type_name.function, &type_name.tuple) {
+        (Some(function), _) => format!("func({}): {}", function.arguments.iter().map(self::type_name).collect::<Vec<_>>().join(", "), self::type_name(&function.return_type)),
+        (None, Some(elements)) => format!("({})", elements.iter().map(self::type_name).collect::<Vec<_>>().join(", ")),
+        (None, None) => type_name.names.iter().map(|name| name.text).collect::<Vec<_>>().join("."),
+    }
+}
+
+#[allow(deprecated)]
+fn symbol(name_span: &Span<'_>, name: String, kind: SymbolKind, detail: Option<String>, children: Vec<DocumentSymbol>, index: &LineIndex<'_>) -> DocumentSymbol {
+    let range = index.range(name_span.position.clone());
+    DocumentSymbol {
+        name,
+        detail,
+        kind,
+        tags: None,
+        deprecated: None,
+        range,
+        selection_range: range,
+        children: (!children.is_empty()).then_some(children),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libtimu::{file::SourceFile, nom_tools::State, process_code};
+    use lsp_types::{Position, SymbolKind};
+
+    use crate::position::LineIndex;
+
+    #[test]
+    fn outline() {
+        let code = "use std.io.println;\nclass Point { x: i32; func len(this): i32 { return 0; } }\ninterface Shape { func area(): i32; }\nextend Point: Shape { func area(this): i32 { return 0; } }\nfunc main(): i32 { return 0; }\nconst LIMIT: i32 = 1;\nenum Result { Ok(i32), Empty }";
+        let state = State::new(SourceFile::new(vec!["main".into()], code.to_string()));
+        let ast = process_code(&state).unwrap();
+        let symbols = super::collect(&ast, &LineIndex::new(code));
+
+        let names = symbols.iter().map(|symbol| (symbol.name.as_str(), symbol.kind)).collect::<Vec<_>>();
+        assert_eq!(names, vec![("Point", SymbolKind::CLASS), ("Shape", SymbolKind::INTERFACE), ("extend Point", SymbolKind::OBJECT), ("main", SymbolKind::FUNCTION), ("LIMIT", SymbolKind::CONSTANT), ("Result", SymbolKind::ENUM)]);
+        assert_eq!(symbols[0].selection_range.start, Position::new(1, 6));
+
+        let children = symbols[0].children.as_ref().unwrap();
+        assert_eq!(children.iter().map(|symbol| (symbol.name.as_str(), symbol.kind, symbol.detail.as_deref())).collect::<Vec<_>>(), vec![
+            ("x", SymbolKind::FIELD, Some("i32")),
+            ("len", SymbolKind::METHOD, Some("i32")),
+        ]);
+        assert_eq!(symbols[1].children.as_ref().unwrap()[0].name, "area");
+        assert_eq!(symbols[2].children.as_ref().unwrap()[0].name, "area");
+
+        let variants = symbols[5].children.as_ref().unwrap();
+        assert_eq!(variants.iter().map(|symbol| (symbol.name.as_str(), symbol.kind, symbol.detail.as_deref())).collect::<Vec<_>>(), vec![
+            ("Ok", SymbolKind::ENUM_MEMBER, Some("(i32)")),
+            ("Empty", SymbolKind::ENUM_MEMBER, None),
+        ]);
+    }
+}
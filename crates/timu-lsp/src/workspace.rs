@@ -0,0 +1,171 @@
+//! Open documents and the analyses run on them.
+//!
+//! Every document is analysed on its own: it is parsed and type checked with a
+//! provider that loads its imports on demand, so the analysis always sees the
+//! latest text of every open document and the files on disk for the rest.
+//!
+//! A document inside a project with a `timu.toml` manifest gets the module path
+//! of its file below the source root, and imports are searched below the
+//! source root. Any other document is a module named after its file, and
+//! imports are searched next to it.
+//!
+//! Half-typed code is the normal case in an editor. A panic of the compiler
+//! while analysing a document is caught, the document gets no results and the
+//! server keeps running.
+
+use std::{collections::HashMap, panic::{catch_unwind, AssertUnwindSafe}, path::{Path, PathBuf}, rc::Rc};
+
+use libtimu::{
+    error::TIMU_LANG_EXT,
+    file::SourceFile,
+    nom_tools::State,
+    process_ast_with_loader,
+    process_code,
+    project::{module_path, Project},
+    provider::{FileSystemProvider, LayeredProvider, MemoryProvider, ModuleLoader, SourceArena},
+    tir::{query, TirContext, TirError},
+};
+use lsp_types::{Diagnostic, DocumentSymbol, Hover, HoverContents, Location, MarkupContent, MarkupKind, Position, Url};
+
+use crate::{diagnostics, position::LineIndex, symbols};
+
+/// Module path of a document and the directory its imports are searched in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleLocation {
+    /// Source root of the project, or the directory of a file outside of a project
+    pub root: Option<PathBuf>,
+    pub module: Vec<String>,
+}
+
+impl ModuleLocation {
+    /// Locates the module of a document, documents that are not files are a module named `main`
+    pub fn of(uri: &Url) -> Self {
+        let Ok(path) = uri.to_file_path() else {
+            return Self { root: None, module: vec!["main".to_string()] };
+        };
+
+        if let Some(directory) = path.parent()
+            && let Ok(project) = Project::find(directory) {
+            let source_root = project.root.join(&project.manifest.project.source_root);
+            if let Some(module) = module_path(&source_root, &path) {
+                return Self { root: Some(source_root), module };
+            }
+        }
+
+        let name = path.file_stem().map(|stem| stem.to_string_lossy().to_string()).unwrap_or_else(|| "main".to_string());
+        Self { root: path.parent().map(Path::to_path_buf), module: vec![name] }
+    }
+}
+
+/// The documents opened by the client, with their unsaved text
+#[derive(Debug, Default)]
+pub struct Workspace {
+    documents: HashMap<Url, String>,
+}
+
+impl Workspace {
+    /// Opens a document or replaces the text of an open one
+    pub fn update(&mut self, uri: Url, text: String) {
+        self.documents.insert(uri, text);
+    }
+
+    pub fn close(&mut self, uri: &Url) {
+        self.documents.remove(uri);
+    }
+
+    pub fn documents(&self) -> impl Iterator<Item = &Url> {
+        self.documents.keys()
+    }
+
+    /// Type errors of an open document, errors of imported modules are reported on their own documents
+    pub fn diagnostics(&self, uri: &Url) -> Vec<Diagnostic> {
+        let Some(text) = self.documents.get(uri) else {
+            return Vec::new();
+        };
+
+        match self.analyze(uri, |_, _| ()) {
+            Some(Err(error)) => diagnostics::convert(&error, uri, &ModuleLocation::of(uri).module, &LineIndex::new(text)),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Type of the item under the cursor
+    pub fn hover(&self, uri: &Url, position: Position) -> Option<Hover> {
+        let index = LineIndex::new(self.documents.get(uri)?);
+        let hover = self.analyze(uri, |context, location| query::hover(context, &location.module, index.offset(position)))?.ok()??;
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent { kind: MarkupKind::Markdown, value: format!("```timu\n{}\n```", hover.text) }),
+            range: Some(index.range(hover.position)),
+        })
+    }
+
+    /// Declaration of the item under the cursor
+    pub fn definition(&self, uri: &Url, position: Position) -> Option<Location> {
+        let offset = LineIndex::new(self.documents.get(uri)?).offset(position);
+        let (location, definition) = self.analyze(uri, |context, location| (location.clone(), query::definition(context, &location.module, offset)))?.ok()?;
+        let definition = definition?;
+
+        let (target, text) = self.find_module(&location, &definition.path)?;
+        Some(Location::new(target, LineIndex::new(&text).range(definition.position)))
+    }
+
+    /// Outline of an open document, `None` when it does not parse
+    pub fn symbols(&self, uri: &Url) -> Option<Vec<DocumentSymbol>> {
+        let text = self.documents.get(uri)?;
+        isolated(|| {
+            let state = State::new(SourceFile::new(ModuleLocation::of(uri).module, text.clone()));
+            let ast = process_code(&state).ok()?;
+            Some(symbols::collect(&ast, &LineIndex::new(text)))
+        }).flatten()
+    }
+
+    /// Parses and type checks an open document and passes the result to `analysis`
+    ///
+    /// Imports are loaded from the other open documents of the same root first
+    /// and from the files below the root after that. `None` when the document
+    /// is not open or the analysis panicked.
+    fn analyze<R>(&self, uri: &Url, analysis: impl FnOnce(&TirContext<'_>, &ModuleLocation) -> R) -> Option<Result<R, TirError>> {
+        let text = self.documents.get(uri)?;
+        let location = ModuleLocation::of(uri);
+
+        let mut memory = MemoryProvider::default();
+        for (other_uri, other_text) in self.documents.iter().filter(|(other_uri, _)| *other_uri != uri) {
+            let other = ModuleLocation::of(other_uri);
+            if other.root == location.root {
+                memory.insert(other.module.join("."), other_text.clone());
+            }
+        }
+
+        let file_system = FileSystemProvider::new(location.root.iter().cloned().collect());
+        let provider = LayeredProvider::new(vec![&memory, &file_system]);
+        let arena = SourceArena::default();
+        let state = State::new(SourceFile::new(location.module.clone(), text.clone()));
+
+        isolated(|| process_code(&state)
+            .and_then(|ast| process_ast_with_loader(vec![Rc::new(ast)], ModuleLoader::new(&provider, &arena)))
+            .map(|context| analysis(&context, &location)))
+    }
+
+    /// Document and text of a module next to the given one, open documents win over files
+    fn find_module(&self, location: &ModuleLocation, module: &[String]) -> Option<(Url, String)> {
+        if let Some((uri, text)) = self.documents.iter().find(|(uri, _)| {
+            let other = ModuleLocation::of(uri);
+            other.root == location.root && other.module == module
+        }) {
+            return Some((uri.clone(), text.clone()));
+        }
+
+        let path = location.root.as_ref()?.join(PathBuf::from_iter(module)).with_extension(TIMU_LANG_EXT);
+        let text = std::fs::read_to_string(&path).ok()?;
+        Some((Url::from_file_path(path).ok()?, text))
+    }
+}
+
+/// Runs an analysis of a document, `None` when the compiler panics
+///
+/// Every analysis builds its own context from the text of the documents, so
+/// nothing of a failed analysis is used again.
+fn isolated<R>(analysis: impl FnOnce() -> R) -> Option<R> {
+    catch_unwind(AssertUnwindSafe(analysis)).ok()
+}
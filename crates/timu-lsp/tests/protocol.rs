@@ -0,0 +1,184 @@
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    path::PathBuf,
+    process::{Child, ChildStdin, ChildStdout, Command, Stdio},
+};
+
+use serde_json::{json, Value};
+
+/// Talks to the server binary the way an editor does, over stdin and stdout
+struct Client {
+    process: Child,
+    input: ChildStdin,
+    output: BufReader<ChildStdout>,
+    next_id: i64,
+}
+
+impl Client {
+    fn start() -> Self {
+        let mut process = Command::new(env!("CARGO_BIN_EXE_timu-lsp"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let input = process.stdin.take().unwrap();
+        let output = BufReader::new(process.stdout.take().unwrap());
+        Self { process, input, output, next_id: 1 }
+    }
+
+    fn send(&mut self, message: Value) {
+        let content = message.to_string();
+        write!(self.input, "Content-Length: {}\r\n\r\n{content}", content.len()).unwrap();
+        self.input.flush().unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut length = 0;
+        loop {
+            let mut header = String::new();
+            self.output.read_line(&mut header).unwrap();
+            let header = header.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some(value) = header.strip_prefix("Content-Length: ") {
+                length = value.parse().unwrap();
+            }
+        }
+
+        let mut content = vec![0; length];
+        self.output.read_exact(&mut content).unwrap();
+        serde_json::from_slice(&content).unwrap()
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.send(json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params }));
+
+        loop {
+            let message = self.receive();
+            if message["id"] == id {
+                return message;
+            }
+        }
+    }
+
+    fn notify(&mut self, method: &str, params: Value) {
+        self.send(json!({ "jsonrpc": "2.0", "method": method, "params": params }));
+    }
+
+    /// Waits for the diagnostics of the given document
+    fn diagnostics(&mut self, uri: &str) -> Vec<Value> {
+        loop {
+            let message = self.receive();
+            if message["method"] == "textDocument/publishDiagnostics" && message["params"]["uri"] == uri {
+                return message["params"]["diagnostics"].as_array().unwrap().clone();
+            }
+        }
+    }
+}
+
+fn workspace(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("timu-lsp-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(&root).unwrap();
+    std::fs::write(root.join("lib.tim"), "pub func one(): i32 {\n    return 1;\n}").unwrap();
+    root
+}
+
+#[test]
+fn session() {
+    let root = workspace("session");
+    let uri = format!("file://{}", root.join("main.tim").display());
+    let mut client = Client::start();
+
+    let response = client.request("initialize", json!({ "capabilities": {} }));
+    let capabilities = &response["result"]["capabilities"];
+    assert_eq!(capabilities["hoverProvider"], true);
+    assert_eq!(capabilities["definitionProvider"], true);
+    assert_eq!(capabilities["documentSymbolProvider"], true);
+    client.notify("initialized", json!({}));
+
+    // The document is never saved, the server works on the text sent by the client
+    let text = "use lib.one;\nfunc main(): i32 {\n    var value = one();\n    return value;\n}";
+    client.notify("textDocument/didOpen", json!({ "textDocument": { "uri": uri, "languageId": "timu", "version": 1, "text": text } }));
+    assert_eq!(client.diagnostics(&uri), Vec::<Value>::new());
+
+    let position = json!({ "textDocument": { "uri": uri }, "position": { "line": 3, "character": 13 } });
+    let response = client.request("textDocument/hover", position.clone());
+    assert_eq!(response["result"]["contents"]["value"], "```timu\nvalue: i32\n```");
+    assert_eq!(response["result"]["range"], json!({ "start": { "line": 3, "character": 11 }, "end": { "line": 3, "character": 16 } }));
+
+    let response = client.request("textDocument/definition", position);
+    assert_eq!(response["result"]["range"]["start"], json!({ "line": 2, "character": 8 }));
+
+    let position = json!({ "textDocument": { "uri": uri }, "position": { "line": 2, "character": 17 } });
+    let response = client.request("textDocument/hover", position.clone());
+    assert_eq!(response["result"]["contents"]["value"], "```timu\npub func one(): i32\n```");
+    let response = client.request("textDocument/definition", position);
+    assert!(response["result"]["uri"].as_str().unwrap().ends_with("/lib.tim"), "{response}");
+    assert_eq!(response["result"]["range"]["start"], json!({ "line": 0, "character": 9 }));
+
+    let response = client.request("textDocument/documentSymbol", json!({ "textDocument": { "uri": uri } }));
+    assert_eq!(response["result"][0]["name"], "main");
+    assert_eq!(response["result"][0]["kind"], 12);
+
+    client.notify("textDocument/didChange", json!({
+        "textDocument": { "uri": uri, "version": 2 },
+        "contentChanges": [{ "text": "func main(): i32 {\n    return true;\n}" }],
+    }));
+    let diagnostics = client.diagnostics(&uri);
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(diagnostics[0]["range"]["start"], json!({ "line": 1, "character": 11 }));
+    assert_eq!(diagnostics[0]["severity"], 1);
+
+    let response = client.request("textDocument/hover", json!({ "textDocument": { "uri": uri }, "position": { "line": 1, "character": 4 } }));
+    assert_eq!(response["result"], Value::Null);
+
+    let response = client.request("timu/unknown", json!({}));
+    assert_eq!(response["error"]["code"], -32601);
+
+    client.notify("textDocument/didClose", json!({ "textDocument": { "uri": uri } }));
+    assert_eq!(client.diagnostics(&uri), Vec::<Value>::new());
+
+    let response = client.request("shutdown", Value::Null);
+    assert_eq!(response["result"], Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(client.process.wait().unwrap().success());
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn incomplete_code() {
+    let root = workspace("incomplete-code");
+    let uri = format!("file://{}", root.join("draft.tim").display());
+    let mut client = Client::start();
+    client.request("initialize", json!({ "capabilities": {} }));
+    client.notify("initialized", json!({}));
+
+    // Calls of names that are not defined yet are reported, the server keeps running
+    let text = "pub func f(): i32 {\n    return undefined_fn();\n}";
+    client.notify("textDocument/didOpen", json!({ "textDocument": { "uri": uri, "languageId": "timu", "version": 1, "text": text } }));
+    let diagnostics = client.diagnostics(&uri);
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(diagnostics[0]["range"]["start"], json!({ "line": 1, "character": 11 }));
+
+    client.notify("textDocument/didChange", json!({
+        "textDocument": { "uri": uri, "version": 2 },
+        "contentChanges": [{ "text": "class Point {}\npub func f(): void {\n    Point();\n}" }],
+    }));
+    let diagnostics = client.diagnostics(&uri);
+    assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+    assert_eq!(diagnostics[0]["range"]["start"], json!({ "line": 2, "character": 4 }));
+
+    let response = client.request("textDocument/documentSymbol", json!({ "textDocument": { "uri": uri } }));
+    assert_eq!(response["result"][1]["name"], "f");
+
+    let response = client.request("shutdown", Value::Null);
+    assert_eq!(response["result"], Value::Null);
+    client.notify("exit", Value::Null);
+    assert!(client.process.wait().unwrap().success());
+    std::fs::remove_dir_all(root).unwrap();
+}
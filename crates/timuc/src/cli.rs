@@ -0,0 +1,395 @@
+//! Command line interface of the compiler.
+//!
+//! The arguments are declared with `clap` and converted into the option types
+//! of the library and the logger, so the rest of the executable never looks at
+//! raw arguments.
+
+use std::{collections::BTreeMap, io::IsTerminal, path::PathBuf};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use libtimu::{error::ReportOptions, lint::{Lint, LintLevel, LintLevels}, optimizer::{OptimizationLevel, OptimizerOptions}};
+use log::LevelFilter;
+use simplelog::ColorChoice;
+
+/// Timu language compiler
+#[derive(Debug, Parser)]
+#[command(name = "timuc", version, about)]
+pub struct Cli {
+    #[command(flatten)]
+    pub global: GlobalOptions,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// Options shared by every subcommand
+#[derive(Debug, Args)]
+pub struct GlobalOptions {
+    /// When to colour diagnostics and log messages
+    #[arg(long, value_enum, default_value_t = Color::Auto, global = true)]
+    pub color: Color,
+
+    /// How errors are reported
+    #[arg(long, value_enum, default_value_t = ErrorFormat::Human, global = true)]
+    pub error_format: ErrorFormat,
+
+    /// Stop reporting after this many errors
+    #[arg(long, value_name = "N", global = true)]
+    pub error_limit: Option<usize>,
+
+    /// Silence a lint, like `unused_variables`
+    #[arg(short = 'A', long = "allow", value_name = "LINT", global = true)]
+    pub allow: Vec<Lint>,
+
+    /// Report a lint as a warning
+    #[arg(short = 'W', long = "warn", value_name = "LINT", global = true)]
+    pub warn: Vec<Lint>,
+
+    /// Report a lint as an error
+    #[arg(short = 'D', long = "deny", value_name = "LINT", global = true)]
+    pub deny: Vec<Lint>,
+
+    /// Directory the build outputs are written to
+    #[arg(long, value_name = "DIR", default_value = ".", global = true)]
+    pub out_dir: PathBuf,
+
+    /// Print more log messages, repeat for more detail
+    #[arg(short, long, action = clap::ArgAction::Count, global = true)]
+    pub verbose: u8,
+
+    /// Print no log messages at all
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+}
+
+impl GlobalOptions {
+    /// Log level selected with `-q` and `-v`, errors only by default
+    pub fn log_level(&self) -> LevelFilter {
+        match (self.quiet, self.verbose) {
+            (true, _) => LevelFilter::Off,
+            (false, 0) => LevelFilter::Error,
+            (false, 1) => LevelFilter::Warn,
+            (false, 2) => LevelFilter::Info,
+            (false, 3) => LevelFilter::Debug,
+            (false, _) => LevelFilter::Trace,
+        }
+    }
+
+    /// Colouring of output written to a stream, `auto` colours terminals only
+    pub fn color_choice(&self, is_terminal: bool) -> ColorChoice {
+        match self.color {
+            Color::Auto if is_terminal => ColorChoice::Auto,
+            Color::Auto => ColorChoice::Never,
+            Color::Always => ColorChoice::Always,
+            Color::Never => ColorChoice::Never,
+        }
+    }
+
+    /// Colouring of the log messages, they are written to stdout and stderr
+    pub fn log_color_choice(&self) -> ColorChoice {
+        self.color_choice(std::io::stdout().is_terminal() && std::io::stderr().is_terminal())
+    }
+
+    /// Levels of the lints, the command line overrides the levels of the manifest
+    ///
+    /// A lint given to more than one option is denied over warned over allowed.
+    pub fn lint_levels(&self, manifest: &BTreeMap<Lint, LintLevel>) -> LintLevels {
+        let mut levels = LintLevels::from(manifest.clone());
+        for (lints, level) in [(&self.allow, LintLevel::Allow), (&self.warn, LintLevel::Warn), (&self.deny, LintLevel::Deny)] {
+            for lint in lints.iter() {
+                levels.set(*lint, level);
+            }
+        }
+
+        levels
+    }
+
+    pub fn report_options(&self) -> ReportOptions {
+        ReportOptions {
+            color: self.color_choice(std::io::stderr().is_terminal()),
+            error_limit: self.error_limit,
+        }
+    }
+}
+
+/// Formats of the error reports
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ErrorFormat {
+    /// Source code with the errors highlighted, for people
+    Human,
+    /// One JSON object per error, for tools
+    Json,
+    /// A SARIF 2.1.0 log, for code-scanning dashboards
+    Sarif,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Color {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Type-check the sources without generating code
+    Check {
+        #[command(flatten)]
+        sources: Sources,
+    },
+
+    /// Compile the sources and write the output to the output directory
+    Build {
+        /// Target to generate
+        #[arg(long, value_enum, default_value_t = Emit::Wat)]
+        emit: Emit,
+
+        #[command(flatten)]
+        optimization: Optimization,
+
+        #[command(flatten)]
+        sources: Sources,
+    },
+
+    /// Compile the sources and run the program
+    Run {
+        /// Public function the program starts from, `main` of the entry module by default
+        #[arg(long, value_name = "FUNCTION")]
+        entry: Option<String>,
+
+        #[command(flatten)]
+        optimization: Optimization,
+
+        #[command(flatten)]
+        sources: Sources,
+    },
+
+    /// Rewrite the sources in the canonical style
+    Fmt {
+        /// Report the files that are not formatted instead of rewriting them
+        #[arg(long)]
+        check: bool,
+
+        #[command(flatten)]
+        sources: Sources,
+    },
+
+    /// Apply the suggested fixes of the errors to the sources
+    Fix {
+        #[command(flatten)]
+        sources: Sources,
+    },
+
+    /// Print an intermediate representation of the sources
+    Dump {
+        /// Representation to print
+        #[arg(value_enum)]
+        representation: Representation,
+
+        #[command(flatten)]
+        sources: Sources,
+    },
+
+    /// Print the explanation of an error code
+    Explain {
+        /// Error code shown in the diagnostic, like `T0001`
+        code: String,
+    },
+}
+
+/// Targets of the `build` subcommand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Emit {
+    /// WebAssembly text format
+    Wat,
+}
+
+impl Emit {
+    /// File extension of the generated file
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Emit::Wat => "wat",
+        }
+    }
+}
+
+/// Representations printed by the `dump` subcommand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Representation {
+    /// Parsed syntax tree, with every expression fully parenthesised
+    Ast,
+    /// Resolved types of every module
+    Tir,
+}
+
+/// Sources to compile, a list of files or a project with a `timu.toml` manifest
+///
+/// Without files, the project of the closest manifest in the current
+/// directory or one of its parents is compiled.
+#[derive(Debug, Default, Args)]
+pub struct Sources {
+    /// Source files, the file name without extension is the module name
+    #[arg(value_name = "FILE")]
+    pub files: Vec<PathBuf>,
+
+    /// Directory of the project to compile
+    #[arg(long, value_name = "DIR", conflicts_with = "files")]
+    pub project: Option<PathBuf>,
+
+    /// Directory imported modules are loaded from when they are not among the sources, can be repeated
+    #[arg(long = "search-path", value_name = "DIR")]
+    pub search_paths: Vec<PathBuf>,
+
+    /// Directory the modules below `std` are loaded from
+    #[arg(long, value_name = "DIR")]
+    pub std_root: Option<PathBuf>,
+}
+
+/// Optimisation options of the subcommands that generate code
+///
+/// Pass toggles are applied on top of the selected level, so
+/// `-O1 -fno-inline` runs every pass except inlining.
+#[derive(Debug, Args)]
+pub struct Optimization {
+    /// Optimisation level, `0` disables every pass and `1` enables all of them
+    #[arg(short = 'O', value_name = "LEVEL", default_value_t = 0, value_parser = clap::value_parser!(u8).range(0..=1))]
+    pub level: u8,
+
+    /// Switch a single pass on or off
+    #[arg(short = 'f', value_name = "PASS", value_parser = ["inline", "no-inline", "constant-folding", "no-constant-folding", "dead-code-elimination", "no-dead-code-elimination"])]
+    pub passes: Vec<String>,
+}
+
+impl Optimization {
+    pub fn options(&self) -> OptimizerOptions {
+        let level = match self.level {
+            0 => OptimizationLevel::O0,
+            _ => OptimizationLevel::O1,
+        };
+
+        let mut options = OptimizerOptions::from(level);
+        for pass in self.passes.iter() {
+            let (name, enabled) = match pass.strip_prefix("no-") {
+                Some(name) => (name, false),
+                None => (pass.as_str(), true),
+            };
+
+            match name {
+                "inline" => options.inlining = enabled,
+                "constant-folding" => options.constant_folding = enabled,
+                _ => options.dead_code_elimination = enabled,
+            };
+        }
+
+        options
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, path::PathBuf};
+
+    use clap::{CommandFactory, Parser};
+    use libtimu::lint::{Lint, LintLevel};
+    use log::LevelFilter;
+    use simplelog::ColorChoice;
+
+    use super::{Cli, Command, ErrorFormat, Representation};
+
+    fn parse(arguments: &str) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("timuc").chain(arguments.split_whitespace()))
+    }
+
+    #[test]
+    fn definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn optimization_options() {
+        let cli = parse("build -O1 -fno-inline main.tim").unwrap();
+        let Command::Build { optimization, sources, .. } = cli.command else { panic!("{:?}", cli.command) };
+        let options = optimization.options();
+        assert!(!options.inlining && options.constant_folding && options.dead_code_elimination);
+        assert_eq!(sources.files.len(), 1);
+
+        let cli = parse("run -fconstant-folding main.tim").unwrap();
+        let Command::Run { optimization, entry, .. } = cli.command else { panic!("{:?}", cli.command) };
+        let options = optimization.options();
+        assert!(!options.inlining && options.constant_folding && !options.dead_code_elimination);
+        assert_eq!(entry, None);
+
+        assert!(parse("build -O2 main.tim").is_err());
+        assert!(parse("build -funroll main.tim").is_err());
+    }
+
+    #[test]
+    fn global_options() {
+        let cli = parse("check main.tim").unwrap();
+        assert_eq!(cli.global.log_level(), LevelFilter::Error);
+        assert_eq!(cli.global.error_limit, None);
+        assert_eq!(cli.global.error_format, ErrorFormat::Human);
+        assert_eq!(parse("--error-format json check main.tim").unwrap().global.error_format, ErrorFormat::Json);
+        assert_eq!(parse("check --error-format sarif main.tim").unwrap().global.error_format, ErrorFormat::Sarif);
+
+        let cli = parse("dump tir --error-limit 3 -vv --color never main.tim lib.tim").unwrap();
+        assert_eq!(cli.global.log_level(), LevelFilter::Info);
+        assert_eq!(cli.global.report_options().error_limit, Some(3));
+        let Command::Dump { representation, sources } = cli.command else { panic!("{:?}", cli.command) };
+        assert_eq!(representation, Representation::Tir);
+        assert_eq!(sources.files.len(), 2);
+
+        let global = parse("check main.tim").unwrap().global;
+        assert_eq!(global.color_choice(true), ColorChoice::Auto);
+        assert_eq!(global.color_choice(false), ColorChoice::Never);
+        assert_eq!(parse("--color always check main.tim").unwrap().global.color_choice(false), ColorChoice::Always);
+        assert_eq!(parse("--color never check main.tim").unwrap().global.color_choice(true), ColorChoice::Never);
+
+        assert_eq!(parse("-q fmt main.tim").unwrap().global.log_level(), LevelFilter::Off);
+        assert!(parse("-q -v fmt main.tim").is_err());
+
+        let Command::Fmt { check, .. } = parse("fmt --check main.tim").unwrap().command else { panic!() };
+        assert!(check);
+
+        let Command::Explain { code } = parse("explain T0001").unwrap().command else { panic!() };
+        assert_eq!(code, "T0001");
+        assert!(parse("explain").is_err());
+    }
+
+    #[test]
+    fn lint_levels() {
+        let levels = parse("check main.tim").unwrap().global.lint_levels(&BTreeMap::new());
+        assert_eq!(levels.get(Lint::UnusedImports), LintLevel::Warn);
+
+        let cli = parse("check -A unused_imports --deny unused_variables -W unused_functions main.tim").unwrap();
+        let manifest = BTreeMap::from([(Lint::UnusedFunctions, LintLevel::Deny), (Lint::UnusedVariables, LintLevel::Allow)]);
+        let levels = cli.global.lint_levels(&manifest);
+        assert_eq!(levels.get(Lint::UnusedImports), LintLevel::Allow);
+        assert_eq!(levels.get(Lint::UnusedFunctions), LintLevel::Warn);
+        assert_eq!(levels.get(Lint::UnusedVariables), LintLevel::Deny);
+
+        let levels = parse("check -A unused_imports -D unused_imports main.tim").unwrap().global.lint_levels(&manifest);
+        assert_eq!(levels.get(Lint::UnusedImports), LintLevel::Deny);
+        assert_eq!(levels.get(Lint::UnusedFunctions), LintLevel::Deny);
+
+        assert!(parse("check -D unused main.tim").is_err());
+    }
+
+    #[test]
+    fn sources() {
+        let cli = parse("check").unwrap();
+        let Command::Check { sources } = cli.command else { panic!("{:?}", cli.command) };
+        assert!(sources.files.is_empty() && sources.project.is_none());
+
+        let cli = parse("check --project app").unwrap();
+        let Command::Check { sources } = cli.command else { panic!("{:?}", cli.command) };
+        assert_eq!(sources.project.unwrap().to_str(), Some("app"));
+
+        assert!(parse("check --project app main.tim").is_err());
+
+        let cli = parse("build --search-path vendor --search-path ../shared --std-root std main.tim").unwrap();
+        let Command::Build { sources, .. } = cli.command else { panic!("{:?}", cli.command) };
+        assert_eq!(sources.search_paths, [PathBuf::from("vendor"), PathBuf::from("../shared")]);
+        assert_eq!(sources.std_root, Some(PathBuf::from("std")));
+    }
+}
@@ -0,0 +1,35 @@
+//! Text rendering of the syntax trees for `timuc dump ast`.
+//!
+//! The resolved types are rendered by [`libtimu::tir::dump`].
+
+use std::{fmt::Write, rc::Rc};
+
+use libtimu::ast::FileAst;
+
+/// Renders the syntax trees, one module after the other
+///
+/// Declarations are printed in their canonical form, which wraps every
+/// operation in parentheses and so shows how expressions were grouped.
+pub fn ast(files: &[Rc<FileAst<'_>>]) -> String {
+    let mut output = String::new();
+    for file in files.iter() {
+        let _ = writeln!(output, "module {}", file.file.path().join("."));
+        for statement in file.statements.iter() {
+            let _ = writeln!(output, "    {statement}");
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use libtimu::{file::SourceFile, nom_tools::State, process_code};
+
+    #[test]
+    fn ast() {
+        let state = State::new(SourceFile::new(vec!["sub".into(), "main".into()], "func test(a: i32): i32 { return a + 2 * a; } class Point { x: i32; }".to_string()));
+        let ast = process_code(&state).unwrap();
+        assert_eq!(super::ast(&[ast.into()]), "module sub.main\n    func test(a: i32): i32 {return (a + (2 * a));}\n    class Point {x: i32;}\n");
+    }
+}
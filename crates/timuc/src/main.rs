@@ -1,95 +1,233 @@
 //! Timu Language Compiler Executable
 //!
-//! This is the main executable for the Timu programming language compiler.
-//! It demonstrates the compilation pipeline by processing example Timu code
-//! through parsing and type checking stages.
+//! This is the command line driver of the Timu programming language compiler.
+//! It reads source files, runs them through the compilation pipeline of
+//! [`libtimu`] and reports errors with rich diagnostics.
 //!
-//! # Current Implementation
+//! # Subcommands
 //!
-//! The current implementation serves as a test harness and demonstration of
-//! the compiler's capabilities. It:
+//! - `check`: Parses and type-checks the sources without generating code
+//! - `build`: Compiles the sources and writes the result for the `--emit`
+//!   target, WebAssembly text by default, to the output directory
+//! - `run`: Compiles the sources and runs the program, starting from the public
+//!   function given with `--entry`, or `main` of the entry module. An `i32`
+//!   result becomes the exit code of the process
+//! - `fmt`: Rewrites the sources in their canonical form, see
+//!   [`libtimu::format`]. With `--check` nothing is written and the command
+//!   fails if a file is not formatted
+//! - `fix`: Applies the suggested fixes of the errors to the sources, see
+//!   [`libtimu::error::fix`], then checks them again and reports what is left
+//! - `dump ast|tir`: Prints the syntax tree or the resolved types
+//! - `explain CODE`: Prints the explanation of an error code like `T0001`,
+//!   with an example of the wrong code and the fix
 //!
-//! 1. Sets up logging for error reporting
-//! 2. Defines sample Timu code for two modules (`lib` and `main`)
-//! 3. Parses both modules into ASTs
-//! 4. Performs type checking and builds the TIR
-//! 5. Reports any compilation errors with detailed diagnostics
+//! # Sources
 //!
-//! # Sample Code
+//! Every subcommand compiles either a list of files or a project:
 //!
-//! The compiler processes a multi-module example that demonstrates:
-//! - Interface definitions
-//! - Class implementations
-//! - Function definitions
-//! - Module imports and usage
-//! - Cross-module type references
+//! - Files given on the command line become modules named after the file, so
+//!   `lib.tim` can be imported with `use lib.Type;`. The module of the first
+//!   file is the entry module.
+//! - Without files, the project of the closest `timu.toml` in the current
+//!   directory or its parents is compiled, or the one given with `--project`.
+//!   Every file below the source root of the project is a module named after
+//!   its path, so `src/net/http.tim` is the module `net.http`. See
+//!   [`libtimu::project`] for the manifest format.
+//!
+//! A module that is imported but not among the sources is loaded from disk, see
+//! [`libtimu::provider`]. It is searched in the directory of the entry file,
+//! or the source root of the project, then in the `search-paths` of the
+//! manifest and the directories given with `--search-path`. Modules below
+//! `std` are loaded from `--std-root`, or the `std-root` of the manifest.
+//!
+//! # Global Options
+//!
+//! - `--color auto|always|never`: When to colour diagnostics and log messages, `auto` colours terminals only
+//! - `--error-format human|json|sarif`: Report errors for people, as JSON for tools or as SARIF for dashboards
+//! - `--error-limit N`: Report at most `N` errors
+//! - `-A LINT`, `-W LINT`, `-D LINT`: Allow, warn about or deny a lint, see
+//!   [`libtimu::lint`]. The options can be repeated and override the
+//!   `[lints]` table of the project manifest
+//! - `--out-dir DIR`: Directory the outputs of `build` are written to
+//! - `-v`, `-q`: Raise the log level from errors to warnings, information,
+//!   debug and trace messages with each `-v`, or silence the log with `-q`
+//!
+//! # Optimisation Options
+//!
+//! `build` and `run` accept the options of the [`libtimu::optimizer`]:
+//!
+//! - `-O0`: Disable all optimisation passes (default)
+//! - `-O1`: Enable inlining, constant folding and dead code elimination
+//! - `-finline`, `-fno-inline`: Toggle function inlining
+//! - `-fconstant-folding`, `-fno-constant-folding`: Toggle constant folding
+//! - `-fdead-code-elimination`, `-fno-dead-code-elimination`: Toggle dead code elimination
+//!
+//! Pass toggles are applied on top of the selected level, so
+//! `-O1 -fno-inline` runs every pass except inlining.
 //!
 //! # Error Reporting
 //!
 //! All compilation errors are displayed using rich diagnostic output
 //! via the [`CodeSpanReportGenerator`] which provides:
 //! - Source code location highlighting
-//! - Error context and suggestions
+//! - Error context and suggested fixes
 //! - Color-coded terminal output
 //!
-//! # Future Development
+//! With `--error-format json` the [`JsonReportGenerator`] writes one JSON
+//! object per error instead, see [`libtimu::error::json`] for its fields, and
+//! with `--error-format sarif` the [`SarifReportGenerator`] writes a SARIF 2.1.0
+//! log for code-scanning dashboards. The log is written even when nothing is
+//! reported, with no results.
 //!
-//! This executable will evolve to:
-//! - Accept command-line arguments for input files
-//! - Support various output formats
-//! - Provide compilation options and flags
-//! - Generate target code or bytecode
+//! `check`, `build` and `run` lint the type-checked program. The warnings are
+//! reported together with the errors, once per run and in the same format. A
+//! denied lint is reported as an error and the command fails.
+
+mod cli;
+mod dump;
+mod runtime;
 
-use std::process::exit;
+use std::{path::{Path, PathBuf}, process::exit, rc::Rc};
 
+use clap::Parser;
+use cli::{Cli, Command, Emit, ErrorFormat, GlobalOptions, Representation, Sources};
 use libtimu::{
-    error::{CodeSpanReportGenerator, ReportGenerator}, 
-    file::SourceFile, 
-    nom_tools::State, 
-    process_ast, 
-    process_code, 
-    tir::TirError
+    ast::FileAst,
+    backend::wat,
+    error::{codes, fix, CodeSpanReportGenerator, Diagnostic, JsonReportGenerator, SarifReportGenerator},
+    file::SourceFile,
+    lint::{self, LintLevels},
+    nom_tools::State,
+    optimizer::{optimize, OptimizerOptions},
+    process_ast_with_loader,
+    process_code,
+    project::{Project, ProjectError},
+    provider::{FileSystemProvider, ModuleLoader, SourceArena},
+    tir::{TirContext, TirError},
 };
+use libtimu_macros_core::SourceCode;
 use log::LevelFilter;
 use simplelog::{
-    ColorChoice, 
-    CombinedLogger, 
-    ConfigBuilder, 
-    LevelPadding, 
-    TermLogger, 
-    TerminalMode, 
+    CombinedLogger,
+    ConfigBuilder,
+    LevelPadding,
+    TermLogger,
+    TerminalMode,
     ThreadLogMode
 };
+use wasmi::Val;
+
+/// Reasons a subcommand fails
+#[derive(Debug)]
+enum Failure {
+    /// The sources do not compile, reported with source locations
+    Compile(TirError),
+    /// A denied lint reported errors, they are in the diagnostics of the session
+    Denied,
+    /// Any other failure, such as a file that cannot be read
+    Other(String),
+}
+
+impl From<TirError> for Failure {
+    fn from(error: TirError) -> Self {
+        Failure::Compile(error)
+    }
+}
+
+impl From<ProjectError> for Failure {
+    fn from(error: ProjectError) -> Self {
+        Failure::Other(format!("Error loading project: {error}"))
+    }
+}
+
+/// Source files read from disk, in the order they are compiled
+struct Loaded {
+    states: Vec<State>,
+    paths: Vec<PathBuf>,
+    /// Path of the entry module
+    entry: Vec<String>,
+    /// Levels of the lints, from the manifest and the command line
+    lints: LintLevels,
+    /// Finds the imported modules that are not among the sources
+    provider: FileSystemProvider,
+    /// Parser states of the modules loaded through the provider
+    arena: SourceArena,
+}
+
+impl Loaded {
+    fn loader(&self) -> ModuleLoader<'_> {
+        ModuleLoader::new(&self.provider, &self.arena)
+    }
+}
+
+/// Options and diagnostics of one run of the compiler
+struct Session<'a> {
+    global: &'a GlobalOptions,
+    /// Warnings and errors not reported yet
+    diagnostics: Vec<Diagnostic>,
+    /// Whether a report was written already
+    reported: bool,
+}
+
+impl<'a> Session<'a> {
+    fn new(global: &'a GlobalOptions) -> Self {
+        Self { global, diagnostics: Vec::new(), reported: false }
+    }
+
+    /// Lints the type-checked program, fails when a denied lint reports something
+    fn lint(&mut self, context: &TirContext<'_>, levels: &LintLevels) -> Result<(), Failure> {
+        let diagnostics = lint::check(context, levels);
+        let denied = diagnostics.iter().any(Diagnostic::is_error);
+        self.diagnostics.extend(diagnostics);
+
+        match denied {
+            true => Err(Failure::Denied),
+            false => Ok(()),
+        }
+    }
+
+    /// Diagnostics of the next report, `None` when there is nothing to report
+    ///
+    /// A SARIF log is written once per run even without diagnostics, so
+    /// dashboards see that the findings of earlier runs are fixed.
+    fn take_report(&mut self) -> Option<Vec<Diagnostic>> {
+        let empty_log = self.global.error_format == ErrorFormat::Sarif && !self.reported;
+        if self.diagnostics.is_empty() && !empty_log {
+            return None;
+        }
+
+        self.reported = true;
+        Some(std::mem::take(&mut self.diagnostics))
+    }
+
+    /// Reports the collected diagnostics in the selected format
+    fn report(&mut self) {
+        let Some(diagnostics) = self.take_report() else {
+            return;
+        };
+
+        match self.global.error_format {
+            ErrorFormat::Human => CodeSpanReportGenerator::generate_diagnostics(diagnostics, self.global.report_options()),
+            ErrorFormat::Json => JsonReportGenerator::generate_diagnostics(diagnostics, self.global.report_options()),
+            ErrorFormat::Sarif => SarifReportGenerator::generate_diagnostics(diagnostics, self.global.report_options()),
+        };
+    }
+}
 
 /// Main entry point for the Timu compiler.
 ///
-/// Processes Timu source files from command line arguments and demonstrates the
-/// complete compilation pipeline from source code to type-checked TIR.
-///
-/// # Process Flow
-///
-/// 1. **Logging Setup**: Configure error-level logging with color output
-/// 2. **Argument Processing**: Read source files from command line arguments
-/// 3. **Parsing**: Convert source code to ASTs for all modules
-/// 4. **Type Checking**: Build TIR with cross-module type resolution
-/// 5. **Error Handling**: Display rich diagnostics for any compilation errors
-///
-/// # Error Handling
-///
-/// The compiler exits with status code 1 if any compilation errors occur.
-/// All errors are displayed with detailed source location information.
+/// Parses the command line, configures logging and runs the selected
+/// subcommand. The process exits with status code 1 if the subcommand fails,
+/// after the errors have been reported.
 ///
 /// # Usage
 ///
+/// ```text
+/// timuc [--color WHEN] [--error-limit N] [--out-dir DIR] [-v|-q] <COMMAND> [--project DIR | file1.tim file2.tim ...]
 /// ```
-/// timuc file1.tim file2.tim ...
-/// ```
-///
-/// # Returns
-///
-/// * `Ok(())` - Compilation completed successfully
-/// * `Err(TirError)` - Should not occur as errors are handled internally
-fn main() -> Result<(), TirError> {
+fn main() {
+    let cli = Cli::parse();
+
     // Configure logging for error reporting
     let config = ConfigBuilder::new()
         .set_location_level(LevelFilter::Error)
@@ -98,72 +236,419 @@ fn main() -> Result<(), TirError> {
         .set_thread_level(LevelFilter::Off)
         .build();
     CombinedLogger::init(vec![TermLogger::new(
-        LevelFilter::Error, 
-        config, 
-        TerminalMode::Mixed, 
-        ColorChoice::Auto
+        cli.global.log_level(),
+        config,
+        TerminalMode::Mixed,
+        cli.global.log_color_choice()
     )]).unwrap();
 
-    // Get command line arguments
-    let args: Vec<String> = std::env::args().collect();
-    
-    if args.len() < 2 {
-        eprintln!("Usage: {} file1.tim file2.tim ...", args[0]);
-        exit(1);
-    }
-
-    let mut asts = Vec::new();
-    let mut states = Vec::new();
-
-    // Process each source file
-    for file_path in &args[1..] {
-        // Read the file content
-        let content = match std::fs::read_to_string(file_path) {
-            Ok(content) => content,
-            Err(error) => {
-                eprintln!("Error reading file {file_path}: {error}");
-                exit(1);
-            }
-        };
+    let mut session = Session::new(&cli.global);
+    let result = match &cli.command {
+        Command::Check { sources } => check(&mut session, sources),
+        Command::Build { emit, optimization, sources } => build(&mut session, *emit, optimization.options(), sources),
+        Command::Run { entry, optimization, sources } => run(&mut session, entry.clone(), optimization.options(), sources),
+        Command::Fmt { check, sources } => format(&session, *check, sources),
+        Command::Fix { sources } => fix(&mut session, sources),
+        Command::Dump { representation, sources } => dump(&session, *representation, sources),
+        Command::Explain { code } => explain(code),
+    };
 
-        // Extract module name from file path (remove .tim extension)
-        let module_name = file_path.strip_suffix(".tim")
-            .or_else(|| file_path.strip_suffix("/"))
-            .unwrap_or(file_path)
-            .split('/')
-            .next_back()
-            .unwrap_or(file_path)
-            .to_string();
-
-        // Create state and parse the file
-        let state = State::new(SourceFile::new(vec![module_name], content));
-        states.push(state);
-    }
-
-    // Parse all states into ASTs
-    for state in &states {
-        let ast = match process_code(state) {
-            Ok(ast) => ast,
-            Err(error) => {
-                CodeSpanReportGenerator::generate(error);
-                exit(1);
-            }
-        };
+    let (code, message) = match result {
+        Ok(code) => (code, None),
+        Err(Failure::Compile(error)) => {
+            session.diagnostics.extend(Diagnostic::from_error(error));
+            (1, None)
+        },
+        Err(Failure::Denied) => (1, None),
+        Err(Failure::Other(message)) => (1, Some(message)),
+    };
 
-        asts.push(ast.into());
+    session.report();
+    if let Some(message) = message {
+        eprintln!("{message}");
     }
+    exit(code);
+}
 
-    // Perform type checking on all modules together
-    match process_ast(asts) {
-        Ok(_tir_context) => {
-            println!("Compilation successful!");
-        },
-        Err(error) => {
-            // Type checking failed - display diagnostic information
-            CodeSpanReportGenerator::generate(error);
-            exit(1);
+/// Type-checks and lints the sources
+fn check(session: &mut Session<'_>, sources: &Sources) -> Result<i32, Failure> {
+    let loaded = load_sources(session.global, sources)?;
+    let files = parse(&loaded.states)?;
+    let context = process_ast_with_loader(files, loaded.loader())?;
+    session.lint(&context, &loaded.lints)?;
+    log::info!("No errors found");
+    Ok(0)
+}
+
+/// Compiles the sources and writes the output file to the output directory
+///
+/// The output is named after the entry module, so `timuc build main.tim lib.tim`
+/// writes `main.wat`.
+fn build(session: &mut Session<'_>, emit: Emit, options: OptimizerOptions, sources: &Sources) -> Result<i32, Failure> {
+    let global = session.global;
+    let loaded = load_sources(global, sources)?;
+    let files = parse(&loaded.states)?;
+    let output = match emit {
+        Emit::Wat => wat::generate(&compile(session, files, &loaded, options)?)?,
+    };
+
+    let name = loaded.entry.join(".");
+    let path = global.out_dir.join(format!("{name}.{}", emit.extension()));
+    std::fs::create_dir_all(&global.out_dir)
+        .and_then(|_| std::fs::write(&path, output))
+        .map_err(|error| Failure::Other(format!("Error writing file {}: {error}", path.display())))?;
+
+    log::info!("Wrote {}", path.display());
+    Ok(0)
+}
+
+/// Compiles the sources and runs the entry function
+///
+/// The warnings are reported before the program starts, so they are not mixed
+/// with its output.
+fn run(session: &mut Session<'_>, entry: Option<String>, options: OptimizerOptions, sources: &Sources) -> Result<i32, Failure> {
+    let loaded = load_sources(session.global, sources)?;
+    let files = parse(&loaded.states)?;
+    let output = wat::generate(&compile(session, files, &loaded, options)?)?;
+    let entry = entry.unwrap_or_else(|| format!("{}.main", loaded.entry.join(".")));
+    session.report();
+
+    match runtime::run(&output, &entry, std::io::stdout(), runtime::HostFunctions::standard()).map_err(Failure::Other)? {
+        Some(Val::I32(code)) => Ok(code),
+        _ => Ok(0),
+    }
+}
+
+/// Rewrites every source file in its canonical form
+///
+/// With `check` no file is written, the files that are not in their canonical
+/// form are reported and the command fails if there are any.
+fn format(session: &Session<'_>, check: bool, sources: &Sources) -> Result<i32, Failure> {
+    let loaded = load_sources(session.global, sources)?;
+    let files = parse(&loaded.states)?;
+    let mut unformatted = 0;
+
+    for (path, file) in loaded.paths.iter().zip(files.iter()) {
+        let formatted = libtimu::format::format(file);
+        if formatted == *file.file.code() {
+            continue;
+        }
+
+        unformatted += 1;
+        match check {
+            true => log::error!("{} is not formatted", path.display()),
+            false => std::fs::write(path, formatted).map_err(|error| Failure::Other(format!("Error writing file {}: {error}", path.display())))?,
+        }
+    }
+
+    Ok(match check && unformatted > 0 {
+        true => 1,
+        false => 0,
+    })
+}
+
+/// Applies the suggested fixes of the errors and checks the sources again
+///
+/// Only the suggestions of the first compilation are applied, the errors that
+/// have no fix or that a fix uncovers are reported by the second check.
+fn fix(session: &mut Session<'_>, sources: &Sources) -> Result<i32, Failure> {
+    let loaded = load_sources(session.global, sources)?;
+    let files = parse(&loaded.states)?;
+    let suggestions = match process_ast_with_loader(files, loaded.loader()) {
+        Ok(_) => Vec::new(),
+        Err(error) => fix::collect(&error),
+    };
+
+    for (path, state) in loaded.paths.iter().zip(loaded.states.iter()) {
+        let code = SourceCode::from(&state.file);
+        let suggestions = suggestions.iter().filter(|suggestion| suggestion.code == code).collect::<Vec<_>>();
+        let (fixed, applied) = fix::apply(&code.source, &suggestions);
+        if applied == 0 {
+            continue;
         }
+
+        std::fs::write(path, fixed).map_err(|error| Failure::Other(format!("Error writing file {}: {error}", path.display())))?;
+        log::info!("Applied {applied} fix{} to {}", if applied == 1 { "" } else { "es" }, path.display());
+    }
+
+    check(session, sources)
+}
+
+/// Prints an intermediate representation of the sources
+fn dump(session: &Session<'_>, representation: Representation, sources: &Sources) -> Result<i32, Failure> {
+    let loaded = load_sources(session.global, sources)?;
+    let files = parse(&loaded.states)?;
+
+    match representation {
+        Representation::Ast => print!("{}", dump::ast(&files)),
+        Representation::Tir => print!("{}", libtimu::tir::dump::render(&process_ast_with_loader(files, loaded.loader())?)),
     };
-    
-    Ok(())
+
+    Ok(0)
+}
+
+/// Prints the explanation of an error code
+fn explain(code: &str) -> Result<i32, Failure> {
+    let error_code = codes::find(code).ok_or_else(|| Failure::Other(format!("{code} is not a Timu error code")))?;
+    print!("{}", error_code.explanation);
+    Ok(0)
+}
+
+/// Type-checks and lints the parsed sources and runs the selected optimisation passes
+fn compile<'base>(session: &mut Session<'_>, files: Vec<Rc<FileAst<'base>>>, loaded: &'base Loaded, options: OptimizerOptions) -> Result<TirContext<'base>, Failure> {
+    let mut context = process_ast_with_loader(files, loaded.loader())?;
+    session.lint(&context, &loaded.lints)?;
+    optimize(&mut context, options);
+    Ok(context)
+}
+
+/// Reads the source files given on the command line or the files of the project
+fn load_sources(global: &GlobalOptions, sources: &Sources) -> Result<Loaded, Failure> {
+    if sources.files.is_empty() {
+        let project = match &sources.project {
+            Some(directory) => Project::load(directory)?,
+            None => Project::find(std::env::current_dir().map_err(|error| Failure::Other(format!("Error reading current directory: {error}")))?)?,
+        };
+
+        let search_paths = project.search_paths().into_iter().chain(sources.search_paths.iter().cloned()).collect();
+        return Ok(Loaded {
+            states: project.sources()?.into_iter().map(State::new).collect(),
+            paths: project.modules.iter().map(|module| module.path.clone()).collect(),
+            entry: project.entry(),
+            lints: global.lint_levels(&project.manifest.lints),
+            provider: provider(search_paths, sources.std_root.clone().or_else(|| project.std_root())),
+            arena: SourceArena::default(),
+        });
+    }
+
+    let states = sources.files.iter()
+        .map(|path| {
+            let content = std::fs::read_to_string(path).map_err(|error| Failure::Other(format!("Error reading file {}: {error}", path.display())))?;
+            Ok(State::new(SourceFile::new(vec![module_name(path)], content)))
+        })
+        .collect::<Result<Vec<_>, Failure>>()?;
+
+    // Modules next to the entry file can be imported without passing them
+    let entry_directory = sources.files[0].parent().map(Path::to_path_buf).unwrap_or_default();
+    let search_paths = std::iter::once(entry_directory).chain(sources.search_paths.iter().cloned()).collect();
+
+    Ok(Loaded {
+        entry: states[0].file.path().clone(),
+        states,
+        paths: sources.files.clone(),
+        lints: global.lint_levels(&Default::default()),
+        provider: provider(search_paths, sources.std_root.clone()),
+        arena: SourceArena::default(),
+    })
+}
+
+/// Provider of the modules that are imported but not among the sources
+fn provider(search_paths: Vec<PathBuf>, std_root: Option<PathBuf>) -> FileSystemProvider {
+    let provider = FileSystemProvider::new(search_paths);
+    match std_root {
+        Some(std_root) => provider.with_std_root(std_root),
+        None => provider,
+    }
+}
+
+/// Extracts the module name from a file path, the file name without the `.tim` extension
+fn module_name(path: &Path) -> String {
+    path.file_stem()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Parses all sources, the first syntax error is returned
+fn parse(states: &[State]) -> Result<Vec<Rc<FileAst<'_>>>, Failure> {
+    states.iter()
+        .map(|state| process_code(state).map(Rc::new).map_err(Failure::from))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use clap::Parser;
+    use libtimu::{error::Severity, tir::TirError};
+
+    use crate::{cli::{Cli, Emit, GlobalOptions, Sources}, Failure, Session};
+
+    use super::{build, check, explain, fix, format, module_name, run};
+
+    /// Global options of a command line like `timuc check -D unused_variables`
+    fn global(arguments: &str) -> GlobalOptions {
+        Cli::try_parse_from(["timuc", "check"].into_iter().chain(arguments.split_whitespace())).unwrap().global
+    }
+
+    fn sources(name: &str, files: &[(&str, &str)]) -> (PathBuf, Sources) {
+        let directory = std::env::temp_dir().join(format!("timuc-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let files = files.iter().map(|(file, code)| {
+            let path = directory.join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, code).unwrap();
+            path
+        }).collect();
+
+        (directory, Sources { files, ..Default::default() })
+    }
+
+    #[test]
+    fn module_names() {
+        assert_eq!(module_name(Path::new("src/lib.tim")), "lib");
+        assert_eq!(module_name(Path::new("main")), "main");
+    }
+
+    #[test]
+    fn check_reports_errors() {
+        let global = global("");
+        let mut session = Session::new(&global);
+        let (directory, files) = sources("check", &[("main.tim", "use lib.Point; pub func main(p: Point): i32 { return 0; }"), ("lib.tim", "pub class Point {}")]);
+        assert!(matches!(check(&mut session, &files), Ok(0)));
+
+        let (_, missing) = sources("check", &[("main.tim", "func main(): i32 { return true; }")]);
+        assert!(matches!(check(&mut session, &missing), Err(Failure::Compile(_))));
+        assert!(matches!(check(&mut session, &Sources { files: vec![directory.join("missing.tim")], ..Default::default() }), Err(Failure::Other(_))));
+        assert!(session.diagnostics.is_empty());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn imported_modules_are_loaded() {
+        let (directory, mut files) = sources("loader", &[
+            ("main.tim", "use lib.base; use extra.value; use std.numbers.one; pub func main(): i32 { return base() + value() + one(); }"),
+            ("lib.tim", "pub func base(): i32 { return 40; }"),
+            ("vendor/extra.tim", "pub func value(): i32 { return 1; }"),
+            ("std/numbers.tim", "pub func one(): i32 { return 1; }"),
+        ]);
+        files.files.truncate(1);
+
+        // `lib` is found next to the entry file, `extra` only with a search path
+        let global = global("");
+        let mut session = Session::new(&global);
+        match check(&mut session, &files) {
+            Err(Failure::Compile(TirError::ImportNotFound(error))) => assert_eq!(error.module, "extra.value"),
+            result => panic!("{result:?}"),
+        };
+
+        files.search_paths.push(directory.join("vendor"));
+        files.std_root = Some(directory.join("std"));
+        assert!(matches!(check(&mut session, &files), Ok(0)));
+        assert!(matches!(run(&mut session, None, Default::default(), &files), Ok(42)));
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn check_reports_lints() {
+        let (directory, files) = sources("lints", &[("main.tim", "use lib.Point; pub func main(): i32 { var a: i32 = 1; return 0; }"), ("lib.tim", "pub class Point {}")]);
+
+        let options = global("");
+        let mut session = Session::new(&options);
+        assert!(matches!(check(&mut session, &files), Ok(0)));
+        assert_eq!(session.diagnostics.iter().map(|diagnostic| diagnostic.severity).collect::<Vec<_>>(), [Severity::Warning, Severity::Warning]);
+
+        let options = global("-A unused_imports -D unused_variables");
+        let mut session = Session::new(&options);
+        assert!(matches!(check(&mut session, &files), Err(Failure::Denied)));
+        assert_eq!(session.diagnostics.len(), 1);
+        assert_eq!(session.diagnostics[0].severity, Severity::Error);
+        assert_eq!(session.diagnostics[0].error.to_string(), "variable `a` is never used");
+
+        session.report();
+        assert!(session.diagnostics.is_empty());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn sarif_log_without_diagnostics() {
+        let (directory, files) = sources("sarif", &[("main.tim", "pub func main(): i32 { return 0; }")]);
+
+        let options = global("--error-format sarif");
+        let mut session = Session::new(&options);
+        assert!(matches!(check(&mut session, &files), Ok(0)));
+        assert!(session.take_report().is_some_and(|diagnostics| diagnostics.is_empty()));
+        assert!(session.take_report().is_none());
+
+        let options = global("");
+        let mut session = Session::new(&options);
+        assert!(matches!(check(&mut session, &files), Ok(0)));
+        assert!(session.take_report().is_none());
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn build_writes_output() {
+        let (directory, files) = sources("build", &[("main.tim", "pub func main(): i32 { return 1 + 2; }")]);
+        let global = global(&format!("--color never --out-dir {}", directory.join("out").display()));
+
+        assert!(matches!(build(&mut Session::new(&global), Emit::Wat, Default::default(), &files), Ok(0)));
+        let output = std::fs::read_to_string(directory.join("out").join("main.wat")).unwrap();
+        assert!(output.contains("(func $main.main"), "{output}");
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn explain_error_code() {
+        assert!(matches!(explain("t0001"), Ok(0)));
+        assert!(matches!(explain("T9999"), Err(Failure::Other(_))));
+    }
+
+    #[test]
+    fn format_rewrites_sources() {
+        let (directory, files) = sources("fmt", &[("main.tim", "class   Point {\n    x: i32;\n}\n\nfunc   test(): i32 {\n    return 1+2;\n}")]);
+        let global = global("");
+        let session = Session::new(&global);
+        assert!(matches!(format(&session, true, &files), Ok(1)));
+        assert!(matches!(format(&session, false, &files), Ok(0)));
+        assert_eq!(std::fs::read_to_string(&files.files[0]).unwrap(), "class Point {\n    x: i32;\n}\n\nfunc test(): i32 {\n    return 1 + 2;\n}\n");
+        assert!(matches!(format(&session, true, &files), Ok(0)));
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn fix_applies_suggestions() {
+        let (directory, files) = sources("fix", &[
+            ("main.tim", "use lib.Point;\nfunc test(shape: Shape): void {}"),
+            ("lib.tim", "class Point {}\npub class Shape {}"),
+        ]);
+        let global = global("");
+        let mut session = Session::new(&global);
+        assert!(matches!(fix(&mut session, &files), Err(Failure::Compile(_))));
+        assert_eq!(std::fs::read_to_string(&files.files[1]).unwrap(), "pub class Point {}\npub class Shape {}");
+
+        assert!(matches!(fix(&mut session, &files), Ok(0)));
+        assert_eq!(std::fs::read_to_string(&files.files[0]).unwrap(), "use lib.Shape;\nuse lib.Point;\nfunc test(shape: Shape): void {}");
+        assert!(matches!(fix(&mut session, &files), Ok(0)));
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn projects() {
+        let (directory, _) = sources("project", &[
+            ("timu.toml", "[project]\nname = \"app\"\nentry = \"app.start\""),
+            ("src/app/start.tim", "use net.http.status; pub func main(): i32 { return status(); }"),
+            ("src/net/http.tim", "pub func status(): i32 { return 200 - 158; }"),
+        ]);
+        let project = Sources { project: Some(directory.clone()), ..Default::default() };
+        let options = global("");
+        let mut session = Session::new(&options);
+        assert!(matches!(check(&mut session, &project), Ok(0)));
+        assert!(matches!(run(&mut session, None, Default::default(), &project), Ok(42)));
+        assert!(matches!(run(&mut session, Some("app.start.missing".into()), Default::default(), &project), Err(Failure::Other(_))));
+
+        std::fs::write(directory.join("src").join("app").join("unused.tim"), "func helper(): void {}").unwrap();
+        std::fs::write(directory.join("timu.toml"), "[project]\nname = \"app\"\nentry = \"app.start\"\n\n[lints]\nunused_functions = \"deny\"").unwrap();
+        assert!(matches!(check(&mut session, &project), Err(Failure::Denied)));
+        assert!(matches!(check(&mut Session::new(&global("-W unused_functions")), &project), Ok(0)));
+        std::fs::remove_file(directory.join("src").join("app").join("unused.tim")).unwrap();
+
+        std::fs::write(directory.join("src").join("net.http.tim"), "").unwrap();
+        match check(&mut session, &project) {
+            Err(Failure::Other(message)) => assert!(message.contains("module `net.http` is defined twice"), "{message}"),
+            result => panic!("{result:?}"),
+        };
+        std::fs::remove_dir_all(directory).unwrap();
+    }
 }
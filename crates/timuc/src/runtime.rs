@@ -0,0 +1,249 @@
+//! Runs compiled programs for the `run` subcommand.
+//!
+//! Programs are compiled to WebAssembly text, assembled and executed with the
+//! `wasmi` interpreter. The native functions of the standard library are
+//! implemented here and linked under the names the backend imports them with,
+//! see `libtimu::backend::wat`.
+//!
+//! Extern functions are only linked when they are on the allow-list of the
+//! runner, a [`HostFunctions`] that binds their symbol names to host closures.
+//! A program importing any other extern is refused before it starts.
+//!
+//! ```ignore
+//! let mut host = HostFunctions::standard();
+//! host.register("twice", |value: i32| value * 2);
+//! runtime::run(&wat, "main.main", std::io::stdout(), host)?;
+//! ```
+
+use std::io::Write;
+
+use wasmi::{errors::LinkerError, Caller, Engine, Extern, IntoFunc, Linker, Module, Store, Val};
+
+/// Links one host closure into the linker
+type Binding<W> = Box<dyn FnOnce(&mut Linker<W>) -> Result<(), LinkerError>>;
+
+/// Allow-list of the extern functions a program can call
+///
+/// Every entry maps the symbol name of an `extern func` declaration to a host
+/// closure. The closure takes and returns the WebAssembly types of the
+/// declaration, `ref` arguments arrive as addresses in the exported memory.
+pub struct HostFunctions<W> {
+    bindings: Vec<(String, Binding<W>)>,
+}
+
+impl<W> Default for HostFunctions<W> {
+    fn default() -> Self {
+        Self { bindings: Vec::new() }
+    }
+}
+
+impl<W: Write> HostFunctions<W> {
+    /// The externs `timuc run` provides, the C functions `putchar` and `puts`
+    ///
+    /// Both write to the output of the program and return a non-negative
+    /// number like their C counterparts.
+    pub fn standard() -> Self {
+        let mut host = Self::default();
+        host.register("putchar", |mut caller: Caller<'_, W>, character: i32| {
+            let _ = write!(caller.data_mut(), "{}", char::from_u32(character as u32).unwrap_or(char::REPLACEMENT_CHARACTER));
+            character
+        });
+        host.register("puts", |mut caller: Caller<'_, W>, address: i32| {
+            let text = read_string(&caller, address);
+            let _ = writeln!(caller.data_mut(), "{text}");
+            0
+        });
+        host
+    }
+}
+
+impl<W> HostFunctions<W> {
+    /// Allows the extern named `name` and binds it to `function`
+    ///
+    /// A later registration of the same name replaces the earlier one.
+    pub fn register<Params, Results>(&mut self, name: &str, function: impl IntoFunc<W, Params, Results>) -> &mut Self {
+        let symbol = name.to_string();
+        self.bindings.retain(|(registered, _)| registered != name);
+        self.bindings.push((name.to_string(), Box::new(move |linker: &mut Linker<W>| linker.func_wrap("env", &symbol, function).map(|_| ()))));
+        self
+    }
+
+    /// Checks if the extern named `name` is on the allow-list
+    pub fn is_allowed(&self, name: &str) -> bool {
+        self.bindings.iter().any(|(registered, _)| registered == name)
+    }
+
+    /// Links every allowed extern the module imports
+    ///
+    /// Fails with the name of the first extern that is not on the allow-list.
+    fn link(self, module: &Module, linker: &mut Linker<W>) -> Result<(), String> {
+        for import in module.imports().filter(|import| import.module() == "env") {
+            if !self.is_allowed(import.name()) {
+                return Err(format!("Extern function `{}` is not available, {}", import.name(), self.available()));
+            }
+        }
+
+        for (_, binding) in self.bindings {
+            binding(linker).map_err(|error| error.to_string())?;
+        }
+        Ok(())
+    }
+
+    fn available(&self) -> String {
+        match self.bindings.is_empty() {
+            true => "the runner provides no extern functions".to_string(),
+            false => format!("the runner only provides {}", self.bindings.iter().map(|(name, _)| format!("`{name}`")).collect::<Vec<_>>().join(", ")),
+        }
+    }
+}
+
+/// Runs a program and returns the value its entry function returned
+///
+/// # Arguments
+/// * `wat` - The program in WebAssembly text format
+/// * `entry` - Full name of the public function to call, such as `main.main`
+/// * `output` - Receives everything the program prints
+/// * `host` - The extern functions the program is allowed to call
+pub fn run<W: Write>(wat: &str, entry: &str, output: W, host: HostFunctions<W>) -> Result<Option<Val>, String> {
+    let wasm = wat::parse_str(wat).map_err(|error| format!("Generated code is not valid WebAssembly, but this is a bug: {error}"))?;
+
+    let engine = Engine::default();
+    let module = Module::new(&engine, &wasm[..]).map_err(|error| error.to_string())?;
+    let mut store = Store::new(&engine, output);
+    let mut linker = Linker::<W>::new(&engine);
+    link_std(&mut linker).map_err(|error| error.to_string())?;
+    host.link(&module, &mut linker)?;
+
+    let instance = linker.instantiate(&mut store, &module)
+        .and_then(|instance| instance.start(&mut store))
+        .map_err(|error| error.to_string())?;
+
+    let function = instance.get_func(&store, entry).ok_or_else(|| format!("Entry function `{entry}` not found, it needs to be a public function"))?;
+    let function_type = function.ty(&store);
+    if !function_type.params().is_empty() {
+        return Err(format!("Entry function `{entry}` must not take arguments"));
+    }
+
+    let mut results = function_type.results().iter().map(|value_type| Val::default(*value_type)).collect::<Vec<_>>();
+    function.call(&mut store, &[], &mut results).map_err(|error| error.to_string())?;
+    store.data_mut().flush().map_err(|error| error.to_string())?;
+    Ok(results.pop())
+}
+
+/// Reads a string argument, a length prefix followed by the UTF-8 bytes
+fn read_string<W>(caller: &Caller<'_, W>, address: i32) -> String {
+    let data = match caller.get_export("memory").and_then(Extern::into_memory) {
+        Some(memory) => memory.data(caller),
+        None => return String::new(),
+    };
+
+    let address = address as usize;
+    let length = data.get(address..address + 4).map(|bytes| u32::from_le_bytes(bytes.try_into().unwrap()) as usize).unwrap_or_default();
+    data.get(address + 4..address + 4 + length).map(|bytes| String::from_utf8_lossy(bytes).into_owned()).unwrap_or_default()
+}
+
+fn link_std<W: Write>(linker: &mut Linker<W>) -> Result<(), wasmi::errors::LinkerError> {
+    linker.func_wrap("std", "std.io.print", |mut caller: Caller<'_, W>, address: i32| {
+        let text = read_string(&caller, address);
+        let _ = write!(caller.data_mut(), "{text}");
+    })?;
+    linker.func_wrap("std", "std.io.println", |mut caller: Caller<'_, W>, address: i32| {
+        let text = read_string(&caller, address);
+        let _ = writeln!(caller.data_mut(), "{text}");
+    })?;
+    linker.func_wrap("std", "std.math.abs", |value: i32| value.wrapping_abs())?;
+    linker.func_wrap("std", "std.math.min", |left: i32, right: i32| left.min(right))?;
+    linker.func_wrap("std", "std.math.max", |left: i32, right: i32| left.max(right))?;
+    linker.func_wrap("std", "std.string.len", |caller: Caller<'_, W>, address: i32| read_string(&caller, address).len() as i32)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use libtimu::{backend::wat, file::SourceFile, nom_tools::State, prelude::NATIVE_FUNCTIONS, process_ast, process_code};
+    use wasmi::Val;
+
+    use super::{run, HostFunctions};
+
+    fn compile(code: &str) -> String {
+        let state = State::new(SourceFile::new(vec!["main".into()], code.to_string()));
+        let ast = process_code(&state).unwrap();
+        let context = process_ast(vec![ast.into()]).unwrap();
+        wat::generate(&context).unwrap()
+    }
+
+    #[test]
+    fn std_functions() {
+        let wat = compile(r#"
+use std.io.print;
+use std.io.println;
+use std.math.abs;
+use std.math.max;
+use std.math.min;
+use std.string.len;
+pub func main(): i32 {
+    print("Hello, ");
+    println("timu");
+    return max(len("timu"), 3) + abs(0 - 2) + min(1, 5);
+}
+"#);
+        for name in NATIVE_FUNCTIONS {
+            assert!(wat.contains(&format!(r#"(import "std" "{name}""#)), "{name} is not linked");
+        }
+
+        let mut output = Vec::new();
+        let result = run(&wat, "main.main", &mut output, HostFunctions::default()).unwrap();
+        assert_eq!(result.and_then(|value| value.i32()), Some(7));
+        assert_eq!(String::from_utf8(output).unwrap(), "Hello, timu\n");
+    }
+
+    #[test]
+    fn entry_function() {
+        let wat = compile("pub func main(): void {} pub func start(a: i32): void {} func hidden(): void {}");
+        assert!(run(&wat, "main.main", Vec::new(), HostFunctions::default()).unwrap().is_none());
+        assert!(run(&wat, "main.start", Vec::new(), HostFunctions::default()).unwrap_err().contains("must not take arguments"));
+        assert!(run(&wat, "main.hidden", Vec::new(), HostFunctions::default()).unwrap_err().contains("not found"));
+    }
+
+    #[test]
+    fn result_types() {
+        let wat = compile("pub func main(): i64 { return 40 + 2; }");
+        assert!(matches!(run(&wat, "main.main", Vec::new(), HostFunctions::default()).unwrap(), Some(Val::I64(42))));
+    }
+
+    #[test]
+    fn extern_functions() {
+        let wat = compile(r#"
+extern func puts(text: ref string): i32;
+extern func putchar(character: i32): i32;
+extern func twice(value: i64): i64;
+pub func main(): i64 {
+    puts("Hello");
+    putchar(33);
+    return twice(21);
+}
+"#);
+        let mut host = HostFunctions::standard();
+        host.register("twice", |value: i64| value * 2);
+
+        let mut output = Vec::new();
+        let result = run(&wat, "main.main", &mut output, host).unwrap();
+        assert!(matches!(result, Some(Val::I64(42))));
+        assert_eq!(String::from_utf8(output).unwrap(), "Hello\n!");
+    }
+
+    #[test]
+    fn extern_allow_list() {
+        let wat = compile("extern func exit(code: i32): void; pub func main(): void { exit(1); }");
+        let error = run(&wat, "main.main", Vec::new(), HostFunctions::standard()).unwrap_err();
+        assert_eq!(error, "Extern function `exit` is not available, the runner only provides `putchar`, `puts`");
+
+        let error = run(&wat, "main.main", Vec::new(), HostFunctions::default()).unwrap_err();
+        assert_eq!(error, "Extern function `exit` is not available, the runner provides no extern functions");
+
+        let mut host = HostFunctions::default();
+        host.register("exit", |_: i32| {});
+        assert!(host.is_allowed("exit") && !host.is_allowed("puts"));
+        assert!(run(&wat, "main.main", Vec::new(), host).unwrap().is_none());
+    }
+}